@@ -3,8 +3,9 @@ use std::{f32::consts::TAU, sync::Arc};
 use criterion::{criterion_group, criterion_main, Criterion};
 use kira::{
 	backend::mock::{MockBackend, MockBackendSettings},
+	effect::{compressor::CompressorBuilder, filter::FilterBuilder},
 	sound::static_sound::{StaticSoundData, StaticSoundSettings},
-	track::MainTrackBuilder,
+	track::{MainTrackBuilder, TrackBuilder},
 	AudioManager, AudioManagerSettings, Frame,
 };
 
@@ -21,6 +22,7 @@ fn create_test_sound(num_samples: usize) -> StaticSoundData {
 		frames: Arc::from(frames),
 		settings: StaticSoundSettings::new().loop_region(0.0..),
 		slice: None,
+		metadata: None,
 	}
 }
 
@@ -70,5 +72,41 @@ fn sounds(c: &mut Criterion) {
 	});
 }
 
-criterion_group!(benches, sounds);
+fn buses(c: &mut Criterion) {
+	// many tracks with a filter and a compressor, each playing one sound,
+	// to compare the cost of effects on mono and stereo tracks
+	fn bench_buses(c: &mut Criterion, name: &str, mono: bool) {
+		c.bench_function(name, |b| {
+			const SAMPLE_RATE: u32 = 48_000;
+			const NUM_TRACKS: usize = 100;
+			let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+				backend_settings: MockBackendSettings {
+					sample_rate: SAMPLE_RATE,
+				},
+				..Default::default()
+			})
+			.unwrap();
+			let sound_data = create_test_sound(SAMPLE_RATE as usize);
+			let mut tracks = vec![];
+			for _ in 0..NUM_TRACKS {
+				let mut builder = TrackBuilder::new();
+				if mono {
+					builder = builder.mono();
+				}
+				builder.add_effect(FilterBuilder::new());
+				builder.add_effect(CompressorBuilder::new());
+				let mut track = manager.add_sub_track(builder).unwrap();
+				track.play(sound_data.clone()).unwrap();
+				tracks.push(track);
+			}
+			manager.backend_mut().on_start_processing();
+			b.iter(|| manager.backend_mut().process());
+		});
+	}
+
+	bench_buses(c, "stereo buses with filter and compressor", false);
+	bench_buses(c, "mono buses with filter and compressor", true);
+}
+
+criterion_group!(benches, sounds, buses);
 criterion_main!(benches);
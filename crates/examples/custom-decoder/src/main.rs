@@ -0,0 +1,116 @@
+use std::{error::Error, f32::consts::TAU, io::stdin};
+
+use kira::{
+	backend::DefaultBackend,
+	sound::{
+		static_sound::StaticSoundData,
+		streaming::{Decoder, StreamingSoundData},
+	},
+	AudioManager, AudioManagerSettings, Frame,
+};
+
+const SAMPLE_RATE: u32 = 44100;
+/// How many frames the decoder decodes at a time.
+const CHUNK_SIZE: usize = 1024;
+
+/// Decodes interleaved stereo 16-bit little-endian PCM audio without a
+/// header, like the audio exported by many audio editors as "raw data".
+struct RawPcmDecoder {
+	bytes: Vec<u8>,
+	current_frame_index: usize,
+}
+
+impl RawPcmDecoder {
+	/// The number of bytes in each frame: two channels of two bytes each.
+	const FRAME_LEN: usize = 4;
+
+	fn new(bytes: Vec<u8>) -> Self {
+		Self {
+			bytes,
+			current_frame_index: 0,
+		}
+	}
+
+	fn sample_at(&self, byte_index: usize) -> f32 {
+		let sample = i16::from_le_bytes([self.bytes[byte_index], self.bytes[byte_index + 1]]);
+		sample as f32 / i16::MAX as f32
+	}
+}
+
+impl Decoder for RawPcmDecoder {
+	type Error = RawPcmError;
+
+	fn sample_rate(&self) -> u32 {
+		SAMPLE_RATE
+	}
+
+	fn num_frames(&self) -> usize {
+		self.bytes.len() / Self::FRAME_LEN
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		let end = (self.current_frame_index + CHUNK_SIZE).min(self.num_frames());
+		let frames = (self.current_frame_index..end)
+			.map(|frame_index| {
+				let byte_index = frame_index * Self::FRAME_LEN;
+				Frame::new(self.sample_at(byte_index), self.sample_at(byte_index + 2))
+			})
+			.collect();
+		self.current_frame_index = end;
+		Ok(frames)
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		if index > self.num_frames() {
+			return Err(RawPcmError::SeekOutOfBounds);
+		}
+		self.current_frame_index = index;
+		Ok(index)
+	}
+}
+
+#[derive(Debug)]
+enum RawPcmError {
+	SeekOutOfBounds,
+}
+
+/// Generates two seconds of raw PCM audio: a 220 Hz sine wave in the left
+/// channel and a 440 Hz sine wave in the right channel.
+fn generate_raw_pcm() -> Vec<u8> {
+	let num_frames = SAMPLE_RATE as usize * 2;
+	let mut bytes = Vec::with_capacity(num_frames * RawPcmDecoder::FRAME_LEN);
+	for frame_index in 0..num_frames {
+		let time = frame_index as f32 / SAMPLE_RATE as f32;
+		for frequency in [220.0, 440.0] {
+			let sample = (time * frequency * TAU).sin() * 0.25;
+			bytes.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+		}
+	}
+	bytes
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let raw_pcm = generate_raw_pcm();
+
+	println!("Press enter to stream the audio");
+	wait_for_enter_press()?;
+	manager.play(StreamingSoundData::from_decoder(RawPcmDecoder::new(
+		raw_pcm.clone(),
+	)))?;
+
+	println!("Press enter to decode the audio up front and play it");
+	wait_for_enter_press()?;
+	let sound_data = StaticSoundData::from_decoder(RawPcmDecoder::new(raw_pcm))
+		.map_err(|error| format!("{error:?}"))?;
+	manager.play(sound_data)?;
+
+	println!("Press enter to exit");
+	wait_for_enter_press()?;
+	Ok(())
+}
+
+fn wait_for_enter_press() -> Result<(), Box<dyn Error>> {
+	stdin().read_line(&mut "".into())?;
+	Ok(())
+}
@@ -69,6 +69,20 @@ impl MockBackend {
 			panic!("backend is not initialized")
 		}
 	}
+
+	/// Calls the [`process`](Renderer::process) callback of the [`Renderer`]
+	/// with room for `num_frames` frames, which must be no more than the
+	/// internal buffer size.
+	pub(crate) fn process_frames(&mut self, num_frames: usize) {
+		if let State::Initialized { renderer } = &mut self.state {
+			renderer
+				.get_mut()
+				.expect("mutex poisoned")
+				.process(&mut self.frames[..num_frames * 2], 2)
+		} else {
+			panic!("backend is not initialized")
+		}
+	}
 }
 
 impl Backend for MockBackend {
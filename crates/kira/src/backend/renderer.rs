@@ -1,15 +1,29 @@
 use std::sync::{
-	atomic::{AtomicU32, Ordering},
+	atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
 	Arc,
 };
 
-use crate::Frame;
+use crate::{
+	manager::SoundCounts,
+	track::{SubTrackOrder, TrackId, TrackNames},
+	Frame, ResourceUsage,
+};
 
 use super::resources::Resources;
 
 #[derive(Debug)]
 pub(crate) struct RendererShared {
 	pub(crate) sample_rate: AtomicU32,
+	num_sounds: AtomicUsize,
+	num_sounds_by_state: SoundCounts,
+	sound_memory: AtomicUsize,
+	/// The ID of the soloed track, or the ID of the main track if no
+	/// track is soloed.
+	soloed_track: AtomicU64,
+	/// The order the top-level sub-tracks have to be processed in.
+	pub(crate) sub_track_order: SubTrackOrder,
+	/// The names of the tracks that have them.
+	pub(crate) track_names: TrackNames,
 }
 
 impl RendererShared {
@@ -17,8 +31,42 @@ impl RendererShared {
 	pub fn new(sample_rate: u32) -> Self {
 		Self {
 			sample_rate: AtomicU32::new(sample_rate),
+			num_sounds: AtomicUsize::new(0),
+			num_sounds_by_state: SoundCounts::default(),
+			sound_memory: AtomicUsize::new(0),
+			soloed_track: AtomicU64::new(TrackId::MAIN.0),
+			sub_track_order: SubTrackOrder::default(),
+			track_names: TrackNames::default(),
 		}
 	}
+
+	#[must_use]
+	pub fn soloed_track(&self) -> Option<TrackId> {
+		Some(TrackId(self.soloed_track.load(Ordering::SeqCst))).filter(|&id| id != TrackId::MAIN)
+	}
+
+	pub fn set_soloed_track(&self, track: Option<TrackId>) {
+		self.soloed_track
+			.store(track.unwrap_or(TrackId::MAIN).0, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn resource_usage(&self) -> ResourceUsage {
+		ResourceUsage {
+			num_sounds: self.num_sounds.load(Ordering::SeqCst),
+			num_sounds_by_state: self.num_sounds_by_state.read(),
+			sound_memory: self.sound_memory.load(Ordering::SeqCst),
+		}
+	}
+
+	fn set_resource_usage(&self, resource_usage: ResourceUsage) {
+		self.num_sounds
+			.store(resource_usage.num_sounds, Ordering::SeqCst);
+		self.num_sounds_by_state
+			.store(resource_usage.num_sounds_by_state);
+		self.sound_memory
+			.store(resource_usage.sound_memory, Ordering::SeqCst);
+	}
 }
 
 /// Produces [`Frame`]s of audio data to be consumed by a
@@ -62,9 +110,14 @@ impl Renderer {
 	/// a new batch of samples.
 	pub fn on_start_processing(&mut self) {
 		self.resources.mixer.on_start_processing();
+		self.resources.mixer.update_solo(self.shared.soloed_track());
 		self.resources.clocks.on_start_processing();
 		self.resources.listeners.on_start_processing();
 		self.resources.modulators.on_start_processing();
+		self.resources.voice_groups.on_start_processing();
+		self.resources.groups.on_start_processing();
+		self.shared
+			.set_resource_usage(self.resources.mixer.resource_usage());
 	}
 
 	/// Produces the next [`Frame`]s of audio.
@@ -79,18 +132,27 @@ impl Renderer {
 
 		self.resources.modulators.process(
 			self.dt * num_frames as f64,
+			self.dt,
 			&self.resources.clocks,
 			&self.resources.listeners,
+			&self.resources.voice_groups,
+			&self.resources.groups,
 		);
 		self.resources.clocks.update(
 			self.dt * num_frames as f64,
+			self.dt,
 			&self.resources.modulators,
 			&self.resources.listeners,
+			&self.resources.voice_groups,
+			&self.resources.groups,
 		);
 		self.resources.listeners.update(
 			self.dt * num_frames as f64,
+			self.dt,
 			&self.resources.clocks,
 			&self.resources.modulators,
+			&self.resources.voice_groups,
+			&self.resources.groups,
 		);
 
 		self.resources.mixer.process(
@@ -99,6 +161,8 @@ impl Renderer {
 			&self.resources.clocks,
 			&self.resources.modulators,
 			&self.resources.listeners,
+			&self.resources.voice_groups,
+			&self.resources.groups,
 		);
 
 		// convert from frames to requested number of channels
@@ -1,7 +1,9 @@
 pub(crate) mod clocks;
+pub(crate) mod groups;
 pub(crate) mod listeners;
 pub(crate) mod mixer;
 pub(crate) mod modulators;
+pub(crate) mod voice_groups;
 
 #[cfg(test)]
 mod test;
@@ -12,8 +14,10 @@ use std::{
 };
 
 use crate::{
+	group::Group,
 	listener::Listener,
 	track::{MainTrackBuilder, MainTrackHandle, SendTrack, Track},
+	voice_group::VoiceGroup,
 	ResourceLimitReached,
 };
 use atomic_arena::{Arena, Controller, Key};
@@ -22,7 +26,9 @@ use rtrb::{Consumer, Producer, RingBuffer};
 
 use crate::{clock::Clock, manager::Capacities, modulator::Modulator};
 
-use self::{clocks::Clocks, mixer::Mixer, modulators::Modulators};
+use self::{
+	clocks::Clocks, groups::Groups, mixer::Mixer, modulators::Modulators, voice_groups::VoiceGroups,
+};
 
 pub(crate) struct ResourceStorage<T> {
 	pub(crate) resources: Arena<T>,
@@ -263,6 +269,8 @@ pub(crate) struct Resources {
 	pub clocks: Clocks,
 	pub modulators: Modulators,
 	pub listeners: Listeners,
+	pub voice_groups: VoiceGroups,
+	pub groups: Groups,
 }
 
 pub(crate) struct ResourceControllers {
@@ -271,6 +279,8 @@ pub(crate) struct ResourceControllers {
 	pub clock_controller: ResourceController<Clock>,
 	pub modulator_controller: ResourceController<Box<dyn Modulator>>,
 	pub listener_controller: ResourceController<Listener>,
+	pub voice_group_controller: ResourceController<VoiceGroup>,
+	pub group_controller: ResourceController<Group>,
 	pub main_track_handle: MainTrackHandle,
 }
 
@@ -290,12 +300,16 @@ pub(crate) fn create_resources(
 	let (clocks, clock_controller) = Clocks::new(capacities.clock_capacity);
 	let (modulators, modulator_controller) = Modulators::new(capacities.modulator_capacity);
 	let (listeners, listener_controller) = Listeners::new(capacities.listener_capacity);
+	let (voice_groups, voice_group_controller) = VoiceGroups::new(capacities.voice_group_capacity);
+	let (groups, group_controller) = Groups::new(capacities.group_capacity);
 	(
 		Resources {
 			mixer,
 			clocks,
 			modulators,
 			listeners,
+			voice_groups,
+			groups,
 		},
 		ResourceControllers {
 			sub_track_controller,
@@ -303,6 +317,8 @@ pub(crate) fn create_resources(
 			clock_controller,
 			modulator_controller,
 			listener_controller,
+			voice_group_controller,
+			group_controller,
 			main_track_handle,
 		},
 	)
@@ -1,8 +1,8 @@
 use crate::{clock::Clock, info::Info};
 
 use super::{
-	listeners::Listeners, modulators::Modulators, ResourceController,
-	SelfReferentialResourceStorage,
+	groups::Groups, listeners::Listeners, modulators::Modulators, voice_groups::VoiceGroups,
+	ResourceController, SelfReferentialResourceStorage,
 };
 
 pub(crate) struct Clocks(pub(crate) SelfReferentialResourceStorage<Clock>);
@@ -22,7 +22,15 @@ impl Clocks {
 		}
 	}
 
-	pub(crate) fn update(&mut self, dt: f64, modulators: &Modulators, listeners: &Listeners) {
+	pub(crate) fn update(
+		&mut self,
+		dt: f64,
+		frame_duration: f64,
+		modulators: &Modulators,
+		listeners: &Listeners,
+		voice_groups: &VoiceGroups,
+		groups: &Groups,
+	) {
 		self.0.for_each(|clock, others| {
 			clock.update(
 				dt,
@@ -30,7 +38,10 @@ impl Clocks {
 					others,
 					&modulators.0.resources,
 					&listeners.0.resources,
+					&voice_groups.0.resources,
+					&groups.0.resources,
 					None,
+					frame_duration,
 				),
 			);
 		});
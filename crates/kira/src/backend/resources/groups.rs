@@ -0,0 +1,21 @@
+use crate::group::Group;
+
+use super::{ResourceController, ResourceStorage};
+
+pub(crate) struct Groups(pub(crate) ResourceStorage<Group>);
+
+impl Groups {
+	#[must_use]
+	pub(crate) fn new(capacity: usize) -> (Self, ResourceController<Group>) {
+		let (storage, controller) = ResourceStorage::new(capacity);
+		(Self(storage), controller)
+	}
+
+	pub(crate) fn on_start_processing(&mut self) {
+		self.0
+			.remove_and_add(|group| group.shared().is_marked_for_removal());
+		for (_, group) in &mut self.0 {
+			group.on_start_processing();
+		}
+	}
+}
@@ -1,7 +1,8 @@
 use crate::{info::Info, listener::Listener};
 
 use super::{
-	clocks::Clocks, modulators::Modulators, ResourceController, SelfReferentialResourceStorage,
+	clocks::Clocks, groups::Groups, modulators::Modulators, voice_groups::VoiceGroups,
+	ResourceController, SelfReferentialResourceStorage,
 };
 
 pub(crate) struct Listeners(pub(crate) SelfReferentialResourceStorage<Listener>);
@@ -21,11 +22,27 @@ impl Listeners {
 		}
 	}
 
-	pub(crate) fn update(&mut self, dt: f64, clocks: &Clocks, modulators: &Modulators) {
+	pub(crate) fn update(
+		&mut self,
+		dt: f64,
+		frame_duration: f64,
+		clocks: &Clocks,
+		modulators: &Modulators,
+		voice_groups: &VoiceGroups,
+		groups: &Groups,
+	) {
 		self.0.for_each(|listener, others| {
 			listener.update(
 				dt,
-				&Info::new(&clocks.0.resources, &modulators.0.resources, others, None),
+				&Info::new(
+					&clocks.0.resources,
+					&modulators.0.resources,
+					others,
+					&voice_groups.0.resources,
+					&groups.0.resources,
+					None,
+					frame_duration,
+				),
 			);
 		});
 	}
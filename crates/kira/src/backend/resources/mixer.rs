@@ -1,18 +1,32 @@
+#[cfg(test)]
+mod test;
+
+use atomic_arena::Key;
+
 use crate::{
 	frame::Frame,
 	info::Info,
-	track::{MainTrack, MainTrackBuilder, MainTrackHandle, SendTrack, Track},
+	track::{
+		align_latency, update_processing_order, FinishedSoundMove, MainTrack, MainTrackBuilder,
+		MainTrackHandle, SendTrack, SoundMove, Track, TrackId, TrackSounds, SOUND_MOVES_PER_CHUNK,
+	},
+	ResourceUsage,
 };
 
 use super::{
-	clocks::Clocks, listeners::Listeners, modulators::Modulators, ResourceController,
-	ResourceStorage,
+	clocks::Clocks, groups::Groups, listeners::Listeners, modulators::Modulators,
+	voice_groups::VoiceGroups, ResourceController, ResourceStorage,
 };
 
 pub(crate) struct Mixer {
 	main_track: MainTrack,
 	sub_tracks: ResourceStorage<Track>,
+	/// The keys of the sub-tracks in the order they're processed in.
+	sub_track_order: Vec<(u32, Key)>,
 	send_tracks: ResourceStorage<SendTrack>,
+	/// The sounds that have asked to be moved to a different track
+	/// during the current chunk.
+	sound_moves: Vec<SoundMove>,
 	temp_buffer: Vec<Frame>,
 }
 
@@ -38,7 +52,9 @@ impl Mixer {
 			Self {
 				main_track,
 				sub_tracks,
+				sub_track_order: Vec::with_capacity(sub_track_capacity),
 				send_tracks,
+				sound_moves: Vec::with_capacity(SOUND_MOVES_PER_CHUNK),
 				temp_buffer: vec![Frame::ZERO; internal_buffer_size],
 			},
 			sub_track_controller,
@@ -58,19 +74,228 @@ impl Mixer {
 	}
 
 	pub fn on_start_processing(&mut self) {
+		// sounds that are done moving have to be moved before finished
+		// sounds and removed tracks are cleaned up, so the keys reserved
+		// for them on their new tracks are used
+		self.finish_sound_moves();
 		self.sub_tracks
 			.remove_and_add(|track| track.should_be_removed());
+		update_processing_order(&mut self.sub_track_order, &self.sub_tracks);
 		for (_, track) in &mut self.sub_tracks {
 			track.on_start_processing();
 		}
+		align_latency(&mut self.sub_tracks);
 		self.send_tracks
 			.remove_and_add(|track| track.shared().is_marked_for_removal());
 		for (_, track) in &mut self.send_tracks {
 			track.on_start_processing();
 		}
 		self.main_track.on_start_processing();
+		self.move_sounds();
+	}
+
+	/// Silences every track that isn't the soloed track or one of its
+	/// ancestors or descendants.
+	pub fn update_solo(&mut self, soloed_track: Option<TrackId>) {
+		// soloing a track that's been removed doesn't silence anything
+		let soloed_track = soloed_track.filter(|&id| {
+			self.sub_tracks
+				.iter()
+				.any(|(_, track)| track.contains_live_track(id))
+		});
+		for (_, track) in &mut self.sub_tracks {
+			track.update_solo(soloed_track, false);
+		}
+	}
+
+	/// Returns the resources used by the sounds on every track.
+	#[must_use]
+	pub fn resource_usage(&self) -> ResourceUsage {
+		let mut resource_usage = ResourceUsage::default();
+		resource_usage.add_sounds(self.main_track.sounds());
+		for (_, track) in self.sub_tracks.iter() {
+			track.add_resource_usage(&mut resource_usage);
+		}
+		resource_usage
+	}
+
+	/**
+	Starts moving sounds that have asked to be played on a different track.
+
+	Each sound's request is checked at most once per chunk. While a sound
+	is crossfading between two tracks, it's processed by whichever track
+	is processed first, and the part of its output that belongs to the
+	other track is handed off to that track, so both sides of the
+	crossfade stay in sync.
+
+	If the destination track doesn't exist anymore or doesn't have room
+	for another sound, the sound stays on its current track.
+	*/
+	fn move_sounds(&mut self) {
+		let mut sound_moves = std::mem::take(&mut self.sound_moves);
+		self.main_track
+			.sounds_mut()
+			.collect_moves(TrackId::MAIN, &mut sound_moves);
+		for (_, track) in &mut self.sub_tracks {
+			track.collect_sound_moves(&mut sound_moves);
+		}
+		for sound_move in sound_moves.drain(..) {
+			self.start_sound_move(sound_move);
+		}
+		self.sound_moves = sound_moves;
+	}
+
+	fn start_sound_move(
+		&mut self,
+		SoundMove {
+			source,
+			key,
+			destination,
+			tween,
+		}: SoundMove,
+	) {
+		let destination_key = if source == destination {
+			None
+		} else {
+			self.reserve_sound_key(destination)
+		};
+		let Some(destination_key) = destination_key else {
+			if let Some(sound) = self
+				.track_sounds_mut(source)
+				.and_then(|sounds| sounds.sounds.get_mut(key))
+			{
+				sound.on_move_finished(source == destination);
+			}
+			return;
+		};
+		let destination_input = self
+			.track_sounds_mut(destination)
+			.expect("destination track of a moving sound should exist")
+			.crossfade_input();
+		if self.processing_index(source) < self.processing_index(destination) {
+			// the sound stays on the source track until the crossfade
+			// is finished
+			self.track_sounds_mut(source)
+				.expect("source track of a moving sound should exist")
+				.start_crossfade(
+					key,
+					destination_input,
+					Some((destination, destination_key)),
+					tween,
+				);
+			return;
+		}
+		let source_sounds = self
+			.track_sounds_mut(source)
+			.expect("source track of a moving sound should exist");
+		let source_input = source_sounds.crossfade_input();
+		let mut sound = source_sounds
+			.sounds
+			.resources
+			.remove(key)
+			.expect("moving sound should exist");
+		sound.on_move_finished(true);
+		let destination_sounds = self
+			.track_sounds_mut(destination)
+			.expect("destination track of a moving sound should exist");
+		destination_sounds
+			.sounds
+			.resources
+			.insert_with_key(destination_key, sound)
+			.expect("error inserting moved sound");
+		destination_sounds.start_crossfade(destination_key, source_input, None, tween);
 	}
 
+	/// Moves sounds that are done crossfading to a different track onto
+	/// that track.
+	fn finish_sound_moves(&mut self) {
+		while let Some(FinishedSoundMove {
+			source,
+			key,
+			destination,
+			destination_key,
+		}) = self.take_finished_sound_move()
+		{
+			let destination_exists = self.track_sounds_mut(destination).is_some();
+			let source_sounds = self
+				.track_sounds_mut(source)
+				.expect("source track of a moving sound should exist");
+			if !destination_exists {
+				if let Some(sound) = source_sounds.sounds.get_mut(key) {
+					sound.on_move_finished(false);
+				}
+				continue;
+			}
+			let Some(mut sound) = source_sounds.sounds.resources.remove(key) else {
+				continue;
+			};
+			sound.on_move_finished(true);
+			self.track_sounds_mut(destination)
+				.expect("destination track of a moving sound should exist")
+				.sounds
+				.resources
+				.insert_with_key(destination_key, sound)
+				.expect("error inserting moved sound");
+		}
+	}
+
+	#[must_use]
+	fn take_finished_sound_move(&mut self) -> Option<FinishedSoundMove> {
+		self.main_track
+			.sounds_mut()
+			.take_finished_move(TrackId::MAIN, false)
+			.or_else(|| {
+				self.sub_tracks
+					.iter_mut()
+					.find_map(|(_, track)| track.take_finished_sound_move())
+			})
+	}
+
+	/// Returns the position of a track in the order tracks are processed in.
+	#[must_use]
+	fn processing_index(&self, id: TrackId) -> usize {
+		let mut index = 0;
+		for (_, key) in &self.sub_track_order {
+			let Some(track) = self.sub_tracks.resources.get(*key) else {
+				continue;
+			};
+			if let Some(found) = track.processing_index(id, &mut index) {
+				return found;
+			}
+		}
+		// the main track is processed last
+		index
+	}
+
+	#[must_use]
+	fn reserve_sound_key(&mut self, id: TrackId) -> Option<Key> {
+		let is_live = id == TrackId::MAIN
+			|| self
+				.sub_tracks
+				.iter()
+				.any(|(_, track)| track.contains_live_track(id));
+		if !is_live {
+			return None;
+		}
+		self.track_sounds_mut(id)?
+			.sounds
+			.resources
+			.controller()
+			.try_reserve()
+			.ok()
+	}
+
+	#[must_use]
+	fn track_sounds_mut(&mut self, id: TrackId) -> Option<&mut TrackSounds> {
+		if id == TrackId::MAIN {
+			return Some(self.main_track.sounds_mut());
+		}
+		self.sub_tracks
+			.iter_mut()
+			.find_map(|(_, track)| track.sounds_mut(id))
+	}
+
+	#[allow(clippy::too_many_arguments)]
 	pub fn process(
 		&mut self,
 		out: &mut [Frame],
@@ -78,15 +303,23 @@ impl Mixer {
 		clocks: &Clocks,
 		modulators: &Modulators,
 		listeners: &Listeners,
+		voice_groups: &VoiceGroups,
+		groups: &Groups,
 	) {
-		for (_, track) in &mut self.sub_tracks {
+		for (_, key) in &self.sub_track_order {
+			let Some(track) = self.sub_tracks.get_mut(*key) else {
+				continue;
+			};
 			track.process(
 				&mut self.temp_buffer[..out.len()],
 				dt,
 				clocks,
 				modulators,
 				listeners,
+				voice_groups,
+				groups,
 				None,
+				1.0,
 				&mut self.send_tracks,
 			);
 			for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
@@ -98,7 +331,10 @@ impl Mixer {
 			&clocks.0.resources,
 			&modulators.0.resources,
 			&listeners.0.resources,
+			&voice_groups.0.resources,
+			&groups.0.resources,
 			None,
+			dt,
 		);
 		for (_, track) in &mut self.send_tracks {
 			track.process(&mut self.temp_buffer[..out.len()], dt, &info);
@@ -0,0 +1,237 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+	backend::{
+		resources::{create_resources, Resources},
+		RendererShared,
+	},
+	frame::Frame,
+	info::Info,
+	manager::Capacities,
+	sound::{
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		Sound,
+	},
+	track::{MainTrackBuilder, TrackBuilder, TrackId},
+	Decibels, Easing, ResourceUsage, StartTime, Tween,
+};
+
+fn process(resources: &mut Resources) -> Frame {
+	let mut out = [Frame::ZERO];
+	resources.mixer.on_start_processing();
+	resources.mixer.process(
+		&mut out,
+		1.0,
+		&resources.clocks,
+		&resources.modulators,
+		&resources.listeners,
+		&resources.voice_groups,
+		&resources.groups,
+	);
+	out[0]
+}
+
+fn sound_data() -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|_| Frame::from_mono(1.0)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+const INSTANT: Tween = Tween {
+	start_time: StartTime::Immediate,
+	duration: Duration::ZERO,
+	easing: Easing::Linear,
+};
+
+const FOUR_SECONDS: Tween = Tween {
+	start_time: StartTime::Immediate,
+	duration: Duration::from_secs(4),
+	easing: Easing::Linear,
+};
+
+/// Tests that a sound can be moved from one track to another while it's playing.
+#[test]
+fn moves_sound_to_another_track() {
+	let (mut resources, mut controllers) =
+		create_resources(Capacities::default(), MainTrackBuilder::new(), 1, 1);
+	let (track, track_handle) =
+		TrackBuilder::new().build(Arc::new(RendererShared::new(1)), 1, None, None);
+	controllers.sub_track_controller.insert(track).unwrap();
+	let mut sound = controllers.main_track_handle.play(sound_data()).unwrap();
+	let full_volume = process(&mut resources);
+
+	sound.set_output_destination(&track_handle, INSTANT);
+	// the sound keeps playing without a gap
+	assert_eq!(process(&mut resources), full_volume);
+	assert_eq!(process(&mut resources), full_volume);
+	assert_eq!(controllers.main_track_handle.num_sounds(), 0);
+	assert_eq!(track_handle.num_sounds(), 1);
+}
+
+/// Tests that a sound crossfades to a track that's processed before
+/// its current track.
+#[test]
+fn crossfades_sound_to_sub_track() {
+	let (mut resources, mut controllers) =
+		create_resources(Capacities::default(), MainTrackBuilder::new(), 1, 1);
+	// silence the sub-track so only the part of the sound that's
+	// still on the main track can be heard
+	let (track, track_handle) = TrackBuilder::new().volume(Decibels::SILENCE).build(
+		Arc::new(RendererShared::new(1)),
+		1,
+		None,
+		None,
+	);
+	controllers.sub_track_controller.insert(track).unwrap();
+	let mut sound = controllers.main_track_handle.play(sound_data()).unwrap();
+	let full_volume = process(&mut resources);
+
+	sound.set_output_destination(&track_handle, FOUR_SECONDS);
+	for volume in [0.75, 0.5, 0.25, 0.0] {
+		assert_eq!(process(&mut resources), full_volume * volume);
+	}
+	assert_eq!(controllers.main_track_handle.num_sounds(), 0);
+	assert_eq!(track_handle.num_sounds(), 1);
+}
+
+/// Tests that a sound crossfades to a track that's processed after
+/// its current track, and only moves once the crossfade is finished.
+#[test]
+fn crossfades_sound_to_main_track() {
+	let (mut resources, mut controllers) =
+		create_resources(Capacities::default(), MainTrackBuilder::new(), 1, 1);
+	let (track, mut track_handle) =
+		TrackBuilder::new().build(Arc::new(RendererShared::new(1)), 1, None, None);
+	controllers.sub_track_controller.insert(track).unwrap();
+	let mut sound = track_handle.play(sound_data()).unwrap();
+	let full_volume = process(&mut resources);
+
+	// silence the sub-track so only the part of the sound that's
+	// already on the main track can be heard
+	track_handle.set_volume(Decibels::SILENCE, INSTANT);
+	sound.set_output_destination(&controllers.main_track_handle, FOUR_SECONDS);
+	for volume in [0.25, 0.5, 0.75, 1.0] {
+		assert_eq!(process(&mut resources), full_volume * volume);
+		assert_eq!(track_handle.num_sounds(), 1);
+	}
+	assert_eq!(process(&mut resources), full_volume);
+	assert_eq!(process(&mut resources), full_volume);
+	assert_eq!(controllers.main_track_handle.num_sounds(), 1);
+	assert_eq!(track_handle.num_sounds(), 0);
+}
+
+/// Tests that a sound stays on its current track if the track it's
+/// moved to has been removed.
+#[test]
+fn keeps_sound_on_track_if_destination_was_removed() {
+	let (mut resources, mut controllers) =
+		create_resources(Capacities::default(), MainTrackBuilder::new(), 1, 1);
+	let (track, track_handle) =
+		TrackBuilder::new().build(Arc::new(RendererShared::new(1)), 1, None, None);
+	controllers.sub_track_controller.insert(track).unwrap();
+	let mut sound = controllers.main_track_handle.play(sound_data()).unwrap();
+	let full_volume = process(&mut resources);
+
+	let track_id = track_handle.id();
+	drop(track_handle);
+	sound.set_output_destination(track_id, INSTANT);
+	assert_eq!(process(&mut resources), full_volume);
+	assert_eq!(process(&mut resources), full_volume);
+	assert_eq!(controllers.main_track_handle.num_sounds(), 1);
+}
+
+/// A silent sound that asks to be moved to a track every time it's asked.
+struct RestlessSound(TrackId);
+
+impl Sound for RestlessSound {
+	fn process(&mut self, _out: &mut [Frame], _dt: f64, _info: &Info) {}
+
+	fn finished(&self) -> bool {
+		false
+	}
+
+	fn take_requested_track(&mut self) -> Option<(TrackId, Tween)> {
+		Some((self.0, INSTANT))
+	}
+}
+
+/// Tests that sounds that always ask to be moved are only moved once
+/// per chunk.
+#[test]
+fn moves_each_sound_at_most_once_per_chunk() {
+	let (mut resources, mut controllers) =
+		create_resources(Capacities::default(), MainTrackBuilder::new(), 1, 1);
+	let (track, track_handle) =
+		TrackBuilder::new().build(Arc::new(RendererShared::new(1)), 1, None, None);
+	controllers.sub_track_controller.insert(track).unwrap();
+	controllers
+		.main_track_handle
+		.sound_controller
+		.insert(Box::new(RestlessSound(TrackId::MAIN)))
+		.unwrap();
+	controllers
+		.main_track_handle
+		.sound_controller
+		.insert(Box::new(RestlessSound(track_handle.id())))
+		.unwrap();
+	for _ in 0..3 {
+		process(&mut resources);
+	}
+	assert_eq!(controllers.main_track_handle.num_sounds(), 1);
+	assert_eq!(track_handle.num_sounds(), 1);
+}
+
+/// A silent sound that reports how much memory it's using.
+struct SoundWithMemoryUsage(usize);
+
+impl Sound for SoundWithMemoryUsage {
+	fn process(&mut self, _out: &mut [Frame], _dt: f64, _info: &Info) {}
+
+	fn finished(&self) -> bool {
+		false
+	}
+
+	fn memory_usage(&self) -> usize {
+		self.0
+	}
+}
+
+/// Tests that the mixer adds up the resources used by the sounds on
+/// every track.
+#[test]
+fn adds_up_resource_usage() {
+	let (mut resources, mut controllers) =
+		create_resources(Capacities::default(), MainTrackBuilder::new(), 1, 1);
+	let (track, mut track_handle) =
+		TrackBuilder::new().build(Arc::new(RendererShared::new(1)), 1, None, None);
+	controllers.sub_track_controller.insert(track).unwrap();
+	let sub_track_handle = track_handle.add_sub_track(TrackBuilder::new()).unwrap();
+	controllers
+		.main_track_handle
+		.sound_controller
+		.insert(Box::new(SoundWithMemoryUsage(100)))
+		.unwrap();
+	track_handle
+		.controls()
+		.sound_controller
+		.insert(Box::new(SoundWithMemoryUsage(20)))
+		.unwrap();
+	sub_track_handle
+		.controls()
+		.sound_controller
+		.insert(Box::new(SoundWithMemoryUsage(3)))
+		.unwrap();
+	process(&mut resources);
+	assert_eq!(
+		resources.mixer.resource_usage(),
+		ResourceUsage {
+			num_sounds: 3,
+			num_sounds_by_state: [3, 0, 0, 0, 0, 0, 0],
+			sound_memory: 123,
+		}
+	);
+}
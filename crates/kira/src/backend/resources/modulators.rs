@@ -1,7 +1,8 @@
 use crate::{info::Info, modulator::Modulator};
 
 use super::{
-	clocks::Clocks, listeners::Listeners, ResourceController, SelfReferentialResourceStorage,
+	clocks::Clocks, groups::Groups, listeners::Listeners, voice_groups::VoiceGroups,
+	ResourceController, SelfReferentialResourceStorage,
 };
 
 pub(crate) struct Modulators(pub(crate) SelfReferentialResourceStorage<Box<dyn Modulator>>);
@@ -20,11 +21,27 @@ impl Modulators {
 		}
 	}
 
-	pub fn process(&mut self, dt: f64, clocks: &Clocks, listeners: &Listeners) {
+	pub fn process(
+		&mut self,
+		dt: f64,
+		frame_duration: f64,
+		clocks: &Clocks,
+		listeners: &Listeners,
+		voice_groups: &VoiceGroups,
+		groups: &Groups,
+	) {
 		self.0.for_each(|modulator, others| {
 			modulator.update(
 				dt,
-				&Info::new(&clocks.0.resources, others, &listeners.0.resources, None),
+				&Info::new(
+					&clocks.0.resources,
+					others,
+					&listeners.0.resources,
+					&voice_groups.0.resources,
+					&groups.0.resources,
+					None,
+					frame_duration,
+				),
 			);
 		});
 	}
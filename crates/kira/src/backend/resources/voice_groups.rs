@@ -0,0 +1,18 @@
+use crate::voice_group::VoiceGroup;
+
+use super::{ResourceController, ResourceStorage};
+
+pub(crate) struct VoiceGroups(pub(crate) ResourceStorage<VoiceGroup>);
+
+impl VoiceGroups {
+	#[must_use]
+	pub(crate) fn new(capacity: usize) -> (Self, ResourceController<VoiceGroup>) {
+		let (storage, controller) = ResourceStorage::new(capacity);
+		(Self(storage), controller)
+	}
+
+	pub(crate) fn on_start_processing(&mut self) {
+		self.0
+			.remove_and_add(|voice_group| voice_group.shared().is_marked_for_removal());
+	}
+}
@@ -34,6 +34,17 @@ impl Decibels {
 		}
 		10.0f32.powf(self.0 / 20.0)
 	}
+
+	/// Converts amplitude, a linear volume measurement, to decibels.
+	///
+	/// An amplitude of `0.0` returns negative infinity.
+	#[must_use]
+	pub fn from_amplitude(amplitude: f32) -> Self {
+		if amplitude == 1.0 {
+			return Self::IDENTITY;
+		}
+		Self(20.0 * amplitude.abs().log10())
+	}
 }
 
 impl Default for Decibels {
@@ -112,7 +123,11 @@ fn test() {
 	for (decibels, amplitude) in TEST_CALCULATIONS {
 		assert!((decibels.as_amplitude() - amplitude).abs() < 0.00001);
 	}
+	for (decibels, amplitude) in &TEST_CALCULATIONS[..5] {
+		assert!((Decibels::from_amplitude(*amplitude).0 - decibels.0).abs() < 0.0001);
+	}
 
 	// test some special cases
 	assert_eq!((Decibels::SILENCE - Decibels(100.0)).as_amplitude(), 0.0);
+	assert_eq!(Decibels::from_amplitude(0.0), Decibels(f32::NEG_INFINITY));
 }
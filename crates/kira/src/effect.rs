@@ -2,24 +2,124 @@
 Modifies audio signals.
 
 Any type that implements [`EffectBuilder`] can be added to a mixer track by
-using [`TrackBuilder::add_effect`](crate::track::TrackBuilder::add_effect). Kira
+using [`TrackBuilder::add_effect`](crate::track::TrackBuilder::add_effect), or
+to a track that already exists by using
+[`TrackHandle::add_effect`](crate::track::TrackHandle::add_effect). Kira
 comes with a number of commonly used effects.
 
 If needed, you can create custom effects by implementing the [`EffectBuilder`]
 and [`Effect`] traits.
 */
 
+mod bypass;
+pub mod chorus;
 pub mod compressor;
+pub mod convolution_reverb;
 pub mod delay;
 pub mod distortion;
+mod envelope;
 pub mod eq_filter;
 pub mod filter;
+pub mod gate;
+pub mod limiter;
 pub mod panning_control;
+pub mod parametric_eq;
+pub mod phaser;
+pub mod pitch_shift;
 pub mod reverb;
+pub mod saturator;
+pub mod stereo_tool;
+mod template;
+#[cfg(test)]
+mod test_helpers;
+pub mod tone_control;
 pub mod volume_control;
 
+pub use template::EffectTemplate;
+
+pub(crate) use template::{template_param_setters, BuilderTemplate, TemplateBuilder};
+
+use std::{
+	ops::{Deref, DerefMut},
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+};
+
 use crate::{frame::Frame, info::Info};
 
+/// How loud audio can be while effects holding onto it are still idle.
+const IDLE_THRESHOLD: f32 = 1.0e-5;
+
+/// Returns `true` if a frame held by an effect is quiet enough for the
+/// effect to be idle.
+#[must_use]
+pub(crate) fn is_silent(frame: Frame) -> bool {
+	frame.left.abs() < IDLE_THRESHOLD && frame.right.abs() < IDLE_THRESHOLD
+}
+
+/// A unique identifier for an effect that was added to a mixer track
+/// after the track was created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EffectId(pub(crate) u64);
+
+impl EffectId {
+	#[must_use]
+	pub(crate) fn next() -> Self {
+		static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+		Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+	}
+}
+
+impl<H> From<&EffectHandle<H>> for EffectId {
+	fn from(handle: &EffectHandle<H>) -> Self {
+		handle.id()
+	}
+}
+
+/**
+Controls an effect that was added to a mixer track after the track was
+created.
+
+This dereferences to the handle returned by the effect's
+[`EffectBuilder`], so the effect's settings can be changed through it.
+Dropping this doesn't remove the effect from the track.
+*/
+#[derive(Debug)]
+pub struct EffectHandle<H> {
+	pub(crate) id: EffectId,
+	pub(crate) handle: H,
+}
+
+impl<H> EffectHandle<H> {
+	/// Returns the unique identifier for the effect.
+	#[must_use]
+	pub fn id(&self) -> EffectId {
+		self.id
+	}
+
+	/// Returns the handle returned by the effect's [`EffectBuilder`].
+	#[must_use]
+	pub fn into_inner(self) -> H {
+		self.handle
+	}
+}
+
+impl<H> Deref for EffectHandle<H> {
+	type Target = H;
+
+	fn deref(&self) -> &Self::Target {
+		&self.handle
+	}
+}
+
+impl<H> DerefMut for EffectHandle<H> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.handle
+	}
+}
+
 /// Configures an effect.
 pub trait EffectBuilder {
 	/// Allows the user to control the effect from gameplay code.
@@ -52,4 +152,79 @@ pub trait Effect: Send {
 	///
 	/// `dt` is the time between each frame (in seconds).
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info);
+
+	/// Returns `true` if the effect would output silence if it was given
+	/// silence. By default, effects are never idle.
+	///
+	/// When a track receives no input and every effect on it is idle, the
+	/// track skips processing its effects. Effects that hold onto audio,
+	/// like reverbs and delays, should return `false` until their tails
+	/// have faded out, and effects that generate sound from nothing should
+	/// always return `false`.
+	fn is_idle(&self) -> bool {
+		false
+	}
+
+	/// Transforms a slice of input [`Frame`]s using the audio from another
+	/// track as a sidechain input.
+	///
+	/// `sidechain` is the same length as `input` and holds the output of
+	/// the track passed to
+	/// [`TrackBuilder::add_effect_with_sidechain`](crate::track::TrackBuilder::add_effect_with_sidechain).
+	/// This is only called for effects added with that method. By default,
+	/// the sidechain input is ignored.
+	fn process_with_sidechain(
+		&mut self,
+		input: &mut [Frame],
+		sidechain: &[Frame],
+		dt: f64,
+		info: &Info,
+	) {
+		self.process(input, dt, info);
+	}
+
+	/// Transforms a slice of input [`Frame`]s on a
+	/// [mono track](crate::track::TrackBuilder::mono).
+	///
+	/// The left and right channels of each frame in `input` are the same,
+	/// and they should still be the same afterwards. Effects can override
+	/// this to only process one channel. By default, this calls
+	/// [`process`](Effect::process) and mixes the output down to mono.
+	fn process_mono(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.process(input, dt, info);
+		for frame in input {
+			*frame = frame.as_mono();
+		}
+	}
+
+	/// Returns a template that creates copies of the effect with its
+	/// current settings, or `None` if the effect can't be copied.
+	/// Defaults to `None`.
+	///
+	/// This is called when the effect is added to a track, before the
+	/// effect is sent to the audio thread, and the template is used by
+	/// [`TrackHandle::builder_snapshot`](crate::track::TrackHandle::builder_snapshot).
+	/// Kira's effects return templates that are kept up to date by the
+	/// effects' handles.
+	fn template(&self) -> Option<Arc<dyn EffectTemplate>> {
+		None
+	}
+
+	/// Returns how many frames the effect delays its input by. Defaults
+	/// to `0`.
+	///
+	/// Effects that need to look ahead at their input, like limiters,
+	/// should report how far they look ahead. The latency of the effects
+	/// on a track is added up, and tracks that have less latency than
+	/// their sibling tracks are delayed so every sub-track of a parent
+	/// track lines up. See
+	/// [`TrackBuilder::compensate_latency`](crate::track::TrackBuilder::compensate_latency)
+	/// for more information.
+	///
+	/// This is checked every time a new batch of audio is requested,
+	/// after [`on_start_processing`](Effect::on_start_processing) is
+	/// called.
+	fn latency_frames(&self) -> usize {
+		0
+	}
 }
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod test;
+
+use std::sync::Arc;
+
+use crate::{
+	command::{command_writer_and_reader, CommandReader, CommandWriter},
+	frame::Frame,
+	info::Info,
+	Parameter, Tween, Value,
+};
+
+use super::{Effect, EffectTemplate};
+
+/// An effect that can be bypassed by [`Bypassable`].
+pub(crate) trait BypassableEffect: Effect {
+	/// Clears the audio the effect is holding onto, like the echoes in a
+	/// delay, so the effect doesn't output stale audio when it stops being
+	/// bypassed.
+	fn clear_tail(&mut self) {}
+}
+
+/// Wraps one of Kira's effects so it can be bypassed.
+///
+/// When the effect is bypassed, its output is crossfaded with its input,
+/// and once the crossfade finishes the effect stops processing audio.
+pub(crate) struct Bypassable<E: BypassableEffect> {
+	effect: E,
+	command_reader: CommandReader<(bool, Tween)>,
+	bypassed: bool,
+	/// How much of the effect's output is heard. This is `1.0` when the
+	/// effect isn't bypassed and `0.0` when it is.
+	wet: Parameter<f32>,
+	dry_buffer: Vec<Frame>,
+	template: Option<Arc<dyn EffectTemplate>>,
+}
+
+impl<E: BypassableEffect> Bypassable<E> {
+	/// Wraps an effect, which starts out bypassed if `bypassed` is `true`.
+	#[must_use]
+	pub fn new(
+		effect: E,
+		template: Option<Arc<dyn EffectTemplate>>,
+		bypassed: bool,
+	) -> (Self, CommandWriter<(bool, Tween)>) {
+		let (command_writer, command_reader) = command_writer_and_reader();
+		let wet = if bypassed { 0.0 } else { 1.0 };
+		(
+			Self {
+				effect,
+				command_reader,
+				bypassed,
+				wet: Parameter::new(Value::Fixed(wet), wet),
+				dry_buffer: vec![],
+				template,
+			},
+			command_writer,
+		)
+	}
+
+	/// Returns `true` if the effect is bypassed and has finished fading
+	/// out.
+	#[must_use]
+	fn fully_bypassed(&self) -> bool {
+		self.bypassed && self.wet.value() == 0.0
+	}
+
+	/// Crossfades between `input` and the audio `process` outputs for it.
+	fn process_with(
+		&mut self,
+		input: &mut [Frame],
+		dt: f64,
+		info: &Info,
+		process: impl FnOnce(&mut E, &mut [Frame]),
+	) {
+		let num_frames = input.len();
+		self.wet.update(dt * num_frames as f64, info);
+		let previous_wet = self.wet.previous_value();
+		let wet = self.wet.value();
+		if previous_wet == 0.0 && wet == 0.0 {
+			return;
+		}
+		if previous_wet == 1.0 && wet == 1.0 {
+			process(&mut self.effect, input);
+			return;
+		}
+		let dry_buffer = &mut self.dry_buffer[..num_frames];
+		dry_buffer.copy_from_slice(input);
+		process(&mut self.effect, input);
+		for (i, (frame, dry)) in input.iter_mut().zip(dry_buffer.iter().copied()).enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let wet = self.wet.interpolated_value(time_in_chunk);
+			*frame = dry + (*frame - dry) * wet;
+		}
+	}
+}
+
+impl<E: BypassableEffect> Effect for Bypassable<E> {
+	fn init(&mut self, sample_rate: u32, internal_buffer_size: usize) {
+		self.dry_buffer = vec![Frame::ZERO; internal_buffer_size];
+		self.effect.init(sample_rate, internal_buffer_size);
+	}
+
+	fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.effect.on_change_sample_rate(sample_rate);
+	}
+
+	fn on_start_processing(&mut self) {
+		if let Some((bypassed, tween)) = self.command_reader.read() {
+			if bypassed != self.bypassed {
+				if self.fully_bypassed() {
+					self.effect.clear_tail();
+				}
+				self.bypassed = bypassed;
+				self.wet
+					.set(Value::Fixed(if bypassed { 0.0 } else { 1.0 }), tween);
+			}
+		}
+		self.effect.on_start_processing();
+	}
+
+	fn is_idle(&self) -> bool {
+		self.fully_bypassed() || self.effect.is_idle()
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.process_with(input, dt, info, |effect, input| {
+			effect.process(input, dt, info)
+		});
+	}
+
+	fn process_with_sidechain(
+		&mut self,
+		input: &mut [Frame],
+		sidechain: &[Frame],
+		dt: f64,
+		info: &Info,
+	) {
+		self.process_with(input, dt, info, |effect, input| {
+			effect.process_with_sidechain(input, sidechain, dt, info)
+		});
+	}
+
+	fn process_mono(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.process_with(input, dt, info, |effect, input| {
+			effect.process_mono(input, dt, info)
+		});
+	}
+
+	fn template(&self) -> Option<Arc<dyn EffectTemplate>> {
+		self.template.clone()
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.effect.latency_frames()
+	}
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use crate::{
+	effect::Effect,
+	frame::Frame,
+	info::{Info, MockInfoBuilder},
+	Tween,
+};
+
+use super::{Bypassable, BypassableEffect};
+
+/// Adds `1.0` to its input and counts how many times it's been used.
+#[derive(Default)]
+struct TestEffect {
+	num_process_calls: usize,
+	num_tail_clears: usize,
+}
+
+impl Effect for TestEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		self.num_process_calls += 1;
+		for frame in input {
+			*frame += Frame::from_mono(1.0);
+		}
+	}
+}
+
+impl BypassableEffect for TestEffect {
+	fn clear_tail(&mut self) {
+		self.num_tail_clears += 1;
+	}
+}
+
+fn tween(seconds: u64) -> Tween {
+	Tween {
+		duration: Duration::from_secs(seconds),
+		..Default::default()
+	}
+}
+
+fn process(effect: &mut Bypassable<TestEffect>) -> Vec<f32> {
+	let mut frames = [Frame::ZERO; 2];
+	effect.on_start_processing();
+	effect.process(&mut frames, 1.0, &MockInfoBuilder::new().build());
+	frames.iter().map(|frame| frame.left).collect()
+}
+
+/// Tests that a bypassed effect crossfades to its input and then stops
+/// processing audio.
+#[test]
+#[allow(clippy::float_cmp)]
+fn crossfades_and_stops_processing() {
+	let (mut effect, mut command_writer) = Bypassable::new(TestEffect::default(), None, false);
+	effect.init(1, 2);
+	assert_eq!(process(&mut effect), [1.0, 1.0]);
+	command_writer.write((true, tween(4)));
+	assert_eq!(process(&mut effect), [0.75, 0.5]);
+	assert_eq!(process(&mut effect), [0.25, 0.0]);
+	assert_eq!(effect.effect.num_process_calls, 3);
+	assert_eq!(process(&mut effect), [0.0, 0.0]);
+	assert_eq!(effect.effect.num_process_calls, 3);
+	command_writer.write((false, tween(0)));
+	assert_eq!(process(&mut effect), [0.5, 1.0]);
+	assert_eq!(effect.effect.num_process_calls, 4);
+}
+
+/// Tests that an effect's tail is only cleared when it stops being bypassed
+/// after it finished fading out.
+#[test]
+fn clears_tail_after_fading_out() {
+	let (mut effect, mut command_writer) = Bypassable::new(TestEffect::default(), None, false);
+	effect.init(1, 2);
+	command_writer.write((true, tween(4)));
+	process(&mut effect);
+	command_writer.write((false, tween(0)));
+	process(&mut effect);
+	assert_eq!(effect.effect.num_tail_clears, 0);
+	command_writer.write((true, tween(0)));
+	process(&mut effect);
+	command_writer.write((false, tween(0)));
+	process(&mut effect);
+	assert_eq!(effect.effect.num_tail_clears, 1);
+}
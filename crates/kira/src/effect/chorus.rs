@@ -0,0 +1,198 @@
+//! Thickens a sound by layering slightly detuned copies of it.
+//!
+//! A chorus mixes its input with a few copies that are each delayed by a
+//! slowly changing amount of time, which makes the copies drift in pitch
+//! and sound like several performers playing together.
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::{f64::consts::TAU, time::Duration};
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::{interpolate_frame, Frame},
+	info::Info,
+	Mix, Parameter,
+};
+
+use super::{bypass::BypassableEffect, is_silent, Effect};
+
+/// The shortest time a voice can be delayed by, in frames, which leaves
+/// room for the frames after the read position that are interpolated.
+const MIN_DELAY_FRAMES: f64 = 3.0;
+
+struct Chorus {
+	command_readers: CommandReaders,
+	num_voices: usize,
+	rate: Parameter,
+	depth: Parameter<Duration>,
+	delay: Parameter<Duration>,
+	feedback: Parameter,
+	stereo_spread: Parameter,
+	mix: Parameter<Mix>,
+	/// The position of the first voice's LFO in its cycle, from `0.0` to
+	/// `1.0`.
+	phase: f64,
+	buffer: Vec<Frame>,
+	/// The index in the buffer the next frame is written to.
+	write_index: usize,
+	/// How many silent frames have been written to the buffer in a row.
+	quiet_frames: usize,
+}
+
+impl Chorus {
+	#[must_use]
+	fn new(builder: ChorusBuilder, command_readers: CommandReaders) -> Self {
+		Self {
+			command_readers,
+			num_voices: builder
+				.voices
+				.clamp(ChorusBuilder::MIN_VOICES, ChorusBuilder::MAX_VOICES),
+			rate: Parameter::new(builder.rate, ChorusBuilder::DEFAULT_RATE),
+			depth: Parameter::new(builder.depth, ChorusBuilder::DEFAULT_DEPTH),
+			delay: Parameter::new(builder.delay, ChorusBuilder::DEFAULT_DELAY),
+			feedback: Parameter::new(builder.feedback, 0.0),
+			stereo_spread: Parameter::new(builder.stereo_spread, 1.0),
+			mix: Parameter::new(builder.mix, ChorusBuilder::DEFAULT_MIX),
+			phase: 0.0,
+			buffer: vec![],
+			write_index: 0,
+			quiet_frames: 0,
+		}
+	}
+
+	/// Allocates room for the longest delay at the given sample rate.
+	fn allocate(&mut self, sample_rate: u32) {
+		let max_delay = ChorusBuilder::MAX_DELAY + ChorusBuilder::MAX_DEPTH;
+		let len = (max_delay.as_secs_f64() * sample_rate as f64).ceil() as usize
+			+ MIN_DELAY_FRAMES as usize
+			+ 1;
+		self.buffer = vec![Frame::ZERO; len];
+		self.write_index = 0;
+		self.quiet_frames = len;
+	}
+
+	/// Returns the frame that was written `delay` frames ago, interpolating
+	/// between the frames around it.
+	#[must_use]
+	fn read(&self, delay: f64) -> Frame {
+		let len = self.buffer.len();
+		let position = self.write_index as f64 + len as f64 - delay;
+		let index = position as usize;
+		let fraction = position.fract() as f32;
+		interpolate_frame(
+			self.buffer[(index + len - 1) % len],
+			self.buffer[index % len],
+			self.buffer[(index + 1) % len],
+			self.buffer[(index + 2) % len],
+			fraction,
+		)
+	}
+}
+
+impl Effect for Chorus {
+	fn init(&mut self, sample_rate: u32, _internal_buffer_size: usize) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, rate, depth, delay, feedback, stereo_spread, mix);
+	}
+
+	// the chorus is idle once everything in its buffer is silent
+	fn is_idle(&self) -> bool {
+		self.quiet_frames >= self.buffer.len()
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		let chunk_duration = dt * input.len() as f64;
+		self.rate.update(chunk_duration, info);
+		self.depth.update(chunk_duration, info);
+		self.delay.update(chunk_duration, info);
+		self.feedback.update(chunk_duration, info);
+		self.stereo_spread.update(chunk_duration, info);
+		self.mix.update(chunk_duration, info);
+
+		let max_delay_frames = (self.buffer.len() - 1) as f64;
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let depth =
+				self.depth
+					.interpolated_value(time_in_chunk)
+					.min(ChorusBuilder::MAX_DEPTH)
+					.as_secs_f64() / dt;
+			let delay =
+				self.delay
+					.interpolated_value(time_in_chunk)
+					.min(ChorusBuilder::MAX_DELAY)
+					.as_secs_f64() / dt;
+			let spread = self
+				.stereo_spread
+				.interpolated_value(time_in_chunk)
+				.clamp(0.0, 1.0);
+
+			// each voice's LFO is offset by an equal part of a cycle, and
+			// the right channel's LFOs are offset by up to a quarter cycle
+			// more to widen the sound
+			let mut wet = Frame::ZERO;
+			for voice in 0..self.num_voices {
+				let phase = self.phase + voice as f64 / self.num_voices as f64;
+				let left_delay =
+					(delay + depth * (phase * TAU).sin()).clamp(MIN_DELAY_FRAMES, max_delay_frames);
+				let right_delay = (delay + depth * ((phase + spread * 0.25) * TAU).sin())
+					.clamp(MIN_DELAY_FRAMES, max_delay_frames);
+				wet.left += self.read(left_delay).left;
+				wet.right += self.read(right_delay).right;
+			}
+			wet /= self.num_voices as f32;
+
+			let feedback = self
+				.feedback
+				.interpolated_value(time_in_chunk)
+				.clamp(-ChorusBuilder::MAX_FEEDBACK, ChorusBuilder::MAX_FEEDBACK);
+			let written = *frame + wet * feedback as f32;
+			self.buffer[self.write_index] = written;
+			self.write_index = (self.write_index + 1) % self.buffer.len();
+			if is_silent(written) {
+				self.quiet_frames = self.quiet_frames.saturating_add(1);
+			} else {
+				self.quiet_frames = 0;
+			}
+
+			let rate = self.rate.interpolated_value(time_in_chunk).max(0.0);
+			self.phase = (self.phase + rate * dt).fract();
+
+			let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
+			*frame = wet * mix.sqrt() + *frame * (1.0 - mix).sqrt();
+		}
+	}
+}
+
+impl BypassableEffect for Chorus {
+	fn clear_tail(&mut self) {
+		self.buffer.fill(Frame::ZERO);
+		self.quiet_frames = self.buffer.len();
+	}
+}
+
+command_writers_and_readers! {
+	set_rate: ValueChangeCommand<f64>,
+	set_depth: ValueChangeCommand<Duration>,
+	set_delay: ValueChangeCommand<Duration>,
+	set_feedback: ValueChangeCommand<f64>,
+	set_stereo_spread: ValueChangeCommand<f64>,
+	set_mix: ValueChangeCommand<Mix>,
+}
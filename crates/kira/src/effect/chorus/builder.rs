@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Mix, Value,
+};
+
+use super::{command_writers_and_readers, Chorus, ChorusHandle};
+
+/// Configures a chorus effect.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChorusBuilder {
+	/// How many delayed copies of the input are layered on top of it.
+	///
+	/// This is kept between 2 and 4.
+	pub voices: usize,
+	/// How many times per second the delay of each voice sweeps back and
+	/// forth (in Hz).
+	pub rate: Value<f64>,
+	/// How far the delay of each voice sweeps away from
+	/// [`delay`](Self::delay) in each direction.
+	///
+	/// Deeper sweeps detune the voices more. This is kept under 20
+	/// milliseconds.
+	pub depth: Value<Duration>,
+	/// The average amount of time each voice is delayed by.
+	///
+	/// This is kept under 50 milliseconds.
+	pub delay: Value<Duration>,
+	/// How much of the voices' output is fed back into them, from `-0.95`
+	/// to `0.95`.
+	///
+	/// Feedback gives the chorus a more resonant, flanger-like sound.
+	pub feedback: Value<f64>,
+	/// How differently the voices are modulated in the left and right
+	/// channels, from `0.0` (the same in both channels) to `1.0` (a quarter
+	/// of a cycle apart).
+	pub stereo_spread: Value<f64>,
+	/// How much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal.
+	pub mix: Value<Mix>,
+}
+
+impl ChorusBuilder {
+	pub(crate) const MIN_VOICES: usize = 2;
+	pub(crate) const MAX_VOICES: usize = 4;
+	pub(crate) const DEFAULT_RATE: f64 = 0.8;
+	pub(crate) const DEFAULT_DEPTH: Duration = Duration::from_millis(2);
+	pub(crate) const MAX_DEPTH: Duration = Duration::from_millis(20);
+	pub(crate) const DEFAULT_DELAY: Duration = Duration::from_millis(15);
+	pub(crate) const MAX_DELAY: Duration = Duration::from_millis(50);
+	pub(crate) const MAX_FEEDBACK: f64 = 0.95;
+	pub(crate) const DEFAULT_MIX: Mix = Mix(0.5);
+
+	/// Creates a new [`ChorusBuilder`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			voices: 3,
+			rate: Value::Fixed(Self::DEFAULT_RATE),
+			depth: Value::Fixed(Self::DEFAULT_DEPTH),
+			delay: Value::Fixed(Self::DEFAULT_DELAY),
+			feedback: Value::Fixed(0.0),
+			stereo_spread: Value::Fixed(1.0),
+			mix: Value::Fixed(Self::DEFAULT_MIX),
+		}
+	}
+
+	/// Sets how many delayed copies of the input are layered on top of it.
+	/// Defaults to 3.
+	///
+	/// This is kept between 2 and 4.
+	#[must_use = "This method consumes self and returns a modified ChorusBuilder, so the return value should be used"]
+	pub fn voices(self, voices: usize) -> Self {
+		Self { voices, ..self }
+	}
+
+	/// Sets how many times per second the delay of each voice sweeps back
+	/// and forth (in Hz). Defaults to 0.8 Hz.
+	#[must_use = "This method consumes self and returns a modified ChorusBuilder, so the return value should be used"]
+	pub fn rate(self, rate: impl Into<Value<f64>>) -> Self {
+		Self {
+			rate: rate.into(),
+			..self
+		}
+	}
+
+	/// Sets how far the delay of each voice sweeps away from the average
+	/// delay in each direction. Defaults to 2 milliseconds.
+	///
+	/// Deeper sweeps detune the voices more. This is kept under 20
+	/// milliseconds.
+	#[must_use = "This method consumes self and returns a modified ChorusBuilder, so the return value should be used"]
+	pub fn depth(self, depth: impl Into<Value<Duration>>) -> Self {
+		Self {
+			depth: depth.into(),
+			..self
+		}
+	}
+
+	/// Sets the average amount of time each voice is delayed by. Defaults
+	/// to 15 milliseconds.
+	///
+	/// This is kept under 50 milliseconds.
+	#[must_use = "This method consumes self and returns a modified ChorusBuilder, so the return value should be used"]
+	pub fn delay(self, delay: impl Into<Value<Duration>>) -> Self {
+		Self {
+			delay: delay.into(),
+			..self
+		}
+	}
+
+	/// Sets how much of the voices' output is fed back into them, from
+	/// `-0.95` to `0.95`. Defaults to `0.0`.
+	#[must_use = "This method consumes self and returns a modified ChorusBuilder, so the return value should be used"]
+	pub fn feedback(self, feedback: impl Into<Value<f64>>) -> Self {
+		Self {
+			feedback: feedback.into(),
+			..self
+		}
+	}
+
+	/// Sets how differently the voices are modulated in the left and right
+	/// channels, from `0.0` (the same in both channels) to `1.0` (a quarter
+	/// of a cycle apart). Defaults to `1.0`.
+	#[must_use = "This method consumes self and returns a modified ChorusBuilder, so the return value should be used"]
+	pub fn stereo_spread(self, stereo_spread: impl Into<Value<f64>>) -> Self {
+		Self {
+			stereo_spread: stereo_spread.into(),
+			..self
+		}
+	}
+
+	/// Sets how much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal. `0.0` means only the dry
+	/// signal will be heard. `1.0` means only the wet signal will
+	/// be heard.
+	#[must_use = "This method consumes self and returns a modified ChorusBuilder, so the return value should be used"]
+	pub fn mix(self, mix: impl Into<Value<Mix>>) -> Self {
+		Self {
+			mix: mix.into(),
+			..self
+		}
+	}
+
+	/// Builds the chorus, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, ChorusHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			Chorus::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			ChorusHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
+impl Default for ChorusBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl EffectBuilder for ChorusBuilder {
+	type Handle = ChorusHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for ChorusBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
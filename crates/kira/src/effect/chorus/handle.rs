@@ -0,0 +1,56 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Mix, Tween,
+};
+
+use super::{ChorusBuilder, CommandWriters};
+
+/// Controls a chorus effect.
+#[derive(Debug)]
+pub struct ChorusHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<ChorusBuilder>>,
+}
+
+impl ChorusHandle {
+	/// Bypasses or stops bypassing the chorus, crossfading between its input
+	/// and output with the given tween.
+	///
+	/// Once it's fully bypassed, the chorus stops processing audio, and the
+	/// audio it was delaying is cleared when it stops being bypassed so old
+	/// audio isn't played.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
+		/// Sets how many times per second the delay of each voice sweeps back
+		/// and forth (in Hz).
+		rate: f64 => rate,
+
+		/// Sets how far the delay of each voice sweeps away from the average
+		/// delay in each direction.
+		depth: Duration => depth,
+
+		/// Sets the average amount of time each voice is delayed by.
+		delay: Duration => delay,
+
+		/// Sets how much of the voices' output is fed back into them, from
+		/// `-0.95` to `0.95`.
+		feedback: f64 => feedback,
+
+		/// Sets how differently the voices are modulated in the left and right
+		/// channels, from `0.0` (the same in both channels) to `1.0` (a quarter
+		/// of a cycle apart).
+		stereo_spread: f64 => stereo_spread,
+
+		/// Sets how much dry (unprocessed) signal should be blended
+		/// with the wet (processed) signal.
+		mix: Mix => mix,
+	}
+}
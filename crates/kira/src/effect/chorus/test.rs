@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use crate::{
+	effect::{
+		test_helpers::{init, process, SAMPLE_RATE},
+		Effect,
+	},
+	frame::Frame,
+	Mix, Value,
+};
+
+use super::{command_writers_and_readers, Chorus, ChorusBuilder};
+
+fn chorus(builder: ChorusBuilder) -> Chorus {
+	init(Chorus::new(builder, command_writers_and_readers().1))
+}
+
+/// Tests that a chorus with a mix of `0.0` outputs exactly the audio it
+/// receives.
+#[test]
+fn passes_audio_through_unchanged_when_dry() {
+	let mut chorus = chorus(ChorusBuilder::new().feedback(0.5).mix(Mix::DRY));
+	let input: Vec<Frame> = (0..512)
+		.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+		.collect();
+	assert_eq!(process(&mut chorus, &input), input);
+}
+
+/// Tests that each voice is delayed by the delay time when there's no
+/// modulation.
+#[test]
+fn delays_voices() {
+	let mut chorus = chorus(
+		ChorusBuilder::new()
+			.depth(Duration::ZERO)
+			.delay(Duration::from_millis(10))
+			.mix(Mix::WET),
+	);
+	let mut input = vec![Frame::ZERO; 1024];
+	input[0] = Frame::from_mono(1.0);
+	let output = process(&mut chorus, &input);
+	let delay_frames = SAMPLE_RATE as usize / 100;
+	for (i, frame) in output.iter().enumerate() {
+		let expected = if i == delay_frames { 1.0 } else { 0.0 };
+		assert!((frame.left - expected).abs() < 1.0e-6);
+		assert!((frame.right - expected).abs() < 1.0e-6);
+	}
+}
+
+/// Tests that the voices are modulated differently in each channel when
+/// the stereo spread is above `0.0`.
+#[test]
+fn stereo_spread_widens_output() {
+	let input: Vec<Frame> = (0..1024)
+		.map(|i| Frame::from_mono((i as f32 * 0.2).sin()))
+		.collect();
+	let builder = ChorusBuilder::new()
+		.rate(5.0)
+		.depth(Duration::from_millis(5))
+		.mix(Mix::WET);
+	let mono = process(&mut chorus(builder.stereo_spread(0.0)), &input);
+	assert!(mono.iter().all(|frame| frame.left == frame.right));
+	let wide = process(&mut chorus(builder.stereo_spread(1.0)), &input);
+	assert!(wide
+		.iter()
+		.any(|frame| (frame.left - frame.right).abs() > 0.01));
+}
+
+/// Tests that the chorus becomes idle once the audio it's delaying has
+/// been played.
+#[test]
+fn becomes_idle_after_delay() {
+	let mut chorus = chorus(ChorusBuilder::new().feedback(Value::Fixed(0.5)));
+	assert!(chorus.is_idle());
+	process(&mut chorus, &[Frame::from_mono(1.0)]);
+	assert!(!chorus.is_idle());
+	process(&mut chorus, &vec![Frame::ZERO; 2 * SAMPLE_RATE as usize]);
+	assert!(chorus.is_idle());
+}
@@ -1,14 +1,32 @@
 //! Reduces (or increases) the dynamic range of audio.
+//!
+//! By default, a compressor follows the level of the audio it's compressing.
+//! To follow the level of another track instead, for example to turn down
+//! music while dialogue is playing, add the compressor with
+//! [`TrackBuilder::add_effect_with_sidechain`](crate::track::TrackBuilder::add_effect_with_sidechain).
+//! The audio the compressor follows (the key) is processed the same way in
+//! both cases, so the compressor attacks and releases the same way for the
+//! same key.
 
 // Loosely based on https://www.musicdsp.org/en/latest/Effects/204-simple-compressor-class-c.html
 
+#[cfg(test)]
+mod test;
+
 mod builder;
 mod handle;
 
 pub use builder::*;
 pub use handle::*;
 
-use std::time::Duration;
+use std::{
+	f64::consts::PI,
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
 
 use crate::{
 	command::{read_commands_into_parameters, ValueChangeCommand},
@@ -18,7 +36,11 @@ use crate::{
 	Decibels, Mix, Parameter,
 };
 
-use super::Effect;
+use super::{bypass::BypassableEffect, envelope::EnvelopeFollower, Effect};
+
+/// How far above the threshold (in decibels) the compressor's envelope can
+/// be while the compressor is idle.
+const IDLE_ENVELOPE: f32 = 1.0e-3;
 
 struct Compressor {
 	command_readers: CommandReaders,
@@ -28,12 +50,23 @@ struct Compressor {
 	release_duration: Parameter<Duration>,
 	makeup_gain: Parameter<Decibels>,
 	mix: Parameter<Mix>,
-	envelope_follower: [f32; 2],
+	/// The cutoff frequency of the high-pass filter applied to the key
+	/// (in Hz), or `0.0` if the key isn't filtered.
+	key_highpass: Parameter,
+	key_filter: KeyFilter,
+	envelope_follower: [EnvelopeFollower; 2],
+	/// The gain reduction of the most recently processed frame (in dB),
+	/// stored as the bits of an `f32`.
+	gain_reduction: Arc<AtomicU32>,
 }
 
 impl Compressor {
 	#[must_use]
-	fn new(builder: CompressorBuilder, command_readers: CommandReaders) -> Self {
+	fn new(
+		builder: CompressorBuilder,
+		command_readers: CommandReaders,
+		gain_reduction: Arc<AtomicU32>,
+	) -> Self {
 		Self {
 			command_readers,
 			threshold: Parameter::new(builder.threshold, CompressorBuilder::DEFAULT_THRESHOLD),
@@ -51,36 +84,35 @@ impl Compressor {
 				CompressorBuilder::DEFAULT_MAKEUP_GAIN,
 			),
 			mix: Parameter::new(builder.mix, CompressorBuilder::DEFAULT_MIX),
-			envelope_follower: [0.0; 2],
+			key_highpass: Parameter::new(
+				builder.key_highpass,
+				CompressorBuilder::DEFAULT_KEY_HIGHPASS,
+			),
+			key_filter: KeyFilter::default(),
+			envelope_follower: Default::default(),
+			gain_reduction,
 		}
 	}
-}
-
-impl Effect for Compressor {
-	fn on_start_processing(&mut self) {
-		read_commands_into_parameters!(
-			self,
-			threshold,
-			ratio,
-			attack_duration,
-			release_duration,
-			makeup_gain,
-			mix,
-		);
-	}
 
-	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+	/// Compresses the input, measuring the level the compression follows
+	/// from `sidechain` instead of the input if it's given.
+	fn compress(&mut self, input: &mut [Frame], sidechain: Option<&[Frame]>, dt: f64, info: &Info) {
 		self.threshold.update(dt * input.len() as f64, info);
 		self.ratio.update(dt * input.len() as f64, info);
 		self.attack_duration.update(dt * input.len() as f64, info);
 		self.release_duration.update(dt * input.len() as f64, info);
 		self.makeup_gain.update(dt * input.len() as f64, info);
 		self.mix.update(dt * input.len() as f64, info);
+		self.key_highpass.update(dt * input.len() as f64, info);
 
 		let threshold = self.threshold.value() as f32;
 		let ratio = self.ratio.value() as f32;
 		let attack_duration = self.attack_duration.value();
 		let release_duration = self.release_duration.value();
+		let key_coefficients = KeyCoefficients::new(self.key_highpass.value(), dt);
+		if key_coefficients.is_none() {
+			self.key_filter = KeyFilter::default();
+		}
 
 		let num_frames = input.len();
 		for (i, frame) in input.iter_mut().enumerate() {
@@ -88,24 +120,21 @@ impl Effect for Compressor {
 			let makeup_gain = self.makeup_gain.interpolated_value(time_in_chunk);
 			let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
 
+			let mut detector = sidechain.map_or(*frame, |sidechain| sidechain[i]);
+			if let Some(key_coefficients) = &key_coefficients {
+				detector = self.key_filter.process(detector, key_coefficients);
+			}
 			let input_decibels = [
-				20.0 * frame.left.abs().log10(),
-				20.0 * frame.right.abs().log10(),
+				20.0 * detector.left.abs().log10(),
+				20.0 * detector.right.abs().log10(),
 			];
 			let over_decibels = input_decibels.map(|input| (input - threshold).max(0.0));
 			for (i, envelope_follower) in self.envelope_follower.iter_mut().enumerate() {
-				let duration = if *envelope_follower > over_decibels[i] {
-					release_duration
-				} else {
-					attack_duration
-				};
-				let speed = (-1.0 / (duration.as_secs_f64() / dt)).exp();
-				*envelope_follower =
-					over_decibels[i] + speed as f32 * (*envelope_follower - over_decibels[i]);
+				envelope_follower.process(over_decibels[i], attack_duration, release_duration, dt);
 			}
 			let gain_reduction = self
 				.envelope_follower
-				.map(|envelope_follower| envelope_follower * ((1.0 / ratio) - 1.0));
+				.map(|envelope_follower| envelope_follower.value() * ((1.0 / ratio) - 1.0));
 			let amplitude =
 				gain_reduction.map(|gain_reduction| 10.0f32.powf(gain_reduction / 20.0));
 			let makeup_gain_linear = 10.0f32.powf(makeup_gain.0 / 20.0);
@@ -114,8 +143,113 @@ impl Effect for Compressor {
 				right: amplitude[1] * frame.right,
 			} * makeup_gain_linear;
 
-			*frame = output * mix.sqrt() + *frame * (1.0 - mix).sqrt()
+			*frame = output * mix.sqrt() + *frame * (1.0 - mix).sqrt();
+
+			if i == num_frames - 1 {
+				self.gain_reduction.store(
+					gain_reduction[0].min(gain_reduction[1]).to_bits(),
+					Ordering::Relaxed,
+				);
+			}
+		}
+	}
+}
+
+/// The values the key filter uses to process one frame of audio.
+struct KeyCoefficients {
+	a1: f32,
+	a2: f32,
+	a3: f32,
+	k: f32,
+}
+
+impl KeyCoefficients {
+	/// Returns the coefficients of a high-pass filter with the given cutoff
+	/// frequency (in Hz), or `None` if the cutoff is too low to filter
+	/// anything.
+	#[must_use]
+	fn new(cutoff: f64, dt: f64) -> Option<Self> {
+		if cutoff <= 0.0 {
+			return None;
 		}
+		// a Q of 1/sqrt(2) keeps the filter from ringing at the cutoff
+		let k = std::f64::consts::SQRT_2;
+		let g = (PI * (cutoff * dt).clamp(0.0001, 0.49)).tan();
+		let a1 = 1.0 / (1.0 + g * (g + k));
+		let a2 = g * a1;
+		let a3 = g * a2;
+		Some(Self {
+			a1: a1 as f32,
+			a2: a2 as f32,
+			a3: a3 as f32,
+			k: k as f32,
+		})
+	}
+}
+
+/// A high-pass filter applied to the audio the compressor follows, so low
+/// frequencies don't turn down the audio as much.
+#[derive(Default)]
+struct KeyFilter {
+	ic1eq: Frame,
+	ic2eq: Frame,
+}
+
+impl KeyFilter {
+	#[must_use]
+	fn process(&mut self, input: Frame, coefficients: &KeyCoefficients) -> Frame {
+		let v3 = input - self.ic2eq;
+		let v1 = self.ic1eq * coefficients.a1 + v3 * coefficients.a2;
+		let v2 = self.ic2eq + self.ic1eq * coefficients.a2 + v3 * coefficients.a3;
+		self.ic1eq = v1 * 2.0 - self.ic1eq;
+		self.ic2eq = v2 * 2.0 - self.ic2eq;
+		input - v1 * coefficients.k - v2
+	}
+}
+
+impl Effect for Compressor {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(
+			self,
+			threshold,
+			ratio,
+			attack_duration,
+			release_duration,
+			makeup_gain,
+			mix,
+			key_highpass,
+		);
+	}
+
+	// silence stays silent, but the envelope has to finish releasing so
+	// the compressor doesn't turn down the next audio it receives
+	fn is_idle(&self) -> bool {
+		self.envelope_follower
+			.iter()
+			.all(|envelope_follower| envelope_follower.value() < IDLE_ENVELOPE)
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.compress(input, None, dt, info);
+	}
+
+	fn process_with_sidechain(
+		&mut self,
+		input: &mut [Frame],
+		sidechain: &[Frame],
+		dt: f64,
+		info: &Info,
+	) {
+		self.compress(input, Some(sidechain), dt, info);
+	}
+}
+
+impl BypassableEffect for Compressor {
+	fn clear_tail(&mut self) {
+		self.envelope_follower = Default::default();
+		self.key_filter = KeyFilter::default();
+		self.gain_reduction
+			.store(0.0f32.to_bits(), Ordering::Relaxed);
 	}
 }
 
@@ -126,4 +260,5 @@ command_writers_and_readers! {
 	set_release_duration: ValueChangeCommand<Duration>,
 	set_makeup_gain: ValueChangeCommand<Decibels>,
 	set_mix: ValueChangeCommand<Mix>,
+	set_key_highpass: ValueChangeCommand<f64>,
 }
@@ -1,13 +1,17 @@
-use std::time::Duration;
+use std::{
+	sync::{atomic::AtomicU32, Arc},
+	time::Duration,
+};
 
 use crate::{
-	effect::{Effect, EffectBuilder},
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
 	Decibels, Mix, Value,
 };
 
 use super::{command_writers_and_readers, Compressor, CompressorHandle};
 
 /// Configures a compressor.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct CompressorBuilder {
 	/// The volume above which volume will start to be decreased (in decibels).
 	pub threshold: Value<f64>,
@@ -32,6 +36,13 @@ pub struct CompressorBuilder {
 	/// How much dry (unprocessed) signal should be blended
 	/// with the wet (processed) signal.
 	pub mix: Value<Mix>,
+	/// The cutoff frequency of a high-pass filter applied to the audio the
+	/// compressor follows (in Hz), or `0.0` to leave it unfiltered.
+	///
+	/// Filtering out low frequencies keeps bass-heavy audio from turning
+	/// down everything else. This only changes the audio the compressor
+	/// measures, not the audio it outputs.
+	pub key_highpass: Value<f64>,
 }
 
 impl CompressorBuilder {
@@ -41,6 +52,7 @@ impl CompressorBuilder {
 	pub(crate) const DEFAULT_RELEASE_DURATION: Duration = Duration::from_millis(100);
 	pub(crate) const DEFAULT_MAKEUP_GAIN: Decibels = Decibels(0.0);
 	pub(crate) const DEFAULT_MIX: Mix = Mix::WET;
+	pub(crate) const DEFAULT_KEY_HIGHPASS: f64 = 0.0;
 
 	/// Creates a new [`CompressorBuilder`] with the default settings.
 	#[must_use]
@@ -52,6 +64,7 @@ impl CompressorBuilder {
 			release_duration: Value::Fixed(Self::DEFAULT_RELEASE_DURATION),
 			makeup_gain: Value::Fixed(Self::DEFAULT_MAKEUP_GAIN),
 			mix: Value::Fixed(Self::DEFAULT_MIX),
+			key_highpass: Value::Fixed(Self::DEFAULT_KEY_HIGHPASS),
 		}
 	}
 
@@ -119,6 +132,20 @@ impl CompressorBuilder {
 			..self
 		}
 	}
+
+	/// Sets the cutoff frequency of a high-pass filter applied to the audio
+	/// the compressor follows (in Hz), or `0.0` to leave it unfiltered.
+	///
+	/// Filtering out low frequencies keeps bass-heavy audio from turning
+	/// down everything else. This only changes the audio the compressor
+	/// measures, not the audio it outputs.
+	#[must_use = "This method consumes self and returns a modified CompressorBuilder, so the return value should be used"]
+	pub fn key_highpass(self, key_highpass: impl Into<Value<f64>>) -> Self {
+		Self {
+			key_highpass: key_highpass.into(),
+			..self
+		}
+	}
 }
 
 impl Default for CompressorBuilder {
@@ -127,14 +154,39 @@ impl Default for CompressorBuilder {
 	}
 }
 
+impl CompressorBuilder {
+	/// Builds the compressor, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, CompressorHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let gain_reduction = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+		let (effect, set_bypassed) = Bypassable::new(
+			Compressor::new(self, command_readers, gain_reduction.clone()),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			CompressorHandle {
+				command_writers,
+				set_bypassed,
+				template,
+				gain_reduction,
+			},
+		)
+	}
+}
+
 impl EffectBuilder for CompressorBuilder {
 	type Handle = CompressorHandle;
 
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
-		let (command_writers, command_readers) = command_writers_and_readers();
-		(
-			Box::new(Compressor::new(self, command_readers)),
-			CompressorHandle { command_writers },
-		)
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for CompressorBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
 	}
 }
@@ -1,44 +1,82 @@
-use std::time::Duration;
+use std::{
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
 
-use crate::{command::handle_param_setters, Decibels, Mix};
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Mix, Tween,
+};
 
-use super::CommandWriters;
+use super::{CommandWriters, CompressorBuilder};
 
 /// Controls a compressor.
 #[derive(Debug)]
 pub struct CompressorHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<CompressorBuilder>>,
+	pub(super) gain_reduction: Arc<AtomicU32>,
 }
 
 impl CompressorHandle {
-	handle_param_setters! {
+	/// Bypasses or stops bypassing the compressor, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the compressor stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	/// Returns how much the compressor is changing the volume of the audio
+	/// (in decibels), not counting the makeup gain.
+	///
+	/// This is negative while the compressor is turning the audio down,
+	/// so a compressor that's reducing the volume by 3 dB returns
+	/// `Decibels(-3.0)`. It's updated every time the audio thread processes
+	/// a batch of audio, which makes it useful for gain reduction meters.
+	#[must_use]
+	pub fn gain_reduction(&self) -> Decibels {
+		Decibels(f32::from_bits(self.gain_reduction.load(Ordering::Relaxed)))
+	}
+
+	template_param_setters! {
 		/// Sets the volume above which volume will start to be decreased (in decibels).
-		threshold: f64,
+		threshold: f64 => threshold,
 
 		/// Sets how much the signal will be compressed.
 		///
 		/// A ratio of `2.0` (or 2 to 1) means an increase of 3dB will
 		/// become an increase of 1.5dB. Ratios between `0.0` and `1.0`
 		/// will actually expand the audio.
-		ratio: f64,
+		ratio: f64 => ratio,
 
 		/// Sets how much time it takes for the volume attenuation to ramp up once
 		/// the input volume exceeds the threshold.
-		attack_duration: Duration,
+		attack_duration: Duration => attack_duration,
 
 		/// Sets how much time it takes for the volume attenuation to relax once
 		/// the input volume dips below the threshold.
-		release_duration: Duration,
+		release_duration: Duration => release_duration,
 
 		/// Sets the amount to change the volume after processing (in dB).
 		///
 		/// This can be used to compensate for the decrease in volume resulting
 		/// from compression. This is only applied to the wet signal, nto the
 		/// dry signal.
-		makeup_gain: Decibels,
+		makeup_gain: Decibels => makeup_gain,
 
 		/// Sets how much dry (unprocessed) signal should be blended
 		/// with the wet (processed) signal.
-		mix: Mix,
+		mix: Mix => mix,
+
+		/// Sets the cutoff frequency of a high-pass filter applied to the audio
+		/// the compressor follows (in Hz), or `0.0` to leave it unfiltered.
+		key_highpass: f64 => key_highpass,
 	}
 }
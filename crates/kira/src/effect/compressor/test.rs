@@ -0,0 +1,94 @@
+use std::sync::{atomic::AtomicU32, Arc};
+
+use crate::{effect::Effect, frame::Frame, info::MockInfoBuilder, Decibels};
+
+use super::{command_writers_and_readers, Compressor, CompressorBuilder};
+
+const DT: f64 = 1.0 / 48_000.0;
+
+fn compressor(builder: CompressorBuilder) -> (Compressor, Arc<AtomicU32>) {
+	let gain_reduction = Arc::new(AtomicU32::new(0));
+	(
+		Compressor::new(
+			builder,
+			command_writers_and_readers().1,
+			gain_reduction.clone(),
+		),
+		gain_reduction,
+	)
+}
+
+fn read(gain_reduction: &AtomicU32) -> Decibels {
+	Decibels(f32::from_bits(
+		gain_reduction.load(std::sync::atomic::Ordering::Relaxed),
+	))
+}
+
+/// A signal that gets louder and quieter so the compressor both attacks
+/// and releases.
+fn key(i: usize) -> Frame {
+	let amplitude = if (i / 2400) % 2 == 0 { 0.9 } else { 0.05 };
+	Frame::from_mono(amplitude * (i as f32 * 0.05).sin())
+}
+
+/// Tests that a compressor following a sidechain input turns down its
+/// input exactly as much as a compressor following the same audio as its
+/// own input does.
+#[test]
+fn external_key_matches_internal_key() {
+	let info = MockInfoBuilder::new().build();
+	let builder = CompressorBuilder::new()
+		.threshold(-20.0)
+		.ratio(4.0)
+		.key_highpass(100.0);
+	let (mut internal, internal_gain_reduction) = compressor(builder);
+	let (mut external, external_gain_reduction) = compressor(builder);
+	for chunk in 0..100 {
+		let key: Vec<Frame> = (0..128).map(|i| key(chunk * 128 + i)).collect();
+		let mut internal_out = key.clone();
+		internal.process(&mut internal_out, DT, &info);
+		let mut external_out = vec![Frame::from_mono(0.5); 128];
+		external.process_with_sidechain(&mut external_out, &key, DT, &info);
+		assert_eq!(
+			read(&internal_gain_reduction),
+			read(&external_gain_reduction)
+		);
+		for ((internal, key), external) in internal_out.iter().zip(&key).zip(&external_out) {
+			if key.left != 0.0 {
+				assert!((internal.left / key.left - external.left / 0.5).abs() < 1.0e-4);
+			}
+		}
+	}
+	assert!(read(&internal_gain_reduction).0 < 0.0);
+}
+
+/// Tests that the key high-pass filter keeps low frequencies from
+/// turning down the audio.
+#[test]
+fn key_highpass_ignores_low_frequencies() {
+	let info = MockInfoBuilder::new().build();
+	let builder = CompressorBuilder::new().threshold(-20.0).ratio(4.0);
+	let (mut unfiltered, unfiltered_gain_reduction) = compressor(builder);
+	let (mut filtered, filtered_gain_reduction) = compressor(builder.key_highpass(200.0));
+	for _ in 0..100 {
+		let mut input = [Frame::from_mono(0.9); 128];
+		unfiltered.process(&mut input, DT, &info);
+		let mut input = [Frame::from_mono(0.9); 128];
+		filtered.process(&mut input, DT, &info);
+	}
+	assert!(read(&unfiltered_gain_reduction).0 < -10.0);
+	assert!(read(&filtered_gain_reduction).0 > -0.1);
+}
+
+/// Tests that the gain reduction is 0 dB while the audio is below the
+/// threshold.
+#[test]
+fn no_gain_reduction_below_threshold() {
+	let info = MockInfoBuilder::new().build();
+	let (mut compressor, gain_reduction) =
+		compressor(CompressorBuilder::new().threshold(-20.0).ratio(4.0));
+	let mut input = [Frame::from_mono(0.05); 128];
+	compressor.process(&mut input, DT, &info);
+	assert_eq!(read(&gain_reduction), Decibels(0.0));
+	assert_eq!(input, [Frame::from_mono(0.05); 128]);
+}
@@ -0,0 +1,427 @@
+//! Adds the reverberations of a recorded space to a sound.
+//!
+//! A convolution reverb applies an impulse response, which is a recording
+//! of how a space responds to a short click, to its input, which makes
+//! the input sound like it was played in that space. This is more
+//! realistic than an [algorithmic reverb](super::reverb), but costs more
+//! CPU time the longer the impulse response is.
+//!
+//! The input is processed in blocks of a fixed size, so the reverb delays
+//! its audio by one block. This is reported as the effect's
+//! [latency](super::Effect::latency_frames) so tracks can
+//! [compensate for it](crate::track::TrackBuilder::compensate_latency).
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod convolver;
+mod fft;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::{
+	fmt::{Debug, Formatter},
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+};
+
+use rtrb::{Consumer, Producer, PushError, RingBuffer};
+use triple_buffer::{triple_buffer, Input, Output};
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::Frame,
+	info::Info,
+	Decibels, Mix, Parameter, Tween, Value,
+};
+
+use super::{is_silent, BuilderTemplate, Effect, EffectTemplate};
+
+use convolver::Convolver;
+use fft::{Complex, Fft};
+
+struct ConvolutionReverb {
+	command_readers: CommandReaders,
+	impulse_responses: ImpulseResponseReader,
+	mix: Parameter<Mix>,
+	gain: Parameter<Decibels>,
+	/// How much of the reverb's output is heard. This is `1.0` when the
+	/// reverb isn't bypassed and `0.0` when it is. The audio is delayed
+	/// either way, so bypassing the reverb doesn't change its latency.
+	wet: Parameter<f32>,
+	partition_size: usize,
+	sample_rate: u32,
+	/// The sample rate of the renderer, which the handle uses to prepare
+	/// new impulse responses.
+	shared_sample_rate: Arc<AtomicU32>,
+	fft: Fft,
+	convolver: Box<Convolver>,
+	/// The convolver for the previous impulse response while the reverb is
+	/// crossfading to a new one.
+	previous_convolver: Option<Box<Convolver>>,
+	/// A convolver the reverb will switch to at the start of the next
+	/// block.
+	pending_convolver: Option<(Box<Convolver>, Tween)>,
+	/// How far the crossfade to the current impulse response has gone.
+	crossfade: Parameter<f32>,
+	/// The previous block of input followed by the block that's being
+	/// filled.
+	input: Vec<Frame>,
+	/// The block of output from each convolver that's being played.
+	output: Vec<Frame>,
+	previous_output: Vec<Frame>,
+	/// How far into the current block the reverb is.
+	position: usize,
+	spectrum: Vec<Complex>,
+	output_spectrum: Vec<Complex>,
+	/// How many blocks in a row the reverb has received that were silent.
+	quiet_blocks: usize,
+	template: Arc<BuilderTemplate<ConvolutionReverbBuilder>>,
+}
+
+impl ConvolutionReverb {
+	#[must_use]
+	fn new(
+		builder: ConvolutionReverbBuilder,
+		command_readers: CommandReaders,
+		impulse_responses: ImpulseResponseReader,
+		shared_sample_rate: Arc<AtomicU32>,
+		template: Arc<BuilderTemplate<ConvolutionReverbBuilder>>,
+		bypassed: bool,
+	) -> Self {
+		let partition_size = builder.clamped_partition_size();
+		let fft = Fft::new(partition_size * 2);
+		let wet = if bypassed { 0.0 } else { 1.0 };
+		Self {
+			command_readers,
+			impulse_responses,
+			mix: Parameter::new(builder.mix, ConvolutionReverbBuilder::DEFAULT_MIX),
+			gain: Parameter::new(builder.gain, Decibels::IDENTITY),
+			wet: Parameter::new(Value::Fixed(wet), wet),
+			partition_size,
+			sample_rate: 0,
+			shared_sample_rate,
+			// the impulse response is resampled once the sample rate is
+			// known
+			convolver: Box::new(Convolver::new(builder.impulse_response, 0, &fft)),
+			fft,
+			previous_convolver: None,
+			pending_convolver: None,
+			crossfade: Parameter::new(Value::Fixed(1.0), 1.0),
+			input: vec![Frame::ZERO; partition_size * 2],
+			output: vec![Frame::ZERO; partition_size],
+			previous_output: vec![Frame::ZERO; partition_size],
+			position: 0,
+			spectrum: vec![Complex::ZERO; partition_size * 2],
+			output_spectrum: vec![Complex::ZERO; partition_size * 2],
+			quiet_blocks: usize::MAX,
+			template,
+		}
+	}
+
+	/// Resamples the impulse response to the given sample rate and clears
+	/// the audio the reverb is holding onto.
+	fn allocate(&mut self, sample_rate: u32) {
+		self.sample_rate = sample_rate;
+		self.shared_sample_rate.store(sample_rate, Ordering::SeqCst);
+		if self.convolver.sample_rate() != sample_rate {
+			*self.convolver = Convolver::new(
+				self.convolver.impulse_response().clone(),
+				sample_rate,
+				&self.fft,
+			);
+		}
+		if let Some((convolver, _)) = &mut self.pending_convolver {
+			if convolver.sample_rate() != sample_rate {
+				**convolver =
+					Convolver::new(convolver.impulse_response().clone(), sample_rate, &self.fft);
+			}
+		}
+		self.previous_convolver = None;
+		self.crossfade = Parameter::new(Value::Fixed(1.0), 1.0);
+		self.clear();
+	}
+
+	/// Clears the audio the reverb is holding onto.
+	fn clear(&mut self) {
+		self.convolver.clear();
+		if let Some(previous_convolver) = &mut self.previous_convolver {
+			previous_convolver.clear();
+		}
+		self.input.fill(Frame::ZERO);
+		self.output.fill(Frame::ZERO);
+		self.previous_output.fill(Frame::ZERO);
+		self.position = 0;
+		self.quiet_blocks = usize::MAX;
+	}
+
+	/// Reads a new impulse response if one was sent and the reverb isn't
+	/// already switching to another one.
+	fn read_impulse_response(&mut self) {
+		if self.previous_convolver.is_some() || self.pending_convolver.is_some() {
+			return;
+		}
+		let Some((mut convolver, tween)) = self.impulse_responses.read() else {
+			return;
+		};
+		// the handle prepares impulse responses at the sample rate it last
+		// saw, so this only happens if the sample rate changed while the
+		// impulse response was being sent
+		if convolver.sample_rate() != self.sample_rate {
+			*convolver = Convolver::new(
+				convolver.impulse_response().clone(),
+				self.sample_rate,
+				&self.fft,
+			);
+		}
+		self.pending_convolver = Some((convolver, tween));
+	}
+
+	/// Starts crossfading to the impulse response that was read, if there
+	/// is one. This happens between blocks so the crossfade starts when
+	/// the first block of the new reverberations is heard.
+	fn switch_impulse_response(&mut self) {
+		let Some((mut convolver, tween)) = self.pending_convolver.take() else {
+			return;
+		};
+		convolver.copy_history_from(&self.convolver);
+		self.previous_convolver = Some(std::mem::replace(&mut self.convolver, convolver));
+		self.crossfade = Parameter::new(Value::Fixed(0.0), 0.0);
+		self.crossfade.set(Value::Fixed(1.0), tween);
+	}
+
+	#[must_use]
+	fn max_partitions(&self) -> usize {
+		self.previous_convolver
+			.as_ref()
+			.map_or(0, |convolver| convolver.num_partitions())
+			.max(self.convolver.num_partitions())
+	}
+
+	/// Convolves the block of input that was just filled and moves on to
+	/// the next one.
+	fn process_block(&mut self, convolve: bool) {
+		let partition_size = self.partition_size;
+		self.switch_impulse_response();
+		// the count is reset as soon as the reverb receives audio that isn't
+		// silent, so it can't be idle while it's filling a block
+		if self.input[partition_size..]
+			.iter()
+			.all(|frame| is_silent(*frame))
+		{
+			self.quiet_blocks = self.quiet_blocks.saturating_add(1);
+		}
+		// once every block the impulse response covers is silent, the
+		// output is too
+		if !convolve || self.quiet_blocks > self.max_partitions() {
+			self.output.fill(Frame::ZERO);
+			self.previous_output.fill(Frame::ZERO);
+		} else {
+			for (value, frame) in self.spectrum.iter_mut().zip(&self.input) {
+				*value = Complex::new(frame.left, frame.right);
+			}
+			self.fft.forward(&mut self.spectrum);
+			for (convolver, output) in [
+				(Some(&mut self.convolver), &mut self.output),
+				(self.previous_convolver.as_mut(), &mut self.previous_output),
+			] {
+				let Some(convolver) = convolver else {
+					continue;
+				};
+				convolver.convolve(&self.spectrum, &mut self.output_spectrum);
+				self.fft.inverse(&mut self.output_spectrum);
+				for (frame, value) in output
+					.iter_mut()
+					.zip(&self.output_spectrum[partition_size..])
+				{
+					*frame = Frame::new(value.re, value.im);
+				}
+			}
+		}
+		self.input.copy_within(partition_size.., 0);
+	}
+}
+
+impl Effect for ConvolutionReverb {
+	fn init(&mut self, sample_rate: u32, _internal_buffer_size: usize) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, mix, gain);
+		if let Some((bypassed, tween)) = self.command_readers.set_bypassed.read() {
+			// the reverb stops convolving audio once it's fully bypassed,
+			// so the tail it was holding onto is stale
+			if !bypassed && self.wet.value() == 0.0 {
+				self.clear();
+			}
+			self.wet
+				.set(Value::Fixed(if bypassed { 0.0 } else { 1.0 }), tween);
+		}
+		self.impulse_responses.retry_retire();
+		self.read_impulse_response();
+	}
+
+	fn is_idle(&self) -> bool {
+		self.quiet_blocks > self.max_partitions()
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		let chunk_duration = dt * input.len() as f64;
+		self.mix.update(chunk_duration, info);
+		self.gain.update(chunk_duration, info);
+		self.wet.update(chunk_duration, info);
+		self.crossfade.update(chunk_duration, info);
+		#[allow(clippy::float_cmp)]
+		let convolve = self.wet.previous_value() != 0.0 || self.wet.value() != 0.0;
+
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			if !is_silent(*frame) {
+				self.quiet_blocks = 0;
+			}
+			self.input[self.partition_size + self.position] = *frame;
+			let dry = self.input[self.position];
+			let mut reverb = self.output[self.position];
+			if self.previous_convolver.is_some() {
+				let previous = self.previous_output[self.position];
+				reverb = previous
+					+ (reverb - previous) * self.crossfade.interpolated_value(time_in_chunk);
+			}
+			reverb *= self.gain.interpolated_value(time_in_chunk).as_amplitude();
+			let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
+			let output = reverb * mix.sqrt() + dry * (1.0 - mix).sqrt();
+			let wet = self.wet.interpolated_value(time_in_chunk).clamp(0.0, 1.0);
+			*frame = dry + (output - dry) * wet;
+
+			self.position += 1;
+			if self.position == self.partition_size {
+				self.process_block(convolve);
+				self.position = 0;
+			}
+		}
+
+		if self.crossfade.value() >= 1.0 {
+			if let Some(previous_convolver) = self.previous_convolver.take() {
+				self.impulse_responses.retire(previous_convolver);
+			}
+		}
+	}
+
+	fn template(&self) -> Option<Arc<dyn EffectTemplate>> {
+		Some(self.template.clone())
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.partition_size
+	}
+}
+
+/// Creates a channel for sending new impulse responses to a convolution
+/// reverb.
+///
+/// Only the most recently sent impulse response is kept, and impulse
+/// responses that are replaced before the reverb reads them are dropped
+/// on the gameplay thread. Once the reverb finishes crossfading away from
+/// an impulse response, it's sent back so it can be dropped on the
+/// gameplay thread as well.
+#[must_use]
+fn impulse_response_channel() -> (ImpulseResponseWriter, ImpulseResponseReader) {
+	let (input, output) = triple_buffer(&None);
+	// the writer empties this before writing each impulse response, but
+	// the reader can retire the one it was using and then the one written
+	// before the new one before the writer empties it again, so the reader
+	// holds onto impulse responses that don't fit until there's room
+	let (retired_producer, retired_consumer) = RingBuffer::new(1);
+	(
+		ImpulseResponseWriter {
+			input,
+			retired_consumer,
+		},
+		ImpulseResponseReader {
+			output,
+			retired_producer,
+			unretired: None,
+		},
+	)
+}
+
+struct ImpulseResponseWriter {
+	input: Input<Option<(Box<Convolver>, Tween)>>,
+	retired_consumer: Consumer<Box<Convolver>>,
+}
+
+impl ImpulseResponseWriter {
+	/// Sends an impulse response, replacing any impulse response the
+	/// reverb hasn't switched to yet.
+	fn write(&mut self, convolver: Box<Convolver>, tween: Tween) {
+		while self.retired_consumer.pop().is_ok() {}
+		self.input.write(Some((convolver, tween)));
+	}
+}
+
+impl Debug for ImpulseResponseWriter {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ImpulseResponseWriter").finish()
+	}
+}
+
+struct ImpulseResponseReader {
+	output: Output<Option<(Box<Convolver>, Tween)>>,
+	retired_producer: Producer<Box<Convolver>>,
+	unretired: Option<Box<Convolver>>,
+}
+
+impl ImpulseResponseReader {
+	/// Returns the most recently sent impulse response, or `None` if no
+	/// impulse response was sent since the last read.
+	#[must_use]
+	fn read(&mut self) -> Option<(Box<Convolver>, Tween)> {
+		if self.output.update() {
+			self.output.output_buffer_mut().take()
+		} else {
+			None
+		}
+	}
+
+	/// Sends an impulse response the reverb is done with back to the
+	/// gameplay thread.
+	///
+	/// If there's no room to send it yet, it's sent by a later call to
+	/// [`retry_retire`](Self::retry_retire).
+	fn retire(&mut self, convolver: Box<Convolver>) {
+		self.retry_retire();
+		// there's only ever one impulse response that doesn't fit, but if
+		// that changes, dropping the older one here is better than panicking
+		self.unretired = Some(convolver);
+		self.retry_retire();
+	}
+
+	/// Sends an impulse response that didn't fit the last time it was
+	/// retired back to the gameplay thread if there's room for it now.
+	fn retry_retire(&mut self) {
+		let Some(convolver) = self.unretired.take() else {
+			return;
+		};
+		if let Err(PushError::Full(convolver)) = self.retired_producer.push(convolver) {
+			self.unretired = Some(convolver);
+		}
+	}
+}
+
+command_writers_and_readers! {
+	set_mix: ValueChangeCommand<Mix>,
+	set_gain: ValueChangeCommand<Decibels>,
+	set_bypassed: (bool, Tween),
+}
@@ -0,0 +1,154 @@
+use std::sync::{atomic::AtomicU32, Arc, Mutex};
+
+use crate::{
+	effect::{BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	sound::static_sound::StaticSoundData,
+	Decibels, Mix, Value,
+};
+
+use super::{
+	command_writers_and_readers, impulse_response_channel, ConvolutionReverb,
+	ConvolutionReverbHandle,
+};
+
+/**
+Configures a convolution reverb.
+
+# Examples
+
+```no_run
+use kira::{
+	effect::convolution_reverb::ConvolutionReverbBuilder,
+	sound::static_sound::StaticSoundData,
+	track::TrackBuilder,
+	Mix,
+};
+
+let impulse_response = StaticSoundData::from_file("hall.wav")?;
+let builder = TrackBuilder::new()
+	.with_effect(ConvolutionReverbBuilder::new(impulse_response).mix(Mix(0.3)));
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvolutionReverbBuilder {
+	/// The recording of the space to simulate.
+	///
+	/// Only the frames in the impulse response's
+	/// [slice](StaticSoundData::slice) are used, and its settings are
+	/// ignored. It's resampled to the renderer's sample rate when the
+	/// reverb is added to a track.
+	pub impulse_response: StaticSoundData,
+	/// How much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal.
+	pub mix: Value<Mix>,
+	/// The volume adjustment applied to the reverberations.
+	pub gain: Value<Decibels>,
+	/// How many frames of audio the reverb processes at a time, which is
+	/// also how many frames the reverb delays its input.
+	///
+	/// Smaller partitions reduce the latency, but cost more CPU time. This
+	/// is rounded up to a power of two and kept between 64 and 8192.
+	pub partition_size: usize,
+}
+
+impl ConvolutionReverbBuilder {
+	pub(crate) const DEFAULT_MIX: Mix = Mix(0.5);
+	pub(crate) const DEFAULT_PARTITION_SIZE: usize = 512;
+	pub(crate) const MIN_PARTITION_SIZE: usize = 64;
+	pub(crate) const MAX_PARTITION_SIZE: usize = 8192;
+
+	/// Creates a new [`ConvolutionReverbBuilder`] that uses the given
+	/// impulse response.
+	#[must_use]
+	pub fn new(impulse_response: StaticSoundData) -> Self {
+		Self {
+			impulse_response,
+			mix: Value::Fixed(Self::DEFAULT_MIX),
+			gain: Value::Fixed(Decibels::IDENTITY),
+			partition_size: Self::DEFAULT_PARTITION_SIZE,
+		}
+	}
+
+	/// Sets how much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal. `0.0` means only the dry
+	/// signal will be heard. `1.0` means only the wet signal will
+	/// be heard.
+	#[must_use = "This method consumes self and returns a modified ConvolutionReverbBuilder, so the return value should be used"]
+	pub fn mix(self, mix: impl Into<Value<Mix>>) -> Self {
+		Self {
+			mix: mix.into(),
+			..self
+		}
+	}
+
+	/// Sets the volume adjustment applied to the reverberations. Defaults
+	/// to 0 dB.
+	#[must_use = "This method consumes self and returns a modified ConvolutionReverbBuilder, so the return value should be used"]
+	pub fn gain(self, gain: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			gain: gain.into(),
+			..self
+		}
+	}
+
+	/// Sets how many frames of audio the reverb processes at a time, which
+	/// is also how many frames the reverb delays its input. Defaults to 512.
+	///
+	/// Smaller partitions reduce the latency, but cost more CPU time. This
+	/// is rounded up to a power of two and kept between 64 and 8192.
+	#[must_use = "This method consumes self and returns a modified ConvolutionReverbBuilder, so the return value should be used"]
+	pub fn partition_size(self, partition_size: usize) -> Self {
+		Self {
+			partition_size,
+			..self
+		}
+	}
+
+	#[must_use]
+	pub(super) fn clamped_partition_size(&self) -> usize {
+		self.partition_size
+			.clamp(Self::MIN_PARTITION_SIZE, Self::MAX_PARTITION_SIZE)
+			.next_power_of_two()
+	}
+
+	/// Builds the reverb, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, ConvolutionReverbHandle) {
+		let template = BuilderTemplate::new(self.clone(), bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (impulse_response_writer, impulse_response_reader) = impulse_response_channel();
+		let sample_rate = Arc::new(AtomicU32::new(0));
+		let partition_size = self.clamped_partition_size();
+		(
+			Box::new(ConvolutionReverb::new(
+				self,
+				command_readers,
+				impulse_response_reader,
+				sample_rate.clone(),
+				template.clone(),
+				bypassed,
+			)),
+			ConvolutionReverbHandle {
+				command_writers,
+				impulse_responses: Mutex::new(impulse_response_writer),
+				partition_size,
+				sample_rate,
+				template,
+			},
+		)
+	}
+}
+
+impl EffectBuilder for ConvolutionReverbBuilder {
+	type Handle = ConvolutionReverbHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for ConvolutionReverbBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
@@ -0,0 +1,189 @@
+use crate::{
+	frame::{interpolate_frame, Frame},
+	sound::static_sound::StaticSoundData,
+};
+
+use super::fft::{Complex, Fft};
+
+/**
+An impulse response split into partitions that are transformed into the
+frequency domain, along with the spectra of the most recent blocks of
+input it's convolved with.
+
+Both channels of the input are transformed at once by treating the left
+channel as the real part of a complex signal and the right channel as the
+imaginary part. Each partition is stored as the average of the left and
+right channels' spectra and half of their difference, which lets the
+packed input spectrum be convolved with both channels at once.
+*/
+#[derive(Clone)]
+pub(super) struct Convolver {
+	impulse_response: StaticSoundData,
+	/// The sample rate the impulse response was resampled to.
+	sample_rate: u32,
+	num_partitions: usize,
+	/// The average of the left and right channels' spectra for each
+	/// partition.
+	sum: Vec<Complex>,
+	/// Half the difference between the left and right channels' spectra
+	/// for each partition, or `None` if both channels are the same.
+	difference: Option<Vec<Complex>>,
+	/// The spectra of the last `num_partitions` blocks of input.
+	history: Vec<Complex>,
+	/// The slot in `history` that holds the most recent spectrum.
+	history_index: usize,
+}
+
+impl Convolver {
+	/// Prepares an impulse response to be convolved with blocks of half
+	/// the FFT's size at the given sample rate.
+	#[must_use]
+	pub fn new(impulse_response: StaticSoundData, sample_rate: u32, fft: &Fft) -> Self {
+		let fft_size = fft.size();
+		let partition_size = fft_size / 2;
+		let frames = resample(&impulse_response, sample_rate);
+		let num_partitions = frames.len().div_ceil(partition_size).max(1);
+		let mono = frames.iter().all(|frame| frame.left == frame.right);
+		let mut sum = vec![Complex::ZERO; num_partitions * fft_size];
+		let mut difference = (!mono).then(|| vec![Complex::ZERO; num_partitions * fft_size]);
+		let mut buffer = vec![Complex::ZERO; fft_size];
+		for partition in 0..num_partitions {
+			// the second half of each partition is left empty so the
+			// convolution doesn't wrap around
+			buffer.fill(Complex::ZERO);
+			for (value, frame) in buffer.iter_mut().zip(
+				frames
+					.iter()
+					.skip(partition * partition_size)
+					.take(partition_size),
+			) {
+				*value = Complex::new(frame.left, frame.right);
+			}
+			fft.forward(&mut buffer);
+			let offset = partition * fft_size;
+			for k in 0..fft_size {
+				let value = buffer[k];
+				let mirrored = buffer[(fft_size - k) % fft_size].conj();
+				let left = (value + mirrored) * 0.5;
+				let right = (value - mirrored) * Complex::new(0.0, -0.5);
+				sum[offset + k] = (left + right) * 0.5;
+				if let Some(difference) = &mut difference {
+					difference[offset + k] = (left - right) * 0.5;
+				}
+			}
+		}
+		Self {
+			impulse_response,
+			sample_rate,
+			num_partitions,
+			sum,
+			difference,
+			history: vec![Complex::ZERO; num_partitions * fft_size],
+			history_index: 0,
+		}
+	}
+
+	#[must_use]
+	pub fn impulse_response(&self) -> &StaticSoundData {
+		&self.impulse_response
+	}
+
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	#[must_use]
+	pub fn num_partitions(&self) -> usize {
+		self.num_partitions
+	}
+
+	/// Forgets the input the convolver has received.
+	pub fn clear(&mut self) {
+		self.history.fill(Complex::ZERO);
+	}
+
+	/// Copies as much of the input history of another convolver as this
+	/// one can hold, so this one can take over without losing the tail
+	/// of the audio the other one was convolving.
+	pub fn copy_history_from(&mut self, other: &Self) {
+		let fft_size = self.history.len() / self.num_partitions;
+		for age in 0..self.num_partitions.min(other.num_partitions) {
+			let from = other.slot(age) * fft_size;
+			let to = self.slot(age) * fft_size;
+			self.history[to..to + fft_size].copy_from_slice(&other.history[from..from + fft_size]);
+		}
+	}
+
+	/// Adds the spectrum of the newest block of input to the history and
+	/// writes the spectrum of the convolution's next block of output.
+	pub fn convolve(&mut self, spectrum: &[Complex], output: &mut [Complex]) {
+		let fft_size = spectrum.len();
+		self.history_index = (self.history_index + 1) % self.num_partitions;
+		let newest = self.history_index * fft_size;
+		self.history[newest..newest + fft_size].copy_from_slice(spectrum);
+		output.fill(Complex::ZERO);
+		for partition in 0..self.num_partitions {
+			let offset = partition * fft_size;
+			let slot = self.slot(partition) * fft_size;
+			let input = &self.history[slot..slot + fft_size];
+			let sum = &self.sum[offset..offset + fft_size];
+			for ((output, input), sum) in output.iter_mut().zip(input).zip(sum) {
+				*output += *input * *sum;
+			}
+			if let Some(difference) = &self.difference {
+				let difference = &difference[offset..offset + fft_size];
+				for (k, (output, difference)) in output.iter_mut().zip(difference).enumerate() {
+					*output += input[(fft_size - k) % fft_size].conj() * *difference;
+				}
+			}
+		}
+	}
+
+	/// Returns the slot in the history that holds the spectrum from `age`
+	/// blocks ago.
+	#[must_use]
+	fn slot(&self, age: usize) -> usize {
+		(self.history_index + self.num_partitions - age) % self.num_partitions
+	}
+}
+
+/// Returns the frames of the impulse response resampled to the given
+/// sample rate.
+#[must_use]
+fn resample(impulse_response: &StaticSoundData, sample_rate: u32) -> Vec<Frame> {
+	let num_frames = impulse_response.num_frames();
+	if sample_rate == 0 {
+		return vec![];
+	}
+	if impulse_response.sample_rate == sample_rate {
+		return (0..num_frames)
+			.filter_map(|index| impulse_response.frame_at_index(index))
+			.collect();
+	}
+	let ratio = impulse_response.sample_rate as f64 / sample_rate as f64;
+	let frame_at = |index: isize| {
+		usize::try_from(index)
+			.ok()
+			.and_then(|index| impulse_response.frame_at_index(index))
+			.unwrap_or(Frame::ZERO)
+	};
+	let resampled_length = (num_frames as f64 / ratio).ceil() as usize;
+	(0..resampled_length)
+		.map(|index| {
+			let position = index as f64 * ratio;
+			let current = position as isize;
+			let fraction = position.fract() as f32;
+			// each sample of the impulse response stands for a longer or
+			// shorter stretch of time at the new sample rate, so it's
+			// scaled to keep the reverb just as loud
+			interpolate_frame(
+				frame_at(current - 1),
+				frame_at(current),
+				frame_at(current + 1),
+				frame_at(current + 2),
+				fraction,
+			) * ratio as f32
+		})
+		.collect()
+}
@@ -0,0 +1,143 @@
+use std::{
+	f64::consts::TAU,
+	ops::{Add, AddAssign, Mul, Sub},
+};
+
+/// A complex number.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(super) struct Complex {
+	pub re: f32,
+	pub im: f32,
+}
+
+impl Complex {
+	pub const ZERO: Self = Self::new(0.0, 0.0);
+
+	#[must_use]
+	pub const fn new(re: f32, im: f32) -> Self {
+		Self { re, im }
+	}
+
+	#[must_use]
+	pub fn conj(self) -> Self {
+		Self::new(self.re, -self.im)
+	}
+}
+
+impl Add for Complex {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self::new(self.re + rhs.re, self.im + rhs.im)
+	}
+}
+
+impl AddAssign for Complex {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+
+impl Sub for Complex {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self::new(self.re - rhs.re, self.im - rhs.im)
+	}
+}
+
+impl Mul for Complex {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		Self::new(
+			self.re * rhs.re - self.im * rhs.im,
+			self.re * rhs.im + self.im * rhs.re,
+		)
+	}
+}
+
+impl Mul<f32> for Complex {
+	type Output = Self;
+
+	fn mul(self, rhs: f32) -> Self::Output {
+		Self::new(self.re * rhs, self.im * rhs)
+	}
+}
+
+/// Performs radix-2 fast Fourier transforms of a fixed size.
+#[derive(Debug, Clone)]
+pub(super) struct Fft {
+	/// `e^(-2πik/n)` for each `k` in the first half of the transform.
+	twiddles: Vec<Complex>,
+	/// The index each element is swapped with before the transform.
+	bit_reversed_indices: Vec<usize>,
+}
+
+impl Fft {
+	/// Creates an [`Fft`] for buffers of the given length, which must be
+	/// a power of two.
+	#[must_use]
+	pub fn new(size: usize) -> Self {
+		assert!(size.is_power_of_two(), "FFT size must be a power of two");
+		let bits = size.trailing_zeros();
+		Self {
+			twiddles: (0..size / 2)
+				.map(|k| {
+					let angle = -TAU * k as f64 / size as f64;
+					Complex::new(angle.cos() as f32, angle.sin() as f32)
+				})
+				.collect(),
+			bit_reversed_indices: (0..size)
+				.map(|i| {
+					i.reverse_bits()
+						.checked_shr(usize::BITS - bits)
+						.unwrap_or(0)
+				})
+				.collect(),
+		}
+	}
+
+	#[must_use]
+	pub fn size(&self) -> usize {
+		self.bit_reversed_indices.len()
+	}
+
+	/// Transforms the buffer from the time domain to the frequency domain.
+	pub fn forward(&self, buffer: &mut [Complex]) {
+		let size = self.size();
+		debug_assert_eq!(buffer.len(), size);
+		for (i, j) in self.bit_reversed_indices.iter().copied().enumerate() {
+			if i < j {
+				buffer.swap(i, j);
+			}
+		}
+		let mut length = 2;
+		while length <= size {
+			let half = length / 2;
+			let twiddle_stride = size / length;
+			for start in (0..size).step_by(length) {
+				for k in 0..half {
+					let a = buffer[start + k];
+					let b = buffer[start + k + half] * self.twiddles[k * twiddle_stride];
+					buffer[start + k] = a + b;
+					buffer[start + k + half] = a - b;
+				}
+			}
+			length *= 2;
+		}
+	}
+
+	/// Transforms the buffer from the frequency domain back to the time
+	/// domain.
+	pub fn inverse(&self, buffer: &mut [Complex]) {
+		for value in buffer.iter_mut() {
+			*value = value.conj();
+		}
+		self.forward(buffer);
+		let scale = 1.0 / self.size() as f32;
+		for value in buffer.iter_mut() {
+			*value = value.conj() * scale;
+		}
+	}
+}
@@ -0,0 +1,73 @@
+use std::sync::{
+	atomic::{AtomicU32, Ordering},
+	Arc, Mutex,
+};
+
+use crate::{
+	effect::{template_param_setters, BuilderTemplate},
+	sound::static_sound::StaticSoundData,
+	Decibels, Mix, Tween,
+};
+
+use super::{
+	convolver::Convolver, fft::Fft, CommandWriters, ConvolutionReverbBuilder, ImpulseResponseWriter,
+};
+
+/// Controls a convolution reverb.
+#[derive(Debug)]
+pub struct ConvolutionReverbHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) impulse_responses: Mutex<ImpulseResponseWriter>,
+	pub(super) partition_size: usize,
+	pub(super) sample_rate: Arc<AtomicU32>,
+	pub(super) template: Arc<BuilderTemplate<ConvolutionReverbBuilder>>,
+}
+
+impl ConvolutionReverbHandle {
+	/// Bypasses or stops bypassing the reverb, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// The reverb keeps delaying the audio while it's bypassed, so
+	/// bypassing it doesn't change the latency of its track.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.command_writers.set_bypassed.write((bypassed, tween))
+	}
+
+	/**
+	Switches the reverb to a different impulse response, crossfading
+	between the old and new reverberations with the given tween.
+
+	The impulse response is resampled and prepared on the current thread
+	before it's sent to the audio thread, which can take a while for long
+	impulse responses. The new impulse response is applied to the audio
+	the reverb already received, so the reverberations don't cut out
+	during the crossfade. If the reverb is still crossfading to a previous
+	impulse response, it switches to this one once that crossfade is
+	finished.
+	*/
+	pub fn set_impulse_response(&mut self, impulse_response: StaticSoundData, tween: Tween) {
+		self.template
+			.update(|builder| builder.impulse_response = impulse_response.clone());
+		let convolver = Convolver::new(
+			impulse_response,
+			self.sample_rate.load(Ordering::SeqCst),
+			&Fft::new(self.partition_size * 2),
+		);
+		self.impulse_responses
+			.get_mut()
+			.expect("impulse response writer mutex poisoned")
+			.write(Box::new(convolver), tween);
+	}
+
+	template_param_setters! {
+		/// Sets how much dry (unprocessed) signal should be blended
+		/// with the wet (processed) signal. `0.0` means only the dry
+		/// signal will be heard. `1.0` means only the wet signal will
+		/// be heard.
+		mix: Mix => mix,
+
+		/// Sets the volume adjustment applied to the reverberations.
+		gain: Decibels => gain,
+	}
+}
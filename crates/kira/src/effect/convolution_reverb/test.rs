@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use crate::{
+	effect::{
+		test_helpers::{CHUNK_SIZE, DT, SAMPLE_RATE},
+		Effect, EffectBuilder,
+	},
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	Mix, Tween,
+};
+
+use super::ConvolutionReverbBuilder;
+
+const PARTITION_SIZE: usize = 64;
+
+fn impulse_response(sample_rate: u32, frames: impl IntoIterator<Item = Frame>) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate,
+		frames: frames.into_iter().collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+/// Returns a deterministic, noisy signal.
+fn noise(seed: usize) -> impl Fn(usize) -> Frame {
+	move |i| {
+		let x = (i * 7919 + seed * 104_729) as f32;
+		Frame::new((x * 0.618).sin() * 0.5, (x * 0.371).cos() * 0.5)
+	}
+}
+
+/// Processes the input in chunks and returns the output. Unlike
+/// [`test_helpers::process`](crate::effect::test_helpers::process), this
+/// lets the reverb read new impulse responses between chunks.
+fn process(effect: &mut dyn Effect, input: &[Frame]) -> Vec<Frame> {
+	let info = MockInfoBuilder::new().build();
+	let mut output = input.to_vec();
+	for chunk in output.chunks_mut(CHUNK_SIZE) {
+		effect.on_start_processing();
+		effect.process(chunk, DT, &info);
+	}
+	output
+}
+
+/// Tests that the reverb's output is the input convolved with the impulse
+/// response, delayed by the partition size.
+#[test]
+fn convolves_input_with_impulse_response() {
+	let ir: Vec<Frame> = (0..300).map(noise(1)).collect();
+	let (mut effect, _) = ConvolutionReverbBuilder::new(impulse_response(SAMPLE_RATE, ir.clone()))
+		.mix(Mix::WET)
+		.partition_size(PARTITION_SIZE)
+		.build();
+	effect.init(SAMPLE_RATE, CHUNK_SIZE);
+	assert_eq!(effect.latency_frames(), PARTITION_SIZE);
+	let input: Vec<Frame> = (0..1024).map(noise(2)).collect();
+	let output = process(&mut *effect, &input);
+	for n in 0..input.len() - PARTITION_SIZE {
+		let expected = (0..=n.min(ir.len() - 1)).fold(Frame::ZERO, |sum, k| {
+			sum + Frame::new(
+				ir[k].left * input[n - k].left,
+				ir[k].right * input[n - k].right,
+			)
+		});
+		let actual = output[n + PARTITION_SIZE];
+		assert!((actual.left - expected.left).abs() < 1.0e-3);
+		assert!((actual.right - expected.right).abs() < 1.0e-3);
+	}
+}
+
+/// Tests that the dry signal is delayed by the partition size so it lines
+/// up with the reverberations.
+#[test]
+fn delays_dry_signal() {
+	let (mut effect, _) =
+		ConvolutionReverbBuilder::new(impulse_response(SAMPLE_RATE, [Frame::from_mono(1.0)]))
+			.mix(Mix::DRY)
+			.partition_size(100)
+			.build();
+	effect.init(SAMPLE_RATE, CHUNK_SIZE);
+	// partition sizes are rounded up to a power of two
+	assert_eq!(effect.latency_frames(), 128);
+	let input: Vec<Frame> = (0..512).map(noise(3)).collect();
+	let output = process(&mut *effect, &input);
+	assert!(output[..128].iter().all(|frame| *frame == Frame::ZERO));
+	assert_eq!(output[128..], input[..512 - 128]);
+}
+
+/// Tests that impulse responses with a different sample rate are
+/// resampled without changing the volume of the reverberations.
+#[test]
+fn resamples_impulse_response() {
+	let (mut effect, _) = ConvolutionReverbBuilder::new(impulse_response(
+		SAMPLE_RATE / 2,
+		vec![Frame::from_mono(0.01); 100],
+	))
+	.mix(Mix::WET)
+	.partition_size(PARTITION_SIZE)
+	.build();
+	effect.init(SAMPLE_RATE, CHUNK_SIZE);
+	let output = process(&mut *effect, &vec![Frame::from_mono(1.0); 1024]);
+	assert!((output[1023].left - 1.0).abs() < 0.02);
+}
+
+/// Tests that switching impulse responses crossfades between the old
+/// and new reverberations.
+#[test]
+fn crossfades_to_new_impulse_response() {
+	let (mut effect, mut handle) =
+		ConvolutionReverbBuilder::new(impulse_response(SAMPLE_RATE, [Frame::from_mono(1.0)]))
+			.mix(Mix::WET)
+			.partition_size(PARTITION_SIZE)
+			.build();
+	effect.init(SAMPLE_RATE, CHUNK_SIZE);
+	let input = vec![Frame::from_mono(1.0); 1024];
+	let output = process(&mut *effect, &input);
+	assert!((output[1023].left - 1.0).abs() < 1.0e-4);
+	handle.set_impulse_response(
+		impulse_response(SAMPLE_RATE, [Frame::from_mono(0.5)]),
+		Tween {
+			duration: Duration::from_millis(10),
+			..Default::default()
+		},
+	);
+	let output = process(&mut *effect, &input);
+	for pair in output.windows(2) {
+		assert!((pair[1].left - pair[0].left).abs() < 0.01);
+	}
+	assert!((output[1023].left - 0.5).abs() < 1.0e-4);
+	// the reverb was done crossfading, so it can switch again
+	handle.set_impulse_response(
+		impulse_response(SAMPLE_RATE, [Frame::from_mono(0.25)]),
+		Tween::default(),
+	);
+	let output = process(&mut *effect, &input);
+	assert!((output[1023].left - 0.25).abs() < 1.0e-4);
+}
+
+/// Tests that impulse responses sent back to back, before the reverb is
+/// done crossfading to the first one, are switched to one after the other.
+#[test]
+fn switches_impulse_responses_sent_back_to_back() {
+	let (mut effect, mut handle) =
+		ConvolutionReverbBuilder::new(impulse_response(SAMPLE_RATE, [Frame::from_mono(1.0)]))
+			.mix(Mix::WET)
+			.partition_size(PARTITION_SIZE)
+			.build();
+	effect.init(SAMPLE_RATE, CHUNK_SIZE);
+	let input = vec![Frame::from_mono(1.0); 1024];
+	process(&mut *effect, &input);
+	let tween = Tween {
+		duration: Duration::from_millis(1),
+		..Default::default()
+	};
+	handle.set_impulse_response(
+		impulse_response(SAMPLE_RATE, [Frame::from_mono(0.5)]),
+		tween,
+	);
+	process(&mut *effect, &input[..128]);
+	handle.set_impulse_response(
+		impulse_response(SAMPLE_RATE, [Frame::from_mono(0.25)]),
+		tween,
+	);
+	let output = process(&mut *effect, &input);
+	assert!((output[1023].left - 0.25).abs() < 1.0e-4);
+	// both of the impulse responses the reverb was done with are sent
+	// back without panicking
+	handle.set_impulse_response(
+		impulse_response(SAMPLE_RATE, [Frame::from_mono(0.5)]),
+		tween,
+	);
+	let output = process(&mut *effect, &input);
+	assert!((output[1023].left - 0.5).abs() < 1.0e-4);
+}
+
+/// Tests that the reverb becomes idle once its reverberations have
+/// finished.
+#[test]
+fn becomes_idle_after_tail() {
+	let (mut effect, _) =
+		ConvolutionReverbBuilder::new(impulse_response(SAMPLE_RATE, (0..200).map(noise(4))))
+			.partition_size(PARTITION_SIZE)
+			.build();
+	effect.init(SAMPLE_RATE, CHUNK_SIZE);
+	assert!(effect.is_idle());
+	process(&mut *effect, &[Frame::from_mono(1.0)]);
+	assert!(!effect.is_idle());
+	let output = process(&mut *effect, &vec![Frame::ZERO; 512]);
+	assert!(output.iter().any(|frame| *frame != Frame::ZERO));
+	assert!(effect.is_idle());
+}
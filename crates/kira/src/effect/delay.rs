@@ -1,5 +1,8 @@
 //! Adds echoes to a sound.
 
+#[cfg(test)]
+mod test;
+
 mod builder;
 mod handle;
 
@@ -16,7 +19,7 @@ use crate::{
 	Decibels, Mix, Parameter,
 };
 
-use super::Effect;
+use super::{bypass::BypassableEffect, is_silent, Effect};
 
 struct Delay {
 	command_readers: CommandReaders,
@@ -26,6 +29,8 @@ struct Delay {
 	buffer: Vec<Frame>,
 	feedback_effects: Vec<Box<dyn Effect>>,
 	temp_buffer: Vec<Frame>,
+	/// How many silent frames have been written to the buffer in a row.
+	quiet_frames: usize,
 }
 
 impl Delay {
@@ -40,6 +45,7 @@ impl Delay {
 			buffer: Vec::with_capacity(0),
 			feedback_effects: builder.feedback_effects,
 			temp_buffer: vec![],
+			quiet_frames: 0,
 		}
 	}
 }
@@ -48,6 +54,7 @@ impl Effect for Delay {
 	fn init(&mut self, sample_rate: u32, internal_buffer_size: usize) {
 		let delay_time_frames = (self.delay_time.as_secs_f64() * sample_rate as f64) as usize;
 		self.buffer = vec![Frame::ZERO; delay_time_frames];
+		self.quiet_frames = delay_time_frames;
 		self.temp_buffer = vec![Frame::ZERO; internal_buffer_size];
 		for effect in &mut self.feedback_effects {
 			effect.init(sample_rate, internal_buffer_size);
@@ -57,6 +64,7 @@ impl Effect for Delay {
 	fn on_change_sample_rate(&mut self, sample_rate: u32) {
 		let delay_time_frames = (self.delay_time.as_secs_f64() * sample_rate as f64) as usize;
 		self.buffer = vec![Frame::ZERO; delay_time_frames];
+		self.quiet_frames = delay_time_frames;
 		for effect in &mut self.feedback_effects {
 			effect.on_change_sample_rate(sample_rate);
 		}
@@ -69,6 +77,12 @@ impl Effect for Delay {
 		}
 	}
 
+	// the delay is idle once everything in its buffer is silent
+	fn is_idle(&self) -> bool {
+		self.quiet_frames >= self.buffer.len()
+			&& self.feedback_effects.iter().all(|effect| effect.is_idle())
+	}
+
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
 		self.feedback.update(dt * input.len() as f64, info);
 		self.mix.update(dt * input.len() as f64, info);
@@ -96,6 +110,11 @@ impl Effect for Delay {
 				.zip(&mut self.temp_buffer[..input.len()])
 			{
 				*out = *input + *read;
+				if is_silent(*out) {
+					self.quiet_frames = self.quiet_frames.saturating_add(1);
+				} else {
+					self.quiet_frames = 0;
+				}
 			}
 
 			// output mix of input and read buffer
@@ -108,6 +127,13 @@ impl Effect for Delay {
 	}
 }
 
+impl BypassableEffect for Delay {
+	fn clear_tail(&mut self) {
+		self.buffer.fill(Frame::ZERO);
+		self.quiet_frames = self.buffer.len();
+	}
+}
+
 command_writers_and_readers! {
 	set_feedback: ValueChangeCommand<Decibels>,
 	set_mix: ValueChangeCommand<Mix>,
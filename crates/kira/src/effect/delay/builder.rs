@@ -1,7 +1,9 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use crate::{
-	effect::{Effect, EffectBuilder},
+	effect::{
+		bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, EffectTemplate, TemplateBuilder,
+	},
 	Decibels, Mix, Value,
 };
 
@@ -81,14 +83,77 @@ impl Default for DelayBuilder {
 	}
 }
 
+impl DelayBuilder {
+	/// Builds the delay, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, DelayHandle) {
+		// the delay can only be copied if all of its feedback effects can be
+		let feedback_effect_templates = self
+			.feedback_effects
+			.iter()
+			.map(|effect| effect.template())
+			.collect::<Option<Vec<_>>>();
+		let can_be_copied = feedback_effect_templates.is_some();
+		let template = BuilderTemplate::new(
+			DelayTemplate {
+				delay_time: self.delay_time,
+				feedback: self.feedback,
+				mix: self.mix,
+				feedback_effects: feedback_effect_templates.unwrap_or_default(),
+			},
+			bypassed,
+		);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			Delay::new(self, command_readers),
+			if can_be_copied {
+				Some(template.clone())
+			} else {
+				None
+			},
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			DelayHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
 impl EffectBuilder for DelayBuilder {
 	type Handle = DelayHandle;
 
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
-		let (command_writers, command_readers) = command_writers_and_readers();
-		(
-			Box::new(Delay::new(self, command_readers)),
-			DelayHandle { command_writers },
-		)
+		self.build_with_template(false)
+	}
+}
+
+/// The settings of a delay effect, which are kept up to date by the
+/// delay's handle and used to create copies of the delay.
+#[derive(Debug, Clone)]
+pub(super) struct DelayTemplate {
+	delay_time: Duration,
+	pub(super) feedback: Value<Decibels>,
+	pub(super) mix: Value<Mix>,
+	feedback_effects: Vec<Arc<dyn EffectTemplate>>,
+}
+
+impl TemplateBuilder for DelayTemplate {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		DelayBuilder {
+			delay_time: self.delay_time,
+			feedback: self.feedback,
+			feedback_effects: self
+				.feedback_effects
+				.iter()
+				.map(|template| template.create_effect())
+				.collect(),
+			mix: self.mix,
+		}
+		.build_with_template(bypassed)
+		.0
 	}
 }
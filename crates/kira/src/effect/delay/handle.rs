@@ -1,20 +1,39 @@
-use crate::{command::handle_param_setters, Decibels, Mix};
+use std::sync::Arc;
 
-use super::CommandWriters;
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Mix, Tween,
+};
+
+use super::{CommandWriters, DelayTemplate};
 
 /// Controls a delay effect.
 #[derive(Debug)]
 pub struct DelayHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<DelayTemplate>>,
 }
 
 impl DelayHandle {
-	handle_param_setters! {
+	/// Bypasses or stops bypassing the delay, crossfading between its input and
+	/// output with the given tween.
+	///
+	/// Once it's fully bypassed, the delay stops processing audio, and its
+	/// echoes are cleared when it stops being bypassed so old audio isn't
+	/// played.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
 		/// Sets the amount of feedback.
-		feedback: Decibels,
+		feedback: Decibels => feedback,
 
 		/// Sets how much dry (unprocessed) signal should be blended
 		/// with the wet (processed) signal.
-		mix: Mix,
+		mix: Mix => mix,
 	}
 }
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use crate::{effect::Effect, frame::Frame, info::MockInfoBuilder, Decibels};
+
+use super::{command_writers_and_readers, Delay, DelayBuilder};
+
+/// Tests that a delay is idle until it receives audio and becomes idle
+/// again once its echoes have faded out.
+#[test]
+fn is_idle_when_echoes_fade_out() {
+	let info = MockInfoBuilder::new().build();
+	let mut delay = Delay::new(
+		DelayBuilder::new()
+			.delay_time(Duration::from_secs(4))
+			.feedback(Decibels(-6.0)),
+		command_writers_and_readers().1,
+	);
+	delay.init(1, 4);
+	assert!(delay.is_idle());
+
+	let mut input = [Frame::from_mono(1.0), Frame::ZERO, Frame::ZERO, Frame::ZERO];
+	delay.process(&mut input, 1.0, &info);
+	let mut num_echoes = 0;
+	while !delay.is_idle() {
+		let mut input = [Frame::ZERO; 4];
+		delay.process(&mut input, 1.0, &info);
+		assert!(input[0].left > 0.0);
+		num_echoes += 1;
+		assert!(num_echoes < 100, "the delay never became idle");
+	}
+	// each echo is about half as loud as the previous one, so it takes 17
+	// echoes to get quieter than the idle threshold
+	assert_eq!(num_echoes, 17);
+}
@@ -9,7 +9,7 @@ pub use handle::*;
 use crate::{
 	command::{read_commands_into_parameters, ValueChangeCommand},
 	command_writers_and_readers,
-	effect::Effect,
+	effect::{bypass::BypassableEffect, Effect},
 	frame::Frame,
 	info::Info,
 	Decibels, Mix, Parameter,
@@ -54,6 +54,11 @@ impl Effect for Distortion {
 		read_commands_into_parameters!(self, drive, mix);
 	}
 
+	// silence stays silent
+	fn is_idle(&self) -> bool {
+		true
+	}
+
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
 		self.drive.update(dt * input.len() as f64, info);
 		self.mix.update(dt * input.len() as f64, info);
@@ -81,6 +86,8 @@ impl Effect for Distortion {
 	}
 }
 
+impl BypassableEffect for Distortion {}
+
 command_writers_and_readers! {
 	set_kind: DistortionKind,
 	set_drive: ValueChangeCommand<Decibels>,
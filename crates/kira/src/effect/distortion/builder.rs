@@ -1,5 +1,5 @@
 use crate::{
-	effect::{Effect, EffectBuilder},
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
 	Decibels, Mix, Parameter, Value,
 };
 
@@ -64,19 +64,42 @@ impl Default for DistortionBuilder {
 	}
 }
 
-impl EffectBuilder for DistortionBuilder {
-	type Handle = DistortionHandle;
-
-	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+impl DistortionBuilder {
+	/// Builds the distortion, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, DistortionHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
 		let (command_writers, command_readers) = command_writers_and_readers();
-		(
-			Box::new(Distortion {
+		let (effect, set_bypassed) = Bypassable::new(
+			Distortion {
 				command_readers,
 				kind: self.kind,
 				drive: Parameter::new(self.drive, Decibels::IDENTITY),
 				mix: Parameter::new(self.mix, Mix::WET),
-			}),
-			DistortionHandle { command_writers },
+			},
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			DistortionHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
 		)
 	}
 }
+
+impl EffectBuilder for DistortionBuilder {
+	type Handle = DistortionHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for DistortionBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
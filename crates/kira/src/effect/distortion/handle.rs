@@ -1,25 +1,43 @@
-use crate::{command::handle_param_setters, Decibels, Mix};
+use std::sync::Arc;
 
-use super::{CommandWriters, DistortionKind};
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Mix, Tween,
+};
+
+use super::{CommandWriters, DistortionBuilder, DistortionKind};
 
 /// Controls a distortion effect.
 #[derive(Debug)]
 pub struct DistortionHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<DistortionBuilder>>,
 }
 
 impl DistortionHandle {
+	/// Bypasses or stops bypassing the distortion, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the distortion stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
 	/// Sets the kind of distortion to use.
 	pub fn set_kind(&mut self, kind: DistortionKind) {
+		self.template.update(|builder| builder.kind = kind);
 		self.command_writers.set_kind.write(kind)
 	}
 
-	handle_param_setters! {
+	template_param_setters! {
 		/// Sets how much distortion should be applied.
-		drive: Decibels,
+		drive: Decibels => drive,
 
 		/// Sets how much dry (unprocessed) signal should be blended
 		/// with the wet (processed) signal.
-		mix: Mix,
+		mix: Mix => mix,
 	}
 }
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+/// Smoothly follows a level, moving towards it at one speed while it's
+/// rising (the attack) and another while it's falling (the release).
+///
+/// This is used by dynamics effects, like compressors and gates, to decide
+/// how much to change the volume of the audio.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct EnvelopeFollower {
+	// the envelope is stored as an `f64` so it can get close to targets far
+	// from zero without the steps towards them being rounded away
+	value: f64,
+}
+
+impl EnvelopeFollower {
+	#[must_use]
+	pub fn new(value: f32) -> Self {
+		Self {
+			value: value.into(),
+		}
+	}
+
+	#[must_use]
+	pub fn value(&self) -> f32 {
+		self.value as f32
+	}
+
+	/// Moves the envelope one frame towards `target` and returns the new
+	/// value. A duration of zero moves the envelope all the way to the
+	/// target immediately.
+	pub fn process(
+		&mut self,
+		target: f32,
+		attack_duration: Duration,
+		release_duration: Duration,
+		dt: f64,
+	) -> f32 {
+		let target = f64::from(target);
+		let duration = if self.value > target {
+			release_duration
+		} else {
+			attack_duration
+		};
+		let speed = (-1.0 / (duration.as_secs_f64() / dt)).exp();
+		self.value = target + speed * (self.value - target);
+		self.value as f32
+	}
+}
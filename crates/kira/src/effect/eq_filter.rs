@@ -18,7 +18,7 @@ use crate::{
 	Decibels, Parameter,
 };
 
-use super::Effect;
+use super::{bypass::BypassableEffect, is_silent, Effect};
 
 const MIN_Q: f64 = 0.01;
 
@@ -55,6 +55,10 @@ impl Effect for EqFilter {
 		read_commands_into_parameters!(self, frequency, gain, q);
 	}
 
+	fn is_idle(&self) -> bool {
+		is_silent(self.ic1eq) && is_silent(self.ic2eq)
+	}
+
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
 		self.frequency.update(dt * input.len() as f64, info);
 		self.gain.update(dt * input.len() as f64, info);
@@ -67,20 +71,11 @@ impl Effect for EqFilter {
 			let q = self.q.interpolated_value(time_in_chunk);
 			let gain = self.gain.interpolated_value(time_in_chunk);
 
-			let Coefficients {
-				a1,
-				a2,
-				a3,
-				m0,
-				m1,
-				m2,
-			} = Coefficients::calculate(self.kind, frequency, q, gain, dt);
-			let v3 = *frame - self.ic2eq;
-			let v1 = self.ic1eq * (a1 as f32) + v3 * (a2 as f32);
-			let v2 = self.ic2eq + self.ic1eq * (a2 as f32) + v3 * (a3 as f32);
-			self.ic1eq = v1 * 2.0 - self.ic1eq;
-			self.ic2eq = v2 * 2.0 - self.ic2eq;
-			*frame = *frame * (m0 as f32) + v1 * (m1 as f32) + v2 * (m2 as f32)
+			*frame = Coefficients::calculate(self.kind, frequency, q, gain, dt).tick(
+				*frame,
+				&mut self.ic1eq,
+				&mut self.ic2eq,
+			);
 		}
 	}
 }
@@ -95,9 +90,17 @@ pub enum EqFilterKind {
 	LowShelf,
 	/// Frequencies around and higher than the user-defined frequency are adjusted.
 	HighShelf,
+	/// Frequencies higher than the user-defined frequency are removed.
+	///
+	/// The gain has no effect on this curve.
+	LowPass,
+	/// Frequencies lower than the user-defined frequency are removed.
+	///
+	/// The gain has no effect on this curve.
+	HighPass,
 }
 
-struct Coefficients {
+pub(super) struct Coefficients {
 	a1: f64,
 	a2: f64,
 	a3: f64,
@@ -108,7 +111,13 @@ struct Coefficients {
 
 impl Coefficients {
 	#[must_use]
-	fn calculate(kind: EqFilterKind, frequency: f64, q: f64, gain: Decibels, dt: f64) -> Self {
+	pub(super) fn calculate(
+		kind: EqFilterKind,
+		frequency: f64,
+		q: f64,
+		gain: Decibels,
+		dt: f64,
+	) -> Self {
 		// In my testing, the filter goes unstable when the frequency exceeds half the sample rate,
 		// so I'm clamping this value to 0.5
 		let relative_frequency = (frequency * dt).clamp(0.0001, 0.5);
@@ -171,8 +180,46 @@ impl Coefficients {
 					m2,
 				}
 			}
+			EqFilterKind::LowPass | EqFilterKind::HighPass => {
+				let g = (PI * relative_frequency).tan();
+				let k = 1.0 / q;
+				let a1 = 1.0 / (1.0 + g * (g + k));
+				let a2 = g * a1;
+				let a3 = g * a2;
+				let (m0, m1, m2) = if kind == EqFilterKind::LowPass {
+					(0.0, 0.0, 1.0)
+				} else {
+					(1.0, -k, -1.0)
+				};
+				Self {
+					a1,
+					a2,
+					a3,
+					m0,
+					m1,
+					m2,
+				}
+			}
 		}
 	}
+
+	/// Filters one frame of audio, updating the filter's state.
+	#[must_use]
+	pub(super) fn tick(&self, input: Frame, ic1eq: &mut Frame, ic2eq: &mut Frame) -> Frame {
+		let v3 = input - *ic2eq;
+		let v1 = *ic1eq * (self.a1 as f32) + v3 * (self.a2 as f32);
+		let v2 = *ic2eq + *ic1eq * (self.a2 as f32) + v3 * (self.a3 as f32);
+		*ic1eq = v1 * 2.0 - *ic1eq;
+		*ic2eq = v2 * 2.0 - *ic2eq;
+		input * (self.m0 as f32) + v1 * (self.m1 as f32) + v2 * (self.m2 as f32)
+	}
+}
+
+impl BypassableEffect for EqFilter {
+	fn clear_tail(&mut self) {
+		self.ic1eq = Frame::ZERO;
+		self.ic2eq = Frame::ZERO;
+	}
 }
 
 command_writers_and_readers! {
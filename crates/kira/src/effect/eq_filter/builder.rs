@@ -1,8 +1,12 @@
-use crate::{effect::EffectBuilder, Decibels, Value};
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Decibels, Value,
+};
 
 use super::{command_writers_and_readers, EqFilter, EqFilterHandle, EqFilterKind};
 
 /// Configures an EQ filter.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct EqFilterBuilder {
 	/// The shape of the frequency adjustment curve.
 	pub kind: EqFilterKind,
@@ -36,14 +40,37 @@ impl EqFilterBuilder {
 	}
 }
 
-impl EffectBuilder for EqFilterBuilder {
-	type Handle = EqFilterHandle;
-
-	fn build(self) -> (Box<dyn crate::effect::Effect>, Self::Handle) {
+impl EqFilterBuilder {
+	/// Builds the EQ filter, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, EqFilterHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
 		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			EqFilter::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
 		(
-			Box::new(EqFilter::new(self, command_readers)),
-			EqFilterHandle { command_writers },
+			Box::new(effect),
+			EqFilterHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
 		)
 	}
 }
+
+impl EffectBuilder for EqFilterBuilder {
+	type Handle = EqFilterHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for EqFilterBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
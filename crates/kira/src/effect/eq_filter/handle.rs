@@ -1,31 +1,49 @@
-use crate::{command::handle_param_setters, Decibels};
+use std::sync::Arc;
 
-use super::{CommandWriters, EqFilterKind};
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Tween,
+};
+
+use super::{CommandWriters, EqFilterBuilder, EqFilterKind};
 
 /// Controls an EQ filter.
 #[derive(Debug)]
 pub struct EqFilterHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<EqFilterBuilder>>,
 }
 
 impl EqFilterHandle {
+	/// Bypasses or stops bypassing the EQ filter, crossfading between its input
+	/// and output with the given tween.
+	///
+	/// Once it's fully bypassed, the filter stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
 	/// Sets the shape of the frequency adjustment curve.
 	pub fn set_kind(&mut self, kind: EqFilterKind) {
+		self.template.update(|builder| builder.kind = kind);
 		self.command_writers.set_kind.write(kind)
 	}
 
-	handle_param_setters! {
+	template_param_setters! {
 		/// Sets the "center" or "corner" of the frequency range to adjust in Hz
 		/// (for bell or shelf curves, respectively).
-		frequency: f64,
+		frequency: f64 => frequency,
 
 		/// Sets the volume adjustment for frequencies in the specified range (in decibels).
-		gain: Decibels,
+		gain: Decibels => gain,
 
 		/// Sets the width of the frequency range to adjust.
 		///
 		/// A higher Q value results in a narrower range of frequencies being adjusted.
 		/// The value should be greater than `0.0`.
-		q: f64,
+		q: f64 => q,
 	}
 }
@@ -6,12 +6,15 @@ mod handle;
 pub use builder::*;
 pub use handle::*;
 
-use std::f64::consts::PI;
+use std::{
+	f64::consts::PI,
+	ops::{Add, Mul, Sub},
+};
 
 use crate::{
 	command::{read_commands_into_parameters, ValueChangeCommand},
 	command_writers_and_readers,
-	effect::Effect,
+	effect::{bypass::BypassableEffect, is_silent, Effect},
 	frame::Frame,
 	info::Info,
 	Mix, Parameter,
@@ -68,43 +71,107 @@ impl Effect for Filter {
 		read_commands_into_parameters!(self, cutoff, resonance, mix);
 	}
 
+	fn is_idle(&self) -> bool {
+		is_silent(self.ic1eq) && is_silent(self.ic2eq)
+	}
+
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
-		self.cutoff.update(dt * input.len() as f64, info);
-		self.resonance.update(dt * input.len() as f64, info);
-		self.mix.update(dt * input.len() as f64, info);
+		self.update_parameters(dt * input.len() as f64, info);
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let coefficients = self.coefficients(time_in_chunk, dt);
+			*frame = coefficients.tick(*frame, &mut self.ic1eq, &mut self.ic2eq);
+		}
+	}
 
+	fn process_mono(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.update_parameters(dt * input.len() as f64, info);
 		let num_frames = input.len();
+		let mut ic1eq = self.ic1eq.left;
+		let mut ic2eq = self.ic2eq.left;
 		for (i, frame) in input.iter_mut().enumerate() {
 			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
-			let cutoff = self.cutoff.interpolated_value(time_in_chunk);
-			let resonance = self
-				.resonance
-				.interpolated_value(time_in_chunk)
-				.clamp(0.0, 1.0);
-			let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
-
-			let sample_rate = 1.0 / dt;
-			let g = (PI * (cutoff / sample_rate).clamp(0.0001, 0.5)).tan();
-			let k = 2.0 - (1.9 * resonance);
-			let a1 = 1.0 / (1.0 + (g * (g + k)));
-			let a2 = g * a1;
-			let a3 = g * a2;
-			let v3 = *frame - self.ic2eq;
-			let v1 = (self.ic1eq * (a1 as f32)) + (v3 * (a2 as f32));
-			let v2 = self.ic2eq + (self.ic1eq * (a2 as f32)) + (v3 * (a3 as f32));
-			self.ic1eq = (v1 * 2.0) - self.ic1eq;
-			self.ic2eq = (v2 * 2.0) - self.ic2eq;
-			let output = match self.mode {
-				FilterMode::LowPass => v2,
-				FilterMode::BandPass => v1,
-				FilterMode::HighPass => *frame - v1 * (k as f32) - v2,
-				FilterMode::Notch => *frame - v1 * (k as f32),
-			};
-			*frame = output * mix.sqrt() + *frame * (1.0 - mix).sqrt()
+			let coefficients = self.coefficients(time_in_chunk, dt);
+			*frame = Frame::from_mono(coefficients.tick(frame.left, &mut ic1eq, &mut ic2eq));
+		}
+		self.ic1eq = Frame::from_mono(ic1eq);
+		self.ic2eq = Frame::from_mono(ic2eq);
+	}
+}
+
+impl Filter {
+	fn update_parameters(&mut self, dt: f64, info: &Info) {
+		self.cutoff.update(dt, info);
+		self.resonance.update(dt, info);
+		self.mix.update(dt, info);
+	}
+
+	#[must_use]
+	fn coefficients(&self, time_in_chunk: f64, dt: f64) -> Coefficients {
+		let cutoff = self.cutoff.interpolated_value(time_in_chunk);
+		let resonance = self
+			.resonance
+			.interpolated_value(time_in_chunk)
+			.clamp(0.0, 1.0);
+		let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
+		let sample_rate = 1.0 / dt;
+		let g = (PI * (cutoff / sample_rate).clamp(0.0001, 0.5)).tan();
+		let k = 2.0 - (1.9 * resonance);
+		let a1 = 1.0 / (1.0 + (g * (g + k)));
+		let a2 = g * a1;
+		let a3 = g * a2;
+		Coefficients {
+			mode: self.mode,
+			k: k as f32,
+			a1: a1 as f32,
+			a2: a2 as f32,
+			a3: a3 as f32,
+			mix,
 		}
 	}
 }
 
+/// The values the filter uses to process one frame of audio.
+struct Coefficients {
+	mode: FilterMode,
+	k: f32,
+	a1: f32,
+	a2: f32,
+	a3: f32,
+	mix: f32,
+}
+
+impl Coefficients {
+	/// Filters one sample of audio, which can be a [`Frame`] or a single
+	/// channel of one.
+	#[must_use]
+	fn tick<T>(&self, input: T, ic1eq: &mut T, ic2eq: &mut T) -> T
+	where
+		T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+	{
+		let v3 = input - *ic2eq;
+		let v1 = (*ic1eq * self.a1) + (v3 * self.a2);
+		let v2 = *ic2eq + (*ic1eq * self.a2) + (v3 * self.a3);
+		*ic1eq = (v1 * 2.0) - *ic1eq;
+		*ic2eq = (v2 * 2.0) - *ic2eq;
+		let output = match self.mode {
+			FilterMode::LowPass => v2,
+			FilterMode::BandPass => v1,
+			FilterMode::HighPass => input - v1 * self.k - v2,
+			FilterMode::Notch => input - v1 * self.k,
+		};
+		output * self.mix.sqrt() + input * (1.0 - self.mix).sqrt()
+	}
+}
+
+impl BypassableEffect for Filter {
+	fn clear_tail(&mut self) {
+		self.ic1eq = Frame::ZERO;
+		self.ic2eq = Frame::ZERO;
+	}
+}
+
 command_writers_and_readers!(
 	set_mode: FilterMode,
 	set_cutoff: ValueChangeCommand<f64>,
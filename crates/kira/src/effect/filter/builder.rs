@@ -1,5 +1,5 @@
 use crate::{
-	effect::{Effect, EffectBuilder},
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
 	Mix, Value,
 };
 
@@ -77,14 +77,37 @@ impl Default for FilterBuilder {
 	}
 }
 
+impl FilterBuilder {
+	/// Builds the filter, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, FilterHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			Filter::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			FilterHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
 impl EffectBuilder for FilterBuilder {
 	type Handle = FilterHandle;
 
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
-		let (command_writers, command_readers) = command_writers_and_readers();
-		(
-			Box::new(Filter::new(self, command_readers)),
-			FilterHandle { command_writers },
-		)
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for FilterBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
 	}
 }
@@ -1,28 +1,46 @@
-use crate::{command::handle_param_setters, Mix};
+use std::sync::Arc;
 
-use super::{CommandWriters, FilterMode};
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Mix, Tween,
+};
+
+use super::{CommandWriters, FilterBuilder, FilterMode};
 
 /// Controls a filter effect.
 #[derive(Debug)]
 pub struct FilterHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<FilterBuilder>>,
 }
 
 impl FilterHandle {
+	/// Bypasses or stops bypassing the filter, crossfading between its input
+	/// and output with the given tween.
+	///
+	/// Once it's fully bypassed, the filter stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
 	/// Sets the frequencies that the filter will remove.
 	pub fn set_mode(&mut self, mode: FilterMode) {
+		self.template.update(|builder| builder.mode = mode);
 		self.command_writers.set_mode.write(mode)
 	}
 
-	handle_param_setters! {
+	template_param_setters! {
 		/// Sets the cutoff frequency of the filter (in hertz).
-		cutoff: f64,
+		cutoff: f64 => cutoff,
 
 		/// Sets the resonance of the filter.
-		resonance: f64,
+		resonance: f64 => resonance,
 
 		/// Sets how much dry (unprocessed) signal should be blended
 		/// with the wet (processed) signal.
-		mix: Mix,
+		mix: Mix => mix,
 	}
 }
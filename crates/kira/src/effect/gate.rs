@@ -0,0 +1,228 @@
+//! Silences (or turns down) audio while it's quiet.
+//!
+//! A gate opens when the audio it follows gets louder than its threshold
+//! and closes again once the audio gets quieter, which keeps background
+//! noise from being heard between louder sounds. Instead of silencing the
+//! audio, a gate with a low [ratio](GateBuilder::ratio) or a small
+//! [range](GateBuilder::range) only turns it down, like an expander.
+//!
+//! Like a [compressor](super::compressor), a gate can follow the level of
+//! another track by adding it with
+//! [`TrackBuilder::add_effect_with_sidechain`](crate::track::TrackBuilder::add_effect_with_sidechain).
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::Frame,
+	info::Info,
+	Decibels, Parameter,
+};
+
+use super::{bypass::BypassableEffect, envelope::EnvelopeFollower, Effect};
+
+/// How long the level the gate follows takes to fall after the audio gets
+/// quieter, which keeps the level from dropping between the peaks of low
+/// sounds.
+const DETECTOR_RELEASE_DURATION: Duration = Duration::from_millis(20);
+/// How far above its range (in decibels) the gate's gain can be while the
+/// gate is idle.
+const IDLE_GAIN: f32 = 1.0e-3;
+
+struct Gate {
+	command_readers: CommandReaders,
+	threshold: Parameter,
+	hysteresis: Parameter,
+	ratio: Parameter,
+	range: Parameter<Decibels>,
+	attack_duration: Parameter<Duration>,
+	hold_duration: Parameter<Duration>,
+	release_duration: Parameter<Duration>,
+	/// Follows the peak amplitude of the audio the gate measures.
+	detector: EnvelopeFollower,
+	/// Follows the gain (in decibels) the gate applies to the audio.
+	gain: EnvelopeFollower,
+	open: bool,
+	/// How much longer the gate stays open after the audio falls below the
+	/// closing threshold (in seconds).
+	hold_remaining: f64,
+	/// The gain of the most recently processed frame (in dB), stored as the
+	/// bits of an `f32`.
+	gain_reduction: Arc<AtomicU32>,
+	/// Whether the gate was open after the most recently processed frame.
+	shared_open: Arc<AtomicBool>,
+}
+
+impl Gate {
+	#[must_use]
+	fn new(
+		builder: GateBuilder,
+		command_readers: CommandReaders,
+		gain_reduction: Arc<AtomicU32>,
+		shared_open: Arc<AtomicBool>,
+	) -> Self {
+		let range = Parameter::new(builder.range, GateBuilder::DEFAULT_RANGE);
+		// the gate starts out closed, so audio that starts quiet stays quiet
+		let gain = EnvelopeFollower::new(range.value().0.min(0.0));
+		gain_reduction.store(gain.value().to_bits(), Ordering::Relaxed);
+		Self {
+			command_readers,
+			threshold: Parameter::new(builder.threshold, GateBuilder::DEFAULT_THRESHOLD),
+			hysteresis: Parameter::new(builder.hysteresis, GateBuilder::DEFAULT_HYSTERESIS),
+			ratio: Parameter::new(builder.ratio, GateBuilder::DEFAULT_RATIO),
+			range,
+			attack_duration: Parameter::new(
+				builder.attack_duration,
+				GateBuilder::DEFAULT_ATTACK_DURATION,
+			),
+			hold_duration: Parameter::new(
+				builder.hold_duration,
+				GateBuilder::DEFAULT_HOLD_DURATION,
+			),
+			release_duration: Parameter::new(
+				builder.release_duration,
+				GateBuilder::DEFAULT_RELEASE_DURATION,
+			),
+			detector: EnvelopeFollower::default(),
+			gain,
+			open: false,
+			hold_remaining: 0.0,
+			gain_reduction,
+			shared_open,
+		}
+	}
+
+	/// Gates the input, measuring the level the gate follows from
+	/// `sidechain` instead of the input if it's given.
+	fn gate(&mut self, input: &mut [Frame], sidechain: Option<&[Frame]>, dt: f64, info: &Info) {
+		let chunk_duration = dt * input.len() as f64;
+		self.threshold.update(chunk_duration, info);
+		self.hysteresis.update(chunk_duration, info);
+		self.ratio.update(chunk_duration, info);
+		self.range.update(chunk_duration, info);
+		self.attack_duration.update(chunk_duration, info);
+		self.hold_duration.update(chunk_duration, info);
+		self.release_duration.update(chunk_duration, info);
+
+		let threshold = self.threshold.value() as f32;
+		// the gate closes at a lower level than it opens at, so audio that
+		// hovers around the threshold doesn't open and close it over and over
+		let close_threshold = threshold - self.hysteresis.value().max(0.0) as f32;
+		let ratio = self.ratio.value().max(1.0) as f32;
+		let range = self.range.value().0.min(0.0);
+		let attack_duration = self.attack_duration.value();
+		let hold_duration = self.hold_duration.value().as_secs_f64();
+		let release_duration = self.release_duration.value();
+
+		for (i, frame) in input.iter_mut().enumerate() {
+			let detector = sidechain.map_or(*frame, |sidechain| sidechain[i]);
+			let level = self.detector.process(
+				detector.left.abs().max(detector.right.abs()),
+				Duration::ZERO,
+				DETECTOR_RELEASE_DURATION,
+				dt,
+			);
+			let level_decibels = 20.0 * level.max(f32::MIN_POSITIVE).log10();
+			if level_decibels >= threshold {
+				self.open = true;
+				self.hold_remaining = hold_duration;
+			} else if self.open && level_decibels < close_threshold {
+				if self.hold_remaining > 0.0 {
+					self.hold_remaining -= dt;
+				} else {
+					self.open = false;
+				}
+			}
+
+			// while the gate is closed, every decibel the audio is below the
+			// threshold turns it down by another `ratio - 1` decibels, until it's
+			// turned down by the full range
+			let target_gain = if self.open {
+				0.0
+			} else {
+				((level_decibels - threshold) * (ratio - 1.0)).max(range)
+			};
+			let gain = self
+				.gain
+				.process(target_gain, attack_duration, release_duration, dt);
+			*frame *= Decibels(gain).as_amplitude();
+		}
+
+		self.gain_reduction
+			.store(self.gain.value().to_bits(), Ordering::Relaxed);
+		self.shared_open.store(self.open, Ordering::Relaxed);
+	}
+}
+
+impl Effect for Gate {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(
+			self,
+			threshold,
+			hysteresis,
+			ratio,
+			range,
+			attack_duration,
+			hold_duration,
+			release_duration,
+		);
+	}
+
+	// silence stays silent, but the gate has to finish closing so it
+	// doesn't let through the start of the next quiet audio it receives
+	fn is_idle(&self) -> bool {
+		!self.open && self.gain.value() - self.range.value().0.min(0.0) < IDLE_GAIN
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.gate(input, None, dt, info);
+	}
+
+	fn process_with_sidechain(
+		&mut self,
+		input: &mut [Frame],
+		sidechain: &[Frame],
+		dt: f64,
+		info: &Info,
+	) {
+		self.gate(input, Some(sidechain), dt, info);
+	}
+}
+
+impl BypassableEffect for Gate {
+	fn clear_tail(&mut self) {
+		self.detector = EnvelopeFollower::default();
+		self.gain = EnvelopeFollower::new(self.range.value().0.min(0.0));
+		self.open = false;
+		self.hold_remaining = 0.0;
+		self.gain_reduction
+			.store(self.gain.value().to_bits(), Ordering::Relaxed);
+		self.shared_open.store(false, Ordering::Relaxed);
+	}
+}
+
+command_writers_and_readers! {
+	set_threshold: ValueChangeCommand<f64>,
+	set_hysteresis: ValueChangeCommand<f64>,
+	set_ratio: ValueChangeCommand<f64>,
+	set_range: ValueChangeCommand<Decibels>,
+	set_attack_duration: ValueChangeCommand<Duration>,
+	set_hold_duration: ValueChangeCommand<Duration>,
+	set_release_duration: ValueChangeCommand<Duration>,
+}
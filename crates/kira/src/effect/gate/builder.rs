@@ -0,0 +1,206 @@
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU32},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Decibels, Value,
+};
+
+use super::{command_writers_and_readers, Gate, GateHandle};
+
+/**
+Configures a gate.
+
+# Examples
+
+Turn quiet audio down by 12 dB, like an expander, instead of silencing it:
+
+```
+use kira::{effect::gate::GateBuilder, Decibels};
+
+let builder = GateBuilder::new()
+	.threshold(-35.0)
+	.ratio(2.0)
+	.range(Decibels(-12.0));
+```
+*/
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GateBuilder {
+	/// The volume above which the gate opens (in decibels).
+	pub threshold: Value<f64>,
+	/// How far below the threshold the volume has to fall for the gate to
+	/// close (in decibels).
+	///
+	/// This keeps audio that hovers around the threshold from opening and
+	/// closing the gate over and over.
+	pub hysteresis: Value<f64>,
+	/// How much the audio is turned down while the gate is closed.
+	///
+	/// A ratio of `2.0` (or 1 to 2) means audio 3dB below the threshold
+	/// will be turned down to 6dB below the threshold. High ratios close
+	/// the gate fully. Ratios below `1.0` are treated as `1.0`.
+	pub ratio: Value<f64>,
+	/// The most the gate turns down the audio while it's closed.
+	///
+	/// [`Decibels::SILENCE`] lets the gate silence the audio completely.
+	pub range: Value<Decibels>,
+	/// How much time it takes for the gate to open once the input volume
+	/// exceeds the threshold.
+	pub attack_duration: Value<Duration>,
+	/// How long the gate stays open after the input volume falls below the
+	/// closing threshold.
+	pub hold_duration: Value<Duration>,
+	/// How much time it takes for the gate to close once it's done holding
+	/// open.
+	pub release_duration: Value<Duration>,
+}
+
+impl GateBuilder {
+	pub(crate) const DEFAULT_THRESHOLD: f64 = -40.0;
+	pub(crate) const DEFAULT_HYSTERESIS: f64 = 0.0;
+	pub(crate) const DEFAULT_RATIO: f64 = 100.0;
+	pub(crate) const DEFAULT_RANGE: Decibels = Decibels::SILENCE;
+	pub(crate) const DEFAULT_ATTACK_DURATION: Duration = Duration::from_millis(1);
+	pub(crate) const DEFAULT_HOLD_DURATION: Duration = Duration::from_millis(50);
+	pub(crate) const DEFAULT_RELEASE_DURATION: Duration = Duration::from_millis(100);
+
+	/// Creates a new [`GateBuilder`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			threshold: Value::Fixed(Self::DEFAULT_THRESHOLD),
+			hysteresis: Value::Fixed(Self::DEFAULT_HYSTERESIS),
+			ratio: Value::Fixed(Self::DEFAULT_RATIO),
+			range: Value::Fixed(Self::DEFAULT_RANGE),
+			attack_duration: Value::Fixed(Self::DEFAULT_ATTACK_DURATION),
+			hold_duration: Value::Fixed(Self::DEFAULT_HOLD_DURATION),
+			release_duration: Value::Fixed(Self::DEFAULT_RELEASE_DURATION),
+		}
+	}
+
+	/// Sets the volume above which the gate opens (in decibels). Defaults
+	/// to -40 dB.
+	#[must_use = "This method consumes self and returns a modified GateBuilder, so the return value should be used"]
+	pub fn threshold(self, threshold: impl Into<Value<f64>>) -> Self {
+		Self {
+			threshold: threshold.into(),
+			..self
+		}
+	}
+
+	/// Sets how far below the threshold the volume has to fall for the gate
+	/// to close (in decibels). Defaults to 0 dB.
+	///
+	/// This keeps audio that hovers around the threshold from opening and
+	/// closing the gate over and over.
+	#[must_use = "This method consumes self and returns a modified GateBuilder, so the return value should be used"]
+	pub fn hysteresis(self, hysteresis: impl Into<Value<f64>>) -> Self {
+		Self {
+			hysteresis: hysteresis.into(),
+			..self
+		}
+	}
+
+	/// Sets how much the audio is turned down while the gate is closed.
+	/// Defaults to `100.0`.
+	///
+	/// A ratio of `2.0` (or 1 to 2) means audio 3dB below the threshold
+	/// will be turned down to 6dB below the threshold. High ratios close
+	/// the gate fully. Ratios below `1.0` are treated as `1.0`.
+	#[must_use = "This method consumes self and returns a modified GateBuilder, so the return value should be used"]
+	pub fn ratio(self, ratio: impl Into<Value<f64>>) -> Self {
+		Self {
+			ratio: ratio.into(),
+			..self
+		}
+	}
+
+	/// Sets the most the gate turns down the audio while it's closed.
+	/// Defaults to [`Decibels::SILENCE`], which lets the gate silence the
+	/// audio completely.
+	#[must_use = "This method consumes self and returns a modified GateBuilder, so the return value should be used"]
+	pub fn range(self, range: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			range: range.into(),
+			..self
+		}
+	}
+
+	/// Sets how much time it takes for the gate to open once the input
+	/// volume exceeds the threshold. Defaults to 1 millisecond.
+	#[must_use = "This method consumes self and returns a modified GateBuilder, so the return value should be used"]
+	pub fn attack_duration(self, attack_duration: impl Into<Value<Duration>>) -> Self {
+		Self {
+			attack_duration: attack_duration.into(),
+			..self
+		}
+	}
+
+	/// Sets how long the gate stays open after the input volume falls below
+	/// the closing threshold. Defaults to 50 milliseconds.
+	#[must_use = "This method consumes self and returns a modified GateBuilder, so the return value should be used"]
+	pub fn hold_duration(self, hold_duration: impl Into<Value<Duration>>) -> Self {
+		Self {
+			hold_duration: hold_duration.into(),
+			..self
+		}
+	}
+
+	/// Sets how much time it takes for the gate to close once it's done
+	/// holding open. Defaults to 100 milliseconds.
+	#[must_use = "This method consumes self and returns a modified GateBuilder, so the return value should be used"]
+	pub fn release_duration(self, release_duration: impl Into<Value<Duration>>) -> Self {
+		Self {
+			release_duration: release_duration.into(),
+			..self
+		}
+	}
+
+	/// Builds the gate, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, GateHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let gain_reduction = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+		let open = Arc::new(AtomicBool::new(false));
+		let (effect, set_bypassed) = Bypassable::new(
+			Gate::new(self, command_readers, gain_reduction.clone(), open.clone()),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			GateHandle {
+				command_writers,
+				set_bypassed,
+				template,
+				gain_reduction,
+				open,
+			},
+		)
+	}
+}
+
+impl Default for GateBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl EffectBuilder for GateBuilder {
+	type Handle = GateHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for GateBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
@@ -0,0 +1,91 @@
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Tween,
+};
+
+use super::{CommandWriters, GateBuilder};
+
+/// Controls a gate.
+#[derive(Debug)]
+pub struct GateHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<GateBuilder>>,
+	pub(super) gain_reduction: Arc<AtomicU32>,
+	pub(super) open: Arc<AtomicBool>,
+}
+
+impl GateHandle {
+	/// Bypasses or stops bypassing the gate, crossfading between its input
+	/// and output with the given tween.
+	///
+	/// Once it's fully bypassed, the gate stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	/// Returns `true` if the gate is open, meaning the audio it follows is
+	/// above the threshold or the gate is holding open.
+	///
+	/// The gain takes the attack and release durations to catch up with
+	/// the gate opening and closing, so this can change before the volume
+	/// of the audio does. It's updated every time the audio thread
+	/// processes a batch of audio, which makes it useful for indicator
+	/// lights.
+	#[must_use]
+	pub fn is_open(&self) -> bool {
+		self.open.load(Ordering::Relaxed)
+	}
+
+	/// Returns how much the gate is changing the volume of the audio (in
+	/// decibels).
+	///
+	/// This is negative while the gate is turning the audio down, so a
+	/// closed gate with a range of -12 dB returns `Decibels(-12.0)`. It's
+	/// updated every time the audio thread processes a batch of audio,
+	/// which makes it useful for gain reduction meters.
+	#[must_use]
+	pub fn gain_reduction(&self) -> Decibels {
+		Decibels(f32::from_bits(self.gain_reduction.load(Ordering::Relaxed)))
+	}
+
+	template_param_setters! {
+		/// Sets the volume above which the gate opens (in decibels).
+		threshold: f64 => threshold,
+
+		/// Sets how far below the threshold the volume has to fall for the gate
+		/// to close (in decibels).
+		hysteresis: f64 => hysteresis,
+
+		/// Sets how much the audio is turned down while the gate is closed.
+		///
+		/// A ratio of `2.0` (or 1 to 2) means audio 3dB below the threshold
+		/// will be turned down to 6dB below the threshold.
+		ratio: f64 => ratio,
+
+		/// Sets the most the gate turns down the audio while it's closed.
+		range: Decibels => range,
+
+		/// Sets how much time it takes for the gate to open once the input
+		/// volume exceeds the threshold.
+		attack_duration: Duration => attack_duration,
+
+		/// Sets how long the gate stays open after the input volume falls below
+		/// the closing threshold.
+		hold_duration: Duration => hold_duration,
+
+		/// Sets how much time it takes for the gate to close once it's done
+		/// holding open.
+		release_duration: Duration => release_duration,
+	}
+}
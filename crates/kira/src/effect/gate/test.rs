@@ -0,0 +1,147 @@
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	effect::{test_helpers::process, Effect},
+	frame::Frame,
+	Decibels,
+};
+
+use super::{command_writers_and_readers, Gate, GateBuilder};
+
+struct TestGate {
+	gate: Gate,
+	gain_reduction: Arc<AtomicU32>,
+	open: Arc<AtomicBool>,
+}
+
+impl TestGate {
+	fn new(builder: GateBuilder) -> Self {
+		let gain_reduction = Arc::new(AtomicU32::new(0));
+		let open = Arc::new(AtomicBool::new(false));
+		Self {
+			gate: Gate::new(
+				builder,
+				command_writers_and_readers().1,
+				gain_reduction.clone(),
+				open.clone(),
+			),
+			gain_reduction,
+			open,
+		}
+	}
+
+	/// Processes `num_frames` frames with the given volume and returns the
+	/// output.
+	fn process(&mut self, volume: Decibels, num_frames: usize) -> Vec<Frame> {
+		process(
+			&mut self.gate,
+			&vec![Frame::from_mono(volume.as_amplitude()); num_frames],
+		)
+	}
+
+	fn gain_reduction(&self) -> Decibels {
+		Decibels(f32::from_bits(self.gain_reduction.load(Ordering::Relaxed)))
+	}
+
+	fn is_open(&self) -> bool {
+		self.open.load(Ordering::Relaxed)
+	}
+}
+
+/// Tests that the gate silences audio below the threshold and lets audio
+/// above it through unchanged.
+#[test]
+fn silences_quiet_audio() {
+	let mut gate = TestGate::new(GateBuilder::new().threshold(-40.0));
+	let quiet = gate.process(Decibels(-50.0), 4800);
+	assert!(!gate.is_open());
+	assert!(quiet.iter().all(|frame| frame.left == 0.0));
+	let loud = gate.process(Decibels(-20.0), 4800);
+	assert!(gate.is_open());
+	assert!(gate.gain_reduction().0 > -1.0e-3);
+	assert!((loud[4799].left - Decibels(-20.0).as_amplitude()).abs() < 1.0e-6);
+}
+
+/// Tests that the gate stays open for the hold duration after the audio
+/// gets quiet, then closes over the release duration.
+#[test]
+fn holds_open_before_releasing() {
+	let mut gate = TestGate::new(
+		GateBuilder::new()
+			.threshold(-40.0)
+			.hold_duration(Duration::from_millis(100))
+			.release_duration(Duration::from_millis(10)),
+	);
+	gate.process(Decibels(-20.0), 4800);
+	// the level the gate follows takes a moment to fall, so the hold starts
+	// a little after the audio gets quiet
+	gate.process(Decibels(-60.0), 4800);
+	assert!(gate.is_open());
+	assert!(gate.gain_reduction().0 > -1.0e-3);
+	gate.process(Decibels(-60.0), 4800);
+	assert!(!gate.is_open());
+	assert!(gate.gain_reduction().0 < -59.0);
+}
+
+/// Tests that hysteresis keeps the gate from closing when the audio hovers
+/// around the threshold.
+#[test]
+fn hysteresis_prevents_chatter() {
+	let count_closes = |hysteresis: f64| {
+		let mut gate = TestGate::new(
+			GateBuilder::new()
+				.threshold(-40.0)
+				.hysteresis(hysteresis)
+				.hold_duration(Duration::ZERO),
+		);
+		let mut closes = 0;
+		for i in 0..10 {
+			let volume = if i % 2 == 0 { -38.0 } else { -42.0 };
+			let was_open = gate.is_open();
+			gate.process(Decibels(volume), 4800);
+			if was_open && !gate.is_open() {
+				closes += 1;
+			}
+		}
+		closes
+	};
+	assert_eq!(count_closes(0.0), 5);
+	assert_eq!(count_closes(6.0), 0);
+}
+
+/// Tests that a low ratio and a small range turn quiet audio down instead
+/// of silencing it.
+#[test]
+fn expands_quiet_audio() {
+	let mut gate = TestGate::new(
+		GateBuilder::new()
+			.threshold(-40.0)
+			.ratio(2.0)
+			.range(Decibels(-12.0)),
+	);
+	// 5 dB below the threshold is turned down by another 5 dB
+	gate.process(Decibels(-45.0), 48_000);
+	assert!((gate.gain_reduction().0 + 5.0).abs() < 1.0e-3);
+	// 20 dB below the threshold is only turned down by the range
+	gate.process(Decibels(-60.0), 48_000);
+	assert!((gate.gain_reduction().0 + 12.0).abs() < 1.0e-3);
+}
+
+/// Tests that the gate becomes idle once it's finished closing.
+#[test]
+fn becomes_idle_after_closing() {
+	let mut gate = TestGate::new(GateBuilder::new());
+	assert!(gate.gate.is_idle());
+	gate.process(Decibels(-20.0), 128);
+	assert!(!gate.gate.is_idle());
+	gate.process(Decibels::SILENCE, 4800);
+	assert!(!gate.gate.is_idle());
+	gate.process(Decibels::SILENCE, 96_000);
+	assert!(gate.gate.is_idle());
+}
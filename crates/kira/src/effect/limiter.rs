@@ -0,0 +1,275 @@
+//! Keeps audio from getting louder than a threshold.
+//!
+//! Unlike a [compressor](super::compressor), which turns down audio gradually
+//! after it gets loud, a limiter looks ahead at its input and turns the audio
+//! down before it gets louder than the threshold, so its output never does.
+//! Looking ahead delays the audio by the lookahead duration, which is
+//! reported as the effect's [latency](super::Effect::latency_frames) so
+//! tracks can [compensate for it](crate::track::TrackBuilder::compensate_latency).
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::{
+	collections::VecDeque,
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::Frame,
+	info::Info,
+	Decibels, Parameter, Tween, Value,
+};
+
+use super::{is_silent, BuilderTemplate, Effect, EffectTemplate};
+
+/// How close to `1.0` the limiter's gain has to be for the limiter to be
+/// idle.
+const IDLE_GAIN: f32 = 0.9999;
+
+struct Limiter {
+	command_readers: CommandReaders,
+	threshold: Parameter<Decibels>,
+	lookahead: Duration,
+	release_duration: Parameter<Duration>,
+	true_peak: bool,
+	/// How much of the limiter's gain is applied. This is `1.0` when the
+	/// limiter isn't bypassed and `0.0` when it is. The audio is delayed
+	/// either way, so bypassing the limiter doesn't change its latency.
+	wet: Parameter<f32>,
+	/// How many frames the limiter looks ahead.
+	lookahead_frames: usize,
+	/// The delayed input, along with the threshold (as an amplitude) each
+	/// frame was limited to.
+	delay_line: Vec<(Frame, f32)>,
+	delay_line_index: usize,
+	/// The lowest gain needed over the lookahead window, stored as
+	/// `(frame number, gain)` pairs in increasing order of gain.
+	gain_hold: VecDeque<(u64, f32)>,
+	/// The held gain after the release is applied.
+	released_gain: f32,
+	/// The most recent released gains, which are averaged to smooth out
+	/// the gain changes.
+	smoothing: Vec<f32>,
+	smoothing_index: usize,
+	smoothing_sum: f64,
+	/// The last three input frames, oldest first, which are used to find
+	/// peaks between samples.
+	history: [Frame; 3],
+	frame_number: u64,
+	/// How many frames have passed since the limiter received audio that
+	/// wasn't silent.
+	quiet_frames: usize,
+	/// The gain applied to the most recently output frame (in dB), stored
+	/// as the bits of an `f32`.
+	gain_reduction: Arc<AtomicU32>,
+	template: Arc<BuilderTemplate<LimiterBuilder>>,
+}
+
+impl Limiter {
+	#[must_use]
+	fn new(
+		builder: LimiterBuilder,
+		command_readers: CommandReaders,
+		gain_reduction: Arc<AtomicU32>,
+		template: Arc<BuilderTemplate<LimiterBuilder>>,
+		bypassed: bool,
+	) -> Self {
+		let wet = if bypassed { 0.0 } else { 1.0 };
+		Self {
+			command_readers,
+			threshold: Parameter::new(builder.threshold, LimiterBuilder::DEFAULT_THRESHOLD),
+			lookahead: builder
+				.lookahead
+				.clamp(LimiterBuilder::MIN_LOOKAHEAD, LimiterBuilder::MAX_LOOKAHEAD),
+			release_duration: Parameter::new(
+				builder.release_duration,
+				LimiterBuilder::DEFAULT_RELEASE_DURATION,
+			),
+			true_peak: builder.true_peak,
+			wet: Parameter::new(Value::Fixed(wet), wet),
+			lookahead_frames: 0,
+			delay_line: vec![],
+			delay_line_index: 0,
+			gain_hold: VecDeque::new(),
+			released_gain: 1.0,
+			smoothing: vec![],
+			smoothing_index: 0,
+			smoothing_sum: 0.0,
+			history: [Frame::ZERO; 3],
+			frame_number: 0,
+			quiet_frames: 0,
+			gain_reduction,
+			template,
+		}
+	}
+
+	/// Allocates the buffers the limiter needs to look ahead at the given
+	/// sample rate and clears the audio it's holding onto.
+	fn allocate(&mut self, sample_rate: u32) {
+		self.lookahead_frames =
+			((self.lookahead.as_secs_f64() * sample_rate as f64).round() as usize).max(1);
+		// the gain for each frame is the lowest gain needed by that frame
+		// and the `lookahead_frames` frames after it
+		let window = self.lookahead_frames + 1;
+		self.delay_line = vec![(Frame::ZERO, 1.0); window];
+		self.delay_line_index = 0;
+		self.gain_hold = VecDeque::with_capacity(window);
+		self.released_gain = 1.0;
+		self.smoothing = vec![1.0; window];
+		self.smoothing_index = 0;
+		self.smoothing_sum = window as f64;
+		self.history = [Frame::ZERO; 3];
+		self.quiet_frames = window;
+	}
+
+	/// Returns the highest peak of the input frame, including the peak
+	/// between the previous two frames if true peak detection is enabled.
+	#[must_use]
+	fn peak(&mut self, frame: Frame) -> f32 {
+		let mut peak = frame.left.abs().max(frame.right.abs());
+		if self.true_peak {
+			// estimates the sample halfway between the previous two frames
+			// as if the audio was upsampled by 2x
+			let [a, b, c] = self.history;
+			let halfway = (b + c) * (9.0 / 16.0) - (a + frame) * (1.0 / 16.0);
+			peak = peak.max(halfway.left.abs()).max(halfway.right.abs());
+			self.history = [b, c, frame];
+		}
+		peak
+	}
+
+	/// Returns the gain to apply to the frame leaving the delay line.
+	#[must_use]
+	fn next_gain(&mut self, needed_gain: f32, release_speed: f32) -> f32 {
+		let window = self.lookahead_frames as u64 + 1;
+		// expired gains are removed before the new one is added so the
+		// queue never holds more than `window` gains and doesn't reallocate
+		while self
+			.gain_hold
+			.front()
+			.is_some_and(|(frame_number, _)| frame_number + window <= self.frame_number)
+		{
+			self.gain_hold.pop_front();
+		}
+		while self
+			.gain_hold
+			.back()
+			.is_some_and(|(_, gain)| *gain >= needed_gain)
+		{
+			self.gain_hold.pop_back();
+		}
+		self.gain_hold.push_back((self.frame_number, needed_gain));
+		let held_gain = self.gain_hold.front().map_or(1.0, |(_, gain)| *gain);
+		self.released_gain = if held_gain < self.released_gain {
+			held_gain
+		} else {
+			self.released_gain + (held_gain - self.released_gain) * release_speed
+		};
+		// every gain being averaged is at most the gain needed by the frame
+		// leaving the delay line, so the average is too
+		self.smoothing_sum +=
+			self.released_gain as f64 - self.smoothing[self.smoothing_index] as f64;
+		self.smoothing[self.smoothing_index] = self.released_gain;
+		self.smoothing_index = (self.smoothing_index + 1) % self.smoothing.len();
+		(self.smoothing_sum / self.smoothing.len() as f64).min(1.0) as f32
+	}
+}
+
+impl Effect for Limiter {
+	fn init(&mut self, sample_rate: u32, _internal_buffer_size: usize) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, threshold, release_duration);
+		if let Some((bypassed, tween)) = self.command_readers.set_bypassed.read() {
+			self.wet
+				.set(Value::Fixed(if bypassed { 0.0 } else { 1.0 }), tween);
+		}
+	}
+
+	fn is_idle(&self) -> bool {
+		self.quiet_frames >= self.delay_line.len()
+			&& self.smoothing_sum / self.smoothing.len() as f64 >= IDLE_GAIN as f64
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.threshold.update(dt * input.len() as f64, info);
+		self.release_duration.update(dt * input.len() as f64, info);
+		self.wet.update(dt * input.len() as f64, info);
+		let release_speed =
+			1.0 - (-dt / self.release_duration.value().as_secs_f64().max(dt)).exp() as f32;
+
+		let num_frames = input.len();
+		let mut gain = 1.0;
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let threshold = self
+				.threshold
+				.interpolated_value(time_in_chunk)
+				.as_amplitude();
+			let wet = self.wet.interpolated_value(time_in_chunk).clamp(0.0, 1.0);
+
+			let peak = self.peak(*frame);
+			let needed_gain = if peak > threshold {
+				threshold / peak
+			} else {
+				1.0
+			};
+			if is_silent(*frame) {
+				self.quiet_frames = self.quiet_frames.saturating_add(1);
+			} else {
+				self.quiet_frames = 0;
+			}
+			let limiter_gain = self.next_gain(needed_gain, release_speed);
+			self.frame_number += 1;
+
+			self.delay_line[self.delay_line_index] = (*frame, threshold);
+			self.delay_line_index = (self.delay_line_index + 1) % self.delay_line.len();
+			let (delayed, delayed_threshold) = self.delay_line[self.delay_line_index];
+
+			gain = 1.0 + (limiter_gain - 1.0) * wet;
+			let mut output = delayed * gain;
+			if wet == 1.0 {
+				// catches rounding errors in the gain so the output is never
+				// louder than the threshold
+				output.left = output.left.clamp(-delayed_threshold, delayed_threshold);
+				output.right = output.right.clamp(-delayed_threshold, delayed_threshold);
+			}
+			*frame = output;
+		}
+		self.gain_reduction
+			.store((20.0 * gain.log10()).to_bits(), Ordering::Relaxed);
+	}
+
+	fn template(&self) -> Option<Arc<dyn EffectTemplate>> {
+		Some(self.template.clone())
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.lookahead_frames
+	}
+}
+
+command_writers_and_readers! {
+	set_threshold: ValueChangeCommand<Decibels>,
+	set_release_duration: ValueChangeCommand<Duration>,
+	set_bypassed: (bool, Tween),
+}
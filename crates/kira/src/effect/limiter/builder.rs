@@ -0,0 +1,135 @@
+use std::{
+	sync::{atomic::AtomicU32, Arc},
+	time::Duration,
+};
+
+use crate::{
+	effect::{BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Decibels, Value,
+};
+
+use super::{command_writers_and_readers, Limiter, LimiterHandle};
+
+/// Configures a limiter.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LimiterBuilder {
+	/// The volume the limiter's output never goes above.
+	pub threshold: Value<Decibels>,
+	/// How far ahead the limiter looks for loud audio, which is also how
+	/// much the limiter delays its input.
+	///
+	/// Longer lookaheads turn the audio down more smoothly before loud
+	/// peaks. This is kept between 1 and 10 milliseconds.
+	pub lookahead: Duration,
+	/// How long it takes the limiter to stop turning down the audio after
+	/// it gets quieter.
+	pub release_duration: Value<Duration>,
+	/// Whether the limiter also looks for peaks between samples, which
+	/// can be louder than the samples themselves once the audio is
+	/// converted to analog.
+	///
+	/// Peaks between samples are estimated as if the audio was upsampled
+	/// by 2x, which keeps most of them under the threshold. Only the
+	/// samples themselves are guaranteed to stay under the threshold.
+	pub true_peak: bool,
+}
+
+impl LimiterBuilder {
+	pub(crate) const DEFAULT_THRESHOLD: Decibels = Decibels(-1.0);
+	pub(crate) const DEFAULT_LOOKAHEAD: Duration = Duration::from_millis(5);
+	pub(crate) const DEFAULT_RELEASE_DURATION: Duration = Duration::from_millis(100);
+	pub(crate) const MIN_LOOKAHEAD: Duration = Duration::from_millis(1);
+	pub(crate) const MAX_LOOKAHEAD: Duration = Duration::from_millis(10);
+
+	/// Creates a new [`LimiterBuilder`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			threshold: Value::Fixed(Self::DEFAULT_THRESHOLD),
+			lookahead: Self::DEFAULT_LOOKAHEAD,
+			release_duration: Value::Fixed(Self::DEFAULT_RELEASE_DURATION),
+			true_peak: false,
+		}
+	}
+
+	/// Sets the volume the limiter's output never goes above. Defaults to
+	/// -1 dB.
+	#[must_use = "This method consumes self and returns a modified LimiterBuilder, so the return value should be used"]
+	pub fn threshold(self, threshold: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			threshold: threshold.into(),
+			..self
+		}
+	}
+
+	/// Sets how far ahead the limiter looks for loud audio, which is also
+	/// how much the limiter delays its input. Defaults to 5 milliseconds.
+	///
+	/// Longer lookaheads turn the audio down more smoothly before loud
+	/// peaks. This is kept between 1 and 10 milliseconds.
+	#[must_use = "This method consumes self and returns a modified LimiterBuilder, so the return value should be used"]
+	pub fn lookahead(self, lookahead: Duration) -> Self {
+		Self { lookahead, ..self }
+	}
+
+	/// Sets how long it takes the limiter to stop turning down the audio
+	/// after it gets quieter. Defaults to 100 milliseconds.
+	#[must_use = "This method consumes self and returns a modified LimiterBuilder, so the return value should be used"]
+	pub fn release_duration(self, release_duration: impl Into<Value<Duration>>) -> Self {
+		Self {
+			release_duration: release_duration.into(),
+			..self
+		}
+	}
+
+	/// Sets whether the limiter also looks for peaks between samples.
+	/// Defaults to `false`.
+	///
+	/// See [`LimiterBuilder::true_peak`](Self#structfield.true_peak) for
+	/// more information.
+	#[must_use = "This method consumes self and returns a modified LimiterBuilder, so the return value should be used"]
+	pub fn true_peak(self, true_peak: bool) -> Self {
+		Self { true_peak, ..self }
+	}
+
+	/// Builds the limiter, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, LimiterHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let gain_reduction = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+		(
+			Box::new(Limiter::new(
+				self,
+				command_readers,
+				gain_reduction.clone(),
+				template.clone(),
+				bypassed,
+			)),
+			LimiterHandle {
+				command_writers,
+				template,
+				gain_reduction,
+			},
+		)
+	}
+}
+
+impl Default for LimiterBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl EffectBuilder for LimiterBuilder {
+	type Handle = LimiterHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for LimiterBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
@@ -0,0 +1,55 @@
+use std::{
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Tween,
+};
+
+use super::{CommandWriters, LimiterBuilder};
+
+/// Controls a limiter.
+#[derive(Debug)]
+pub struct LimiterHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) template: Arc<BuilderTemplate<LimiterBuilder>>,
+	pub(super) gain_reduction: Arc<AtomicU32>,
+}
+
+impl LimiterHandle {
+	/// Bypasses or stops bypassing the limiter, fading its gain reduction
+	/// in or out with the given tween.
+	///
+	/// The limiter keeps delaying the audio while it's bypassed, so
+	/// bypassing it doesn't change the latency of its track.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.command_writers.set_bypassed.write((bypassed, tween))
+	}
+
+	/// Returns how much the limiter is changing the volume of the audio
+	/// (in decibels).
+	///
+	/// This is negative while the limiter is turning the audio down, so a
+	/// limiter that's reducing the volume by 3 dB returns `Decibels(-3.0)`.
+	/// It's updated every time the audio thread processes a batch of
+	/// audio, which makes it useful for gain reduction meters.
+	#[must_use]
+	pub fn gain_reduction(&self) -> Decibels {
+		Decibels(f32::from_bits(self.gain_reduction.load(Ordering::Relaxed)))
+	}
+
+	template_param_setters! {
+		/// Sets the volume the limiter's output never goes above.
+		threshold: Decibels => threshold,
+
+		/// Sets how long it takes the limiter to stop turning down the audio
+		/// after it gets quieter.
+		release_duration: Duration => release_duration,
+	}
+}
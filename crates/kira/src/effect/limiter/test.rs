@@ -0,0 +1,159 @@
+use std::{
+	sync::atomic::{AtomicU32, Ordering},
+	sync::Arc,
+	time::Duration,
+};
+
+use crate::{
+	effect::{
+		test_helpers::{init, process, CHUNK_SIZE},
+		BuilderTemplate, Effect,
+	},
+	frame::Frame,
+	Decibels,
+};
+
+use super::{command_writers_and_readers, Limiter, LimiterBuilder};
+
+fn new_limiter(builder: LimiterBuilder, bypassed: bool) -> (Limiter, Arc<AtomicU32>) {
+	let gain_reduction = Arc::new(AtomicU32::new(0));
+	let limiter = init(Limiter::new(
+		builder,
+		command_writers_and_readers().1,
+		gain_reduction.clone(),
+		BuilderTemplate::new(builder, bypassed),
+		bypassed,
+	));
+	(limiter, gain_reduction)
+}
+
+fn read(gain_reduction: &AtomicU32) -> Decibels {
+	Decibels(f32::from_bits(gain_reduction.load(Ordering::Relaxed)))
+}
+
+/// Processes `num_chunks` chunks of a signal and returns the output.
+fn process_chunks(
+	limiter: &mut Limiter,
+	num_chunks: usize,
+	signal: impl Fn(usize) -> Frame,
+) -> Vec<Frame> {
+	let input: Vec<Frame> = (0..num_chunks * CHUNK_SIZE).map(signal).collect();
+	process(limiter, &input)
+}
+
+/// Tests that the limiter delays its input by the lookahead duration and
+/// reports that delay as its latency.
+#[test]
+fn delays_input_by_lookahead() {
+	let (mut limiter, _) = new_limiter(
+		LimiterBuilder::new().lookahead(Duration::from_millis(2)),
+		false,
+	);
+	assert_eq!(limiter.latency_frames(), 96);
+	let output = process_chunks(&mut limiter, 2, |i| {
+		if i == 0 {
+			Frame::new(0.5, -0.25)
+		} else {
+			Frame::ZERO
+		}
+	});
+	// audio below the threshold is only delayed
+	assert_eq!(output[96], Frame::new(0.5, -0.25));
+	assert!(output
+		.iter()
+		.enumerate()
+		.all(|(i, frame)| i == 96 || *frame == Frame::ZERO));
+}
+
+/// Tests that the lookahead is kept between 1 and 10 milliseconds.
+#[test]
+fn clamps_lookahead() {
+	let (limiter, _) = new_limiter(LimiterBuilder::new().lookahead(Duration::ZERO), false);
+	assert_eq!(limiter.latency_frames(), 48);
+	let (limiter, _) = new_limiter(
+		LimiterBuilder::new().lookahead(Duration::from_secs(1)),
+		false,
+	);
+	assert_eq!(limiter.latency_frames(), 480);
+}
+
+/// Tests that the output never goes above the threshold, even when the
+/// input jumps from silence to much louder than the threshold.
+#[test]
+fn output_never_exceeds_threshold() {
+	let threshold = Decibels(-6.0);
+	let (mut limiter, gain_reduction) =
+		new_limiter(LimiterBuilder::new().threshold(threshold), false);
+	let output = process_chunks(&mut limiter, 50, |i| {
+		let loudness = if (i / 1000) % 3 == 0 { 3.0 } else { 0.2 };
+		Frame::new(
+			loudness * (i as f32 * 0.02).sin() + 0.3 * (i as f32 * 0.31).sin(),
+			loudness * (i as f32 * 0.013).cos(),
+		)
+	});
+	let peak = output
+		.iter()
+		.map(|frame| frame.left.abs().max(frame.right.abs()))
+		.fold(0.0, f32::max);
+	assert!(peak <= threshold.as_amplitude(), "{}", peak);
+	assert!(peak > threshold.as_amplitude() * 0.9, "{}", peak);
+	assert!(read(&gain_reduction).0 < 0.0);
+}
+
+/// Tests that the gain reduction recovers after the input gets quieter.
+#[test]
+fn releases_after_input_gets_quieter() {
+	let (mut limiter, gain_reduction) = new_limiter(
+		LimiterBuilder::new().release_duration(Duration::from_millis(10)),
+		false,
+	);
+	process_chunks(&mut limiter, 10, |_| Frame::from_mono(2.0));
+	assert!(read(&gain_reduction).0 < -6.0);
+	process_chunks(&mut limiter, 100, |_| Frame::from_mono(0.1));
+	assert!(read(&gain_reduction).0 > -0.01);
+}
+
+/// Tests that the queue of held gains doesn't grow past the capacity
+/// it's allocated with, even when every gain in the window is kept.
+#[test]
+fn gain_hold_does_not_reallocate() {
+	let (mut limiter, _) = new_limiter(LimiterBuilder::new(), false);
+	let capacity = limiter.gain_hold.capacity();
+	// the input keeps getting quieter, so each frame needs less gain
+	// reduction than the frames before it
+	process_chunks(&mut limiter, 10, |i| {
+		Frame::from_mono(4.0 - i as f32 * 0.001)
+	});
+	assert_eq!(limiter.gain_hold.len(), limiter.lookahead_frames + 1);
+	assert_eq!(limiter.gain_hold.capacity(), capacity);
+}
+
+/// Tests that true peak detection turns down peaks between samples that
+/// are louder than the threshold.
+#[test]
+fn true_peak_detects_peaks_between_samples() {
+	// a sine wave at a quarter of the sample rate whose samples are all
+	// about 0.707, even though it peaks at 1.0 between them
+	let signal = |i: usize| {
+		Frame::from_mono(
+			(std::f32::consts::FRAC_PI_4 + i as f32 * std::f32::consts::FRAC_PI_2).sin(),
+		)
+	};
+	let builder = LimiterBuilder::new().threshold(Decibels(-2.0));
+	let (mut limiter, gain_reduction) = new_limiter(builder, false);
+	process_chunks(&mut limiter, 10, signal);
+	assert_eq!(read(&gain_reduction), Decibels(0.0));
+	let (mut limiter, gain_reduction) = new_limiter(builder.true_peak(true), false);
+	process_chunks(&mut limiter, 10, signal);
+	assert!(read(&gain_reduction).0 < 0.0);
+}
+
+/// Tests that a bypassed limiter still delays its input but doesn't turn
+/// it down.
+#[test]
+fn bypassed_limiter_only_delays_input() {
+	let (mut limiter, _) = new_limiter(LimiterBuilder::new(), true);
+	let output = process_chunks(&mut limiter, 2, |i| Frame::from_mono(i as f32));
+	assert_eq!(output[240], Frame::from_mono(0.0));
+	assert_eq!(output[250], Frame::from_mono(10.0));
+}
@@ -14,7 +14,7 @@ use crate::{
 	Panning, Parameter,
 };
 
-use super::Effect;
+use super::{bypass::BypassableEffect, Effect};
 
 struct PanningControl {
 	command_readers: CommandReaders,
@@ -36,6 +36,11 @@ impl Effect for PanningControl {
 		read_commands_into_parameters!(self, panning);
 	}
 
+	// silence stays silent
+	fn is_idle(&self) -> bool {
+		true
+	}
+
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
 		self.panning.update(dt * input.len() as f64, info);
 		let num_frames = input.len();
@@ -46,6 +51,8 @@ impl Effect for PanningControl {
 	}
 }
 
+impl BypassableEffect for PanningControl {}
+
 command_writers_and_readers! {
 	set_panning: ValueChangeCommand<Panning>,
 }
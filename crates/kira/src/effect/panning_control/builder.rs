@@ -1,4 +1,7 @@
-use crate::{effect::EffectBuilder, Panning, Value};
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Panning, Value,
+};
 
 use super::{command_writers_and_readers, PanningControl, PanningControlHandle};
 
@@ -12,14 +15,37 @@ impl Default for PanningControlBuilder {
 	}
 }
 
-impl EffectBuilder for PanningControlBuilder {
-	type Handle = PanningControlHandle;
-
-	fn build(self) -> (Box<dyn crate::effect::Effect>, Self::Handle) {
+impl PanningControlBuilder {
+	/// Builds the panning control, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, PanningControlHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
 		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			PanningControl::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
 		(
-			Box::new(PanningControl::new(self, command_readers)),
-			PanningControlHandle { command_writers },
+			Box::new(effect),
+			PanningControlHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
 		)
 	}
 }
+
+impl EffectBuilder for PanningControlBuilder {
+	type Handle = PanningControlHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for PanningControlBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
@@ -1,16 +1,33 @@
-use crate::{command::handle_param_setters, Panning};
+use std::sync::Arc;
 
-use super::CommandWriters;
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Panning, Tween,
+};
+
+use super::{CommandWriters, PanningControlBuilder};
 
 /// Controls a panning control effect.
 #[derive(Debug)]
 pub struct PanningControlHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<PanningControlBuilder>>,
 }
 
 impl PanningControlHandle {
-	handle_param_setters! {
+	/// Bypasses or stops bypassing the panning control, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the panning control stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
 		/// Sets the panning adjustment to apply to input audio.
-		panning: Panning,
+		panning: Panning => 0,
 	}
 }
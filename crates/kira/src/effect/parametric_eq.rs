@@ -0,0 +1,210 @@
+//! Adjusts the volume of several frequency ranges of a sound.
+//!
+//! A parametric EQ processes a number of bands, each of which works like an
+//! [EQ filter](super::eq_filter), and all of them are controlled from a
+//! single handle. The number of bands is set when the EQ is built, and bands
+//! that are disabled are skipped entirely.
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::{error::Error, fmt::Display, time::Duration};
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, frame::Frame, info::Info, Decibels,
+	Parameter, Tween, Value,
+};
+
+use super::{
+	bypass::BypassableEffect,
+	eq_filter::{Coefficients, EqFilterKind},
+	is_silent, Effect,
+};
+
+/// How long it takes a band to fade in when it's enabled and fade out when
+/// it's disabled.
+const ENABLE_FADE_DURATION: Duration = Duration::from_millis(10);
+
+/// An error that's returned when trying to change a band of a parametric
+/// EQ that doesn't have that many bands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonexistentBand;
+
+impl Display for NonexistentBand {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("The parametric EQ does not have a band with that index")
+	}
+}
+
+impl Error for NonexistentBand {}
+
+struct ParametricEq {
+	bands: Vec<Band>,
+}
+
+impl ParametricEq {
+	#[must_use]
+	fn new(builder: &ParametricEqBuilder, command_readers: Vec<CommandReaders>) -> Self {
+		Self {
+			bands: builder
+				.bands
+				.iter()
+				.zip(command_readers)
+				.map(|(settings, command_readers)| Band::new(*settings, command_readers))
+				.collect(),
+		}
+	}
+}
+
+impl Effect for ParametricEq {
+	fn on_start_processing(&mut self) {
+		for band in &mut self.bands {
+			band.read_commands();
+		}
+	}
+
+	fn is_idle(&self) -> bool {
+		self.bands.iter().all(Band::is_idle)
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		for band in &mut self.bands {
+			band.process(input, dt, info);
+		}
+	}
+}
+
+impl BypassableEffect for ParametricEq {
+	fn clear_tail(&mut self) {
+		for band in &mut self.bands {
+			band.clear();
+		}
+	}
+}
+
+/// One of the frequency ranges a parametric EQ adjusts.
+struct Band {
+	command_readers: CommandReaders,
+	kind: EqFilterKind,
+	frequency: Parameter,
+	gain: Parameter<Decibels>,
+	q: Parameter,
+	/// How much of the band's output is heard. This is `1.0` when the band
+	/// is enabled and `0.0` when it's disabled.
+	enabled: Parameter<f32>,
+	ic1eq: Frame,
+	ic2eq: Frame,
+}
+
+impl Band {
+	#[must_use]
+	fn new(settings: BandSettings, command_readers: CommandReaders) -> Self {
+		let enabled = if settings.enabled { 1.0 } else { 0.0 };
+		Self {
+			command_readers,
+			kind: settings.kind,
+			frequency: Parameter::new(settings.frequency, BandSettings::DEFAULT_FREQUENCY),
+			gain: Parameter::new(settings.gain, Decibels::IDENTITY),
+			q: Parameter::new(settings.q, BandSettings::DEFAULT_Q),
+			enabled: Parameter::new(Value::Fixed(enabled), enabled),
+			ic1eq: Frame::ZERO,
+			ic2eq: Frame::ZERO,
+		}
+	}
+
+	fn read_commands(&mut self) {
+		if let Some(kind) = self.command_readers.set_kind.read() {
+			self.kind = kind;
+		}
+		self.frequency
+			.read_command(&mut self.command_readers.set_frequency);
+		self.gain.read_command(&mut self.command_readers.set_gain);
+		self.q.read_command(&mut self.command_readers.set_q);
+		if let Some(enabled) = self.command_readers.set_enabled.read() {
+			self.enabled.set(
+				Value::Fixed(if enabled { 1.0 } else { 0.0 }),
+				Tween {
+					duration: ENABLE_FADE_DURATION,
+					..Default::default()
+				},
+			);
+		}
+	}
+
+	#[must_use]
+	fn is_idle(&self) -> bool {
+		is_silent(self.ic1eq) && is_silent(self.ic2eq)
+	}
+
+	fn clear(&mut self) {
+		self.ic1eq = Frame::ZERO;
+		self.ic2eq = Frame::ZERO;
+	}
+
+	#[allow(clippy::float_cmp)]
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		let chunk_duration = dt * input.len() as f64;
+		self.frequency.update(chunk_duration, info);
+		self.gain.update(chunk_duration, info);
+		self.q.update(chunk_duration, info);
+		self.enabled.update(chunk_duration, info);
+		// disabled bands are skipped entirely
+		if self.enabled.previous_value() == 0.0 && self.enabled.value() == 0.0 {
+			self.clear();
+			return;
+		}
+		// the coefficients only need to be calculated once if none of the
+		// settings are changing
+		let fixed_coefficients = (self.frequency.previous_value() == self.frequency.value()
+			&& self.gain.previous_value() == self.gain.value()
+			&& self.q.previous_value() == self.q.value())
+		.then(|| {
+			Coefficients::calculate(
+				self.kind,
+				self.frequency.value(),
+				self.q.value(),
+				self.gain.value(),
+				dt,
+			)
+		});
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let calculated_coefficients;
+			let coefficients = match &fixed_coefficients {
+				Some(coefficients) => coefficients,
+				None => {
+					calculated_coefficients = Coefficients::calculate(
+						self.kind,
+						self.frequency.interpolated_value(time_in_chunk),
+						self.q.interpolated_value(time_in_chunk),
+						self.gain.interpolated_value(time_in_chunk),
+						dt,
+					);
+					&calculated_coefficients
+				}
+			};
+			let output = coefficients.tick(*frame, &mut self.ic1eq, &mut self.ic2eq);
+			let enabled = self.enabled.interpolated_value(time_in_chunk);
+			*frame = if enabled == 1.0 {
+				output
+			} else {
+				*frame + (output - *frame) * enabled
+			};
+		}
+	}
+}
+
+command_writers_and_readers! {
+	set_kind: EqFilterKind,
+	set_frequency: ValueChangeCommand<f64>,
+	set_gain: ValueChangeCommand<Decibels>,
+	set_q: ValueChangeCommand<f64>,
+	set_enabled: bool,
+}
@@ -0,0 +1,152 @@
+use crate::{
+	effect::{
+		bypass::Bypassable, eq_filter::EqFilterKind, BuilderTemplate, Effect, EffectBuilder,
+		TemplateBuilder,
+	},
+	Decibels, Value,
+};
+
+use super::{command_writers_and_readers, ParametricEq, ParametricEqHandle};
+
+/// The settings of one band of a parametric EQ.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BandSettings {
+	/// The shape of the frequency adjustment curve.
+	pub kind: EqFilterKind,
+	/// The "center" or "corner" of the frequency range to adjust in Hz
+	/// (for bell or shelf curves, respectively), or the cutoff frequency
+	/// for low-pass and high-pass curves.
+	pub frequency: Value<f64>,
+	/// The volume adjustment for frequencies in the specified range (in decibels).
+	pub gain: Value<Decibels>,
+	/// The width of the frequency range to adjust.
+	///
+	/// A higher Q value results in a narrower range of frequencies being adjusted.
+	/// The value should be greater than `0.0`.
+	pub q: Value<f64>,
+	/// Whether the band adjusts the audio. Disabled bands are skipped.
+	pub enabled: bool,
+}
+
+impl BandSettings {
+	pub(crate) const DEFAULT_FREQUENCY: f64 = 1000.0;
+	pub(crate) const DEFAULT_Q: f64 = 1.0;
+
+	/// Creates a new, enabled [`BandSettings`].
+	#[must_use]
+	pub fn new(
+		kind: EqFilterKind,
+		frequency: impl Into<Value<f64>>,
+		gain: impl Into<Value<Decibels>>,
+		q: impl Into<Value<f64>>,
+	) -> Self {
+		Self {
+			kind,
+			frequency: frequency.into(),
+			gain: gain.into(),
+			q: q.into(),
+			enabled: true,
+		}
+	}
+
+	/// Sets whether the band adjusts the audio.
+	#[must_use = "This method consumes self and returns a modified BandSettings, so the return value should be used"]
+	pub fn enabled(self, enabled: bool) -> Self {
+		Self { enabled, ..self }
+	}
+}
+
+impl Default for BandSettings {
+	/// Creates an enabled bell band at 1,000 Hz that doesn't change the
+	/// volume.
+	fn default() -> Self {
+		Self::new(
+			EqFilterKind::Bell,
+			Self::DEFAULT_FREQUENCY,
+			Decibels::IDENTITY,
+			Self::DEFAULT_Q,
+		)
+	}
+}
+
+/**
+Configures a parametric EQ.
+
+# Examples
+
+Set up a four band EQ with two spare bands that can be enabled later:
+
+```
+use kira::{
+	effect::{
+		eq_filter::EqFilterKind,
+		parametric_eq::{BandSettings, ParametricEqBuilder},
+	},
+	Decibels,
+};
+
+let builder = ParametricEqBuilder::new()
+	.with_band(BandSettings::new(EqFilterKind::HighPass, 40.0, Decibels::IDENTITY, 0.7))
+	.with_band(BandSettings::new(EqFilterKind::Bell, 3000.0, Decibels(-4.0), 2.0))
+	.with_band(BandSettings::default().enabled(false))
+	.with_band(BandSettings::default().enabled(false));
+```
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParametricEqBuilder {
+	/// The bands of the EQ, which are applied in order.
+	pub bands: Vec<BandSettings>,
+}
+
+impl ParametricEqBuilder {
+	/// Creates a new [`ParametricEqBuilder`] with no bands.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a band to the EQ.
+	///
+	/// The EQ can't gain more bands once it's built, so bands that might
+	/// be needed later should be added in a
+	/// [disabled](BandSettings::enabled) state.
+	#[must_use = "This method consumes self and returns a modified ParametricEqBuilder, so the return value should be used"]
+	pub fn with_band(mut self, band: BandSettings) -> Self {
+		self.bands.push(band);
+		self
+	}
+
+	/// Builds the EQ, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, ParametricEqHandle) {
+		let (command_writers, command_readers) = self
+			.bands
+			.iter()
+			.map(|_| command_writers_and_readers())
+			.unzip();
+		let effect = ParametricEq::new(&self, command_readers);
+		let template = BuilderTemplate::new(self, bypassed);
+		let (effect, set_bypassed) = Bypassable::new(effect, Some(template.clone()), bypassed);
+		(
+			Box::new(effect),
+			ParametricEqHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
+impl EffectBuilder for ParametricEqBuilder {
+	type Handle = ParametricEqHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for ParametricEqBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
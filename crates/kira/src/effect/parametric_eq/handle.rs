@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::{
+	command::{CommandWriter, ValueChangeCommand},
+	effect::BuilderTemplate,
+	Tween,
+};
+
+use super::{BandSettings, CommandWriters, NonexistentBand, ParametricEqBuilder};
+
+/// Controls a parametric EQ.
+#[derive(Debug)]
+pub struct ParametricEqHandle {
+	pub(super) command_writers: Vec<CommandWriters>,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<ParametricEqBuilder>>,
+}
+
+impl ParametricEqHandle {
+	/// Bypasses or stops bypassing the EQ, crossfading between its input
+	/// and output with the given tween.
+	///
+	/// Once it's fully bypassed, the EQ stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	/// Returns the number of bands the EQ has.
+	#[must_use]
+	pub fn num_bands(&self) -> usize {
+		self.command_writers.len()
+	}
+
+	/**
+	Changes the settings of the band with the given index.
+
+	The frequency, gain, and Q move to their new values with the given
+	tween, and the band's curve is recalculated for every frame while they
+	do, so the change is smooth. The band's
+	[kind](BandSettings::kind) changes immediately, and the band fades in
+	or out if [`enabled`](BandSettings::enabled) changes.
+
+	Returns an error if the EQ doesn't have a band with the given index.
+	*/
+	pub fn set_band(
+		&mut self,
+		index: usize,
+		settings: BandSettings,
+		tween: Tween,
+	) -> Result<(), NonexistentBand> {
+		let command_writers = self.command_writers.get_mut(index).ok_or(NonexistentBand)?;
+		self.template
+			.update(|builder| builder.bands[index] = settings);
+		command_writers.set_kind.write(settings.kind);
+		command_writers.set_frequency.write(ValueChangeCommand {
+			target: settings.frequency,
+			tween,
+		});
+		command_writers.set_gain.write(ValueChangeCommand {
+			target: settings.gain,
+			tween,
+		});
+		command_writers.set_q.write(ValueChangeCommand {
+			target: settings.q,
+			tween,
+		});
+		command_writers.set_enabled.write(settings.enabled);
+		Ok(())
+	}
+
+	/// Enables or disables the band with the given index.
+	///
+	/// The band fades in or out over a few milliseconds so the change
+	/// doesn't click. Disabled bands are skipped once they've faded out.
+	///
+	/// Returns an error if the EQ doesn't have a band with the given index.
+	pub fn set_band_enabled(&mut self, index: usize, enabled: bool) -> Result<(), NonexistentBand> {
+		let command_writers = self.command_writers.get_mut(index).ok_or(NonexistentBand)?;
+		self.template
+			.update(|builder| builder.bands[index].enabled = enabled);
+		command_writers.set_enabled.write(enabled);
+		Ok(())
+	}
+}
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use crate::{
+	effect::{
+		eq_filter::EqFilterKind,
+		test_helpers::{nyquist, settled_peak, DT},
+		EffectBuilder,
+	},
+	frame::Frame,
+	info::MockInfoBuilder,
+	Decibels, Tween,
+};
+
+use super::{BandSettings, NonexistentBand, ParametricEqBuilder};
+
+/// Tests that disabled bands don't affect the audio at all.
+#[test]
+fn disabled_bands_pass_audio_through_unchanged() {
+	let info = MockInfoBuilder::new().build();
+	let (mut effect, _) = ParametricEqBuilder::new()
+		.with_band(
+			BandSettings::new(EqFilterKind::Bell, 1000.0, Decibels(12.0), 1.0).enabled(false),
+		)
+		.with_band(
+			BandSettings::new(EqFilterKind::LowPass, 100.0, Decibels::IDENTITY, 0.7).enabled(false),
+		)
+		.build();
+	let input: Vec<Frame> = (0..128)
+		.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+		.collect();
+	let mut output = input.clone();
+	effect.on_start_processing();
+	effect.process(&mut output, DT, &info);
+	assert_eq!(output, input);
+	assert!(effect.is_idle());
+}
+
+/// Tests that each band shapes the audio and the bands are applied in
+/// series.
+#[test]
+fn bands_are_applied_in_series() {
+	let builder = ParametricEqBuilder::new()
+		.with_band(BandSettings::new(
+			EqFilterKind::LowShelf,
+			200.0,
+			Decibels(6.0),
+			0.7,
+		))
+		.with_band(BandSettings::new(
+			EqFilterKind::HighPass,
+			20.0,
+			Decibels::IDENTITY,
+			0.7,
+		));
+	// the high-pass band removes the DC offset that the low shelf boosts
+	let (mut effect, _) = builder.clone().build();
+	assert!(settled_peak(&mut *effect, |_| 0.25) < 0.001);
+	let (mut effect, _) = builder.build();
+	assert!((settled_peak(&mut *effect, nyquist) - 0.25).abs() < 0.001);
+}
+
+/// Tests that bands can be changed, enabled, and disabled from the
+/// handle.
+#[test]
+fn handle_controls_bands() {
+	let (mut effect, mut handle) = ParametricEqBuilder::new()
+		.with_band(BandSettings::default().enabled(false))
+		.build();
+	assert_eq!(handle.num_bands(), 1);
+	handle
+		.set_band(
+			0,
+			BandSettings::new(EqFilterKind::LowPass, 1000.0, Decibels::IDENTITY, 0.7),
+			Tween {
+				duration: Duration::from_millis(50),
+				..Default::default()
+			},
+		)
+		.unwrap();
+	assert!(settled_peak(&mut *effect, nyquist) < 0.001);
+	handle.set_band_enabled(0, false).unwrap();
+	assert!((settled_peak(&mut *effect, nyquist) - 0.25).abs() < f32::EPSILON);
+	assert!(effect.is_idle());
+	assert_eq!(
+		handle.set_band(1, BandSettings::default(), Tween::default()),
+		Err(NonexistentBand)
+	);
+	assert_eq!(handle.set_band_enabled(1, true), Err(NonexistentBand));
+}
+
+/// Tests that moving a band's settings changes its curve gradually
+/// instead of all at once.
+#[test]
+fn band_changes_are_smoothed() {
+	let info = MockInfoBuilder::new().build();
+	let (mut effect, mut handle) = ParametricEqBuilder::new()
+		.with_band(BandSettings::new(
+			EqFilterKind::LowShelf,
+			200.0,
+			Decibels::IDENTITY,
+			0.7,
+		))
+		.build();
+	settled_peak(&mut *effect, |_| 0.25);
+	handle
+		.set_band(
+			0,
+			BandSettings::new(EqFilterKind::LowShelf, 200.0, Decibels(12.0), 0.7),
+			Tween {
+				duration: Duration::from_secs(1),
+				..Default::default()
+			},
+		)
+		.unwrap();
+	effect.on_start_processing();
+	let mut output = vec![Frame::from_mono(0.25); 128];
+	effect.process(&mut output, DT, &info);
+	// the first chunk is only a little way into the tween, so the output
+	// should only be a little louder, and never jumps between frames
+	assert!(output[127].left < 0.26);
+	for pair in output.windows(2) {
+		assert!((pair[1].left - pair[0].left).abs() < 0.001);
+	}
+}
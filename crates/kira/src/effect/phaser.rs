@@ -0,0 +1,229 @@
+//! Sweeps notches through the frequencies of a sound.
+//!
+//! A phaser mixes its input with a copy that's passed through a chain of
+//! all-pass filters. The filters shift the phase of each frequency by a
+//! different amount, so mixing the copy with the input cancels out some
+//! frequencies, and an LFO sweeps the filters up and down to move the
+//! cancelled frequencies over time. The LFO can be
+//! [synced to a clock](PhaserBuilder::sync_to_clock) so the sweeps line
+//! up with the beat.
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::f64::consts::{PI, TAU};
+
+use crate::{
+	clock::ClockId,
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::Frame,
+	info::Info,
+	Mix, Parameter,
+};
+
+use super::{bypass::BypassableEffect, is_silent, Effect};
+
+/// The lowest frequency the all-pass filters are swept to in Hz.
+const MIN_FREQUENCY: f64 = 20.0;
+/// The highest frequency the all-pass filters are swept to, as a fraction
+/// of the sample rate.
+const MAX_FREQUENCY_RATIO: f64 = 0.45;
+
+/// Syncs a phaser's LFO to a clock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhaserSync {
+	/// The clock to follow.
+	pub clock: ClockId,
+	/// How many ticks of the clock each sweep of the LFO lasts.
+	pub ticks_per_cycle: f64,
+}
+
+struct Phaser {
+	command_readers: CommandReaders,
+	rate: Parameter,
+	depth: Parameter,
+	center_frequency: Parameter,
+	feedback: Parameter,
+	stereo_phase_offset: Parameter,
+	mix: Parameter<Mix>,
+	sync: Option<PhaserSync>,
+	/// The position of the LFO in its cycle, from `0.0` to `1.0`.
+	phase: f64,
+	/// The position of the synced clock (in ticks) when the last chunk of
+	/// audio was processed, along with how long that chunk was.
+	last_clock_position: Option<(f64, usize)>,
+	stages: Vec<AllPassStage>,
+	/// The output of the last stage for the previous frame, which is fed
+	/// back into the first stage.
+	last_output: Frame,
+}
+
+impl Phaser {
+	#[must_use]
+	fn new(builder: PhaserBuilder, command_readers: CommandReaders) -> Self {
+		let num_stages = builder
+			.stages
+			.clamp(PhaserBuilder::MIN_STAGES, PhaserBuilder::MAX_STAGES);
+		Self {
+			command_readers,
+			rate: Parameter::new(builder.rate, PhaserBuilder::DEFAULT_RATE),
+			depth: Parameter::new(builder.depth, PhaserBuilder::DEFAULT_DEPTH),
+			center_frequency: Parameter::new(
+				builder.center_frequency,
+				PhaserBuilder::DEFAULT_CENTER_FREQUENCY,
+			),
+			feedback: Parameter::new(builder.feedback, 0.0),
+			stereo_phase_offset: Parameter::new(
+				builder.stereo_phase_offset,
+				PhaserBuilder::DEFAULT_STEREO_PHASE_OFFSET,
+			),
+			mix: Parameter::new(builder.mix, PhaserBuilder::DEFAULT_MIX),
+			sync: builder.sync,
+			phase: 0.0,
+			last_clock_position: None,
+			stages: vec![AllPassStage::default(); num_stages],
+			last_output: Frame::ZERO,
+		}
+	}
+
+	/// Returns how far the LFO moves through its cycle each frame, and
+	/// moves the LFO to the synced clock's position if there is one.
+	#[must_use]
+	fn sync_phase(&mut self, num_frames: usize, info: &Info) -> Option<f64> {
+		let sync = self.sync?;
+		let ticks_per_cycle = sync.ticks_per_cycle.max(f64::EPSILON);
+		let Some(clock_info) = info.clock_info(sync.clock) else {
+			self.last_clock_position = None;
+			return Some(0.0);
+		};
+		let position = clock_info.time.ticks as f64 + clock_info.time.fraction;
+		// the clock's speed isn't known, so the LFO moves at the speed the
+		// clock moved during the last chunk and is corrected at the start
+		// of each chunk
+		let ticks_per_frame = match self.last_clock_position {
+			Some((last_position, last_num_frames))
+				if clock_info.ticking && position >= last_position && last_num_frames > 0 =>
+			{
+				(position - last_position) / last_num_frames as f64
+			}
+			_ => 0.0,
+		};
+		self.last_clock_position = Some((position, num_frames));
+		self.phase = (position / ticks_per_cycle).fract();
+		Some(ticks_per_frame / ticks_per_cycle)
+	}
+}
+
+impl Effect for Phaser {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(
+			self,
+			rate,
+			depth,
+			center_frequency,
+			feedback,
+			stereo_phase_offset,
+			mix
+		);
+	}
+
+	fn is_idle(&self) -> bool {
+		is_silent(self.last_output)
+			&& self
+				.stages
+				.iter()
+				.all(|stage| is_silent(stage.input) && is_silent(stage.output))
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		let chunk_duration = dt * input.len() as f64;
+		self.rate.update(chunk_duration, info);
+		self.depth.update(chunk_duration, info);
+		self.center_frequency.update(chunk_duration, info);
+		self.feedback.update(chunk_duration, info);
+		self.stereo_phase_offset.update(chunk_duration, info);
+		self.mix.update(chunk_duration, info);
+		let synced_phase_increment = self.sync_phase(input.len(), info);
+
+		let max_frequency = MAX_FREQUENCY_RATIO / dt;
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			// the coefficients are recalculated for every frame so the sweep
+			// is smooth
+			let center_frequency = self.center_frequency.interpolated_value(time_in_chunk);
+			let depth = self.depth.interpolated_value(time_in_chunk);
+			let stereo_phase_offset = self.stereo_phase_offset.interpolated_value(time_in_chunk);
+			let left_phase = self.phase * TAU;
+			let coefficient = |phase: f64| {
+				let frequency = (center_frequency * 2.0f64.powf(depth * phase.sin()))
+					.clamp(MIN_FREQUENCY, max_frequency);
+				let tan = (PI * frequency * dt).tan();
+				((tan - 1.0) / (tan + 1.0)) as f32
+			};
+			let left_coefficient = coefficient(left_phase);
+			let right_coefficient = coefficient(left_phase + stereo_phase_offset);
+
+			let feedback = self
+				.feedback
+				.interpolated_value(time_in_chunk)
+				.clamp(-PhaserBuilder::MAX_FEEDBACK, PhaserBuilder::MAX_FEEDBACK);
+			let mut wet = *frame + self.last_output * feedback as f32;
+			for stage in &mut self.stages {
+				wet = stage.process(wet, left_coefficient, right_coefficient);
+			}
+			self.last_output = wet;
+
+			let phase_increment = synced_phase_increment
+				.unwrap_or_else(|| self.rate.interpolated_value(time_in_chunk).max(0.0) * dt);
+			self.phase = (self.phase + phase_increment).fract();
+
+			let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
+			*frame = wet * mix.sqrt() + *frame * (1.0 - mix).sqrt();
+		}
+	}
+}
+
+impl BypassableEffect for Phaser {
+	fn clear_tail(&mut self) {
+		self.stages.fill(AllPassStage::default());
+		self.last_output = Frame::ZERO;
+	}
+}
+
+/// A first-order all-pass filter.
+#[derive(Debug, Clone, Copy, Default)]
+struct AllPassStage {
+	input: Frame,
+	output: Frame,
+}
+
+impl AllPassStage {
+	#[must_use]
+	fn process(&mut self, input: Frame, left_coefficient: f32, right_coefficient: f32) -> Frame {
+		let output = Frame::new(
+			left_coefficient * input.left + self.input.left - left_coefficient * self.output.left,
+			right_coefficient * input.right + self.input.right
+				- right_coefficient * self.output.right,
+		);
+		self.input = input;
+		self.output = output;
+		output
+	}
+}
+
+command_writers_and_readers! {
+	set_rate: ValueChangeCommand<f64>,
+	set_depth: ValueChangeCommand<f64>,
+	set_center_frequency: ValueChangeCommand<f64>,
+	set_feedback: ValueChangeCommand<f64>,
+	set_stereo_phase_offset: ValueChangeCommand<f64>,
+	set_mix: ValueChangeCommand<Mix>,
+}
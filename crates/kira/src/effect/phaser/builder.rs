@@ -0,0 +1,211 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::{
+	clock::ClockId,
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Mix, Value,
+};
+
+use super::{command_writers_and_readers, Phaser, PhaserHandle, PhaserSync};
+
+/**
+Configures a phaser effect.
+
+# Examples
+
+Sweep the phaser once every 4 ticks of a clock:
+
+```
+use kira::{
+	effect::phaser::PhaserBuilder,
+	clock::ClockSpeed,
+	AudioManager, AudioManagerSettings, DefaultBackend,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let clock = manager.add_clock(ClockSpeed::TicksPerMinute(120.0))?;
+let builder = PhaserBuilder::new().sync_to_clock(&clock, 4.0);
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PhaserBuilder {
+	/// How many all-pass filters the audio passes through.
+	///
+	/// More stages create more notches. This is kept between 4 and 12.
+	pub stages: usize,
+	/// How many times per second the LFO sweeps the filters up and down (in
+	/// Hz). This is ignored if the LFO is [synced to a clock](Self::sync).
+	pub rate: Value<f64>,
+	/// How many octaves above and below the center frequency the LFO sweeps
+	/// the filters.
+	pub depth: Value<f64>,
+	/// The frequency the LFO sweeps the filters around (in Hz).
+	pub center_frequency: Value<f64>,
+	/// How much of the filters' output is fed back into them, from `-0.95`
+	/// to `0.95`.
+	///
+	/// Feedback makes the notches sharper and more resonant.
+	pub feedback: Value<f64>,
+	/// How far ahead the LFO for the right channel is of the LFO for the
+	/// left channel (in radians).
+	pub stereo_phase_offset: Value<f64>,
+	/// How much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal.
+	pub mix: Value<Mix>,
+	/// The clock the LFO follows, or `None` if the LFO runs at its own
+	/// [`rate`](Self::rate).
+	pub sync: Option<PhaserSync>,
+}
+
+impl PhaserBuilder {
+	pub(crate) const MIN_STAGES: usize = 4;
+	pub(crate) const MAX_STAGES: usize = 12;
+	pub(crate) const DEFAULT_RATE: f64 = 0.5;
+	pub(crate) const DEFAULT_DEPTH: f64 = 2.0;
+	pub(crate) const DEFAULT_CENTER_FREQUENCY: f64 = 1000.0;
+	pub(crate) const MAX_FEEDBACK: f64 = 0.95;
+	pub(crate) const DEFAULT_STEREO_PHASE_OFFSET: f64 = FRAC_PI_2;
+	pub(crate) const DEFAULT_MIX: Mix = Mix(0.5);
+
+	/// Creates a new [`PhaserBuilder`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			stages: 6,
+			rate: Value::Fixed(Self::DEFAULT_RATE),
+			depth: Value::Fixed(Self::DEFAULT_DEPTH),
+			center_frequency: Value::Fixed(Self::DEFAULT_CENTER_FREQUENCY),
+			feedback: Value::Fixed(0.0),
+			stereo_phase_offset: Value::Fixed(Self::DEFAULT_STEREO_PHASE_OFFSET),
+			mix: Value::Fixed(Self::DEFAULT_MIX),
+			sync: None,
+		}
+	}
+
+	/// Sets how many all-pass filters the audio passes through. Defaults
+	/// to 6.
+	///
+	/// More stages create more notches. This is kept between 4 and 12.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn stages(self, stages: usize) -> Self {
+		Self { stages, ..self }
+	}
+
+	/// Sets how many times per second the LFO sweeps the filters up and
+	/// down (in Hz). Defaults to 0.5 Hz.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn rate(self, rate: impl Into<Value<f64>>) -> Self {
+		Self {
+			rate: rate.into(),
+			..self
+		}
+	}
+
+	/// Sets how many octaves above and below the center frequency the LFO
+	/// sweeps the filters. Defaults to 2 octaves.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn depth(self, depth: impl Into<Value<f64>>) -> Self {
+		Self {
+			depth: depth.into(),
+			..self
+		}
+	}
+
+	/// Sets the frequency the LFO sweeps the filters around (in Hz).
+	/// Defaults to 1,000 Hz.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn center_frequency(self, center_frequency: impl Into<Value<f64>>) -> Self {
+		Self {
+			center_frequency: center_frequency.into(),
+			..self
+		}
+	}
+
+	/// Sets how much of the filters' output is fed back into them, from
+	/// `-0.95` to `0.95`. Defaults to `0.0`.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn feedback(self, feedback: impl Into<Value<f64>>) -> Self {
+		Self {
+			feedback: feedback.into(),
+			..self
+		}
+	}
+
+	/// Sets how far ahead the LFO for the right channel is of the LFO for
+	/// the left channel (in radians). Defaults to π/2.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn stereo_phase_offset(self, stereo_phase_offset: impl Into<Value<f64>>) -> Self {
+		Self {
+			stereo_phase_offset: stereo_phase_offset.into(),
+			..self
+		}
+	}
+
+	/// Sets how much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal. `0.0` means only the dry
+	/// signal will be heard. `1.0` means only the wet signal will
+	/// be heard.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn mix(self, mix: impl Into<Value<Mix>>) -> Self {
+		Self {
+			mix: mix.into(),
+			..self
+		}
+	}
+
+	/// Makes the LFO follow a clock, sweeping the filters once every
+	/// `ticks_per_cycle` ticks instead of at its own rate.
+	///
+	/// Each sweep starts on a multiple of `ticks_per_cycle` ticks, and the
+	/// LFO stops while the clock is stopped.
+	#[must_use = "This method consumes self and returns a modified PhaserBuilder, so the return value should be used"]
+	pub fn sync_to_clock(self, clock: impl Into<ClockId>, ticks_per_cycle: f64) -> Self {
+		Self {
+			sync: Some(PhaserSync {
+				clock: clock.into(),
+				ticks_per_cycle,
+			}),
+			..self
+		}
+	}
+
+	/// Builds the phaser, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, PhaserHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			Phaser::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			PhaserHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
+impl Default for PhaserBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl EffectBuilder for PhaserBuilder {
+	type Handle = PhaserHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for PhaserBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
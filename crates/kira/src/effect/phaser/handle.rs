@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Mix, Tween,
+};
+
+use super::{CommandWriters, PhaserBuilder};
+
+/// Controls a phaser effect.
+#[derive(Debug)]
+pub struct PhaserHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<PhaserBuilder>>,
+}
+
+impl PhaserHandle {
+	/// Bypasses or stops bypassing the phaser, crossfading between its input
+	/// and output with the given tween.
+	///
+	/// Once it's fully bypassed, the phaser stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
+		/// Sets how many times per second the LFO sweeps the filters up and
+		/// down (in Hz).
+		rate: f64 => rate,
+
+		/// Sets how many octaves above and below the center frequency the LFO
+		/// sweeps the filters.
+		depth: f64 => depth,
+
+		/// Sets the frequency the LFO sweeps the filters around (in Hz).
+		center_frequency: f64 => center_frequency,
+
+		/// Sets how much of the filters' output is fed back into them, from
+		/// `-0.95` to `0.95`.
+		feedback: f64 => feedback,
+
+		/// Sets how far ahead the LFO for the right channel is of the LFO for
+		/// the left channel (in radians).
+		stereo_phase_offset: f64 => stereo_phase_offset,
+
+		/// Sets how much dry (unprocessed) signal should be blended
+		/// with the wet (processed) signal.
+		mix: Mix => mix,
+	}
+}
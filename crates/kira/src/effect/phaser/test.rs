@@ -0,0 +1,114 @@
+use std::f64::consts::PI;
+
+use crate::{
+	effect::{
+		test_helpers::{init, process_with_info, sine, SAMPLE_RATE},
+		Effect,
+	},
+	frame::Frame,
+	info::MockInfoBuilder,
+	Mix,
+};
+
+use super::{command_writers_and_readers, Phaser, PhaserBuilder, PhaserSync};
+
+fn phaser(builder: PhaserBuilder) -> Phaser {
+	init(Phaser::new(builder, command_writers_and_readers().1))
+}
+
+fn peak(frames: &[Frame]) -> f32 {
+	frames
+		.iter()
+		.map(|frame| frame.left.abs().max(frame.right.abs()))
+		.fold(0.0, f32::max)
+}
+
+/// Tests that a phaser with a mix of `0.0` outputs exactly the audio it
+/// receives.
+#[test]
+fn passes_audio_through_unchanged_when_dry() {
+	let info = MockInfoBuilder::new().build();
+	let mut phaser = phaser(PhaserBuilder::new().feedback(0.5).mix(Mix::DRY));
+	let input: Vec<Frame> = (0..512)
+		.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+		.collect();
+	assert_eq!(process_with_info(&mut phaser, &input, &info), input);
+}
+
+/// Tests that mixing the filtered signal with the input cancels out the
+/// frequencies the all-pass filters shift by half a cycle.
+#[test]
+fn cancels_notch_frequencies() {
+	let info = MockInfoBuilder::new().build();
+	let center_frequency = 1000.0;
+	let builder = PhaserBuilder::new()
+		.stages(4)
+		.depth(0.0)
+		.center_frequency(center_frequency);
+	// each of the 4 stages shifts this frequency by an eighth of a cycle
+	let notch_frequency = ((PI * center_frequency / SAMPLE_RATE as f64).tan() * (PI / 8.0).tan())
+		.atan()
+		* SAMPLE_RATE as f64
+		/ PI;
+	let notch = process_with_info(
+		&mut phaser(builder),
+		&sine(notch_frequency, 1.0, 9600),
+		&info,
+	);
+	assert!(peak(&notch[4800..]) < 0.01);
+	// the center frequency is shifted by a whole cycle, so it's reinforced
+	let center = process_with_info(
+		&mut phaser(builder),
+		&sine(center_frequency, 1.0, 9600),
+		&info,
+	);
+	assert!(peak(&center[4800..]) > 1.4);
+}
+
+/// Tests that the LFO follows the position of the clock it's synced to.
+#[test]
+fn syncs_lfo_to_clock() {
+	let mut builder = MockInfoBuilder::new();
+	let clock = builder.add_clock(true, 2, 0.0);
+	let info = builder.build();
+	let mut phaser = phaser(PhaserBuilder::new().sync_to_clock(clock, 4.0));
+	process_with_info(&mut phaser, &[Frame::ZERO; 128], &info);
+	// the clock's speed isn't known yet, so the LFO stays at the clock's position
+	assert!((phaser.phase - 0.5).abs() < 1.0e-9);
+
+	let mut builder = MockInfoBuilder::new();
+	let clock = builder.add_clock(true, 3, 0.0);
+	let info = builder.build();
+	phaser.sync = Some(PhaserSync {
+		clock,
+		ticks_per_cycle: 4.0,
+	});
+	process_with_info(&mut phaser, &[Frame::ZERO; 64], &info);
+	// the clock moved 1 tick over the last 128 frames, so after 64 more
+	// frames it should be half a tick further along
+	assert!((phaser.phase - 0.875).abs() < 1.0e-9);
+}
+
+/// Tests that the LFO stops moving while the clock it's synced to is
+/// stopped.
+#[test]
+fn holds_lfo_while_clock_is_stopped() {
+	let mut builder = MockInfoBuilder::new();
+	let clock = builder.add_clock(false, 1, 0.0);
+	let info = builder.build();
+	let mut phaser = phaser(PhaserBuilder::new().sync_to_clock(clock, 4.0));
+	process_with_info(&mut phaser, &[Frame::ZERO; 512], &info);
+	assert!((phaser.phase - 0.25).abs() < 1.0e-9);
+}
+
+/// Tests that the phaser becomes idle once the filters have rung out.
+#[test]
+fn becomes_idle_after_input_stops() {
+	let info = MockInfoBuilder::new().build();
+	let mut phaser = phaser(PhaserBuilder::new().feedback(0.5));
+	assert!(phaser.is_idle());
+	process_with_info(&mut phaser, &[Frame::from_mono(1.0)], &info);
+	assert!(!phaser.is_idle());
+	process_with_info(&mut phaser, &[Frame::ZERO; 4800], &info);
+	assert!(phaser.is_idle());
+}
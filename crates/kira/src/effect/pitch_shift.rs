@@ -0,0 +1,175 @@
+//! Changes the pitch of audio without changing its speed.
+//!
+//! The pitch shifter reads its input back from two taps on a delay line.
+//! Each tap's delay sweeps steadily shorter (to raise the pitch) or longer
+//! (to lower it), jumping back by a grain's worth of audio when it runs
+//! out of room. The taps are half a grain apart, and each one fades out
+//! before it jumps, so crossfading between them hides the jumps.
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::{f64::consts::PI, time::Duration};
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::{interpolate_frame, Frame},
+	info::Info,
+	Mix, Parameter, PlaybackRate, Semitones,
+};
+
+use super::{bypass::BypassableEffect, is_silent, Effect};
+
+/// The shortest time a tap can be delayed by, in frames, which leaves
+/// room for the frames after the read position that are interpolated.
+const MIN_DELAY_FRAMES: f64 = 3.0;
+
+struct PitchShift {
+	command_readers: CommandReaders,
+	shift: Parameter<Semitones>,
+	mix: Parameter<Mix>,
+	grain_size: Duration,
+	/// The length of a grain in frames at the current sample rate.
+	grain_frames: f64,
+	/// How far the first tap is through its grain, from `0.0` to `1.0`.
+	/// The second tap is always half a grain further along.
+	phase: f64,
+	buffer: Vec<Frame>,
+	/// The index in the buffer the next frame is written to.
+	write_index: usize,
+	/// How many silent frames have been written to the buffer in a row.
+	quiet_frames: usize,
+}
+
+impl PitchShift {
+	#[must_use]
+	fn new(builder: PitchShiftBuilder, command_readers: CommandReaders) -> Self {
+		Self {
+			command_readers,
+			shift: Parameter::new(builder.shift, Semitones(0.0)),
+			mix: Parameter::new(builder.mix, PitchShiftBuilder::DEFAULT_MIX),
+			grain_size: builder.grain_size.clamp(
+				PitchShiftBuilder::MIN_GRAIN_SIZE,
+				PitchShiftBuilder::MAX_GRAIN_SIZE,
+			),
+			grain_frames: 0.0,
+			phase: 0.0,
+			buffer: vec![],
+			write_index: 0,
+			quiet_frames: 0,
+		}
+	}
+
+	/// Allocates room for a grain at the given sample rate.
+	fn allocate(&mut self, sample_rate: u32) {
+		self.grain_frames = (self.grain_size.as_secs_f64() * sample_rate as f64).round();
+		let len = self.grain_frames as usize + MIN_DELAY_FRAMES as usize + 1;
+		self.buffer = vec![Frame::ZERO; len];
+		self.write_index = 0;
+		self.quiet_frames = len;
+	}
+
+	/// Returns the frame that was written `delay` frames ago, interpolating
+	/// between the frames around it.
+	#[must_use]
+	fn read(&self, delay: f64) -> Frame {
+		let len = self.buffer.len();
+		let position = self.write_index as f64 + len as f64 - delay;
+		let index = position as usize;
+		let fraction = position.fract() as f32;
+		interpolate_frame(
+			self.buffer[(index + len - 1) % len],
+			self.buffer[index % len],
+			self.buffer[(index + 1) % len],
+			self.buffer[(index + 2) % len],
+			fraction,
+		)
+	}
+
+	/// Returns the output of the tap that's `phase` of the way through its
+	/// grain, faded out towards the start and end of the grain.
+	#[must_use]
+	fn read_tap(&self, phase: f64) -> Frame {
+		let gain = (PI * phase).sin().powi(2) as f32;
+		self.read(MIN_DELAY_FRAMES + phase * self.grain_frames) * gain
+	}
+}
+
+impl Effect for PitchShift {
+	fn init(&mut self, sample_rate: u32, _internal_buffer_size: usize) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.allocate(sample_rate);
+	}
+
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, shift, mix);
+	}
+
+	// the pitch shifter is idle once everything in its buffer is silent
+	fn is_idle(&self) -> bool {
+		self.quiet_frames >= self.buffer.len()
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		let chunk_duration = dt * input.len() as f64;
+		self.shift.update(chunk_duration, info);
+		self.mix.update(chunk_duration, info);
+
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			// the windows of the two taps add up to 1, so the crossfade
+			// doesn't change the volume
+			let wet = self.read_tap(self.phase) + self.read_tap((self.phase + 0.5) % 1.0);
+
+			self.buffer[self.write_index] = *frame;
+			self.write_index = (self.write_index + 1) % self.buffer.len();
+			if is_silent(*frame) {
+				self.quiet_frames = self.quiet_frames.saturating_add(1);
+			} else {
+				self.quiet_frames = 0;
+			}
+
+			// reading the delay line `ratio` frames further along each frame
+			// plays it back `ratio` times as fast, so each tap's delay changes
+			// by the difference between that and the speed it's written at
+			let shift = Semitones(
+				self.shift
+					.interpolated_value(time_in_chunk)
+					.0
+					.clamp(-PitchShiftBuilder::MAX_SHIFT, PitchShiftBuilder::MAX_SHIFT),
+			);
+			let ratio = PlaybackRate::from(shift).0;
+			self.phase = (self.phase + (1.0 - ratio) / self.grain_frames).rem_euclid(1.0);
+
+			let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
+			*frame = wet * mix.sqrt() + *frame * (1.0 - mix).sqrt();
+		}
+	}
+
+	fn latency_frames(&self) -> usize {
+		(MIN_DELAY_FRAMES + self.grain_frames / 2.0).round() as usize
+	}
+}
+
+impl BypassableEffect for PitchShift {
+	fn clear_tail(&mut self) {
+		self.buffer.fill(Frame::ZERO);
+		self.quiet_frames = self.buffer.len();
+	}
+}
+
+command_writers_and_readers! {
+	set_shift: ValueChangeCommand<Semitones>,
+	set_mix: ValueChangeCommand<Mix>,
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Mix, Semitones, Value,
+};
+
+use super::{command_writers_and_readers, PitchShift, PitchShiftHandle};
+
+/**
+Configures a pitch shifter effect.
+
+# Examples
+
+Lower everything on a track by 3 semitones:
+
+```
+use kira::{effect::pitch_shift::PitchShiftBuilder, track::TrackBuilder, Semitones};
+
+let builder = TrackBuilder::new().with_effect(PitchShiftBuilder::new().shift(Semitones(-3.0)));
+```
+*/
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PitchShiftBuilder {
+	/// How much the pitch of the audio is raised or lowered.
+	///
+	/// This is kept between -24 and 24 semitones.
+	pub shift: Value<Semitones>,
+	/// The length of the pieces of audio that are crossfaded between.
+	///
+	/// Longer grains smear transients more and delay the output more,
+	/// while shorter grains make low sounds rougher. The output is delayed
+	/// by about half a grain. This is kept between 10 and 200 milliseconds.
+	pub grain_size: Duration,
+	/// How much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal.
+	pub mix: Value<Mix>,
+}
+
+impl PitchShiftBuilder {
+	pub(crate) const MAX_SHIFT: f64 = 24.0;
+	pub(crate) const DEFAULT_GRAIN_SIZE: Duration = Duration::from_millis(50);
+	pub(crate) const MIN_GRAIN_SIZE: Duration = Duration::from_millis(10);
+	pub(crate) const MAX_GRAIN_SIZE: Duration = Duration::from_millis(200);
+	pub(crate) const DEFAULT_MIX: Mix = Mix::WET;
+
+	/// Creates a new [`PitchShiftBuilder`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			shift: Value::Fixed(Semitones(0.0)),
+			grain_size: Self::DEFAULT_GRAIN_SIZE,
+			mix: Value::Fixed(Self::DEFAULT_MIX),
+		}
+	}
+
+	/// Sets how much the pitch of the audio is raised or lowered. Defaults
+	/// to 0 semitones.
+	#[must_use = "This method consumes self and returns a modified PitchShiftBuilder, so the return value should be used"]
+	pub fn shift(self, shift: impl Into<Value<Semitones>>) -> Self {
+		Self {
+			shift: shift.into(),
+			..self
+		}
+	}
+
+	/// Sets the length of the pieces of audio that are crossfaded between.
+	/// Defaults to 50 milliseconds.
+	///
+	/// The output is delayed by about half a grain. This is kept between 10
+	/// and 200 milliseconds.
+	#[must_use = "This method consumes self and returns a modified PitchShiftBuilder, so the return value should be used"]
+	pub fn grain_size(self, grain_size: Duration) -> Self {
+		Self { grain_size, ..self }
+	}
+
+	/// Sets how much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal. `0.0` means only the dry
+	/// signal will be heard. `1.0` means only the wet signal will
+	/// be heard. Defaults to `1.0`.
+	#[must_use = "This method consumes self and returns a modified PitchShiftBuilder, so the return value should be used"]
+	pub fn mix(self, mix: impl Into<Value<Mix>>) -> Self {
+		Self {
+			mix: mix.into(),
+			..self
+		}
+	}
+
+	/// Builds the pitch shifter, which starts out bypassed if `bypassed` is
+	/// `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, PitchShiftHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			PitchShift::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			PitchShiftHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
+impl Default for PitchShiftBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl EffectBuilder for PitchShiftBuilder {
+	type Handle = PitchShiftHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for PitchShiftBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
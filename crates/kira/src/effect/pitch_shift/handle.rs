@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Mix, Semitones, Tween,
+};
+
+use super::{CommandWriters, PitchShiftBuilder};
+
+/// Controls a pitch shifter effect.
+#[derive(Debug)]
+pub struct PitchShiftHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<PitchShiftBuilder>>,
+}
+
+impl PitchShiftHandle {
+	/// Bypasses or stops bypassing the pitch shifter, crossfading between
+	/// its input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the pitch shifter stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
+		/// Sets how much the pitch of the audio is raised or lowered.
+		shift: Semitones => shift,
+
+		/// Sets how much dry (unprocessed) signal should be blended
+		/// with the wet (processed) signal.
+		mix: Mix => mix,
+	}
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use crate::{
+	effect::{
+		test_helpers::{init, process, sine, SAMPLE_RATE},
+		Effect,
+	},
+	frame::Frame,
+	Semitones,
+};
+
+use super::{command_writers_and_readers, PitchShift, PitchShiftBuilder};
+
+fn pitch_shift(builder: PitchShiftBuilder) -> PitchShift {
+	init(PitchShift::new(builder, command_writers_and_readers().1))
+}
+
+/// Estimates the frequency of the left channel of `frames` (in Hz) by
+/// counting how many times it rises past zero.
+fn frequency(frames: &[Frame]) -> f64 {
+	let rising_zero_crossings = frames
+		.windows(2)
+		.filter(|pair| pair[0].left < 0.0 && pair[1].left >= 0.0)
+		.count();
+	rising_zero_crossings as f64 * SAMPLE_RATE as f64 / frames.len() as f64
+}
+
+/// Tests that a pitch shifter that doesn't shift the pitch only delays
+/// its input by the latency it reports.
+#[test]
+fn delays_by_latency_without_shift() {
+	let mut pitch_shift = pitch_shift(PitchShiftBuilder::new());
+	let latency = pitch_shift.latency_frames();
+	assert_eq!(latency, 1203);
+	let input: Vec<Frame> = (0..4096)
+		.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+		.collect();
+	let output = process(&mut pitch_shift, &input);
+	for (i, frame) in output.iter().enumerate() {
+		let expected = i.checked_sub(latency).map_or(Frame::ZERO, |i| input[i]);
+		assert!((frame.left - expected.left).abs() < 1.0e-6);
+		assert!((frame.right - expected.right).abs() < 1.0e-6);
+	}
+}
+
+/// Tests that the pitch of a sine wave is raised and lowered by the
+/// expected amount.
+#[test]
+fn shifts_pitch() {
+	for shift in [-4.0, -3.0, 3.0, 4.0] {
+		let mut pitch_shift = pitch_shift(PitchShiftBuilder::new().shift(Semitones(shift)));
+		let output = process(&mut pitch_shift, &sine(440.0, 1.0, 48_000));
+		let expected = 440.0 * 2.0f64.powf(shift / 12.0);
+		let actual = frequency(&output[4800..]);
+		assert!(
+			(actual / expected - 1.0).abs() < 0.02,
+			"expected {expected} Hz, got {actual} Hz"
+		);
+	}
+}
+
+/// Tests that the latency changes with the grain size.
+#[test]
+fn latency_depends_on_grain_size() {
+	let pitch_shift = pitch_shift(PitchShiftBuilder::new().grain_size(Duration::from_millis(20)));
+	assert_eq!(pitch_shift.latency_frames(), 483);
+}
+
+/// Tests that the pitch shifter becomes idle once the audio it's delaying
+/// has been played.
+#[test]
+fn becomes_idle_after_delay() {
+	let mut pitch_shift = pitch_shift(PitchShiftBuilder::new().shift(Semitones(2.0)));
+	assert!(pitch_shift.is_idle());
+	process(&mut pitch_shift, &[Frame::from_mono(1.0)]);
+	assert!(!pitch_shift.is_idle());
+	process(&mut pitch_shift, &[Frame::ZERO; 2400]);
+	assert!(!pitch_shift.is_idle());
+	process(&mut pitch_shift, &[Frame::ZERO; 4]);
+	assert!(pitch_shift.is_idle());
+}
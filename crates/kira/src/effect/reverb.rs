@@ -9,7 +9,7 @@ pub use handle::*;
 use crate::{
 	command::{read_commands_into_parameters, ValueChangeCommand},
 	command_writers_and_readers,
-	effect::Effect,
+	effect::{bypass::BypassableEffect, is_silent, Effect},
 	frame::Frame,
 	info::Info,
 	Mix, Parameter,
@@ -43,6 +43,11 @@ struct Reverb {
 	stereo_width: Parameter,
 	mix: Parameter<Mix>,
 	state: ReverbState,
+	/// How many frames it takes audio to pass through the filters.
+	tail_length: usize,
+	/// How many frames in a row the reverb has received and output
+	/// silence.
+	quiet_frames: usize,
 }
 
 impl Reverb {
@@ -56,6 +61,8 @@ impl Reverb {
 			stereo_width: Parameter::new(settings.stereo_width, 1.0),
 			mix: Parameter::new(settings.mix, Mix(0.5)),
 			state: ReverbState::Uninitialized,
+			tail_length: 0,
+			quiet_frames: 0,
 		}
 	}
 
@@ -67,6 +74,13 @@ impl Reverb {
 			((buffer_size as f64) * sample_rate_factor) as usize
 		};
 
+		// the longest comb filter followed by every all-pass filter
+		self.tail_length = adjust_buffer_size(1617 + STEREO_SPREAD)
+			+ [556, 441, 341, 225]
+				.map(|buffer_size| adjust_buffer_size(buffer_size + STEREO_SPREAD))
+				.iter()
+				.sum::<usize>();
+		self.quiet_frames = self.tail_length;
 		self.state = ReverbState::Initialized {
 			comb_filters: [
 				(
@@ -137,6 +151,10 @@ impl Effect for Reverb {
 		read_commands_into_parameters!(self, feedback, damping, stereo_width, mix);
 	}
 
+	fn is_idle(&self) -> bool {
+		self.quiet_frames >= self.tail_length
+	}
+
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
 		if let ReverbState::Initialized {
 			comb_filters,
@@ -175,6 +193,11 @@ impl Effect for Reverb {
 					output.left * wet_1 + output.right * wet_2,
 					output.right * wet_1 + output.left * wet_2,
 				);
+				if is_silent(*frame) && is_silent(output) {
+					self.quiet_frames = self.quiet_frames.saturating_add(1);
+				} else {
+					self.quiet_frames = 0;
+				}
 				*frame = output * mix.sqrt() + *frame * (1.0 - mix).sqrt()
 			}
 		} else {
@@ -183,6 +206,26 @@ impl Effect for Reverb {
 	}
 }
 
+impl BypassableEffect for Reverb {
+	fn clear_tail(&mut self) {
+		if let ReverbState::Initialized {
+			comb_filters,
+			all_pass_filters,
+		} = &mut self.state
+		{
+			for (left, right) in comb_filters {
+				left.clear();
+				right.clear();
+			}
+			for (left, right) in all_pass_filters {
+				left.clear();
+				right.clear();
+			}
+			self.quiet_frames = self.tail_length;
+		}
+	}
+}
+
 command_writers_and_readers! {
 	set_feedback: ValueChangeCommand<f64>,
 	set_damping: ValueChangeCommand<f64>,
@@ -15,6 +15,10 @@ impl AllPassFilter {
 		}
 	}
 
+	pub fn clear(&mut self) {
+		self.buffer.fill(0.0);
+	}
+
 	#[must_use]
 	pub fn process(&mut self, input: f32) -> f32 {
 		let buffer_output = self.buffer[self.current_index];
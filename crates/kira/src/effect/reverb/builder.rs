@@ -1,5 +1,5 @@
 use crate::{
-	effect::{Effect, EffectBuilder},
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
 	Mix, Value,
 };
 
@@ -83,14 +83,37 @@ impl Default for ReverbBuilder {
 	}
 }
 
+impl ReverbBuilder {
+	/// Builds the reverb, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, ReverbHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			Reverb::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			ReverbHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
 impl EffectBuilder for ReverbBuilder {
 	type Handle = ReverbHandle;
 
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
-		let (command_writers, command_readers) = command_writers_and_readers();
-		(
-			Box::new(Reverb::new(self, command_readers)),
-			ReverbHandle { command_writers },
-		)
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for ReverbBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
 	}
 }
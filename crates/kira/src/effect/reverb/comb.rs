@@ -15,6 +15,11 @@ impl CombFilter {
 		}
 	}
 
+	pub fn clear(&mut self) {
+		self.filter_store = 0.0;
+		self.buffer.fill(0.0);
+	}
+
 	#[must_use]
 	pub fn process(&mut self, input: f32, feedback: f32, damp: f32) -> f32 {
 		let output = self.buffer[self.current_index];
@@ -1,29 +1,48 @@
-use crate::{command::handle_param_setters, Mix};
+use std::sync::Arc;
 
-use super::CommandWriters;
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Mix, Tween,
+};
+
+use super::{CommandWriters, ReverbBuilder};
 
 /// Controls a reverb effect.
 #[derive(Debug)]
 pub struct ReverbHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<ReverbBuilder>>,
 }
 
 impl ReverbHandle {
-	handle_param_setters! {
+	/// Bypasses or stops bypassing the reverb, crossfading between its input
+	/// and output with the given tween.
+	///
+	/// Once it's fully bypassed, the reverb stops processing audio, and its
+	/// reverberations are cleared when it stops being bypassed so old audio
+	/// isn't played.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
 		/// Sets how much the room reverberates. A higher value will
 		/// result in a bigger sounding room. 1.0 gives an infinitely
 		/// reverberating room.
-		feedback: f64,
+		feedback: f64 => feedback,
 
 		/// Sets how quickly high frequencies disappear from the reverberation.
-		damping: f64,
+		damping: f64 => damping,
 
 		/// Sets the stereo width of the reverb effect (0.0 being fully mono,
 		/// 1.0 being fully stereo).
-		stereo_width: f64,
+		stereo_width: f64 => stereo_width,
 
 		/// Sets how much dry (unprocessed) signal should be blended
 		/// with the wet (processed) signal.
-		mix: Mix,
+		mix: Mix => mix,
 	}
 }
@@ -0,0 +1,239 @@
+//! Warms up a sound by gently rounding off its peaks.
+//!
+//! Unlike [`Distortion`](super::distortion), the saturator shapes the audio
+//! at a multiple of the sample rate, which keeps the harmonics it adds from
+//! folding back down into audible frequencies as aliasing.
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+mod oversampler;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::f64::consts::TAU;
+
+use oversampler::Oversampler;
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::Frame,
+	info::Info,
+	Decibels, Mix, Parameter,
+};
+
+use super::{bypass::BypassableEffect, is_silent, Effect};
+
+/// The frequency below which the DC blocker removes audio (in Hz).
+const DC_BLOCKER_FREQUENCY: f64 = 10.0;
+/// How far the [`SaturationCurve::SoftKnee`] curve's input can go from
+/// `0.0` before the curve starts to bend.
+const SOFT_KNEE_THRESHOLD: f32 = 0.5;
+/// How far the [`SaturationCurve::Asymmetric`] curve is shifted along the
+/// tanh curve.
+const ASYMMETRIC_BIAS: f32 = 0.3;
+
+/// The shapes a saturator can bend audio with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SaturationCurve {
+	/// The audio is shaped by a tanh curve.
+	///
+	/// This rounds off quiet and loud parts of the audio alike, for a warm
+	/// sound that gets smoothly more saturated as the audio gets louder.
+	#[default]
+	Tanh,
+	/// The audio is left alone until it gets halfway to `1.0` or `-1.0`,
+	/// and then it's smoothly squashed so it never goes past them.
+	///
+	/// This keeps quiet audio clean and only saturates the peaks.
+	SoftKnee,
+	/// The audio is shaped by a tanh curve that bends positive values
+	/// sooner than negative ones.
+	///
+	/// This adds even harmonics as well as odd ones, like an overdriven tube
+	/// or tape machine.
+	Asymmetric,
+}
+
+impl SaturationCurve {
+	#[must_use]
+	fn apply(self, x: f32) -> f32 {
+		match self {
+			Self::Tanh => x.tanh(),
+			Self::SoftKnee => {
+				if x.abs() <= SOFT_KNEE_THRESHOLD {
+					x
+				} else {
+					// above the knee, the curve is a tanh curve scaled to fit
+					// between the knee and 1, which keeps the slope continuous
+					let range = 1.0 - SOFT_KNEE_THRESHOLD;
+					x.signum()
+						* (SOFT_KNEE_THRESHOLD
+							+ range * ((x.abs() - SOFT_KNEE_THRESHOLD) / range).tanh())
+				}
+			}
+			// the curve is shifted so it still goes through 0 and scaled so
+			// its slope at 0 is 1, like the other curves
+			Self::Asymmetric => {
+				let bias = ASYMMETRIC_BIAS.tanh();
+				((x + ASYMMETRIC_BIAS).tanh() - bias) / (1.0 - bias * bias)
+			}
+		}
+	}
+}
+
+/// How many times the sample rate a saturator shapes audio at.
+///
+/// Higher oversampling factors prevent more aliasing, but they take more
+/// CPU time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Oversampling {
+	/// The audio is shaped at the sample rate, without oversampling.
+	X1,
+	/// The audio is shaped at twice the sample rate.
+	#[default]
+	X2,
+	/// The audio is shaped at four times the sample rate.
+	X4,
+}
+
+impl Oversampling {
+	#[must_use]
+	fn factor(self) -> usize {
+		match self {
+			Self::X1 => 1,
+			Self::X2 => 2,
+			Self::X4 => 4,
+		}
+	}
+}
+
+struct Saturator {
+	command_readers: CommandReaders,
+	curve: SaturationCurve,
+	drive: Parameter<Decibels>,
+	mix: Parameter<Mix>,
+	oversampler: Oversampler,
+	/// Delays the dry signal so it lines up with the wet signal.
+	dry_buffer: Vec<Frame>,
+	/// The index in the dry buffer the next frame is written to.
+	dry_index: usize,
+	dc_blocker: DcBlocker,
+}
+
+impl Saturator {
+	#[must_use]
+	fn new(builder: SaturatorBuilder, command_readers: CommandReaders) -> Self {
+		let oversampler = Oversampler::new(builder.oversampling);
+		Self {
+			command_readers,
+			curve: builder.curve,
+			drive: Parameter::new(builder.drive, Decibels::IDENTITY),
+			mix: Parameter::new(builder.mix, Mix::WET),
+			dry_buffer: vec![Frame::ZERO; oversampler.latency_frames()],
+			dry_index: 0,
+			oversampler,
+			dc_blocker: DcBlocker::default(),
+		}
+	}
+
+	/// Returns the dry frame from [`latency_frames`](Effect::latency_frames)
+	/// frames ago and stores `input` in its place.
+	#[must_use]
+	fn delay_dry(&mut self, input: Frame) -> Frame {
+		if self.dry_buffer.is_empty() {
+			return input;
+		}
+		let output = std::mem::replace(&mut self.dry_buffer[self.dry_index], input);
+		self.dry_index = (self.dry_index + 1) % self.dry_buffer.len();
+		output
+	}
+}
+
+impl Effect for Saturator {
+	fn on_start_processing(&mut self) {
+		if let Some(curve) = self.command_readers.set_curve.read() {
+			self.curve = curve;
+		}
+		read_commands_into_parameters!(self, drive, mix);
+	}
+
+	// the saturator is idle once the audio it's delaying has been played
+	fn is_idle(&self) -> bool {
+		self.oversampler.is_silent()
+			&& self.dc_blocker.is_silent()
+			&& self.dry_buffer.iter().all(|frame| is_silent(*frame))
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.drive.update(dt * input.len() as f64, info);
+		self.mix.update(dt * input.len() as f64, info);
+
+		let dc_blocker_coefficient = (-TAU * DC_BLOCKER_FREQUENCY * dt).exp() as f32;
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let drive = self.drive.interpolated_value(time_in_chunk).as_amplitude();
+			let mix = self.mix.interpolated_value(time_in_chunk).0.clamp(0.0, 1.0);
+
+			let curve = self.curve;
+			let wet = self.oversampler.process(*frame, |frame| {
+				Frame::new(
+					curve.apply(frame.left * drive),
+					curve.apply(frame.right * drive),
+				) / drive
+			});
+			// asymmetric curves push the audio away from 0, so the offset is
+			// filtered out
+			let wet = self.dc_blocker.process(wet, dc_blocker_coefficient);
+			let dry = self.delay_dry(*frame);
+
+			*frame = wet * mix.sqrt() + dry * (1.0 - mix).sqrt();
+		}
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.oversampler.latency_frames()
+	}
+}
+
+impl BypassableEffect for Saturator {
+	fn clear_tail(&mut self) {
+		self.oversampler.reset();
+		self.dry_buffer.fill(Frame::ZERO);
+		self.dc_blocker = DcBlocker::default();
+	}
+}
+
+/// A one-pole highpass filter that removes DC offset.
+#[derive(Debug, Clone, Copy, Default)]
+struct DcBlocker {
+	input: Frame,
+	output: Frame,
+}
+
+impl DcBlocker {
+	#[must_use]
+	fn is_silent(&self) -> bool {
+		is_silent(self.input) && is_silent(self.output)
+	}
+
+	#[must_use]
+	fn process(&mut self, input: Frame, coefficient: f32) -> Frame {
+		self.output = input - self.input + self.output * coefficient;
+		self.input = input;
+		self.output
+	}
+}
+
+command_writers_and_readers! {
+	set_curve: SaturationCurve,
+	set_drive: ValueChangeCommand<Decibels>,
+	set_mix: ValueChangeCommand<Mix>,
+}
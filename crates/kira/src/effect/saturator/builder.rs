@@ -0,0 +1,119 @@
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Decibels, Mix, Value,
+};
+
+use super::{
+	command_writers_and_readers, Oversampling, SaturationCurve, Saturator, SaturatorHandle,
+};
+
+/// Configures a saturator effect.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SaturatorBuilder {
+	/// The shape the audio is bent with.
+	pub curve: SaturationCurve,
+	/// The factor to multiply the signal by before it's shaped.
+	///
+	/// The signal is divided by the same factor afterwards, so more drive
+	/// means more saturation rather than louder output.
+	pub drive: Value<Decibels>,
+	/// How much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal.
+	pub mix: Value<Mix>,
+	/// How many times the sample rate the audio is shaped at.
+	///
+	/// Oversampling delays the output by 47 frames, which is reported as
+	/// the effect's latency. Defaults to [`Oversampling::X2`].
+	pub oversampling: Oversampling,
+}
+
+impl SaturatorBuilder {
+	/// Creates a new [`SaturatorBuilder`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the shape the audio is bent with.
+	#[must_use = "This method consumes self and returns a modified SaturatorBuilder, so the return value should be used"]
+	pub fn curve(self, curve: SaturationCurve) -> Self {
+		Self { curve, ..self }
+	}
+
+	/// Sets the factor to multiply the signal by before it's shaped.
+	#[must_use = "This method consumes self and returns a modified SaturatorBuilder, so the return value should be used"]
+	pub fn drive(self, drive: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			drive: drive.into(),
+			..self
+		}
+	}
+
+	/// Sets how much dry (unprocessed) signal should be blended
+	/// with the wet (processed) signal. `0.0` means only the dry
+	/// signal will be heard. `1.0` means only the wet signal will
+	/// be heard.
+	#[must_use = "This method consumes self and returns a modified SaturatorBuilder, so the return value should be used"]
+	pub fn mix(self, mix: impl Into<Value<Mix>>) -> Self {
+		Self {
+			mix: mix.into(),
+			..self
+		}
+	}
+
+	/// Sets how many times the sample rate the audio is shaped at.
+	///
+	/// Oversampling delays the output by 47 frames, which is reported as
+	/// the effect's latency.
+	#[must_use = "This method consumes self and returns a modified SaturatorBuilder, so the return value should be used"]
+	pub fn oversampling(self, oversampling: Oversampling) -> Self {
+		Self {
+			oversampling,
+			..self
+		}
+	}
+
+	/// Builds the saturator, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, SaturatorHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			Saturator::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			SaturatorHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
+impl Default for SaturatorBuilder {
+	fn default() -> Self {
+		Self {
+			curve: Default::default(),
+			drive: Value::Fixed(Decibels::IDENTITY),
+			mix: Value::Fixed(Mix::WET),
+			oversampling: Default::default(),
+		}
+	}
+}
+
+impl EffectBuilder for SaturatorBuilder {
+	type Handle = SaturatorHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for SaturatorBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
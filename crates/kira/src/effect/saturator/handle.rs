@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Mix, Tween,
+};
+
+use super::{CommandWriters, SaturationCurve, SaturatorBuilder};
+
+/// Controls a saturator effect.
+#[derive(Debug)]
+pub struct SaturatorHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<SaturatorBuilder>>,
+}
+
+impl SaturatorHandle {
+	/// Bypasses or stops bypassing the saturator, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the saturator stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	/// Sets the shape the audio is bent with.
+	pub fn set_curve(&mut self, curve: SaturationCurve) {
+		self.template.update(|builder| builder.curve = curve);
+		self.command_writers.set_curve.write(curve)
+	}
+
+	template_param_setters! {
+		/// Sets how much saturation should be applied.
+		drive: Decibels => drive,
+
+		/// Sets how much dry (unprocessed) signal should be blended
+		/// with the wet (processed) signal.
+		mix: Mix => mix,
+	}
+}
@@ -0,0 +1,157 @@
+use std::f64::consts::PI;
+
+use crate::{effect::is_silent, frame::Frame};
+
+use super::Oversampling;
+
+/// How many taps of the anti-aliasing filter are used for each of the
+/// frames the input is upsampled to.
+const TAPS_PER_PHASE: usize = 48;
+/// How much the anti-aliasing filter turns down frequencies above the
+/// original Nyquist frequency (in decibels).
+const STOPBAND_ATTENUATION: f64 = 80.0;
+
+/// Runs a function at a multiple of the sample rate by upsampling the
+/// input, calling the function for each upsampled frame, and filtering and
+/// downsampling the result.
+///
+/// The same linear-phase lowpass filter is used for the upsampling and
+/// downsampling, and it's split into one phase per upsampled frame so the
+/// zeros between the input frames never have to be multiplied.
+pub(super) struct Oversampler {
+	factor: usize,
+	/// The taps of the lowpass filter, which has `factor * TAPS_PER_PHASE`
+	/// taps.
+	coefficients: Vec<f32>,
+	/// The most recent input frames.
+	input_history: Vec<Frame>,
+	/// The index in the input history the last frame was written to.
+	input_index: usize,
+	/// The most recent upsampled frames after they've been processed.
+	output_history: Vec<Frame>,
+	/// The index in the output history the last frame was written to.
+	output_index: usize,
+}
+
+impl Oversampler {
+	#[must_use]
+	pub fn new(oversampling: Oversampling) -> Self {
+		let factor = oversampling.factor();
+		let coefficients = if factor > 1 {
+			lowpass_coefficients(factor)
+		} else {
+			vec![]
+		};
+		Self {
+			factor,
+			input_history: vec![Frame::ZERO; TAPS_PER_PHASE],
+			input_index: 0,
+			output_history: vec![Frame::ZERO; coefficients.len()],
+			output_index: 0,
+			coefficients,
+		}
+	}
+
+	/// Returns how many frames the filters delay the audio by.
+	#[must_use]
+	pub fn latency_frames(&self) -> usize {
+		if self.factor > 1 {
+			// each filter delays its input by half its length minus half a
+			// frame at the higher sample rate, and the last upsampled frame of
+			// each input frame is the one that's downsampled
+			TAPS_PER_PHASE - 1
+		} else {
+			0
+		}
+	}
+
+	#[must_use]
+	pub fn is_silent(&self) -> bool {
+		self.input_history
+			.iter()
+			.chain(&self.output_history)
+			.all(|frame| is_silent(*frame))
+	}
+
+	pub fn reset(&mut self) {
+		self.input_history.fill(Frame::ZERO);
+		self.output_history.fill(Frame::ZERO);
+	}
+
+	/// Runs `process` on `input` at the higher sample rate and returns the
+	/// result at the original sample rate.
+	#[must_use]
+	pub fn process(&mut self, input: Frame, mut process: impl FnMut(Frame) -> Frame) -> Frame {
+		if self.factor == 1 {
+			return process(input);
+		}
+		self.input_index = (self.input_index + 1) % TAPS_PER_PHASE;
+		self.input_history[self.input_index] = input;
+		let output_len = self.output_history.len();
+		for phase in 0..self.factor {
+			let mut upsampled = Frame::ZERO;
+			for i in 0..TAPS_PER_PHASE {
+				let frame =
+					self.input_history[(self.input_index + TAPS_PER_PHASE - i) % TAPS_PER_PHASE];
+				upsampled += frame * self.coefficients[i * self.factor + phase];
+			}
+			// the upsampled signal only has one non-zero frame for every
+			// `factor` frames, so it's turned up to keep the same volume
+			self.output_index = (self.output_index + 1) % output_len;
+			self.output_history[self.output_index] = process(upsampled * self.factor as f32);
+		}
+		let mut output = Frame::ZERO;
+		for (i, coefficient) in self.coefficients.iter().enumerate() {
+			output += self.output_history[(self.output_index + output_len - i) % output_len]
+				* *coefficient;
+		}
+		output
+	}
+}
+
+/// Designs a Kaiser-windowed sinc lowpass filter for audio at `factor`
+/// times the original sample rate that passes the frequencies below the
+/// original Nyquist frequency.
+#[must_use]
+fn lowpass_coefficients(factor: usize) -> Vec<f32> {
+	let len = factor * TAPS_PER_PHASE;
+	// the transition band is as wide as the filter length allows, and it
+	// ends at the original Nyquist frequency so nothing above it is folded
+	// back down when the audio is downsampled
+	let transition_width = (STOPBAND_ATTENUATION - 7.95) / (14.36 * TAPS_PER_PHASE as f64);
+	let cutoff = (0.5 - transition_width / 2.0) / factor as f64;
+	let beta = 0.1102 * (STOPBAND_ATTENUATION - 8.7);
+	let center = (len - 1) as f64 / 2.0;
+	let coefficients: Vec<f64> = (0..len)
+		.map(|i| {
+			let offset = i as f64 - center;
+			let sinc = if offset == 0.0 {
+				2.0 * cutoff
+			} else {
+				(2.0 * PI * cutoff * offset).sin() / (PI * offset)
+			};
+			let window = bessel_i0(beta * (1.0 - (offset / center).powi(2)).max(0.0).sqrt())
+				/ bessel_i0(beta);
+			sinc * window
+		})
+		.collect();
+	let sum: f64 = coefficients.iter().sum();
+	coefficients
+		.into_iter()
+		.map(|coefficient| (coefficient / sum) as f32)
+		.collect()
+}
+
+/// The zeroth-order modified Bessel function of the first kind.
+#[must_use]
+fn bessel_i0(x: f64) -> f64 {
+	let mut sum = 1.0;
+	let mut term = 1.0;
+	let mut k = 1.0;
+	while term > sum * 1.0e-12 {
+		term *= (x / (2.0 * k)).powi(2);
+		sum += term;
+		k += 1.0;
+	}
+	sum
+}
@@ -0,0 +1,142 @@
+use std::f64::consts::TAU;
+
+use crate::{
+	effect::{
+		test_helpers::{init, process, sine, SAMPLE_RATE},
+		Effect,
+	},
+	frame::Frame,
+	Decibels, Mix,
+};
+
+use super::{
+	command_writers_and_readers, Oversampling, SaturationCurve, Saturator, SaturatorBuilder,
+};
+
+fn saturator(builder: SaturatorBuilder) -> Saturator {
+	init(Saturator::new(builder, command_writers_and_readers().1))
+}
+
+/// Returns the amplitude of the given frequency in the left channel of
+/// `frames`.
+fn magnitude(frames: &[Frame], frequency: f64) -> f64 {
+	let (real, imaginary) =
+		frames
+			.iter()
+			.enumerate()
+			.fold((0.0, 0.0), |(real, imaginary), (i, frame)| {
+				let angle = i as f64 * frequency * TAU / SAMPLE_RATE as f64;
+				(
+					real + frame.left as f64 * angle.cos(),
+					imaginary - frame.left as f64 * angle.sin(),
+				)
+			});
+	2.0 * (real * real + imaginary * imaginary).sqrt() / frames.len() as f64
+}
+
+/// Returns the amplitude of the loudest harmonic of a tanh-saturated sine
+/// wave that's folded back down below the Nyquist frequency, relative to the
+/// amplitude of the sine wave.
+fn loudest_alias(oversampling: Oversampling) -> f64 {
+	// 4800 frames is exactly 1002 cycles of the sine wave, and every
+	// harmonic lands on a multiple of 10 Hz, so none of them leak into the
+	// others
+	let frequency = 5010.0;
+	let mut saturator = saturator(
+		SaturatorBuilder::new()
+			.drive(Decibels(12.0))
+			.oversampling(oversampling),
+	);
+	let output = process(&mut saturator, &sine(frequency, 1.0, 6000));
+	let output = &output[1200..];
+	let fundamental = magnitude(output, frequency);
+	let nyquist = SAMPLE_RATE as f64 / 2.0;
+	(3..=19)
+		.step_by(2)
+		.map(|harmonic| harmonic as f64 * frequency)
+		.filter(|&harmonic_frequency| harmonic_frequency > nyquist)
+		.map(|harmonic_frequency| {
+			let folded = harmonic_frequency % SAMPLE_RATE as f64;
+			let alias = if folded > nyquist {
+				SAMPLE_RATE as f64 - folded
+			} else {
+				folded
+			};
+			magnitude(output, alias) / fundamental
+		})
+		.fold(0.0, f64::max)
+}
+
+/// Tests that 4x oversampling keeps the harmonics of a high sine wave from
+/// folding back down into audible frequencies.
+#[test]
+fn oversampling_prevents_aliasing() {
+	// without oversampling, the harmonics are clearly audible as aliasing
+	assert!(loudest_alias(Oversampling::X1) > 1.0e-2);
+	assert!(loudest_alias(Oversampling::X4) < 1.0e-3);
+}
+
+/// Tests that the dry signal is delayed to line up with the wet signal.
+#[test]
+fn delays_dry_signal_by_latency() {
+	for oversampling in [Oversampling::X1, Oversampling::X2, Oversampling::X4] {
+		let mut saturator = saturator(
+			SaturatorBuilder::new()
+				.oversampling(oversampling)
+				.mix(Mix::DRY),
+		);
+		let latency = saturator.latency_frames();
+		let input: Vec<Frame> = (0..512)
+			.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+			.collect();
+		let output = process(&mut saturator, &input);
+		for (i, frame) in output.iter().enumerate() {
+			let expected = i.checked_sub(latency).map_or(Frame::ZERO, |i| input[i]);
+			assert_eq!(*frame, expected);
+		}
+	}
+}
+
+/// Tests that quiet audio passes through the oversampling filters with the
+/// same volume and the reported latency.
+#[test]
+fn oversampled_audio_lines_up_with_input() {
+	for oversampling in [Oversampling::X2, Oversampling::X4] {
+		let mut saturator = saturator(SaturatorBuilder::new().oversampling(oversampling));
+		let latency = saturator.latency_frames();
+		assert_eq!(latency, 47);
+		let input = sine(1000.0, 0.01, 4800);
+		let output = process(&mut saturator, &input);
+		// the DC blocker shifts the phase of the sine wave very slightly
+		for i in 2400..4800 {
+			assert!((output[i].left - input[i - latency].left).abs() < 2.0e-4);
+		}
+	}
+}
+
+/// Tests that the DC offset added by the asymmetric curve is removed.
+#[test]
+fn removes_dc_offset() {
+	let mut saturator = saturator(
+		SaturatorBuilder::new()
+			.curve(SaturationCurve::Asymmetric)
+			.drive(Decibels(18.0)),
+	);
+	// 100 Hz fits exactly 480 times in 48,000 frames
+	let output = process(&mut saturator, &sine(100.0, 1.0, 48_000));
+	let tail = &output[24_000..];
+	let mean = tail.iter().map(|frame| frame.left as f64).sum::<f64>() / tail.len() as f64;
+	assert!(mean.abs() < 1.0e-3);
+}
+
+/// Tests that the saturator becomes idle once the audio it's delaying has
+/// been played.
+#[test]
+fn becomes_idle_after_latency() {
+	let mut saturator = saturator(SaturatorBuilder::new().oversampling(Oversampling::X4));
+	assert!(saturator.is_idle());
+	process(&mut saturator, &[Frame::from_mono(1.0)]);
+	assert!(!saturator.is_idle());
+	process(&mut saturator, &[Frame::ZERO; 48_000]);
+	assert!(saturator.is_idle());
+}
@@ -0,0 +1,154 @@
+//! Adjusts the stereo image of audio using mid/side processing.
+//!
+//! The stereo tool splits its input into a mid signal (what the left and
+//! right channels have in common) and a side signal (how they differ),
+//! changes the volume of each, and combines them back into left and right
+//! channels. It also measures how similar the channels of its output are,
+//! which can be read with [`StereoToolHandle::correlation`].
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::{
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	command_writers_and_readers,
+	frame::Frame,
+	info::Info,
+	Decibels, Parameter, Value,
+};
+
+use super::{bypass::BypassableEffect, Effect};
+
+/// How long the correlation meter takes to respond to changes in the audio.
+const CORRELATION_WINDOW: Duration = Duration::from_millis(300);
+/// The quietest the output can be (as the product of the power of each
+/// channel) for the correlation to be measured.
+const MIN_CORRELATION_POWER: f32 = 1.0e-12;
+
+struct StereoTool {
+	command_readers: CommandReaders,
+	mid_gain: Parameter<Decibels>,
+	side_gain: Parameter<Decibels>,
+	width: Parameter,
+	/// How much the output is folded down to mono, from `0.0` to `1.0`.
+	mono: Parameter,
+	/// The average of `left * right` of the output.
+	product: f32,
+	/// The average of `left * left` and `right * right` of the output.
+	power: Frame,
+	correlation: Arc<AtomicU32>,
+}
+
+impl StereoTool {
+	#[must_use]
+	fn new(
+		builder: StereoToolBuilder,
+		command_readers: CommandReaders,
+		correlation: Arc<AtomicU32>,
+	) -> Self {
+		Self {
+			command_readers,
+			mid_gain: Parameter::new(builder.mid_gain, Decibels::IDENTITY),
+			side_gain: Parameter::new(builder.side_gain, Decibels::IDENTITY),
+			width: Parameter::new(builder.width, 1.0),
+			mono: Parameter::new(mono_amount(builder.mono), 0.0),
+			product: 0.0,
+			power: Frame::ZERO,
+			correlation,
+		}
+	}
+
+	/// Adds a frame of output to the correlation meter.
+	fn measure(&mut self, frame: Frame, smoothing: f32) {
+		self.product += (frame.left * frame.right - self.product) * smoothing;
+		self.power.left += (frame.left * frame.left - self.power.left) * smoothing;
+		self.power.right += (frame.right * frame.right - self.power.right) * smoothing;
+	}
+}
+
+impl Effect for StereoTool {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, mid_gain, side_gain, width, mono);
+	}
+
+	// silence stays silent, but the correlation meter has to keep running
+	// until it's gone back to 0
+	fn is_idle(&self) -> bool {
+		self.power.left * self.power.right <= MIN_CORRELATION_POWER
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		let chunk_duration = dt * input.len() as f64;
+		self.mid_gain.update(chunk_duration, info);
+		self.side_gain.update(chunk_duration, info);
+		self.width.update(chunk_duration, info);
+		self.mono.update(chunk_duration, info);
+
+		let smoothing = 1.0 - (-dt / CORRELATION_WINDOW.as_secs_f64()).exp() as f32;
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let mid_gain = self.mid_gain.interpolated_value(time_in_chunk);
+			let side_gain = self.side_gain.interpolated_value(time_in_chunk);
+			let width = self
+				.width
+				.interpolated_value(time_in_chunk)
+				.clamp(0.0, StereoToolBuilder::MAX_WIDTH);
+			let mono = self.mono.interpolated_value(time_in_chunk).clamp(0.0, 1.0);
+			// encoding and decoding the mid and side signals can round the
+			// audio slightly, so it's skipped when nothing would change
+			if mid_gain != Decibels::IDENTITY
+				|| side_gain != Decibels::IDENTITY
+				|| width != 1.0
+				|| mono != 0.0
+			{
+				let mid = (frame.left + frame.right) / 2.0 * mid_gain.as_amplitude();
+				// folding down to mono removes the side signal, just like
+				// narrowing the width does
+				let side = (frame.left - frame.right) / 2.0
+					* side_gain.as_amplitude()
+					* (width * (1.0 - mono)) as f32;
+				*frame = Frame::new(mid + side, mid - side);
+			}
+			self.measure(*frame, smoothing);
+		}
+
+		let power = self.power.left * self.power.right;
+		let correlation = if power > MIN_CORRELATION_POWER {
+			(self.product / power.sqrt()).clamp(-1.0, 1.0)
+		} else {
+			0.0
+		};
+		self.correlation
+			.store(correlation.to_bits(), Ordering::Relaxed);
+	}
+}
+
+impl BypassableEffect for StereoTool {}
+
+/// Returns how much the output should be folded down to mono.
+#[must_use]
+fn mono_amount(mono: bool) -> Value<f64> {
+	Value::Fixed(if mono { 1.0 } else { 0.0 })
+}
+
+command_writers_and_readers! {
+	set_mid_gain: ValueChangeCommand<Decibels>,
+	set_side_gain: ValueChangeCommand<Decibels>,
+	set_width: ValueChangeCommand<f64>,
+	set_mono: ValueChangeCommand<f64>,
+}
@@ -0,0 +1,140 @@
+use std::sync::{atomic::AtomicU32, Arc};
+
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Decibels, Value,
+};
+
+use super::{command_writers_and_readers, StereoTool, StereoToolHandle};
+
+/**
+Configures a stereo tool effect.
+
+# Examples
+
+Narrow a music track so dialogue stands out more:
+
+```
+use kira::{effect::stereo_tool::StereoToolBuilder, track::TrackBuilder};
+
+let builder = TrackBuilder::new().with_effect(StereoToolBuilder::new().width(0.5));
+```
+*/
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StereoToolBuilder {
+	/// How much the volume of the mid signal (what the left and right
+	/// channels have in common) is changed.
+	pub mid_gain: Value<Decibels>,
+	/// How much the volume of the side signal (how the left and right
+	/// channels differ) is changed.
+	pub side_gain: Value<Decibels>,
+	/// How wide the stereo image is.
+	///
+	/// `0.0` mixes the audio down to mono, `1.0` leaves the audio unchanged,
+	/// and values above `1.0` exaggerate the differences between the left and
+	/// right channels. This is kept between `0.0` and `2.0`.
+	///
+	/// Widening the audio makes the channels more out of phase with each
+	/// other, so parts of the audio can get quieter or disappear when it's
+	/// played back in mono.
+	pub width: Value<f64>,
+	/// Whether the output is folded down to mono.
+	pub mono: bool,
+}
+
+impl StereoToolBuilder {
+	pub(crate) const MAX_WIDTH: f64 = 2.0;
+
+	/// Creates a new [`StereoToolBuilder`] with the default settings, which
+	/// leave the audio unchanged.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			mid_gain: Value::Fixed(Decibels::IDENTITY),
+			side_gain: Value::Fixed(Decibels::IDENTITY),
+			width: Value::Fixed(1.0),
+			mono: false,
+		}
+	}
+
+	/// Sets how much the volume of the mid signal (what the left and right
+	/// channels have in common) is changed. Defaults to 0 dB.
+	#[must_use = "This method consumes self and returns a modified StereoToolBuilder, so the return value should be used"]
+	pub fn mid_gain(self, mid_gain: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			mid_gain: mid_gain.into(),
+			..self
+		}
+	}
+
+	/// Sets how much the volume of the side signal (how the left and right
+	/// channels differ) is changed. Defaults to 0 dB.
+	#[must_use = "This method consumes self and returns a modified StereoToolBuilder, so the return value should be used"]
+	pub fn side_gain(self, side_gain: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			side_gain: side_gain.into(),
+			..self
+		}
+	}
+
+	/// Sets how wide the stereo image is. Defaults to `1.0`.
+	///
+	/// `0.0` mixes the audio down to mono, `1.0` leaves the audio unchanged,
+	/// and values above `1.0` exaggerate the differences between the left and
+	/// right channels. This is kept between `0.0` and `2.0`.
+	#[must_use = "This method consumes self and returns a modified StereoToolBuilder, so the return value should be used"]
+	pub fn width(self, width: impl Into<Value<f64>>) -> Self {
+		Self {
+			width: width.into(),
+			..self
+		}
+	}
+
+	/// Sets whether the output is folded down to mono. Defaults to `false`.
+	#[must_use = "This method consumes self and returns a modified StereoToolBuilder, so the return value should be used"]
+	pub fn mono(self, mono: bool) -> Self {
+		Self { mono, ..self }
+	}
+
+	/// Builds the stereo tool, which starts out bypassed if `bypassed` is
+	/// `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, StereoToolHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let correlation = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+		let (effect, set_bypassed) = Bypassable::new(
+			StereoTool::new(self, command_readers, correlation.clone()),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			StereoToolHandle {
+				command_writers,
+				set_bypassed,
+				template,
+				correlation,
+			},
+		)
+	}
+}
+
+impl Default for StereoToolBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl EffectBuilder for StereoToolBuilder {
+	type Handle = StereoToolHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for StereoToolBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
@@ -0,0 +1,71 @@
+use std::sync::{
+	atomic::{AtomicU32, Ordering},
+	Arc,
+};
+
+use crate::{
+	command::{CommandWriter, ValueChangeCommand},
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Tween,
+};
+
+use super::{mono_amount, CommandWriters, StereoToolBuilder};
+
+/// Controls a stereo tool effect.
+#[derive(Debug)]
+pub struct StereoToolHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<StereoToolBuilder>>,
+	pub(super) correlation: Arc<AtomicU32>,
+}
+
+impl StereoToolHandle {
+	/// Bypasses or stops bypassing the stereo tool, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the stereo tool stops processing audio,
+	/// and its correlation meter stops updating.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	/// Folds the output down to mono or stops folding it down, crossfading
+	/// between the stereo and mono output with the given tween.
+	pub fn set_mono(&mut self, mono: bool, tween: Tween) {
+		self.template.update(|builder| builder.mono = mono);
+		self.command_writers.set_mono.write(ValueChangeCommand {
+			target: mono_amount(mono),
+			tween,
+		})
+	}
+
+	/// Returns how similar the left and right channels of the stereo tool's
+	/// output are, from `-1.0` to `1.0`.
+	///
+	/// `1.0` means the channels are the same (the audio is mono), `0.0`
+	/// means they're unrelated, and `-1.0` means they're opposites, which
+	/// cancel each other out when the audio is played back in mono. It's
+	/// averaged over the last few hundred milliseconds of audio and updated
+	/// every time the audio thread processes a batch of audio, which makes
+	/// it useful for correlation meters. It goes back to `0.0` a few
+	/// seconds after the output goes silent.
+	#[must_use]
+	pub fn correlation(&self) -> f32 {
+		f32::from_bits(self.correlation.load(Ordering::Relaxed))
+	}
+
+	template_param_setters! {
+		/// Sets how much the volume of the mid signal (what the left and right
+		/// channels have in common) is changed.
+		mid_gain: Decibels => mid_gain,
+
+		/// Sets how much the volume of the side signal (how the left and right
+		/// channels differ) is changed.
+		side_gain: Decibels => side_gain,
+
+		/// Sets how wide the stereo image is, from `0.0` (mono) to `2.0`.
+		width: f64 => width,
+	}
+}
@@ -0,0 +1,102 @@
+use std::sync::{
+	atomic::{AtomicU32, Ordering},
+	Arc,
+};
+
+use crate::{
+	effect::{
+		test_helpers::{init, process},
+		Effect,
+	},
+	frame::Frame,
+	Decibels,
+};
+
+use super::{command_writers_and_readers, StereoTool, StereoToolBuilder};
+
+fn stereo_tool(builder: StereoToolBuilder) -> (StereoTool, Arc<AtomicU32>) {
+	let correlation = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+	let stereo_tool = init(StereoTool::new(
+		builder,
+		command_writers_and_readers().1,
+		correlation.clone(),
+	));
+	(stereo_tool, correlation)
+}
+
+fn input() -> Vec<Frame> {
+	(0..512)
+		.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos() * 0.3))
+		.collect()
+}
+
+/// Tests that a stereo tool with the default settings outputs exactly the
+/// audio it receives.
+#[test]
+fn passes_audio_through_unchanged_by_default() {
+	let (mut stereo_tool, _) = stereo_tool(StereoToolBuilder::new());
+	let input = input();
+	assert_eq!(process(&mut stereo_tool, &input), input);
+}
+
+/// Tests that a width of `0.0` and folding down to mono both output
+/// exactly the mono sum of the input.
+#[test]
+fn outputs_mono_sum() {
+	let input = input();
+	let expected: Vec<Frame> = input.iter().map(|frame| frame.as_mono()).collect();
+	let (mut narrow, _) = stereo_tool(StereoToolBuilder::new().width(0.0));
+	assert_eq!(process(&mut narrow, &input), expected);
+	let (mut mono, _) = stereo_tool(StereoToolBuilder::new().width(2.0).mono(true));
+	assert_eq!(process(&mut mono, &input), expected);
+}
+
+/// Tests that the mid and side gains change the volume of the mid and
+/// side signals.
+#[test]
+fn applies_mid_and_side_gain() {
+	let (mut stereo_tool, _) = stereo_tool(
+		StereoToolBuilder::new()
+			.mid_gain(Decibels::SILENCE)
+			.side_gain(Decibels(20.0 * 2.0f32.log10())),
+	);
+	let output = process(&mut stereo_tool, &[Frame::new(1.0, 0.5)]);
+	// the side signal is 0.25, which is doubled
+	assert!((output[0].left - 0.5).abs() < 1.0e-6);
+	assert!((output[0].right + 0.5).abs() < 1.0e-6);
+}
+
+/// Tests that the correlation meter measures how similar the channels of
+/// the output are.
+#[test]
+fn measures_correlation() {
+	let signal: Vec<f32> = (0..48_000).map(|i| (i as f32 * 0.05).sin()).collect();
+	let read = |correlation: &AtomicU32| f32::from_bits(correlation.load(Ordering::Relaxed));
+
+	let (mut unchanged, correlation) = stereo_tool(StereoToolBuilder::new());
+	assert_eq!(read(&correlation), 0.0);
+	let same: Vec<Frame> = signal.iter().map(|&x| Frame::new(x, x)).collect();
+	process(&mut unchanged, &same);
+	assert!((read(&correlation) - 1.0).abs() < 1.0e-3);
+	let opposite: Vec<Frame> = signal.iter().map(|&x| Frame::new(x, -x)).collect();
+	// the meter takes a moment to forget the earlier audio
+	process(&mut unchanged, &opposite);
+	assert!(read(&correlation) < -0.9);
+	process(&mut unchanged, &opposite);
+	assert!((read(&correlation) + 1.0).abs() < 1.0e-2);
+	// a width of 0 makes the output mono, even though the input isn't
+	let (mut narrow, correlation) = stereo_tool(StereoToolBuilder::new().width(0.0));
+	let unrelated: Vec<Frame> = signal
+		.iter()
+		.enumerate()
+		.map(|(i, &x)| Frame::new(x, (i as f32 * 0.13).cos()))
+		.collect();
+	process(&mut narrow, &unrelated);
+	assert!((read(&correlation) - 1.0).abs() < 1.0e-3);
+	// once the output has been silent for long enough, the meter goes back
+	// to 0 and the stereo tool becomes idle
+	assert!(!narrow.is_idle());
+	process(&mut narrow, &vec![Frame::ZERO; 48_000 * 5]);
+	assert_eq!(read(&correlation), 0.0);
+	assert!(narrow.is_idle());
+}
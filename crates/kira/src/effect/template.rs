@@ -0,0 +1,99 @@
+use std::{
+	fmt::{Debug, Formatter},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+};
+
+use super::Effect;
+
+/// Creates copies of an effect with the settings the effect has when the
+/// copy is made.
+///
+/// Effects can return one of these from [`Effect::template`] so tracks
+/// that use them can be copied with
+/// [`TrackHandle::builder_snapshot`](crate::track::TrackHandle::builder_snapshot).
+pub trait EffectTemplate: Send + Sync {
+	/// Creates a new effect with the current settings of the original
+	/// effect.
+	///
+	/// The new effect doesn't have any of the audio the original effect is
+	/// holding onto, like the echoes in a delay.
+	#[must_use]
+	fn create_effect(&self) -> Box<dyn Effect>;
+}
+
+impl Debug for dyn EffectTemplate {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("EffectTemplate").finish_non_exhaustive()
+	}
+}
+
+/// The builder of one of Kira's effects, which can build the effect in a
+/// bypassed state.
+pub(crate) trait TemplateBuilder: Clone + Send + 'static {
+	/// Builds the effect without a handle to control it.
+	#[must_use]
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect>;
+}
+
+/// An [`EffectTemplate`] for one of Kira's effects. The effect's handle
+/// keeps the template's builder up to date with the settings it sends to
+/// the effect.
+#[derive(Debug)]
+pub(crate) struct BuilderTemplate<B> {
+	builder: Mutex<B>,
+	bypassed: AtomicBool,
+}
+
+impl<B> BuilderTemplate<B> {
+	#[must_use]
+	pub fn new(builder: B, bypassed: bool) -> Arc<Self> {
+		Arc::new(Self {
+			builder: Mutex::new(builder),
+			bypassed: AtomicBool::new(bypassed),
+		})
+	}
+
+	pub fn update(&self, update: impl FnOnce(&mut B)) {
+		update(&mut self.builder.lock().expect("effect template mutex poisoned"));
+	}
+
+	pub fn set_bypassed(&self, bypassed: bool) {
+		self.bypassed.store(bypassed, Ordering::SeqCst);
+	}
+}
+
+impl<B: TemplateBuilder> EffectTemplate for BuilderTemplate<B> {
+	fn create_effect(&self) -> Box<dyn Effect> {
+		let builder = self
+			.builder
+			.lock()
+			.expect("effect template mutex poisoned")
+			.clone();
+		builder.build_effect(self.bypassed.load(Ordering::SeqCst))
+	}
+}
+
+/// Creates setters for the parameters of one of Kira's effects, which
+/// also update the effect's [`BuilderTemplate`].
+macro_rules! template_param_setters {
+	($($(#[$m:meta])* $name:ident: $type:ty => $field:tt),*$(,)?) => {
+		paste::paste! {
+			$(
+				$(#[$m])*
+				pub fn [<set_ $name>](&mut self, $name: impl Into<$crate::Value<$type>>, tween: $crate::tween::Tween) {
+					let target = $name.into();
+					self.template.update(|builder| builder.$field = target);
+					self.command_writers.[<set_ $name>].write($crate::command::ValueChangeCommand {
+						target,
+						tween,
+					})
+				}
+			)*
+		}
+	};
+}
+
+pub(crate) use template_param_setters;
@@ -0,0 +1,78 @@
+use std::f64::consts::TAU;
+
+use crate::{
+	effect::Effect,
+	frame::Frame,
+	info::{Info, MockInfoBuilder},
+};
+
+/// The sample rate effects are tested at.
+pub const SAMPLE_RATE: u32 = 48_000;
+/// The time between frames at [`SAMPLE_RATE`].
+pub const DT: f64 = 1.0 / SAMPLE_RATE as f64;
+/// How many frames effects are given at a time.
+pub const CHUNK_SIZE: usize = 128;
+
+/// Initializes an effect at [`SAMPLE_RATE`].
+#[must_use]
+pub fn init<T: Effect>(mut effect: T) -> T {
+	effect.init(SAMPLE_RATE, CHUNK_SIZE);
+	effect
+}
+
+/// Processes the input in chunks of [`CHUNK_SIZE`] frames and returns
+/// the output.
+pub fn process(effect: &mut dyn Effect, input: &[Frame]) -> Vec<Frame> {
+	process_with_info(effect, input, &MockInfoBuilder::new().build())
+}
+
+/// Like [`process`], but with the given `info`.
+pub fn process_with_info(effect: &mut dyn Effect, input: &[Frame], info: &Info) -> Vec<Frame> {
+	let mut output = input.to_vec();
+	for chunk in output.chunks_mut(CHUNK_SIZE) {
+		effect.process(chunk, DT, info);
+	}
+	output
+}
+
+/// Returns a mono sine wave at [`SAMPLE_RATE`].
+#[must_use]
+pub fn sine(frequency: f64, amplitude: f32, num_frames: usize) -> Vec<Frame> {
+	(0..num_frames)
+		.map(|i| {
+			Frame::from_mono(
+				amplitude * (i as f64 * frequency * TAU / SAMPLE_RATE as f64).sin() as f32,
+			)
+		})
+		.collect()
+}
+
+/// Processes enough of a signal for an effect's filters to settle and
+/// returns the peak amplitude of the last chunk.
+pub fn settled_peak(effect: &mut dyn Effect, signal: impl Fn(usize) -> f32) -> f32 {
+	let info = MockInfoBuilder::new().build();
+	let mut peak = 0.0;
+	for chunk in 0..100 {
+		effect.on_start_processing();
+		let mut input: Vec<Frame> = (0..CHUNK_SIZE)
+			.map(|i| Frame::from_mono(signal(chunk * CHUNK_SIZE + i)))
+			.collect();
+		effect.process(&mut input, DT, &info);
+		peak = input
+			.iter()
+			.map(|frame| frame.left.abs())
+			.fold(0.0, f32::max);
+	}
+	peak
+}
+
+/// A signal at the Nyquist frequency, the highest frequency that can be
+/// represented at the sample rate.
+#[must_use]
+pub fn nyquist(i: usize) -> f32 {
+	if i % 2 == 0 {
+		0.25
+	} else {
+		-0.25
+	}
+}
@@ -0,0 +1,148 @@
+//! Boosts or cuts the bass and treble of audio.
+//!
+//! This is meant for the kind of bass and treble settings players expect in
+//! an options menu. For more control over the frequencies that are adjusted,
+//! use an [`EqFilterBuilder`](super::eq_filter::EqFilterBuilder).
+
+#[cfg(test)]
+mod test;
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, frame::Frame, info::Info, Decibels,
+	Parameter, Value,
+};
+
+use super::{
+	bypass::BypassableEffect,
+	eq_filter::{Coefficients, EqFilterKind},
+	is_silent, Effect,
+};
+
+/// The corner frequency of the bass shelf in Hz.
+pub const BASS_FREQUENCY: f64 = 250.0;
+/// The corner frequency of the treble shelf in Hz.
+pub const TREBLE_FREQUENCY: f64 = 4000.0;
+
+const Q: f64 = std::f64::consts::FRAC_1_SQRT_2;
+
+struct ToneControl {
+	command_readers: CommandReaders,
+	bass: Shelf,
+	treble: Shelf,
+}
+
+impl ToneControl {
+	#[must_use]
+	fn new(builder: ToneControlBuilder, command_readers: CommandReaders) -> Self {
+		Self {
+			command_readers,
+			bass: Shelf::new(EqFilterKind::LowShelf, BASS_FREQUENCY, builder.bass),
+			treble: Shelf::new(EqFilterKind::HighShelf, TREBLE_FREQUENCY, builder.treble),
+		}
+	}
+}
+
+impl Effect for ToneControl {
+	fn on_start_processing(&mut self) {
+		self.bass
+			.gain
+			.read_command(&mut self.command_readers.set_bass);
+		self.treble
+			.gain
+			.read_command(&mut self.command_readers.set_treble);
+	}
+
+	fn is_idle(&self) -> bool {
+		self.bass.is_idle() && self.treble.is_idle()
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.bass.process(input, dt, info);
+		self.treble.process(input, dt, info);
+	}
+}
+
+impl BypassableEffect for ToneControl {
+	fn clear_tail(&mut self) {
+		self.bass.clear();
+		self.treble.clear();
+	}
+}
+
+/// One of the shelf filters of a tone control.
+struct Shelf {
+	kind: EqFilterKind,
+	frequency: f64,
+	gain: Parameter<Decibels>,
+	ic1eq: Frame,
+	ic2eq: Frame,
+}
+
+impl Shelf {
+	#[must_use]
+	fn new(kind: EqFilterKind, frequency: f64, gain: Value<Decibels>) -> Self {
+		Self {
+			kind,
+			frequency,
+			gain: Parameter::new(gain, Decibels::IDENTITY),
+			ic1eq: Frame::ZERO,
+			ic2eq: Frame::ZERO,
+		}
+	}
+
+	#[must_use]
+	fn is_idle(&self) -> bool {
+		is_silent(self.ic1eq) && is_silent(self.ic2eq)
+	}
+
+	fn clear(&mut self) {
+		self.ic1eq = Frame::ZERO;
+		self.ic2eq = Frame::ZERO;
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		self.gain.update(dt * input.len() as f64, info);
+		let previous_gain = self.gain.previous_value();
+		let gain = self.gain.value();
+		// a shelf with no gain doesn't change the audio, so skipping it
+		// leaves the audio exactly as it was
+		if previous_gain == Decibels::IDENTITY && gain == Decibels::IDENTITY {
+			self.clear();
+			return;
+		}
+		// the coefficients only need to be calculated once if the gain
+		// isn't changing
+		let fixed_coefficients = (previous_gain == gain)
+			.then(|| Coefficients::calculate(self.kind, self.frequency, Q, gain, dt));
+		let num_frames = input.len();
+		for (i, frame) in input.iter_mut().enumerate() {
+			let calculated_coefficients;
+			let coefficients = match &fixed_coefficients {
+				Some(coefficients) => coefficients,
+				None => {
+					let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+					calculated_coefficients = Coefficients::calculate(
+						self.kind,
+						self.frequency,
+						Q,
+						self.gain.interpolated_value(time_in_chunk),
+						dt,
+					);
+					&calculated_coefficients
+				}
+			};
+			*frame = coefficients.tick(*frame, &mut self.ic1eq, &mut self.ic2eq);
+		}
+	}
+}
+
+command_writers_and_readers! {
+	set_bass: ValueChangeCommand<Decibels>,
+	set_treble: ValueChangeCommand<Decibels>,
+}
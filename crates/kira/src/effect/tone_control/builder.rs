@@ -0,0 +1,87 @@
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Decibels, Value,
+};
+
+use super::{command_writers_and_readers, ToneControl, ToneControlHandle};
+
+/// Configures a tone control effect.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ToneControlBuilder {
+	/// The volume adjustment for frequencies around and below
+	/// [`BASS_FREQUENCY`](super::BASS_FREQUENCY).
+	pub bass: Value<Decibels>,
+	/// The volume adjustment for frequencies around and above
+	/// [`TREBLE_FREQUENCY`](super::TREBLE_FREQUENCY).
+	pub treble: Value<Decibels>,
+}
+
+impl ToneControlBuilder {
+	/// Creates a new [`ToneControlBuilder`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			bass: Value::Fixed(Decibels::IDENTITY),
+			treble: Value::Fixed(Decibels::IDENTITY),
+		}
+	}
+
+	/// Sets the volume adjustment for frequencies around and below
+	/// [`BASS_FREQUENCY`](super::BASS_FREQUENCY).
+	#[must_use = "This method consumes self and returns a modified ToneControlBuilder, so the return value should be used"]
+	pub fn bass(self, bass: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			bass: bass.into(),
+			..self
+		}
+	}
+
+	/// Sets the volume adjustment for frequencies around and above
+	/// [`TREBLE_FREQUENCY`](super::TREBLE_FREQUENCY).
+	#[must_use = "This method consumes self and returns a modified ToneControlBuilder, so the return value should be used"]
+	pub fn treble(self, treble: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			treble: treble.into(),
+			..self
+		}
+	}
+
+	/// Builds the tone control, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, ToneControlHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			ToneControl::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
+		(
+			Box::new(effect),
+			ToneControlHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
+		)
+	}
+}
+
+impl Default for ToneControlBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl EffectBuilder for ToneControlBuilder {
+	type Handle = ToneControlHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for ToneControlBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
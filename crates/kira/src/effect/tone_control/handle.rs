@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Tween,
+};
+
+use super::{CommandWriters, ToneControlBuilder};
+
+/// Controls a tone control effect.
+#[derive(Debug)]
+pub struct ToneControlHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<ToneControlBuilder>>,
+}
+
+impl ToneControlHandle {
+	/// Bypasses or stops bypassing the tone control, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the tone control stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
+		/// Sets the volume adjustment for frequencies around and below
+		/// [`BASS_FREQUENCY`](super::BASS_FREQUENCY).
+		bass: Decibels => bass,
+
+		/// Sets the volume adjustment for frequencies around and above
+		/// [`TREBLE_FREQUENCY`](super::TREBLE_FREQUENCY).
+		treble: Decibels => treble,
+	}
+}
@@ -0,0 +1,53 @@
+use crate::{
+	effect::{
+		test_helpers::{nyquist, settled_peak, DT},
+		Effect,
+	},
+	frame::Frame,
+	info::MockInfoBuilder,
+	Decibels,
+};
+
+use super::{command_writers_and_readers, ToneControl, ToneControlBuilder};
+
+/// Tests that a tone control with no bass or treble adjustment outputs
+/// exactly the audio it receives.
+#[test]
+fn passes_audio_through_unchanged_at_0_db() {
+	let info = MockInfoBuilder::new().build();
+	let mut tone_control =
+		ToneControl::new(ToneControlBuilder::new(), command_writers_and_readers().1);
+	let input: Vec<Frame> = (0..128)
+		.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+		.collect();
+	let mut output = input.clone();
+	tone_control.process(&mut output, DT, &info);
+	assert_eq!(output, input);
+	assert!(tone_control.is_idle());
+}
+
+/// Tests that the bass adjustment changes the volume of low frequencies
+/// but not high frequencies.
+#[test]
+fn bass_adjusts_low_frequencies() {
+	let builder = ToneControlBuilder::new().bass(Decibels(6.0));
+	let mut tone_control = ToneControl::new(builder, command_writers_and_readers().1);
+	let low = settled_peak(&mut tone_control, |_| 0.25);
+	assert!((low - 0.25 * Decibels(6.0).as_amplitude()).abs() < 0.001);
+	let mut tone_control = ToneControl::new(builder, command_writers_and_readers().1);
+	let high = settled_peak(&mut tone_control, nyquist);
+	assert!((high - 0.25).abs() < 0.001);
+}
+
+/// Tests that the treble adjustment changes the volume of high
+/// frequencies but not low frequencies.
+#[test]
+fn treble_adjusts_high_frequencies() {
+	let builder = ToneControlBuilder::new().treble(Decibels(-6.0));
+	let mut tone_control = ToneControl::new(builder, command_writers_and_readers().1);
+	let low = settled_peak(&mut tone_control, |_| 0.25);
+	assert!((low - 0.25).abs() < 0.001);
+	let mut tone_control = ToneControl::new(builder, command_writers_and_readers().1);
+	let high = settled_peak(&mut tone_control, nyquist);
+	assert!((high - 0.25 * Decibels(-6.0).as_amplitude()).abs() < 0.001);
+}
@@ -14,7 +14,7 @@ use crate::{
 	Decibels, Parameter,
 };
 
-use super::Effect;
+use super::{bypass::BypassableEffect, Effect};
 
 struct VolumeControl {
 	command_readers: CommandReaders,
@@ -36,6 +36,11 @@ impl Effect for VolumeControl {
 		read_commands_into_parameters!(self, volume);
 	}
 
+	// silence stays silent
+	fn is_idle(&self) -> bool {
+		true
+	}
+
 	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
 		self.volume.update(dt * input.len() as f64, info);
 		let num_frames = input.len();
@@ -46,6 +51,8 @@ impl Effect for VolumeControl {
 	}
 }
 
+impl BypassableEffect for VolumeControl {}
+
 command_writers_and_readers! {
 	set_volume: ValueChangeCommand<Decibels>,
 }
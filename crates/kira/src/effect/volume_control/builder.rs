@@ -1,4 +1,7 @@
-use crate::{effect::EffectBuilder, Decibels, Value};
+use crate::{
+	effect::{bypass::Bypassable, BuilderTemplate, Effect, EffectBuilder, TemplateBuilder},
+	Decibels, Value,
+};
 
 use super::{command_writers_and_readers, VolumeControl, VolumeControlHandle};
 
@@ -20,14 +23,37 @@ impl Default for VolumeControlBuilder {
 	}
 }
 
-impl EffectBuilder for VolumeControlBuilder {
-	type Handle = VolumeControlHandle;
-
-	fn build(self) -> (Box<dyn crate::effect::Effect>, Self::Handle) {
+impl VolumeControlBuilder {
+	/// Builds the volume control, which starts out bypassed if `bypassed` is `true`.
+	fn build_with_template(self, bypassed: bool) -> (Box<dyn Effect>, VolumeControlHandle) {
+		let template = BuilderTemplate::new(self, bypassed);
 		let (command_writers, command_readers) = command_writers_and_readers();
+		let (effect, set_bypassed) = Bypassable::new(
+			VolumeControl::new(self, command_readers),
+			Some(template.clone()),
+			bypassed,
+		);
 		(
-			Box::new(VolumeControl::new(self, command_readers)),
-			VolumeControlHandle { command_writers },
+			Box::new(effect),
+			VolumeControlHandle {
+				command_writers,
+				set_bypassed,
+				template,
+			},
 		)
 	}
 }
+
+impl EffectBuilder for VolumeControlBuilder {
+	type Handle = VolumeControlHandle;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		self.build_with_template(false)
+	}
+}
+
+impl TemplateBuilder for VolumeControlBuilder {
+	fn build_effect(self, bypassed: bool) -> Box<dyn Effect> {
+		self.build_with_template(bypassed).0
+	}
+}
@@ -1,16 +1,33 @@
-use crate::{command::handle_param_setters, Decibels};
+use std::sync::Arc;
 
-use super::CommandWriters;
+use crate::{
+	command::CommandWriter,
+	effect::{template_param_setters, BuilderTemplate},
+	Decibels, Tween,
+};
+
+use super::{CommandWriters, VolumeControlBuilder};
 
 /// Controls a volume control effect.
 #[derive(Debug)]
 pub struct VolumeControlHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) set_bypassed: CommandWriter<(bool, Tween)>,
+	pub(super) template: Arc<BuilderTemplate<VolumeControlBuilder>>,
 }
 
 impl VolumeControlHandle {
-	handle_param_setters! {
+	/// Bypasses or stops bypassing the volume control, crossfading between its
+	/// input and output with the given tween.
+	///
+	/// Once it's fully bypassed, the volume control stops processing audio.
+	pub fn set_bypassed(&mut self, bypassed: bool, tween: Tween) {
+		self.template.set_bypassed(bypassed);
+		self.set_bypassed.write((bypassed, tween))
+	}
+
+	template_param_setters! {
 		/// Sets the volume adjustment to apply to input audio.
-		volume: Decibels,
+		volume: Decibels => 0,
 	}
 }
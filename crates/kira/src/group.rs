@@ -0,0 +1,208 @@
+/*!
+Sets of sounds that can be controlled together.
+
+A group lets you pause, resume, stop, or change the volume of every
+sound in the group at once without keeping track of all of their
+handles. For example, you might want to stop every sound in the game
+world when a new level is loaded while leaving UI sounds and music alone.
+
+To create a group, use [`AudioManager::add_group`](crate::AudioManager::add_group).
+Sounds can then be added to the group using
+[`StaticSoundSettings::group`](crate::sound::static_sound::StaticSoundSettings::group)
+or [`StreamingSoundSettings::group`](crate::sound::streaming::StreamingSoundSettings::group).
+Each sound can be in at most one group.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+	sound::static_sound::StaticSoundData,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let mut world = manager.add_group()?;
+manager.play(StaticSoundData::from_file("waterfall.ogg")?.group(&world))?;
+manager.play(StaticSoundData::from_file("birds.ogg")?.group(&world))?;
+// when loading a new level
+world.stop(Tween::default());
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+Pausing, resuming, and stopping a group only affects sounds that are
+playing when the command is sent. Sounds that are added to the group later
+use the group's most recent volume.
+
+Groups only affect sounds that are playing, so sounds in a group
+can be routed to any mixer track.
+*/
+
+mod handle;
+
+#[cfg(test)]
+mod test;
+
+pub use handle::*;
+
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use atomic_arena::Key;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, info::Info, Decibels, Parameter,
+	Tween, Value,
+};
+
+/// A unique identifier for a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupId(pub(crate) Key);
+
+impl From<&GroupHandle> for GroupId {
+	fn from(handle: &GroupHandle) -> Self {
+		handle.id()
+	}
+}
+
+/// A change to the playback state of every sound in a group or on a
+/// mixer track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum GroupPlaybackCommand {
+	Pause(Tween),
+	Resume(Tween),
+	Stop(Tween),
+}
+
+pub(crate) struct Group {
+	shared: Arc<GroupShared>,
+	command_readers: CommandReaders,
+	/// How many commands the group has received. This is used to
+	/// number each command so sounds can tell which ones they've
+	/// already applied.
+	num_commands: u64,
+	volume_command: Option<(u64, ValueChangeCommand<Decibels>)>,
+	playback_command: Option<(u64, GroupPlaybackCommand)>,
+}
+
+impl Group {
+	#[must_use]
+	pub fn new(id: GroupId) -> (Self, GroupHandle) {
+		let shared = Arc::new(GroupShared {
+			removed: AtomicBool::new(false),
+		});
+		let (command_writers, command_readers) = command_writers_and_readers();
+		(
+			Self {
+				shared: shared.clone(),
+				command_readers,
+				num_commands: 0,
+				volume_command: None,
+				playback_command: None,
+			},
+			GroupHandle {
+				id,
+				shared,
+				command_writers,
+			},
+		)
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> &Arc<GroupShared> {
+		&self.shared
+	}
+
+	pub fn on_start_processing(&mut self) {
+		if let Some(command) = self.command_readers.set_volume.read() {
+			self.num_commands += 1;
+			self.volume_command = Some((self.num_commands, command));
+		}
+		if let Some(command) = self.command_readers.set_playback.read() {
+			self.num_commands += 1;
+			self.playback_command = Some((self.num_commands, command));
+		}
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct GroupShared {
+	removed: AtomicBool,
+}
+
+impl GroupShared {
+	#[must_use]
+	pub fn is_marked_for_removal(&self) -> bool {
+		self.removed.load(Ordering::SeqCst)
+	}
+
+	pub fn mark_for_removal(&self) {
+		self.removed.store(true, Ordering::SeqCst);
+	}
+}
+
+/// Applies the commands sent to a group to one of the sounds in it.
+pub(crate) struct GroupMember {
+	id: GroupId,
+	volume: Parameter<Decibels>,
+	/// The number of the most recent command that's been applied,
+	/// or `None` if the sound hasn't checked the group yet.
+	last_command: Option<u64>,
+}
+
+impl GroupMember {
+	#[must_use]
+	pub fn new(id: GroupId) -> Self {
+		Self {
+			id,
+			volume: Parameter::new(Value::Fixed(Decibels::IDENTITY), Decibels::IDENTITY),
+			last_command: None,
+		}
+	}
+
+	/// Applies any new commands sent to the group and updates the group
+	/// volume. Returns a playback command the sound should apply.
+	#[must_use]
+	pub fn update(&mut self, dt: f64, info: &Info) -> Option<GroupPlaybackCommand> {
+		let mut playback_command = None;
+		if let Some(group) = info.group(self.id) {
+			let last_command = self.last_command;
+			let joined = last_command.is_some();
+			let is_new = |number: u64| last_command.is_none_or(|last| number > last);
+			if let Some((_, command)) = group.volume_command.filter(|&(number, _)| is_new(number)) {
+				// sounds that just joined the group pick up the
+				// group's volume immediately
+				let tween = if joined {
+					command.tween
+				} else {
+					Tween {
+						duration: Duration::ZERO,
+						..Default::default()
+					}
+				};
+				self.volume.set(command.target, tween);
+			}
+			if let Some((_, command)) = group.playback_command.filter(|&(number, _)| is_new(number))
+			{
+				if joined {
+					playback_command = Some(command);
+				}
+			}
+			self.last_command = Some(group.num_commands);
+		}
+		self.volume.update(dt, info);
+		playback_command
+	}
+
+	#[must_use]
+	pub fn interpolated_volume(&self, amount: f64) -> Decibels {
+		self.volume.interpolated_value(amount)
+	}
+}
+
+command_writers_and_readers! {
+	set_volume: ValueChangeCommand<Decibels>,
+	set_playback: GroupPlaybackCommand,
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use crate::{command::ValueChangeCommand, Decibels, Tween, Value};
+
+use super::{CommandWriters, GroupId, GroupPlaybackCommand, GroupShared};
+
+/// Controls a group of sounds.
+///
+/// When a [`GroupHandle`] is dropped, the corresponding group will be
+/// removed, and sounds in the group will keep playing at the group's
+/// most recent volume.
+#[derive(Debug)]
+pub struct GroupHandle {
+	pub(crate) id: GroupId,
+	pub(crate) shared: Arc<GroupShared>,
+	pub(crate) command_writers: CommandWriters,
+}
+
+impl GroupHandle {
+	/// Returns the unique identifier for the group.
+	#[must_use]
+	pub fn id(&self) -> GroupId {
+		self.id
+	}
+
+	/// Sets the volume of every sound in the group.
+	///
+	/// This is applied on top of each sound's own volume.
+	pub fn set_volume(&mut self, volume: impl Into<Value<Decibels>>, tween: Tween) {
+		self.command_writers.set_volume.write(ValueChangeCommand {
+			target: volume.into(),
+			tween,
+		})
+	}
+
+	/// Fades out every sound in the group to silence with the given tween
+	/// and then pauses playback.
+	pub fn pause(&mut self, tween: Tween) {
+		self.command_writers
+			.set_playback
+			.write(GroupPlaybackCommand::Pause(tween))
+	}
+
+	/// Resumes playback of every sound in the group and fades in the
+	/// sounds from silence with the given tween.
+	pub fn resume(&mut self, tween: Tween) {
+		self.command_writers
+			.set_playback
+			.write(GroupPlaybackCommand::Resume(tween))
+	}
+
+	/// Fades out every sound in the group to silence with the given tween
+	/// and then stops playback.
+	///
+	/// Once the sounds are stopped, they cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) {
+		self.command_writers
+			.set_playback
+			.write(GroupPlaybackCommand::Stop(tween))
+	}
+}
+
+impl Drop for GroupHandle {
+	fn drop(&mut self) {
+		self.shared.mark_for_removal();
+	}
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use crate::{
+	backend::resources::{create_resources, ResourceControllers, Resources},
+	frame::Frame,
+	manager::Capacities,
+	sound::{
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		PlaybackState,
+	},
+	track::MainTrackBuilder,
+	Decibels, Easing, Panning, StartTime, Tween,
+};
+
+use super::{Group, GroupHandle, GroupId};
+
+const INSTANT: Tween = Tween {
+	start_time: StartTime::Immediate,
+	duration: Duration::ZERO,
+	easing: Easing::Linear,
+};
+
+fn setup() -> (Resources, ResourceControllers, GroupHandle) {
+	let (resources, mut controllers) =
+		create_resources(Capacities::default(), MainTrackBuilder::new(), 1, 1);
+	let key = controllers.group_controller.try_reserve().unwrap();
+	let (group, handle) = Group::new(GroupId(key));
+	controllers.group_controller.insert_with_key(key, group);
+	(resources, controllers, handle)
+}
+
+fn process(resources: &mut Resources) -> Frame {
+	let mut out = [Frame::ZERO];
+	resources.groups.on_start_processing();
+	resources.mixer.on_start_processing();
+	resources.mixer.process(
+		&mut out,
+		1.0,
+		&resources.clocks,
+		&resources.modulators,
+		&resources.listeners,
+		&resources.voice_groups,
+		&resources.groups,
+	);
+	out[0]
+}
+
+fn sound_data() -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|_| Frame::from_mono(1.0)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+/// Tests that stopping a group stops every sound in the group and
+/// leaves other sounds alone.
+#[test]
+fn stops_sounds_in_group() {
+	let (mut resources, mut controllers, mut group) = setup();
+	let main_track = &mut controllers.main_track_handle;
+	let grouped_sounds = [
+		main_track.play(sound_data().group(&group)).unwrap(),
+		main_track.play(sound_data().group(&group)).unwrap(),
+	];
+	let other_sound = main_track.play(sound_data()).unwrap();
+	process(&mut resources);
+
+	group.stop(INSTANT);
+	process(&mut resources);
+	for sound in &grouped_sounds {
+		assert_eq!(sound.state(), PlaybackState::Stopped);
+	}
+	assert_eq!(other_sound.state(), PlaybackState::Playing);
+
+	// sounds added to the group later aren't affected by earlier commands
+	let new_sound = controllers
+		.main_track_handle
+		.play(sound_data().group(&group))
+		.unwrap();
+	process(&mut resources);
+	assert_eq!(new_sound.state(), PlaybackState::Playing);
+}
+
+/// Tests that the volume of a group applies to every sound in the group,
+/// including sounds added after the volume was set.
+#[test]
+fn sets_volume_of_sounds_in_group() {
+	let (mut resources, mut controllers, mut group) = setup();
+	controllers
+		.main_track_handle
+		.play(sound_data().group(&group))
+		.unwrap();
+	assert_eq!(
+		process(&mut resources),
+		Frame::from_mono(1.0).panned(Panning::CENTER)
+	);
+
+	group.set_volume(Decibels::SILENCE, INSTANT);
+	assert_eq!(process(&mut resources), Frame::ZERO);
+
+	controllers
+		.main_track_handle
+		.play(sound_data().group(&group))
+		.unwrap();
+	assert_eq!(process(&mut resources), Frame::ZERO);
+}
+
+/// Tests that a group can pause and resume its sounds.
+#[test]
+fn pauses_and_resumes_sounds_in_group() {
+	let (mut resources, mut controllers, mut group) = setup();
+	let sound = controllers
+		.main_track_handle
+		.play(sound_data().group(&group))
+		.unwrap();
+	process(&mut resources);
+
+	group.pause(INSTANT);
+	process(&mut resources);
+	process(&mut resources);
+	assert_eq!(sound.state(), PlaybackState::Paused);
+
+	group.resume(INSTANT);
+	process(&mut resources);
+	process(&mut resources);
+	assert_eq!(sound.state(), PlaybackState::Playing);
+}
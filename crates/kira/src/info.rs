@@ -5,13 +5,17 @@
  * like [`Sound`](crate::sound::Sound) or [`Effect`](crate::effect::Effect).
  */
 
+use std::sync::Arc;
+
 use atomic_arena::Arena;
 use glam::{Quat, Vec3};
 
 use crate::{
 	clock::{Clock, ClockId, ClockTime, State as ClockState},
+	group::{Group, GroupId, GroupPlaybackCommand},
 	listener::{Listener, ListenerId},
 	modulator::{Modulator, ModulatorId},
+	voice_group::{VoiceGroup, VoiceGroupId, VoiceGroupShared, VoiceStealPolicy},
 };
 
 /// Provides info about resources on the audio thread.
@@ -21,6 +25,14 @@ use crate::{
 pub struct Info<'a> {
 	kind: InfoKind<'a>,
 	spatial_track_info: Option<SpatialTrackInfo>,
+	/// The most recent command sent to every sound on the current track
+	/// and its number.
+	sound_playback_command: Option<(u64, GroupPlaybackCommand)>,
+	/// The playback rate of the current track, including the playback
+	/// rates of its ancestors.
+	track_playback_rate: f64,
+	/// The duration of a single frame of audio (in seconds).
+	frame_duration: f64,
 }
 
 impl<'a> Info<'a> {
@@ -28,18 +40,54 @@ impl<'a> Info<'a> {
 		clocks: &'a Arena<Clock>,
 		modulators: &'a Arena<Box<dyn Modulator>>,
 		listeners: &'a Arena<Listener>,
+		voice_groups: &'a Arena<VoiceGroup>,
+		groups: &'a Arena<Group>,
 		spatial_track_info: Option<SpatialTrackInfo>,
+		frame_duration: f64,
 	) -> Self {
 		Self {
 			kind: InfoKind::Real {
 				clocks,
 				modulators,
 				listeners,
+				voice_groups,
+				groups,
 			},
 			spatial_track_info,
+			sound_playback_command: None,
+			track_playback_rate: 1.0,
+			frame_duration,
+		}
+	}
+
+	/// Sets the most recent command sent to every sound on the current
+	/// track.
+	#[must_use]
+	pub(crate) fn with_sound_playback_command(
+		self,
+		sound_playback_command: Option<(u64, GroupPlaybackCommand)>,
+	) -> Self {
+		Self {
+			sound_playback_command,
+			..self
+		}
+	}
+
+	/// Sets the playback rate of the current track.
+	#[must_use]
+	pub(crate) fn with_track_playback_rate(self, track_playback_rate: f64) -> Self {
+		Self {
+			track_playback_rate,
+			..self
 		}
 	}
 
+	/// Returns how many frames of audio fit in the given duration (in seconds).
+	#[must_use]
+	pub(crate) fn num_frames_in(&self, duration: f64) -> u64 {
+		(duration / self.frame_duration).round() as u64
+	}
+
 	/// Gets information about the clock with the given ID if it
 	/// exists, returns `None` otherwise.
 	#[must_use]
@@ -115,6 +163,44 @@ impl<'a> Info<'a> {
 		})
 	}
 
+	/// Gets the shared state of the voice group with the given ID if it
+	/// exists, returns `None` otherwise.
+	#[must_use]
+	pub(crate) fn voice_group(&self, id: VoiceGroupId) -> Option<&Arc<VoiceGroupShared>> {
+		match &self.kind {
+			InfoKind::Real { voice_groups, .. } => voice_groups.get(id.0),
+			InfoKind::Mock { voice_groups, .. } => voice_groups.get(id.0),
+		}
+		.map(VoiceGroup::shared)
+	}
+
+	/// Returns the most recent command sent to every sound on the current
+	/// track and its number, or `None` if there hasn't been one.
+	#[must_use]
+	pub(crate) fn sound_playback_command(&self) -> Option<(u64, GroupPlaybackCommand)> {
+		self.sound_playback_command
+	}
+
+	/// Returns the playback rate of the track the current sound or effect is
+	/// on, multiplied by the playback rates of the track's ancestors.
+	///
+	/// Sounds should multiply their own playback rate by this. It's `1.0`
+	/// unless a track's playback rate was changed with
+	/// [`TrackHandle::set_playback_rate`](crate::track::TrackHandle::set_playback_rate).
+	#[must_use]
+	pub fn track_playback_rate(&self) -> f64 {
+		self.track_playback_rate
+	}
+
+	/// Gets the group with the given ID if it exists, returns `None` otherwise.
+	#[must_use]
+	pub(crate) fn group(&self, id: GroupId) -> Option<&Group> {
+		match &self.kind {
+			InfoKind::Real { groups, .. } => groups.get(id.0),
+			InfoKind::Mock { groups, .. } => groups.get(id.0),
+		}
+	}
+
 	/// If this is called from an effect on a spatial track, returns the distance
 	/// of the spatial track's from the spatial track. Otherwise, returns `None`.
 	pub fn listener_distance(&self) -> Option<f32> {
@@ -189,7 +275,11 @@ pub struct MockInfoBuilder {
 	clock_info: Arena<ClockInfo>,
 	modulator_values: Arena<f64>,
 	listener_info: Arena<ListenerInfo>,
+	voice_groups: Arena<VoiceGroup>,
+	groups: Arena<Group>,
 	spatial_track_info: Option<SpatialTrackInfo>,
+	track_playback_rate: f64,
+	frame_duration: f64,
 }
 
 impl MockInfoBuilder {
@@ -199,10 +289,27 @@ impl MockInfoBuilder {
 			clock_info: Arena::new(100),
 			modulator_values: Arena::new(100),
 			listener_info: Arena::new(100),
+			voice_groups: Arena::new(100),
+			groups: Arena::new(100),
 			spatial_track_info: None,
+			track_playback_rate: 1.0,
+			frame_duration: 1.0,
 		}
 	}
 
+	/// Sets the duration of a single frame of audio (in seconds), which is
+	/// used to count frames for [`StartTime::Frames`](crate::StartTime::Frames).
+	/// Defaults to `1.0`.
+	pub fn set_frame_duration(&mut self, frame_duration: f64) {
+		self.frame_duration = frame_duration;
+	}
+
+	/// Sets the playback rate returned by [`Info::track_playback_rate`].
+	/// Defaults to `1.0`.
+	pub fn set_track_playback_rate(&mut self, track_playback_rate: f64) {
+		self.track_playback_rate = track_playback_rate;
+	}
+
 	/// Adds a fake clock with the given ticking state and time. Returns a fake
 	/// `ClockId`.
 	pub fn add_clock(&mut self, ticking: bool, ticks: u64, fraction: f64) -> ClockId {
@@ -254,6 +361,26 @@ impl MockInfoBuilder {
 		id
 	}
 
+	/// Adds a voice group that allows the given number of sounds to play at once.
+	/// Returns a `VoiceGroupId` for the voice group.
+	pub fn add_voice_group(
+		&mut self,
+		max_instances: usize,
+		steal_policy: VoiceStealPolicy,
+	) -> VoiceGroupId {
+		let id = VoiceGroupId(
+			self.voice_groups
+				.controller()
+				.try_reserve()
+				.expect("voice group arena is full"),
+		);
+		let (voice_group, _) = VoiceGroup::new(id, max_instances, steal_policy);
+		self.voice_groups
+			.insert_with_key(id.0, voice_group)
+			.unwrap();
+		id
+	}
+
 	/// Consumes the `MockInfoProvider` and returns a fake `Info`.
 	pub fn build(self) -> Info<'static> {
 		Info {
@@ -261,8 +388,13 @@ impl MockInfoBuilder {
 				clock_info: self.clock_info,
 				modulator_values: self.modulator_values,
 				listener_info: self.listener_info,
+				voice_groups: self.voice_groups,
+				groups: self.groups,
 			},
 			spatial_track_info: self.spatial_track_info,
+			sound_playback_command: None,
+			track_playback_rate: self.track_playback_rate,
+			frame_duration: self.frame_duration,
 		}
 	}
 }
@@ -278,11 +410,15 @@ enum InfoKind<'a> {
 		clocks: &'a Arena<Clock>,
 		modulators: &'a Arena<Box<dyn Modulator>>,
 		listeners: &'a Arena<Listener>,
+		voice_groups: &'a Arena<VoiceGroup>,
+		groups: &'a Arena<Group>,
 	},
 	Mock {
 		clock_info: Arena<ClockInfo>,
 		modulator_values: Arena<f64>,
 		listener_info: Arena<ListenerInfo>,
+		voice_groups: Arena<VoiceGroup>,
+		groups: Arena<Group>,
 	},
 }
 
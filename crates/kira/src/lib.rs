@@ -124,7 +124,8 @@ The Kira crate has the following feature flags:
   audio managers. This allows Kira to talk to the operating system to output audio. Most
   users should leave this enabled.
 - `symphonia` (enabled by default) - allows loading and streaming audio from common
-  audio formats, like MP3 and WAV.
+  audio formats, like MP3 and WAV. Without it, WAV files can still be loaded and
+  streamed using a small built-in decoder.
 	- `mp3` (enabled by default) - enables support for loading and streaming MP3 audio (enables
 	  the `symphonia` feature automatically)
 	- `ogg` (enabled by default) - enables support for loading and streaming OGG audio (enables
@@ -133,6 +134,11 @@ The Kira crate has the following feature flags:
 	  the `symphonia` feature automatically)
 	- `wav` (enabled by default) - enables support for loading and streaming WAV audio (enables
 	  the `symphonia` feature automatically)
+- `async` - adds async versions of the functions that load audio files, like
+  `StaticSoundData::from_file_async`, which decode the audio on a separate thread
+  instead of blocking the async executor
+- `waveforms` - adds constructors for generating sine, square, triangle, and sawtooth waves
+  and white noise as [`StaticSoundData`](crate::sound::static_sound::StaticSoundData)
 - `serde` - adds `Serialize` and `Deserialize` implementations for the following types:
 	- [`Capacities`]
 	- [`ClockSpeed`](crate::clock::ClockSpeed)
@@ -141,13 +147,17 @@ The Kira crate has the following feature flags:
 	- [`EndPosition`](crate::sound::EndPosition)
 	- [`EqFilterKind`](crate::effect::eq_filter::EqFilterKind)
 	- [`FilterMode`](crate::effect::filter::FilterMode)
+	- [`FinishReason`](crate::sound::FinishReason)
 	- [`Frame`]
+	- [`LoopMode`](crate::sound::LoopMode)
 	- [`Mapping`]
 	- [`PlaybackPosition`](crate::sound::PlaybackPosition)
 	- [`PlaybackRate`]
 	- [`PlaybackState`](crate::sound::PlaybackState)
 	- [`Region`](crate::sound::Region)
+	- [`StartTime`] (except for [`StartTime::ClockTime`])
 	- [`Decibels`]
+	- [`VoiceStealPolicy`](crate::voice_group::VoiceStealPolicy)
 	- [`Waveform`](crate::modulator::lfo::Waveform)
 - `assert_no_alloc` - uses the [`assert_no_alloc`](https://crates.io/crates/assert_no_alloc) crate
   to cause panics if memory is allocated or deallocated on the audio thread. This is mainly useful
@@ -239,6 +249,7 @@ mod decibels;
 pub mod effect;
 mod error;
 mod frame;
+pub mod group;
 pub mod info;
 pub mod listener;
 mod manager;
@@ -248,6 +259,7 @@ mod panning;
 mod parameter;
 mod playback_rate;
 mod playback_state_manager;
+mod random;
 mod semitones;
 pub mod sound;
 mod start_time;
@@ -256,6 +268,7 @@ mod test_helpers;
 pub mod track;
 mod tween;
 mod value;
+pub mod voice_group;
 
 pub use backend::DefaultBackend;
 pub use decibels::*;
@@ -5,8 +5,10 @@
 //! resources like clocks, mixer tracks, and spatial scenes. Once the
 //! [`AudioManager`] is dropped, its audio output will be stopped.
 
+mod resource_usage;
 mod settings;
 
+pub use resource_usage::*;
 pub use settings::*;
 
 use std::sync::{atomic::Ordering, Arc};
@@ -17,13 +19,15 @@ use crate::{
 		Backend, DefaultBackend, Renderer, RendererShared,
 	},
 	clock::{Clock, ClockHandle, ClockId, ClockSpeed},
+	group::{Group, GroupHandle, GroupId},
 	listener::{Listener, ListenerHandle, ListenerId},
 	modulator::{ModulatorBuilder, ModulatorId},
-	sound::SoundData,
+	sound::{streaming::DecoderThreadShutdown, SoundData},
 	track::{
-		MainTrackHandle, SendTrackBuilder, SendTrackHandle, SendTrackId, SpatialTrackBuilder,
-		SpatialTrackHandle, TrackBuilder, TrackHandle,
+		AddSubTrackError, MainTrackHandle, SendTrackBuilder, SendTrackHandle, SendTrackId,
+		SpatialTrackBuilder, SpatialTrackHandle, TrackBuilder, TrackHandle, TrackId, TrackLevels,
 	},
+	voice_group::{VoiceGroup, VoiceGroupHandle, VoiceGroupId, VoiceStealPolicy},
 	PlaySoundError, ResourceLimitReached, Value,
 };
 
@@ -33,6 +37,9 @@ pub struct AudioManager<B: Backend = DefaultBackend> {
 	resource_controllers: ResourceControllers,
 	renderer_shared: Arc<RendererShared>,
 	internal_buffer_size: usize,
+	// fields are dropped in order, so this gives decoding threads a moment
+	// to stop after the backend and the sounds it was playing are gone
+	_decoder_thread_shutdown: DecoderThreadShutdown,
 }
 
 impl<B: Backend> AudioManager<B> {
@@ -88,6 +95,7 @@ impl<B: Backend> AudioManager<B> {
 			resource_controllers,
 			renderer_shared,
 			internal_buffer_size: settings.internal_buffer_size,
+			_decoder_thread_shutdown: DecoderThreadShutdown,
 		})
 	}
 
@@ -114,12 +122,20 @@ impl<B: Backend> AudioManager<B> {
 	}
 
 	/// Creates a mixer sub-track.
+	///
+	/// Returns an error if the track has a [name](TrackBuilder::name)
+	/// that another track already has.
 	pub fn add_sub_track(
 		&mut self,
 		builder: TrackBuilder,
-	) -> Result<TrackHandle, ResourceLimitReached> {
-		let (mut track, handle) =
-			builder.build(self.renderer_shared.clone(), self.internal_buffer_size);
+	) -> Result<TrackHandle, AddSubTrackError> {
+		let (mut track, handle) = builder.build(
+			self.renderer_shared.clone(),
+			self.internal_buffer_size,
+			None,
+			None,
+		);
+		handle.register_name()?;
 		track.init_effects(self.renderer_shared.sample_rate.load(Ordering::SeqCst));
 		self.resource_controllers
 			.sub_track_controller
@@ -139,6 +155,7 @@ impl<B: Backend> AudioManager<B> {
 			self.internal_buffer_size,
 			listener.into(),
 			position.into().to_(),
+			None,
 		);
 		track.init_effects(self.renderer_shared.sample_rate.load(Ordering::SeqCst));
 		self.resource_controllers
@@ -165,6 +182,52 @@ impl<B: Backend> AudioManager<B> {
 		Ok(handle)
 	}
 
+	/**
+	Solos a mixer track, muting every other track except for the track's
+	ancestors and descendants.
+
+	The ancestors of the soloed track keep passing audio through, so the
+	soloed track can still be heard, but sounds played directly on them
+	can be heard too. Send tracks aren't muted by soloing, but the tracks
+	that are muted don't send any audio to them. Only one track can be
+	soloed at a time, so soloing a track unsolos the previously soloed
+	track.
+
+	Like [`TrackHandle::set_muted`], muted tracks fade out over a few
+	milliseconds to avoid clicks.
+
+	# Examples
+
+	```no_run
+	# use kira::{AudioManager, AudioManagerSettings, DefaultBackend};
+	use kira::track::TrackBuilder;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut music = manager.add_sub_track(TrackBuilder::new())?;
+	let drums = music.add_sub_track(TrackBuilder::new())?;
+	let sfx = manager.add_sub_track(TrackBuilder::new())?;
+	// only the drums can be heard
+	manager.solo_track(&drums);
+	// every track can be heard again
+	manager.clear_solo();
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn solo_track(&mut self, track: impl Into<TrackId>) {
+		self.renderer_shared.set_soloed_track(Some(track.into()));
+	}
+
+	/// Unsolos the soloed mixer track, if there is one.
+	pub fn clear_solo(&mut self) {
+		self.renderer_shared.set_soloed_track(None);
+	}
+
+	/// Returns the ID of the soloed mixer track, if there is one.
+	#[must_use]
+	pub fn soloed_track(&self) -> Option<TrackId> {
+		self.renderer_shared.soloed_track()
+	}
+
 	/**
 	Creates a clock.
 
@@ -253,6 +316,66 @@ impl<B: Backend> AudioManager<B> {
 		Ok(handle)
 	}
 
+	/**
+	Creates a voice group, which limits how many sounds in the group
+	can play at the same time.
+
+	When a new sound starts in a group that's already playing `max_instances`
+	sounds, the sound chosen by `steal_policy` is stopped with a short fade-out.
+	See the [`voice_group`](crate::voice_group) module for more information.
+
+	# Examples
+
+	```no_run
+	# use kira::{AudioManager, AudioManagerSettings, DefaultBackend};
+	use kira::voice_group::VoiceStealPolicy;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let voice_group = manager.add_voice_group(4, VoiceStealPolicy::Oldest)?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn add_voice_group(
+		&mut self,
+		max_instances: usize,
+		steal_policy: VoiceStealPolicy,
+	) -> Result<VoiceGroupHandle, ResourceLimitReached> {
+		let key = self
+			.resource_controllers
+			.voice_group_controller
+			.try_reserve()?;
+		let id = VoiceGroupId(key);
+		let (voice_group, handle) = VoiceGroup::new(id, max_instances, steal_policy);
+		self.resource_controllers
+			.voice_group_controller
+			.insert_with_key(key, voice_group);
+		Ok(handle)
+	}
+
+	/**
+	Creates a group, which can be used to pause, resume, stop, or change
+	the volume of many sounds at once.
+
+	See the [`group`](crate::group) module for more information.
+
+	# Examples
+
+	```no_run
+	# use kira::{AudioManager, AudioManagerSettings, DefaultBackend};
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let group = manager.add_group()?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn add_group(&mut self) -> Result<GroupHandle, ResourceLimitReached> {
+		let key = self.resource_controllers.group_controller.try_reserve()?;
+		let (group, handle) = Group::new(GroupId(key));
+		self.resource_controllers
+			.group_controller
+			.insert_with_key(key, group);
+		Ok(handle)
+	}
+
 	/**
 	Returns a handle to the main mixer track.
 
@@ -274,6 +397,58 @@ impl<B: Backend> AudioManager<B> {
 		&mut self.resource_controllers.main_track_handle
 	}
 
+	/**
+	Returns a handle to the track with the given full name, or `None`
+	if no track has that name.
+
+	A track's full name is the name given to [`TrackBuilder::name`] after
+	the names of its parent tracks, separated by `/`s. The handle controls
+	the same track as the handle returned when the track was added, and
+	the track is removed once every handle to it is dropped, so this
+	only finds tracks whose handles are still around.
+
+	# Examples
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+		track::TrackBuilder,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut sfx = manager.add_sub_track(TrackBuilder::new().name("sfx"))?;
+	let weapons = sfx.add_sub_track(TrackBuilder::new().name("weapons"))?;
+	// later...
+	if let Some(mut weapons) = manager.track_by_name("sfx/weapons") {
+		weapons.set_volume(-6.0, Tween::default());
+	}
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn track_by_name(&self, name: &str) -> Option<TrackHandle> {
+		let controls = self.renderer_shared.track_names.get(name)?;
+		Some(TrackHandle::from_controls(
+			controls,
+			self.internal_buffer_size,
+		))
+	}
+
+	/// Returns the levels of the audio the main mixer track output most
+	/// recently.
+	///
+	/// This is the same as calling [`MainTrackHandle::level`].
+	#[must_use]
+	pub fn main_track_level(&self) -> TrackLevels {
+		self.resource_controllers.main_track_handle.level()
+	}
+
+	/// Returns the sample rate of the audio device (in Hz).
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.renderer_shared.sample_rate.load(Ordering::SeqCst)
+	}
+
 	/// Returns the number of mixer sub-tracks that can exist at a time.
 	#[must_use]
 	pub fn sub_track_capacity(&self) -> usize {
@@ -298,12 +473,33 @@ impl<B: Backend> AudioManager<B> {
 		self.resource_controllers.modulator_controller.capacity()
 	}
 
+	/// Returns the number of voice groups that can exist at a time.
+	#[must_use]
+	pub fn voice_group_capacity(&self) -> usize {
+		self.resource_controllers.voice_group_controller.capacity()
+	}
+
+	/// Returns the number of groups that can exist at a time.
+	#[must_use]
+	pub fn group_capacity(&self) -> usize {
+		self.resource_controllers.group_controller.capacity()
+	}
+
 	/// Returns the number of mixer sub-tracks that currently exist.
 	#[must_use]
 	pub fn num_sub_tracks(&self) -> usize {
 		self.resource_controllers.sub_track_controller.len()
 	}
 
+	/// Returns the number of mixer sub-tracks that can be added before
+	/// [`sub_track_capacity`](Self::sub_track_capacity) is reached.
+	///
+	/// Sub-tracks of other sub-tracks don't count towards this limit.
+	#[must_use]
+	pub fn sub_track_capacity_remaining(&self) -> usize {
+		self.sub_track_capacity() - self.num_sub_tracks()
+	}
+
 	/// Returns the number of mixer send tracks that currently exist.
 	#[must_use]
 	pub fn num_send_tracks(&self) -> usize {
@@ -322,6 +518,51 @@ impl<B: Backend> AudioManager<B> {
 		self.resource_controllers.modulator_controller.len()
 	}
 
+	/// Returns the number of voice groups that currently exist.
+	#[must_use]
+	pub fn num_voice_groups(&self) -> usize {
+		self.resource_controllers.voice_group_controller.len()
+	}
+
+	/// Returns the number of groups that currently exist.
+	#[must_use]
+	pub fn num_groups(&self) -> usize {
+		self.resource_controllers.group_controller.len()
+	}
+
+	/// Returns the number of sounds that are playing on every track,
+	/// including sounds that are paused.
+	///
+	/// This is updated by the audio thread every time it processes a
+	/// batch of audio, so sounds that were just played may not be
+	/// counted yet.
+	#[must_use]
+	pub fn num_sounds(&self) -> usize {
+		self.resource_usage().num_sounds
+	}
+
+	/// Returns the number of sounds on every track in each
+	/// [`PlaybackState`](crate::sound::PlaybackState), in the order of
+	/// [`PlaybackState::ALL`](crate::sound::PlaybackState::ALL).
+	///
+	/// Like [`num_sounds`](Self::num_sounds), this is updated every time
+	/// the audio thread processes a batch of audio.
+	#[must_use]
+	pub fn num_sounds_by_state(&self) -> [usize; 7] {
+		self.resource_usage().num_sounds_by_state
+	}
+
+	/// Returns an estimate of the resources used by the sounds that are
+	/// playing.
+	///
+	/// This is updated by the audio thread every time it processes a
+	/// batch of audio, so sounds that were just played may not be
+	/// counted yet.
+	#[must_use]
+	pub fn resource_usage(&self) -> ResourceUsage {
+		self.renderer_shared.resource_usage()
+	}
+
 	/// Returns a mutable reference to this manager's backend.
 	#[must_use]
 	pub fn backend_mut(&mut self) -> &mut B {
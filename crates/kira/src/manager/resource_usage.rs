@@ -0,0 +1,61 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{backend::resources::ResourceStorage, sound::Sound};
+
+/// An estimate of the resources used by the sounds an
+/// [`AudioManager`](crate::AudioManager) is playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ResourceUsage {
+	/// The number of sounds that are playing, including sounds that are
+	/// paused.
+	pub num_sounds: usize,
+	/// The number of sounds in each
+	/// [`PlaybackState`](crate::sound::PlaybackState), in the order of
+	/// [`PlaybackState::ALL`](crate::sound::PlaybackState::ALL).
+	pub num_sounds_by_state: [usize; 7],
+	/// The total memory used by the sounds (in bytes), as reported by
+	/// [`Sound::memory_usage`].
+	///
+	/// Memory shared between sounds, like the samples of a
+	/// [`StaticSoundData`](crate::sound::static_sound::StaticSoundData),
+	/// isn't counted.
+	pub sound_memory: usize,
+}
+
+impl ResourceUsage {
+	/// Adds the resources used by the given sounds.
+	pub(crate) fn add_sounds(&mut self, sounds: &ResourceStorage<Box<dyn Sound>>) {
+		for (_, sound) in sounds.iter() {
+			self.num_sounds += 1;
+			self.num_sounds_by_state[sound.playback_state() as usize] += 1;
+			self.sound_memory += sound.memory_usage();
+		}
+	}
+}
+
+/// The number of sounds in each playback state on a track, written
+/// by the audio thread and read by the track's handle.
+#[derive(Debug, Default)]
+pub(crate) struct SoundCounts([AtomicUsize; 7]);
+
+impl SoundCounts {
+	#[must_use]
+	pub fn read(&self) -> [usize; 7] {
+		std::array::from_fn(|i| self.0[i].load(Ordering::SeqCst))
+	}
+
+	pub fn store(&self, counts: [usize; 7]) {
+		for (count, new_count) in self.0.iter().zip(counts) {
+			count.store(new_count, Ordering::SeqCst);
+		}
+	}
+
+	/// Counts the sounds in each playback state.
+	pub fn record(&self, sounds: &ResourceStorage<Box<dyn Sound>>) {
+		let mut counts = [0; 7];
+		for (_, sound) in sounds.iter() {
+			counts[sound.playback_state() as usize] += 1;
+		}
+		self.store(counts);
+	}
+}
@@ -17,6 +17,10 @@ pub struct Capacities {
 	pub modulator_capacity: usize,
 	/// The maximum number of listeners that can exist at a time.
 	pub listener_capacity: usize,
+	/// The maximum number of voice groups that can exist at a time.
+	pub voice_group_capacity: usize,
+	/// The maximum number of groups that can exist at a time.
+	pub group_capacity: usize,
 }
 
 impl Default for Capacities {
@@ -27,6 +31,8 @@ impl Default for Capacities {
 			clock_capacity: 8,
 			modulator_capacity: 16,
 			listener_capacity: 8,
+			voice_group_capacity: 16,
+			group_capacity: 16,
 		}
 	}
 }
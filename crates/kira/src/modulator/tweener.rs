@@ -85,6 +85,14 @@ impl Modulator for Tweener {
 				StartTime::ClockTime(clock_time) => {
 					info.when_to_start(*clock_time) == WhenToStart::Now
 				}
+				StartTime::Frames(frames_remaining) => {
+					if *frames_remaining == 0 {
+						true
+					} else {
+						*frames_remaining = frames_remaining.saturating_sub(info.num_frames_in(dt));
+						false
+					}
+				}
 			};
 			if !started {
 				return;
@@ -62,6 +62,38 @@ fn waits_for_delay() {
 	assert_eq!(parameter.value(), 1.0);
 }
 
+/// Tests that a `Parameter` with a start time measured in frames
+/// waits for that many frames before it begins tweening.
+#[test]
+#[allow(clippy::float_cmp)]
+fn waits_for_frames() {
+	let info = {
+		let mut builder = MockInfoBuilder::new();
+		builder.set_frame_duration(0.25);
+		builder.build()
+	};
+
+	let mut parameter = Parameter::new(Value::Fixed(0.0), 0.0);
+	parameter.set(
+		Value::Fixed(1.0),
+		Tween {
+			start_time: StartTime::Frames(8),
+			duration: Duration::from_secs(1),
+			..Default::default()
+		},
+	);
+
+	// value should not be changing yet
+	for _ in 0..2 {
+		assert_eq!(parameter.value(), 0.0);
+		assert!(!parameter.update(1.0, &info));
+	}
+
+	// the tween should start now
+	assert!(parameter.update(1.0, &info));
+	assert_eq!(parameter.value(), 1.0);
+}
+
 /// Tests that a `Parameter` with a clock time set as
 /// the start time waits for that time before it
 /// begins tweening.
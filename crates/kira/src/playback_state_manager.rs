@@ -5,6 +5,12 @@ use crate::{
 pub(crate) struct PlaybackStateManager {
 	state: State,
 	volume_fade: Parameter<Decibels>,
+	scheduled_pause: Option<(StartTime, Tween)>,
+	scheduled_stop: Option<(StartTime, Tween)>,
+	/// When to resume after being paused by [`pause_until`](Self::pause_until),
+	/// and the tween to fade in with.
+	scheduled_resume: Option<(StartTime, Tween)>,
+	stopped_early: bool,
 }
 
 impl PlaybackStateManager {
@@ -21,6 +27,19 @@ impl PlaybackStateManager {
 				.unwrap_or_else(|| {
 					Parameter::new(Value::Fixed(Decibels::IDENTITY), Decibels::IDENTITY)
 				}),
+			scheduled_pause: None,
+			scheduled_stop: None,
+			scheduled_resume: None,
+			stopped_early: false,
+		}
+	}
+
+	/// Creates a manager that starts out paused and silent.
+	pub fn new_paused() -> Self {
+		Self {
+			state: State::Paused,
+			volume_fade: Parameter::new(Value::Fixed(Decibels::SILENCE), Decibels::SILENCE),
+			..Self::new(None)
 		}
 	}
 
@@ -32,6 +51,7 @@ impl PlaybackStateManager {
 		match self.state {
 			State::Playing => PlaybackState::Playing,
 			State::Pausing => PlaybackState::Pausing,
+			State::Paused if self.scheduled_resume.is_some() => PlaybackState::WaitingToResume,
 			State::Paused => PlaybackState::Paused,
 			State::WaitingToResume { .. } => PlaybackState::WaitingToResume,
 			State::Resuming => PlaybackState::Resuming,
@@ -45,14 +65,37 @@ impl PlaybackStateManager {
 			return;
 		}
 		self.state = State::Pausing;
+		self.scheduled_resume = None;
 		self.volume_fade
 			.set(Value::Fixed(Decibels::SILENCE), fade_out_tween);
 	}
 
+	/// Pauses playback and then resumes once the start time is reached,
+	/// using the same tween to fade out and back in. If the start time
+	/// can never be reached, playback stays paused.
+	pub fn pause_until(&mut self, start_time: StartTime, tween: Tween) {
+		if let State::Stopped = &self.state {
+			return;
+		}
+		self.pause(tween);
+		self.scheduled_resume = Some((start_time, tween));
+	}
+
+	/// Pauses playback once the start time is reached. If the start time
+	/// can never be reached, the pause is discarded.
+	pub fn pause_at(&mut self, start_time: StartTime, fade_out_tween: Tween) {
+		if start_time == StartTime::Immediate {
+			self.pause(fade_out_tween);
+		} else {
+			self.scheduled_pause = Some((start_time, fade_out_tween));
+		}
+	}
+
 	pub fn resume(&mut self, start_time: StartTime, fade_in_tween: Tween) {
 		if let State::Stopped = &self.state {
 			return;
 		}
+		self.scheduled_resume = None;
 		if let StartTime::Immediate = start_time {
 			self.state = State::Resuming;
 			self.volume_fade
@@ -70,15 +113,66 @@ impl PlaybackStateManager {
 			return;
 		}
 		self.state = State::Stopping;
+		self.scheduled_resume = None;
+		self.stopped_early = true;
 		self.volume_fade
 			.set(Value::Fixed(Decibels::SILENCE), fade_out_tween);
 	}
 
+	/// Stops playback once the start time is reached. If the start time
+	/// can never be reached, the stop is discarded.
+	pub fn stop_at(&mut self, start_time: StartTime, fade_out_tween: Tween) {
+		if start_time == StartTime::Immediate {
+			self.stop(fade_out_tween);
+		} else {
+			self.scheduled_stop = Some((start_time, fade_out_tween));
+		}
+	}
+
+	/// Whether playback was stopped before reaching the end of the sound,
+	/// either by [`stop`](Self::stop) or because a resume time can never
+	/// be reached.
+	pub fn stopped_early(&self) -> bool {
+		self.stopped_early
+	}
+
 	pub fn mark_as_stopped(&mut self) {
 		self.state = State::Stopped;
 	}
 
+	/// Pauses playback immediately without fading out.
+	pub fn mark_as_paused(&mut self) {
+		if let State::Stopped = &self.state {
+			return;
+		}
+		self.state = State::Paused;
+		self.scheduled_pause = None;
+		self.scheduled_resume = None;
+		self.volume_fade = Parameter::new(Value::Fixed(Decibels::SILENCE), Decibels::SILENCE);
+	}
+
 	pub fn update(&mut self, dt: f64, info: &Info) -> ChangedPlaybackState {
+		let mut changed = false;
+		if let Some(fade_out_tween) = update_scheduled_command(&mut self.scheduled_stop, dt, info) {
+			self.stop(fade_out_tween);
+			changed = true;
+		}
+		if let Some(fade_out_tween) = update_scheduled_command(&mut self.scheduled_pause, dt, info)
+		{
+			self.pause(fade_out_tween);
+			changed = true;
+		}
+		if self.scheduled_resume.is_some() {
+			if let Some(fade_in_tween) =
+				update_scheduled_command(&mut self.scheduled_resume, dt, info)
+			{
+				self.resume(StartTime::Immediate, fade_in_tween);
+				changed = true;
+			} else if self.scheduled_resume.is_none() {
+				// the resume time can never be reached, so stay paused
+				changed = true;
+			}
+		}
 		let finished = self.volume_fade.update(dt, info);
 		match &mut self.state {
 			State::Playing => {}
@@ -96,6 +190,7 @@ impl PlaybackStateManager {
 				let will_never_start = start_time.update(dt, info);
 				if will_never_start {
 					self.state = State::Stopped;
+					self.stopped_early = true;
 					return true;
 				}
 				if *start_time == StartTime::Immediate {
@@ -118,12 +213,34 @@ impl PlaybackStateManager {
 			}
 			State::Stopped => {}
 		}
-		false
+		changed
 	}
 }
 
 pub type ChangedPlaybackState = bool;
 
+/// Advances the start time of a scheduled pause or stop, returning its tween
+/// if it should happen now. Commands whose start times can never be reached
+/// are dropped.
+fn update_scheduled_command(
+	scheduled: &mut Option<(StartTime, Tween)>,
+	dt: f64,
+	info: &Info,
+) -> Option<Tween> {
+	let (start_time, tween) = scheduled.as_mut()?;
+	let will_never_start = start_time.update(dt, info);
+	if will_never_start {
+		*scheduled = None;
+		return None;
+	}
+	if *start_time == StartTime::Immediate {
+		let tween = *tween;
+		*scheduled = None;
+		return Some(tween);
+	}
+	None
+}
+
 enum State {
 	Playing,
 	Pausing,
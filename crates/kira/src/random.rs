@@ -0,0 +1,50 @@
+use std::{
+	collections::hash_map::RandomState,
+	hash::{BuildHasher, Hasher},
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A small, fast pseudo-random number generator (SplitMix64).
+///
+/// This isn't suitable for anything security-related, but it's more than
+/// good enough for adding variation to audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Random(u64);
+
+impl Random {
+	#[must_use]
+	pub fn new(seed: u64) -> Self {
+		Self(seed)
+	}
+
+	/// Creates a generator with a seed that's different every time
+	/// this is called.
+	#[must_use]
+	pub fn from_entropy() -> Self {
+		static COUNTER: AtomicU64 = AtomicU64::new(0);
+		let mut hasher = RandomState::new().build_hasher();
+		hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+		Self::new(hasher.finish())
+	}
+
+	#[must_use]
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+
+	/// Returns a random number from `0.0` (inclusive) to `1.0` (exclusive).
+	#[must_use]
+	pub fn next_f64(&mut self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+	}
+
+	/// Returns a random number from `-1.0` to `1.0`.
+	#[must_use]
+	pub fn next_bipolar(&mut self) -> f64 {
+		self.next_f64() * 2.0 - 1.0
+	}
+}
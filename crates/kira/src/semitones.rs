@@ -37,6 +37,18 @@ impl From<Semitones> for Value<PlaybackRate> {
 	}
 }
 
+impl From<f64> for Value<Semitones> {
+	fn from(value: f64) -> Self {
+		Self::Fixed(Semitones(value))
+	}
+}
+
+impl From<Semitones> for Value<Semitones> {
+	fn from(value: Semitones) -> Self {
+		Self::Fixed(value)
+	}
+}
+
 impl Add<Semitones> for Semitones {
 	type Output = Semitones;
 
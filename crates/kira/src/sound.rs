@@ -2,37 +2,69 @@
 Sources of audio.
 
 Any type that implements [`SoundData`] can be played using
-[`AudioManager::play`](crate::AudioManager::play). Kira comes with two
+[`AudioManager::play`](crate::AudioManager::play). Kira comes with three
 [`SoundData`] implementations:
 
 - [`StaticSoundData`](static_sound::StaticSoundData), which loads an entire chunk of audio
   into memory. This is more appropriate for short sounds, sounds you want to play multiple times,
   or sounds where consistent start times are important.
-- [`StreamingSoundData`](streaming::StreamingSoundData), which streams audio from a file or cursor
-  (only available on desktop platforms). This is more appropriate for long sounds that you only
-  play once at a time, like background music. Streaming sounds use less memory than static sounds.
+- [`StreamingSoundData`](streaming::StreamingSoundData), which streams audio from a file or cursor.
+  This is more appropriate for long sounds that you only play once at a time, like background music.
+  Streaming sounds use less memory than static sounds.
+- [`LiveSoundData`](live::LiveSoundData), which plays frames of audio pushed to it from another
+  thread. This is useful for audio that's generated while it plays, like the output of a
+  software synthesizer.
 
-These two sound types should cover most use cases, but if you need something else, you can
+These sound types should cover most use cases, but if you need something else, you can
 create your own types that implement the [`SoundData`] and [`Sound`] traits.
 */
 
+#[cfg(all(feature = "async", feature = "symphonia", not(target_arch = "wasm32")))]
+mod blocking_task;
+mod channel_mapping;
 #[cfg(feature = "symphonia")]
+mod decode_options;
+#[cfg(feature = "symphonia")]
+mod decode_progress;
 mod error;
+mod finished;
+pub mod live;
+mod metadata;
 mod playback_position;
+mod rerouting;
+mod send;
 pub mod static_sound;
-#[cfg(not(target_arch = "wasm32"))]
 pub mod streaming;
 #[cfg(feature = "symphonia")]
 mod symphonia;
+#[cfg(test)]
+mod test;
 mod transport;
+mod wav;
 
-use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use std::{
+	ops::{Range, RangeFrom, RangeFull, RangeTo},
+	time::Duration,
+};
 
+pub use channel_mapping::*;
+#[cfg(feature = "symphonia")]
+pub use decode_options::*;
 #[cfg(feature = "symphonia")]
+pub use decode_progress::DecodeProgress;
 pub use error::*;
+pub use finished::*;
+pub use metadata::*;
 pub use playback_position::*;
+pub use send::*;
+pub use wav::WavError;
 
-use crate::{frame::Frame, info::Info};
+use crate::{
+	frame::Frame,
+	info::Info,
+	track::{SendTrackId, TrackId},
+	Decibels, Parameter, Tween,
+};
 
 /// A source of audio that is loaded, but not yet playing.
 pub trait SoundData {
@@ -87,6 +119,61 @@ pub trait Sound: Send {
 	/// handle for the sound is dropped.
 	#[must_use]
 	fn finished(&self) -> bool;
+
+	/// Returns the current playback state of the sound.
+	///
+	/// This is used to count the sounds in each state for
+	/// [`AudioManager::num_sounds_by_state`](crate::AudioManager::num_sounds_by_state).
+	/// Defaults to [`PlaybackState::Playing`].
+	#[must_use]
+	fn playback_state(&self) -> PlaybackState {
+		PlaybackState::Playing
+	}
+
+	/// Returns an estimate of how much memory the sound is using (in
+	/// bytes), not counting memory shared with other sounds.
+	///
+	/// This is added up for every playing sound by
+	/// [`AudioManager::resource_usage`](crate::AudioManager::resource_usage).
+	/// Defaults to `0`.
+	#[must_use]
+	fn memory_usage(&self) -> usize {
+		0
+	}
+
+	/// Returns the track this sound would like to be moved to and the tween
+	/// to crossfade between the two tracks with, if any, and forgets the
+	/// request.
+	///
+	/// This is checked once per chunk after
+	/// [`on_start_processing`](Sound::on_start_processing) is called, except
+	/// while the sound is being moved. If it returns `Some`, the mixer will
+	/// crossfade the sound's output from its current track to the new track
+	/// and call [`on_move_finished`](Sound::on_move_finished) once the sound
+	/// is on the new track. The tween's start time is ignored.
+	#[must_use]
+	fn take_requested_track(&mut self) -> Option<(TrackId, Tween)> {
+		None
+	}
+
+	/// Called after the sound was moved to the track returned by
+	/// [`take_requested_track`](Sound::take_requested_track).
+	///
+	/// `moved` is `false` if the track no longer exists or has no room for
+	/// more sounds, in which case the sound stays on its current track.
+	fn on_move_finished(&mut self, moved: bool) {}
+
+	/// Returns the send tracks this sound sends its audio to and the
+	/// volume of each send.
+	///
+	/// After each call to [`process`](Sound::process), the track the sound
+	/// is on adds the sound's output to these send tracks, fading between
+	/// the previous and current value of each volume over the chunk.
+	/// Send tracks that no longer exist are skipped. Defaults to no sends.
+	#[must_use]
+	fn sends(&self) -> &[(SendTrackId, Parameter<Decibels>)] {
+		&[]
+	}
 }
 
 /// The playback state of a sound.
@@ -112,6 +199,18 @@ pub enum PlaybackState {
 }
 
 impl PlaybackState {
+	/// Every playback state, in the order used by
+	/// [`AudioManager::num_sounds_by_state`](crate::AudioManager::num_sounds_by_state).
+	pub const ALL: [PlaybackState; 7] = [
+		PlaybackState::Playing,
+		PlaybackState::Pausing,
+		PlaybackState::Paused,
+		PlaybackState::WaitingToResume,
+		PlaybackState::Resuming,
+		PlaybackState::Stopping,
+		PlaybackState::Stopped,
+	];
+
 	/// Whether the sound is advancing and outputting audio given
 	/// its current playback state.
 	pub fn is_advancing(self) -> bool {
@@ -137,6 +236,96 @@ pub struct Region {
 	pub end: EndPosition,
 }
 
+impl Region {
+	/// Creates a region that starts at `start` and lasts for `duration`.
+	///
+	/// The end of the region is measured from the start, so a region
+	/// that starts at a position in samples ends exactly `duration`
+	/// worth of samples later, whatever the sample rate of the audio is.
+	#[must_use]
+	pub fn from_start_and_duration(start: impl Into<PlaybackPosition>, duration: Duration) -> Self {
+		Self {
+			start: start.into(),
+			end: EndPosition::AfterStart(duration),
+		}
+	}
+
+	/// Returns the length of the region in samples for audio with the
+	/// given sample rate, or `None` if the region extends to the end of
+	/// the audio.
+	///
+	/// Inverted regions have a length of `0`.
+	#[must_use]
+	pub fn len_in_samples(&self, sample_rate: u32) -> Option<usize> {
+		self.end_in_samples(sample_rate)
+			.map(|end| end.saturating_sub(self.start.into_samples(sample_rate)))
+	}
+
+	/// Returns `true` if the region starts and ends at the same sample
+	/// for audio with the given sample rate.
+	///
+	/// Regions that extend to the end of the audio are never considered
+	/// empty, since the length of the audio isn't known.
+	#[must_use]
+	pub fn is_empty(&self, sample_rate: u32) -> bool {
+		self.end_in_samples(sample_rate)
+			.is_some_and(|end| end == self.start.into_samples(sample_rate))
+	}
+
+	/// Returns `true` if the region ends before it starts for audio with
+	/// the given sample rate.
+	#[must_use]
+	pub fn is_inverted(&self, sample_rate: u32) -> bool {
+		self.end_in_samples(sample_rate)
+			.is_some_and(|end| end < self.start.into_samples(sample_rate))
+	}
+
+	/// Returns `true` if the region can be played for audio with the
+	/// given sample rate.
+	///
+	/// A region is invalid if it's empty, inverted, or has a position in
+	/// seconds that is negative or not a number. Kira quietly treats
+	/// invalid regions as empty, so this is useful for catching bad data
+	/// before it's used.
+	#[must_use]
+	pub fn is_valid(&self, sample_rate: u32) -> bool {
+		self.positions_are_valid() && !self.is_empty(sample_rate) && !self.is_inverted(sample_rate)
+	}
+
+	/// Returns `false` if the start or end of the region is a negative or
+	/// NaN number of seconds.
+	#[must_use]
+	pub(crate) fn positions_are_valid(&self) -> bool {
+		self.start.is_valid()
+			&& match self.end {
+				EndPosition::EndOfAudio => true,
+				EndPosition::Custom(end) => end.is_valid(),
+				EndPosition::AfterStart(_) => true,
+			}
+	}
+
+	/// Returns the (exclusive) end of the region in samples, or `None`
+	/// if the region extends to the end of the audio.
+	#[must_use]
+	pub(crate) fn end_in_samples(&self, sample_rate: u32) -> Option<usize> {
+		match self.end {
+			EndPosition::EndOfAudio => None,
+			EndPosition::Custom(end) => Some(end.into_samples(sample_rate)),
+			EndPosition::AfterStart(duration) => {
+				Some(self.start.into_samples(sample_rate).saturating_add(
+					PlaybackPosition::Seconds(duration.as_secs_f64()).into_samples(sample_rate),
+				))
+			}
+		}
+	}
+}
+
+impl<T: Into<PlaybackPosition>> From<(T, Duration)> for Region {
+	fn from((start, duration): (T, Duration)) -> Self {
+		Self::from_start_and_duration(start, duration)
+	}
+}
+
 impl<T: Into<PlaybackPosition>> From<RangeFrom<T>> for Region {
 	fn from(range: RangeFrom<T>) -> Self {
 		Self {
@@ -173,6 +362,52 @@ impl From<RangeFull> for Region {
 	}
 }
 
+/// How a sound behaves when playback reaches the end of its loop region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoopMode {
+	/// Playback jumps back to the start of the loop region.
+	#[default]
+	Forward,
+	/// Playback changes direction, bouncing back and forth between the
+	/// start and end of the loop region.
+	PingPong,
+}
+
+/// How the left and right channels of a sound are combined before
+/// volume and panning are applied.
+///
+/// Folding a stereo sound down to mono is useful for sounds that should
+/// act as a single point, such as sounds played on a spatial track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChannelMixMode {
+	/// The channels are left as they are.
+	#[default]
+	Stereo,
+	/// Both channels are summed and lowered by 3dB, and the result is
+	/// played in both channels.
+	MonoSum,
+	/// The left channel is played in both channels.
+	LeftOnly,
+	/// The right channel is played in both channels.
+	RightOnly,
+}
+
+impl ChannelMixMode {
+	#[must_use]
+	pub(crate) fn apply(self, frame: Frame) -> Frame {
+		match self {
+			ChannelMixMode::Stereo => frame,
+			ChannelMixMode::MonoSum => {
+				Frame::from_mono((frame.left + frame.right) * std::f32::consts::FRAC_1_SQRT_2)
+			}
+			ChannelMixMode::LeftOnly => Frame::from_mono(frame.left),
+			ChannelMixMode::RightOnly => Frame::from_mono(frame.right),
+		}
+	}
+}
+
 /// A trait for types that can be converted into an `Option<Region>`.
 pub trait IntoOptionalRegion {
 	/// Converts the type into an `Option<Region>`.
@@ -201,4 +436,6 @@ pub enum EndPosition {
 	EndOfAudio,
 	/// A user-defined time in seconds.
 	Custom(PlaybackPosition),
+	/// A length of time after the start of the region.
+	AfterStart(Duration),
 }
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	future::Future,
+	panic::AssertUnwindSafe,
+	pin::Pin,
+	sync::{Arc, Mutex},
+	task::{Context, Poll, Waker},
+	thread,
+};
+
+/// A future that runs blocking work, like decoding an audio file, on a
+/// separate thread so it doesn't block the async executor.
+///
+/// The work starts right away, even if the future is never polled.
+pub(crate) struct BlockingTask<T> {
+	shared: Arc<Mutex<TaskState<T>>>,
+}
+
+impl<T: Send + 'static> BlockingTask<T> {
+	/// Starts running `work` on a new thread.
+	#[must_use]
+	pub fn spawn(work: impl FnOnce() -> T + Send + 'static) -> Self {
+		let shared = Arc::new(Mutex::new(TaskState {
+			result: None,
+			waker: None,
+		}));
+		thread::spawn({
+			let shared = shared.clone();
+			move || {
+				// panics are re-raised when the future is polled
+				let result = std::panic::catch_unwind(AssertUnwindSafe(work));
+				let mut state = shared.lock().expect("blocking task mutex poisoned");
+				state.result = Some(result);
+				if let Some(waker) = state.waker.take() {
+					waker.wake();
+				}
+			}
+		});
+		Self { shared }
+	}
+}
+
+impl<T> Future for BlockingTask<T> {
+	type Output = T;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut state = self.shared.lock().expect("blocking task mutex poisoned");
+		match state.result.take() {
+			Some(Ok(result)) => Poll::Ready(result),
+			Some(Err(panic)) => std::panic::resume_unwind(panic),
+			None => {
+				state.waker = Some(cx.waker().clone());
+				Poll::Pending
+			}
+		}
+	}
+}
+
+struct TaskState<T> {
+	result: Option<thread::Result<T>>,
+	waker: Option<Waker>,
+}
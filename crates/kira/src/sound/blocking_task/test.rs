@@ -0,0 +1,22 @@
+use crate::test_helpers::block_on;
+
+use super::BlockingTask;
+
+/// Tests that a blocking task outputs the result of its work.
+#[test]
+fn outputs_result() {
+	let task = BlockingTask::spawn(|| {
+		std::thread::sleep(std::time::Duration::from_millis(10));
+		2 + 2
+	});
+	assert_eq!(block_on(task), 4);
+}
+
+/// Tests that a panic in a blocking task's work is re-raised when the
+/// task is awaited.
+#[test]
+#[should_panic(expected = "decoding failed")]
+fn reraises_panics() {
+	let task = BlockingTask::spawn(|| -> () { panic!("decoding failed") });
+	block_on(task);
+}
@@ -0,0 +1,30 @@
+/**
+How the channels of audio files with more than two channels are turned
+into stereo audio.
+
+This can be set with
+[`DecodeOptions::channel_mapping`](super::DecodeOptions::channel_mapping).
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChannelMapping {
+	/**
+	Mixes every channel into the left and right channels using the
+	standard downmix coefficients.
+
+	The front left and right channels are kept at full volume. Center
+	channels are mixed into both sides and surround channels are mixed
+	into their side, all at -3 dB. Low frequency effects channels are
+	left out. Mono and stereo audio is kept as it is.
+	*/
+	#[default]
+	Downmix,
+	/// Uses the channels at the given indices as the left and right
+	/// channels and leaves out the rest.
+	Select {
+		/// The index of the channel to use as the left channel.
+		left: usize,
+		/// The index of the channel to use as the right channel.
+		right: usize,
+	},
+}
@@ -0,0 +1,268 @@
+#[cfg(test)]
+mod test;
+
+use symphonia::core::{formats::FormatReader, io::MediaSource, probe::Hint};
+
+use super::{static_sound::ResampleQuality, symphonia::probe, ChannelMapping, FromFileError};
+
+/**
+Options for decoding an audio file.
+
+These can be passed to
+[`StaticSoundData::from_file_with`](super::static_sound::StaticSoundData::from_file_with)
+and [`StreamingSoundData::from_file_with`](super::streaming::StreamingSoundData::from_file_with).
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodeOptions {
+	/// How many threads to decode the audio with. Defaults to `1`.
+	///
+	/// Formats whose packets can be decoded independently, like FLAC and
+	/// WAV, are split into parts that are decoded at the same time. For
+	/// other formats, the file is read on one thread while the audio is
+	/// decoded on another. The decoded audio is exactly the same no matter
+	/// how many threads are used.
+	///
+	/// This only affects static sounds. Streaming sounds always decode
+	/// audio on one thread.
+	pub threads: usize,
+	/// The index of the track to decode in files that contain multiple
+	/// tracks, or `None` to decode the default track. Defaults to `None`.
+	///
+	/// The tracks in a file can be listed with [`probe_tracks`].
+	pub track_index: Option<usize>,
+	/// How to turn audio with more than two channels into stereo audio.
+	/// Defaults to [`ChannelMapping::Downmix`].
+	///
+	/// The mapping that was used is reported by
+	/// [`Metadata::channel_mapping`](super::Metadata::channel_mapping).
+	pub channel_mapping: ChannelMapping,
+	/// The file extension of the audio (like `"ogg"`), if it's known.
+	/// Defaults to `None`.
+	///
+	/// This is used to help detect the format of the audio, which is
+	/// useful for audio that doesn't come from a file with the right
+	/// extension.
+	pub format_hint: Option<String>,
+	/// The MIME type of the audio (like `"audio/ogg"`), if it's known.
+	/// Defaults to `None`.
+	///
+	/// Like [`format_hint`](Self::format_hint), this is used to help
+	/// detect the format of the audio.
+	pub mime_hint: Option<String>,
+	/// The sample rate (in Hz) to convert the audio to after it's
+	/// decoded, and how accurately to convert it, or `None` to keep the
+	/// audio's own sample rate. Defaults to `None`.
+	///
+	/// This only affects static sounds. See
+	/// [`StaticSoundData::resampled`](super::static_sound::StaticSoundData::resampled)
+	/// for more information.
+	pub resample_to: Option<(u32, ResampleQuality)>,
+}
+
+impl DecodeOptions {
+	/// Creates a new [`DecodeOptions`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			threads: 1,
+			track_index: None,
+			channel_mapping: ChannelMapping::Downmix,
+			format_hint: None,
+			mime_hint: None,
+			resample_to: None,
+		}
+	}
+
+	/// Sets how many threads to decode the audio with.
+	#[must_use = "This method consumes self and returns a modified DecodeOptions, so the return value should be used"]
+	pub fn threads(self, threads: usize) -> Self {
+		Self { threads, ..self }
+	}
+
+	/// Sets the index of the track to decode.
+	#[must_use = "This method consumes self and returns a modified DecodeOptions, so the return value should be used"]
+	pub fn track_index(self, track_index: impl Into<Option<usize>>) -> Self {
+		Self {
+			track_index: track_index.into(),
+			..self
+		}
+	}
+
+	/// Sets how to turn audio with more than two channels into stereo
+	/// audio.
+	#[must_use = "This method consumes self and returns a modified DecodeOptions, so the return value should be used"]
+	pub fn channel_mapping(self, channel_mapping: ChannelMapping) -> Self {
+		Self {
+			channel_mapping,
+			..self
+		}
+	}
+
+	/// Sets the file extension of the audio.
+	#[must_use = "This method consumes self and returns a modified DecodeOptions, so the return value should be used"]
+	pub fn format_hint(self, format_hint: impl Into<String>) -> Self {
+		Self {
+			format_hint: Some(format_hint.into()),
+			..self
+		}
+	}
+
+	/// Sets the MIME type of the audio.
+	#[must_use = "This method consumes self and returns a modified DecodeOptions, so the return value should be used"]
+	pub fn mime_hint(self, mime_hint: impl Into<String>) -> Self {
+		Self {
+			mime_hint: Some(mime_hint.into()),
+			..self
+		}
+	}
+
+	/**
+	Sets the sample rate (in Hz) to convert the audio to after it's
+	decoded.
+
+	# Examples
+
+	Resample sound effects to the audio device's sample rate when they're
+	loaded:
+
+	```no_run
+	use kira::sound::{
+		static_sound::{ResampleQuality, StaticSoundData},
+		DecodeOptions,
+	};
+
+	let sound_data = StaticSoundData::from_file_with(
+		"blip.ogg",
+		DecodeOptions::new().resample_to(48_000, ResampleQuality::Sinc),
+	)?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+
+	# Panics
+
+	Panics if `sample_rate` is `0`.
+	*/
+	#[must_use = "This method consumes self and returns a modified DecodeOptions, so the return value should be used"]
+	pub fn resample_to(self, sample_rate: u32, quality: ResampleQuality) -> Self {
+		assert!(sample_rate != 0, "sample rates must be greater than 0");
+		Self {
+			resample_to: Some((sample_rate, quality)),
+			..self
+		}
+	}
+
+	/// Returns the hint Symphonia uses to detect the format of the audio.
+	#[must_use]
+	pub(crate) fn hint(&self) -> Hint {
+		let mut hint = Hint::new();
+		if let Some(format_hint) = &self.format_hint {
+			hint.with_extension(format_hint);
+		}
+		if let Some(mime_hint) = &self.mime_hint {
+			hint.mime_type(mime_hint);
+		}
+		hint
+	}
+}
+
+impl Default for DecodeOptions {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Information about a track in an audio file, returned by
+/// [`probe_tracks`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackInfo {
+	/// The index of the track, which can be passed to
+	/// [`DecodeOptions::track_index`].
+	pub index: usize,
+	/// The short name of the codec the track is encoded with, like
+	/// `"vorbis"`, or `None` if the codec isn't supported.
+	pub codec: Option<String>,
+	/// The number of channels in the track, if it's known.
+	pub num_channels: Option<u16>,
+	/// The sample rate of the track (in Hz), if it's known.
+	pub sample_rate: Option<u32>,
+	/// The language of the track, if it's known, usually as an ISO 639-2
+	/// code like `"eng"`.
+	pub language: Option<String>,
+	/// Whether the track is decoded when no
+	/// [`track_index`](DecodeOptions::track_index) is set.
+	pub is_default: bool,
+}
+
+/**
+Lists the tracks in an audio file.
+
+# Examples
+
+Decode the first English track in a file:
+
+```no_run
+use kira::sound::{probe_tracks, static_sound::StaticSoundData, DecodeOptions};
+
+let tracks = probe_tracks("movie.mka")?;
+let track = tracks
+	.iter()
+	.find(|track| track.language.as_deref() == Some("eng"))
+	.unwrap_or(&tracks[0]);
+let sound_data = StaticSoundData::from_file_with(
+	"movie.mka",
+	DecodeOptions::new().track_index(track.index),
+)?;
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "symphonia", not(wasm32)))))]
+pub fn probe_tracks(path: impl AsRef<std::path::Path>) -> Result<Vec<TrackInfo>, FromFileError> {
+	probe_media_source_tracks(Box::new(std::fs::File::open(path)?))
+}
+
+/// Lists the tracks in audio from a type that implements Symphonia's
+/// [`MediaSource`] trait.
+#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
+pub fn probe_media_source_tracks(
+	media_source: Box<dyn MediaSource>,
+) -> Result<Vec<TrackInfo>, FromFileError> {
+	let format_reader = probe(media_source, &Default::default())?.format;
+	let default_track_id = format_reader.default_track().map(|track| track.id);
+	let codecs = symphonia::default::get_codecs();
+	Ok(format_reader
+		.tracks()
+		.iter()
+		.enumerate()
+		.map(|(index, track)| TrackInfo {
+			index,
+			codec: codecs
+				.get_codec(track.codec_params.codec)
+				.map(|descriptor| descriptor.short_name.to_owned()),
+			num_channels: track
+				.codec_params
+				.channels
+				.map(|channels| channels.count() as u16),
+			sample_rate: track.codec_params.sample_rate,
+			language: track.language.clone(),
+			is_default: Some(track.id) == default_track_id,
+		})
+		.collect())
+}
+
+/// Returns the track to decode from a file.
+pub(crate) fn select_track(
+	format_reader: &dyn FormatReader,
+	track_index: Option<usize>,
+) -> Result<&symphonia::core::formats::Track, FromFileError> {
+	match track_index {
+		Some(index) => format_reader
+			.tracks()
+			.get(index)
+			.ok_or(FromFileError::TrackNotFound(index)),
+		None => format_reader
+			.default_track()
+			.ok_or(FromFileError::NoDefaultTrack),
+	}
+}
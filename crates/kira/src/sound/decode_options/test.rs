@@ -0,0 +1,75 @@
+#[cfg(feature = "wav")]
+use std::io::Cursor;
+
+#[cfg(feature = "wav")]
+use crate::{
+	sound::{static_sound::StaticSoundData, FromFileError},
+	test_helpers::wav_fixture,
+};
+
+#[cfg(feature = "wav")]
+use super::{probe_media_source_tracks, DecodeOptions, TrackInfo};
+
+/// Tests that the tracks in a file are listed with their codec, channel
+/// count, and sample rate.
+#[test]
+#[cfg(feature = "wav")]
+fn probes_tracks() {
+	let tracks =
+		probe_media_source_tracks(Box::new(Cursor::new(wav_fixture(100, &[0; 4])))).unwrap();
+	assert_eq!(
+		tracks,
+		[TrackInfo {
+			index: 0,
+			codec: Some("pcm_s16le".to_owned()),
+			num_channels: Some(1),
+			sample_rate: Some(100),
+			language: None,
+			is_default: true,
+		}]
+	);
+}
+
+/// Tests that a track can be picked by its index, and that picking a
+/// track that doesn't exist is reported.
+#[test]
+#[cfg(feature = "wav")]
+fn selects_track_by_index() {
+	let wav = wav_fixture(100, &[0, 1000, 2000, 3000]);
+	let decode = |track_index| {
+		StaticSoundData::from_boxed_media_source_with(
+			Box::new(Cursor::new(wav.clone())),
+			DecodeOptions::new().track_index(track_index),
+		)
+	};
+	assert_eq!(decode(0).unwrap().frames.len(), 4);
+	assert!(matches!(decode(1), Err(FromFileError::TrackNotFound(1))));
+}
+
+/// Tests that streaming a track that doesn't exist is reported.
+#[test]
+#[cfg(feature = "wav")]
+fn streaming_reports_missing_track() {
+	use crate::sound::streaming::symphonia::SymphoniaDecoder;
+
+	let result = SymphoniaDecoder::new(
+		Box::new(Cursor::new(wav_fixture(100, &[0; 4]))),
+		DecodeOptions::new().track_index(2),
+	);
+	assert!(matches!(result, Err(FromFileError::TrackNotFound(2))));
+}
+
+/// Tests that static sounds are resampled after they're decoded.
+#[test]
+#[cfg(feature = "wav")]
+fn resamples_static_sounds() {
+	use crate::sound::static_sound::ResampleQuality;
+
+	let data = StaticSoundData::from_boxed_media_source_with(
+		Box::new(Cursor::new(wav_fixture(100, &[1000; 100]))),
+		DecodeOptions::new().resample_to(150, ResampleQuality::Sinc),
+	)
+	.unwrap();
+	assert_eq!(data.sample_rate, 150);
+	assert_eq!(data.frames.len(), 150);
+}
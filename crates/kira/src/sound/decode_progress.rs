@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	io::{Read, Seek, SeekFrom},
+	ops::ControlFlow,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+};
+
+use symphonia::core::io::MediaSource;
+
+use super::FromFileError;
+
+/// How many packets are decoded between each progress report.
+pub(crate) const PACKETS_PER_PROGRESS_REPORT: usize = 16;
+
+/**
+How much of an audio file has been decoded so far.
+
+This is passed to the callback given to
+[`StaticSoundData::from_file_with_progress`](super::static_sound::StaticSoundData::from_file_with_progress).
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodeProgress {
+	/// The number of frames of audio that have been decoded.
+	pub frames_decoded: usize,
+	/// The total number of frames of audio in the file, if the file
+	/// stores it.
+	///
+	/// Some formats only store an estimate, so `frames_decoded` can end
+	/// up slightly different from this.
+	pub total_frames: Option<usize>,
+	/// The number of bytes of the file that have been read.
+	pub bytes_read: u64,
+	/// The size of the file in bytes, if it's known.
+	pub total_bytes: Option<u64>,
+}
+
+impl DecodeProgress {
+	/// Returns how much of the file has been decoded, from `0.0` to
+	/// `1.0`, or `None` if neither the number of frames nor the size of
+	/// the file is known.
+	///
+	/// This is measured in frames if the total number of frames is
+	/// known, and in bytes otherwise.
+	#[must_use]
+	pub fn fraction(&self) -> Option<f64> {
+		let (done, total) = match (self.total_frames, self.total_bytes) {
+			(Some(total_frames), _) if total_frames > 0 => {
+				(self.frames_decoded as f64, total_frames as f64)
+			}
+			(_, Some(total_bytes)) if total_bytes > 0 => {
+				(self.bytes_read as f64, total_bytes as f64)
+			}
+			_ => return None,
+		};
+		Some((done / total).min(1.0))
+	}
+}
+
+/// A callback that is given the progress of a decode and returns whether
+/// to keep decoding.
+pub(crate) type ProgressCallback<'a> = &'a mut dyn FnMut(DecodeProgress) -> ControlFlow<()>;
+
+/// Keeps track of the progress of a decode and reports it every
+/// [`PACKETS_PER_PROGRESS_REPORT`] packets.
+pub(crate) struct ProgressReporter<'a> {
+	callback: Option<ProgressCallback<'a>>,
+	bytes_read: Arc<AtomicU64>,
+	total_bytes: Option<u64>,
+	total_frames: Option<usize>,
+	frames_decoded: usize,
+	packets_since_report: usize,
+}
+
+impl<'a> ProgressReporter<'a> {
+	#[must_use]
+	pub(crate) fn new(
+		callback: Option<ProgressCallback<'a>>,
+		bytes_read: Arc<AtomicU64>,
+		total_bytes: Option<u64>,
+	) -> Self {
+		Self {
+			callback,
+			bytes_read,
+			total_bytes,
+			total_frames: None,
+			frames_decoded: 0,
+			packets_since_report: 0,
+		}
+	}
+
+	/// Returns `true` if there's a callback to report progress to.
+	#[must_use]
+	pub(crate) fn is_reporting(&self) -> bool {
+		self.callback.is_some()
+	}
+
+	pub(crate) fn set_total_frames(&mut self, total_frames: Option<usize>) {
+		self.total_frames = total_frames;
+	}
+
+	/// Records that a packet was decoded, reporting the progress if
+	/// enough packets have been decoded since the last report.
+	///
+	/// Returns [`FromFileError::Cancelled`] if the callback asks for the
+	/// decode to stop.
+	pub(crate) fn packet_decoded(&mut self, num_frames: usize) -> Result<(), FromFileError> {
+		self.frames_decoded += num_frames;
+		self.packets_since_report += 1;
+		if self.packets_since_report < PACKETS_PER_PROGRESS_REPORT {
+			return Ok(());
+		}
+		self.packets_since_report = 0;
+		let Some(callback) = &mut self.callback else {
+			return Ok(());
+		};
+		let progress = DecodeProgress {
+			frames_decoded: self.frames_decoded,
+			total_frames: self.total_frames,
+			bytes_read: self.bytes_read.load(Ordering::Relaxed),
+			total_bytes: self.total_bytes,
+		};
+		match callback(progress) {
+			ControlFlow::Continue(()) => Ok(()),
+			ControlFlow::Break(()) => Err(FromFileError::Cancelled),
+		}
+	}
+}
+
+/// Wraps a [`MediaSource`] and keeps count of how far into it has been
+/// read.
+pub(crate) struct CountingSource {
+	source: Box<dyn MediaSource>,
+	position: Arc<AtomicU64>,
+}
+
+impl CountingSource {
+	#[must_use]
+	pub(crate) fn new(source: Box<dyn MediaSource>, position: Arc<AtomicU64>) -> Self {
+		Self { source, position }
+	}
+}
+
+impl Read for CountingSource {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let num_bytes = self.source.read(buf)?;
+		self.position.fetch_add(num_bytes as u64, Ordering::Relaxed);
+		Ok(num_bytes)
+	}
+}
+
+impl Seek for CountingSource {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		let position = self.source.seek(pos)?;
+		self.position.store(position, Ordering::Relaxed);
+		Ok(position)
+	}
+}
+
+impl MediaSource for CountingSource {
+	fn is_seekable(&self) -> bool {
+		self.source.is_seekable()
+	}
+
+	fn byte_len(&self) -> Option<u64> {
+		self.source.byte_len()
+	}
+}
@@ -0,0 +1,99 @@
+#[cfg(feature = "wav")]
+use std::{io::Cursor, ops::ControlFlow};
+
+#[cfg(feature = "wav")]
+use crate::{
+	sound::{static_sound::StaticSoundData, DecodeOptions, FromFileError},
+	test_helpers::wav_fixture,
+};
+
+use super::DecodeProgress;
+
+/// Tests that the fraction of the file that was decoded is measured in
+/// frames when the total number of frames is known, and in bytes
+/// otherwise.
+#[test]
+fn fraction() {
+	let progress = DecodeProgress {
+		frames_decoded: 25,
+		total_frames: Some(100),
+		bytes_read: 90,
+		total_bytes: Some(100),
+	};
+	assert_eq!(progress.fraction(), Some(0.25));
+	assert_eq!(
+		DecodeProgress {
+			total_frames: None,
+			..progress
+		}
+		.fraction(),
+		Some(0.9)
+	);
+	assert_eq!(
+		DecodeProgress {
+			frames_decoded: 150,
+			..progress
+		}
+		.fraction(),
+		Some(1.0)
+	);
+	assert_eq!(
+		DecodeProgress {
+			total_frames: None,
+			total_bytes: None,
+			..progress
+		}
+		.fraction(),
+		None
+	);
+}
+
+/// Tests that progress is reported while decoding with any number of
+/// threads.
+#[test]
+#[cfg(feature = "wav")]
+fn reports_progress() {
+	let wav = wav_fixture(44100, &[0; 441000]);
+	for threads in [1, 4] {
+		let mut reports = vec![];
+		let data = StaticSoundData::decode_media_source(
+			Box::new(Cursor::new(wav.clone())),
+			DecodeOptions::new().threads(threads),
+			Some(&mut |progress| {
+				reports.push(progress);
+				ControlFlow::Continue(())
+			}),
+		)
+		.unwrap();
+		assert_eq!(data.num_frames(), 441000);
+		assert!(!reports.is_empty());
+		assert!(reports
+			.windows(2)
+			.all(|reports| { reports[1].frames_decoded > reports[0].frames_decoded }));
+		for report in &reports {
+			assert_eq!(report.total_frames, Some(441000));
+			assert_eq!(report.total_bytes, Some(wav.len() as u64));
+			assert!(report.frames_decoded <= 441000);
+		}
+	}
+}
+
+/// Tests that breaking from the callback cancels the decode.
+#[test]
+#[cfg(feature = "wav")]
+fn cancels_decode() {
+	let wav = wav_fixture(44100, &[0; 441000]);
+	for threads in [1, 4] {
+		let mut num_reports = 0;
+		let result = StaticSoundData::decode_media_source(
+			Box::new(Cursor::new(wav.clone())),
+			DecodeOptions::new().threads(threads),
+			Some(&mut |_| {
+				num_reports += 1;
+				ControlFlow::Break(())
+			}),
+		);
+		assert!(matches!(result, Err(FromFileError::Cancelled)));
+		assert_eq!(num_reports, 1);
+	}
+}
@@ -1,21 +1,49 @@
+#[cfg(all(test, feature = "symphonia"))]
+mod test;
+
 use std::fmt::Display;
 
+use super::WavError;
+
 /// Errors that can occur when loading or streaming an audio file.
 #[derive(Debug)]
-#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
 pub enum FromFileError {
 	/// Could not determine the default audio track in the file.
 	NoDefaultTrack,
+	/// The file doesn't have a track at the index given by
+	/// [`DecodeOptions::track_index`](crate::sound::DecodeOptions::track_index).
+	TrackNotFound(usize),
 	/// Could not determine the sample rate of the audio.
 	UnknownSampleRate,
 	/// Could not determine the duration of the audio.
 	UnknownDuration,
-	/// The audio uses an unsupported channel configuration. Only
-	/// mono and stereo audio is supported.
+	/// The channels selected with
+	/// [`ChannelMapping::Select`](super::ChannelMapping::Select) don't
+	/// exist in the audio.
 	UnsupportedChannelConfiguration,
+	/// The format of the audio couldn't be determined. This usually means
+	/// the audio is corrupt or uses a container format that isn't supported.
+	UnrecognizedFormat(UnrecognizedFormatError),
+	/// The audio can't be seeked because it comes from a source that
+	/// doesn't support seeking.
+	NotSeekable,
+	/// A [`SeekIndex`](crate::sound::streaming::SeekIndex) can't be built
+	/// for the audio because it isn't an MP3 file.
+	SeekIndexUnsupported,
+	/// The [`SeekIndex`](crate::sound::streaming::SeekIndex) was built for
+	/// a different file.
+	SeekIndexMismatch,
+	/// The decode was cancelled by the callback passed to
+	/// [`StaticSoundData::from_file_with_progress`](crate::sound::static_sound::StaticSoundData::from_file_with_progress).
+	Cancelled,
+	/// The WAV file couldn't be read by the decoder that's used when the
+	/// `symphonia` feature is disabled.
+	InvalidWav(WavError),
 	/// An error occurred while reading the file from the filesystem.
 	IoError(std::io::Error),
 	/// An error occurred when parsing the file.
+	#[cfg(feature = "symphonia")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
 	SymphoniaError(symphonia::core::errors::Error),
 }
 
@@ -25,6 +53,9 @@ impl Display for FromFileError {
 			FromFileError::NoDefaultTrack => {
 				f.write_str("Could not determine the default audio track")
 			}
+			FromFileError::TrackNotFound(index) => {
+				write!(f, "The file does not have a track at index {}", index)
+			}
 			FromFileError::UnknownSampleRate => {
 				f.write_str("Could not detect the sample rate of the audio")
 			}
@@ -32,9 +63,20 @@ impl Display for FromFileError {
 				f.write_str("Could not detect the duration of the audio")
 			}
 			FromFileError::UnsupportedChannelConfiguration => {
-				f.write_str("Only mono and stereo audio is supported")
+				f.write_str("The selected channels don't exist in the audio")
+			}
+			FromFileError::UnrecognizedFormat(error) => error.fmt(f),
+			FromFileError::NotSeekable => f.write_str("The audio source does not support seeking"),
+			FromFileError::SeekIndexUnsupported => {
+				f.write_str("Seek indexes can only be built for MP3 files")
 			}
+			FromFileError::SeekIndexMismatch => {
+				f.write_str("The seek index was built for a different file")
+			}
+			FromFileError::Cancelled => f.write_str("The decode was cancelled"),
+			FromFileError::InvalidWav(error) => error.fmt(f),
 			FromFileError::IoError(error) => error.fmt(f),
+			#[cfg(feature = "symphonia")]
 			FromFileError::SymphoniaError(error) => error.fmt(f),
 		}
 	}
@@ -43,7 +85,11 @@ impl Display for FromFileError {
 impl std::error::Error for FromFileError {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
 		match self {
+			#[cfg(feature = "symphonia")]
+			FromFileError::UnrecognizedFormat(error) => Some(&error.error),
+			FromFileError::InvalidWav(error) => Some(error),
 			FromFileError::IoError(error) => Some(error),
+			#[cfg(feature = "symphonia")]
 			FromFileError::SymphoniaError(error) => Some(error),
 			_ => None,
 		}
@@ -56,8 +102,137 @@ impl From<std::io::Error> for FromFileError {
 	}
 }
 
+#[cfg(feature = "symphonia")]
 impl From<symphonia::core::errors::Error> for FromFileError {
 	fn from(v: symphonia::core::errors::Error) -> Self {
 		Self::SymphoniaError(v)
 	}
 }
+
+/// An audio format that can be enabled with one of Kira's features.
+struct Format {
+	/// The name of the format, which is also the name of the feature.
+	name: &'static str,
+	enabled: bool,
+	/// Returns `true` if the first bytes of a file belong to this format.
+	matches_signature: fn(&[u8]) -> bool,
+}
+
+const FORMATS: [Format; 4] = [
+	Format {
+		name: "mp3",
+		enabled: cfg!(feature = "mp3"),
+		matches_signature: |signature| {
+			signature.starts_with(b"ID3")
+				// an mpeg frame header, which sets the layer bits unlike aac
+				|| (signature.len() >= 2
+					&& signature[0] == 0xff
+					&& signature[1] & 0xe0 == 0xe0
+					&& signature[1] & 0x06 != 0)
+		},
+	},
+	Format {
+		name: "ogg",
+		enabled: cfg!(feature = "ogg"),
+		matches_signature: |signature| signature.starts_with(b"OggS"),
+	},
+	Format {
+		name: "flac",
+		enabled: cfg!(feature = "flac"),
+		matches_signature: |signature| signature.starts_with(b"fLaC"),
+	},
+	Format {
+		name: "wav",
+		// wav files can be read without symphonia
+		enabled: cfg!(any(feature = "wav", not(feature = "symphonia"))),
+		matches_signature: |signature| {
+			signature.starts_with(b"RIFF") && signature.get(8..12) == Some(b"WAVE")
+		},
+	},
+];
+
+/// Returns the audio formats that the enabled features of Kira can load,
+/// like `"ogg"`.
+///
+/// Formats enabled through Symphonia's own features, like AAC, aren't
+/// included.
+#[must_use]
+pub fn supported_formats() -> Vec<&'static str> {
+	FORMATS
+		.iter()
+		.filter(|format| format.enabled)
+		.map(|format| format.name)
+		.collect()
+}
+
+/// Information about audio whose format couldn't be determined.
+#[derive(Debug)]
+pub struct UnrecognizedFormatError {
+	/// The error Symphonia returned when detecting the format.
+	#[cfg(feature = "symphonia")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
+	pub error: symphonia::core::errors::Error,
+	/// Up to the first 16 bytes of the audio, which usually identify
+	/// its format.
+	///
+	/// This is empty if the audio comes from a source that can't seek,
+	/// since the bytes have already been read by then.
+	pub signature: Vec<u8>,
+}
+
+impl UnrecognizedFormatError {
+	/// The number of bytes kept in [`signature`](Self::signature).
+	pub(crate) const SIGNATURE_LEN: usize = 16;
+
+	/// Returns the name of the format the audio appears to be in, like
+	/// `"mp3"`, based on its first bytes.
+	#[must_use]
+	pub fn detected_format(&self) -> Option<&'static str> {
+		FORMATS
+			.iter()
+			.find(|format| (format.matches_signature)(&self.signature))
+			.map(|format| format.name)
+	}
+
+	/// Returns the name of the format the audio appears to be in if
+	/// support for that format isn't enabled.
+	#[must_use]
+	pub fn disabled_format(&self) -> Option<&'static str> {
+		FORMATS
+			.iter()
+			.find(|format| !format.enabled && (format.matches_signature)(&self.signature))
+			.map(|format| format.name)
+	}
+}
+
+impl Display for UnrecognizedFormatError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Could not determine the format of the audio")?;
+		#[cfg(feature = "symphonia")]
+		write!(f, " ({})", self.error)?;
+		if let Some(format) = self.disabled_format() {
+			write!(
+				f,
+				". The audio looks like {format}, but {format} support is not enabled (enable the `{format}` feature)"
+			)?;
+		} else {
+			let supported_formats = supported_formats();
+			if supported_formats.is_empty() {
+				f.write_str(". No audio formats are enabled")?;
+			} else {
+				write!(
+					f,
+					". The supported formats are {}",
+					supported_formats.join(", ")
+				)?;
+			}
+		}
+		if !self.signature.is_empty() {
+			f.write_str(". The audio starts with the bytes")?;
+			for byte in &self.signature {
+				write!(f, " {byte:02x}")?;
+			}
+		}
+		Ok(())
+	}
+}
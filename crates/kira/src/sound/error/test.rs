@@ -0,0 +1,57 @@
+use std::io::Cursor;
+
+use crate::sound::static_sound::StaticSoundData;
+
+use super::{supported_formats, FromFileError, UnrecognizedFormatError};
+
+fn unrecognized_format_error(signature: &[u8]) -> UnrecognizedFormatError {
+	UnrecognizedFormatError {
+		error: symphonia::core::errors::Error::Unsupported("no suitable format reader found"),
+		signature: signature.to_vec(),
+	}
+}
+
+/// Tests that the format of audio is detected from its first bytes.
+#[test]
+fn detects_format_from_signature() {
+	for (signature, format) in [
+		(&b"ID3\x04\0\0\0\0\0\0"[..], Some("mp3")),
+		(&[0xff, 0xfb, 0x90, 0x64], Some("mp3")),
+		// aac adts headers look similar to mp3 frame headers
+		(&[0xff, 0xf1, 0x50, 0x80], None),
+		(b"OggS\0\x02", Some("ogg")),
+		(b"fLaC\0\0\0\x22", Some("flac")),
+		(b"RIFF\x24\0\0\0WAVEfmt ", Some("wav")),
+		(b"RIFF\x24\0\0\0AVI LIST", None),
+		(&[], None),
+	] {
+		let error = unrecognized_format_error(signature);
+		assert_eq!(error.detected_format(), format);
+		assert_eq!(
+			error.disabled_format(),
+			format.filter(|format| !supported_formats().contains(format))
+		);
+	}
+}
+
+/// Tests that the error message includes the supported formats and the
+/// first bytes of the audio.
+#[test]
+fn unrecognized_format_message() {
+	let message = unrecognized_format_error(&[0x00, 0x01, 0xab]).to_string();
+	assert!(message.contains(&supported_formats().join(", ")));
+	assert!(message.ends_with("The audio starts with the bytes 00 01 ab"));
+}
+
+/// Tests that the first bytes of audio whose format can't be detected
+/// are kept in the error.
+#[test]
+fn keeps_signature_of_unrecognized_audio() {
+	let bytes = (0..64).collect::<Vec<u8>>();
+	let Err(FromFileError::UnrecognizedFormat(error)) =
+		StaticSoundData::from_cursor(Cursor::new(bytes.clone()))
+	else {
+		panic!("the format of the audio should not be detected");
+	};
+	assert_eq!(error.signature, &bytes[..16]);
+}
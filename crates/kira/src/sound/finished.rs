@@ -0,0 +1,87 @@
+use std::sync::{
+	atomic::{AtomicU8, Ordering},
+	Arc,
+};
+
+/// Why a sound finished playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FinishReason {
+	/// Playback reached the end of the audio.
+	ReachedEnd,
+	/// The sound was stopped before reaching the end of the audio,
+	/// either using its handle or because its start time can never
+	/// be reached.
+	Stopped,
+	/// The sound stopped because it encountered an error. This only
+	/// happens for streaming sounds.
+	EncounteredError,
+}
+
+impl FinishReason {
+	fn from_u8(value: u8) -> Option<Self> {
+		match value {
+			0 => None,
+			1 => Some(Self::ReachedEnd),
+			2 => Some(Self::Stopped),
+			3 => Some(Self::EncounteredError),
+			_ => panic!("Invalid finish reason"),
+		}
+	}
+
+	fn as_u8(self) -> u8 {
+		match self {
+			Self::ReachedEnd => 1,
+			Self::Stopped => 2,
+			Self::EncounteredError => 3,
+		}
+	}
+}
+
+/**
+Receives a notification when a sound finishes playing.
+
+The receiver can be cloned and checked from any thread without blocking.
+*/
+#[derive(Debug, Clone)]
+pub struct FinishedReceiver {
+	pub(crate) finished: Arc<Finished>,
+}
+
+impl FinishedReceiver {
+	/// Returns the reason the sound finished, or `None` if the sound
+	/// is still playing.
+	#[must_use]
+	pub fn try_recv(&self) -> Option<FinishReason> {
+		self.finished.reason()
+	}
+
+	/// Returns `true` if the sound has finished playing.
+	#[must_use]
+	pub fn is_finished(&self) -> bool {
+		self.try_recv().is_some()
+	}
+}
+
+/// A lock-free flag that the audio thread sets when a sound finishes.
+#[derive(Debug, Default)]
+pub(crate) struct Finished(AtomicU8);
+
+impl Finished {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Records why the sound finished. Only the first reason is kept.
+	pub fn notify(&self, reason: FinishReason) {
+		self.0
+			.compare_exchange(0, reason.as_u8(), Ordering::SeqCst, Ordering::SeqCst)
+			.ok();
+	}
+
+	#[must_use]
+	pub fn reason(&self) -> Option<FinishReason> {
+		FinishReason::from_u8(self.0.load(Ordering::SeqCst))
+	}
+}
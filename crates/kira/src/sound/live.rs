@@ -0,0 +1,63 @@
+/*!
+Sounds that play audio generated while they're playing.
+
+A [`LiveSoundData`] is created along with a [`LiveSoundProducer`], which can
+be sent to another thread, such as a thread running a software synthesizer,
+and used to push frames of audio to the sound as they're generated. The
+sound can be played on any mixer track like any other sound, so it can use
+the track's effects and spatialization.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	sound::live::LiveSoundData,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let (data, mut producer) = LiveSoundData::new(48_000, 4096);
+let handle = manager.play(data)?;
+std::thread::spawn(move || {
+	let mut phase = 0.0f32;
+	loop {
+		let mut samples = [0.0; 512];
+		for sample in samples.chunks_mut(2) {
+			phase = (phase + 440.0 / 48_000.0) % 1.0;
+			let value = (phase * std::f32::consts::TAU).sin() * 0.25;
+			sample.fill(value);
+		}
+		let mut remaining = &samples[..];
+		while !remaining.is_empty() {
+			let frames_pushed = producer.push_interleaved(remaining);
+			remaining = &remaining[frames_pushed * 2..];
+		}
+	}
+});
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+If the producer doesn't push frames fast enough, the sound outputs
+silence until more frames are available. Dropping the producer signals
+the end of the stream: the sound plays the frames that are still queued
+and then finishes.
+*/
+
+mod data;
+mod handle;
+mod producer;
+mod settings;
+mod sound;
+
+pub use data::*;
+pub use handle::*;
+pub use producer::*;
+pub use settings::*;
+
+use crate::{command::ValueChangeCommand, command_writers_and_readers, Decibels, Panning, Tween};
+
+command_writers_and_readers! {
+	set_volume: ValueChangeCommand<Decibels>,
+	set_panning: ValueChangeCommand<Panning>,
+	pause: Tween,
+	resume: Tween,
+	stop: Tween,
+}
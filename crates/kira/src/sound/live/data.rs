@@ -0,0 +1,113 @@
+use std::{convert::Infallible, sync::Arc};
+
+use rtrb::{Consumer, RingBuffer};
+
+use crate::{
+	frame::Frame,
+	sound::{Sound, SoundData},
+	Decibels, Panning, Tween, Value,
+};
+
+use super::{
+	command_writers_and_readers,
+	sound::{LiveSound, Shared},
+	LiveSoundHandle, LiveSoundProducer, LiveSoundSettings,
+};
+
+/// A sound that plays frames of audio pushed to it from a
+/// [`LiveSoundProducer`].
+pub struct LiveSoundData {
+	/// The sample rate of the frames pushed to the sound.
+	pub sample_rate: u32,
+	/// Settings for the sound.
+	pub settings: LiveSoundSettings,
+	pub(super) consumer: Consumer<Frame>,
+}
+
+impl LiveSoundData {
+	/// Creates a [`LiveSoundData`] and a [`LiveSoundProducer`] that can
+	/// queue up to `buffer_length` frames of audio for it.
+	///
+	/// A longer buffer gives the producer more leeway to fall behind
+	/// without the sound running out of audio, but frames take longer to
+	/// be heard when the buffer is full.
+	#[must_use]
+	pub fn new(sample_rate: u32, buffer_length: usize) -> (Self, LiveSoundProducer) {
+		// one extra slot is needed for the "previous" frame the sound
+		// keeps for interpolation
+		let (mut producer, consumer) = RingBuffer::new(buffer_length + 1);
+		producer
+			.push(Frame::ZERO)
+			.expect("The frame producer shouldn't be full because we just created it");
+		(
+			Self {
+				sample_rate,
+				settings: LiveSoundSettings::new(),
+				consumer,
+			},
+			LiveSoundProducer { producer },
+		)
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified LiveSoundData, so the return value should be used"]
+	pub fn volume(mut self, volume: impl Into<Value<Decibels>>) -> Self {
+		self.settings.volume = volume.into();
+		self
+	}
+
+	/// Sets the panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	#[must_use = "This method consumes self and returns a modified LiveSoundData, so the return value should be used"]
+	pub fn panning(mut self, panning: impl Into<Value<Panning>>) -> Self {
+		self.settings.panning = panning.into();
+		self
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified LiveSoundData, so the return value should be used"]
+	pub fn fade_in_tween(mut self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		self.settings.fade_in_tween = fade_in_tween.into();
+		self
+	}
+
+	/// Returns the `LiveSoundData` with the specified settings.
+	#[must_use = "This method consumes self and returns a modified LiveSoundData, so the return value should be used"]
+	pub fn with_settings(mut self, settings: LiveSoundSettings) -> Self {
+		self.settings = settings;
+		self
+	}
+
+	#[must_use]
+	pub(super) fn split(self) -> (LiveSound, LiveSoundHandle) {
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let shared = Arc::new(Shared::new());
+		let sound = LiveSound::new(self, shared.clone(), command_readers);
+		let handle = LiveSoundHandle {
+			shared,
+			command_writers,
+		};
+		(sound, handle)
+	}
+}
+
+impl SoundData for LiveSoundData {
+	type Error = Infallible;
+
+	type Handle = LiveSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.split();
+		Ok((Box::new(sound), handle))
+	}
+}
+
+impl std::fmt::Debug for LiveSoundData {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("LiveSoundData")
+			.field("sample_rate", &self.sample_rate)
+			.field("settings", &self.settings)
+			.finish()
+	}
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crate::{command::handle_param_setters, sound::PlaybackState, Decibels, Panning, Tween};
+
+use super::{sound::Shared, CommandWriters};
+
+/// Controls a live sound.
+#[derive(Debug)]
+pub struct LiveSoundHandle {
+	pub(super) shared: Arc<Shared>,
+	pub(super) command_writers: CommandWriters,
+}
+
+impl LiveSoundHandle {
+	/// Returns the current playback state of the sound.
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state()
+	}
+
+	/// Returns the number of frames that have been pushed to the sound
+	/// but haven't been played yet.
+	///
+	/// This is updated each time the audio thread processes a batch of
+	/// audio, so it can lag slightly behind the producer.
+	#[must_use]
+	pub fn queued_frames(&self) -> usize {
+		self.shared.queued_frames()
+	}
+
+	handle_param_setters! {
+		/// Sets the volume of the sound.
+		volume: Decibels,
+
+		/// Sets the panning of the sound, where 0 is hard left
+		/// and 1 is hard right.
+		panning: Panning,
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback.
+	///
+	/// Frames pushed to the sound while it's paused are kept until
+	/// it's resumed.
+	pub fn pause(&mut self, tween: Tween) {
+		self.command_writers.pause.write(tween)
+	}
+
+	/// Resumes playback and fades in the sound from silence
+	/// with the given tween.
+	pub fn resume(&mut self, tween: Tween) {
+		self.command_writers.resume.write(tween)
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the sound is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) {
+		self.command_writers.stop.write(tween)
+	}
+}
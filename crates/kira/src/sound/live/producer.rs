@@ -0,0 +1,70 @@
+use rtrb::Producer;
+
+use crate::frame::Frame;
+
+/// Sends frames of audio to a live sound.
+///
+/// Dropping the producer signals the end of the stream. The sound plays
+/// the frames that are still queued and then finishes.
+pub struct LiveSoundProducer {
+	pub(super) producer: Producer<Frame>,
+}
+
+impl LiveSoundProducer {
+	/// Queues a frame of audio. Returns the frame back if the buffer
+	/// is full.
+	pub fn push(&mut self, frame: Frame) -> Result<(), Frame> {
+		self.producer
+			.push(frame)
+			.map_err(|rtrb::PushError::Full(frame)| frame)
+	}
+
+	/// Queues as many of the given frames as will fit in the buffer.
+	/// Returns the number of frames that were queued.
+	pub fn push_frames(&mut self, frames: &[Frame]) -> usize {
+		let mut num_frames = 0;
+		for frame in frames {
+			if self.push(*frame).is_err() {
+				break;
+			}
+			num_frames += 1;
+		}
+		num_frames
+	}
+
+	/// Queues as many frames as will fit in the buffer from interleaved
+	/// stereo samples (left, right, left, right, etc.). Returns the number
+	/// of frames that were queued.
+	///
+	/// If there's an odd number of samples, the last sample is ignored.
+	pub fn push_interleaved(&mut self, samples: &[f32]) -> usize {
+		let mut num_frames = 0;
+		for sample in samples.chunks_exact(2) {
+			if self.push(Frame::new(sample[0], sample[1])).is_err() {
+				break;
+			}
+			num_frames += 1;
+		}
+		num_frames
+	}
+
+	/// Returns the number of frames that can be queued before the buffer
+	/// is full.
+	#[must_use]
+	pub fn free_slots(&self) -> usize {
+		self.producer.slots()
+	}
+
+	/// Returns `true` if the sound was removed, in which case pushing
+	/// more frames has no effect.
+	#[must_use]
+	pub fn is_sound_removed(&self) -> bool {
+		self.producer.is_abandoned()
+	}
+}
+
+impl std::fmt::Debug for LiveSoundProducer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("LiveSoundProducer").finish()
+	}
+}
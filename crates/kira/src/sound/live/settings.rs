@@ -0,0 +1,59 @@
+use crate::{Decibels, Panning, Tween, Value};
+
+/// Settings for a live sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiveSoundSettings {
+	/// The volume of the sound.
+	pub volume: Value<Decibels>,
+	/// The panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	pub panning: Value<Panning>,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+}
+
+impl LiveSoundSettings {
+	/// Creates a new [`LiveSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			volume: Value::Fixed(Decibels::IDENTITY),
+			panning: Value::Fixed(Panning::CENTER),
+			fade_in_tween: None,
+		}
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified LiveSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/// Sets the panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	#[must_use = "This method consumes self and returns a modified LiveSoundSettings, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified LiveSoundSettings, so the return value should be used"]
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+}
+
+impl Default for LiveSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod test;
+
+use std::sync::{
+	atomic::{AtomicU64, AtomicU8, Ordering},
+	Arc,
+};
+
+use rtrb::Consumer;
+
+use crate::{
+	command::read_commands_into_parameters,
+	frame::{interpolate_frame, Frame},
+	info::Info,
+	playback_state_manager::PlaybackStateManager,
+	sound::{PlaybackState, Sound},
+	Decibels, Panning, Parameter, StartTime,
+};
+
+use super::{CommandReaders, LiveSoundData};
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+	state: AtomicU8,
+	queued_frames: AtomicU64,
+}
+
+impl Shared {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: AtomicU8::new(PlaybackState::Playing as u8),
+			queued_frames: AtomicU64::new(0),
+		}
+	}
+
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		match self.state.load(Ordering::SeqCst) {
+			0 => PlaybackState::Playing,
+			1 => PlaybackState::Pausing,
+			2 => PlaybackState::Paused,
+			3 => PlaybackState::WaitingToResume,
+			4 => PlaybackState::Resuming,
+			5 => PlaybackState::Stopping,
+			6 => PlaybackState::Stopped,
+			_ => panic!("Invalid playback state"),
+		}
+	}
+
+	#[must_use]
+	pub fn queued_frames(&self) -> usize {
+		self.queued_frames.load(Ordering::SeqCst) as usize
+	}
+}
+
+pub(crate) struct LiveSound {
+	command_readers: CommandReaders,
+	sample_rate: u32,
+	/// Frames pushed by the producer. The first frame is the frame that
+	/// was most recently passed, which is kept for interpolation.
+	consumer: Consumer<Frame>,
+	playback_state_manager: PlaybackStateManager,
+	fractional_position: f64,
+	volume: Parameter<Decibels>,
+	panning: Parameter<Panning>,
+	shared: Arc<Shared>,
+}
+
+impl LiveSound {
+	#[must_use]
+	pub(super) fn new(
+		data: LiveSoundData,
+		shared: Arc<Shared>,
+		command_readers: CommandReaders,
+	) -> Self {
+		let settings = data.settings;
+		Self {
+			command_readers,
+			sample_rate: data.sample_rate,
+			consumer: data.consumer,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			fractional_position: 0.0,
+			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			panning: Parameter::new(settings.panning, Panning::CENTER),
+			shared,
+		}
+	}
+
+	fn update_shared_playback_state(&mut self) {
+		self.shared.state.store(
+			self.playback_state_manager.playback_state() as u8,
+			Ordering::SeqCst,
+		);
+	}
+
+	#[must_use]
+	fn next_frames(&mut self) -> [Frame; 4] {
+		let mut frames = [Frame::ZERO; 4];
+		let chunk = self
+			.consumer
+			.read_chunk(self.consumer.slots().min(4))
+			.unwrap();
+		let (a, b) = chunk.as_slices();
+		for (frame, next_frame) in frames.iter_mut().zip(a.iter().chain(b.iter())) {
+			*frame = *next_frame;
+		}
+		frames
+	}
+
+	fn read_commands(&mut self) {
+		read_commands_into_parameters!(self, volume, panning);
+		if let Some(tween) = self.command_readers.pause.read() {
+			self.playback_state_manager.pause(tween);
+			self.update_shared_playback_state();
+		}
+		if let Some(tween) = self.command_readers.resume.read() {
+			self.playback_state_manager
+				.resume(StartTime::Immediate, tween);
+			self.update_shared_playback_state();
+		}
+		if let Some(tween) = self.command_readers.stop.read() {
+			self.playback_state_manager.stop(tween);
+			self.update_shared_playback_state();
+		}
+	}
+}
+
+impl Sound for LiveSound {
+	fn on_start_processing(&mut self) {
+		self.read_commands();
+		self.shared.queued_frames.store(
+			self.consumer.slots().saturating_sub(1) as u64,
+			Ordering::SeqCst,
+		);
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		self.volume.update(dt * out.len() as f64, info);
+		self.panning.update(dt * out.len() as f64, info);
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * out.len() as f64, info);
+		if changed_playback_state {
+			self.update_shared_playback_state();
+		}
+		if !self.playback_state_manager.playback_state().is_advancing() {
+			out.fill(Frame::ZERO);
+			return;
+		}
+
+		let num_frames = out.len();
+		let mut reached_end = false;
+		for (i, frame) in out.iter_mut().enumerate() {
+			while self.fractional_position >= 1.0 && self.consumer.slots() >= 2 {
+				self.fractional_position -= 1.0;
+				self.consumer.pop().ok();
+			}
+			// the first frame in the ringbuffer is the previous frame, so
+			// there need to be at least 2 frames to continue playing
+			let producer_dropped = self.consumer.is_abandoned();
+			if self.consumer.slots() < 2 {
+				// output silence while waiting for the producer to push
+				// more frames
+				*frame = Frame::ZERO;
+				reached_end |= producer_dropped;
+				continue;
+			}
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
+			let fade_volume = self
+				.playback_state_manager
+				.interpolated_fade_volume(time_in_chunk)
+				.as_amplitude();
+			let panning = self.panning.interpolated_value(time_in_chunk);
+			let next_frames = self.next_frames();
+			let interpolated_out = interpolate_frame(
+				next_frames[0],
+				next_frames[1],
+				next_frames[2],
+				next_frames[3],
+				self.fractional_position as f32,
+			);
+			self.fractional_position += self.sample_rate as f64 * dt;
+			*frame = (interpolated_out * fade_volume * volume).panned(panning);
+		}
+		if reached_end {
+			self.playback_state_manager.mark_as_stopped();
+			self.update_shared_playback_state();
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.playback_state() == PlaybackState::Stopped
+	}
+
+	fn playback_state(&self) -> PlaybackState {
+		self.playback_state_manager.playback_state()
+	}
+}
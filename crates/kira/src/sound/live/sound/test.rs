@@ -0,0 +1,89 @@
+use crate::{
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::{live::LiveSoundData, PlaybackState, Sound},
+	Panning,
+};
+
+/// Tests that a `LiveSound` plays the frames pushed to it.
+#[test]
+fn plays_pushed_frames() {
+	let (data, mut producer) = LiveSoundData::new(1, 10);
+	let (mut sound, _) = data.split();
+	assert_eq!(
+		producer.push_interleaved(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0]),
+		3
+	);
+	for i in 1..=3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that a `LiveSound` outputs silence while waiting for frames
+/// and continues once more frames are pushed.
+#[test]
+fn outputs_silence_on_underrun() {
+	let (data, mut producer) = LiveSoundData::new(1, 10);
+	let (mut sound, handle) = data.split();
+	for _ in 0..3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::ZERO
+		);
+	}
+	producer.push(Frame::from_mono(1.0)).unwrap();
+	producer.push(Frame::from_mono(2.0)).unwrap();
+	sound.on_start_processing();
+	assert_eq!(handle.queued_frames(), 2);
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(1.0).panned(Panning::CENTER)
+	);
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(2.0).panned(Panning::CENTER)
+	);
+	for _ in 0..3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::ZERO
+		);
+	}
+	assert!(!sound.finished());
+}
+
+/// Tests that a `LiveSound` finishes once the producer is dropped and
+/// every queued frame has been played.
+#[test]
+fn finishes_when_producer_is_dropped() {
+	let (data, mut producer) = LiveSoundData::new(1, 10);
+	let (mut sound, handle) = data.split();
+	producer.push_frames(&[Frame::from_mono(1.0), Frame::from_mono(2.0)]);
+	drop(producer);
+	for i in 1..=2 {
+		assert!(!sound.finished());
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
+/// Tests that a `LiveSoundProducer` only queues as many frames as fit
+/// in the buffer.
+#[test]
+fn producer_respects_buffer_length() {
+	let (_data, mut producer) = LiveSoundData::new(1, 3);
+	assert_eq!(producer.free_slots(), 3);
+	assert_eq!(producer.push_frames(&[Frame::from_mono(1.0); 5]), 3);
+	assert_eq!(
+		producer.push(Frame::from_mono(1.0)),
+		Err(Frame::from_mono(1.0))
+	);
+}
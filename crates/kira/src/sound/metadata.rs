@@ -0,0 +1,216 @@
+#[cfg(all(test, feature = "symphonia"))]
+mod test;
+
+#[cfg(feature = "symphonia")]
+use std::io::{Read, Seek, SeekFrom};
+
+use super::{ChannelMapping, Region};
+#[cfg(feature = "symphonia")]
+use super::{EndPosition, PlaybackPosition};
+
+/// The length of the part of a `smpl` chunk before the list of loops (in
+/// bytes).
+#[cfg(feature = "symphonia")]
+const SMPL_HEADER_LEN: usize = 36;
+/// The length of each loop in a `smpl` chunk (in bytes).
+#[cfg(feature = "symphonia")]
+const SMPL_LOOP_LEN: usize = 24;
+
+/**
+Information about a piece of audio read from the tags in its file, like
+ID3 tags in MP3 files or Vorbis comments in OGG and FLAC files.
+
+[`StaticSoundData`](super::static_sound::StaticSoundData) and
+[`StreamingSoundData`](super::streaming::StreamingSoundData) keep the
+metadata of the file they were loaded from, so the file doesn't have to be
+opened again to read it.
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata {
+	/// The title of the track.
+	pub title: Option<String>,
+	/// The artist who performed the track.
+	pub artist: Option<String>,
+	/// The album the track is from.
+	pub album: Option<String>,
+	/// The number of the track on its album.
+	pub track_number: Option<u32>,
+	/**
+	The loop points stored in the file, if there are any.
+
+	Loop points are read from the first loop in the `smpl` chunk of WAV
+	files, or from the `LOOPSTART` tag and either the `LOOPLENGTH` or
+	`LOOPEND` tag, which are measured in samples. Unless
+	`use_detected_loop_region` is turned off in the sound's settings,
+	this region is looped if no other loop region is set. It can also be
+	passed to
+	[`StaticSoundData::loop_region`](super::static_sound::StaticSoundData::loop_region)
+	or [`StreamingSoundData::loop_region`](super::streaming::StreamingSoundData::loop_region).
+	*/
+	pub loop_region: Option<Region>,
+	/// How the channels of the audio were turned into stereo audio, if
+	/// the audio has more than two channels.
+	///
+	/// Folding audio down to two channels loses information, so this can
+	/// be used to warn about audio that wasn't mixed for stereo.
+	pub channel_mapping: Option<ChannelMapping>,
+	/// Every tag in the file as a key and a value, in the order they were
+	/// stored, including the ones the other fields were read from.
+	pub tags: Vec<(String, String)>,
+}
+
+impl Metadata {
+	/// Returns the value of the first tag with the given key, ignoring
+	/// case.
+	#[must_use]
+	pub fn tag(&self, key: &str) -> Option<&str> {
+		self.tags
+			.iter()
+			.find(|(tag_key, _)| tag_key.eq_ignore_ascii_case(key))
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// Reads the metadata from a list of Symphonia tags and the loop
+	/// region from a WAV file's `smpl` chunk, or returns `None` if there
+	/// aren't any.
+	#[cfg(feature = "symphonia")]
+	#[must_use]
+	pub(crate) fn from_tags(
+		tags: &[symphonia::core::meta::Tag],
+		smpl_loop_region: Option<Region>,
+	) -> Option<Self> {
+		use symphonia::core::meta::StandardTagKey;
+
+		if tags.is_empty() && smpl_loop_region.is_none() {
+			return None;
+		}
+		let tags = tags
+			.iter()
+			.map(|tag| {
+				// strings in RIFF files keep their null terminators
+				let value = tag.value.to_string().trim_end_matches('\0').to_owned();
+				(tag.std_key, tag.key.clone(), value)
+			})
+			.collect::<Vec<_>>();
+		let find = |std_key: StandardTagKey| {
+			tags.iter()
+				.find(|(tag_std_key, _, _)| *tag_std_key == Some(std_key))
+				.map(|(_, _, value)| value.clone())
+		};
+		let mut metadata = Self {
+			title: find(StandardTagKey::TrackTitle),
+			artist: find(StandardTagKey::Artist),
+			album: find(StandardTagKey::Album),
+			// track numbers are sometimes stored with the number of tracks,
+			// like `3/12`
+			track_number: find(StandardTagKey::TrackNumber).and_then(|value| {
+				value
+					.split('/')
+					.next()
+					.and_then(|number| number.trim().parse().ok())
+			}),
+			loop_region: None,
+			channel_mapping: None,
+			tags: tags
+				.into_iter()
+				.map(|(_, key, value)| (key, value))
+				.collect(),
+		};
+		metadata.loop_region = smpl_loop_region.or_else(|| metadata.loop_region_from_tags());
+		Some(metadata)
+	}
+
+	/// Records how the channels of the audio were turned into stereo
+	/// audio if the audio has more than two channels.
+	#[cfg(feature = "symphonia")]
+	#[must_use]
+	pub(crate) fn with_channel_mapping(
+		metadata: Option<Self>,
+		num_channels: usize,
+		channel_mapping: ChannelMapping,
+	) -> Option<Self> {
+		if num_channels <= 2 {
+			return metadata;
+		}
+		Some(Self {
+			channel_mapping: Some(channel_mapping),
+			..metadata.unwrap_or_default()
+		})
+	}
+
+	#[cfg(feature = "symphonia")]
+	#[must_use]
+	fn loop_region_from_tags(&self) -> Option<Region> {
+		let sample_tag = |key| self.tag(key)?.trim().parse::<usize>().ok();
+		let start = sample_tag("LOOPSTART")?;
+		let end = sample_tag("LOOPLENGTH")
+			.map(|length| start + length)
+			.or_else(|| sample_tag("LOOPEND"))?;
+		if end <= start {
+			return None;
+		}
+		Some(Region {
+			start: PlaybackPosition::Samples(start),
+			end: EndPosition::Custom(PlaybackPosition::Samples(end)),
+		})
+	}
+}
+
+/// Reads the first loop from the `smpl` chunk of a WAV file, if there is
+/// one, and seeks back to where the reader started.
+///
+/// Symphonia doesn't read `smpl` chunks, so the chunks are walked through
+/// before the file is decoded.
+#[cfg(feature = "symphonia")]
+#[must_use]
+pub(crate) fn read_smpl_loop_region<R: Read + Seek + ?Sized>(reader: &mut R) -> Option<Region> {
+	let start = reader.stream_position().ok()?;
+	let loop_region = find_smpl_loop_region(reader);
+	reader.seek(SeekFrom::Start(start)).ok()?;
+	loop_region
+}
+
+#[cfg(feature = "symphonia")]
+#[must_use]
+fn find_smpl_loop_region<R: Read + Seek + ?Sized>(reader: &mut R) -> Option<Region> {
+	let mut header = [0; 12];
+	reader.read_exact(&mut header).ok()?;
+	if &header[..4] != b"RIFF" || &header[8..] != b"WAVE" {
+		return None;
+	}
+	loop {
+		let mut chunk_header = [0; 8];
+		reader.read_exact(&mut chunk_header).ok()?;
+		let len = u32::from_le_bytes(chunk_header[4..].try_into().unwrap());
+		if &chunk_header[..4] != b"smpl" {
+			// chunks are padded to an even length
+			reader
+				.seek(SeekFrom::Current(i64::from(len) + i64::from(len % 2)))
+				.ok()?;
+			continue;
+		}
+		let mut chunk = [0; SMPL_HEADER_LEN + SMPL_LOOP_LEN];
+		if (len as usize) < chunk.len() {
+			return None;
+		}
+		reader.read_exact(&mut chunk).ok()?;
+		let read_u32 = |offset: usize| {
+			u32::from_le_bytes(chunk[offset..offset + 4].try_into().unwrap()) as usize
+		};
+		let num_loops = read_u32(28);
+		if num_loops == 0 {
+			return None;
+		}
+		// the end of the loop is the last sample that's played
+		let start = read_u32(SMPL_HEADER_LEN + 8);
+		let end = read_u32(SMPL_HEADER_LEN + 12) + 1;
+		if end <= start {
+			return None;
+		}
+		return Some(Region {
+			start: PlaybackPosition::Samples(start),
+			end: EndPosition::Custom(PlaybackPosition::Samples(end)),
+		});
+	}
+}
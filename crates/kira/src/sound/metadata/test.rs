@@ -0,0 +1,94 @@
+use symphonia::core::meta::{StandardTagKey, Tag, Value};
+
+use crate::sound::{ChannelMapping, EndPosition, PlaybackPosition, Region};
+
+use super::Metadata;
+
+/// Tests that `Metadata` reads the standard tags and keeps every tag.
+#[test]
+fn reads_standard_tags() {
+	let metadata = Metadata::from_tags(
+		&[
+			Tag::new(
+				Some(StandardTagKey::TrackTitle),
+				"TITLE",
+				Value::from("Blip"),
+			),
+			Tag::new(Some(StandardTagKey::Artist), "ARTIST", Value::from("Kira")),
+			Tag::new(Some(StandardTagKey::Album), "ALBUM", Value::from("Sounds")),
+			Tag::new(
+				Some(StandardTagKey::TrackNumber),
+				"TRACKNUMBER",
+				Value::from("3/12"),
+			),
+			Tag::new(None, "MOOD", Value::from("happy")),
+		],
+		None,
+	)
+	.unwrap();
+	assert_eq!(metadata.title.as_deref(), Some("Blip"));
+	assert_eq!(metadata.artist.as_deref(), Some("Kira"));
+	assert_eq!(metadata.album.as_deref(), Some("Sounds"));
+	assert_eq!(metadata.track_number, Some(3));
+	assert_eq!(metadata.loop_region, None);
+	assert_eq!(metadata.tags.len(), 5);
+	assert_eq!(metadata.tag("mood"), Some("happy"));
+	assert_eq!(metadata.tag("genre"), None);
+}
+
+/// Tests that `Metadata` reads loop points from `LOOPSTART` and either
+/// `LOOPLENGTH` or `LOOPEND`.
+#[test]
+fn reads_loop_points() {
+	let loop_region = |tags: &[(&str, &str)]| {
+		Metadata::from_tags(
+			&tags
+				.iter()
+				.map(|(key, value)| Tag::new(None, key, Value::from(*value)))
+				.collect::<Vec<_>>(),
+			None,
+		)
+		.unwrap()
+		.loop_region
+	};
+	let expected = Some(Region {
+		start: PlaybackPosition::Samples(100),
+		end: EndPosition::Custom(PlaybackPosition::Samples(300)),
+	});
+	assert_eq!(
+		loop_region(&[("LOOPSTART", "100"), ("LOOPLENGTH", "200")]),
+		expected
+	);
+	assert_eq!(
+		loop_region(&[("LoopStart", "100"), ("LoopEnd", "300")]),
+		expected
+	);
+	assert_eq!(loop_region(&[("LOOPSTART", "100")]), None);
+	assert_eq!(
+		loop_region(&[("LOOPSTART", "300"), ("LOOPEND", "100")]),
+		None
+	);
+}
+
+/// Tests that `Metadata` isn't created for audio without any tags.
+#[test]
+fn no_tags() {
+	assert_eq!(Metadata::from_tags(&[], None), None);
+}
+
+/// Tests that the channel mapping is only reported for audio with more
+/// than two channels.
+#[test]
+fn reports_channel_mapping() {
+	assert_eq!(
+		Metadata::with_channel_mapping(None, 2, ChannelMapping::Downmix),
+		None
+	);
+	assert_eq!(
+		Metadata::with_channel_mapping(None, 6, ChannelMapping::Downmix),
+		Some(Metadata {
+			channel_mapping: Some(ChannelMapping::Downmix),
+			..Default::default()
+		})
+	);
+}
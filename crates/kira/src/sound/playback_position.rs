@@ -19,6 +19,15 @@ impl PlaybackPosition {
 			PlaybackPosition::Samples(samples) => samples,
 		}
 	}
+
+	/// Returns `false` if the position is a negative or NaN number of seconds.
+	#[must_use]
+	pub(crate) fn is_valid(self) -> bool {
+		match self {
+			PlaybackPosition::Seconds(seconds) => seconds >= 0.0,
+			PlaybackPosition::Samples(_) => true,
+		}
+	}
 }
 
 impl From<f64> for PlaybackPosition {
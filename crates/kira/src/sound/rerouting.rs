@@ -0,0 +1,38 @@
+use crate::{info::Info, track::TrackId, StartTime, Tween};
+
+/// Holds a request to move a sound to a different track until the
+/// mixer picks it up.
+pub(crate) struct Rerouting {
+	request: Option<(TrackId, Tween)>,
+}
+
+impl Rerouting {
+	#[must_use]
+	pub fn new() -> Self {
+		Self { request: None }
+	}
+
+	/// Asks for the sound to be crossfaded to the `destination` track.
+	pub fn start(&mut self, destination: TrackId, tween: Tween) {
+		self.request = Some((destination, tween));
+	}
+
+	pub fn update(&mut self, dt: f64, info: &Info) {
+		if let Some((_, tween)) = &mut self.request {
+			let will_never_start = tween.start_time.update(dt, info);
+			if will_never_start {
+				self.request = None;
+			}
+		}
+	}
+
+	/// Returns the track the sound should be moved to once the tween's
+	/// start time has been reached.
+	#[must_use]
+	pub fn take_requested_track(&mut self) -> Option<(TrackId, Tween)> {
+		match self.request {
+			Some((_, tween)) if tween.start_time == StartTime::Immediate => self.request.take(),
+			_ => None,
+		}
+	}
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod test;
+
+use crate::{
+	command::{command_writer_and_reader, CommandReader, CommandWriter, ValueChangeCommand},
+	info::Info,
+	track::{NonexistentRoute, SendTrackId},
+	Decibels, Parameter, Tween, Value,
+};
+
+/// The most send tracks a single sound can send its audio to.
+pub const MAX_SOUND_SENDS: usize = 4;
+
+/// The send tracks a sound sends its audio to, along with the volume
+/// of each send.
+///
+/// Unlike a track's sends, which apply to every sound on the track, these
+/// only apply to a single sound. The sound's output is sent after its own
+/// volume and panning are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SoundSends {
+	sends: [Option<(SendTrackId, Value<Decibels>)>; MAX_SOUND_SENDS],
+}
+
+impl SoundSends {
+	/// Creates a new [`SoundSends`] with no sends.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/**
+	Sets the volume of the send to the given send track, adding the
+	send if it doesn't exist yet.
+
+	# Panics
+
+	Panics if there are already [`MAX_SOUND_SENDS`] sends to other
+	send tracks.
+	*/
+	pub fn set(&mut self, to: impl Into<SendTrackId>, volume: impl Into<Value<Decibels>>) {
+		let to = to.into();
+		let volume = volume.into();
+		if let Some((_, existing_volume)) =
+			self.sends.iter_mut().flatten().find(|(id, _)| *id == to)
+		{
+			*existing_volume = volume;
+			return;
+		}
+		let slot = self
+			.sends
+			.iter_mut()
+			.find(|send| send.is_none())
+			.unwrap_or_else(|| panic!("a sound can't have more than {MAX_SOUND_SENDS} sends"));
+		*slot = Some((to, volume));
+	}
+
+	/// Returns the volume of the send to the given send track, or `None`
+	/// if the sound doesn't send to that track.
+	#[must_use]
+	pub fn get(&self, to: impl Into<SendTrackId>) -> Option<Value<Decibels>> {
+		let to = to.into();
+		self.iter()
+			.find_map(|(id, volume)| (id == to).then_some(volume))
+	}
+
+	/// Returns an iterator over the send tracks the sound sends to and
+	/// the volume of each send.
+	pub fn iter(&self) -> impl Iterator<Item = (SendTrackId, Value<Decibels>)> + '_ {
+		self.sends.iter().flatten().copied()
+	}
+}
+
+/// The volumes of a playing sound's sends, which the track the sound is
+/// on uses to add the sound's output to the send tracks.
+pub(crate) struct SoundSendLevels {
+	levels: Vec<(SendTrackId, Parameter<Decibels>)>,
+	command_readers: Vec<CommandReader<ValueChangeCommand<Decibels>>>,
+}
+
+impl SoundSendLevels {
+	#[must_use]
+	pub fn levels(&self) -> &[(SendTrackId, Parameter<Decibels>)] {
+		&self.levels
+	}
+
+	pub fn read_commands(&mut self) {
+		for ((_, level), command_reader) in self.levels.iter_mut().zip(&mut self.command_readers) {
+			level.read_command(command_reader);
+		}
+	}
+
+	pub fn update(&mut self, dt: f64, info: &Info) {
+		for (_, level) in &mut self.levels {
+			level.update(dt, info);
+		}
+	}
+}
+
+/// Changes the volumes of a playing sound's sends from the sound's
+/// handle.
+#[derive(Debug)]
+pub(crate) struct SoundSendLevelWriters {
+	command_writers: Vec<(SendTrackId, CommandWriter<ValueChangeCommand<Decibels>>)>,
+}
+
+impl SoundSendLevelWriters {
+	pub fn set(
+		&mut self,
+		to: impl Into<SendTrackId>,
+		volume: impl Into<Value<Decibels>>,
+		tween: Tween,
+	) -> Result<(), NonexistentRoute> {
+		let to = to.into();
+		let (_, command_writer) = self
+			.command_writers
+			.iter_mut()
+			.find(|(id, _)| *id == to)
+			.ok_or(NonexistentRoute)?;
+		command_writer.write(ValueChangeCommand {
+			target: volume.into(),
+			tween,
+		});
+		Ok(())
+	}
+}
+
+#[must_use]
+pub(crate) fn sound_send_levels(sends: &SoundSends) -> (SoundSendLevels, SoundSendLevelWriters) {
+	let mut levels = Vec::new();
+	let mut command_readers = Vec::new();
+	let mut command_writers = Vec::new();
+	for (id, volume) in sends.iter() {
+		let (command_writer, command_reader) = command_writer_and_reader();
+		levels.push((id, Parameter::new(volume, Decibels::IDENTITY)));
+		command_readers.push(command_reader);
+		command_writers.push((id, command_writer));
+	}
+	(
+		SoundSendLevels {
+			levels,
+			command_readers,
+		},
+		SoundSendLevelWriters { command_writers },
+	)
+}
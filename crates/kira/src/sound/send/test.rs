@@ -0,0 +1,43 @@
+use atomic_arena::Arena;
+
+use crate::{track::SendTrackId, Decibels, Value};
+
+use super::{SoundSends, MAX_SOUND_SENDS};
+
+fn fake_send_track_ids(count: usize) -> Vec<SendTrackId> {
+	let arena = Arena::<()>::new(count);
+	let controller = arena.controller();
+	(0..count)
+		.map(|_| SendTrackId(controller.try_reserve().unwrap()))
+		.collect()
+}
+
+#[test]
+fn setting_an_existing_send_replaces_its_volume() {
+	let ids = fake_send_track_ids(2);
+	let mut sends = SoundSends::new();
+	sends.set(ids[0], Decibels(-6.0));
+	sends.set(ids[1], Decibels(-3.0));
+	sends.set(ids[0], Decibels(-12.0));
+	assert_eq!(sends.get(ids[0]), Some(Value::Fixed(Decibels(-12.0))));
+	assert_eq!(sends.get(ids[1]), Some(Value::Fixed(Decibels(-3.0))));
+	assert_eq!(sends.iter().count(), 2);
+}
+
+#[test]
+fn missing_sends_are_none() {
+	let ids = fake_send_track_ids(2);
+	let mut sends = SoundSends::new();
+	sends.set(ids[0], Decibels(-6.0));
+	assert_eq!(sends.get(ids[1]), None);
+}
+
+#[test]
+#[should_panic]
+fn panics_when_there_are_too_many_sends() {
+	let ids = fake_send_track_ids(MAX_SOUND_SENDS + 1);
+	let mut sends = SoundSends::new();
+	for id in ids {
+		sends.set(id, Decibels(-6.0));
+	}
+}
@@ -20,30 +20,57 @@ Compared to streaming sounds, static sounds have lower CPU usage and shorter del
 when starting and seeking, but they use a lot more memory.
 */
 
+mod cache;
 mod data;
+mod error;
 mod handle;
+mod queue;
 mod settings;
 mod sound;
+mod stats;
+mod synced;
 
+pub use cache::*;
 pub use data::*;
+pub use error::*;
 pub use handle::*;
 pub use settings::*;
+pub use stats::*;
+pub use synced::*;
 
 use crate::{
-	command::ValueChangeCommand, command_writers_and_readers, tween::Tween, Decibels, Panning,
-	PlaybackRate, StartTime,
+	command::ValueChangeCommand, command_writers_and_readers, track::TrackId, tween::Tween,
+	Decibels, Panning, PlaybackRate, StartTime,
 };
 
 use super::Region;
 
+/**
+How many frames ahead of the audible frame a static sound reads its audio.
+
+Static sounds use cubic interpolation to play audio at playback rates other
+than `1.0`, which needs the frame before and the two frames after the
+current position. The frame being output is interpolated between the
+oldest two of the four frames the sound keeps, so the most recently read
+frame is this many frames ahead of the audio being output, and the sound's
+read position is one frame further ahead than that. This delay is already
+subtracted from [`StaticSoundHandle::position`].
+*/
+pub const RESAMPLER_LOOKAHEAD_FRAMES: usize = 2;
+
 command_writers_and_readers! {
 	set_volume: ValueChangeCommand<Decibels>,
 	set_playback_rate: ValueChangeCommand<PlaybackRate>,
 	set_panning: ValueChangeCommand<Panning>,
 	set_loop_region: Option<Region>,
-	pause: Tween,
+	set_loop_count: Option<u32>,
+	set_playback_region: Option<Region>,
+	set_output_destination: (TrackId, Tween),
+	trigger_pitch_envelope: (PlaybackRate, Tween),
+	pause: (StartTime, Tween),
+	pause_until: (StartTime, Tween),
 	resume: (StartTime, Tween),
-	stop: Tween,
+	stop: (StartTime, Tween),
 	seek_by: f64,
 	seek_to: f64,
 }
@@ -0,0 +1,150 @@
+#[cfg(all(test, any(feature = "wav", not(feature = "symphonia"))))]
+mod test;
+
+use std::{
+	collections::{hash_map::DefaultHasher, HashMap},
+	hash::{Hash, Hasher},
+	sync::Arc,
+};
+
+use crate::{frame::Frame, sound::FromFileError};
+
+use super::StaticSoundData;
+
+/// Identifies the contents of an audio file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ContentKey {
+	/// The length of the file (in bytes).
+	len: usize,
+	/// A hash of the bytes of the file.
+	hash: u64,
+}
+
+impl ContentKey {
+	#[must_use]
+	fn new(bytes: &[u8]) -> Self {
+		let mut hasher = DefaultHasher::new();
+		bytes.hash(&mut hasher);
+		Self {
+			len: bytes.len(),
+			hash: hasher.finish(),
+		}
+	}
+}
+
+/**
+Loads static sounds, sharing the audio of files with the same contents.
+
+When the same file is stored under more than one path, loading each path
+with [`StaticSoundData::from_file`] decodes the audio again and keeps a
+separate copy of it in memory. A [`SoundCache`] recognizes files it has
+already loaded by their contents and returns a [`StaticSoundData`] that
+shares the decoded audio instead.
+
+Each [`StaticSoundData`] the cache returns has its own settings, so
+changing the settings of one doesn't affect sounds that were loaded from
+the same file.
+
+# Examples
+
+```no_run
+use kira::sound::static_sound::SoundCache;
+
+let mut cache = SoundCache::new();
+let jingle = cache.load_static("sfx/jingle.ogg")?;
+// this file has the same contents, so its audio isn't decoded again
+let also_jingle = cache.load_static("ui/jingle.ogg")?.volume(-6.0);
+assert_eq!(cache.len(), 1);
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+#[derive(Debug, Default)]
+pub struct SoundCache {
+	sounds: HashMap<ContentKey, StaticSoundData>,
+}
+
+impl SoundCache {
+	/// Creates a new, empty [`SoundCache`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Loads an audio file into a [`StaticSoundData`], or returns a copy of
+	/// a sound that was already loaded from a file with the same contents.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(not(wasm32))))]
+	pub fn load_static(
+		&mut self,
+		path: impl AsRef<std::path::Path>,
+	) -> Result<StaticSoundData, FromFileError> {
+		self.load_static_from_bytes(std::fs::read(path)?)
+	}
+
+	/// Loads audio file data that's already in memory into a
+	/// [`StaticSoundData`], or returns a copy of a sound that was already
+	/// loaded from data with the same contents.
+	pub fn load_static_from_bytes(
+		&mut self,
+		bytes: impl AsRef<[u8]> + Send + Sync + 'static,
+	) -> Result<StaticSoundData, FromFileError> {
+		let key = ContentKey::new(bytes.as_ref());
+		if let Some(sound) = self.sounds.get(&key) {
+			return Ok(sound.clone());
+		}
+		let sound = StaticSoundData::from_bytes(bytes)?;
+		self.sounds.insert(key, sound.clone());
+		Ok(sound)
+	}
+
+	/// Returns the number of distinct sounds in the cache.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.sounds.len()
+	}
+
+	/// Returns `true` if there are no sounds in the cache.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.sounds.is_empty()
+	}
+
+	/// Returns how much memory the audio of the sounds in the cache takes
+	/// up (in bytes).
+	///
+	/// Audio that's still used by sounds outside of the cache is counted
+	/// too, since evicting it wouldn't free it.
+	#[must_use]
+	pub fn memory_usage(&self) -> usize {
+		self.sounds
+			.values()
+			.map(|sound| std::mem::size_of_val::<[Frame]>(&sound.frames))
+			.sum()
+	}
+
+	/// Removes the sound that shares its audio with `sound` from the cache.
+	///
+	/// Returns `true` if the sound was in the cache. Sounds that were
+	/// already loaded keep their audio, but the next file with the same
+	/// contents will be decoded again.
+	pub fn evict(&mut self, sound: &StaticSoundData) -> bool {
+		let len = self.sounds.len();
+		self.sounds
+			.retain(|_, cached| !Arc::ptr_eq(&cached.frames, &sound.frames));
+		self.sounds.len() != len
+	}
+
+	/// Removes the sounds whose audio isn't used by any [`StaticSoundData`]
+	/// outside of the cache, and returns how many were removed.
+	pub fn evict_unused(&mut self) -> usize {
+		let len = self.sounds.len();
+		self.sounds
+			.retain(|_, cached| Arc::strong_count(&cached.frames) > 1);
+		len - self.sounds.len()
+	}
+
+	/// Removes every sound from the cache.
+	pub fn clear(&mut self) {
+		self.sounds.clear();
+	}
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::{frame::Frame, test_helpers::wav_fixture, Decibels};
+
+use super::SoundCache;
+
+/// Tests that files with the same contents share their audio.
+#[test]
+fn shares_audio_between_identical_files() {
+	let mut cache = SoundCache::new();
+	let a = cache
+		.load_static_from_bytes(wav_fixture(44_100, &[1, 2, 3]))
+		.unwrap();
+	let b = cache
+		.load_static_from_bytes(wav_fixture(44_100, &[1, 2, 3]))
+		.unwrap();
+	let c = cache
+		.load_static_from_bytes(wav_fixture(44_100, &[3, 2, 1]))
+		.unwrap();
+	assert!(Arc::ptr_eq(&a.frames, &b.frames));
+	assert!(!Arc::ptr_eq(&a.frames, &c.frames));
+	assert_eq!(cache.len(), 2);
+	assert_eq!(cache.memory_usage(), 6 * std::mem::size_of::<Frame>());
+}
+
+/// Tests that settings applied to a sound from the cache don't affect
+/// other sounds loaded from the same file.
+#[test]
+fn does_not_share_settings() {
+	let mut cache = SoundCache::new();
+	let a = cache
+		.load_static_from_bytes(wav_fixture(1, &[1, 2, 3]))
+		.unwrap()
+		.volume(-6.0)
+		.slice(1.0..2.0);
+	let b = cache
+		.load_static_from_bytes(wav_fixture(44_100, &[1, 2, 3]))
+		.unwrap();
+	assert_eq!(a.settings.volume, Decibels(-6.0).into());
+	assert_eq!(b.settings.volume, Decibels::IDENTITY.into());
+	assert_eq!(b.slice, None);
+	assert_eq!(b.num_frames(), 3);
+}
+
+/// Tests that sounds can be removed from the cache.
+#[test]
+fn evicts_sounds() {
+	let mut cache = SoundCache::new();
+	let a = cache
+		.load_static_from_bytes(wav_fixture(44_100, &[1]))
+		.unwrap();
+	let b = cache
+		.load_static_from_bytes(wav_fixture(44_100, &[2]))
+		.unwrap();
+	cache
+		.load_static_from_bytes(wav_fixture(44_100, &[3]))
+		.unwrap();
+	assert!(cache.evict(&a));
+	assert!(!cache.evict(&a));
+	assert_eq!(cache.len(), 2);
+	// the third sound is only used by the cache
+	assert_eq!(cache.evict_unused(), 1);
+	assert_eq!(cache.len(), 1);
+	drop(b);
+	assert_eq!(cache.evict_unused(), 1);
+	assert!(cache.is_empty());
+	// evicted files are decoded again
+	let a2 = cache
+		.load_static_from_bytes(wav_fixture(44_100, &[1]))
+		.unwrap();
+	assert!(!Arc::ptr_eq(&a.frames, &a2.frames));
+	cache.clear();
+	assert_eq!(cache.memory_usage(), 0);
+}
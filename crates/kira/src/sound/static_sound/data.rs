@@ -1,26 +1,47 @@
+mod analysis;
+mod from_decoder;
 #[cfg(feature = "symphonia")]
 mod from_file;
+#[cfg(not(feature = "symphonia"))]
+mod from_wav;
+mod resample;
+#[cfg(feature = "waveforms")]
+mod waveforms;
 
 #[cfg(test)]
 mod test;
 
+pub use resample::ResampleQuality;
+
 use std::{
 	fmt::{Debug, Formatter},
-	sync::Arc,
+	sync::{Arc, Mutex},
 	time::Duration,
 };
 
 use crate::{
 	frame::Frame,
-	sound::{EndPosition, IntoOptionalRegion, PlaybackPosition, Region, Sound, SoundData},
-	Tween,
-	Decibels, Panning, PlaybackRate, StartTime, Value,
+	group::GroupId,
+	info::MockInfoBuilder,
+	sound::{
+		sound_send_levels, ChannelMixMode, IntoOptionalRegion, LoopMode, Metadata,
+		PlaybackPosition, Region, Sound, SoundData,
+	},
+	track::SendTrackId,
+	voice_group::VoiceGroupId,
+	Decibels, Panning, PlaybackRate, Semitones, StartTime, Tween, Value,
 };
 
 use super::{
-	command_writers_and_readers, handle::StaticSoundHandle, sound::StaticSound, StaticSoundSettings,
+	command_writers_and_readers, handle::StaticSoundHandle, queue::segment_queue,
+	sound::StaticSound, CombineError, SettingsError, StaticSoundSettings,
 };
 
+/// The number of frames [`StaticSoundData::render`] processes at a time.
+/// This matches the default internal buffer size of the
+/// [`AudioManager`](crate::AudioManager).
+const RENDER_CHUNK_SIZE: usize = 128;
+
 /// A piece of audio loaded into memory all at once.
 ///
 /// These can be cheaply cloned, as the audio data is shared
@@ -44,6 +65,9 @@ pub struct StaticSoundData {
 	audio.
 	*/
 	pub slice: Option<(usize, usize)>,
+	/// The information read from the tags in the audio file the sound
+	/// was loaded from, if there are any.
+	pub metadata: Option<Arc<Metadata>>,
 }
 
 impl StaticSoundData {
@@ -87,6 +111,35 @@ impl StaticSoundData {
 		new
 	}
 
+	/**
+	Sets whether the sound should start out paused.
+
+	This returns a cheap clone of the [`StaticSoundData`] with the modified setting.
+
+	# Examples
+
+	Prepare a sound so it can start playing without delay:
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+		sound::static_sound::StaticSoundData,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut gunshot = manager.play(StaticSoundData::from_file("gunshot.ogg")?.start_paused(true))?;
+	// when the player pulls the trigger
+	gunshot.resume(Tween::default());
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn start_paused(&self, start_paused: bool) -> Self {
+		let mut new = self.clone();
+		new.settings.start_paused = start_paused;
+		new
+	}
+
 	/// Sets whether the sound should be played in reverse.
 	///
 	/// This returns a cheap clone of the [`StaticSoundData`] with the modified setting.
@@ -97,6 +150,17 @@ impl StaticSoundData {
 		new
 	}
 
+	/// Flips the direction the sound will be played in.
+	///
+	/// Calling this on a sound that is already set to play in reverse will
+	/// make it play forward again.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified setting.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn reversed(&self) -> Self {
+		self.reverse(!self.settings.reverse)
+	}
+
 	/**
 	Sets the portion of the sound that should be looped.
 
@@ -125,6 +189,107 @@ impl StaticSoundData {
 		new
 	}
 
+	/**
+	Sets whether to loop the loop points stored in the audio file when
+	no loop region is set.
+
+	This returns a cheap clone of the [`StaticSoundData`] with the modified setting.
+
+	# Examples
+
+	Play a sound from start to end, ignoring the loop points in its file:
+
+	```no_run
+	# use kira::sound::static_sound::StaticSoundData;
+	let sound = StaticSoundData::from_file("music.wav")?.use_detected_loop_region(false);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn use_detected_loop_region(&self, use_detected_loop_region: bool) -> Self {
+		let mut new = self.clone();
+		new.settings.use_detected_loop_region = use_detected_loop_region;
+		new
+	}
+
+	/**
+	Sets how many times the loop region should play before playback
+	continues to the end of the sound, or `None` to loop indefinitely.
+
+	This returns a cheap clone of the [`StaticSoundData`] with the modified loop count.
+
+	# Examples
+
+	Play an intro, then the portion from 2 to 4 seconds three times, then the
+	rest of the sound:
+
+	```no_run
+	use kira::sound::static_sound::StaticSoundData;
+
+	let sound = StaticSoundData::from_file("music.ogg")?
+		.loop_region(2.0..4.0)
+		.loop_count(3);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn loop_count(&self, loop_count: impl Into<Option<u32>>) -> Self {
+		let mut new = self.clone();
+		new.settings.loop_count = loop_count.into();
+		new
+	}
+
+	/**
+	Sets what happens when playback reaches the end of the loop region.
+
+	This returns a cheap clone of the [`StaticSoundData`] with the modified loop mode.
+
+	# Examples
+
+	Bounce back and forth between 1 and 3 seconds:
+
+	```no_run
+	use kira::sound::{static_sound::StaticSoundData, LoopMode};
+
+	let sound = StaticSoundData::from_file("engine_hum.ogg")?
+		.loop_region(1.0..3.0)
+		.loop_mode(LoopMode::PingPong);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn loop_mode(&self, loop_mode: LoopMode) -> Self {
+		let mut new = self.clone();
+		new.settings.loop_mode = loop_mode;
+		new
+	}
+
+	/**
+	Sets how long to crossfade between the end and the start of the
+	loop region when playback loops.
+
+	This returns a cheap clone of the [`StaticSoundData`] with the modified loop crossfade.
+
+	# Examples
+
+	```no_run
+	use std::time::Duration;
+
+	use kira::sound::static_sound::StaticSoundData;
+
+	let sound = StaticSoundData::from_file("ambience.ogg")?
+		.loop_region(..)
+		.loop_crossfade(Duration::from_millis(250));
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn loop_crossfade(&self, loop_crossfade: impl Into<Option<Duration>>) -> Self {
+		let mut new = self.clone();
+		new.settings.loop_crossfade = loop_crossfade.into();
+		new
+	}
+
 	/**
 	Sets the volume of the sound.
 
@@ -276,6 +441,78 @@ impl StaticSoundData {
 		new
 	}
 
+	/**
+	Sets how the left and right channels are combined before volume
+	and panning are applied.
+
+	This returns a cheap clone of the [`StaticSoundData`] with the modified channel mix mode.
+
+	# Examples
+
+	Play a stereo recording as a mono source:
+
+	```no_run
+	use kira::sound::{static_sound::StaticSoundData, ChannelMixMode};
+
+	let sound = StaticSoundData::from_file("campfire.ogg")?
+		.channel_mix_mode(ChannelMixMode::MonoSum);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn channel_mix_mode(&self, channel_mix_mode: ChannelMixMode) -> Self {
+		let mut new = self.clone();
+		new.settings.channel_mix_mode = channel_mix_mode;
+		new
+	}
+
+	/**
+	Sets the maximum amount the volume will be randomly raised or lowered
+	by each time the sound is played.
+
+	This returns a cheap clone of the [`StaticSoundData`] with the modified volume jitter.
+
+	# Examples
+
+	Make footsteps vary slightly in volume and pitch:
+
+	```no_run
+	use kira::sound::static_sound::StaticSoundData;
+
+	let footstep = StaticSoundData::from_file("footstep.ogg")?
+		.volume_jitter(3.0)
+		.playback_rate_jitter(1.0);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn volume_jitter(&self, volume_jitter: impl Into<Decibels>) -> Self {
+		let mut new = self.clone();
+		new.settings.volume_jitter = volume_jitter.into();
+		new
+	}
+
+	/// Sets the maximum amount the playback rate will be randomly raised or
+	/// lowered by each time the sound is played.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified playback rate jitter.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn playback_rate_jitter(&self, playback_rate_jitter: impl Into<Semitones>) -> Self {
+		let mut new = self.clone();
+		new.settings.playback_rate_jitter = playback_rate_jitter.into();
+		new
+	}
+
+	/// Sets the seed used to pick the random volume and playback rate offsets.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified seed.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn jitter_seed(&self, jitter_seed: impl Into<Option<u64>>) -> Self {
+		let mut new = self.clone();
+		new.settings.jitter_seed = jitter_seed.into();
+		new
+	}
+
 	/// Sets the tween used to fade in the sound from silence.
 	///
 	/// This returns a cheap clone of the [`StaticSoundData`] with the modified fade in tween.
@@ -286,6 +523,75 @@ impl StaticSoundData {
 		new
 	}
 
+	/// Sets a sweep of the playback rate that's applied when the sound starts.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified pitch envelope.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn pitch_envelope(&self, start: impl Into<PlaybackRate>, tween: Tween) -> Self {
+		let mut new = self.clone();
+		new.settings.pitch_envelope = Some((start.into(), tween));
+		new
+	}
+
+	/// Sends the sound's audio to a send track at the given volume, in
+	/// addition to the track it's playing on.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the added send.
+	///
+	/// # Panics
+	///
+	/// Panics if the sound already sends to [`MAX_SOUND_SENDS`](crate::sound::MAX_SOUND_SENDS)
+	/// other send tracks.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn send(&self, to: impl Into<SendTrackId>, volume: impl Into<Value<Decibels>>) -> Self {
+		let mut new = self.clone();
+		new.settings.sends.set(to, volume);
+		new
+	}
+
+	/// Sets the tween used to fade out the sound to silence as playback
+	/// approaches the end of the sound.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified fade out tween.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn fade_out_tween(&self, fade_out_tween: impl Into<Option<Tween>>) -> Self {
+		let mut new = self.clone();
+		new.settings.fade_out_tween = fade_out_tween.into();
+		new
+	}
+
+	/// Sets the [voice group](crate::voice_group) that limits how many instances
+	/// of the sound can play at once.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified voice group.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn voice_group(&self, voice_group: impl Into<VoiceGroupId>) -> Self {
+		let mut new = self.clone();
+		new.settings.voice_group = Some(voice_group.into());
+		new
+	}
+
+	/// Sets the [group](crate::group) the sound belongs to.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified group.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn group(&self, group: impl Into<GroupId>) -> Self {
+		let mut new = self.clone();
+		new.settings.group = Some(group.into());
+		new
+	}
+
+	/// Sets whether to skip checking the start position and loop region
+	/// when the sound is played.
+	///
+	/// This returns a cheap clone of the [`StaticSoundData`] with the modified setting.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn lenient(&self, lenient: bool) -> Self {
+		let mut new = self.clone();
+		new.settings.lenient = lenient;
+		new
+	}
+
 	/// Returns a cheap clone of the `StaticSoundData` with the specified settings.
 	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
 	pub fn with_settings(&self, settings: StaticSoundSettings) -> Self {
@@ -295,6 +601,73 @@ impl StaticSoundData {
 		}
 	}
 
+	/**
+	Checks that the start position and loop region are inside the
+	portion of the audio that plays.
+
+	This is called when the sound is played unless
+	[`StaticSoundSettings::lenient`] is `true`.
+	*/
+	pub fn validate(&self) -> Result<(), SettingsError> {
+		let num_frames = self.num_frames();
+		let start_position = self.settings.start_position;
+		let start = start_position.into_samples(self.sample_rate);
+		if !start_position.is_valid() || (start > 0 && start >= num_frames) {
+			return Err(SettingsError::InvalidStartPosition);
+		}
+		self.validate_loop_region()
+	}
+
+	pub(super) fn validate_loop_region(&self) -> Result<(), SettingsError> {
+		let Some(loop_region) = self.loop_region_to_play() else {
+			return Ok(());
+		};
+		if !loop_region.positions_are_valid() {
+			return Err(SettingsError::InvalidLoopRegionPosition);
+		}
+		if !loop_region.is_valid(self.sample_rate) {
+			return Err(SettingsError::EmptyLoopRegion);
+		}
+		let num_frames = self.num_frames();
+		let start = loop_region.start.into_samples(self.sample_rate);
+		let end = loop_region
+			.end_in_samples(self.sample_rate)
+			.unwrap_or(num_frames);
+		if start >= num_frames || end > num_frames {
+			return Err(SettingsError::LoopRegionOutsidePlaybackRegion);
+		}
+		Ok(())
+	}
+
+	/// Returns the information read from the tags in the audio file, like
+	/// its title and artist, if there are any.
+	#[must_use]
+	pub fn metadata(&self) -> Option<&Metadata> {
+		self.metadata.as_deref()
+	}
+
+	/// Returns the loop points stored in the audio file, like the ones in
+	/// a WAV file's `smpl` chunk or `LOOPSTART` tags, if there are any.
+	///
+	/// This region is looped when [`StaticSoundSettings::loop_region`] is
+	/// `None`, unless
+	/// [`use_detected_loop_region`](StaticSoundSettings::use_detected_loop_region)
+	/// is `false`.
+	#[must_use]
+	pub fn detected_loop_region(&self) -> Option<Region> {
+		self.metadata()?.loop_region
+	}
+
+	/// Returns the portion of the sound that will be looped when it's
+	/// played.
+	#[must_use]
+	pub(super) fn loop_region_to_play(&self) -> Option<Region> {
+		if self.settings.loop_region.is_some() || !self.settings.use_detected_loop_region {
+			return self.settings.loop_region;
+		}
+		self.detected_loop_region()
+	}
+
 	/// Returns the number of frames in the [`StaticSoundData`].
 	///
 	/// If [`StaticSoundData::slice`] is `Some`, this will be the number
@@ -338,6 +711,11 @@ impl StaticSoundData {
 	[`StaticSoundData::duration`], and [`StaticSoundData::frame_at_index`] will all behave
 	as if this [`StaticSoundData`] only contained the specified portion of audio.
 
+	If this [`StaticSoundData`] is already sliced, the region is measured relative to
+	the existing slice, so slicing a slice narrows it further. Regions that extend past
+	the end of the audio are clamped, and a region that ends before it starts produces
+	an empty sound that finishes immediately. Passing `None` removes the slice.
+
 	# Example
 
 	```
@@ -350,35 +728,320 @@ impl StaticSoundData {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::default(),
 		slice: None,
+		metadata: None,
 	};
 	let sliced = sound.slice(3.0..6.0);
 	assert_eq!(sliced.num_frames(), 3);
 	assert_eq!(sliced.frame_at_index(0), Some(Frame::from_mono(3.0)));
 	assert_eq!(sliced.frame_at_index(1), Some(Frame::from_mono(4.0)));
+	let sliced_again = sliced.slice(1.0..);
+	assert_eq!(sliced_again.num_frames(), 2);
+	assert_eq!(sliced_again.frame_at_index(0), Some(Frame::from_mono(4.0)));
 	```
 	*/
 	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
 	pub fn slice(&self, region: impl IntoOptionalRegion) -> Self {
 		let mut new = self.clone();
-		new.slice = region.into_optional_region().map(|Region { start, end }| {
-			let start = start.into_samples(self.sample_rate);
-			let end = match end {
-				EndPosition::EndOfAudio => self.frames.len(),
-				EndPosition::Custom(end) => end.into_samples(self.sample_rate),
-			};
-			(start, end)
-		});
+		new.slice = region
+			.into_optional_region()
+			.map(|region| self.slice_bounds(region));
 		new
 	}
 
+	/// Returns the frames of audio in the [`StaticSoundData`].
+	///
+	/// If [`StaticSoundData::slice`] is `Some`, this will only return the frames
+	/// in that portion of the audio. To get all of the frames regardless of the
+	/// slice, use the [`frames`](StaticSoundData#structfield.frames) field.
+	#[must_use]
+	pub fn frames(&self) -> &[Frame] {
+		let (start, end) = self.slice.unwrap_or((0, self.frames.len()));
+		&self.frames[start..end]
+	}
+
+	/**
+	Returns the frames of audio in the given region.
+
+	Like [`StaticSoundData::slice`], the region is measured relative
+	to the current slice and clamped to the audio.
+
+	# Example
+
+	```
+	use kira::{
+		sound::static_sound::{StaticSoundData, StaticSoundSettings},
+		Frame,
+	};
+	let sound = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	}
+	.slice(2.0..);
+	assert_eq!(
+		sound.frames_in_region(1.0..3.0),
+		&[Frame::from_mono(3.0), Frame::from_mono(4.0)]
+	);
+	```
+	*/
+	#[must_use]
+	pub fn frames_in_region(&self, region: impl Into<Region>) -> &[Frame] {
+		let (start, end) = self.slice_bounds(region.into());
+		&self.frames[start..end]
+	}
+
+	/// Returns the [`Frame`] of audio at the given position (in seconds).
+	///
+	/// This converts the position to a frame index the same way
+	/// the [`start_position`](StaticSoundSettings::start_position) setting
+	/// and loop regions do, so it matches the frame that's heard when
+	/// playback is at that position. Returns `None` if the position is
+	/// negative or past the end of the audio.
+	///
+	/// If [`StaticSoundData::slice`] is `Some`, this will behave as if the [`StaticSoundData`]
+	/// only contained that portion of the audio.
+	#[must_use]
+	pub fn frame_at_position(&self, position: f64) -> Option<Frame> {
+		if position.is_nan() || position < 0.0 {
+			return None;
+		}
+		self.frame_at_index(PlaybackPosition::Seconds(position).into_samples(self.sample_rate))
+	}
+
+	/// Returns the start and end index in [`StaticSoundData::frames`] of
+	/// a region measured relative to the current slice.
+	#[must_use]
+	fn slice_bounds(&self, region: Region) -> (usize, usize) {
+		let (current_start, current_end) = self.slice.unwrap_or((0, self.frames.len()));
+		let start = current_start
+			.saturating_add(region.start.into_samples(self.sample_rate))
+			.min(current_end);
+		let end = match region.end_in_samples(self.sample_rate) {
+			None => current_end,
+			Some(end) => current_start.saturating_add(end).clamp(start, current_end),
+		};
+		(start, end)
+	}
+
+	/**
+	Plays the sound offline and returns the resulting frames at the
+	given output sample rate.
+
+	This uses the same code path as live playback, so the playback rate,
+	fade-in, slice, loop region, and other settings are applied exactly as
+	they would be by an [`AudioManager`](crate::AudioManager), including
+	the resampling from [`StaticSoundData::sample_rate`] to `sample_rate`.
+
+	Since rendering has to end at some point, a loop region with no
+	[`loop_count`](StaticSoundData::loop_count) only plays through once.
+	Some sounds never finish on their own, such as sounds with a playback
+	rate of `0.0` or sounds that start paused, so rendering also stops
+	after `max_frames` frames, and the result never holds more than that.
+	Start times based on a clock are treated as immediate, and values linked
+	to modulators keep their initial values.
+
+	# Example
+
+	```
+	use kira::{
+		sound::static_sound::{StaticSoundData, StaticSoundSettings},
+		Frame,
+	};
+	let sound = StaticSoundData {
+		sample_rate: 48_000,
+		frames: (0..48_000).map(|_| Frame::from_mono(0.5)).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	}
+	.loop_region(..)
+	.loop_count(2);
+	let frames = sound.render(44_100, 441_000);
+	assert!(frames.len() >= 88_200);
+	```
+	*/
+	#[must_use]
+	pub fn render(&self, sample_rate: u32, max_frames: usize) -> Vec<Frame> {
+		let mut data = self.clone();
+		if data.loop_region_to_play().is_some() && data.settings.loop_count.is_none() {
+			data.settings.loop_count = Some(1);
+		}
+		let mut frames = vec![];
+		match data.settings.start_time {
+			StartTime::ClockTime(_) => data.settings.start_time = StartTime::Immediate,
+			// chunks always start at the beginning, so add the delay
+			// ahead of time to keep it sample-accurate
+			StartTime::Frames(delay) => {
+				frames.resize(delay as usize, Frame::ZERO);
+				data.settings.start_time = StartTime::Immediate;
+			}
+			_ => {}
+		}
+		let (mut sound, _) = data.split();
+		let dt = 1.0 / sample_rate as f64;
+		let info = {
+			let mut builder = MockInfoBuilder::new();
+			builder.set_frame_duration(dt);
+			builder.build()
+		};
+		while !sound.finished() && frames.len() < max_frames {
+			sound.on_start_processing();
+			let advancing = sound.start_chunk(RENDER_CHUNK_SIZE, dt, &info);
+			for i in 0..RENDER_CHUNK_SIZE {
+				if !advancing {
+					frames.push(Frame::ZERO);
+					continue;
+				}
+				let time_in_chunk = (i + 1) as f64 / RENDER_CHUNK_SIZE as f64;
+				frames.push(sound.next_frame(time_in_chunk, dt));
+				if sound.finished() {
+					break;
+				}
+			}
+		}
+		frames.truncate(max_frames);
+		frames
+	}
+
+	/**
+	Returns a new [`StaticSoundData`] that plays this sound followed by `other`.
+
+	If the two sounds have different sample rates, `other` is resampled to
+	match this sound's sample rate using the same interpolation Kira uses
+	during playback. Slices are applied before joining the audio.
+
+	The result keeps this sound's settings, except for the loop region, which
+	is removed since it no longer refers to the same portion of audio.
+
+	# Example
+
+	```
+	use kira::{
+		sound::static_sound::{StaticSoundData, StaticSoundSettings},
+		Frame,
+	};
+	let intro = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..3).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	};
+	let outro = StaticSoundData {
+		sample_rate: 1,
+		frames: (3..5).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	};
+	let sound = intro.concat(&outro)?;
+	assert_eq!(sound.num_frames(), 5);
+	assert_eq!(sound.frame_at_index(3), Some(Frame::from_mono(3.0)));
+	# Result::<(), kira::sound::static_sound::CombineError>::Ok(())
+	```
+	*/
+	pub fn concat(&self, other: &StaticSoundData) -> Result<Self, CombineError> {
+		let other_frames = other.resampled_frames(self.sample_rate)?;
+		let frames = self.frames().iter().chain(&other_frames).copied().collect();
+		Ok(self.combined(frames))
+	}
+
+	/**
+	Returns a new [`StaticSoundData`] that plays this sound and `other`
+	at the same time.
+
+	The frames of the two sounds are summed together, and the result is
+	as long as the longer of the two sounds. If the two sounds have
+	different sample rates, `other` is resampled to match this sound's
+	sample rate. Slices are applied before mixing the audio.
+
+	The result keeps this sound's settings, except for the loop region, which
+	is removed since it no longer refers to the same portion of audio.
+
+	# Example
+
+	```
+	use kira::{
+		sound::static_sound::{StaticSoundData, StaticSoundSettings},
+		Frame,
+	};
+	let a = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..3).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	};
+	let b = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..5).map(|_| Frame::from_mono(1.0)).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	};
+	let sound = a.mix(&b)?;
+	assert_eq!(sound.num_frames(), 5);
+	assert_eq!(sound.frame_at_index(2), Some(Frame::from_mono(3.0)));
+	assert_eq!(sound.frame_at_index(4), Some(Frame::from_mono(1.0)));
+	# Result::<(), kira::sound::static_sound::CombineError>::Ok(())
+	```
+	*/
+	pub fn mix(&self, other: &StaticSoundData) -> Result<Self, CombineError> {
+		let frames = self.frames();
+		let other_frames = other.resampled_frames(self.sample_rate)?;
+		let frames = (0..frames.len().max(other_frames.len()))
+			.map(|i| {
+				frames.get(i).copied().unwrap_or_default()
+					+ other_frames.get(i).copied().unwrap_or_default()
+			})
+			.collect();
+		Ok(self.combined(frames))
+	}
+
+	/// Returns the frames in the current slice resampled to the
+	/// given sample rate.
+	fn resampled_frames(&self, sample_rate: u32) -> Result<Vec<Frame>, CombineError> {
+		if self.sample_rate == 0 || sample_rate == 0 {
+			return Err(CombineError::ZeroSampleRate);
+		}
+		Ok(resample::resample_frames(
+			self.frames(),
+			self.sample_rate,
+			sample_rate,
+			ResampleQuality::Cubic,
+		))
+	}
+
+	/// Creates a [`StaticSoundData`] with this sound's sample rate and
+	/// settings and the given frames.
+	#[must_use]
+	fn combined(&self, frames: Vec<Frame>) -> Self {
+		Self {
+			sample_rate: self.sample_rate,
+			frames: frames.into(),
+			settings: StaticSoundSettings {
+				loop_region: None,
+				..self.settings
+			},
+			slice: None,
+			metadata: None,
+		}
+	}
+
 	pub(super) fn split(self) -> (StaticSound, StaticSoundHandle) {
 		let (command_writers, command_readers) = command_writers_and_readers();
-		let sound = StaticSound::new(self, command_readers);
+		let (segment_queue_writer, segment_queue_reader) = segment_queue();
+		let (send_levels, send_level_command_writers) = sound_send_levels(&self.settings.sends);
+		let sound = StaticSound::new(self, command_readers, segment_queue_reader, send_levels);
 		let shared = sound.shared();
 		(
 			sound,
 			StaticSoundHandle {
 				command_writers,
+				send_level_command_writers,
+				segment_queue: Mutex::new(segment_queue_writer),
 				shared,
 			},
 		)
@@ -386,12 +1049,15 @@ impl StaticSoundData {
 }
 
 impl SoundData for StaticSoundData {
-	type Error = ();
+	type Error = SettingsError;
 
 	type Handle = StaticSoundHandle;
 
 	#[allow(clippy::type_complexity)]
 	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		if !self.settings.lenient {
+			self.validate()?;
+		}
 		let (sound, handle) = self.split();
 		Ok((Box::new(sound), handle))
 	}
@@ -408,6 +1074,7 @@ impl Debug for StaticSoundData {
 				},
 			)
 			.field("settings", &self.settings)
+			.field("metadata", &self.metadata)
 			.finish()
 	}
 }
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use crate::{frame::Frame, Decibels};
+
+use super::StaticSoundData;
+
+impl StaticSoundData {
+	/// The largest gain [`StaticSoundData::normalized`] will apply to a sound.
+	///
+	/// This keeps near-silent sounds from having their noise floor
+	/// amplified to full volume.
+	pub const MAX_NORMALIZATION_GAIN: Decibels = Decibels(40.0);
+
+	/// Returns the volume of the loudest sample in the sound, taking
+	/// the louder of the left and right channels.
+	///
+	/// NaN and infinite samples are ignored. An empty or completely silent
+	/// sound returns negative infinity.
+	#[must_use]
+	pub fn peak(&self) -> Decibels {
+		Decibels::from_amplitude(peak_amplitude(self.frames()))
+	}
+
+	/// Returns the root mean square volume of the sound, taking
+	/// the louder of the left and right channels.
+	///
+	/// NaN and infinite samples are ignored. An empty or completely silent
+	/// sound returns negative infinity.
+	#[must_use]
+	pub fn rms(&self) -> Decibels {
+		Decibels::from_amplitude(rms_amplitude(self.frames()))
+	}
+
+	/// Returns the root mean square volume of each consecutive `window`
+	/// of the sound, which is useful for drawing waveform overviews.
+	///
+	/// The last window may be shorter than the others. Windows shorter
+	/// than one frame are treated as one frame long.
+	#[must_use]
+	pub fn windowed_rms(&self, window: Duration) -> Vec<Decibels> {
+		let window_frames =
+			((window.as_secs_f64() * self.sample_rate as f64).round() as usize).max(1);
+		self.frames()
+			.chunks(window_frames)
+			.map(|frames| Decibels::from_amplitude(rms_amplitude(frames)))
+			.collect()
+	}
+
+	/// Returns the index of the frame containing the loudest sample,
+	/// or `None` if the sound has no finite, non-zero samples.
+	///
+	/// If [`StaticSoundData::slice`] is `Some`, the index is relative to
+	/// the start of the slice.
+	#[must_use]
+	pub fn loudest_frame_index(&self) -> Option<usize> {
+		let mut loudest = None;
+		let mut loudest_amplitude = 0.0;
+		for (i, frame) in self.frames().iter().enumerate() {
+			let amplitude = frame_peak(*frame);
+			if amplitude > loudest_amplitude {
+				loudest = Some(i);
+				loudest_amplitude = amplitude;
+			}
+		}
+		loudest
+	}
+
+	/**
+	Returns a new [`StaticSoundData`] with its volume adjusted so that
+	its [peak](StaticSoundData::peak) is at the `target` volume.
+
+	The gain is applied to a copy of the audio, and it's limited to
+	[`StaticSoundData::MAX_NORMALIZATION_GAIN`], so silent or nearly silent
+	sounds won't reach the target.
+
+	# Example
+
+	```
+	use kira::{
+		sound::static_sound::{StaticSoundData, StaticSoundSettings},
+		Decibels, Frame,
+	};
+	let sound = StaticSoundData {
+		sample_rate: 1,
+		frames: [0.1, -0.25, 0.2].into_iter().map(Frame::from_mono).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	};
+	let normalized = sound.normalized(Decibels::IDENTITY);
+	assert!((normalized.peak().0 - 0.0).abs() < 0.0001);
+	```
+	*/
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn normalized(&self, target: Decibels) -> Self {
+		let gain = Decibels((target - self.peak()).0.min(Self::MAX_NORMALIZATION_GAIN.0));
+		let amplitude = gain.as_amplitude();
+		Self {
+			frames: self.frames.iter().map(|frame| *frame * amplitude).collect(),
+			..self.clone()
+		}
+	}
+}
+
+#[must_use]
+fn finite_or_zero(sample: f32) -> f32 {
+	if sample.is_finite() {
+		sample
+	} else {
+		0.0
+	}
+}
+
+#[must_use]
+fn frame_peak(frame: Frame) -> f32 {
+	finite_or_zero(frame.left)
+		.abs()
+		.max(finite_or_zero(frame.right).abs())
+}
+
+#[must_use]
+fn peak_amplitude(frames: &[Frame]) -> f32 {
+	frames
+		.iter()
+		.map(|frame| frame_peak(*frame))
+		.fold(0.0, f32::max)
+}
+
+#[must_use]
+fn rms_amplitude(frames: &[Frame]) -> f32 {
+	let mut left = 0.0f64;
+	let mut right = 0.0f64;
+	let mut num_left = 0usize;
+	let mut num_right = 0usize;
+	for frame in frames {
+		if frame.left.is_finite() {
+			left += (frame.left as f64).powi(2);
+			num_left += 1;
+		}
+		if frame.right.is_finite() {
+			right += (frame.right as f64).powi(2);
+			num_right += 1;
+		}
+	}
+	let channel_rms = |sum: f64, count: usize| {
+		if count == 0 {
+			0.0
+		} else {
+			(sum / count as f64).sqrt()
+		}
+	};
+	channel_rms(left, num_left).max(channel_rms(right, num_right)) as f32
+}
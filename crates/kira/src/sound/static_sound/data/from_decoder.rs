@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::sound::{static_sound::StaticSoundSettings, streaming::Decoder};
+
+use super::StaticSoundData;
+
+impl StaticSoundData {
+	/**
+	Decodes all of the audio from a [`Decoder`] into a [`StaticSoundData`].
+
+	The audio is decoded until the decoder returns an empty `Vec` or
+	[`Decoder::num_frames`] samples have been decoded, so decoders that
+	return empty `Vec`s while they wait for more audio should be played
+	with [`StreamingSoundData::from_decoder`](crate::sound::streaming::StreamingSoundData::from_decoder)
+	instead.
+	*/
+	pub fn from_decoder<D: Decoder>(mut decoder: D) -> Result<Self, D::Error> {
+		let mut frames = vec![];
+		while frames.len() < decoder.num_frames() {
+			let chunk = decoder.decode()?;
+			if chunk.is_empty() {
+				break;
+			}
+			frames.extend(chunk);
+		}
+		frames.truncate(decoder.num_frames());
+		Ok(Self {
+			sample_rate: decoder.sample_rate(),
+			frames: frames.into(),
+			settings: StaticSoundSettings::default(),
+			slice: None,
+			metadata: decoder.metadata().cloned().map(Arc::new),
+		})
+	}
+}
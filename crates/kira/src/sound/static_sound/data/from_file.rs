@@ -1,13 +1,41 @@
-use std::io::Cursor;
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+use std::future::Future;
+use std::{
+	io::Cursor,
+	sync::{
+		atomic::{AtomicBool, AtomicU64, Ordering},
+		Arc,
+	},
+};
 
-use symphonia::core::io::{MediaSource, MediaSourceStream};
+use symphonia::core::{
+	codecs::{CodecParameters, CodecType, CODEC_TYPE_FLAC},
+	formats::{FormatReader, Packet},
+	io::MediaSource,
+};
 
-use crate::sound::{
-	static_sound::StaticSoundSettings, symphonia::load_frames_from_buffer_ref, FromFileError,
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+use crate::sound::blocking_task::BlockingTask;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sound::DecodeProgress;
+use crate::{
+	frame::Frame,
+	sound::{
+		decode_options::select_track,
+		decode_progress::{CountingSource, ProgressCallback, ProgressReporter},
+		metadata::read_smpl_loop_region,
+		static_sound::StaticSoundSettings,
+		symphonia::{load_frames_from_buffer_ref, probe, probed_tags},
+		ChannelMapping, DecodeOptions, FromFileError, Metadata,
+	},
 };
 
 use super::StaticSoundData;
 
+/// How many packets the thread reading a file can get ahead of the thread
+/// decoding it.
+const MAX_PACKETS_READ_AHEAD: usize = 64;
+
 impl StaticSoundData {
 	/// Loads an audio file into a [`StaticSoundData`].
 	#[cfg(not(target_arch = "wasm32"))]
@@ -16,6 +44,114 @@ impl StaticSoundData {
 		Self::from_media_source(std::fs::File::open(path)?)
 	}
 
+	/**
+	Loads an audio file into a [`StaticSoundData`] using the given
+	[`DecodeOptions`].
+
+	# Examples
+
+	Decode a long file on 4 threads:
+
+	```no_run
+	use kira::sound::{static_sound::StaticSoundData, DecodeOptions};
+
+	let sound_data =
+		StaticSoundData::from_file_with("music.flac", DecodeOptions::new().threads(4))?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "symphonia", not(wasm32)))))]
+	pub fn from_file_with(
+		path: impl AsRef<std::path::Path>,
+		options: DecodeOptions,
+	) -> Result<Self, FromFileError> {
+		Self::from_boxed_media_source_with(Box::new(std::fs::File::open(path)?), options)
+	}
+
+	/**
+	Loads an audio file into a [`StaticSoundData`], reporting how much of
+	the file has been decoded along the way.
+
+	The callback is called every few packets of audio. Returning
+	[`ControlFlow::Break`](std::ops::ControlFlow::Break) from it stops
+	decoding, and [`FromFileError::Cancelled`] is returned.
+
+	# Examples
+
+	Show the progress of loading a file:
+
+	```no_run
+	use std::ops::ControlFlow;
+
+	use kira::sound::static_sound::StaticSoundData;
+
+	let sound_data = StaticSoundData::from_file_with_progress("music.flac", |progress| {
+		if let Some(fraction) = progress.fraction() {
+			println!("{:.0}% loaded", fraction * 100.0);
+		}
+		ControlFlow::Continue(())
+	})?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "symphonia", not(wasm32)))))]
+	pub fn from_file_with_progress(
+		path: impl AsRef<std::path::Path>,
+		mut callback: impl FnMut(DecodeProgress) -> std::ops::ControlFlow<()>,
+	) -> Result<Self, FromFileError> {
+		Self::decode_media_source(
+			Box::new(std::fs::File::open(path)?),
+			DecodeOptions::default(),
+			Some(&mut callback),
+		)
+	}
+
+	/**
+	Loads an audio file into a [`StaticSoundData`] without blocking the
+	async executor.
+
+	The file is loaded and decoded on a separate thread.
+
+	# Examples
+
+	```no_run
+	use kira::sound::static_sound::StaticSoundData;
+
+	# async fn load() -> Result<(), kira::sound::FromFileError> {
+	let sound_data = StaticSoundData::from_file_async("music.flac").await?;
+	# Ok(())
+	# }
+	```
+	*/
+	#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(all(feature = "symphonia", feature = "async", not(wasm32))))
+	)]
+	pub fn from_file_async(
+		path: impl AsRef<std::path::Path>,
+	) -> impl Future<Output = Result<Self, FromFileError>> + Send + 'static {
+		let path = path.as_ref().to_owned();
+		BlockingTask::spawn(move || Self::from_file(path))
+	}
+
+	/// Loads a cursor wrapping audio file data into a [`StaticSoundData`]
+	/// without blocking the async executor.
+	///
+	/// The audio is decoded on a separate thread.
+	#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(all(feature = "symphonia", feature = "async", not(wasm32))))
+	)]
+	pub fn from_cursor_async<T: AsRef<[u8]> + Send + Sync + 'static>(
+		cursor: Cursor<T>,
+	) -> impl Future<Output = Result<Self, FromFileError>> + Send + 'static {
+		BlockingTask::spawn(move || Self::from_cursor(cursor))
+	}
+
 	/// Loads a cursor wrapping audio file data into a [`StaticSoundData`].
 	#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
 	pub fn from_cursor<T: AsRef<[u8]> + Send + Sync + 'static>(
@@ -24,58 +160,267 @@ impl StaticSoundData {
 		Self::from_media_source(cursor)
 	}
 
+	/**
+	Loads audio file data that's already in memory into a
+	[`StaticSoundData`].
+
+	The data isn't copied, so buffers that are shared with other code,
+	like an `Arc<[u8]>`, can be loaded without using extra memory.
+
+	# Examples
+
+	```no_run
+	use std::sync::Arc;
+
+	use kira::sound::static_sound::StaticSoundData;
+
+	let asset_pack: Arc<[u8]> = std::fs::read("sfx.ogg")?.into();
+	let sound_data = StaticSoundData::from_bytes(asset_pack.clone())?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
+	pub fn from_bytes(
+		bytes: impl AsRef<[u8]> + Send + Sync + 'static,
+	) -> Result<StaticSoundData, FromFileError> {
+		Self::from_cursor(Cursor::new(bytes))
+	}
+
 	/// Loads an audio file from a type that implements Symphonia's [`MediaSource`]
 	/// trait.
 	#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
 	pub fn from_media_source(
 		media_source: impl MediaSource + 'static,
 	) -> Result<Self, FromFileError> {
-		Self::from_boxed_media_source(Box::new(media_source))
+		Self::from_boxed_media_source_with(Box::new(media_source), DecodeOptions::default())
 	}
 
-	fn from_boxed_media_source(media_source: Box<dyn MediaSource>) -> Result<Self, FromFileError> {
-		let codecs = symphonia::default::get_codecs();
-		let probe = symphonia::default::get_probe();
-		let mss = MediaSourceStream::new(media_source, Default::default());
-		let mut format_reader = probe
-			.format(
-				&Default::default(),
-				mss,
-				&Default::default(),
-				&Default::default(),
-			)?
-			.format;
-		let codec_params = &format_reader
-			.default_track()
-			.ok_or(FromFileError::NoDefaultTrack)?
-			.codec_params;
+	pub(crate) fn from_boxed_media_source_with(
+		media_source: Box<dyn MediaSource>,
+		options: DecodeOptions,
+	) -> Result<Self, FromFileError> {
+		Self::decode_media_source(media_source, options, None)
+	}
+
+	pub(crate) fn decode_media_source(
+		media_source: Box<dyn MediaSource>,
+		options: DecodeOptions,
+		progress_callback: Option<ProgressCallback>,
+	) -> Result<Self, FromFileError> {
+		let bytes_read = Arc::new(AtomicU64::new(0));
+		let total_bytes = media_source.byte_len();
+		let mut media_source: Box<dyn MediaSource> = if progress_callback.is_some() {
+			Box::new(CountingSource::new(media_source, bytes_read.clone()))
+		} else {
+			media_source
+		};
+		let mut progress = ProgressReporter::new(progress_callback, bytes_read, total_bytes);
+		let smpl_loop_region = if media_source.is_seekable() {
+			read_smpl_loop_region(&mut *media_source)
+		} else {
+			None
+		};
+		let mut probed = probe(media_source, &options.hint())?;
+		let metadata = Metadata::from_tags(&probed_tags(&mut probed), smpl_loop_region);
+		let mut format_reader = probed.format;
+		let track = select_track(&*format_reader, options.track_index)?;
+		let track_id = track.id;
+		let codec_params = track.codec_params.clone();
+		let metadata = Metadata::with_channel_mapping(
+			metadata,
+			codec_params.channels.map_or(0, |channels| channels.count()),
+			options.channel_mapping,
+		)
+		.map(Arc::new);
+		let channel_mapping = options.channel_mapping;
+		progress.set_total_frames(codec_params.n_frames.map(|n_frames| n_frames as usize));
 		let sample_rate = codec_params
 			.sample_rate
 			.ok_or(FromFileError::UnknownSampleRate)?;
-		let mut decoder = codecs.make(codec_params, &Default::default())?;
-		let mut frames = vec![];
-		loop {
-			match format_reader.next_packet() {
-				Ok(packet) => {
-					let buffer = decoder.decode(&packet)?;
-					frames.append(&mut load_frames_from_buffer_ref(&buffer)?);
-				}
-				Err(error) => match error {
-					symphonia::core::errors::Error::IoError(error) => {
-						if error.kind() == std::io::ErrorKind::UnexpectedEof {
-							break;
-						}
-						return Err(symphonia::core::errors::Error::IoError(error).into());
-					}
-					error => return Err(error.into()),
-				},
-			}
-		}
-		Ok(Self {
+		let frames = if options.threads <= 1 {
+			decode_packets(
+				&codec_params,
+				channel_mapping,
+				std::iter::from_fn(|| next_packet(&mut *format_reader, track_id).transpose()),
+				|num_frames| progress.packet_decoded(num_frames),
+			)?
+		} else if packets_are_independent(codec_params.codec) {
+			decode_in_parallel(
+				&mut *format_reader,
+				track_id,
+				&codec_params,
+				channel_mapping,
+				options.threads,
+				&mut progress,
+			)?
+		} else {
+			decode_while_reading(
+				format_reader,
+				track_id,
+				&codec_params,
+				channel_mapping,
+				&mut progress,
+			)?
+		};
+		let data = Self {
 			sample_rate,
 			frames: frames.into(),
 			settings: StaticSoundSettings::default(),
 			slice: None,
+			metadata,
+		};
+		Ok(match options.resample_to {
+			Some((sample_rate, quality)) => data.resampled(sample_rate, quality),
+			None => data,
 		})
 	}
 }
+
+/// Reads the next packet of the track from the file, or returns `None` if
+/// the end of the file was reached.
+fn next_packet(
+	format_reader: &mut dyn FormatReader,
+	track_id: u32,
+) -> Result<Option<Packet>, FromFileError> {
+	loop {
+		match format_reader.next_packet() {
+			// packets from other tracks are skipped
+			Ok(packet) if packet.track_id() != track_id => {}
+			Ok(packet) => return Ok(Some(packet)),
+			Err(symphonia::core::errors::Error::IoError(error))
+				if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+			{
+				return Ok(None)
+			}
+			Err(error) => return Err(error.into()),
+		}
+	}
+}
+
+/// Returns `true` if each packet of audio encoded with the codec can be
+/// decoded without decoding the packets before it.
+#[must_use]
+fn packets_are_independent(codec: CodecType) -> bool {
+	codec == CODEC_TYPE_FLAC
+		|| symphonia::default::get_codecs()
+			.get_codec(codec)
+			.is_some_and(|descriptor| descriptor.short_name.starts_with("pcm"))
+}
+
+/// Decodes packets in order with a new decoder.
+///
+/// `on_packet_decoded` is called with the number of frames in each
+/// decoded packet, and decoding stops if it returns an error.
+fn decode_packets(
+	codec_params: &CodecParameters,
+	channel_mapping: ChannelMapping,
+	packets: impl IntoIterator<Item = Result<Packet, FromFileError>>,
+	mut on_packet_decoded: impl FnMut(usize) -> Result<(), FromFileError>,
+) -> Result<Vec<Frame>, FromFileError> {
+	let mut decoder = symphonia::default::get_codecs().make(codec_params, &Default::default())?;
+	let mut frames = vec![];
+	for packet in packets {
+		let buffer = decoder.decode(&packet?)?;
+		let mut packet_frames = load_frames_from_buffer_ref(&buffer, channel_mapping)?;
+		on_packet_decoded(packet_frames.len())?;
+		frames.append(&mut packet_frames);
+	}
+	Ok(frames)
+}
+
+/// Reads every packet from the file, splits the packets into one part
+/// for each thread, and decodes the parts at the same time.
+fn decode_in_parallel(
+	format_reader: &mut dyn FormatReader,
+	track_id: u32,
+	codec_params: &CodecParameters,
+	channel_mapping: ChannelMapping,
+	threads: usize,
+	progress: &mut ProgressReporter,
+) -> Result<Vec<Frame>, FromFileError> {
+	let mut packets = vec![];
+	while let Some(packet) = next_packet(format_reader, track_id)? {
+		packets.push(packet);
+	}
+	let packets_per_thread = packets.len().div_ceil(threads).max(1);
+	let mut packets = packets.into_iter();
+	let reporting = progress.is_reporting();
+	let cancelled = AtomicBool::new(false);
+	let (decoded_sender, decoded_receiver) = std::sync::mpsc::channel();
+	let parts = std::thread::scope(|scope| {
+		let decoding_threads = std::iter::repeat_with(|| {
+			packets
+				.by_ref()
+				.take(packets_per_thread)
+				.collect::<Vec<_>>()
+		})
+		.take(threads)
+		.map(|part| {
+			let decoded_sender = decoded_sender.clone();
+			let cancelled = &cancelled;
+			scope.spawn(move || {
+				decode_packets(
+					codec_params,
+					channel_mapping,
+					part.into_iter().map(Ok),
+					|num_frames| {
+						if cancelled.load(Ordering::Relaxed) {
+							return Err(FromFileError::Cancelled);
+						}
+						if reporting {
+							decoded_sender.send(num_frames).ok();
+						}
+						Ok(())
+					},
+				)
+			})
+		})
+		.collect::<Vec<_>>();
+		// the progress is reported from this thread as the decoding
+		// threads finish packets
+		drop(decoded_sender);
+		for num_frames in decoded_receiver {
+			if progress.packet_decoded(num_frames).is_err() {
+				cancelled.store(true, Ordering::Relaxed);
+				break;
+			}
+		}
+		decoding_threads
+			.into_iter()
+			.map(|thread| thread.join().expect("decoding thread panicked"))
+			.collect::<Result<Vec<_>, _>>()
+	})?;
+	Ok(parts.concat())
+}
+
+/// Reads packets from the file on a separate thread while decoding them.
+fn decode_while_reading(
+	mut format_reader: Box<dyn FormatReader>,
+	track_id: u32,
+	codec_params: &CodecParameters,
+	channel_mapping: ChannelMapping,
+	progress: &mut ProgressReporter,
+) -> Result<Vec<Frame>, FromFileError> {
+	let (packet_sender, packet_receiver) = std::sync::mpsc::sync_channel(MAX_PACKETS_READ_AHEAD);
+	std::thread::scope(|scope| {
+		scope.spawn(move || loop {
+			let packet = next_packet(&mut *format_reader, track_id).transpose();
+			let reached_end = !matches!(packet, Some(Ok(_)));
+			// the decoder stops receiving packets if it fails
+			if let Some(packet) = packet {
+				if packet_sender.send(packet).is_err() {
+					return;
+				}
+			}
+			if reached_end {
+				return;
+			}
+		});
+		decode_packets(
+			codec_params,
+			channel_mapping,
+			packet_receiver,
+			|num_frames| progress.packet_decoded(num_frames),
+		)
+	})
+}
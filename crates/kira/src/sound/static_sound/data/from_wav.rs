@@ -0,0 +1,36 @@
+use std::io::Cursor;
+
+use crate::sound::{wav::WavDecoder, FromFileError};
+
+use super::StaticSoundData;
+
+impl StaticSoundData {
+	/// Loads a WAV file into a [`StaticSoundData`].
+	///
+	/// Without the `symphonia` feature, only WAV files with 16, 24, or
+	/// 32-bit integer samples or 32-bit floating point samples can be
+	/// loaded.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(all(not(feature = "symphonia"), not(wasm32)))))]
+	pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, FromFileError> {
+		let file = std::io::BufReader::new(std::fs::File::open(path)?);
+		Self::from_decoder(WavDecoder::new(Box::new(file))?)
+	}
+
+	/// Loads a cursor wrapping WAV file data into a [`StaticSoundData`].
+	#[cfg_attr(docsrs, doc(cfg(not(feature = "symphonia"))))]
+	pub fn from_cursor<T: AsRef<[u8]> + Send + Sync + 'static>(
+		cursor: Cursor<T>,
+	) -> Result<StaticSoundData, FromFileError> {
+		Self::from_decoder(WavDecoder::new(Box::new(cursor))?)
+	}
+
+	/// Loads WAV file data that's already in memory into a
+	/// [`StaticSoundData`] without copying it.
+	#[cfg_attr(docsrs, doc(cfg(not(feature = "symphonia"))))]
+	pub fn from_bytes(
+		bytes: impl AsRef<[u8]> + Send + Sync + 'static,
+	) -> Result<StaticSoundData, FromFileError> {
+		Self::from_cursor(Cursor::new(bytes))
+	}
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod test;
+
+use std::{f64::consts::PI, sync::Arc};
+
+use crate::{
+	frame::{interpolate_frame, Frame},
+	sound::{EndPosition, Metadata, PlaybackPosition, Region},
+};
+
+use super::StaticSoundData;
+
+/// How many zero crossings of the sinc function are used on each side of
+/// a sample by [`ResampleQuality::Sinc`].
+const SINC_ZERO_CROSSINGS: usize = 32;
+
+/// How accurately audio is converted to a different sample rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResampleQuality {
+	/// Draws straight lines between samples. This is the fastest, but it
+	/// muffles high frequencies and adds aliasing.
+	Linear,
+	/// Uses the same cubic interpolation Kira uses when playing sounds at
+	/// playback rates other than `1.0`.
+	Cubic,
+	/// Uses a windowed sinc filter, which keeps the frequencies that both
+	/// sample rates can represent intact and removes the ones above them.
+	/// This is the slowest, so it's best used when loading sounds.
+	#[default]
+	Sinc,
+}
+
+impl StaticSoundData {
+	/**
+	Returns a copy of this sound converted to a different sample rate.
+
+	Sounds are resampled while they play if their sample rate doesn't match
+	the sample rate of the [`AudioManager`](crate::AudioManager), which costs
+	CPU time for every instance of the sound. Resampling sounds to the
+	audio device's sample rate ahead of time avoids that.
+
+	Positions in the settings, the slice, and the metadata that are
+	measured in samples are moved to match the new sample rate.
+
+	# Panics
+
+	Panics if either this sound's sample rate or `sample_rate` is `0`.
+
+	# Examples
+
+	```
+	use kira::{
+		sound::static_sound::{ResampleQuality, StaticSoundData, StaticSoundSettings},
+		Frame,
+	};
+	let sound = StaticSoundData {
+		sample_rate: 44_100,
+		frames: (0..44_100).map(|_| Frame::from_mono(0.5)).collect(),
+		settings: StaticSoundSettings::default(),
+		slice: None,
+		metadata: None,
+	};
+	let resampled = sound.resampled(48_000, ResampleQuality::Sinc);
+	assert_eq!(resampled.sample_rate, 48_000);
+	assert_eq!(resampled.num_frames(), 48_000);
+	```
+	*/
+	#[must_use]
+	pub fn resampled(&self, sample_rate: u32, quality: ResampleQuality) -> Self {
+		assert!(
+			self.sample_rate != 0 && sample_rate != 0,
+			"sample rates must be greater than 0"
+		);
+		if self.sample_rate == sample_rate {
+			return self.clone();
+		}
+		let rescale = |position| rescale_position(position, self.sample_rate, sample_rate);
+		let rescale_region = |region: Region| Region {
+			start: rescale(region.start),
+			end: match region.end {
+				EndPosition::Custom(position) => EndPosition::Custom(rescale(position)),
+				end => end,
+			},
+		};
+		let mut settings = self.settings;
+		settings.start_position = rescale(settings.start_position);
+		settings.loop_region = settings.loop_region.map(rescale_region);
+		let rescale_index = |index: usize| {
+			(index as f64 * sample_rate as f64 / self.sample_rate as f64).round() as usize
+		};
+		let frames = resample_frames(&self.frames, self.sample_rate, sample_rate, quality);
+		Self {
+			sample_rate,
+			slice: self.slice.map(|(start, end)| {
+				let end = rescale_index(end).min(frames.len());
+				(rescale_index(start).min(end), end)
+			}),
+			frames: frames.into(),
+			settings,
+			metadata: self.metadata.as_ref().map(|metadata| {
+				Arc::new(Metadata {
+					loop_region: metadata.loop_region.map(rescale_region),
+					..(**metadata).clone()
+				})
+			}),
+		}
+	}
+}
+
+/// Converts frames of audio from one sample rate to another. Both sample
+/// rates must be greater than `0`.
+#[must_use]
+pub(super) fn resample_frames(
+	frames: &[Frame],
+	from_sample_rate: u32,
+	to_sample_rate: u32,
+	quality: ResampleQuality,
+) -> Vec<Frame> {
+	if from_sample_rate == to_sample_rate {
+		return frames.to_vec();
+	}
+	// how many input frames each output frame advances by
+	let ratio = from_sample_rate as f64 / to_sample_rate as f64;
+	let num_frames = (frames.len() as f64 / ratio).round() as usize;
+	let frame_at = |index: isize| -> Frame {
+		usize::try_from(index)
+			.ok()
+			.and_then(|index| frames.get(index))
+			.copied()
+			.unwrap_or_default()
+	};
+	let positions = (0..num_frames).map(|i| i as f64 * ratio);
+	match quality {
+		ResampleQuality::Linear => positions
+			.map(|position| {
+				let index = position as isize;
+				let fraction = position.fract() as f32;
+				frame_at(index) * (1.0 - fraction) + frame_at(index + 1) * fraction
+			})
+			.collect(),
+		ResampleQuality::Cubic => positions
+			.map(|position| {
+				let index = position as isize;
+				interpolate_frame(
+					frame_at(index - 1),
+					frame_at(index),
+					frame_at(index + 1),
+					frame_at(index + 2),
+					position.fract() as f32,
+				)
+			})
+			.collect(),
+		ResampleQuality::Sinc => {
+			// when downsampling, the cutoff frequency is lowered to the new
+			// nyquist frequency, which widens the filter
+			let cutoff = ratio.recip().min(1.0);
+			let half_width = SINC_ZERO_CROSSINGS as f64 / cutoff;
+			positions
+				.map(|position| {
+					let first = (position - half_width).ceil() as isize;
+					let last = (position + half_width).floor() as isize;
+					let mut sum = Frame::ZERO;
+					let mut total_weight = 0.0;
+					for index in first..=last {
+						let distance = position - index as f64;
+						let weight =
+							cutoff * sinc(cutoff * distance) * blackman(distance / half_width);
+						sum += frame_at(index) * weight as f32;
+						total_weight += weight;
+					}
+					// keeps the volume of constant signals the same
+					if total_weight.abs() > f64::EPSILON {
+						sum / total_weight as f32
+					} else {
+						sum
+					}
+				})
+				.collect()
+		}
+	}
+}
+
+#[must_use]
+fn rescale_position(
+	position: PlaybackPosition,
+	from_sample_rate: u32,
+	to_sample_rate: u32,
+) -> PlaybackPosition {
+	match position {
+		PlaybackPosition::Samples(samples) => PlaybackPosition::Samples(
+			(samples as f64 * to_sample_rate as f64 / from_sample_rate as f64).round() as usize,
+		),
+		seconds => seconds,
+	}
+}
+
+/// The normalized sinc function, `sin(πx) / (πx)`.
+#[must_use]
+fn sinc(x: f64) -> f64 {
+	if x.abs() < f64::EPSILON {
+		1.0
+	} else {
+		(PI * x).sin() / (PI * x)
+	}
+}
+
+/// The Blackman window, centered on `0` and reaching `0` at `-1` and `1`.
+#[must_use]
+fn blackman(x: f64) -> f64 {
+	if x.abs() >= 1.0 {
+		return 0.0;
+	}
+	0.42 + 0.5 * (PI * x).cos() + 0.08 * (2.0 * PI * x).cos()
+}
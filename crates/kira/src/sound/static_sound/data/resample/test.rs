@@ -0,0 +1,116 @@
+use std::f64::consts::TAU;
+
+use crate::{
+	frame::Frame,
+	sound::{
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		EndPosition, PlaybackPosition, Region,
+	},
+};
+
+use super::{resample_frames, ResampleQuality};
+
+const QUALITIES: [ResampleQuality; 3] = [
+	ResampleQuality::Linear,
+	ResampleQuality::Cubic,
+	ResampleQuality::Sinc,
+];
+
+fn sine(frequency: f64, sample_rate: u32, num_frames: usize) -> Vec<Frame> {
+	(0..num_frames)
+		.map(|i| Frame::from_mono((i as f64 / sample_rate as f64 * frequency * TAU).sin() as f32))
+		.collect()
+}
+
+/// Tests that resampling changes the number of frames to match the new
+/// sample rate and keeps the volume of constant audio the same.
+#[test]
+fn keeps_constant_audio() {
+	let frames = vec![Frame::from_mono(0.5); 1000];
+	for quality in QUALITIES {
+		for to_sample_rate in [441, 480, 220] {
+			let resampled = resample_frames(&frames, 441, to_sample_rate, quality);
+			let expected_len = (1000.0 * to_sample_rate as f64 / 441.0).round() as usize;
+			assert_eq!(resampled.len(), expected_len);
+			// the edges fade in and out since there's silence around the audio
+			for frame in &resampled[100..expected_len - 100] {
+				assert!((frame.left - 0.5).abs() < 1.0e-4, "{:?}", quality);
+				assert_eq!(frame.left, frame.right);
+			}
+		}
+	}
+}
+
+/// Tests that a sinc resample reproduces a sine wave at the new sample
+/// rate.
+#[test]
+fn sinc_reproduces_sine() {
+	let resampled = resample_frames(
+		&sine(1000.0, 44_100, 4410),
+		44_100,
+		48_000,
+		ResampleQuality::Sinc,
+	);
+	let expected = sine(1000.0, 48_000, 4800);
+	for (frame, expected) in resampled.iter().zip(&expected).skip(200).take(4400) {
+		assert!((frame.left - expected.left).abs() < 1.0e-3);
+	}
+}
+
+/// Tests that a sinc resample to a lower sample rate removes frequencies
+/// that are too high to be represented at the new sample rate.
+#[test]
+fn sinc_removes_aliasing() {
+	let resampled = resample_frames(
+		&sine(20_000.0, 48_000, 4800),
+		48_000,
+		22_050,
+		ResampleQuality::Sinc,
+	);
+	for frame in &resampled[200..2000] {
+		assert!(frame.left.abs() < 0.01);
+	}
+}
+
+/// Tests that positions measured in samples are moved to match the new
+/// sample rate.
+#[test]
+fn rescales_sample_positions() {
+	let data = StaticSoundData {
+		sample_rate: 100,
+		frames: vec![Frame::from_mono(0.5); 100].into(),
+		settings: StaticSoundSettings::default(),
+		slice: Some((10, 90)),
+		metadata: None,
+	}
+	.start_position(PlaybackPosition::Samples(20))
+	.loop_region(Region {
+		start: PlaybackPosition::Samples(30),
+		end: EndPosition::Custom(PlaybackPosition::Seconds(0.5)),
+	});
+	let resampled = data.resampled(200, ResampleQuality::Linear);
+	assert_eq!(resampled.sample_rate, 200);
+	assert_eq!(resampled.frames.len(), 200);
+	assert_eq!(resampled.slice, Some((20, 180)));
+	assert_eq!(
+		resampled.settings.start_position,
+		PlaybackPosition::Samples(40)
+	);
+	assert_eq!(
+		resampled.settings.loop_region,
+		Some(Region {
+			start: PlaybackPosition::Samples(60),
+			end: EndPosition::Custom(PlaybackPosition::Seconds(0.5)),
+		})
+	);
+}
+
+/// Tests that resampling to the same sample rate doesn't change the
+/// audio.
+#[test]
+fn same_sample_rate() {
+	let frames = sine(3.0, 100, 100);
+	for quality in QUALITIES {
+		assert_eq!(resample_frames(&frames, 100, 100, quality), frames);
+	}
+}
@@ -1,8 +1,18 @@
 use std::{sync::Arc, time::Duration};
 
-use crate::frame::Frame;
+use crate::{
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::{static_sound::StaticSoundSettings, streaming::Decoder, Sound, SoundData},
+	Decibels, Panning,
+};
 
-use super::StaticSoundData;
+#[cfg(feature = "wav")]
+use crate::test_helpers::wav_fixture;
+
+use super::{CombineError, SettingsError, StaticSoundData};
+#[cfg(feature = "symphonia")]
+use crate::sound::DecodeOptions;
 
 #[test]
 fn duration() {
@@ -11,6 +21,7 @@ fn duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	assert_eq!(static_sound.duration(), Duration::from_secs(4));
 }
@@ -22,6 +33,7 @@ fn unsliced_duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: Some((2, 3)),
+		metadata: None,
 	};
 	assert_eq!(static_sound.unsliced_duration(), Duration::from_secs(4));
 }
@@ -33,6 +45,7 @@ fn sliced_duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	assert_eq!(static_sound.duration(), Duration::from_secs(4));
 
@@ -41,6 +54,7 @@ fn sliced_duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: Some((2, 3)),
+		metadata: None,
 	};
 	assert_eq!(static_sound.duration(), Duration::from_secs(1));
 }
@@ -52,6 +66,7 @@ fn slice() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	}
 	.slice(3.0..6.0);
 	for i in 0..3 {
@@ -62,3 +77,704 @@ fn slice() {
 	}
 	assert!(static_sound.frame_at_index(3).is_none());
 }
+
+#[test]
+fn slice_past_end_clamps() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	}
+	.slice(7.0..20.0);
+	assert_eq!(static_sound.slice, Some((7, 10)));
+	assert_eq!(static_sound.num_frames(), 3);
+}
+
+#[test]
+fn empty_slice() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(static_sound.slice(5.0..2.0).num_frames(), 0);
+	assert_eq!(static_sound.slice(15.0..).num_frames(), 0);
+	assert_eq!(static_sound.slice(4.0..4.0).duration(), Duration::ZERO);
+}
+
+#[test]
+fn slice_of_slice() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	}
+	.slice(2.0..8.0)
+	.slice(1.0..10.0);
+	assert_eq!(static_sound.slice, Some((3, 8)));
+	for i in 0..5 {
+		assert_eq!(
+			static_sound.frame_at_index(i),
+			Some(Frame::from_mono(i as f32 + 3.0))
+		);
+	}
+	assert!(static_sound.frame_at_index(5).is_none());
+}
+
+/// Tests that rendering a `StaticSoundData` produces the same frames
+/// as playing it back live.
+#[test]
+fn render_matches_playback() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.loop_region(3.0..6.0)
+			.loop_count(2)
+			.playback_rate(0.5),
+		slice: None,
+		metadata: None,
+	};
+	let rendered = data.render(1, 100);
+
+	let (mut sound, _) = data.split();
+	let mut expected = vec![];
+	while !sound.finished() {
+		expected.push(sound.process_one(1.0, &MockInfoBuilder::new().build()));
+	}
+	assert_eq!(rendered.len(), expected.len());
+	for (rendered, expected) in rendered.iter().zip(&expected) {
+		assert!((rendered.left - expected.left).abs() < 0.0001);
+		assert!((rendered.right - expected.right).abs() < 0.0001);
+	}
+}
+
+/// Tests that rendering a `StaticSoundData` with a loop region
+/// and no loop count only plays the loop once.
+#[test]
+fn render_infinite_loop_plays_once() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().loop_region(3.0..6.0),
+		slice: None,
+		metadata: None,
+	};
+	let rendered = data.render(1, 100);
+	for (i, frame) in rendered.iter().take(10).enumerate() {
+		assert_eq!(*frame, Frame::from_mono(i as f32).panned(Panning::CENTER));
+	}
+	assert!(rendered[10..].iter().all(|frame| *frame == Frame::ZERO));
+}
+
+/// Tests that rendering a `StaticSoundData` that never finishes
+/// stops after the maximum number of frames.
+#[test]
+fn render_stops_at_max_frames() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().playback_rate(0.0),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(data.render(1, 1000).len(), 1000);
+}
+
+/// Tests that two `StaticSoundData`s can be joined together.
+#[test]
+fn concat() {
+	let a = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().loop_region(2.0..4.0),
+		slice: None,
+		metadata: None,
+	}
+	.slice(5.0..);
+	let b = StaticSoundData {
+		sample_rate: 1,
+		frames: (10..13).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let sound = a.concat(&b).unwrap();
+	assert_eq!(sound.slice, None);
+	assert_eq!(sound.settings.loop_region, None);
+	assert_eq!(sound.num_frames(), 8);
+	for (i, value) in [5, 6, 7, 8, 9, 10, 11, 12].into_iter().enumerate() {
+		assert_eq!(
+			sound.frame_at_index(i),
+			Some(Frame::from_mono(value as f32))
+		);
+	}
+}
+
+/// Tests that the second operand of `StaticSoundData::concat` is
+/// resampled to match the first.
+#[test]
+fn concat_resamples() {
+	let a = StaticSoundData {
+		sample_rate: 2,
+		frames: Arc::new([Frame::from_mono(0.0); 4]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let b = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 5]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let sound = a.concat(&b).unwrap();
+	assert_eq!(sound.sample_rate, 2);
+	assert_eq!(sound.num_frames(), 14);
+	assert_eq!(sound.duration(), Duration::from_secs(7));
+	// away from the edges, the constant signal stays constant
+	for i in 6..=9 {
+		assert_eq!(sound.frame_at_index(i), Some(Frame::from_mono(1.0)));
+	}
+}
+
+/// Tests that two `StaticSoundData`s can be mixed together.
+#[test]
+fn mix() {
+	let a = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..3).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let b = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(10.0); 5]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let sound = a.mix(&b).unwrap();
+	assert_eq!(sound.num_frames(), 5);
+	for (i, value) in [10, 11, 12, 10, 10].into_iter().enumerate() {
+		assert_eq!(
+			sound.frame_at_index(i),
+			Some(Frame::from_mono(value as f32))
+		);
+	}
+}
+
+/// Tests that sounds with a sample rate of 0 Hz can't be combined.
+#[test]
+fn combine_zero_sample_rate() {
+	let a = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(0.0); 3]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let b = StaticSoundData {
+		sample_rate: 0,
+		frames: Arc::new([Frame::from_mono(0.0); 3]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(a.concat(&b), Err(CombineError::ZeroSampleRate));
+	assert_eq!(b.mix(&a), Err(CombineError::ZeroSampleRate));
+}
+
+/// Tests that the peak and RMS volume of a `StaticSoundData` are
+/// measured correctly, skipping non-finite samples.
+#[test]
+fn peak_and_rms() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([
+			Frame::new(0.5, -0.25),
+			Frame::new(f32::NAN, 0.25),
+			Frame::new(-0.5, f32::INFINITY),
+			Frame::new(0.5, -1.0),
+		]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(static_sound.peak(), Decibels::IDENTITY);
+	assert_eq!(static_sound.loudest_frame_index(), Some(3));
+	// left: sqrt((0.25 * 3) / 3) = 0.5, right: sqrt((0.0625 * 2 + 1.0) / 3) ~= 0.6124
+	assert!((static_sound.rms().0 - Decibels::from_amplitude(0.612_372_4).0).abs() < 0.001);
+}
+
+/// Tests that `StaticSoundData` analysis only looks at the current slice.
+#[test]
+fn analysis_respects_slice() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..5).map(|i| Frame::from_mono(i as f32 / 10.0)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	}
+	.slice(1.0..3.0);
+	assert!((static_sound.peak().0 - Decibels::from_amplitude(0.2).0).abs() < 0.0001);
+	assert_eq!(static_sound.loudest_frame_index(), Some(1));
+}
+
+/// Tests that an empty or silent `StaticSoundData` has no loudest frame.
+#[test]
+fn analysis_of_silence() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::ZERO; 4]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(static_sound.peak(), Decibels(f32::NEG_INFINITY));
+	assert_eq!(static_sound.rms(), Decibels(f32::NEG_INFINITY));
+	assert_eq!(static_sound.loudest_frame_index(), None);
+	assert_eq!(static_sound.slice(0.0..0.0).loudest_frame_index(), None);
+}
+
+/// Tests that the windowed RMS volume of a `StaticSoundData` is
+/// measured for each window.
+#[test]
+fn windowed_rms() {
+	let static_sound = StaticSoundData {
+		sample_rate: 2,
+		frames: Arc::new([
+			Frame::from_mono(1.0),
+			Frame::from_mono(1.0),
+			Frame::from_mono(0.5),
+			Frame::from_mono(-0.5),
+			Frame::ZERO,
+		]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(
+		static_sound.windowed_rms(Duration::from_secs(1)),
+		vec![
+			Decibels::IDENTITY,
+			Decibels::from_amplitude(0.5),
+			Decibels(f32::NEG_INFINITY)
+		]
+	);
+}
+
+/// Tests that `StaticSoundData::normalized` applies gain to reach
+/// the target peak volume.
+#[test]
+fn normalized() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(0.25), Frame::from_mono(-0.5)]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let normalized = static_sound.normalized(Decibels::IDENTITY);
+	assert!((normalized.frames[0].left - 0.5).abs() < 0.0001);
+	assert!((normalized.frames[1].left + 1.0).abs() < 0.0001);
+	// the original frames are left untouched
+	assert_eq!(static_sound.frames[0], Frame::from_mono(0.25));
+}
+
+/// Tests that `StaticSoundData::normalized` doesn't apply more than
+/// the maximum gain.
+#[test]
+fn normalized_limits_gain() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(0.0001), Frame::ZERO]),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	let normalized = static_sound.normalized(Decibels::IDENTITY);
+	assert!((normalized.frames[0].left - 0.01).abs() < 0.0001);
+	assert_eq!(normalized.frames[1], Frame::ZERO);
+}
+
+/// Tests that generated waveforms have the expected shape.
+#[cfg(feature = "waveforms")]
+#[test]
+fn waveforms() {
+	let settings = StaticSoundSettings::default();
+	let duration = Duration::from_secs(1);
+	let sine = StaticSoundData::sine(1.0, duration, 4, settings);
+	let square = StaticSoundData::square(1.0, duration, 4, settings);
+	let triangle = StaticSoundData::triangle(1.0, duration, 4, settings);
+	let sawtooth = StaticSoundData::sawtooth(1.0, duration, 4, settings);
+	for (sound, expected) in [
+		(sine, [0.0, 1.0, 0.0, -1.0]),
+		(square, [1.0, 1.0, -1.0, -1.0]),
+		(triangle, [0.0, 1.0, 0.0, -1.0]),
+		(sawtooth, [0.0, 0.5, -1.0, -0.5]),
+	] {
+		assert_eq!(sound.sample_rate, 4);
+		assert_eq!(sound.num_frames(), 4);
+		for (frame, expected) in sound.frames.iter().zip(expected) {
+			assert!((frame.left - expected).abs() < 0.0001);
+			assert_eq!(frame.left, frame.right);
+		}
+	}
+}
+
+/// Tests that white noise is reproducible given the same seed.
+#[cfg(feature = "waveforms")]
+#[test]
+fn white_noise() {
+	let duration = Duration::from_secs(1);
+	let settings = StaticSoundSettings::default();
+	let a = StaticSoundData::white_noise(duration, 100, 1, settings);
+	let b = StaticSoundData::white_noise(duration, 100, 1, settings);
+	let c = StaticSoundData::white_noise(duration, 100, 2, settings);
+	assert_eq!(a.num_frames(), 100);
+	assert_eq!(a.frames, b.frames);
+	assert_ne!(a.frames, c.frames);
+	assert!(a
+		.frames
+		.iter()
+		.all(|frame| (-1.0..=1.0).contains(&frame.left) && frame.left == frame.right));
+}
+
+/// Tests that the frames of a `StaticSoundData` can be read,
+/// respecting its slice.
+#[test]
+fn frames() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(static_sound.frames(), &*static_sound.frames);
+	let sliced = static_sound.slice(3.0..6.0);
+	assert_eq!(
+		sliced.frames(),
+		&[
+			Frame::from_mono(3.0),
+			Frame::from_mono(4.0),
+			Frame::from_mono(5.0)
+		]
+	);
+	assert_eq!(
+		sliced.frames_in_region(1.0..),
+		&[Frame::from_mono(4.0), Frame::from_mono(5.0)]
+	);
+	assert_eq!(
+		sliced.frames_in_region(2.0..100.0),
+		&[Frame::from_mono(5.0)]
+	);
+	assert!(sliced.frames_in_region(5.0..).is_empty());
+}
+
+/// Tests that `StaticSoundData::frame_at_position` converts positions
+/// to frames.
+#[test]
+fn frame_at_position() {
+	let static_sound = StaticSoundData {
+		sample_rate: 10,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	};
+	assert_eq!(
+		static_sound.frame_at_position(0.0),
+		Some(Frame::from_mono(0.0))
+	);
+	assert_eq!(
+		static_sound.frame_at_position(0.3),
+		Some(Frame::from_mono(3.0))
+	);
+	assert_eq!(
+		static_sound.frame_at_position(0.7),
+		Some(Frame::from_mono(7.0))
+	);
+	assert_eq!(static_sound.frame_at_position(1.0), None);
+	assert_eq!(static_sound.frame_at_position(-0.1), None);
+	assert_eq!(static_sound.frame_at_position(f64::NAN), None);
+	assert_eq!(
+		static_sound.slice(0.5..).frame_at_position(0.2),
+		Some(Frame::from_mono(7.0))
+	);
+}
+
+#[test]
+fn validate() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(0.0); 4]),
+		settings: Default::default(),
+		slice: Some((1, 4)),
+		metadata: None,
+	};
+	assert_eq!(data.validate(), Ok(()));
+	assert_eq!(data.loop_region(1.0..3.0).validate(), Ok(()));
+	assert_eq!(
+		data.start_position(3.0).validate(),
+		Err(SettingsError::InvalidStartPosition)
+	);
+	assert_eq!(
+		data.start_position(-1.0).validate(),
+		Err(SettingsError::InvalidStartPosition)
+	);
+	assert_eq!(
+		data.loop_region(2.0..2.0).validate(),
+		Err(SettingsError::EmptyLoopRegion)
+	);
+	assert_eq!(
+		data.loop_region(2.0..1.0).validate(),
+		Err(SettingsError::EmptyLoopRegion)
+	);
+	assert_eq!(
+		data.loop_region(-1.0..2.0).validate(),
+		Err(SettingsError::InvalidLoopRegionPosition)
+	);
+	assert_eq!(
+		data.loop_region(1.0..f64::NAN).validate(),
+		Err(SettingsError::InvalidLoopRegionPosition)
+	);
+	// loop regions are measured from the start of the slice
+	assert_eq!(
+		data.loop_region(1.0..4.0).validate(),
+		Err(SettingsError::LoopRegionOutsidePlaybackRegion)
+	);
+	assert_eq!(
+		data.loop_region(3.0..).validate(),
+		Err(SettingsError::LoopRegionOutsidePlaybackRegion)
+	);
+
+	assert!(data.start_position(3.0).into_sound().is_err());
+	assert!(data.start_position(3.0).lenient(true).into_sound().is_ok());
+}
+
+/// Tests that decoding a file whose packets can be decoded
+/// independently on multiple threads gives the same audio as decoding
+/// it on one thread.
+#[cfg(feature = "wav")]
+#[test]
+fn decodes_independent_packets_in_parallel() {
+	let samples = (0..10_000)
+		.map(|i| (i * 7 % 2000) as i16 - 1000)
+		.collect::<Vec<_>>();
+	let decode = |threads| {
+		StaticSoundData::from_boxed_media_source_with(
+			Box::new(std::io::Cursor::new(wav_fixture(100, &samples))),
+			DecodeOptions::new().threads(threads),
+		)
+		.unwrap()
+	};
+	let expected = decode(1);
+	assert_eq!(expected.frames.len(), samples.len());
+	for threads in [2, 3, 16] {
+		assert_eq!(decode(threads).frames, expected.frames);
+	}
+}
+
+/// Tests that decoding a file whose packets depend on each other on
+/// multiple threads gives the same audio as decoding it on one thread.
+#[cfg(feature = "ogg")]
+#[test]
+fn decodes_dependent_packets_while_reading() {
+	let file = std::fs::read(concat!(
+		env!("CARGO_MANIFEST_DIR"),
+		"/../examples/assets/blip.ogg"
+	))
+	.unwrap();
+	let decode = |threads| {
+		StaticSoundData::from_boxed_media_source_with(
+			Box::new(std::io::Cursor::new(file.clone())),
+			DecodeOptions::new().threads(threads),
+		)
+		.unwrap()
+	};
+	let expected = decode(1);
+	assert!(!expected.frames.is_empty());
+	assert_eq!(decode(4).frames, expected.frames);
+}
+
+/// Tests that loading a sound asynchronously gives the same audio as
+/// loading it synchronously.
+#[cfg(all(feature = "async", feature = "wav"))]
+#[test]
+fn from_cursor_async() {
+	let wav = wav_fixture(100, &[0, 1000, -1000, 2000]);
+	let expected = StaticSoundData::from_cursor(std::io::Cursor::new(wav.clone())).unwrap();
+	let data = crate::test_helpers::block_on(StaticSoundData::from_cursor_async(
+		std::io::Cursor::new(wav),
+	))
+	.unwrap();
+	assert_eq!(data.sample_rate, expected.sample_rate);
+	assert_eq!(data.frames, expected.frames);
+}
+
+/// Tests that a sound can be loaded from a shared buffer.
+#[cfg(feature = "wav")]
+#[test]
+fn from_bytes() {
+	let wav: Arc<[u8]> = wav_fixture(100, &[0, 1000, -1000, 2000]).into();
+	let expected = StaticSoundData::from_cursor(std::io::Cursor::new(wav.to_vec())).unwrap();
+	let data = StaticSoundData::from_bytes(wav.clone()).unwrap();
+	assert_eq!(data.frames, expected.frames);
+	// the buffer isn't needed after the audio is decoded
+	assert_eq!(Arc::strong_count(&wav), 1);
+}
+
+/// Tests that loading a sound asynchronously reports errors.
+#[cfg(all(feature = "async", feature = "symphonia"))]
+#[test]
+fn from_file_async_reports_errors() {
+	let result = crate::test_helpers::block_on(StaticSoundData::from_file_async(
+		"this file does not exist.wav",
+	));
+	assert!(matches!(
+		result,
+		Err(crate::sound::FromFileError::IoError(_))
+	));
+}
+
+/// Tests that loading a sound keeps the metadata stored in the file.
+#[cfg(feature = "wav")]
+#[test]
+fn from_cursor_reads_metadata() {
+	let wav = crate::test_helpers::with_wav_tags(
+		wav_fixture(100, &[0; 4]),
+		&[(b"INAM", "Blip"), (b"IART", "Kira"), (b"IPRT", "3")],
+	);
+	let data = StaticSoundData::from_cursor(std::io::Cursor::new(wav)).unwrap();
+	let metadata = data.metadata().unwrap();
+	assert_eq!(metadata.title.as_deref(), Some("Blip"));
+	assert_eq!(metadata.artist.as_deref(), Some("Kira"));
+	assert_eq!(metadata.track_number, Some(3));
+
+	let data =
+		StaticSoundData::from_cursor(std::io::Cursor::new(wav_fixture(100, &[0; 4]))).unwrap();
+	assert!(data.metadata().is_none());
+}
+
+/// Tests that the loop points in a WAV file's `smpl` chunk are looped
+/// exactly when no other loop region is set.
+#[cfg(feature = "wav")]
+#[test]
+fn loops_detected_loop_region() {
+	use crate::sound::{EndPosition, PlaybackPosition, Region};
+
+	let samples = (0..8).map(|i| i * 1000).collect::<Vec<_>>();
+	let wav = crate::test_helpers::with_wav_loop(wav_fixture(1, &samples), 2, 4);
+	let data = StaticSoundData::from_cursor(std::io::Cursor::new(wav)).unwrap();
+	assert_eq!(
+		data.detected_loop_region(),
+		Some(Region {
+			start: PlaybackPosition::Samples(2),
+			end: EndPosition::Custom(PlaybackPosition::Samples(5)),
+		})
+	);
+
+	let play = |data: StaticSoundData, num_frames: usize| {
+		let (mut sound, _) = data.split();
+		(0..num_frames)
+			.map(|_| sound.process_one(1.0, &MockInfoBuilder::new().build()))
+			.collect::<Vec<_>>()
+	};
+	let expected = |indices: &[usize]| {
+		indices
+			.iter()
+			.map(|&i| data.frames[i].panned(Panning::CENTER))
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(
+		play(data.clone(), 9),
+		expected(&[0, 1, 2, 3, 4, 2, 3, 4, 2])
+	);
+	assert_eq!(
+		play(data.use_detected_loop_region(false), 8),
+		expected(&[0, 1, 2, 3, 4, 5, 6, 7])
+	);
+	assert_eq!(
+		play(
+			data.loop_region(PlaybackPosition::Samples(1)..PlaybackPosition::Samples(3)),
+			5
+		),
+		expected(&[0, 1, 2, 1, 2])
+	);
+}
+
+/// Tests that all of the audio from a decoder is decoded into a
+/// `StaticSoundData`.
+#[test]
+fn from_decoder() {
+	struct ChunkedDecoder {
+		frames: Vec<Frame>,
+		num_frames: usize,
+		position: usize,
+	}
+
+	impl Decoder for ChunkedDecoder {
+		type Error = ();
+
+		fn sample_rate(&self) -> u32 {
+			100
+		}
+
+		fn num_frames(&self) -> usize {
+			self.num_frames
+		}
+
+		fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+			let end = (self.position + 3).min(self.frames.len());
+			let chunk = self.frames[self.position..end].to_vec();
+			self.position = end;
+			Ok(chunk)
+		}
+
+		fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+			self.position = index;
+			Ok(index)
+		}
+	}
+
+	let frames = (0..10)
+		.map(|i| Frame::from_mono(i as f32))
+		.collect::<Vec<_>>();
+	let data = StaticSoundData::from_decoder(ChunkedDecoder {
+		frames: frames.clone(),
+		num_frames: 10,
+		position: 0,
+	})
+	.unwrap();
+	assert_eq!(data.sample_rate, 100);
+	assert_eq!(&*data.frames, &frames[..]);
+
+	// audio past the end reported by the decoder is left out
+	let data = StaticSoundData::from_decoder(ChunkedDecoder {
+		frames: frames.clone(),
+		num_frames: 5,
+		position: 0,
+	})
+	.unwrap();
+	assert_eq!(&*data.frames, &frames[..5]);
+
+	// decoding stops when the decoder runs out of audio
+	let data = StaticSoundData::from_decoder(ChunkedDecoder {
+		frames: frames.clone(),
+		num_frames: usize::MAX,
+		position: 0,
+	})
+	.unwrap();
+	assert_eq!(&*data.frames, &frames[..]);
+}
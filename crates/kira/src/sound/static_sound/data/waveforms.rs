@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use crate::{
+	frame::Frame, modulator::lfo::Waveform, random::Random,
+	sound::static_sound::StaticSoundSettings,
+};
+
+use super::StaticSoundData;
+
+impl StaticSoundData {
+	/**
+	Generates a sine wave at the given frequency (in Hz).
+
+	# Example
+
+	```
+	use std::time::Duration;
+
+	use kira::sound::static_sound::{StaticSoundData, StaticSoundSettings};
+
+	let sound = StaticSoundData::sine(
+		440.0,
+		Duration::from_secs(1),
+		48_000,
+		StaticSoundSettings::default(),
+	);
+	assert_eq!(sound.num_frames(), 48_000);
+	```
+	*/
+	#[cfg_attr(docsrs, doc(cfg(feature = "waveforms")))]
+	#[must_use]
+	pub fn sine(
+		frequency: f64,
+		duration: Duration,
+		sample_rate: u32,
+		settings: StaticSoundSettings,
+	) -> Self {
+		Self::from_waveform(Waveform::Sine, frequency, duration, sample_rate, settings)
+	}
+
+	/// Generates a square wave at the given frequency (in Hz).
+	#[cfg_attr(docsrs, doc(cfg(feature = "waveforms")))]
+	#[must_use]
+	pub fn square(
+		frequency: f64,
+		duration: Duration,
+		sample_rate: u32,
+		settings: StaticSoundSettings,
+	) -> Self {
+		Self::from_waveform(
+			Waveform::Pulse { width: 0.5 },
+			frequency,
+			duration,
+			sample_rate,
+			settings,
+		)
+	}
+
+	/// Generates a triangle wave at the given frequency (in Hz).
+	#[cfg_attr(docsrs, doc(cfg(feature = "waveforms")))]
+	#[must_use]
+	pub fn triangle(
+		frequency: f64,
+		duration: Duration,
+		sample_rate: u32,
+		settings: StaticSoundSettings,
+	) -> Self {
+		Self::from_waveform(
+			Waveform::Triangle,
+			frequency,
+			duration,
+			sample_rate,
+			settings,
+		)
+	}
+
+	/// Generates a sawtooth wave at the given frequency (in Hz).
+	#[cfg_attr(docsrs, doc(cfg(feature = "waveforms")))]
+	#[must_use]
+	pub fn sawtooth(
+		frequency: f64,
+		duration: Duration,
+		sample_rate: u32,
+		settings: StaticSoundSettings,
+	) -> Self {
+		Self::from_waveform(Waveform::Saw, frequency, duration, sample_rate, settings)
+	}
+
+	/// Generates white noise.
+	///
+	/// The same `seed` always produces the same audio.
+	#[cfg_attr(docsrs, doc(cfg(feature = "waveforms")))]
+	#[must_use]
+	pub fn white_noise(
+		duration: Duration,
+		sample_rate: u32,
+		seed: u64,
+		settings: StaticSoundSettings,
+	) -> Self {
+		let mut random = Random::new(seed);
+		Self {
+			sample_rate,
+			frames: (0..num_frames(duration, sample_rate))
+				.map(|_| Frame::from_mono(random.next_bipolar() as f32))
+				.collect(),
+			settings,
+			slice: None,
+			metadata: None,
+		}
+	}
+
+	#[must_use]
+	fn from_waveform(
+		waveform: Waveform,
+		frequency: f64,
+		duration: Duration,
+		sample_rate: u32,
+		settings: StaticSoundSettings,
+	) -> Self {
+		Self {
+			sample_rate,
+			frames: (0..num_frames(duration, sample_rate))
+				.map(|i| {
+					let phase = (i as f64 * frequency / sample_rate as f64).fract();
+					Frame::from_mono(waveform.value(phase) as f32)
+				})
+				.collect(),
+			settings,
+			slice: None,
+			metadata: None,
+		}
+	}
+}
+
+#[must_use]
+fn num_frames(duration: Duration, sample_rate: u32) -> usize {
+	(duration.as_secs_f64() * sample_rate as f64).round() as usize
+}
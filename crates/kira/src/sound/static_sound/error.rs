@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+/// Errors that can occur when combining two [`StaticSoundData`](super::StaticSoundData)s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CombineError {
+	/// One of the sounds has a sample rate of 0 Hz, so it can't
+	/// be resampled to match the other sound.
+	ZeroSampleRate,
+}
+
+impl Display for CombineError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			CombineError::ZeroSampleRate => {
+				f.write_str("Cannot resample a sound with a sample rate of 0 Hz")
+			}
+		}
+	}
+}
+
+impl std::error::Error for CombineError {}
+
+/// Errors that can occur when playing a [`StaticSoundData`](super::StaticSoundData)
+/// with invalid settings.
+///
+/// These checks can be skipped using
+/// [`StaticSoundSettings::lenient`](super::StaticSoundSettings::lenient).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingsError {
+	/// The start position is negative or past the end of the sound.
+	InvalidStartPosition,
+	/// The loop region starts or ends at a negative or NaN position.
+	InvalidLoopRegionPosition,
+	/// The loop region is empty or ends before it starts.
+	EmptyLoopRegion,
+	/// The loop region starts or ends past the end of the portion of the
+	/// audio that plays.
+	LoopRegionOutsidePlaybackRegion,
+}
+
+impl Display for SettingsError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SettingsError::InvalidStartPosition => {
+				f.write_str("The start position is negative or past the end of the sound")
+			}
+			SettingsError::InvalidLoopRegionPosition => {
+				f.write_str("The loop region starts or ends at a negative or NaN position")
+			}
+			SettingsError::EmptyLoopRegion => {
+				f.write_str("The loop region is empty or ends before it starts")
+			}
+			SettingsError::LoopRegionOutsidePlaybackRegion => {
+				f.write_str("The loop region extends past the end of the sound")
+			}
+		}
+	}
+}
+
+impl std::error::Error for SettingsError {}
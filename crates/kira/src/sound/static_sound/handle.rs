@@ -1,17 +1,25 @@
-use std::sync::Arc;
+use std::{
+	sync::{Arc, Mutex},
+	time::Duration,
+};
 
 use crate::{
 	command::handle_param_setters,
-	sound::{IntoOptionalRegion, PlaybackState},
-	Decibels, Panning, PlaybackRate, StartTime, Tween,
+	sound::{FinishedReceiver, IntoOptionalRegion, PlaybackState, SoundSendLevelWriters},
+	track::{NonexistentRoute, SendTrackId, TrackId},
+	Decibels, Panning, PlaybackRate, StartTime, Tween, Value,
 };
 
-use super::{sound::Shared, CommandWriters};
+use super::{
+	queue::SegmentQueueWriter, sound::Shared, CommandWriters, PlaybackStats, StaticSoundData,
+};
 
 /// Controls a static sound.
 #[derive(Debug)]
 pub struct StaticSoundHandle {
 	pub(super) command_writers: CommandWriters,
+	pub(super) send_level_command_writers: SoundSendLevelWriters,
+	pub(super) segment_queue: Mutex<SegmentQueueWriter>,
 	pub(super) shared: Arc<Shared>,
 }
 
@@ -22,12 +30,105 @@ impl StaticSoundHandle {
 		self.shared.state()
 	}
 
-	/// Returns the current playback position of the sound (in seconds).
+	/**
+	Returns the current playback position of the sound (in seconds).
+
+	This is the frame the sound is outputting at the start of the most
+	recent chunk of audio, and it's the position that
+	[`seek_to`](Self::seek_to) and [`seek_by`](Self::seek_by) are measured
+	from. The sound reads audio [`RESAMPLER_LOOKAHEAD_FRAMES`](super::RESAMPLER_LOOKAHEAD_FRAMES)
+	frames ahead of this position to interpolate between frames, but that
+	delay is already accounted for here.
+
+	This doesn't include the time it takes for audio to travel from the
+	mixer to the speakers. Use [`audible_position`](Self::audible_position)
+	for timing visuals to the audio.
+	*/
 	#[must_use]
 	pub fn position(&self) -> f64 {
 		self.shared.position()
 	}
 
+	/**
+	Returns the position (in seconds) of the audio that's currently coming
+	out of the speakers, given the latency of the audio output.
+
+	Unlike [`position`](Self::position), this includes the fractional
+	position between frames. `output_latency` should be the time between
+	the audio being mixed and it being heard, such as the length of the
+	backend's buffer. The latency is converted to a distance using the
+	sound's current playback rate, playback rate jitter, and pitch envelope,
+	so the result is only an estimate if the playback rate changed or the
+	sound looped within the latency. The result is never negative.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# };
+	use std::time::Duration;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	// the position of the audio the player is hearing on a device with
+	// 20ms of latency
+	let position = sound.audible_position(Duration::from_millis(20));
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn audible_position(&self, output_latency: Duration) -> f64 {
+		self.shared.position_before(output_latency.as_secs_f64())
+	}
+
+	/**
+	Returns a [`FinishedReceiver`] that can be checked to find out
+	when and why the sound finished playing.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	# };
+	use kira::sound::FinishReason;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	let finished = sound.on_finished();
+	// later...
+	if finished.try_recv() == Some(FinishReason::ReachedEnd) {
+		println!("the sound played all the way through");
+	}
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn on_finished(&self) -> FinishedReceiver {
+		FinishedReceiver {
+			finished: self.shared.finished(),
+		}
+	}
+
+	/// Returns how many more times playback will jump back to the start
+	/// of the loop region, or `None` if the sound loops indefinitely.
+	#[must_use]
+	pub fn remaining_loops(&self) -> Option<u32> {
+		self.shared.remaining_loops()
+	}
+
+	/// Returns statistics about the sound's playback so far, such as how
+	/// many times it's looped and whether it's clipping.
+	///
+	/// This is mainly useful for debugging.
+	#[must_use]
+	pub fn stats(&self) -> PlaybackStats {
+		self.shared.stats()
+	}
+
 	handle_param_setters! {
 		/**
 		Sets the volume of the sound.
@@ -266,6 +367,20 @@ impl StaticSoundHandle {
 	# Result::<(), Box<dyn std::error::Error>>::Ok(())
 	```
 
+	Positions in seconds are rounded to the nearest sample. To loop an exact
+	range of samples, use [`PlaybackPosition::Samples`](crate::sound::PlaybackPosition::Samples):
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::{PlaybackPosition, static_sound::{StaticSoundData, StaticSoundSettings}},
+	# };
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	# let mut sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	sound.set_loop_region(PlaybackPosition::Samples(88200)..PlaybackPosition::Samples(176400));
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+
 	Set a sound that was previously looping to stop looping:
 
 	```no_run
@@ -285,10 +400,259 @@ impl StaticSoundHandle {
 			.write(loop_region.into_optional_region())
 	}
 
+	/**
+	Sets how many times the loop region should play before playback
+	continues to the end of the sound, or `None` to loop indefinitely.
+
+	The count includes the pass through the loop region that's currently
+	playing, so setting the loop count to `1` lets the sound finish after
+	the current loop.
+
+	# Examples
+
+	Let background music play its outro after the current loop:
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	# };
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	# let mut sound = manager.play(StaticSoundData::from_file("sound.ogg")?.loop_region(2.0..4.0))?;
+	sound.set_loop_count(1);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn set_loop_count(&mut self, loop_count: impl Into<Option<u32>>) {
+		self.command_writers.set_loop_count.write(loop_count.into())
+	}
+
+	/**
+	Sets the portion of the audio that should be played, or `None`
+	to play all of the audio.
+
+	Unlike [`StaticSoundData::slice`](super::StaticSoundData::slice), the
+	region is measured from the start of the underlying audio rather than
+	the current slice, and it's clamped to the length of the audio. The
+	playback position, loop region, and the position reported by
+	[`StaticSoundHandle::position`] are measured relative to the new region.
+
+	If playback has already passed the end of the new region, the sound
+	finishes instead of jumping back. If playback hasn't reached the start
+	of the new region yet, it jumps to the start.
+
+	# Examples
+
+	Stop playback 5 seconds into the audio:
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	# };
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	# let mut sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	sound.set_playback_region(..5.0);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn set_playback_region(&mut self, region: impl IntoOptionalRegion) {
+		self.command_writers
+			.set_playback_region
+			.write(region.into_optional_region())
+	}
+
+	/**
+	Sweeps the playback rate from `start` back to the normal playback
+	rate using the tween, replacing any sweep already in progress.
+
+	This works the same way as
+	[`StaticSoundSettings::pitch_envelope`](super::StaticSoundSettings::pitch_envelope),
+	but it can be triggered at any time while the sound is playing.
+
+	# Examples
+
+	Briefly lower the pitch when the player gets hit:
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# };
+	use std::time::Duration;
+	use kira::{Semitones, Tween};
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	sound.trigger_pitch_envelope(
+		Semitones(-3.0),
+		Tween {
+			duration: Duration::from_millis(100),
+			..Default::default()
+		},
+	);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn trigger_pitch_envelope(&mut self, start: impl Into<PlaybackRate>, tween: Tween) {
+		self.command_writers
+			.trigger_pitch_envelope
+			.write((start.into(), tween))
+	}
+
+	/**
+	Queues audio to play as soon as the sound reaches the end of its
+	current audio, without any gap in between.
+
+	Once the sound switches to the queued audio, this handle controls the
+	new audio, and the sound only finishes once the new audio ends. If audio
+	was already queued and the sound hasn't switched to it yet, it's replaced.
+
+	The sound plays the queued audio using its slice and these settings:
+	`start_position`, `reverse`, `loop_region`, `loop_count`, `loop_mode`,
+	`loop_crossfade`, and `fade_out_tween`. All other settings, such as the
+	volume and playback rate, are ignored, since they continue to be
+	controlled using this handle.
+
+	If the current audio loops indefinitely, the queued audio will never play.
+
+	# Examples
+
+	Play an intro, and then loop the rest of a song:
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# };
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut sound = manager.play(StaticSoundData::from_file("intro.ogg")?)?;
+	sound.queue_next(StaticSoundData::from_file("loop.ogg")?.loop_region(..));
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn queue_next(&mut self, data: StaticSoundData) {
+		self.segment_queue
+			.get_mut()
+			.expect("segment queue mutex poisoned")
+			.write(data);
+	}
+
+	/**
+	Moves the sound to a different mixer track.
+
+	The sound's output crossfades from its current track to the new
+	track over the duration of the tween, starting at the tween's start
+	time. The destination can be a sub-track, a spatial sub-track, or
+	the main track.
+
+	If the destination track has been removed or has no room for more
+	sounds, the sound stays on its current track.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# 	track::TrackBuilder,
+	# };
+	use kira::Tween;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let track = manager.add_sub_track(TrackBuilder::new())?;
+	let mut sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	sound.set_output_destination(&track, Tween::default());
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn set_output_destination(&mut self, track: impl Into<TrackId>, tween: Tween) {
+		self.command_writers
+			.set_output_destination
+			.write((track.into(), tween))
+	}
+
+	/**
+	Sets the volume of the sound's send to a send track.
+
+	This can only be used to change the volume of sends the sound was
+	played with using [`StaticSoundSettings::send`](super::StaticSoundSettings::send),
+	not to add new sends.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# 	track::SendTrackBuilder,
+	# };
+	use kira::Tween;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let reverb_send = manager.add_send_track(SendTrackBuilder::new())?;
+	let mut sound = manager.play(StaticSoundData::from_file("footstep.ogg")?.send(&reverb_send, -6.0))?;
+	sound.set_send_level(&reverb_send, -12.0, Tween::default())?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn set_send_level(
+		&mut self,
+		to: impl Into<SendTrackId>,
+		volume: impl Into<Value<Decibels>>,
+		tween: Tween,
+	) -> Result<(), NonexistentRoute> {
+		self.send_level_command_writers.set(to, volume, tween)
+	}
+
 	/// Fades out the sound to silence with the given tween and then
 	/// pauses playback.
 	pub fn pause(&mut self, tween: Tween) {
-		self.command_writers.pause.write(tween)
+		self.pause_at(StartTime::Immediate, tween)
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback once the start time is reached.
+	///
+	/// If the start time can never be reached (for example, because the
+	/// clock it refers to was removed), the pause is discarded.
+	pub fn pause_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.pause.write((start_time, tween))
+	}
+
+	/**
+	Fades out the sound to silence with the given tween, pauses playback,
+	and then resumes playback and fades back in with the same tween once
+	the start time is reached.
+
+	While waiting to resume, the sound's state is
+	[`PlaybackState::WaitingToResume`]. If the start time refers to a clock
+	that's stopped, the sound stays paused until the clock reaches the
+	start time, and if the clock is removed, the sound stays paused
+	indefinitely. Calling [`resume`](Self::resume), [`pause`](Self::pause),
+	or [`stop`](Self::stop) cancels the scheduled resume.
+
+	# Examples
+
+	Pause the sound until the next beat:
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# 	clock::ClockSpeed,
+	# };
+	use kira::Tween;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let clock = manager.add_clock(ClockSpeed::TicksPerMinute(120.0))?;
+	let mut sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	sound.pause_until((clock.time() + 1).into(), Tween::default());
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn pause_until(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.pause_until.write((start_time, tween))
 	}
 
 	/// Resumes playback and fades in the sound from silence
@@ -308,16 +672,70 @@ impl StaticSoundHandle {
 	///
 	/// Once the sound is stopped, it cannot be restarted.
 	pub fn stop(&mut self, tween: Tween) {
-		self.command_writers.stop.write(tween)
+		self.stop_at(StartTime::Immediate, tween)
+	}
+
+	/**
+	Fades out the sound to silence with the given tween and then
+	stops playback once the start time is reached.
+
+	If the start time can never be reached (for example, because the
+	clock it refers to was removed), the stop is discarded and the
+	sound keeps playing.
+
+	# Examples
+
+	Stop the sound on the next beat:
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# 	clock::ClockSpeed,
+	# };
+	use kira::Tween;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let clock = manager.add_clock(ClockSpeed::TicksPerMinute(120.0))?;
+	let mut sound = manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+	sound.stop_at((clock.time() + 1).into(), Tween::default());
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn stop_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.stop.write((start_time, tween))
 	}
 
 	/// Sets the playback position to the specified time in seconds.
+	///
+	/// [`StaticSoundHandle::position`] reports the new position right away,
+	/// and the first frame played after the seek is the one at the new
+	/// position, even if the sound is paused. Seeking past the end of the
+	/// sound finishes it, and the reported position is clamped to the end.
 	pub fn seek_to(&mut self, position: f64) {
-		self.command_writers.seek_to.write(position)
+		self.command_writers.seek_to.write(position);
+		self.shared
+			.set_position(position.max(0.0).min(self.shared.duration()));
 	}
 
-	/// Moves the playback position by the specified amount of time in seconds.
+	/**
+	Moves the playback position by the specified amount of time in seconds.
+
+	[`StaticSoundHandle::position`] reports the new position right away.
+
+	The amount is measured from the position of the sound when the audio
+	thread next processes commands. Like other commands, only the most
+	recent call is applied if this is called more than once before then,
+	so for multiple moves in a row, add up the amounts or use
+	[`seek_to`](Self::seek_to) instead. Until the audio thread catches up,
+	[`position`](Self::position) reports the sum of all of the moves.
+	*/
 	pub fn seek_by(&mut self, amount: f64) {
-		self.command_writers.seek_by.write(amount)
+		self.command_writers.seek_by.write(amount);
+		self.shared.set_position(
+			(self.shared.position() + amount)
+				.max(0.0)
+				.min(self.shared.duration()),
+		);
 	}
 }
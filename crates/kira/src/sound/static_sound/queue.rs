@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	fmt::{Debug, Formatter},
+	sync::Arc,
+};
+
+use rtrb::{Consumer, Producer, PushError, RingBuffer};
+use triple_buffer::{triple_buffer, Input, Output};
+
+use crate::frame::Frame;
+
+use super::StaticSoundData;
+
+/// Creates a channel for sending the next segment of audio to a static
+/// sound.
+///
+/// Only the most recently queued segment is kept, and segments that are
+/// replaced before the sound reads them are dropped on the gameplay thread.
+/// Once the sound switches to a segment, the frames it was previously
+/// playing are sent back so they can be dropped on the gameplay thread
+/// as well.
+#[must_use]
+pub(super) fn segment_queue() -> (SegmentQueueWriter, SegmentQueueReader) {
+	let (input, output) = triple_buffer(&None);
+	// the writer empties this before writing each segment, but the reader
+	// can switch to the segment written before that one and then to the
+	// new one before the writer empties it again, so the reader holds onto
+	// frames that don't fit until there's room
+	let (retired_frames_producer, retired_frames_consumer) = RingBuffer::new(1);
+	(
+		SegmentQueueWriter {
+			input,
+			retired_frames_consumer,
+		},
+		SegmentQueueReader {
+			output,
+			retired_frames_producer,
+			unretired_frames: None,
+		},
+	)
+}
+
+pub(super) struct SegmentQueueWriter {
+	input: Input<Option<StaticSoundData>>,
+	retired_frames_consumer: Consumer<Arc<[Frame]>>,
+}
+
+impl SegmentQueueWriter {
+	/// Queues a segment, replacing any segment that the sound hasn't
+	/// switched to yet.
+	pub fn write(&mut self, data: StaticSoundData) {
+		while self.retired_frames_consumer.pop().is_ok() {}
+		self.input.write(Some(data));
+	}
+}
+
+impl Debug for SegmentQueueWriter {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SegmentQueueWriter").finish()
+	}
+}
+
+pub(super) struct SegmentQueueReader {
+	output: Output<Option<StaticSoundData>>,
+	retired_frames_producer: Producer<Arc<[Frame]>>,
+	unretired_frames: Option<Arc<[Frame]>>,
+}
+
+impl SegmentQueueReader {
+	/// Returns the most recently queued segment, or `None` if no segment
+	/// was queued since the last read.
+	#[must_use]
+	pub fn read(&mut self) -> Option<StaticSoundData> {
+		if self.output.update() {
+			self.output.output_buffer_mut().take()
+		} else {
+			None
+		}
+	}
+
+	/// Sends frames the sound is done with back to the gameplay thread.
+	///
+	/// If there's no room to send them yet, they're sent by a later call
+	/// to [`retry_retire`](Self::retry_retire).
+	pub fn retire(&mut self, frames: Arc<[Frame]>) {
+		self.retry_retire();
+		// there's only ever one set of frames that doesn't fit, but if that
+		// changes, dropping the older frames here is better than panicking
+		self.unretired_frames = Some(frames);
+		self.retry_retire();
+	}
+
+	/// Sends frames that didn't fit the last time they were retired back
+	/// to the gameplay thread if there's room for them now.
+	pub fn retry_retire(&mut self) {
+		let Some(frames) = self.unretired_frames.take() else {
+			return;
+		};
+		if let Err(PushError::Full(frames)) = self.retired_frames_producer.push(frames) {
+			self.unretired_frames = Some(frames);
+		}
+	}
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::{frame::Frame, sound::static_sound::StaticSoundSettings};
+
+use super::{segment_queue, StaticSoundData};
+
+fn segment() -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::ZERO]),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+/// Tests that the reader holds onto retired frames that don't fit yet
+/// instead of panicking, and sends them later.
+#[test]
+fn retires_frames_when_there_is_room() {
+	let (mut writer, mut reader) = segment_queue();
+	let first_frames: Arc<[Frame]> = Arc::new([Frame::ZERO]);
+	let second_frames: Arc<[Frame]> = Arc::new([Frame::ZERO]);
+
+	// the reader switches to the first segment, but the writer writes the
+	// second segment before the reader retires the frames it was playing
+	writer.write(segment());
+	assert!(reader.read().is_some());
+	writer.write(segment());
+	reader.retire(first_frames.clone());
+	assert!(reader.read().is_some());
+	reader.retire(second_frames.clone());
+	assert_eq!(Arc::strong_count(&first_frames), 2);
+	assert_eq!(Arc::strong_count(&second_frames), 2);
+
+	// writing a segment frees up room for the held frames
+	writer.write(segment());
+	assert_eq!(Arc::strong_count(&first_frames), 1);
+	reader.retry_retire();
+	writer.write(segment());
+	assert_eq!(Arc::strong_count(&second_frames), 1);
+}
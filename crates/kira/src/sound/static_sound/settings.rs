@@ -1,7 +1,11 @@
+use std::time::Duration;
+
 use crate::{
-	sound::{IntoOptionalRegion, PlaybackPosition, Region},
-	Tween,
-	Decibels, Panning, PlaybackRate, StartTime, Value,
+	group::GroupId,
+	sound::{ChannelMixMode, IntoOptionalRegion, LoopMode, PlaybackPosition, Region, SoundSends},
+	track::SendTrackId,
+	voice_group::VoiceGroupId,
+	Decibels, Panning, PlaybackRate, Semitones, StartTime, Tween, Value,
 };
 
 /// Settings for a static sound.
@@ -11,22 +15,110 @@ pub struct StaticSoundSettings {
 	pub start_time: StartTime,
 	/// Where in the sound playback should start.
 	pub start_position: PlaybackPosition,
+	/// Whether the sound should start out paused.
+	///
+	/// A paused sound is ready to play as soon as it's resumed, so this
+	/// is useful for sounds that need to start with as little delay as
+	/// possible. The [`fade_in_tween`](Self::fade_in_tween) isn't used,
+	/// since the sound fades in with the tween passed to
+	/// [`StaticSoundHandle::resume`](super::StaticSoundHandle::resume).
+	pub start_paused: bool,
 	/// The portion of the sound that should be looped.
 	pub loop_region: Option<Region>,
+	/// Whether to loop the loop points stored in the audio file, like the
+	/// ones in a WAV file's `smpl` chunk or `LOOPSTART` tags, when
+	/// [`loop_region`](Self::loop_region) is `None`. Defaults to `true`.
+	pub use_detected_loop_region: bool,
+	/// How many times the loop region should play before playback
+	/// continues to the end of the sound, or `None` to loop indefinitely.
+	///
+	/// A loop count of `0` or `1` plays the loop region once without
+	/// repeating it. This has no effect if there's no loop region.
+	pub loop_count: Option<u32>,
+	/// What happens when playback reaches the end of the loop region.
+	///
+	/// With [`LoopMode::PingPong`], each change of direction counts
+	/// towards the [`loop_count`](Self::loop_count).
+	pub loop_mode: LoopMode,
+	/// How long to crossfade between the end and the start of the loop
+	/// region when playback loops, which can hide clicks caused by the
+	/// waveform not lining up.
+	///
+	/// The crossfade is clamped to half of the length of the loop region.
+	/// This has no effect when using [`LoopMode::PingPong`].
+	pub loop_crossfade: Option<Duration>,
 	/// Whether the sound should be played in reverse.
+	///
+	/// When a sound is reversed, the start position, seeking, and the
+	/// position reported by the handle are all measured from the end
+	/// of the sound. Loop regions are still given in the sound's
+	/// original timeline. A negative playback rate plays a reversed
+	/// sound forward.
 	pub reverse: bool,
 	/// The volume of the sound.
 	pub volume: Value<Decibels>,
+	/// The maximum amount the volume will be randomly raised or lowered
+	/// by each time the sound is played.
+	///
+	/// The random offset is added on top of [`volume`](Self::volume), including
+	/// when the volume is linked to a modulator.
+	pub volume_jitter: Decibels,
 	/// The playback rate of the sound.
 	///
 	/// Changing the playback rate will change both the speed
 	/// and the pitch of the sound.
 	pub playback_rate: Value<PlaybackRate>,
+	/// The maximum amount the playback rate will be randomly raised or
+	/// lowered by each time the sound is played.
+	///
+	/// The random offset is applied on top of [`playback_rate`](Self::playback_rate),
+	/// including when the playback rate is linked to a modulator.
+	pub playback_rate_jitter: Semitones,
+	/// An optional sweep of the playback rate when the sound starts.
+	///
+	/// The playback rate is multiplied by the first value, which then
+	/// moves to `1.0` using the tween. This is applied on top of
+	/// [`playback_rate`](Self::playback_rate).
+	pub pitch_envelope: Option<(PlaybackRate, Tween)>,
+	/// The seed used to pick the random volume and playback rate offsets,
+	/// or `None` to use a different seed every time the sound is played.
+	///
+	/// This is mostly useful for making tests deterministic.
+	pub jitter_seed: Option<u64>,
 	/// The panning of the sound, where 0 is hard left
 	/// and 1 is hard right.
 	pub panning: Value<Panning>,
+	/// How the left and right channels are combined before volume and
+	/// panning are applied.
+	pub channel_mix_mode: ChannelMixMode,
+	/// The send tracks the sound sends its audio to, in addition to the
+	/// track it's playing on.
+	///
+	/// Sounds playing on the main track don't send audio to send tracks.
+	pub sends: SoundSends,
 	/// An optional fade-in from silence.
 	pub fade_in_tween: Option<Tween>,
+	/// An optional fade-out to silence as playback approaches the end
+	/// of the sound.
+	///
+	/// Only the duration and easing of the tween are used. The fade-out
+	/// isn't applied while there are loops left to play, and stopping the
+	/// sound using its handle still fades out with the tween given to
+	/// `stop`.
+	pub fade_out_tween: Option<Tween>,
+	/// The [voice group](crate::voice_group) that limits how many instances
+	/// of the sound can play at once.
+	pub voice_group: Option<VoiceGroupId>,
+	/// The [group](crate::group) the sound belongs to.
+	pub group: Option<GroupId>,
+	/// Whether to skip checking the start position and loop region when
+	/// the sound is played.
+	///
+	/// By default, playing a sound with a start position past the end of
+	/// the sound or a loop region that's empty or extends past the end of
+	/// the sound returns a [`SettingsError`](super::SettingsError). When
+	/// this is `true`, those settings are clamped to the sound instead.
+	pub lenient: bool,
 }
 
 impl StaticSoundSettings {
@@ -36,12 +128,27 @@ impl StaticSoundSettings {
 		Self {
 			start_time: StartTime::default(),
 			start_position: PlaybackPosition::Seconds(0.0),
+			start_paused: false,
 			reverse: false,
 			loop_region: None,
+			use_detected_loop_region: true,
+			loop_count: None,
+			loop_mode: LoopMode::Forward,
+			loop_crossfade: None,
 			volume: Value::Fixed(Decibels::IDENTITY),
+			volume_jitter: Decibels(0.0),
 			playback_rate: Value::Fixed(PlaybackRate(1.0)),
+			playback_rate_jitter: Semitones(0.0),
+			pitch_envelope: None,
+			jitter_seed: None,
 			panning: Value::Fixed(Panning::CENTER),
+			channel_mix_mode: ChannelMixMode::Stereo,
+			sends: SoundSends::new(),
 			fade_in_tween: None,
+			fade_out_tween: None,
+			voice_group: None,
+			group: None,
+			lenient: false,
 		}
 	}
 
@@ -63,13 +170,27 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/// Sets whether the sound should start out paused.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn start_paused(self, start_paused: bool) -> Self {
+		Self {
+			start_paused,
+			..self
+		}
+	}
+
 	/// Sets whether the sound should be played in reverse.
 	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
 	pub fn reverse(self, reverse: bool) -> Self {
 		Self { reverse, ..self }
 	}
 
-	/** Sets the portion of the sound that should be looped. */
+	/**
+	Sets the portion of the sound that should be looped.
+
+	Positions in seconds are rounded to the nearest sample, and positions
+	given as [`PlaybackPosition::Samples`] are used exactly.
+	*/
 	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
 	pub fn loop_region(self, loop_region: impl IntoOptionalRegion) -> Self {
 		Self {
@@ -78,6 +199,48 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/**
+	Sets whether to loop the loop points stored in the audio file when
+	no loop region is set.
+	*/
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn use_detected_loop_region(self, use_detected_loop_region: bool) -> Self {
+		Self {
+			use_detected_loop_region,
+			..self
+		}
+	}
+
+	/**
+	Sets how many times the loop region should play before playback
+	continues to the end of the sound, or `None` to loop indefinitely.
+	*/
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn loop_count(self, loop_count: impl Into<Option<u32>>) -> Self {
+		Self {
+			loop_count: loop_count.into(),
+			..self
+		}
+	}
+
+	/** Sets what happens when playback reaches the end of the loop region. */
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn loop_mode(self, loop_mode: LoopMode) -> Self {
+		Self { loop_mode, ..self }
+	}
+
+	/**
+	Sets how long to crossfade between the end and the start of the
+	loop region when playback loops.
+	*/
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn loop_crossfade(self, loop_crossfade: impl Into<Option<Duration>>) -> Self {
+		Self {
+			loop_crossfade: loop_crossfade.into(),
+			..self
+		}
+	}
+
 	/** Sets the volume of the sound. */
 	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
 	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
@@ -101,6 +264,39 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/**
+	Sets the maximum amount the volume will be randomly raised or lowered
+	by each time the sound is played.
+	*/
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn volume_jitter(self, volume_jitter: impl Into<Decibels>) -> Self {
+		Self {
+			volume_jitter: volume_jitter.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the maximum amount the playback rate will be randomly raised or
+	lowered by each time the sound is played.
+	*/
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn playback_rate_jitter(self, playback_rate_jitter: impl Into<Semitones>) -> Self {
+		Self {
+			playback_rate_jitter: playback_rate_jitter.into(),
+			..self
+		}
+	}
+
+	/// Sets the seed used to pick the random volume and playback rate offsets.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn jitter_seed(self, jitter_seed: impl Into<Option<u64>>) -> Self {
+		Self {
+			jitter_seed: jitter_seed.into(),
+			..self
+		}
+	}
+
 	/**
 	Sets the panning of the sound, where 0 is hard left
 	and 1 is hard right.
@@ -113,6 +309,85 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/// Sets how the left and right channels are combined before volume
+	/// and panning are applied.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn channel_mix_mode(self, channel_mix_mode: ChannelMixMode) -> Self {
+		Self {
+			channel_mix_mode,
+			..self
+		}
+	}
+
+	/**
+	Sends the sound's audio to a send track at the given volume, in
+	addition to the track it's playing on.
+
+	The sound's output is sent after its own volume and panning are
+	applied. If the send track is removed, the sound stops sending audio
+	to it. Sounds playing on the main track don't send audio to send
+	tracks.
+
+	# Panics
+
+	Panics if the sound already sends to [`MAX_SOUND_SENDS`](crate::sound::MAX_SOUND_SENDS)
+	other send tracks.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	# 	track::{SendTrackBuilder, TrackBuilder},
+	# };
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let reverb_send = manager.add_send_track(SendTrackBuilder::new())?;
+	let mut footsteps = manager.add_sub_track(TrackBuilder::new())?;
+	footsteps.play(
+		StaticSoundData::from_file("footstep.ogg")?
+			.with_settings(StaticSoundSettings::new().send(&reverb_send, -6.0)),
+	)?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn send(mut self, to: impl Into<SendTrackId>, volume: impl Into<Value<Decibels>>) -> Self {
+		self.sends.set(to, volume);
+		self
+	}
+
+	/**
+	Sets a sweep of the playback rate that's applied when the sound starts.
+
+	The playback rate is multiplied by `start`, which then moves to `1.0`
+	using the tween.
+
+	# Examples
+
+	Start the sound a semitone high and glide down over 50 milliseconds:
+
+	```
+	use std::time::Duration;
+	use kira::{sound::static_sound::StaticSoundSettings, PlaybackRate, Semitones, Tween};
+
+	let settings = StaticSoundSettings::new().pitch_envelope(
+		PlaybackRate::from(Semitones(1.0)),
+		Tween {
+			duration: Duration::from_millis(50),
+			..Default::default()
+		},
+	);
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn pitch_envelope(self, start: impl Into<PlaybackRate>, tween: Tween) -> Self {
+		Self {
+			pitch_envelope: Some((start.into(), tween)),
+			..self
+		}
+	}
+
 	/// Sets the tween used to fade in the sound from silence.
 	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
 	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
@@ -121,6 +396,42 @@ impl StaticSoundSettings {
 			..self
 		}
 	}
+
+	/// Sets the tween used to fade out the sound to silence as playback
+	/// approaches the end of the sound.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn fade_out_tween(self, fade_out_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_out_tween: fade_out_tween.into(),
+			..self
+		}
+	}
+
+	/// Sets the [voice group](crate::voice_group) that limits how many instances
+	/// of the sound can play at once.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn voice_group(self, voice_group: impl Into<VoiceGroupId>) -> Self {
+		Self {
+			voice_group: Some(voice_group.into()),
+			..self
+		}
+	}
+
+	/// Sets the [group](crate::group) the sound belongs to.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn group(self, group: impl Into<GroupId>) -> Self {
+		Self {
+			group: Some(group.into()),
+			..self
+		}
+	}
+
+	/// Sets whether to skip checking the start position and loop region
+	/// when the sound is played.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn lenient(self, lenient: bool) -> Self {
+		Self { lenient, ..self }
+	}
 }
 
 impl Default for StaticSoundSettings {
@@ -3,27 +3,50 @@ mod resampler;
 #[cfg(test)]
 mod test;
 
-use std::sync::{
-	atomic::{AtomicU64, AtomicU8, Ordering},
-	Arc,
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+		Arc,
+	},
+	time::Duration,
 };
 
 use crate::{
 	command::read_commands_into_parameters,
 	frame::Frame,
+	group::{GroupMember, GroupPlaybackCommand},
 	info::Info,
 	playback_state_manager::PlaybackStateManager,
-	sound::{transport::Transport, PlaybackState, Sound},
-	Tween,
-	Decibels, Panning, Parameter, PlaybackRate, StartTime,
+	random::Random,
+	sound::{
+		rerouting::Rerouting,
+		transport::{FadeOut, Transport},
+		ChannelMixMode, FinishReason, Finished, PlaybackState, Region, Sound, SoundSendLevels,
+	},
+	track::{SendTrackId, TrackId, TrackMember},
+	voice_group::{VoiceClaim, VoiceGroupId, VoiceGroupShared},
+	Decibels, Easing, Panning, Parameter, PlaybackRate, Semitones, StartTime, Tween, Value,
 };
 
 use self::resampler::Resampler;
 
-use super::{data::StaticSoundData, frame_at_index, num_frames, CommandReaders};
+use super::{
+	data::StaticSoundData, frame_at_index, num_frames, queue::SegmentQueueReader, CommandReaders,
+	PlaybackStats, StaticSoundSettings,
+};
+
+/// The fade-out used when a sound is stopped to make room for a new
+/// sound in its voice group.
+const VOICE_STEAL_FADE_OUT_TWEEN: Tween = Tween {
+	start_time: StartTime::Immediate,
+	duration: Duration::from_millis(10),
+	easing: Easing::Linear,
+};
 
 pub(super) struct StaticSound {
 	command_readers: CommandReaders,
+	/// Receives the audio to play after the current audio ends.
+	segment_queue: SegmentQueueReader,
 	sample_rate: u32,
 	frames: Arc<[Frame]>,
 	slice: Option<(usize, usize)>,
@@ -32,43 +55,124 @@ pub(super) struct StaticSound {
 	start_time: StartTime,
 	resampler: Resampler,
 	transport: Transport,
+	/// The fade-out applied as playback approaches the end of the sound.
+	fade_out: Option<FadeOut>,
 	fractional_position: f64,
 	volume: Parameter<Decibels>,
 	playback_rate: Parameter<PlaybackRate>,
+	/// A sweep the playback rate is multiplied by, which settles at `1.0`.
+	pitch_envelope: Parameter<PlaybackRate>,
 	panning: Parameter<Panning>,
+	/// The volumes of the sound's sends to send tracks.
+	send_levels: SoundSendLevels,
+	channel_mix_mode: ChannelMixMode,
+	/// Fades the sound out and back in when it's moved to a different track.
+	rerouting: Rerouting,
+	/// The group the sound belongs to.
+	group: Option<GroupMember>,
+	/// Applies the commands sent to every sound on the sound's track.
+	track_member: TrackMember,
+	/// A random offset added to the volume for this play of the sound.
+	volume_jitter: Decibels,
+	/// A random factor the playback rate is multiplied by for this play
+	/// of the sound.
+	playback_rate_jitter: f64,
+	/// The playback rate of the track the sound is on, which the sound's
+	/// own playback rate is multiplied by.
+	track_playback_rate: f64,
+	finish_reason: FinishReason,
+	/// Another sound whose playback position this sound should jump
+	/// to when it starts.
+	sync_target: Option<Arc<Shared>>,
+	/// How much time has been processed since the last call to
+	/// `on_start_processing` (in seconds).
+	time_since_block_start: f64,
+	/// How many frames have been produced while playback was advancing.
+	frames_played: u64,
+	/// Whether any output since the last call to `on_start_processing`
+	/// was outside the range of `-1.0` to `1.0`.
+	clipped: bool,
+	/// The voice group this sound should claim a voice from when it starts.
+	voice_group: Option<VoiceGroupId>,
+	/// The voice this sound is using, if it's in a voice group.
+	voice: Option<(Arc<VoiceGroupShared>, VoiceClaim)>,
 	shared: Arc<Shared>,
 }
 
 impl StaticSound {
 	#[must_use]
-	pub fn new(data: StaticSoundData, command_readers: CommandReaders) -> Self {
+	pub fn new(
+		data: StaticSoundData,
+		command_readers: CommandReaders,
+		segment_queue: SegmentQueueReader,
+		send_levels: SoundSendLevels,
+	) -> Self {
 		let settings = data.settings;
-		let transport = Transport::new(
-			data.settings.start_position.into_samples(data.sample_rate),
-			data.settings.loop_region,
+		let transport = create_transport(&data);
+		let fade_out = create_fade_out(&data);
+		let remaining_loops = transport.remaining_loops;
+		let (volume_jitter, playback_rate_jitter) = pick_jitter(&settings);
+		let starting_frame_index = transport.position;
+		let position = directional_index(
+			starting_frame_index,
 			data.settings.reverse,
-			data.sample_rate,
 			data.num_frames(),
-		);
-		let starting_frame_index = transport.position;
-		let position = starting_frame_index as f64 / data.sample_rate as f64;
+		) as f64 / data.sample_rate as f64;
+		let duration = data.num_frames() as f64 / data.sample_rate as f64;
 		let mut sound = Self {
 			command_readers,
+			segment_queue,
 			sample_rate: data.sample_rate,
 			frames: data.frames,
 			slice: data.slice,
 			reverse: data.settings.reverse,
-			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			playback_state_manager: if settings.start_paused {
+				PlaybackStateManager::new_paused()
+			} else {
+				PlaybackStateManager::new(settings.fade_in_tween)
+			},
 			start_time: settings.start_time,
 			resampler: Resampler::new(starting_frame_index),
 			transport,
+			fade_out,
 			fractional_position: 0.0,
 			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
 			playback_rate: Parameter::new(settings.playback_rate, PlaybackRate(1.0)),
+			pitch_envelope: match settings.pitch_envelope {
+				Some((start, tween)) => pitch_envelope(start, tween),
+				None => Parameter::new(Value::Fixed(PlaybackRate(1.0)), PlaybackRate(1.0)),
+			},
 			panning: Parameter::new(settings.panning, Panning::CENTER),
+			send_levels,
+			channel_mix_mode: settings.channel_mix_mode,
+			rerouting: Rerouting::new(),
+			group: settings.group.map(GroupMember::new),
+			track_member: TrackMember::default(),
+			volume_jitter,
+			playback_rate_jitter,
+			track_playback_rate: 1.0,
+			finish_reason: FinishReason::ReachedEnd,
+			sync_target: None,
+			time_since_block_start: 0.0,
+			frames_played: 0,
+			clipped: false,
+			voice_group: settings.voice_group,
+			voice: None,
 			shared: Arc::new(Shared {
-				state: AtomicU8::new(PlaybackState::Playing as u8),
+				state: AtomicU8::new(if settings.start_paused {
+					PlaybackState::Paused as u8
+				} else {
+					PlaybackState::Playing as u8
+				}),
 				position: AtomicU64::new(position.to_bits()),
+				precise_position: AtomicU64::new(position.to_bits()),
+				velocity: AtomicU64::new(0.0f64.to_bits()),
+				duration: AtomicU64::new(duration.to_bits()),
+				remaining_loops: AtomicU64::new(encode_remaining_loops(remaining_loops)),
+				loops_completed: AtomicU64::new(0),
+				frames_played: AtomicU64::new(0),
+				clipped: AtomicBool::new(false),
+				finished: Arc::new(Finished::new()),
 			}),
 		};
 		// fill the resample buffer with 3 samples so playback can
@@ -83,13 +187,119 @@ impl StaticSound {
 		self.shared.clone()
 	}
 
+	/// Makes the sound jump to the playback position of another sound
+	/// when it starts.
+	pub(super) fn sync_with(&mut self, target: Arc<Shared>) {
+		self.sync_target = Some(target);
+	}
+
+	/// Claims a voice from the sound's voice group when the sound starts,
+	/// and starts fading out if another sound has taken the voice.
+	fn update_voice(&mut self, info: &Info) {
+		if let Some(voice_group) = self.voice_group.take() {
+			if let Some(voice_group) = info.voice_group(voice_group) {
+				let claim = voice_group.claim();
+				self.voice = Some((voice_group.clone(), claim));
+			}
+		}
+		let Some((voice_group, claim)) = &self.voice else {
+			return;
+		};
+		if voice_group.owns(*claim) {
+			let volume = (self.volume.value() + self.volume_jitter).as_amplitude()
+				* self
+					.playback_state_manager
+					.interpolated_fade_volume(0.0)
+					.as_amplitude();
+			voice_group.report_volume(*claim, volume);
+		} else {
+			self.voice = None;
+			self.stop(StartTime::Immediate, VOICE_STEAL_FADE_OUT_TWEEN);
+		}
+	}
+
+	fn release_voice(&mut self) {
+		if let Some((voice_group, claim)) = self.voice.take() {
+			voice_group.release(claim);
+		}
+	}
+
+	/// Returns the position (in seconds) of the audio currently being heard,
+	/// including the fractional position between frames.
+	#[must_use]
+	fn precise_position(&self) -> f64 {
+		let num_frames = num_frames(&self.frames, self.slice);
+		let mut position = self.resampler.current_frame_index() as f64;
+		if self.is_playing_backwards() {
+			position -= self.fractional_position;
+		} else {
+			position += self.fractional_position;
+		}
+		if self.reverse {
+			position = num_frames.saturating_sub(1) as f64 - position;
+		}
+		position / self.sample_rate as f64
+	}
+
+	/// Returns how fast the precise position is moving (in seconds of audio
+	/// per second). This is negative when the position is decreasing.
+	#[must_use]
+	fn velocity(&self) -> f64 {
+		if self.start_time != StartTime::Immediate
+			|| !self.playback_state_manager.playback_state().is_advancing()
+		{
+			return 0.0;
+		}
+		let speed = self.playback_rate.value().0.abs()
+			* self.playback_rate_jitter
+			* self.track_playback_rate
+			* self.pitch_envelope.value().0.abs();
+		if self.is_playing_backwards() != self.reverse {
+			-speed
+		} else {
+			speed
+		}
+	}
+
+	/// Jumps to the playback position of the sync target, accounting for
+	/// the time that's passed since the target reported its position.
+	fn sync_to(&mut self, target: &Shared, elapsed: f64) {
+		let playback_rate =
+			self.playback_rate.value().0 * self.playback_rate_jitter * self.track_playback_rate;
+		let position = ((target.precise_position() + elapsed * playback_rate)
+			* self.sample_rate as f64)
+			.max(0.0);
+		let (index, fraction) = if playback_rate < 0.0 {
+			(position.ceil(), position.ceil() - position)
+		} else {
+			(position.floor(), position - position.floor())
+		};
+		self.seek_to_directional_index(index as usize);
+		self.fractional_position = fraction;
+	}
+
 	fn update_shared_playback_state(&mut self) {
-		self.shared
-			.set_state(self.playback_state_manager.playback_state());
+		let state = self.playback_state_manager.playback_state();
+		self.shared.set_state(state);
+		if state == PlaybackState::Stopped {
+			self.release_voice();
+			let finish_reason = if self.playback_state_manager.stopped_early() {
+				FinishReason::Stopped
+			} else {
+				self.finish_reason
+			};
+			self.shared.finished.notify(finish_reason);
+		}
+	}
+
+	fn pause(&mut self, start_time: StartTime, fade_out_tween: Tween) {
+		self.playback_state_manager
+			.pause_at(start_time, fade_out_tween);
+		self.update_shared_playback_state();
 	}
 
-	fn pause(&mut self, fade_out_tween: Tween) {
-		self.playback_state_manager.pause(fade_out_tween);
+	fn pause_until(&mut self, start_time: StartTime, tween: Tween) {
+		self.playback_state_manager.pause_until(start_time, tween);
 		self.update_shared_playback_state();
 	}
 
@@ -99,22 +309,39 @@ impl StaticSound {
 		self.update_shared_playback_state();
 	}
 
-	fn stop(&mut self, fade_out_tween: Tween) {
-		self.playback_state_manager.stop(fade_out_tween);
+	fn stop(&mut self, start_time: StartTime, fade_out_tween: Tween) {
+		self.playback_state_manager
+			.stop_at(start_time, fade_out_tween);
 		self.update_shared_playback_state();
 	}
 
+	fn apply_group_command(&mut self, command: GroupPlaybackCommand) {
+		match command {
+			GroupPlaybackCommand::Pause(tween) => self.pause(StartTime::Immediate, tween),
+			GroupPlaybackCommand::Resume(tween) => self.resume(StartTime::Immediate, tween),
+			GroupPlaybackCommand::Stop(tween) => self.stop(StartTime::Immediate, tween),
+		}
+	}
+
 	#[must_use]
 	fn is_playing_backwards(&self) -> bool {
 		let mut is_playing_backwards = self.playback_rate.value().0.is_sign_negative();
 		if self.reverse {
 			is_playing_backwards = !is_playing_backwards
 		}
+		if self.transport.reflected {
+			is_playing_backwards = !is_playing_backwards
+		}
 		is_playing_backwards
 	}
 
 	/// Updates the current frame index by 1 and pushes a new sample to the resampler.
 	fn update_position(&mut self) {
+		// switch to the queued audio before reading the next frame so
+		// there's no gap between the two
+		if !self.transport.playing {
+			self.start_queued_segment();
+		}
 		self.push_frame_to_resampler();
 		if self.is_playing_backwards() {
 			self.transport.decrement_position();
@@ -128,37 +355,228 @@ impl StaticSound {
 		}
 	}
 
+	/// Switches to the most recently queued segment, if there is one.
+	///
+	/// The resampler isn't reset, so the end of the previous audio flows
+	/// directly into the start of the new audio.
+	fn start_queued_segment(&mut self) {
+		let Some(data) = self.segment_queue.read() else {
+			return;
+		};
+		let loops_completed = self.transport.loops_completed;
+		self.transport = create_transport(&data);
+		self.transport.loops_completed = loops_completed;
+		self.fade_out = create_fade_out(&data);
+		self.sample_rate = data.sample_rate;
+		self.slice = data.slice;
+		self.reverse = data.settings.reverse;
+		let previous_frames = std::mem::replace(&mut self.frames, data.frames);
+		self.segment_queue.retire(previous_frames);
+	}
+
 	fn seek_to_index(&mut self, index: usize) {
 		self.transport
 			.seek_to(index, num_frames(&self.frames, self.slice));
-		// if the sound is playing, push a frame to the resample buffer
-		// to make sure it doesn't get skipped
-		if self.playback_state_manager.playback_state().is_advancing() {
-			self.push_frame_to_resampler();
+		// flush the resample buffer and refill it from the new position,
+		// so the next frame we output is the one that was seeked to, even
+		// if the sound is currently paused
+		self.resampler = Resampler::new(self.transport.position);
+		self.fractional_position = 0.0;
+		if self.transport.playing {
+			// start with the frame just before the new position so
+			// the first frames are interpolated correctly
+			let previous_index = if self.is_playing_backwards() {
+				self.transport.position.checked_add(1)
+			} else {
+				self.transport.position.checked_sub(1)
+			};
+			let previous_frame = previous_index
+				.and_then(|index| frame_at_index(index, &self.frames, self.slice))
+				.unwrap_or_default();
+			self.resampler
+				.push_frame(Some(previous_frame), self.transport.position);
+		}
+		for _ in 0..3 {
+			self.update_position();
 		}
 	}
 
 	fn push_frame_to_resampler(&mut self) {
 		let frame = self.transport.playing.then(|| {
-			frame_at_index(self.transport.position, &self.frames, self.slice).unwrap_or_default()
+			let frame = frame_at_index(self.transport.position, &self.frames, self.slice)
+				.unwrap_or_default();
+			let frame = match self.transport.loop_crossfade(self.is_playing_backwards()) {
+				Some((other_index, amount)) => {
+					let other_frame =
+						frame_at_index(other_index, &self.frames, self.slice).unwrap_or_default();
+					// equal-power crossfade
+					let angle = amount * std::f32::consts::FRAC_PI_2;
+					frame * angle.cos() + other_frame * angle.sin()
+				}
+				None => frame,
+			};
+			frame * self.fade_out_volume()
 		});
 		self.resampler.push_frame(frame, self.transport.position);
 	}
 
+	/// Returns the volume of the frame at the transport's position given
+	/// the fade-out at the end of the sound.
+	#[must_use]
+	fn fade_out_volume(&self) -> f32 {
+		let Some(fade_out) = self.fade_out else {
+			return 1.0;
+		};
+		self.transport
+			.frames_until_end(
+				self.is_playing_backwards(),
+				num_frames(&self.frames, self.slice),
+			)
+			.map_or(1.0, |frames_until_end| {
+				fade_out.volume(frames_until_end).as_amplitude()
+			})
+	}
+
+	/// Seeks to an index measured in the direction the sound plays in,
+	/// so when the sound is reversed, index 0 is the last frame.
+	fn seek_to_directional_index(&mut self, index: usize) {
+		let num_frames = num_frames(&self.frames, self.slice);
+		if self.reverse && index >= num_frames {
+			// seeking past the end of a reversed sound means seeking before
+			// the first frame of the underlying audio, so just stop
+			self.seek_to_index(num_frames);
+			return;
+		}
+		self.seek_to_index(directional_index(index, self.reverse, num_frames));
+	}
+
+	fn set_playback_region(&mut self, region: Option<Region>) {
+		let total_frames = self.frames.len();
+		let slice = region.map(|region| {
+			let start = region
+				.start
+				.into_samples(self.sample_rate)
+				.min(total_frames);
+			let end = match region.end_in_samples(self.sample_rate) {
+				None => total_frames,
+				Some(end) => end.clamp(start, total_frames),
+			};
+			(start, end)
+		});
+		let (old_start, _) = self.slice.unwrap_or((0, total_frames));
+		let (new_start, new_end) = slice.unwrap_or((0, total_frames));
+		let backwards = self.is_playing_backwards();
+		self.slice = slice;
+		self.transport
+			.rebase(old_start, new_start, new_end - new_start, backwards);
+		self.resampler.rebase(old_start, new_start);
+	}
+
 	fn seek_by(&mut self, amount: f64) {
-		let current_position = self.transport.position as f64 / self.sample_rate as f64;
+		// measure from the frame that's currently being heard rather than
+		// the transport, which runs a few frames ahead to fill the resampler
+		let current_index = directional_index(
+			self.resampler.current_frame_index(),
+			self.reverse,
+			num_frames(&self.frames, self.slice),
+		);
+		let current_position = current_index as f64 / self.sample_rate as f64;
 		let position = current_position + amount;
 		let index = (position * self.sample_rate as f64) as usize;
-		self.seek_to_index(index);
+		self.seek_to_directional_index(index);
 	}
 
 	fn seek_to(&mut self, position: f64) {
 		let index = (position * self.sample_rate as f64) as usize;
-		self.seek_to_index(index);
+		self.seek_to_directional_index(index);
+	}
+
+	/// Updates parameters and the playback state at the start of a chunk
+	/// of `num_frames` frames. Returns `false` if the sound shouldn't output
+	/// any audio during this chunk.
+	pub(super) fn start_chunk(&mut self, num_frames: usize, dt: f64, info: &Info) -> bool {
+		let time_since_block_start = self.time_since_block_start;
+		self.time_since_block_start += dt * num_frames as f64;
+
+		// update parameters
+		self.volume.update(dt * num_frames as f64, info);
+		self.playback_rate.update(dt * num_frames as f64, info);
+		self.track_playback_rate = info.track_playback_rate();
+		self.panning.update(dt * num_frames as f64, info);
+		self.send_levels.update(dt * num_frames as f64, info);
+		self.rerouting.update(dt * num_frames as f64, info);
+		if let Some(command) = self
+			.group
+			.as_mut()
+			.and_then(|group| group.update(dt * num_frames as f64, info))
+		{
+			self.apply_group_command(command);
+		}
+		if let Some(command) = self.track_member.update(info) {
+			self.apply_group_command(command);
+		}
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * num_frames as f64, info);
+		if changed_playback_state {
+			self.update_shared_playback_state();
+		}
+
+		let will_never_start = self.start_time.update(dt * num_frames as f64, info);
+		if will_never_start {
+			self.finish_reason = FinishReason::Stopped;
+			self.playback_state_manager.mark_as_stopped();
+			self.update_shared_playback_state();
+		}
+		if self.start_time != StartTime::Immediate {
+			return false;
+		}
+		if let Some(target) = self.sync_target.take() {
+			self.sync_to(&target, time_since_block_start);
+		}
+		if self.playback_state_manager.playback_state() != PlaybackState::Stopped {
+			self.update_voice(info);
+		}
+
+		let advancing = self.playback_state_manager.playback_state().is_advancing();
+		// the pitch envelope only progresses while the sound is audibly playing
+		if advancing {
+			self.pitch_envelope.update(dt * num_frames as f64, info);
+		}
+		advancing
+	}
+
+	/// Produces the next frame of audio. `time_in_chunk` is how far
+	/// through the current chunk this frame is (from `0.0` to `1.0`).
+	pub(super) fn next_frame(&mut self, time_in_chunk: f64, dt: f64) -> Frame {
+		let volume =
+			(self.volume.interpolated_value(time_in_chunk) + self.volume_jitter).as_amplitude();
+		let fade_volume = self
+			.playback_state_manager
+			.interpolated_fade_volume(time_in_chunk)
+			.as_amplitude()
+			* self.group.as_ref().map_or(1.0, |group| {
+				group.interpolated_volume(time_in_chunk).as_amplitude()
+			});
+		let panning = self.panning.interpolated_value(time_in_chunk);
+		let playback_rate = self.playback_rate.interpolated_value(time_in_chunk).0
+			* self.pitch_envelope.interpolated_value(time_in_chunk).0
+			* self.track_playback_rate;
+		let resampler_out = self
+			.channel_mix_mode
+			.apply(self.resampler.get(self.fractional_position as f32));
+		self.fractional_position +=
+			self.sample_rate as f64 * playback_rate.abs() * self.playback_rate_jitter * dt;
+		while self.fractional_position >= 1.0 {
+			self.fractional_position -= 1.0;
+			self.update_position();
+		}
+		(resampler_out * fade_volume * volume).panned(panning)
 	}
 
 	fn read_commands(&mut self) {
 		read_commands_into_parameters!(self, volume, playback_rate, panning);
+		self.send_levels.read_commands();
 		if let Some(loop_region) = self.command_readers.set_loop_region.read() {
 			self.transport.set_loop_region(
 				loop_region,
@@ -166,14 +584,29 @@ impl StaticSound {
 				num_frames(&self.frames, self.slice),
 			);
 		}
-		if let Some(tween) = self.command_readers.pause.read() {
-			self.pause(tween);
+		if let Some(loop_count) = self.command_readers.set_loop_count.read() {
+			self.transport.set_loop_count(loop_count);
+		}
+		if let Some(region) = self.command_readers.set_playback_region.read() {
+			self.set_playback_region(region);
+		}
+		if let Some((destination, tween)) = self.command_readers.set_output_destination.read() {
+			self.rerouting.start(destination, tween);
+		}
+		if let Some((start, tween)) = self.command_readers.trigger_pitch_envelope.read() {
+			self.pitch_envelope = pitch_envelope(start, tween);
+		}
+		if let Some((start_time, tween)) = self.command_readers.pause.read() {
+			self.pause(start_time, tween);
+		}
+		if let Some((start_time, tween)) = self.command_readers.pause_until.read() {
+			self.pause_until(start_time, tween);
 		}
 		if let Some((start_time, tween)) = self.command_readers.resume.read() {
 			self.resume(start_time, tween);
 		}
-		if let Some(tween) = self.command_readers.stop.read() {
-			self.stop(tween);
+		if let Some((start_time, tween)) = self.command_readers.stop.read() {
+			self.stop(start_time, tween);
 		}
 		if let Some(amount) = self.command_readers.seek_by.read() {
 			self.seek_by(amount);
@@ -186,37 +619,48 @@ impl StaticSound {
 
 impl Sound for StaticSound {
 	fn on_start_processing(&mut self) {
-		let last_played_frame_position = self.resampler.current_frame_index();
-		self.shared.position.store(
-			(last_played_frame_position as f64 / self.sample_rate as f64).to_bits(),
+		self.read_commands();
+		self.segment_queue.retry_retire();
+		let last_played_frame_position = directional_index(
+			self.resampler.current_frame_index(),
+			self.reverse,
+			num_frames(&self.frames, self.slice),
+		);
+		self.shared
+			.set_position(last_played_frame_position as f64 / self.sample_rate as f64);
+		self.shared
+			.precise_position
+			.store(self.precise_position().to_bits(), Ordering::SeqCst);
+		self.shared
+			.velocity
+			.store(self.velocity().to_bits(), Ordering::SeqCst);
+		self.shared.duration.store(
+			(num_frames(&self.frames, self.slice) as f64 / self.sample_rate as f64).to_bits(),
 			Ordering::SeqCst,
 		);
-		self.read_commands();
+		self.time_since_block_start = 0.0;
+		self.shared.remaining_loops.store(
+			encode_remaining_loops(self.transport.remaining_loops),
+			Ordering::SeqCst,
+		);
+		self.shared.set_stats(PlaybackStats {
+			loops_completed: self.transport.loops_completed,
+			frames_played: self.frames_played,
+			clipped: self.clipped,
+		});
+		self.clipped = false;
 	}
 
 	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
-		// update parameters
-		self.volume.update(dt * out.len() as f64, info);
-		self.playback_rate.update(dt * out.len() as f64, info);
-		self.panning.update(dt * out.len() as f64, info);
-		let changed_playback_state = self
-			.playback_state_manager
-			.update(dt * out.len() as f64, info);
-		if changed_playback_state {
-			self.update_shared_playback_state();
-		}
-
-		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
-		if will_never_start {
-			self.playback_state_manager.mark_as_stopped();
-			self.update_shared_playback_state();
-		}
-		if self.start_time != StartTime::Immediate {
-			out.fill(Frame::ZERO);
+		// a start time measured in frames can be reached partway through
+		// the chunk, so process the frames before and after it separately
+		if let Some(split_index) = self.start_time.frames_into_chunk(out.len()) {
+			let (before, after) = out.split_at_mut(split_index);
+			self.process(before, dt, info);
+			self.process(after, dt, info);
 			return;
 		}
-
-		if !self.playback_state_manager.playback_state().is_advancing() {
+		if !self.start_chunk(out.len(), dt, info) {
 			out.fill(Frame::ZERO);
 			return;
 		}
@@ -224,33 +668,133 @@ impl Sound for StaticSound {
 		// play back audio
 		let num_frames = out.len();
 		for (i, frame) in out.iter_mut().enumerate() {
-			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
-			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
-			let fade_volume = self
-				.playback_state_manager
-				.interpolated_fade_volume(time_in_chunk)
-				.as_amplitude();
-			let panning = self.panning.interpolated_value(time_in_chunk);
-			let playback_rate = self.playback_rate.interpolated_value(time_in_chunk);
-			let resampler_out = self.resampler.get(self.fractional_position as f32);
-			self.fractional_position += self.sample_rate as f64 * playback_rate.0.abs() * dt;
-			while self.fractional_position >= 1.0 {
-				self.fractional_position -= 1.0;
-				self.update_position();
+			// the sound can finish partway through the chunk, in which
+			// case the rest of the chunk is silence
+			if self.playback_state_manager.playback_state() != PlaybackState::Stopped {
+				self.frames_played += 1;
 			}
-			*frame = (resampler_out * fade_volume * volume).panned(panning);
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			*frame = self.next_frame(time_in_chunk, dt);
 		}
+		self.clipped |= out
+			.iter()
+			.any(|frame| frame.left.abs() > 1.0 || frame.right.abs() > 1.0);
 	}
 
 	fn finished(&self) -> bool {
 		self.playback_state_manager.playback_state() == PlaybackState::Stopped
 	}
+
+	fn playback_state(&self) -> PlaybackState {
+		self.playback_state_manager.playback_state()
+	}
+
+	fn take_requested_track(&mut self) -> Option<(TrackId, Tween)> {
+		self.rerouting.take_requested_track()
+	}
+
+	fn on_move_finished(&mut self, moved: bool) {
+		if moved {
+			self.track_member.rejoin();
+		}
+	}
+
+	fn sends(&self) -> &[(SendTrackId, Parameter<Decibels>)] {
+		self.send_levels.levels()
+	}
+}
+
+impl Drop for StaticSound {
+	fn drop(&mut self) {
+		self.release_voice();
+	}
+}
+
+/// Creates a transport that plays the sound data according to its settings.
+#[must_use]
+fn create_transport(data: &StaticSoundData) -> Transport {
+	let settings = data.settings;
+	let mut transport = Transport::new(
+		settings.start_position.into_samples(data.sample_rate),
+		data.loop_region_to_play(),
+		settings.reverse,
+		data.sample_rate,
+		data.num_frames(),
+	);
+	transport.set_loop_count(settings.loop_count);
+	transport.loop_mode = settings.loop_mode;
+	transport.loop_crossfade_frames = settings
+		.loop_crossfade
+		.map(|duration| (duration.as_secs_f64() * data.sample_rate as f64).round() as usize)
+		.unwrap_or_default();
+	transport
+}
+
+#[must_use]
+fn create_fade_out(data: &StaticSoundData) -> Option<FadeOut> {
+	data.settings
+		.fade_out_tween
+		.map(|tween| FadeOut::new(tween, data.sample_rate))
+}
+
+/// Creates a parameter that starts at `start` and moves to `1.0`
+/// using the tween.
+#[must_use]
+fn pitch_envelope(start: PlaybackRate, tween: Tween) -> Parameter<PlaybackRate> {
+	let mut parameter = Parameter::new(Value::Fixed(start), PlaybackRate(1.0));
+	parameter.set(Value::Fixed(PlaybackRate(1.0)), tween);
+	parameter
+}
+
+/// Picks the random volume offset and playback rate factor for one play
+/// of a sound.
+#[must_use]
+fn pick_jitter(settings: &StaticSoundSettings) -> (Decibels, f64) {
+	if settings.volume_jitter.0 == 0.0 && settings.playback_rate_jitter.0 == 0.0 {
+		return (Decibels(0.0), 1.0);
+	}
+	let mut random = settings
+		.jitter_seed
+		.map(Random::new)
+		.unwrap_or_else(Random::from_entropy);
+	let volume_jitter = Decibels(settings.volume_jitter.0 * random.next_bipolar() as f32);
+	let playback_rate_jitter = PlaybackRate::from(Semitones(
+		settings.playback_rate_jitter.0 * random.next_bipolar(),
+	))
+	.0;
+	(volume_jitter, playback_rate_jitter)
+}
+
+/// Converts between an index into the underlying frames and an index
+/// measured in the direction the sound plays in. The conversion is its
+/// own inverse.
+#[must_use]
+fn directional_index(index: usize, reverse: bool, num_frames: usize) -> usize {
+	if reverse {
+		num_frames.saturating_sub(1).saturating_sub(index)
+	} else {
+		index
+	}
 }
 
 #[derive(Debug)]
 pub(super) struct Shared {
 	state: AtomicU8,
 	position: AtomicU64,
+	/// The position including the fraction between frames, which
+	/// is used to sync other sounds to this one.
+	precise_position: AtomicU64,
+	/// How fast the precise position is moving (in seconds of audio
+	/// per second), or `0.0` if playback isn't advancing.
+	velocity: AtomicU64,
+	/// The length of the audio being played (in seconds), which can
+	/// change when the playback region changes or queued audio starts.
+	duration: AtomicU64,
+	remaining_loops: AtomicU64,
+	loops_completed: AtomicU64,
+	frames_played: AtomicU64,
+	clipped: AtomicBool,
+	finished: Arc<Finished>,
 }
 
 impl Shared {
@@ -274,4 +818,57 @@ impl Shared {
 	pub fn position(&self) -> f64 {
 		f64::from_bits(self.position.load(Ordering::SeqCst))
 	}
+
+	pub fn precise_position(&self) -> f64 {
+		f64::from_bits(self.precise_position.load(Ordering::SeqCst))
+	}
+
+	pub fn duration(&self) -> f64 {
+		f64::from_bits(self.duration.load(Ordering::SeqCst))
+	}
+
+	pub fn set_position(&self, position: f64) {
+		self.position.store(position.to_bits(), Ordering::SeqCst);
+	}
+
+	/// Returns the position of the audio that was playing `latency`
+	/// seconds ago, assuming the playback rate hasn't changed since then.
+	pub fn position_before(&self, latency: f64) -> f64 {
+		let velocity = f64::from_bits(self.velocity.load(Ordering::SeqCst));
+		(self.precise_position() - latency * velocity).max(0.0)
+	}
+
+	pub fn finished(&self) -> Arc<Finished> {
+		self.finished.clone()
+	}
+
+	pub fn remaining_loops(&self) -> Option<u32> {
+		match self.remaining_loops.load(Ordering::SeqCst) {
+			u64::MAX => None,
+			remaining_loops => Some(remaining_loops as u32),
+		}
+	}
+
+	pub fn stats(&self) -> PlaybackStats {
+		PlaybackStats {
+			loops_completed: self.loops_completed.load(Ordering::SeqCst),
+			frames_played: self.frames_played.load(Ordering::SeqCst),
+			clipped: self.clipped.load(Ordering::SeqCst),
+		}
+	}
+
+	pub fn set_stats(&self, stats: PlaybackStats) {
+		self.loops_completed
+			.store(stats.loops_completed, Ordering::SeqCst);
+		self.frames_played
+			.store(stats.frames_played, Ordering::SeqCst);
+		self.clipped.store(stats.clipped, Ordering::SeqCst);
+	}
+}
+
+/// Encodes the number of remaining loops so it can be stored in an atomic,
+/// using `u64::MAX` to represent looping indefinitely.
+#[must_use]
+fn encode_remaining_loops(remaining_loops: Option<u32>) -> u64 {
+	remaining_loops.map_or(u64::MAX, u64::from)
 }
@@ -58,12 +58,22 @@ impl Resampler {
 	/// The user mainly hears a frame between `self.frames[1]` and
 	/// `self.frames[2]`. `self.frames[0]` and `self.frames[3]`
 	/// are used to provide additional information to the interpolation
-	/// algorithm to get a smoother result.
+	/// algorithm to get a smoother result. This is why the output lags
+	/// [`RESAMPLER_LOOKAHEAD_FRAMES`](crate::sound::static_sound::RESAMPLER_LOOKAHEAD_FRAMES)
+	/// frames behind the most recently pushed frame.
 	#[must_use]
 	pub fn current_frame_index(&self) -> usize {
 		self.frames[1].frame_index
 	}
 
+	/// Changes the frame indices of the recent frames to be measured
+	/// from a different starting frame in the source sound.
+	pub fn rebase(&mut self, old_start: usize, new_start: usize) {
+		for frame in &mut self.frames {
+			frame.frame_index = (frame.frame_index + old_start).saturating_sub(new_start);
+		}
+	}
+
 	#[must_use]
 	pub fn empty(&self) -> bool {
 		self.time_until_empty == 0
@@ -5,11 +5,12 @@ use crate::{
 	frame::Frame,
 	info::MockInfoBuilder,
 	sound::{
-		static_sound::{StaticSoundData, StaticSoundSettings},
-		PlaybackState, Sound,
+		static_sound::{PlaybackStats, StaticSoundData, StaticSoundSettings},
+		ChannelMixMode, FinishReason, LoopMode, PlaybackPosition, PlaybackState, Sound, SoundData,
 	},
 	test_helpers::expect_frame_soon,
-	Decibels, Panning, StartTime, Tween,
+	voice_group::VoiceStealPolicy,
+	Decibels, Easing, Mapping, Panning, PlaybackRate, StartTime, Tween, Value,
 };
 
 /// Tests that a `StaticSound` will play all of its samples before finishing.
@@ -24,6 +25,7 @@ fn plays_all_samples() {
 		]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -54,6 +56,42 @@ fn plays_all_samples() {
 	);
 }
 
+/// Tests that a `StaticSound` can start paused and then play from
+/// the beginning as soon as it's resumed.
+#[test]
+fn start_paused() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (1..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().start_paused(true),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	assert_eq!(handle.state(), PlaybackState::Paused);
+	for _ in 0..3 {
+		sound.on_start_processing();
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::ZERO
+		);
+	}
+	assert_eq!(handle.state(), PlaybackState::Paused);
+	assert_eq!(handle.position(), 0.0);
+
+	handle.resume(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	for i in 1..4 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
 /// Tests that a `StaticSound` correctly reports its playback state
 /// to be queried by StaticSoundHandle::state.
 #[test]
@@ -63,6 +101,7 @@ fn reports_playback_state() {
 		frames: Arc::new([Frame::from_mono(0.0); 10]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -85,6 +124,7 @@ fn reports_playback_position() {
 		frames: Arc::new([Frame::from_mono(0.0); 10]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -98,6 +138,57 @@ fn reports_playback_position() {
 	}
 }
 
+/// Tests that a `StaticSound` notifies its `FinishedReceiver`s when it
+/// reaches the end of the audio.
+#[test]
+fn notifies_when_reaching_end() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 3]),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, handle) = data.split();
+	let finished = handle.on_finished();
+
+	for _ in 0..3 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+		assert_eq!(finished.try_recv(), None);
+	}
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+	assert_eq!(finished.try_recv(), Some(FinishReason::ReachedEnd));
+}
+
+/// Tests that a `StaticSound` notifies its `FinishedReceiver`s when it
+/// is stopped using its handle.
+#[test]
+fn notifies_when_stopped() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 100]),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	let finished = handle.on_finished();
+
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	handle.stop(Tween {
+		duration: Duration::from_secs(2),
+		..Default::default()
+	});
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert_eq!(finished.try_recv(), None);
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+	assert_eq!(finished.try_recv(), Some(FinishReason::Stopped));
+}
+
 /// Tests that a `StaticSound` fades out fully before pausing
 /// and fades back in when resuming.
 #[test]
@@ -108,6 +199,7 @@ fn pauses_and_resumes_with_fades() {
 		frames: Arc::new([Frame::from_mono(1.0); 100]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -218,6 +310,7 @@ fn stops_with_fade_out() {
 		frames: Arc::new([Frame::from_mono(1.0); 100]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -287,6 +380,7 @@ fn waits_for_start_time() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -358,6 +452,7 @@ fn stops_if_waiting_on_missing_clock() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -391,6 +486,7 @@ fn continues_when_clock_stops() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -422,6 +518,7 @@ fn immediate_playback_state_change_with_start_time() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -465,6 +562,7 @@ fn resume_at() {
 		frames: (1..100).map(|_| Frame::from_mono(1.0)).collect(),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -506,6 +604,199 @@ fn resume_at() {
 	assert_eq!(handle.state(), PlaybackState::Playing);
 }
 
+/// Tests that a `StaticSound` can be paused until a certain start time.
+#[test]
+fn pause_until() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (1..100).map(|_| Frame::from_mono(1.0)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	let clock_info = |ticking, ticks| {
+		let mut builder = MockInfoBuilder::new();
+		let clock_id = builder.add_clock(ticking, ticks, 0.0);
+		(clock_id, builder.build())
+	};
+	let (clock_id, info) = clock_info(true, 0);
+
+	handle.pause_until(
+		StartTime::ClockTime(ClockTime {
+			clock: clock_id,
+			ticks: 1,
+			fraction: 0.0,
+		}),
+		Tween {
+			duration: Duration::ZERO,
+			..Default::default()
+		},
+	);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Pausing);
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::WaitingToResume);
+	assert_eq!(
+		sound.process_one(1.0, &info),
+		Frame::from_mono(0.0).panned(Panning::CENTER)
+	);
+
+	// a stopped clock never reaches the start time
+	let (_, info) = clock_info(false, 1);
+	for _ in 0..3 {
+		sound.process_one(1.0, &info);
+		sound.on_start_processing();
+		assert_eq!(handle.state(), PlaybackState::WaitingToResume);
+	}
+
+	let (_, info) = clock_info(true, 1);
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Playing);
+	assert_eq!(
+		sound.process_one(1.0, &info),
+		Frame::from_mono(1.0).panned(Panning::CENTER)
+	);
+}
+
+/// Tests that resuming a `StaticSound` manually cancels a resume scheduled
+/// by `pause_until`.
+#[test]
+fn resume_cancels_pause_until() {
+	let mut info_builder = MockInfoBuilder::new();
+	let clock_id = info_builder.add_clock(true, 0, 0.0);
+	let info = info_builder.build();
+
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (1..100).map(|_| Frame::from_mono(1.0)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	let tween = Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	};
+
+	handle.pause_until(
+		StartTime::ClockTime(ClockTime {
+			clock: clock_id,
+			ticks: 1,
+			fraction: 0.0,
+		}),
+		tween,
+	);
+	sound.on_start_processing();
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::WaitingToResume);
+
+	handle.resume(tween);
+	sound.on_start_processing();
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Playing);
+
+	handle.pause(tween);
+	sound.on_start_processing();
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Paused);
+
+	// the clock reaching the old start time shouldn't resume the sound
+	let info = {
+		let mut builder = MockInfoBuilder::new();
+		builder.add_clock(true, 1, 0.0);
+		builder.build()
+	};
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Paused);
+}
+
+/// Tests that a `StaticSound` can be set to stop at a certain start time.
+#[test]
+fn stop_at() {
+	let mut info_builder = MockInfoBuilder::new();
+	let clock_id = info_builder.add_clock(true, 0, 0.0);
+	let info = info_builder.build();
+
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (1..100).map(|_| Frame::from_mono(1.0)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+
+	handle.stop_at(
+		StartTime::ClockTime(ClockTime {
+			clock: clock_id,
+			ticks: 1,
+			fraction: 0.0,
+		}),
+		Tween {
+			duration: Duration::from_secs(2),
+			..Default::default()
+		},
+	);
+	sound.on_start_processing();
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Playing);
+
+	let info = {
+		let mut builder = MockInfoBuilder::new();
+		builder.add_clock(true, 1, 0.0);
+		builder.build()
+	};
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Stopping);
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
+/// Tests that a `StaticSound` discards a scheduled pause if the clock
+/// it's waiting on is removed.
+#[test]
+fn pause_at_missing_clock() {
+	let clock_id = {
+		let mut info_builder = MockInfoBuilder::new();
+		info_builder.add_clock(true, 0, 0.0)
+	};
+
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (1..100).map(|_| Frame::from_mono(1.0)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+
+	handle.pause_at(
+		StartTime::ClockTime(ClockTime {
+			clock: clock_id,
+			ticks: 1,
+			fraction: 0.0,
+		}),
+		Tween::default(),
+	);
+	sound.on_start_processing();
+	for _ in 0..3 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+		sound.on_start_processing();
+		assert_eq!(handle.state(), PlaybackState::Playing);
+	}
+}
+
 /// Tests that a `StaticSound` can be played partially.
 #[test]
 #[allow(clippy::float_cmp)]
@@ -515,6 +806,7 @@ fn start_position() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().start_position(3.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -538,6 +830,7 @@ fn out_of_bounds_start_position() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().start_position(15.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 	sound.process_one(1.0, &MockInfoBuilder::new().build());
@@ -553,6 +846,7 @@ fn loops_forward() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().loop_region(Some((3.0..6.0).into())),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -581,55 +875,426 @@ fn loops_forward() {
 	);
 }
 
-/// Tests that the volume of a `StaticSound` can be adjusted.
+/// Tests that a `StaticSound` plays its loop region the specified
+/// number of times and then continues to the end of the sound.
 #[test]
-#[allow(clippy::float_cmp)]
-fn volume() {
+fn loop_count() {
 	let data = StaticSoundData {
 		sample_rate: 1,
-		frames: Arc::new([Frame::from_mono(1.0); 10]),
-		settings: StaticSoundSettings::new().volume(-6.0),
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.loop_region(3.0..6.0)
+			.loop_count(3),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
-	assert_eq!(
-		sound.process_one(1.0, &MockInfoBuilder::new().build()),
-		Frame::from_mono(Decibels(-6.0).as_amplitude()).panned(Panning::CENTER)
-	);
+	for i in [0, 1, 2, 3, 4, 5, 3, 4, 5, 3, 4, 5, 6, 7, 8, 9] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
 }
 
-/// Tests that the volume of a `StaticSound` can be changed
-/// after the sound is started.
+/// Tests that loop regions given in samples are applied exactly, whether
+/// they're set in the settings or from the handle.
 #[test]
-fn set_volume() {
+fn loops_at_exact_samples() {
+	const SAMPLE_RATE: u32 = 44100;
+	const LOOP_START: usize = 12345;
+	const LOOP_END: usize = 13579;
+	const CHUNK_SIZE: usize = 512;
+	let loop_region = PlaybackPosition::Samples(LOOP_START)..PlaybackPosition::Samples(LOOP_END);
 	let data = StaticSoundData {
-		sample_rate: 1,
-		frames: Arc::new([Frame::from_mono(1.0); 10]),
+		sample_rate: SAMPLE_RATE,
+		frames: (0..20000).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
-	let (mut sound, mut handle) = data.split();
-
-	assert_eq!(
-		sound.process_one(1.0, &MockInfoBuilder::new().build()),
-		Frame::from_mono(1.0).panned(Panning::CENTER)
-	);
-	handle.set_volume(
-		-6.0,
-		Tween {
-			duration: Duration::ZERO,
-			..Default::default()
-		},
-	);
-	sound.on_start_processing();
-	expect_frame_soon(
-		Frame::from_mono(Decibels(-6.0).as_amplitude()).panned(Panning::CENTER),
-		&mut sound,
-	);
+	let (mut from_settings, _) = data.loop_region(loop_region.clone()).split();
+	let (mut from_handle, mut handle) = data.split();
+	handle.set_loop_region(loop_region);
+	let expected = (0..LOOP_END)
+		.chain((0..3).flat_map(|_| LOOP_START..LOOP_END))
+		.map(|i| Frame::from_mono(i as f32).panned(Panning::CENTER))
+		.collect::<Vec<_>>();
+
+	let dt = 1.0 / SAMPLE_RATE as f64;
+	for sound in [&mut from_settings, &mut from_handle] {
+		sound.on_start_processing();
+		let mut output = vec![Frame::ZERO; expected.len().next_multiple_of(CHUNK_SIZE)];
+		for chunk in output.chunks_mut(CHUNK_SIZE) {
+			sound.process(chunk, dt, &MockInfoBuilder::new().build());
+		}
+		assert_eq!(output[..expected.len()], expected[..]);
+	}
 }
 
-/// Tests that the panning of a `StaticSound` can be adjusted.
+/// Tests that a `StaticSound` reports how many times it's looped, how
+/// many frames it's played, and whether its last block clipped.
+#[test]
+fn reports_stats() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().loop_region(3.0..6.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, handle) = data.split();
+	assert_eq!(handle.stats(), PlaybackStats::default());
+
+	// frame 1 is below the clipping threshold once it's panned to the center
+	let mut out = [Frame::ZERO; 2];
+	sound.process(&mut out, 1.0, &MockInfoBuilder::new().build());
+	sound.on_start_processing();
+	assert_eq!(
+		handle.stats(),
+		PlaybackStats {
+			loops_completed: 0,
+			frames_played: 2,
+			clipped: false,
+		}
+	);
+
+	let mut out = [Frame::ZERO; 10];
+	sound.process(&mut out, 1.0, &MockInfoBuilder::new().build());
+	sound.on_start_processing();
+	let stats = handle.stats();
+	assert_eq!(stats.frames_played, 12);
+	assert!(stats.loops_completed > 0);
+	assert!(stats.clipped);
+
+	// the clipping flag only covers the most recent block
+	sound.on_start_processing();
+	assert!(!handle.stats().clipped);
+}
+
+/// Tests that the frames of a chunk after a sound finishes aren't
+/// counted as played.
+#[test]
+fn frames_played_stops_counting_when_finished() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, handle) = data.split();
+	let mut out = [Frame::ZERO; 100];
+	sound.process(&mut out, 1.0, &MockInfoBuilder::new().build());
+	sound.on_start_processing();
+	assert!(sound.finished());
+	// the ten frames of audio, plus one more while the resampler empties out
+	assert_eq!(handle.stats().frames_played, 11);
+}
+
+/// Tests that a `StaticSound` continues directly into queued audio
+/// when it reaches the end.
+#[test]
+#[allow(clippy::float_cmp)]
+fn queue_next() {
+	let data = |frames: &[f32], settings: StaticSoundSettings| StaticSoundData {
+		sample_rate: 1,
+		frames: frames.iter().copied().map(Frame::from_mono).collect(),
+		settings,
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data(&[0.0, 1.0, 2.0], StaticSoundSettings::new()).split();
+	handle.queue_next(data(&[10.0, 11.0], StaticSoundSettings::new()));
+	// the most recently queued audio replaces audio that hasn't played yet
+	handle.queue_next(data(
+		&[20.0, 21.0, 22.0],
+		StaticSoundSettings::new().loop_region(1.0..).loop_count(2),
+	));
+
+	for expected in [0.0, 1.0, 2.0, 20.0, 21.0, 22.0, 21.0, 22.0] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(expected).panned(Panning::CENTER)
+		);
+		assert!(!sound.finished());
+	}
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+}
+
+/// Tests that the loop count of a `StaticSound` can be changed
+/// during playback.
+#[test]
+fn set_loop_count() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().loop_region(3.0..6.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	sound.on_start_processing();
+	assert_eq!(handle.remaining_loops(), None);
+
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(0.0).panned(Panning::CENTER)
+	);
+	handle.set_loop_count(2);
+	sound.on_start_processing();
+	sound.on_start_processing();
+	assert_eq!(handle.remaining_loops(), Some(1));
+	for i in [1, 2, 3, 4, 5, 3, 4, 5, 6, 7, 8, 9] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that a `StaticSound` crossfades the end of its loop region into
+/// the start when looping.
+#[test]
+fn loop_crossfade() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.loop_region(2.0..8.0)
+			.loop_crossfade(Duration::from_secs(2)),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+
+	let blended = 7.0 * std::f32::consts::FRAC_1_SQRT_2 + 3.0 * std::f32::consts::FRAC_1_SQRT_2;
+	for expected in [
+		0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, blended, 4.0, 5.0, 6.0, blended, 4.0,
+	] {
+		let frame = sound.process_one(1.0, &MockInfoBuilder::new().build());
+		let expected = Frame::from_mono(expected).panned(Panning::CENTER);
+		assert!((frame.left - expected.left).abs() < 0.0001);
+		assert!((frame.right - expected.right).abs() < 0.0001);
+	}
+}
+
+/// Tests that a `StaticSound` bounces back and forth within its loop region
+/// when using `LoopMode::PingPong`.
+#[test]
+fn loops_ping_pong() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.loop_region(3.0..6.0)
+			.loop_mode(LoopMode::PingPong),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+
+	for i in [0, 1, 2, 3, 4, 5, 4, 3, 4, 5, 4, 3] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that a `StaticSound` using `LoopMode::PingPong` continues to the end
+/// of the sound after the specified number of loops.
+#[test]
+fn ping_pong_loop_count() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.loop_region(3.0..6.0)
+			.loop_mode(LoopMode::PingPong)
+			.loop_count(3),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+
+	for i in [0, 1, 2, 3, 4, 5, 4, 3, 4, 5, 6, 7, 8, 9] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+}
+
+/// Tests that the volume of a `StaticSound` can be adjusted.
+#[test]
+#[allow(clippy::float_cmp)]
+fn volume() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().volume(-6.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(Decibels(-6.0).as_amplitude()).panned(Panning::CENTER)
+	);
+}
+
+/// Tests that a `StaticSound` with volume jitter plays at a random volume
+/// within the given range, and that the same seed always picks the same volume.
+#[test]
+fn volume_jitter() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().volume(-6.0).volume_jitter(3.0),
+		slice: None,
+		metadata: None,
+	};
+	let min = Frame::from_mono(Decibels(-9.0).as_amplitude()).panned(Panning::CENTER);
+	let max = Frame::from_mono(Decibels(-3.0).as_amplitude()).panned(Panning::CENTER);
+	let mut outputs = vec![];
+	for seed in 0..10 {
+		let (mut sound, _) = data.jitter_seed(seed).split();
+		let out = sound.process_one(1.0, &MockInfoBuilder::new().build());
+		assert!(out.left >= min.left && out.left <= max.left);
+		outputs.push(out);
+	}
+	assert!(outputs.iter().any(|out| *out != outputs[0]));
+
+	let (mut sound_a, _) = data.jitter_seed(5).split();
+	let (mut sound_b, _) = data.jitter_seed(5).split();
+	assert_eq!(
+		sound_a.process_one(1.0, &MockInfoBuilder::new().build()),
+		sound_b.process_one(1.0, &MockInfoBuilder::new().build())
+	);
+}
+
+/// Tests that volume jitter is applied on top of a volume linked to a modulator.
+#[test]
+fn volume_jitter_with_modulator() {
+	let mut info_builder = MockInfoBuilder::new();
+	let modulator_id = info_builder.add_modulator(-6.0);
+	let info = info_builder.build();
+	let settings = StaticSoundSettings::new()
+		.volume(Value::FromModulator {
+			id: modulator_id,
+			mapping: Mapping {
+				input_range: (-6.0, 0.0),
+				output_range: (Decibels(-6.0), Decibels(0.0)),
+				easing: Easing::Linear,
+			},
+		})
+		.volume_jitter(3.0)
+		.jitter_seed(1);
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 10]),
+		settings,
+		slice: None,
+		metadata: None,
+	};
+	let (mut jittered_sound, _) = data.clone().split();
+	let (mut fixed_sound, _) = data
+		.volume(Decibels(-6.0) + jittered_sound.volume_jitter)
+		.volume_jitter(0.0)
+		.split();
+	let jittered = jittered_sound.process_one(1.0, &info);
+	let fixed = fixed_sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!((jittered.left - fixed.left).abs() < 0.0001);
+}
+
+/// Tests that a `StaticSound` with playback rate jitter plays at a random
+/// speed within the given range.
+#[test]
+fn playback_rate_jitter() {
+	for seed in 0..10 {
+		let data = StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new([Frame::from_mono(1.0); 10]),
+			settings: StaticSoundSettings::new()
+				.playback_rate_jitter(12.0)
+				.jitter_seed(seed),
+			slice: None,
+			metadata: None,
+		};
+		let (sound, _) = data.split();
+		assert!(sound.playback_rate_jitter >= 0.5 && sound.playback_rate_jitter <= 2.0);
+	}
+}
+
+/// Tests that the volume of a `StaticSound` can be changed
+/// after the sound is started.
+#[test]
+fn set_volume() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(1.0).panned(Panning::CENTER)
+	);
+	handle.set_volume(
+		-6.0,
+		Tween {
+			duration: Duration::ZERO,
+			..Default::default()
+		},
+	);
+	sound.on_start_processing();
+	expect_frame_soon(
+		Frame::from_mono(Decibels(-6.0).as_amplitude()).panned(Panning::CENTER),
+		&mut sound,
+	);
+}
+
+/// Tests that a `StaticSound` combines its channels according to its
+/// channel mix mode.
+#[test]
+#[allow(clippy::float_cmp)]
+fn channel_mix_mode() {
+	for (channel_mix_mode, expected) in [
+		(ChannelMixMode::Stereo, Frame::new(1.0, 0.5)),
+		(
+			ChannelMixMode::MonoSum,
+			Frame::from_mono(1.5 * std::f32::consts::FRAC_1_SQRT_2),
+		),
+		(ChannelMixMode::LeftOnly, Frame::from_mono(1.0)),
+		(ChannelMixMode::RightOnly, Frame::from_mono(0.5)),
+	] {
+		let data = StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new([Frame::new(1.0, 0.5)]),
+			settings: StaticSoundSettings::new().channel_mix_mode(channel_mix_mode),
+			slice: None,
+			metadata: None,
+		};
+		let (mut sound, _) = data.split();
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			expected.panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that the panning of a `StaticSound` can be adjusted.
 #[test]
 #[allow(clippy::float_cmp)]
 fn panning() {
@@ -638,6 +1303,7 @@ fn panning() {
 		frames: Arc::new([Frame::from_mono(1.0); 10]),
 		settings: StaticSoundSettings::new().panning(Panning::LEFT),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -656,6 +1322,7 @@ fn set_panning() {
 		frames: Arc::new([Frame::from_mono(1.0); 10]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -683,6 +1350,7 @@ fn playback_rate() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().playback_rate(2.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -706,6 +1374,7 @@ fn set_playback_rate() {
 		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -754,6 +1423,7 @@ fn interpolates_samples() {
 		]),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -779,6 +1449,7 @@ fn interpolates_samples_when_looping() {
 		frames: Arc::new([Frame::from_mono(10.0), Frame::from_mono(9.0)]),
 		settings: StaticSoundSettings::new().loop_region(Some((..).into())),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 	sound.process_one(1.5, &MockInfoBuilder::new().build());
@@ -796,6 +1467,7 @@ fn seek_to() {
 		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 	handle.seek_to(15.0);
@@ -811,6 +1483,7 @@ fn seek_by() {
 		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().start_position(10.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 	handle.seek_by(5.0);
@@ -821,21 +1494,601 @@ fn seek_by() {
 	expect_frame_soon(Frame::from_mono(20.0).panned(Panning::CENTER), &mut sound);
 }
 
-/// Tests that a `StaticSound` can play in reverse.
+/// Tests that seeking a paused `StaticSound` takes effect on the very
+/// first frame after resuming, and that the handle reports the new
+/// position right away.
 #[test]
-fn reverse() {
+fn seek_while_paused() {
 	let data = StaticSoundData {
 		sample_rate: 1,
-		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
-		settings: StaticSoundSettings::new().reverse(true),
+		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
-	let (mut sound, _) = data.split();
+	let (mut sound, mut handle) = data.split();
+	for i in 0..5 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	handle.pause(Tween::default());
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert_eq!(handle.state(), PlaybackState::Paused);
 
-	for i in (4..=9).rev() {
+	handle.seek_to(30.0);
+	assert_eq!(handle.position(), 30.0);
+	sound.on_start_processing();
+	handle.seek_to(50.0);
+	assert_eq!(handle.position(), 50.0);
+	sound.on_start_processing();
+	assert_eq!(handle.position(), 50.0);
+
+	handle.resume(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	for i in 50..55 {
 		assert_eq!(
 			sound.process_one(1.0, &MockInfoBuilder::new().build()),
 			Frame::from_mono(i as f32).panned(Panning::CENTER)
 		);
 	}
 }
+
+/// Tests that the position reported by the handle after seeking
+/// past the end of a `StaticSound` is clamped to the end.
+#[test]
+fn seek_past_end_clamps_reported_position() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	handle.seek_by(-5.0);
+	assert_eq!(handle.position(), 0.0);
+	handle.seek_to(50.0);
+	assert_eq!(handle.position(), 10.0);
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+}
+
+/// Tests that seeking by an amount of time is measured from the
+/// position reported by the handle.
+#[test]
+fn seek_by_from_reported_position() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().start_position(10.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	sound.on_start_processing();
+	assert_eq!(handle.position(), 10.0);
+	handle.seek_by(5.0);
+	assert_eq!(handle.position(), 15.0);
+	sound.on_start_processing();
+	assert_eq!(handle.position(), 15.0);
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(15.0).panned(Panning::CENTER)
+	);
+}
+
+/// Tests that a `StaticSound` synced with another sound starts at the same
+/// playback position as that sound.
+#[test]
+fn synced_with() {
+	for playback_rate in [1.0, 0.5, 1.75] {
+		let data = StaticSoundData {
+			sample_rate: 1,
+			frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
+			settings: StaticSoundSettings::new().playback_rate(playback_rate),
+			slice: None,
+			metadata: None,
+		};
+		let (mut leader, leader_handle) = data.clone().split();
+		for _ in 0..7 {
+			leader.on_start_processing();
+			leader.process_one(1.0, &MockInfoBuilder::new().build());
+		}
+		let (mut follower, _) = data
+			.start_position(50.0)
+			.synced_with(&leader_handle)
+			.into_sound()
+			.unwrap();
+		for _ in 0..10 {
+			leader.on_start_processing();
+			follower.on_start_processing();
+			let leader_out = leader.process_one(1.0, &MockInfoBuilder::new().build());
+			let follower_out = follower.process_one(1.0, &MockInfoBuilder::new().build());
+			assert!((leader_out.left - follower_out.left).abs() < 0.0001);
+		}
+	}
+}
+
+/// Tests that a `StaticSound` synced with another sound accounts for time
+/// passing before the synced sound starts.
+#[test]
+fn synced_with_delayed_start() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut leader, leader_handle) = data.clone().split();
+	let (mut follower, _) = data
+		.start_time(StartTime::Delayed(Duration::from_secs(4)))
+		.synced_with(&leader_handle)
+		.into_sound()
+		.unwrap();
+	leader.on_start_processing();
+	follower.on_start_processing();
+	let mut leader_out = [Frame::ZERO; 10];
+	let mut follower_out = [Frame::ZERO; 10];
+	for i in 0..10 {
+		leader_out[i] = leader.process_one(1.0, &MockInfoBuilder::new().build());
+		follower_out[i] = follower.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	assert_eq!(follower_out[0], Frame::ZERO);
+	assert_eq!(leader_out[5..], follower_out[5..]);
+}
+
+/// Tests that starting a `StaticSound` in a full voice group quickly
+/// fades out another sound in the group.
+#[test]
+fn voice_group_steals_voice() {
+	let mut info_builder = MockInfoBuilder::new();
+	let voice_group = info_builder.add_voice_group(2, VoiceStealPolicy::Oldest);
+	let info = info_builder.build();
+	let data = StaticSoundData {
+		sample_rate: 100,
+		frames: Arc::new([Frame::from_mono(1.0); 100]),
+		settings: StaticSoundSettings::new().voice_group(voice_group),
+		slice: None,
+		metadata: None,
+	};
+	let (mut first, first_handle) = data.clone().split();
+	let (mut second, second_handle) = data.clone().split();
+	first.process_one(0.01, &info);
+	second.process_one(0.01, &info);
+
+	let (mut third, third_handle) = data.split();
+	third.process_one(0.01, &info);
+	for _ in 0..3 {
+		for sound in [&mut first, &mut second, &mut third] {
+			sound.on_start_processing();
+			sound.process_one(0.01, &info);
+		}
+	}
+	assert_eq!(first_handle.state(), PlaybackState::Stopped);
+	assert_eq!(
+		first_handle.on_finished().try_recv(),
+		Some(FinishReason::Stopped)
+	);
+	assert_eq!(second_handle.state(), PlaybackState::Playing);
+	assert_eq!(third_handle.state(), PlaybackState::Playing);
+}
+
+/// Tests that a `StaticSound` frees up its voice when it finishes.
+#[test]
+fn voice_group_releases_voice() {
+	let mut info_builder = MockInfoBuilder::new();
+	let voice_group = info_builder.add_voice_group(1, VoiceStealPolicy::Oldest);
+	let info = info_builder.build();
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 2]),
+		settings: StaticSoundSettings::new().voice_group(voice_group),
+		slice: None,
+		metadata: None,
+	};
+	let (mut first, _) = data.clone().split();
+	while !first.finished() {
+		first.process_one(1.0, &info);
+	}
+	let voice_group = info.voice_group(voice_group).unwrap();
+	assert_eq!(voice_group.num_playing(), 0);
+	let (mut second, _) = data.split();
+	second.process_one(1.0, &info);
+	assert_eq!(voice_group.num_playing(), 1);
+	drop(second);
+	assert_eq!(voice_group.num_playing(), 0);
+}
+
+/// Tests that a `StaticSound` can play in reverse.
+#[test]
+fn reverse() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().reverse(true),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+
+	for i in (4..=9).rev() {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that a reversed `StaticSound` wraps from the start of the loop
+/// region back to the end.
+#[test]
+fn loops_in_reverse() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.reverse(true)
+			.loop_region(Some((3.0..6.0).into())),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+
+	for i in [9, 8, 7, 6, 5, 4, 3, 5, 4, 3, 5] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that a negative playback rate plays a reversed `StaticSound` forward.
+#[test]
+fn reverse_with_negative_playback_rate() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.reverse(true)
+			.playback_rate(-1.0)
+			.start_position(5.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+
+	for i in 4..10 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that seeking a reversed `StaticSound` is measured from the end
+/// of the sound.
+#[test]
+fn seek_to_reversed() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().reverse(true),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	handle.seek_to(15.0);
+	sound.on_start_processing();
+	expect_frame_soon(Frame::from_mono(84.0).panned(Panning::CENTER), &mut sound);
+}
+
+/// Tests that a reversed `StaticSound` reports its playback position
+/// measured from the end of the sound.
+#[test]
+#[allow(clippy::float_cmp)]
+fn reports_reversed_playback_position() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(0.0); 10]),
+		settings: StaticSoundSettings::new().reverse(true),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, handle) = data.split();
+
+	for i in 0..10 {
+		assert_eq!(handle.position(), i as f64);
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+		sound.on_start_processing();
+	}
+}
+
+/// Tests that a `StaticSound` with an empty slice finishes immediately,
+/// even if it's set to loop.
+#[test]
+fn empty_slice_finishes_immediately() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().loop_region(..),
+		slice: None,
+		metadata: None,
+	}
+	.slice(4.0..4.0);
+	let (mut sound, _) = data.split();
+	for _ in 0..4 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::ZERO
+		);
+	}
+	assert!(sound.finished());
+}
+
+/// Tests that a `StaticSound` fades out as playback approaches the end
+/// of the sound, but only after the last loop.
+#[test]
+#[allow(clippy::float_cmp)]
+fn fades_out_at_end() {
+	let fade_out_tween = Tween {
+		duration: Duration::from_secs(4),
+		..Default::default()
+	};
+	let fade_out = [
+		1.0,
+		Decibels(-15.0).as_amplitude(),
+		Decibels(-30.0).as_amplitude(),
+		Decibels(-45.0).as_amplitude(),
+		0.0,
+	];
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 5]),
+		settings: StaticSoundSettings::new()
+			.loop_region(..)
+			.loop_count(2)
+			.fade_out_tween(fade_out_tween),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+	let mut out = vec![];
+	for _ in 0..10 {
+		out.push(sound.process_one(1.0, &MockInfoBuilder::new().build()));
+	}
+	let expected = [1.0; 5]
+		.into_iter()
+		.chain(fade_out)
+		.map(|amplitude| Frame::from_mono(amplitude).panned(Panning::CENTER))
+		.collect::<Vec<_>>();
+	assert_eq!(out, expected);
+}
+
+/// Tests that a `StaticSound` that loops indefinitely fades out at the end
+/// once the loop region is removed.
+#[test]
+#[allow(clippy::float_cmp)]
+fn fades_out_at_end_after_loop_disabled() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new([Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new()
+			.loop_region(..)
+			.fade_out_tween(Tween {
+				duration: Duration::from_secs(2),
+				..Default::default()
+			}),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	for _ in 0..15 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(1.0).panned(Panning::CENTER)
+		);
+	}
+	handle.set_loop_region(None);
+	sound.on_start_processing();
+	for _ in 15..18 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(1.0).panned(Panning::CENTER)
+		);
+	}
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(Decibels(-30.0).as_amplitude()).panned(Panning::CENTER)
+	);
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(0.0).panned(Panning::CENTER)
+	);
+}
+
+/// Tests that the playback region of a `StaticSound` can be changed
+/// while it's playing.
+#[test]
+#[allow(clippy::float_cmp)]
+fn set_playback_region() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	for i in 0..3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+
+	handle.set_playback_region(2.0..6.0);
+	sound.on_start_processing();
+	assert_eq!(handle.position(), 1.0);
+	for i in 3..6 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	for _ in 0..3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(0.0).panned(Panning::CENTER)
+		);
+	}
+	assert!(sound.finished());
+}
+
+/// Tests that a `StaticSound` finishes instead of jumping back when
+/// the playback region is moved to end before the current position.
+#[test]
+fn set_playback_region_before_position() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (1..=10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	for _ in 0..5 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+
+	handle.set_playback_region(..2.0);
+	sound.on_start_processing();
+	sound.process(
+		&mut [Frame::ZERO; 128],
+		1.0,
+		&MockInfoBuilder::new().build(),
+	);
+	assert!(sound.finished());
+}
+
+/// Tests that a `StaticSound` with a start time measured in frames
+/// starts on exactly that frame, even partway through a chunk.
+#[test]
+fn waits_for_frames() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (1..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().start_time(StartTime::Frames(6)),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+	let mut out = [Frame::ZERO; 8];
+	for chunk in out.chunks_mut(4) {
+		sound.on_start_processing();
+		sound.process(chunk, 1.0, &MockInfoBuilder::new().build());
+	}
+	assert_eq!(
+		out,
+		[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0]
+			.map(|amplitude| Frame::from_mono(amplitude).panned(Panning::CENTER))
+	);
+}
+
+/// Tests that a `StaticSound` sweeps its playback rate when it starts
+/// if it has a pitch envelope.
+#[test]
+fn pitch_envelope() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().pitch_envelope(
+			PlaybackRate(2.0),
+			Tween {
+				duration: Duration::from_secs(2),
+				..Default::default()
+			},
+		),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, _) = data.split();
+	sound.on_start_processing();
+	for expected in [0.0, 1.5, 2.5] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(expected).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that the pitch envelope of a `StaticSound` can be triggered
+/// using its handle.
+#[test]
+fn trigger_pitch_envelope() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+
+	handle.trigger_pitch_envelope(
+		PlaybackRate(2.0),
+		Tween {
+			duration: Duration::from_secs(2),
+			..Default::default()
+		},
+	);
+	sound.on_start_processing();
+	for expected in [1.0, 2.5, 3.5] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(expected).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that `StaticSoundHandle::audible_position` accounts for the
+/// output latency.
+#[test]
+fn audible_position() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().playback_rate(0.5),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	sound.on_start_processing();
+	for _ in 0..6 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	sound.on_start_processing();
+	assert_eq!(handle.position(), 3.0);
+	assert_eq!(handle.audible_position(Duration::ZERO), 3.0);
+	assert_eq!(handle.audible_position(Duration::from_secs(1)), 2.5);
+	assert_eq!(handle.audible_position(Duration::from_secs(100)), 0.0);
+
+	// the position doesn't move while the sound is paused
+	handle.pause(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	sound.on_start_processing();
+	assert_eq!(handle.audible_position(Duration::from_secs(1)), 3.0);
+}
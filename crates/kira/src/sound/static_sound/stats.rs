@@ -0,0 +1,17 @@
+/// Statistics about a static sound's playback so far.
+///
+/// These are updated once per block of audio, so they may lag slightly
+/// behind what's actually playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PlaybackStats {
+	/// How many times playback has jumped back to the start of the loop
+	/// region (or bounced off either end of it for ping-pong loops).
+	pub loops_completed: u64,
+	/// How many frames of audio the sound has produced while playing.
+	/// This doesn't include silence output while the sound is paused or
+	/// waiting to start.
+	pub frames_played: u64,
+	/// Whether any sample in the most recent block of audio had an
+	/// absolute value greater than `1.0`.
+	pub clipped: bool,
+}
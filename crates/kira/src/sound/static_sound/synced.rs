@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::sound::{Sound, SoundData};
+
+use super::{sound::Shared, SettingsError, StaticSoundData, StaticSoundHandle};
+
+/**
+A [`StaticSoundData`] that starts playing at the same playback position
+as another static sound.
+
+Created by [`StaticSoundData::synced_with`].
+*/
+#[derive(Debug, Clone)]
+pub struct SyncedStaticSoundData {
+	data: StaticSoundData,
+	target: Arc<Shared>,
+}
+
+impl SoundData for SyncedStaticSoundData {
+	type Error = SettingsError;
+
+	type Handle = StaticSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		// the start position is replaced by the other sound's position,
+		// so only the loop region needs to be checked
+		if !self.data.settings.lenient {
+			self.data.validate_loop_region()?;
+		}
+		let (mut sound, handle) = self.data.split();
+		sound.sync_with(self.target);
+		Ok((Box::new(sound), handle))
+	}
+}
+
+impl StaticSoundData {
+	/**
+	Returns a [`SyncedStaticSoundData`] that starts playing at the same
+	playback position as the sound controlled by `handle`.
+
+	The position is looked up on the audio thread right when this sound
+	starts, so the two sounds stay in sync, which is useful for layering
+	stems of a piece of music. This overrides the
+	[`start_position`](super::StaticSoundSettings::start_position) setting.
+
+	The sounds stay in sync as long as they have the same playback rate.
+
+	# Example
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend,
+		sound::static_sound::StaticSoundData,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let drums = manager.play(StaticSoundData::from_file("drums.ogg")?)?;
+	// later...
+	let bass = StaticSoundData::from_file("bass.ogg")?;
+	manager.play(bass.synced_with(&drums))?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn synced_with(&self, handle: &StaticSoundHandle) -> SyncedStaticSoundData {
+		SyncedStaticSoundData {
+			data: self.clone(),
+			target: handle.shared.clone(),
+		}
+	}
+}
@@ -17,24 +17,42 @@ manager.play(sound_data)?;
 ```
 
 Streaming sounds use less memory than static sounds, but they use more
-CPU, and they can have delays when starting or seeking.
-*/
+CPU, and they can have delays when starting or seeking. To avoid the delay
+when starting, use [`StreamingSoundData::prepare`] to start decoding audio
+before the sound is played.
 
-#![cfg_attr(docsrs, doc(cfg(not(wasm32))))]
+On most platforms, streaming sounds decode audio on a separate thread. On
+`wasm32`, where threads aren't available, audio is decoded on the audio thread
+a little bit at a time as the sound plays. If the audio isn't decoded in time,
+the sound outputs silence until it catches up.
+*/
 
 mod data;
 mod decoder;
 mod handle;
+mod prepared;
+#[cfg(feature = "symphonia")]
+mod seek_index;
 mod settings;
 mod sound;
+#[cfg(not(target_arch = "wasm32"))]
+mod worker_pool;
 
 pub use data::*;
 pub use decoder::*;
 pub use handle::*;
+pub use prepared::*;
+#[cfg(feature = "symphonia")]
+pub use seek_index::*;
 pub use settings::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use worker_pool::*;
+
+pub(crate) use sound::decode_scheduler::DecoderThreadShutdown;
 
 use crate::{
 	command::{command_writer_and_reader, CommandReader, CommandWriter, ValueChangeCommand},
+	track::TrackId,
 	Decibels, Panning, PlaybackRate, StartTime, Tween,
 };
 
@@ -46,9 +64,11 @@ pub(crate) struct CommandWriters {
 	set_playback_rate: CommandWriter<ValueChangeCommand<PlaybackRate>>,
 	set_panning: CommandWriter<ValueChangeCommand<Panning>>,
 	set_loop_region: CommandWriter<Option<Region>>,
-	pause: CommandWriter<Tween>,
+	set_loop_count: CommandWriter<Option<u32>>,
+	set_output_destination: CommandWriter<(TrackId, Tween)>,
+	pause: CommandWriter<(StartTime, Tween)>,
 	resume: CommandWriter<(StartTime, Tween)>,
-	stop: CommandWriter<Tween>,
+	stop: CommandWriter<(StartTime, Tween)>,
 	seek_by: CommandWriter<f64>,
 	seek_to: CommandWriter<f64>,
 }
@@ -57,14 +77,16 @@ pub(crate) struct CommandReaders {
 	set_volume: CommandReader<ValueChangeCommand<Decibels>>,
 	set_playback_rate: CommandReader<ValueChangeCommand<PlaybackRate>>,
 	set_panning: CommandReader<ValueChangeCommand<Panning>>,
-	pause: CommandReader<Tween>,
+	set_output_destination: CommandReader<(TrackId, Tween)>,
+	pause: CommandReader<(StartTime, Tween)>,
 	resume: CommandReader<(StartTime, Tween)>,
-	stop: CommandReader<Tween>,
+	stop: CommandReader<(StartTime, Tween)>,
 }
 
 #[derive(Debug)]
 pub(crate) struct DecodeSchedulerCommandReaders {
 	set_loop_region: CommandReader<Option<Region>>,
+	set_loop_count: CommandReader<Option<u32>>,
 	seek_by: CommandReader<f64>,
 	seek_to: CommandReader<f64>,
 }
@@ -79,6 +101,9 @@ fn command_writers_and_readers() -> (
 	let (set_playback_rate_writer, set_playback_rate_reader) = command_writer_and_reader();
 	let (set_panning_writer, set_panning_reader) = command_writer_and_reader();
 	let (set_loop_region_writer, set_loop_region_reader) = command_writer_and_reader();
+	let (set_loop_count_writer, set_loop_count_reader) = command_writer_and_reader();
+	let (set_output_destination_writer, set_output_destination_reader) =
+		command_writer_and_reader();
 	let (pause_writer, pause_reader) = command_writer_and_reader();
 	let (resume_writer, resume_reader) = command_writer_and_reader();
 	let (stop_writer, stop_reader) = command_writer_and_reader();
@@ -90,6 +115,8 @@ fn command_writers_and_readers() -> (
 			set_playback_rate: set_playback_rate_writer,
 			set_panning: set_panning_writer,
 			set_loop_region: set_loop_region_writer,
+			set_loop_count: set_loop_count_writer,
+			set_output_destination: set_output_destination_writer,
 			pause: pause_writer,
 			resume: resume_writer,
 			stop: stop_writer,
@@ -100,12 +127,14 @@ fn command_writers_and_readers() -> (
 			set_volume: set_volume_reader,
 			set_playback_rate: set_playback_rate_reader,
 			set_panning: set_panning_reader,
+			set_output_destination: set_output_destination_reader,
 			pause: pause_reader,
 			resume: resume_reader,
 			stop: stop_reader,
 		},
 		DecodeSchedulerCommandReaders {
 			set_loop_region: set_loop_region_reader,
+			set_loop_count: set_loop_count_reader,
 			seek_by: seek_by_reader,
 			seek_to: seek_to_reader,
 		},
@@ -4,20 +4,29 @@ mod test;
 use std::sync::Mutex;
 use std::{sync::Arc, time::Duration};
 
-use crate::sound::{EndPosition, IntoOptionalRegion, PlaybackPosition, Region, SoundData};
-use crate::{Decibels, Panning, PlaybackRate, StartTime};
+use crate::sound::{
+	sound_send_levels, ChannelMixMode, IntoOptionalRegion, Metadata, PlaybackPosition, Region,
+	SoundData,
+};
+use crate::track::SendTrackId;
+use crate::{group::GroupId, Decibels, Panning, PlaybackRate, StartTime};
 use crate::{Tween, Value};
 use rtrb::RingBuffer;
 
 use super::sound::Shared;
-use super::{command_writers_and_readers, StreamingSoundHandle, StreamingSoundSettings};
+#[cfg(not(target_arch = "wasm32"))]
+use super::StreamingWorkerPool;
+use super::{
+	command_writers_and_readers, DecodeErrorBehavior, EndBehavior, PreparedStreamingSoundData,
+	StreamingSoundHandle, StreamingSoundSettings, MAX_PREPARED_DURATION,
+};
 
 use super::{
 	decoder::Decoder,
 	sound::{decode_scheduler::DecodeScheduler, StreamingSound},
 };
 
-const ERROR_BUFFER_CAPACITY: usize = 1;
+const ERROR_BUFFER_CAPACITY: usize = 64;
 
 /// A streaming sound that is not playing yet.
 pub struct StreamingSoundData<Error: Send + 'static> {
@@ -37,7 +46,8 @@ pub struct StreamingSoundData<Error: Send + 'static> {
 }
 
 impl<Error: Send> StreamingSoundData<Error> {
-	/// Creates a [`StreamingSoundData`] for a [`Decoder`].
+	/// Creates a [`StreamingSoundData`] that plays audio from a
+	/// [`Decoder`].
 	#[must_use]
 	pub fn from_decoder(decoder: impl Decoder<Error = Error> + 'static) -> Self {
 		Self {
@@ -108,6 +118,49 @@ impl<Error: Send> StreamingSoundData<Error> {
 		self
 	}
 
+	/**
+	Sets whether to loop the loop points stored in the audio file when
+	no loop region is set.
+
+	# Examples
+
+	Play a sound from start to end, ignoring the loop points in its file:
+
+	```no_run
+	# use kira::sound::streaming::StreamingSoundData;
+	let sound = StreamingSoundData::from_file("music.ogg")?.use_detected_loop_region(false);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn use_detected_loop_region(mut self, use_detected_loop_region: bool) -> Self {
+		self.settings.use_detected_loop_region = use_detected_loop_region;
+		self
+	}
+
+	/**
+	Sets how many times the loop region should play before playback
+	continues to the end of the sound, or `None` to loop indefinitely.
+
+	# Examples
+
+	Play an intro, then the portion from 2 to 4 seconds three times, then the
+	rest of the sound:
+
+	```no_run
+	# use kira::sound::streaming::StreamingSoundData;
+	let sound = StreamingSoundData::from_file("music.ogg")?
+		.loop_region(2.0..4.0)
+		.loop_count(3);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn loop_count(mut self, loop_count: impl Into<Option<u32>>) -> Self {
+		self.settings.loop_count = loop_count.into();
+		self
+	}
+
 	/**
 	Sets the volume of the sound.
 
@@ -256,6 +309,27 @@ impl<Error: Send> StreamingSoundData<Error> {
 		self
 	}
 
+	/// Sets how the left and right channels are combined before volume
+	/// and panning are applied.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn channel_mix_mode(mut self, channel_mix_mode: ChannelMixMode) -> Self {
+		self.settings.channel_mix_mode = channel_mix_mode;
+		self
+	}
+
+	/// Sends the sound's audio to a send track at the given volume, in
+	/// addition to the track it's playing on.
+	///
+	/// # Panics
+	///
+	/// Panics if the sound already sends to [`MAX_SOUND_SENDS`](crate::sound::MAX_SOUND_SENDS)
+	/// other send tracks.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn send(mut self, to: impl Into<SendTrackId>, volume: impl Into<Value<Decibels>>) -> Self {
+		self.settings.sends.set(to, volume);
+		self
+	}
+
 	/// Sets the tween used to fade in the instance from silence.
 	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
 	pub fn fade_in_tween(mut self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
@@ -263,6 +337,178 @@ impl<Error: Send> StreamingSoundData<Error> {
 		self
 	}
 
+	/// Sets the tween used to fade out the instance to silence as playback
+	/// approaches the end of the sound.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn fade_out_tween(mut self, fade_out_tween: impl Into<Option<Tween>>) -> Self {
+		self.settings.fade_out_tween = fade_out_tween.into();
+		self
+	}
+
+	/// Sets the [group](crate::group) the sound belongs to.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn group(mut self, group: impl Into<GroupId>) -> Self {
+		self.settings.group = Some(group.into());
+		self
+	}
+
+	/**
+	Sets how many frames of decoded audio can be kept ahead of the
+	playback position.
+
+	A longer buffer gives the decoder more time to catch up when it
+	falls behind, which helps to avoid dropouts on slow storage, at the
+	cost of more memory. Playback starts as soon as the first frames
+	are decoded, so a longer buffer doesn't delay the start of the
+	sound.
+
+	# Examples
+
+	Keep about 3 seconds of 44.1kHz audio decoded ahead of time:
+
+	```no_run
+	# use kira::sound::streaming::StreamingSoundData;
+	let sound = StreamingSoundData::from_file("music.ogg")?.buffer_length(44_100 * 3);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn buffer_length(mut self, buffer_length: usize) -> Self {
+		self.settings.buffer_length = buffer_length;
+		self
+	}
+
+	/**
+	Sets what the sound should do when the decoder fails to decode a
+	chunk of audio.
+
+	# Examples
+
+	Skip over damaged parts of a file instead of stopping the sound:
+
+	```no_run
+	use kira::sound::streaming::{DecodeErrorBehavior, StreamingSoundData};
+
+	let sound = StreamingSoundData::from_file("music.mp3")?
+		.on_decode_error(DecodeErrorBehavior::Skip);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn on_decode_error(mut self, on_decode_error: DecodeErrorBehavior) -> Self {
+		self.settings.on_decode_error = on_decode_error;
+		self
+	}
+
+	/**
+	Sets what the sound should do when playback reaches the end of the
+	audio.
+
+	# Examples
+
+	Keep a song loaded after it ends so it can be played again:
+
+	```no_run
+	# use kira::{AudioManager, AudioManagerSettings, DefaultBackend};
+	use kira::{
+		Tween,
+		sound::streaming::{EndBehavior, StreamingSoundData},
+	};
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut sound = manager.play(StreamingSoundData::from_file("song.ogg")?.on_end(EndBehavior::Pause))?;
+	// later, after the song ends...
+	sound.seek_to(0.0);
+	sound.resume(Tween::default());
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn on_end(mut self, on_end: EndBehavior) -> Self {
+		self.settings.on_end = on_end;
+		self
+	}
+
+	/**
+	Sets the portions of the sound that should play back-to-back.
+
+	# Examples
+
+	Playing the intro of a song, then the chorus twice:
+
+	```no_run
+	use kira::sound::streaming::StreamingSoundData;
+
+	let sound = StreamingSoundData::from_file("music.ogg")?.section_sequence(vec![
+		(0.0..8.0).into(),
+		(24.0..40.0).into(),
+		(24.0..40.0).into(),
+	]);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn section_sequence(mut self, section_sequence: Vec<Region>) -> Self {
+		self.settings.section_sequence = section_sequence;
+		self
+	}
+
+	/**
+	Sets the integrated loudness (in LUFS) the sound should be
+	normalized to.
+
+	The loudness of the sound is read from ReplayGain or EBU R128 tags.
+	Sounds without loudness tags are played at their original loudness.
+	The gain can be checked with
+	[`StreamingSoundHandle::loudness_gain`].
+
+	# Examples
+
+	Normalizing a song to the loudness most streaming services use:
+
+	```no_run
+	use kira::sound::streaming::StreamingSoundData;
+
+	let sound = StreamingSoundData::from_file("music.flac")?.apply_loudness_normalization(-14.0);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn apply_loudness_normalization(mut self, target_lufs: f64) -> Self {
+		self.settings.loudness_normalization = Some(target_lufs);
+		self
+	}
+
+	/**
+	Sets the worker pool that should decode audio for the sound.
+
+	By default, each streaming sound decodes audio on its own thread.
+
+	# Examples
+
+	```no_run
+	use kira::sound::streaming::{StreamingSoundData, StreamingWorkerPool};
+
+	let pool = StreamingWorkerPool::new(2);
+	let sound = StreamingSoundData::from_file("ambience.ogg")?.worker_pool(&pool);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[cfg(not(target_arch = "wasm32"))]
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn worker_pool(mut self, worker_pool: &StreamingWorkerPool) -> Self {
+		self.settings.worker_pool = Some(worker_pool.clone());
+		self
+	}
+
+	/// Sets whether the sound should decode audio on the audio thread as
+	/// it plays instead of on a separate thread.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn decode_on_audio_thread(mut self, decode_on_audio_thread: bool) -> Self {
+		self.settings.decode_on_audio_thread = decode_on_audio_thread;
+		self
+	}
+
 	/// Returns the `StreamingSoundData` with the specified settings.
 	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
 	pub fn with_settings(mut self, settings: StreamingSoundSettings) -> Self {
@@ -300,17 +546,70 @@ impl<Error: Send> StreamingSoundData<Error> {
 		)
 	}
 
+	/**
+	Returns the exact duration of the audio.
+
+	Some formats, like variable-bitrate MP3s, only store an estimate of
+	their duration, so [`StreamingSoundData::duration`] can be off by a few
+	seconds for long files. This reads through the audio on the calling
+	thread to find its exact length, so it can be slow. Afterwards, the
+	exact length is used for [`duration`](StreamingSoundData::duration),
+	playback, and seeking.
+
+	If [`StreamingSoundData::slice`] is `Some`, this will be the duration of
+	the slice, clamped to the exact length of the audio.
+	*/
+	pub fn duration_accurate(&mut self) -> Result<Duration, Error> {
+		let num_frames = self.decoder.count_frames_accurately()?;
+		self.slice = self
+			.slice
+			.map(|(start, end)| (start.min(num_frames), end.min(num_frames)));
+		Ok(self.duration())
+	}
+
+	/// Returns the sample rate of the audio (in Hz).
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.decoder.sample_rate()
+	}
+
+	/// Returns the number of channels in the audio before it's converted
+	/// to stereo.
+	#[must_use]
+	pub fn num_channels(&self) -> u16 {
+		self.decoder.num_channels()
+	}
+
+	/// Returns the information read from the tags in the audio file, like
+	/// its title and artist, if there are any.
+	#[must_use]
+	pub fn metadata(&self) -> Option<&Metadata> {
+		self.decoder.metadata()
+	}
+
+	/// Returns the loop points stored in the audio file, like the ones in
+	/// a WAV file's `smpl` chunk or `LOOPSTART` tags, if there are any.
+	///
+	/// This region is looped when [`StreamingSoundSettings::loop_region`]
+	/// is `None`, unless
+	/// [`use_detected_loop_region`](StreamingSoundSettings::use_detected_loop_region)
+	/// is `false`.
+	#[must_use]
+	pub fn detected_loop_region(&self) -> Option<Region> {
+		self.metadata()?.loop_region
+	}
+
 	/**
 	Sets the portion of the audio this [`StreamingSoundData`] represents.
 	*/
 	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
 	pub fn slice(mut self, region: impl IntoOptionalRegion) -> Self {
-		self.slice = region.into_optional_region().map(|Region { start, end }| {
-			let start = start.into_samples(self.decoder.sample_rate());
-			let end = match end {
-				EndPosition::EndOfAudio => self.decoder.num_frames(),
-				EndPosition::Custom(end) => end.into_samples(self.decoder.sample_rate()),
-			};
+		self.slice = region.into_optional_region().map(|region| {
+			let sample_rate = self.decoder.sample_rate();
+			let start = region.start.into_samples(sample_rate);
+			let end = region
+				.end_in_samples(sample_rate)
+				.unwrap_or_else(|| self.decoder.num_frames());
 			(start, end)
 		});
 		self
@@ -322,6 +621,8 @@ impl<T: Send> StreamingSoundData<T> {}
 #[cfg(feature = "symphonia")]
 impl StreamingSoundData<crate::sound::FromFileError> {
 	/// Creates a [`StreamingSoundData`] for an audio file.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(not(wasm32))))]
 	pub fn from_file(
 		path: impl AsRef<std::path::Path>,
 	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
@@ -329,9 +630,123 @@ impl StreamingSoundData<crate::sound::FromFileError> {
 
 		use super::symphonia::SymphoniaDecoder;
 
-		Ok(Self::from_decoder(SymphoniaDecoder::new(Box::new(
-			File::open(path)?,
-		))?))
+		Ok(Self::from_decoder(SymphoniaDecoder::new(
+			Box::new(File::open(path)?),
+			Default::default(),
+		)?))
+	}
+
+	/**
+	Creates a [`StreamingSoundData`] for an audio file using the given
+	[`DecodeOptions`](crate::sound::DecodeOptions).
+
+	# Examples
+
+	Stream the second track of a file:
+
+	```no_run
+	use kira::sound::{streaming::StreamingSoundData, DecodeOptions};
+
+	let sound = StreamingSoundData::from_file_with("music.mka", DecodeOptions::new().track_index(1))?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(not(wasm32))))]
+	pub fn from_file_with(
+		path: impl AsRef<std::path::Path>,
+		options: crate::sound::DecodeOptions,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		use std::fs::File;
+
+		use super::symphonia::SymphoniaDecoder;
+
+		Ok(Self::from_decoder(SymphoniaDecoder::new(
+			Box::new(File::open(path)?),
+			options,
+		)?))
+	}
+
+	/**
+	Creates a [`StreamingSoundData`] for an audio file without blocking the
+	async executor.
+
+	Opening the file and detecting its format happens on a separate thread.
+
+	# Examples
+
+	```no_run
+	use kira::sound::streaming::StreamingSoundData;
+
+	# async fn load() -> Result<(), kira::sound::FromFileError> {
+	let sound_data = StreamingSoundData::from_file_async("music.ogg").await?;
+	# Ok(())
+	# }
+	```
+	*/
+	#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "async", not(wasm32)))))]
+	pub fn from_file_async(
+		path: impl AsRef<std::path::Path>,
+	) -> impl std::future::Future<Output = Result<Self, crate::sound::FromFileError>> + Send + 'static
+	{
+		let path = path.as_ref().to_owned();
+		crate::sound::blocking_task::BlockingTask::spawn(move || Self::from_file(path))
+	}
+
+	/**
+	Creates a [`StreamingSoundData`] for an MP3 file that seeks using a
+	[`SeekIndex`](super::SeekIndex) built with
+	[`build_seek_index`](Self::build_seek_index).
+
+	Seeking to any position, including the start position, is fast and
+	sample-accurate, and the duration of the audio is exact. Returns
+	[`FromFileError::SeekIndexMismatch`](crate::sound::FromFileError::SeekIndexMismatch)
+	if the file has changed since the index was built.
+
+	# Examples
+
+	Resuming a podcast where the player left off:
+
+	```no_run
+	use kira::sound::streaming::StreamingSoundData;
+
+	# let saved_position = 0.0;
+	// this only has to be done once, and the index can be saved
+	let seek_index = StreamingSoundData::build_seek_index("podcast.mp3")?;
+	let sound = StreamingSoundData::from_file_with_seek_index("podcast.mp3", seek_index)?
+		.start_position(saved_position);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(not(wasm32))))]
+	pub fn from_file_with_seek_index(
+		path: impl AsRef<std::path::Path>,
+		seek_index: super::SeekIndex,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		use std::fs::File;
+
+		use super::symphonia::SymphoniaDecoder;
+
+		Ok(Self::from_decoder(SymphoniaDecoder::with_seek_index(
+			Box::new(File::open(path)?),
+			seek_index,
+		)?))
+	}
+
+	/// Reads through an MP3 file and builds a [`SeekIndex`](super::SeekIndex)
+	/// for it, which can be passed to
+	/// [`from_file_with_seek_index`](Self::from_file_with_seek_index).
+	///
+	/// This reads the entire file, so it's best to build the index once
+	/// and save it.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(not(wasm32))))]
+	pub fn build_seek_index(
+		path: impl AsRef<std::path::Path>,
+	) -> Result<super::SeekIndex, crate::sound::FromFileError> {
+		super::SeekIndex::from_read(std::fs::File::open(path)?)
 	}
 
 	/// Creates a [`StreamingSoundData`] for a cursor wrapping audio file data.
@@ -340,7 +755,49 @@ impl StreamingSoundData<crate::sound::FromFileError> {
 	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
 		use super::symphonia::SymphoniaDecoder;
 
-		Ok(Self::from_decoder(SymphoniaDecoder::new(Box::new(cursor))?))
+		Ok(Self::from_decoder(SymphoniaDecoder::new(
+			Box::new(cursor),
+			Default::default(),
+		)?))
+	}
+
+	/**
+	Creates a [`StreamingSoundData`] for audio file data that's already in
+	memory.
+
+	The data isn't copied, so buffers that are shared with other code,
+	like an `Arc<[u8]>`, can be streamed without using extra memory. The
+	buffer is kept alive until the sound is done with it.
+
+	# Examples
+
+	```no_run
+	use std::sync::Arc;
+
+	use kira::sound::streaming::StreamingSoundData;
+
+	let music_pack: Arc<[u8]> = std::fs::read("music.ogg")?.into();
+	let sound_data = StreamingSoundData::from_bytes(music_pack.clone())?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn from_bytes(
+		bytes: impl AsRef<[u8]> + Send + Sync + 'static,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		Self::from_cursor(std::io::Cursor::new(bytes))
+	}
+
+	/// Creates a [`StreamingSoundData`] for a cursor wrapping audio file data
+	/// without blocking the async executor.
+	///
+	/// Detecting the format of the audio happens on a separate thread.
+	#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "async", not(wasm32)))))]
+	pub fn from_cursor_async<T: AsRef<[u8]> + Send + Sync + 'static>(
+		cursor: std::io::Cursor<T>,
+	) -> impl std::future::Future<Output = Result<Self, crate::sound::FromFileError>> + Send + 'static
+	{
+		crate::sound::blocking_task::BlockingTask::spawn(move || Self::from_cursor(cursor))
 	}
 
 	/// Creates a [`StreamingSoundData`] for a type that implements Symphonia's
@@ -350,9 +807,131 @@ impl StreamingSoundData<crate::sound::FromFileError> {
 	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
 		use super::symphonia::SymphoniaDecoder;
 
-		Ok(Self::from_decoder(SymphoniaDecoder::new(Box::new(
-			media_source,
-		))?))
+		Ok(Self::from_decoder(SymphoniaDecoder::new(
+			Box::new(media_source),
+			Default::default(),
+		)?))
+	}
+
+	/**
+	Creates a [`StreamingSoundData`] for any reader that supports seeking,
+	such as an entry in an archive.
+
+	`hint` is the file extension of the audio (like `"ogg"`), if it's known.
+	It's used to help detect the format of the audio.
+
+	# Examples
+
+	```no_run
+	use std::{fs::File, io::BufReader};
+
+	use kira::sound::streaming::StreamingSoundData;
+
+	let reader = BufReader::new(File::open("music.pak")?);
+	let sound = StreamingSoundData::from_read(reader, Some("ogg"))?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn from_read(
+		reader: impl std::io::Read + std::io::Seek + Send + 'static,
+		hint: Option<&str>,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		use crate::sound::symphonia::ReadSeekSource;
+
+		use super::symphonia::SymphoniaDecoder;
+
+		let mut options = crate::sound::DecodeOptions::new();
+		options.format_hint = hint.map(str::to_owned);
+		Ok(Self::from_decoder(SymphoniaDecoder::new(
+			Box::new(ReadSeekSource::new(reader)),
+			options,
+		)?))
+	}
+
+	/**
+	Creates a [`StreamingSoundData`] for a reader that can't seek, such as
+	audio that's being downloaded.
+
+	`hint` is the file extension of the audio (like `"ogg"`), if it's known.
+	It's used to help detect the format of the audio.
+
+	The length of the audio doesn't have to be known ahead of time. If it
+	isn't, [`duration`](Self::duration) is meaningless, and regions that end
+	at [`EndPosition::EndOfAudio`](crate::sound::EndPosition::EndOfAudio) end
+	wherever the stream ends.
+
+	Seeking isn't supported, so the sound must start at the beginning of the
+	audio. Calls to [`StreamingSoundHandle::seek_to`] and
+	[`StreamingSoundHandle::seek_by`] are ignored, and the
+	[`FromFileError::NotSeekable`](crate::sound::FromFileError::NotSeekable)
+	error is sent to the handle. Use [`StreamingSoundHandle::stream_state`]
+	to find out when the sound is waiting for more audio.
+
+	# Examples
+
+	```no_run
+	use std::net::TcpStream;
+
+	use kira::sound::streaming::StreamingSoundData;
+
+	let stream = TcpStream::connect("127.0.0.1:8000")?;
+	let sound = StreamingSoundData::from_unseekable(stream, Some("mp3"))?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn from_unseekable(
+		reader: impl std::io::Read + Send + 'static,
+		hint: Option<&str>,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		use crate::sound::symphonia::ReadOnlySource;
+
+		use super::symphonia::SymphoniaDecoder;
+
+		let mut options = crate::sound::DecodeOptions::new();
+		options.format_hint = hint.map(str::to_owned);
+		Ok(Self::from_decoder(SymphoniaDecoder::new(
+			Box::new(ReadOnlySource::new(reader)),
+			options,
+		)?))
+	}
+}
+
+#[cfg(not(feature = "symphonia"))]
+impl StreamingSoundData<crate::sound::FromFileError> {
+	/// Creates a [`StreamingSoundData`] for a WAV file.
+	///
+	/// Without the `symphonia` feature, only WAV files with 16, 24, or
+	/// 32-bit integer samples or 32-bit floating point samples can be
+	/// streamed.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(all(not(feature = "symphonia"), not(wasm32)))))]
+	pub fn from_file(
+		path: impl AsRef<std::path::Path>,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		use crate::sound::wav::WavDecoder;
+
+		let file = std::io::BufReader::new(std::fs::File::open(path)?);
+		Ok(Self::from_decoder(WavDecoder::new(Box::new(file))?))
+	}
+
+	/// Creates a [`StreamingSoundData`] for a cursor wrapping WAV file
+	/// data.
+	#[cfg_attr(docsrs, doc(cfg(not(feature = "symphonia"))))]
+	pub fn from_cursor<T: AsRef<[u8]> + Send + Sync + 'static>(
+		cursor: std::io::Cursor<T>,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		use crate::sound::wav::WavDecoder;
+
+		Ok(Self::from_decoder(WavDecoder::new(Box::new(cursor))?))
+	}
+
+	/// Creates a [`StreamingSoundData`] for WAV file data that's already
+	/// in memory without copying it.
+	#[cfg_attr(docsrs, doc(cfg(not(feature = "symphonia"))))]
+	pub fn from_bytes(
+		bytes: impl AsRef<[u8]> + Send + Sync + 'static,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		Self::from_cursor(std::io::Cursor::new(bytes))
 	}
 }
 
@@ -371,11 +950,13 @@ impl<Error: Send + 'static> StreamingSoundData<Error> {
 			command_writers_and_readers();
 		let (error_producer, error_consumer) = RingBuffer::new(ERROR_BUFFER_CAPACITY);
 		let sample_rate = self.decoder.sample_rate();
+		let loudness_gain = self.loudness_gain();
 		let shared = Arc::new(Shared::new());
+		let (send_levels, send_level_command_writers) = sound_send_levels(&self.settings.sends);
 		let (scheduler, frame_consumer) = DecodeScheduler::new(
 			self.decoder,
 			self.slice,
-			self.settings,
+			&self.settings,
 			shared.clone(),
 			decode_scheduler_command_readers,
 			error_producer,
@@ -386,15 +967,63 @@ impl<Error: Send + 'static> StreamingSoundData<Error> {
 			shared.clone(),
 			frame_consumer,
 			command_readers,
+			send_levels,
 			&scheduler,
+			loudness_gain,
 		);
 		let handle = StreamingSoundHandle {
 			shared,
 			command_writers,
+			send_level_command_writers,
 			error_consumer: Mutex::new(error_consumer),
+			loudness_gain,
 		};
 		Ok((sound, handle, scheduler))
 	}
+
+	/// Returns the gain that brings the sound to the loudness set by
+	/// [`loudness_normalization`](StreamingSoundSettings::loudness_normalization).
+	#[must_use]
+	fn loudness_gain(&self) -> Decibels {
+		let (Some(target_lufs), Some(loudness)) = (
+			self.settings.loudness_normalization,
+			self.decoder.loudness(),
+		) else {
+			return Decibels::IDENTITY;
+		};
+		Decibels((target_lufs - loudness) as f32)
+	}
+
+	/**
+	Starts decoding audio before the sound is played.
+
+	Normally, a streaming sound only starts decoding audio once it's
+	played, so it may take a moment for the sound to start. A
+	[`PreparedStreamingSoundData`] can start playing right away.
+
+	The decoding thread only waits for the sound to be played for
+	[`MAX_PREPARED_DURATION`]. After that, the thread stops, and decoding
+	continues once the sound is played. Dropping the prepared sound without
+	playing it stops the thread.
+
+	# Examples
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend,
+		sound::streaming::StreamingSoundData,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let sound = StreamingSoundData::from_file("explosion.ogg")?.prepare()?;
+	// later...
+	manager.play(sound)?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn prepare(self) -> Result<PreparedStreamingSoundData<Error>, Error> {
+		PreparedStreamingSoundData::new(self, MAX_PREPARED_DURATION)
+	}
 }
 
 impl<Error: Send + 'static> SoundData for StreamingSoundData<Error> {
@@ -404,8 +1033,22 @@ impl<Error: Send + 'static> SoundData for StreamingSoundData<Error> {
 
 	#[allow(clippy::type_complexity)]
 	fn into_sound(self) -> Result<(Box<dyn crate::sound::Sound>, Self::Handle), Self::Error> {
-		let (sound, handle, scheduler) = self.split()?;
-		scheduler.start();
+		#[cfg(not(target_arch = "wasm32"))]
+		let worker_pool = self.settings.worker_pool.clone();
+		// threads can't be spawned on the web, so the sound decodes
+		// audio itself instead
+		let decode_on_audio_thread =
+			self.settings.decode_on_audio_thread || cfg!(target_arch = "wasm32");
+		let (mut sound, handle, scheduler) = self.split()?;
+		if decode_on_audio_thread {
+			sound.decode_on_audio_thread(scheduler);
+		} else {
+			#[cfg(not(target_arch = "wasm32"))]
+			match worker_pool {
+				Some(worker_pool) => worker_pool.submit(Box::new(scheduler)),
+				None => scheduler.start(),
+			}
+		}
 		Ok((Box::new(sound), handle))
 	}
 }
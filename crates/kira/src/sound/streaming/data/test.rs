@@ -1,9 +1,16 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::{
 	frame::Frame,
-	sound::streaming::{mock::MockDecoder, StreamingSoundData},
+	info::MockInfoBuilder,
+	sound::{
+		streaming::{mock::MockDecoder, StreamingSoundData, StreamingSoundSettings},
+		PlaybackState, SoundData,
+	},
+	Tween,
 };
+#[cfg(feature = "wav")]
+use crate::{sound::streaming::StreamState, test_helpers::wav_fixture};
 
 #[test]
 fn duration() {
@@ -34,3 +41,171 @@ fn sliced_duration() {
 	};
 	assert_eq!(sound.duration(), Duration::from_secs(1));
 }
+
+/// Tests that a `StreamingSoundData` can be created from any reader
+/// that supports seeking.
+#[test]
+#[cfg(feature = "wav")]
+fn from_read() {
+	let sound =
+		StreamingSoundData::from_read(std::io::Cursor::new(wav_fixture(4, &[0; 8])), Some("wav"))
+			.unwrap();
+	assert_eq!(sound.duration(), Duration::from_secs(2));
+}
+
+/// Tests that `StreamingSoundData::from_read` reports when the format of
+/// the audio can't be determined.
+#[test]
+#[cfg(feature = "symphonia")]
+fn from_read_unrecognized_format() {
+	let result = StreamingSoundData::from_read(std::io::Cursor::new(vec![0u8; 64]), None);
+	assert!(matches!(
+		result,
+		Err(crate::sound::FromFileError::UnrecognizedFormat(_))
+	));
+}
+
+/// Tests that a `StreamingSoundData` reports information about the
+/// underlying audio, including its exact duration.
+#[test]
+#[cfg(feature = "wav")]
+fn metadata() {
+	let mut sound =
+		StreamingSoundData::from_read(std::io::Cursor::new(wav_fixture(4, &[0; 8])), None).unwrap();
+	assert_eq!(sound.sample_rate(), 4);
+	assert_eq!(sound.num_channels(), 1);
+	assert_eq!(sound.duration_accurate().unwrap(), Duration::from_secs(2));
+	assert_eq!(sound.duration(), Duration::from_secs(2));
+}
+
+/// Tests that a `StreamingSoundData` can be created from a reader that
+/// can't seek, and that seeking it reports an error instead of
+/// interrupting playback.
+#[test]
+#[cfg(feature = "wav")]
+fn from_unseekable() {
+	use crate::{
+		info::MockInfoBuilder,
+		sound::{streaming::sound::decode_scheduler::NextStep, FromFileError, Sound},
+		Panning,
+	};
+
+	let samples = (0..8).map(|i| i * 1000).collect::<Vec<i16>>();
+	let data =
+		StreamingSoundData::from_unseekable(std::io::Cursor::new(wav_fixture(1, &samples)), None)
+			.unwrap();
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	handle.seek_to(4.0);
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert!(matches!(
+		handle.take_decode_errors()[..],
+		[FromFileError::NotSeekable]
+	));
+	assert_eq!(handle.stream_state(), StreamState::Ended);
+	for sample in samples {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(sample as f32 / 32768.0).panned(Panning::CENTER)
+		);
+	}
+
+	// unseekable sounds have to start at the beginning
+	let result = StreamingSoundData::from_unseekable(
+		std::io::Cursor::new(wav_fixture(1, &[0; 8])),
+		Some("wav"),
+	)
+	.unwrap()
+	.start_position(2.0)
+	.split();
+	assert!(matches!(result, Err(FromFileError::NotSeekable)));
+}
+
+/// Tests that the decoding thread of a streaming sound exits right after
+/// the sound finishes stopping.
+#[test]
+fn decoder_thread_exits_when_stopped() {
+	let (mut sound, mut handle) = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100_000])),
+		settings: StreamingSoundSettings::new().buffer_length(10),
+		slice: None,
+	}
+	.into_sound()
+	.unwrap();
+	handle.stop(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+	let stopped_at = Instant::now();
+	while !handle.shared.decoder_finished() {
+		assert!(
+			stopped_at.elapsed() < Duration::from_millis(20),
+			"decoder thread did not exit in time"
+		);
+		std::thread::sleep(Duration::from_micros(100));
+	}
+}
+
+/// Tests that dropping a streaming sound that's still playing, like when
+/// the audio manager shuts down, stops its decoding thread.
+#[test]
+fn decoder_thread_exits_when_sound_dropped() {
+	let (sound, handle) = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100_000])),
+		settings: StreamingSoundSettings::new().buffer_length(10),
+		slice: None,
+	}
+	.into_sound()
+	.unwrap();
+	drop(sound);
+	let dropped_at = Instant::now();
+	while !handle.shared.decoder_finished() {
+		assert!(
+			dropped_at.elapsed() < Duration::from_millis(20),
+			"decoder thread did not exit in time"
+		);
+		std::thread::sleep(Duration::from_micros(100));
+	}
+	assert_eq!(handle.state(), PlaybackState::Playing);
+}
+
+/// Tests that a `StreamingSoundData` can be created asynchronously.
+#[test]
+#[cfg(all(feature = "async", feature = "wav"))]
+fn from_cursor_async() {
+	let sound = crate::test_helpers::block_on(StreamingSoundData::from_cursor_async(
+		std::io::Cursor::new(wav_fixture(4, &[0; 8])),
+	))
+	.unwrap();
+	assert_eq!(sound.duration(), Duration::from_secs(2));
+}
+
+/// Tests that a `StreamingSoundData` streams from a shared buffer
+/// without copying it.
+#[test]
+#[cfg(feature = "wav")]
+fn from_bytes() {
+	let wav: std::sync::Arc<[u8]> = wav_fixture(4, &[0; 8]).into();
+	let sound = StreamingSoundData::from_bytes(wav.clone()).unwrap();
+	assert_eq!(sound.duration(), Duration::from_secs(2));
+	assert_eq!(std::sync::Arc::strong_count(&wav), 2);
+	drop(sound);
+	assert_eq!(std::sync::Arc::strong_count(&wav), 1);
+}
+
+/// Tests that a `StreamingSoundData` keeps the metadata stored in the
+/// file.
+#[test]
+#[cfg(feature = "wav")]
+fn reads_metadata() {
+	let wav = crate::test_helpers::with_wav_tags(
+		wav_fixture(4, &[0; 8]),
+		&[(b"INAM", "Blip"), (b"IPRD", "Sounds")],
+	);
+	let sound = StreamingSoundData::from_read(std::io::Cursor::new(wav), None).unwrap();
+	let metadata = sound.metadata().unwrap();
+	assert_eq!(metadata.title.as_deref(), Some("Blip"));
+	assert_eq!(metadata.album.as_deref(), Some("Sounds"));
+}
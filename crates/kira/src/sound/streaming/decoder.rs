@@ -3,9 +3,19 @@ pub(crate) mod mock;
 #[cfg(feature = "symphonia")]
 pub(crate) mod symphonia;
 
-use crate::frame::Frame;
+use crate::{frame::Frame, sound::Metadata};
 
-/// Decodes chunks of audio.
+/**
+Decodes chunks of audio.
+
+Implementing this trait lets Kira play audio formats it doesn't support
+out of the box. A [`Decoder`] can be streamed with
+[`StreamingSoundData::from_decoder`](super::StreamingSoundData::from_decoder)
+or decoded all at once with
+[`StaticSoundData::from_decoder`](crate::sound::static_sound::StaticSoundData::from_decoder).
+The `custom-decoder` example in the repository implements a decoder for
+raw PCM audio.
+*/
 pub trait Decoder: Send {
 	/// Errors that can occur when decoding audio.
 	type Error;
@@ -15,10 +25,82 @@ pub trait Decoder: Send {
 	fn sample_rate(&self) -> u32;
 
 	/// Returns the total number of samples of audio.
+	///
+	/// If the length of the audio isn't known ahead of time, such as when
+	/// the audio is being streamed over a network, this can return
+	/// `usize::MAX` until the end of the audio is reached. Once `decode`
+	/// reaches the end, this should return the actual number of samples,
+	/// and `decode` should return an empty `Vec`.
 	#[must_use]
 	fn num_frames(&self) -> usize;
 
+	/// Returns the number of channels in the audio.
+	///
+	/// Decoded audio is always converted to stereo [`Frame`]s, so this is
+	/// only used for informational purposes. Defaults to `2`.
+	#[must_use]
+	fn num_channels(&self) -> u16 {
+		2
+	}
+
+	/// Counts the exact number of samples of audio and returns it.
+	///
+	/// Some formats only store an estimate of their length, so
+	/// [`num_frames`](Decoder::num_frames) may be inaccurate. This can take
+	/// much longer, for example by reading through the entire file. Afterwards,
+	/// `num_frames` should return the exact number of samples as well.
+	///
+	/// The default implementation returns the result of `num_frames`.
+	fn count_frames_accurately(&mut self) -> Result<usize, Self::Error> {
+		Ok(self.num_frames())
+	}
+
+	/// Returns the integrated loudness of the audio (in LUFS), if it's
+	/// known.
+	///
+	/// This is used to normalize the loudness of the sound when
+	/// [`loudness_normalization`](super::StreamingSoundSettings::loudness_normalization)
+	/// is set. Defaults to `None`.
+	#[must_use]
+	fn loudness(&self) -> Option<f64> {
+		None
+	}
+
+	/// Returns the information read from the tags in the audio file, if
+	/// there are any.
+	///
+	/// This is returned by
+	/// [`StreamingSoundData::metadata`](super::StreamingSoundData::metadata).
+	/// Defaults to `None`.
+	#[must_use]
+	fn metadata(&self) -> Option<&Metadata> {
+		None
+	}
+
+	/// Returns an estimate of how much memory the decoder is using for
+	/// its own buffers (in bytes), not counting a seek index.
+	///
+	/// This is reported by
+	/// [`StreamingSoundHandle::memory_usage`](super::StreamingSoundHandle::memory_usage).
+	/// Defaults to `0`.
+	#[must_use]
+	fn memory_usage(&self) -> usize {
+		0
+	}
+
+	/// Returns how much memory the decoder's seek index is using (in
+	/// bytes), if it has one. Defaults to `0`.
+	#[must_use]
+	fn seek_index_memory_usage(&self) -> usize {
+		0
+	}
+
 	/// Decodes the next chunk of audio.
+	///
+	/// If no audio is available yet, such as when the audio is being fed
+	/// in a little at a time, this can return an empty `Vec`, and the
+	/// sound will try again later. In the meantime, the sound will output
+	/// silence once it runs out of decoded audio.
 	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error>;
 
 	/// Seeks to an audio sample.
@@ -28,6 +110,17 @@ pub trait Decoder: Send {
 	///
 	/// This should return the sample index that was _actually_ seeked to.
 	fn seek(&mut self, index: usize) -> Result<SeekedToIndex, Self::Error>;
+
+	/// Returns how many samples before the requested sample the decoder
+	/// should seek to when seeking.
+	///
+	/// Some codecs need to decode a packet or two after seeking before
+	/// their output is correct. These samples are decoded and discarded,
+	/// and the sound outputs silence in the meantime. Defaults to `0`.
+	#[must_use]
+	fn seek_pre_roll(&self) -> usize {
+		0
+	}
 }
 
 type SeekedToIndex = usize;
@@ -1,60 +1,152 @@
-use std::convert::TryInto;
+#[cfg(test)]
+mod test;
+
+use std::{
+	convert::TryInto,
+	io::{Read, Seek, SeekFrom},
+	sync::{Arc, Mutex},
+};
 
 use crate::{
 	frame::Frame,
-	sound::{symphonia::load_frames_from_buffer_ref, FromFileError},
+	sound::{
+		decode_options::select_track,
+		metadata::read_smpl_loop_region,
+		streaming::SeekIndex,
+		symphonia::{load_frames_from_buffer_ref, probe, probed_tags},
+		ChannelMapping, DecodeOptions, FromFileError, Metadata,
+	},
 };
 use symphonia::core::{
+	audio::AudioBufferRef,
 	codecs::Decoder,
 	formats::{FormatReader, SeekMode, SeekTo},
-	io::{MediaSource, MediaSourceStream},
+	io::{MediaSource, MediaSourceStreamOptions},
+	meta::{StandardTagKey, Tag, Value},
 	probe::Hint,
 };
 
+/// How many samples before the requested sample to seek to if the codec
+/// doesn't say how long its packets can be.
+const DEFAULT_SEEK_PRE_ROLL: usize = 4096;
+/// The loudness (in LUFS) that ReplayGain 2.0 gains are relative to.
+const REPLAY_GAIN_REFERENCE_LOUDNESS: f64 = -18.0;
+/// The loudness (in LUFS) that EBU R128 gains are relative to.
+const R128_REFERENCE_LOUDNESS: f64 = -23.0;
+
 pub(crate) struct SymphoniaDecoder {
 	format_reader: Box<dyn FormatReader>,
 	decoder: Box<dyn Decoder>,
 	sample_rate: u32,
 	num_frames: usize,
+	num_channels: u16,
+	channel_mapping: ChannelMapping,
+	seek_pre_roll: usize,
 	track_id: u32,
+	/// Whether the media source supports seeking.
+	seekable: bool,
+	/// Whether the number of frames was known ahead of time.
+	length_known: bool,
+	/// The index of the next frame that will be decoded.
+	current_frame_index: usize,
+	/// The integrated loudness of the audio, read from its tags.
+	loudness: Option<f64>,
+	metadata: Option<Metadata>,
+	/// The seek index for the audio, if there is one, and the source the
+	/// format reader is recreated from when seeking.
+	seek_index: Option<(SeekIndex, IndexedSource)>,
 }
 
 impl SymphoniaDecoder {
-	pub(crate) fn new(media_source: Box<dyn MediaSource>) -> Result<Self, FromFileError> {
+	pub(crate) fn new(
+		mut media_source: Box<dyn MediaSource>,
+		options: DecodeOptions,
+	) -> Result<Self, FromFileError> {
+		let seekable = media_source.is_seekable();
+		let smpl_loop_region = if seekable {
+			read_smpl_loop_region(&mut *media_source)
+		} else {
+			None
+		};
 		let codecs = symphonia::default::get_codecs();
-		let probe = symphonia::default::get_probe();
-		let mss = MediaSourceStream::new(media_source, Default::default());
-		let format_reader = probe
-			.format(
-				&Hint::default(),
-				mss,
-				&Default::default(),
-				&Default::default(),
-			)?
-			.format;
-		let default_track = format_reader
-			.default_track()
-			.ok_or(FromFileError::NoDefaultTrack)?;
-		let sample_rate = default_track
+		let mut probed = probe(media_source, &options.hint())?;
+		let tags = probed_tags(&mut probed);
+		let loudness = loudness_from_tags(&tags);
+		let metadata = Metadata::from_tags(&tags, smpl_loop_region);
+		let format_reader = probed.format;
+		let track = select_track(&*format_reader, options.track_index)?;
+		let sample_rate = track
 			.codec_params
 			.sample_rate
 			.ok_or(FromFileError::UnknownSampleRate)?;
-		let num_frames = default_track
+		// the length of streams that can't seek often isn't known until
+		// the end of the stream is reached
+		let length_known = track.codec_params.n_frames.is_some();
+		let num_frames = track.codec_params.n_frames.map_or(usize::MAX, |n_frames| {
+			n_frames
+				.try_into()
+				.expect("could not convert u64 into usize")
+		});
+		let num_channels = track
 			.codec_params
-			.n_frames
-			.ok_or(FromFileError::UnknownSampleRate)?
-			.try_into()
-			.expect("could not convert u64 into usize");
-		let decoder = codecs.make(&default_track.codec_params, &Default::default())?;
-		let track_id = default_track.id;
+			.channels
+			.map_or(2, |channels| channels.count() as u16);
+		let metadata =
+			Metadata::with_channel_mapping(metadata, num_channels.into(), options.channel_mapping);
+		// two packets is enough for codecs that depend on data from
+		// previous packets, like MP3 and Vorbis
+		let seek_pre_roll = if seekable {
+			track
+				.codec_params
+				.max_frames_per_packet
+				.map_or(DEFAULT_SEEK_PRE_ROLL, |frames| frames as usize * 2)
+		} else {
+			0
+		};
+		let decoder = codecs.make(&track.codec_params, &Default::default())?;
+		let track_id = track.id;
 		Ok(Self {
 			format_reader,
 			decoder,
 			sample_rate,
 			num_frames,
+			num_channels,
+			channel_mapping: options.channel_mapping,
+			seek_pre_roll,
 			track_id,
+			seekable,
+			length_known,
+			current_frame_index: 0,
+			loudness,
+			metadata,
+			seek_index: None,
 		})
 	}
+
+	/// Creates a decoder for an MP3 file that seeks using a [`SeekIndex`].
+	#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+	pub(crate) fn with_seek_index(
+		media_source: Box<dyn MediaSource>,
+		seek_index: SeekIndex,
+	) -> Result<Self, FromFileError> {
+		if media_source.byte_len() != Some(seek_index.byte_len()) {
+			return Err(FromFileError::SeekIndexMismatch);
+		}
+		let source = IndexedSource {
+			source: Arc::new(Mutex::new(media_source)),
+			len: seek_index.byte_len(),
+			start: 0,
+			position: 0,
+		};
+		let mut decoder = Self::new(
+			Box::new(source.starting_at(0)),
+			DecodeOptions::new().format_hint("mp3"),
+		)?;
+		decoder.num_frames = seek_index.num_frames();
+		decoder.length_known = true;
+		decoder.seek_index = Some((seek_index, source));
+		Ok(decoder)
+	}
 }
 
 impl super::Decoder for SymphoniaDecoder {
@@ -68,13 +160,123 @@ impl super::Decoder for SymphoniaDecoder {
 		self.num_frames
 	}
 
+	fn num_channels(&self) -> u16 {
+		self.num_channels
+	}
+
+	fn seek_pre_roll(&self) -> usize {
+		self.seek_pre_roll
+	}
+
+	fn loudness(&self) -> Option<f64> {
+		self.loudness
+	}
+
+	fn metadata(&self) -> Option<&Metadata> {
+		self.metadata.as_ref()
+	}
+
+	fn memory_usage(&self) -> usize {
+		// the format reader reads the file through a buffer, and the codec
+		// keeps the last decoded packet around
+		let last_decoded = self.decoder.last_decoded();
+		let bytes_per_sample = match last_decoded {
+			AudioBufferRef::U8(_) | AudioBufferRef::S8(_) => 1,
+			AudioBufferRef::U16(_) | AudioBufferRef::S16(_) => 2,
+			AudioBufferRef::F64(_) => 8,
+			_ => 4,
+		};
+		MediaSourceStreamOptions::default().buffer_len
+			+ last_decoded.capacity() * last_decoded.spec().channels.count() * bytes_per_sample
+	}
+
+	fn seek_index_memory_usage(&self) -> usize {
+		self.seek_index
+			.as_ref()
+			.map_or(0, |(seek_index, _)| seek_index.memory_usage())
+	}
+
+	fn count_frames_accurately(&mut self) -> Result<usize, Self::Error> {
+		if let Some((seek_index, _)) = &self.seek_index {
+			return Ok(seek_index.num_frames());
+		}
+		// add up the durations of each packet, which (unlike the duration
+		// stored in some containers) is always accurate
+		if !self.seekable {
+			return Err(FromFileError::NotSeekable);
+		}
+		self.seek(0)?;
+		let mut num_frames = 0;
+		loop {
+			match self.format_reader.next_packet() {
+				Ok(packet) => {
+					if packet.track_id() == self.track_id {
+						num_frames += packet.dur;
+					}
+				}
+				Err(symphonia::core::errors::Error::IoError(error))
+					if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+				{
+					break
+				}
+				Err(error) => return Err(error.into()),
+			}
+		}
+		self.seek(0)?;
+		self.num_frames = num_frames
+			.try_into()
+			.expect("could not convert u64 into usize");
+		Ok(self.num_frames)
+	}
+
 	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
-		let packet = self.format_reader.next_packet()?;
+		let packet = loop {
+			match self.format_reader.next_packet() {
+				// packets from other tracks are skipped
+				Ok(packet) if packet.track_id() != self.track_id => {}
+				Ok(packet) => break packet,
+				// if the length of the audio wasn't known, the end of the stream
+				// is the end of the audio
+				Err(symphonia::core::errors::Error::IoError(error))
+					if !self.length_known && error.kind() == std::io::ErrorKind::UnexpectedEof =>
+				{
+					self.num_frames = self.current_frame_index;
+					return Ok(vec![]);
+				}
+				Err(error) => return Err(error.into()),
+			}
+		};
 		let buffer = self.decoder.decode(&packet)?;
-		load_frames_from_buffer_ref(&buffer)
+		let frames = load_frames_from_buffer_ref(&buffer, self.channel_mapping)?;
+		self.current_frame_index += frames.len();
+		Ok(frames)
 	}
 
 	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		// streams that can't seek can only "seek" to where they already are
+		if !self.seekable {
+			return if index == self.current_frame_index {
+				Ok(index)
+			} else {
+				Err(FromFileError::NotSeekable)
+			};
+		}
+		// start reading the file again from the closest point in the seek
+		// index. the format reader can't be moved to a position in the file
+		// directly, so a new one is created
+		if let Some((seek_index, source)) = &self.seek_index {
+			let point = seek_index.point_before(index);
+			self.format_reader =
+				probe(Box::new(source.starting_at(point.byte_offset)), &mp3_hint())?.format;
+			self.track_id = self
+				.format_reader
+				.default_track()
+				.ok_or(FromFileError::NoDefaultTrack)?
+				.id;
+			self.decoder.reset();
+			self.current_frame_index = point.frame_index;
+			return Ok(point.frame_index);
+		}
 		let seeked_to = self.format_reader.seek(
 			SeekMode::Accurate,
 			SeekTo::TimeStamp {
@@ -82,9 +284,141 @@ impl super::Decoder for SymphoniaDecoder {
 				track_id: self.track_id,
 			},
 		)?;
-		Ok(seeked_to
+		// the decoder has to be reset after seeking so it doesn't
+		// combine audio from before and after the seek
+		self.decoder.reset();
+		self.current_frame_index = seeked_to
 			.actual_ts
 			.try_into()
-			.expect("could not convert u64 into usize"))
+			.expect("could not convert u64 into usize");
+		Ok(self.current_frame_index)
 	}
 }
+
+#[must_use]
+fn mp3_hint() -> Hint {
+	let mut hint = Hint::new();
+	hint.with_extension("mp3");
+	hint
+}
+
+/// A view of a media source that starts partway through it. Views
+/// created with [`starting_at`](IndexedSource::starting_at) share the
+/// same underlying source.
+struct IndexedSource {
+	source: Arc<Mutex<Box<dyn MediaSource>>>,
+	/// The length of the underlying source (in bytes).
+	len: u64,
+	/// Where in the underlying source this view starts.
+	start: u64,
+	/// The position relative to the start of this view.
+	position: u64,
+}
+
+impl IndexedSource {
+	#[must_use]
+	fn starting_at(&self, start: u64) -> Self {
+		Self {
+			source: self.source.clone(),
+			len: self.len,
+			start,
+			position: 0,
+		}
+	}
+}
+
+impl Read for IndexedSource {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let mut source = self.source.lock().expect("media source mutex poisoned");
+		source.seek(SeekFrom::Start(self.start + self.position))?;
+		let num_bytes_read = source.read(buf)?;
+		self.position += num_bytes_read as u64;
+		Ok(num_bytes_read)
+	}
+}
+
+impl Seek for IndexedSource {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		let position = match pos {
+			SeekFrom::Start(position) => Some(position),
+			SeekFrom::End(offset) => (self.len - self.start).checked_add_signed(offset),
+			SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+		}
+		.ok_or_else(|| {
+			std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				"invalid seek to a negative position",
+			)
+		})?;
+		self.position = position;
+		Ok(position)
+	}
+}
+
+impl MediaSource for IndexedSource {
+	fn is_seekable(&self) -> bool {
+		true
+	}
+
+	fn byte_len(&self) -> Option<u64> {
+		Some(self.len - self.start)
+	}
+}
+
+/// Returns the integrated loudness (in LUFS) given by ReplayGain or EBU
+/// R128 tags. Track gains are preferred over album gains.
+#[must_use]
+fn loudness_from_tags(tags: &[Tag]) -> Option<f64> {
+	let mut track_loudness = None;
+	let mut album_loudness = None;
+	for tag in tags {
+		let key = tag.key.to_ascii_uppercase();
+		match (tag.std_key, key.as_str()) {
+			(Some(StandardTagKey::ReplayGainTrackGain), _) => {
+				track_loudness = track_loudness.or(replay_gain_loudness(&tag.value));
+			}
+			(Some(StandardTagKey::ReplayGainAlbumGain), _) => {
+				album_loudness = album_loudness.or(replay_gain_loudness(&tag.value));
+			}
+			(_, "R128_TRACK_GAIN") => {
+				track_loudness = track_loudness.or(r128_loudness(&tag.value));
+			}
+			(_, "R128_ALBUM_GAIN") => {
+				album_loudness = album_loudness.or(r128_loudness(&tag.value));
+			}
+			_ => {}
+		}
+	}
+	track_loudness.or(album_loudness)
+}
+
+/// Converts a ReplayGain gain, like `-6.5 dB`, to a loudness.
+#[must_use]
+fn replay_gain_loudness(value: &Value) -> Option<f64> {
+	let gain = match value {
+		Value::Float(gain) => *gain,
+		Value::String(gain) => {
+			let gain = gain.trim();
+			let gain = gain
+				.strip_suffix("dB")
+				.or_else(|| gain.strip_suffix("db"))
+				.unwrap_or(gain);
+			gain.trim().parse().ok()?
+		}
+		_ => return None,
+	};
+	Some(REPLAY_GAIN_REFERENCE_LOUDNESS - gain)
+}
+
+/// Converts an EBU R128 gain, which is stored in 1/256ths of a decibel,
+/// to a loudness.
+#[must_use]
+fn r128_loudness(value: &Value) -> Option<f64> {
+	let gain = match value {
+		Value::SignedInt(gain) => *gain as f64,
+		Value::UnsignedInt(gain) => *gain as f64,
+		Value::String(gain) => gain.trim().parse::<i64>().ok()? as f64,
+		_ => return None,
+	};
+	Some(R128_REFERENCE_LOUDNESS - gain / 256.0)
+}
@@ -0,0 +1,58 @@
+use symphonia::core::meta::{StandardTagKey, Tag, Value};
+
+use super::loudness_from_tags;
+
+/// Tests that the loudness of audio is read from ReplayGain tags.
+#[test]
+fn reads_replay_gain_tags() {
+	let tags = [Tag::new(
+		Some(StandardTagKey::ReplayGainTrackGain),
+		"REPLAYGAIN_TRACK_GAIN",
+		Value::from("-6.50 dB"),
+	)];
+	assert_eq!(loudness_from_tags(&tags), Some(-11.5));
+}
+
+/// Tests that the loudness of audio is read from EBU R128 tags.
+#[test]
+fn reads_r128_tags() {
+	let tags = [Tag::new(None, "r128_track_gain", Value::from("-512"))];
+	assert_eq!(loudness_from_tags(&tags), Some(-21.0));
+}
+
+/// Tests that track gains are preferred over album gains.
+#[test]
+fn prefers_track_gain() {
+	let tags = [
+		Tag::new(
+			Some(StandardTagKey::ReplayGainAlbumGain),
+			"REPLAYGAIN_ALBUM_GAIN",
+			Value::from("-3 dB"),
+		),
+		Tag::new(
+			Some(StandardTagKey::ReplayGainTrackGain),
+			"REPLAYGAIN_TRACK_GAIN",
+			Value::from("2 dB"),
+		),
+	];
+	assert_eq!(loudness_from_tags(&tags), Some(-20.0));
+	assert_eq!(loudness_from_tags(&tags[..1]), Some(-15.0));
+}
+
+/// Tests that audio without loudness tags has an unknown loudness.
+#[test]
+fn ignores_other_tags() {
+	let tags = [
+		Tag::new(
+			Some(StandardTagKey::TrackTitle),
+			"TITLE",
+			Value::from("Song"),
+		),
+		Tag::new(
+			Some(StandardTagKey::ReplayGainTrackGain),
+			"REPLAYGAIN_TRACK_GAIN",
+			Value::from("loud"),
+		),
+	];
+	assert_eq!(loudness_from_tags(&tags), None);
+}
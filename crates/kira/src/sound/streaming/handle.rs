@@ -5,18 +5,57 @@ use std::{
 
 use crate::{
 	command::handle_param_setters,
-	sound::{IntoOptionalRegion, PlaybackState},
-	Decibels, Panning, PlaybackRate, StartTime, Tween,
+	sound::{FinishedReceiver, IntoOptionalRegion, PlaybackState, Region, SoundSendLevelWriters},
+	track::{NonexistentRoute, SendTrackId, TrackId},
+	Decibels, Panning, PlaybackRate, StartTime, Tween, Value,
 };
 use rtrb::Consumer;
 
 use super::{sound::Shared, CommandWriters};
 
+/// Whether a streaming sound has audio to play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamState {
+	/// The sound is waiting for the decoder to provide more audio.
+	Buffering,
+	/// The sound has decoded audio to play.
+	///
+	/// This doesn't mean the sound isn't paused. Use
+	/// [`StreamingSoundHandle::state`] to get the playback state of the sound.
+	Playing,
+	/// The decoder reached the end of the audio. The sound may still be
+	/// playing the last of the decoded audio.
+	Ended,
+}
+
+/// An estimate of how much memory a streaming sound is using.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StreamingMemoryUsage {
+	/// The memory used by audio that's been decoded ahead of time (in
+	/// bytes), including the frames kept decoded at the start of the loop
+	/// region and the next section.
+	pub buffer: usize,
+	/// The memory used by the decoder's own buffers (in bytes).
+	pub decoder: usize,
+	/// The memory used by the seek index (in bytes), if the sound has one.
+	pub seek_index: usize,
+}
+
+impl StreamingMemoryUsage {
+	/// Returns the total memory used by the sound (in bytes).
+	#[must_use]
+	pub fn total(&self) -> usize {
+		self.buffer + self.decoder + self.seek_index
+	}
+}
+
 /// Controls a streaming sound.
 pub struct StreamingSoundHandle<Error> {
 	pub(super) shared: Arc<Shared>,
 	pub(super) command_writers: CommandWriters,
+	pub(super) send_level_command_writers: SoundSendLevelWriters,
 	pub(super) error_consumer: Mutex<Consumer<Error>>,
+	pub(super) loudness_gain: Decibels,
 }
 
 impl<Error> StreamingSoundHandle<Error> {
@@ -27,11 +66,143 @@ impl<Error> StreamingSoundHandle<Error> {
 	}
 
 	/// Returns the current playback position of the sound (in seconds).
+	///
+	/// The audio thread only reports the position once per chunk of
+	/// audio it processes, so the position is moved forward by the time
+	/// that's passed since it was last reported. This makes the position
+	/// advance smoothly between reports, which is useful for syncing
+	/// visuals to the sound. On the web, this is the same as
+	/// [`raw_position`](Self::raw_position).
 	#[must_use]
 	pub fn position(&self) -> f64 {
+		self.shared.interpolated_position()
+	}
+
+	/// Returns the playback position of the sound (in seconds) the last
+	/// time the audio thread reported it.
+	#[must_use]
+	pub fn raw_position(&self) -> f64 {
 		self.shared.position()
 	}
 
+	/**
+	Returns a [`FinishedReceiver`] that can be checked to find out
+	when and why the sound finished playing.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::streaming::{StreamingSoundData, StreamingSoundSettings},
+	# };
+	use kira::sound::FinishReason;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut sound = manager.play(StreamingSoundData::from_file("sound.ogg")?)?;
+	let finished = sound.on_finished();
+	// later...
+	if finished.try_recv() == Some(FinishReason::EncounteredError) {
+		if let Some(error) = sound.pop_error() {
+			eprintln!("streaming failed: {}", error);
+		}
+	}
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn on_finished(&self) -> FinishedReceiver {
+		FinishedReceiver {
+			finished: self.shared.finished(),
+		}
+	}
+
+	/// Returns how many more times playback will jump back to the start
+	/// of the loop region, or `None` if the sound loops indefinitely.
+	///
+	/// Streaming sounds decode audio ahead of time, so this can change
+	/// slightly before the jump is audible.
+	#[must_use]
+	pub fn remaining_loops(&self) -> Option<u32> {
+		self.shared.remaining_loops()
+	}
+
+	/// Returns `true` if the sound is waiting for the decoder to provide
+	/// more audio, either because it just started or because the decoder
+	/// couldn't keep up.
+	///
+	/// The sound outputs silence while it's buffering.
+	#[must_use]
+	pub fn is_buffering(&self) -> bool {
+		self.shared.is_buffering()
+	}
+
+	/// Returns how many times the sound ran out of decoded audio after
+	/// it started playing.
+	///
+	/// Waiting for audio when the sound first starts isn't counted.
+	/// If this keeps increasing, consider using a longer
+	/// [`buffer_length`](super::StreamingSoundSettings::buffer_length).
+	#[must_use]
+	pub fn underrun_count(&self) -> u64 {
+		self.shared.underrun_count()
+	}
+
+	/// Returns whether the sound is waiting for audio, playing decoded
+	/// audio, or has decoded all of its audio.
+	///
+	/// This is useful for showing the state of audio that's streamed over
+	/// a network.
+	#[must_use]
+	pub fn stream_state(&self) -> StreamState {
+		if self.shared.reached_end() {
+			StreamState::Ended
+		} else if self.shared.is_buffering() {
+			StreamState::Buffering
+		} else {
+			StreamState::Playing
+		}
+	}
+
+	/// Returns the total number of frames of silence the sound output
+	/// because it ran out of decoded audio after it started playing.
+	#[must_use]
+	pub fn silent_frames_inserted(&self) -> u64 {
+		self.shared.silent_frames_inserted()
+	}
+
+	/// Returns the index of the section that's playing, counting every
+	/// section that's played so far, or `None` if the sound isn't playing
+	/// a section.
+	///
+	/// The first section in
+	/// [`section_sequence`](super::StreamingSoundSettings::section_sequence)
+	/// has an index of `0`, and sections queued with
+	/// [`queue_section`](Self::queue_section) continue the count.
+	#[must_use]
+	pub fn current_section_index(&self) -> Option<usize> {
+		self.shared.section_index()
+	}
+
+	/// Returns the gain applied to the sound to normalize its loudness.
+	///
+	/// This is [`Decibels::IDENTITY`] if
+	/// [`loudness_normalization`](super::StreamingSoundSettings::loudness_normalization)
+	/// isn't set or the sound doesn't have loudness tags.
+	#[must_use]
+	pub fn loudness_gain(&self) -> Decibels {
+		self.loudness_gain
+	}
+
+	/// Returns an estimate of how much memory the sound is using.
+	///
+	/// This is updated by the decoder as it decodes audio, so it changes
+	/// as the decoder's buffers grow and shrink.
+	#[must_use]
+	pub fn memory_usage(&self) -> StreamingMemoryUsage {
+		self.shared.memory_usage()
+	}
+
 	handle_param_setters! {
 		/**
 		Sets the volume of the sound.
@@ -289,10 +460,112 @@ impl<Error> StreamingSoundHandle<Error> {
 			.write(loop_region.into_optional_region())
 	}
 
+	/**
+	Queues a portion of the sound to play after the current section
+	ends.
+
+	If the sound isn't playing a section, the queued section plays once
+	the sound reaches the end of the audio. The start of the next section
+	is decoded ahead of time, so the transition is seamless even if the
+	section is earlier in the audio.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::streaming::{StreamingSoundData, StreamingSoundSettings},
+	# };
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	# let mut sound = manager.play(StreamingSoundData::from_file("music.ogg")?.section_sequence(vec![(0.0..8.0).into()]))?;
+	// play the chorus after the intro
+	sound.queue_section(24.0..40.0);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn queue_section(&mut self, section: impl Into<Region>) {
+		self.shared.queue_sections([section.into()]);
+	}
+
+	/// Removes every queued section. The current section still plays
+	/// until its end, and then the sound finishes.
+	pub fn clear_queued_sections(&mut self) {
+		self.shared.clear_queued_sections();
+	}
+
+	/**
+	Sets how many times the loop region should play before playback
+	continues to the end of the sound, or `None` to loop indefinitely.
+
+	The count includes the pass through the loop region that's currently
+	being decoded, so setting the loop count to `1` lets the sound finish
+	after the current loop.
+	*/
+	pub fn set_loop_count(&mut self, loop_count: impl Into<Option<u32>>) {
+		self.command_writers.set_loop_count.write(loop_count.into())
+	}
+
+	/**
+	Moves the sound to a different mixer track.
+
+	The sound's output crossfades from its current track to the new
+	track over the duration of the tween, starting at the tween's start
+	time. The destination can be a sub-track, a spatial sub-track, or
+	the main track.
+
+	If the destination track has been removed or has no room for more
+	sounds, the sound stays on its current track.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::streaming::StreamingSoundData,
+	# 	track::TrackBuilder,
+	# };
+	use kira::Tween;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let track = manager.add_sub_track(TrackBuilder::new())?;
+	let mut sound = manager.play(StreamingSoundData::from_file("sound.ogg")?)?;
+	sound.set_output_destination(&track, Tween::default());
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn set_output_destination(&mut self, track: impl Into<TrackId>, tween: Tween) {
+		self.command_writers
+			.set_output_destination
+			.write((track.into(), tween))
+	}
+
+	/// Sets the volume of the sound's send to a send track.
+	///
+	/// This can only be used to change the volume of sends the sound was
+	/// played with using [`StreamingSoundSettings::send`](super::StreamingSoundSettings::send),
+	/// not to add new sends.
+	pub fn set_send_level(
+		&mut self,
+		to: impl Into<SendTrackId>,
+		volume: impl Into<Value<Decibels>>,
+		tween: Tween,
+	) -> Result<(), NonexistentRoute> {
+		self.send_level_command_writers.set(to, volume, tween)
+	}
+
 	/// Fades out the sound to silence with the given tween and then
 	/// pauses playback.
 	pub fn pause(&mut self, tween: Tween) {
-		self.command_writers.pause.write(tween)
+		self.pause_at(StartTime::Immediate, tween)
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback once the start time is reached.
+	///
+	/// If the start time can never be reached (for example, because the
+	/// clock it refers to was removed), the pause is discarded.
+	pub fn pause_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.pause.write((start_time, tween))
 	}
 
 	/// Resumes playback and fades in the sound from silence
@@ -312,15 +585,52 @@ impl<Error> StreamingSoundHandle<Error> {
 	///
 	/// Once the sound is stopped, it cannot be restarted.
 	pub fn stop(&mut self, tween: Tween) {
-		self.command_writers.stop.write(tween)
+		self.stop_at(StartTime::Immediate, tween)
+	}
+
+	/**
+	Fades out the sound to silence with the given tween and then
+	stops playback once the start time is reached.
+
+	If the start time can never be reached (for example, because the
+	clock it refers to was removed), the stop is discarded and the
+	sound keeps playing.
+
+	# Examples
+
+	Stop the sound on the next beat:
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::streaming::StreamingSoundData,
+	# 	clock::ClockSpeed,
+	# };
+	use kira::Tween;
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let clock = manager.add_clock(ClockSpeed::TicksPerMinute(120.0))?;
+	let mut sound = manager.play(StreamingSoundData::from_file("sound.ogg")?)?;
+	sound.stop_at((clock.time() + 1).into(), Tween::default());
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn stop_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.stop.write((start_time, tween))
 	}
 
 	/// Sets the playback position to the specified time in seconds.
+	///
+	/// If the decoder can't seek, the sound keeps playing from where it
+	/// was, and the error can be retrieved with [`pop_error`](Self::pop_error).
 	pub fn seek_to(&mut self, position: f64) {
 		self.command_writers.seek_to.write(position)
 	}
 
 	/// Moves the playback position by the specified amount of time in seconds.
+	///
+	/// If the decoder can't seek, the sound keeps playing from where it
+	/// was, and the error can be retrieved with [`pop_error`](Self::pop_error).
 	pub fn seek_by(&mut self, amount: f64) {
 		self.command_writers.seek_by.write(amount)
 	}
@@ -334,6 +644,28 @@ impl<Error> StreamingSoundHandle<Error> {
 			.pop()
 			.ok()
 	}
+
+	/// Returns every error that occurred while decoding audio since the
+	/// last time errors were retrieved.
+	///
+	/// Only the 64 most recent errors are kept until they're retrieved.
+	/// Older errors are discarded.
+	#[must_use]
+	pub fn take_decode_errors(&mut self) -> Vec<Error> {
+		let mut error_consumer = self
+			.error_consumer
+			.lock()
+			.expect("error consumer mutex poisoned");
+		std::iter::from_fn(|| error_consumer.pop().ok()).collect()
+	}
+}
+
+impl<Error> Drop for StreamingSoundHandle<Error> {
+	fn drop(&mut self) {
+		// lets sounds that paused at the end finish, since they can't be
+		// resumed anymore
+		self.shared.mark_handle_dropped();
+	}
 }
 
 impl<Error: Debug> Debug for StreamingSoundHandle<Error> {
@@ -341,6 +673,10 @@ impl<Error: Debug> Debug for StreamingSoundHandle<Error> {
 		f.debug_struct("StreamingSoundHandle")
 			.field("shared", &self.shared)
 			.field("command_writers", &self.command_writers)
+			.field(
+				"send_level_command_writers",
+				&self.send_level_command_writers,
+			)
 			.field("error_consumer", &ConsumerDebug)
 			.finish()
 	}
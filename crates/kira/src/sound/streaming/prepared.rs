@@ -0,0 +1,193 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test;
+
+use std::{fmt::Debug, sync::Arc, time::Duration};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+	sync::atomic::{AtomicU8, Ordering},
+	thread::JoinHandle,
+	time::Instant,
+};
+
+use crate::sound::{PlaybackState, Sound, SoundData};
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::sound::decode_scheduler::DecodeScheduler;
+use super::{
+	sound::{Shared, StreamingSound},
+	StreamingSoundData, StreamingSoundHandle,
+};
+
+/// How long a [`PreparedStreamingSoundData`] keeps its decoding thread
+/// running while waiting to be played.
+pub const MAX_PREPARED_DURATION: Duration = Duration::from_secs(10);
+
+/**
+A streaming sound that has started decoding audio, but is not playing yet.
+
+Created by [`StreamingSoundData::prepare`]. Pass it to
+[`AudioManager::play`](crate::AudioManager::play) to play the sound.
+
+The initial audio is decoded ahead of time, so playback can start
+right away.
+If the sound isn't played within [`MAX_PREPARED_DURATION`], the decoding
+thread stops so it doesn't idle forever. The audio that was already decoded
+is kept, and decoding resumes when the sound is played.
+
+Dropping a [`PreparedStreamingSoundData`] without playing it stops the
+decoding thread.
+*/
+pub struct PreparedStreamingSoundData<Error: Send + 'static> {
+	/// The parts of the sound, which are taken out when the sound is played.
+	prepared: Option<Prepared<Error>>,
+	shared: Arc<Shared>,
+}
+
+struct Prepared<Error: Send + 'static> {
+	sound: StreamingSound,
+	handle: StreamingSoundHandle<Error>,
+	#[cfg(not(target_arch = "wasm32"))]
+	preparation: Arc<Preparation>,
+	/// The thread decoding audio for the sound, or `None` if the sound is
+	/// decoded by a worker pool.
+	#[cfg(not(target_arch = "wasm32"))]
+	decoder_thread: Option<JoinHandle<Option<DecodeScheduler<Error>>>>,
+}
+
+impl<Error: Send + 'static> PreparedStreamingSoundData<Error> {
+	pub(super) fn new(
+		data: StreamingSoundData<Error>,
+		#[cfg_attr(target_arch = "wasm32", allow(unused_variables))] max_duration: Duration,
+	) -> Result<Self, Error> {
+		#[cfg(not(target_arch = "wasm32"))]
+		let worker_pool = data.settings.worker_pool.clone();
+		#[cfg(not(target_arch = "wasm32"))]
+		let decode_on_audio_thread = data.settings.decode_on_audio_thread;
+		let (mut sound, handle, scheduler) = data.split()?;
+		let shared = handle.shared.clone();
+		// threads can't be spawned on the web, so the initial audio is
+		// decoded right away instead
+		#[cfg(target_arch = "wasm32")]
+		sound.decode_on_audio_thread(scheduler);
+		#[cfg(not(target_arch = "wasm32"))]
+		let preparation = Arc::new(Preparation::new(max_duration));
+		#[cfg(not(target_arch = "wasm32"))]
+		let decoder_thread = if decode_on_audio_thread {
+			sound.decode_on_audio_thread(scheduler);
+			None
+		} else {
+			match worker_pool {
+				Some(worker_pool) => {
+					worker_pool.submit(Box::new(scheduler));
+					None
+				}
+				None => Some(scheduler.start_prepared(preparation.clone())),
+			}
+		};
+		Ok(Self {
+			prepared: Some(Prepared {
+				sound,
+				handle,
+				#[cfg(not(target_arch = "wasm32"))]
+				decoder_thread,
+				#[cfg(not(target_arch = "wasm32"))]
+				preparation,
+			}),
+			shared,
+		})
+	}
+}
+
+impl<Error: Send + 'static> SoundData for PreparedStreamingSoundData<Error> {
+	type Error = Error;
+
+	type Handle = StreamingSoundHandle<Error>;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(mut self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let prepared = self
+			.prepared
+			.take()
+			.expect("prepared sound was already played");
+		// if the decoding thread already stopped, resume decoding on a
+		// new thread
+		#[cfg(not(target_arch = "wasm32"))]
+		if let Some(decoder_thread) = prepared.decoder_thread {
+			if !prepared.preparation.go_live() {
+				if let Some(scheduler) = decoder_thread.join().expect("decoder thread panicked") {
+					scheduler.start();
+				}
+			}
+		}
+		Ok((Box::new(prepared.sound), prepared.handle))
+	}
+}
+
+impl<Error: Send + 'static> Drop for PreparedStreamingSoundData<Error> {
+	fn drop(&mut self) {
+		// if the sound was never played, let the decoding thread know
+		// it can stop
+		if let Some(prepared) = self.prepared.take() {
+			self.shared.set_state(PlaybackState::Stopped);
+			// if the decoding thread expired, it handed back the scheduler,
+			// which has to be dropped before the sound waits for the
+			// decoder to finish
+			#[cfg(not(target_arch = "wasm32"))]
+			drop(prepared.decoder_thread);
+			#[cfg(target_arch = "wasm32")]
+			drop(prepared);
+		}
+	}
+}
+
+impl<Error: Send + 'static> Debug for PreparedStreamingSoundData<Error> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("PreparedStreamingSoundData")
+			.field("shared", &self.shared)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Coordinates handing a prepared sound over to the audio thread with
+/// the decoding thread stopping when the preparation expires.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub(crate) struct Preparation {
+	expires_at: Instant,
+	state: AtomicU8,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Preparation {
+	const WAITING: u8 = 0;
+	const LIVE: u8 = 1;
+	const EXPIRED: u8 = 2;
+
+	#[must_use]
+	fn new(max_duration: Duration) -> Self {
+		Self {
+			expires_at: Instant::now() + max_duration,
+			state: AtomicU8::new(Self::WAITING),
+		}
+	}
+
+	/// Marks the sound as played. Returns `false` if the decoding thread
+	/// already stopped.
+	#[must_use]
+	fn go_live(&self) -> bool {
+		self.transition(Self::LIVE)
+	}
+
+	/// Marks the preparation as expired if the sound hasn't been played
+	/// in time. Returns `true` if the decoding thread should stop.
+	#[must_use]
+	pub fn expire(&self) -> bool {
+		Instant::now() >= self.expires_at && self.transition(Self::EXPIRED)
+	}
+
+	fn transition(&self, state: u8) -> bool {
+		self.state
+			.compare_exchange(Self::WAITING, state, Ordering::SeqCst, Ordering::SeqCst)
+			.is_ok()
+	}
+}
@@ -0,0 +1,105 @@
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use crate::{
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::{
+		streaming::{
+			decoder::mock::MockDecoder, PreparedStreamingSoundData, StreamingSoundData,
+			StreamingSoundSettings,
+		},
+		SoundData,
+	},
+	Panning,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn wait_for_decoder_thread<Error: Send + 'static>(prepared: &PreparedStreamingSoundData<Error>) {
+	let start = Instant::now();
+	while !prepared
+		.prepared
+		.as_ref()
+		.unwrap()
+		.decoder_thread
+		.as_ref()
+		.unwrap()
+		.is_finished()
+	{
+		assert!(
+			start.elapsed() < TIMEOUT,
+			"decoder thread did not finish in time"
+		);
+		std::thread::sleep(Duration::from_millis(1));
+	}
+}
+
+/// Tests that a `PreparedStreamingSoundData` decodes audio before
+/// it's played.
+#[test]
+fn decodes_before_playing() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![
+			Frame::from_mono(1.0),
+			Frame::from_mono(2.0),
+			Frame::from_mono(3.0),
+		])),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let prepared = PreparedStreamingSoundData::new(data, Duration::from_secs(600)).unwrap();
+	// the decoder thread finishes once it decodes the whole sound
+	wait_for_decoder_thread(&prepared);
+	let (mut sound, _) = prepared.into_sound().unwrap();
+	for i in 1..=3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
+
+/// Tests that dropping a `PreparedStreamingSoundData` stops its
+/// decoder thread.
+#[test]
+fn stops_decoding_when_dropped() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
+		settings: StreamingSoundSettings::new().buffer_length(10),
+		slice: None,
+	};
+	let prepared = PreparedStreamingSoundData::new(data, Duration::from_secs(600)).unwrap();
+	let shared = prepared.shared.clone();
+	drop(prepared);
+	// the decoder thread holds on to the shared state until it exits
+	let start = Instant::now();
+	while Arc::strong_count(&shared) > 1 {
+		assert!(start.elapsed() < TIMEOUT, "decoder thread did not stop");
+		std::thread::sleep(Duration::from_millis(1));
+	}
+}
+
+/// Tests that a `PreparedStreamingSoundData` resumes decoding when it's
+/// played after its decoder thread stopped waiting.
+#[test]
+fn resumes_decoding_after_expiring() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
+		settings: StreamingSoundSettings::new().buffer_length(4),
+		slice: None,
+	};
+	let prepared = PreparedStreamingSoundData::new(data, Duration::ZERO).unwrap();
+	wait_for_decoder_thread(&prepared);
+	let (mut sound, handle) = prepared.into_sound().unwrap();
+	for _ in 0..30 {
+		std::thread::sleep(Duration::from_millis(5));
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(1.0).panned(Panning::CENTER)
+		);
+	}
+	assert_eq!(handle.underrun_count(), 0);
+}
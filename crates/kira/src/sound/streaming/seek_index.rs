@@ -0,0 +1,286 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	io::{BufReader, Read, Seek, SeekFrom},
+	mem::size_of,
+};
+
+use crate::sound::FromFileError;
+
+/// How many MPEG frames apart the points in a [`SeekIndex`] are.
+const FRAMES_PER_SEEK_POINT: usize = 16;
+/// The length of an MPEG frame header (in bytes).
+const MPEG_HEADER_LEN: u64 = 4;
+/// The length of an ID3v1 tag (in bytes), which is sometimes stored at
+/// the end of MP3 files.
+const ID3V1_TAG_LEN: u64 = 128;
+
+/**
+A table of positions in an audio file that a streaming sound can seek
+to quickly and accurately.
+
+Some formats, like MP3 files with a variable bitrate, don't store where
+each part of the audio is in the file, so seeking has to read through
+the file from the beginning to find the right position. A seek index
+records where the audio is ahead of time, so it only has to be built
+once, for example when the game is installed, and can be saved with
+[serde](https://serde.rs) when the `serde` feature is enabled.
+
+Seek indexes can be built for MP3 files with
+[`StreamingSoundData::build_seek_index`](super::StreamingSoundData::build_seek_index).
+Other formats Kira supports can already seek quickly.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeekIndex {
+	/// The length of the file the index was built for (in bytes).
+	byte_len: u64,
+	/// The exact number of frames of audio in the file.
+	num_frames: usize,
+	points: Vec<SeekPoint>,
+}
+
+impl SeekIndex {
+	/// Returns the exact number of frames of audio in the file the index
+	/// was built for.
+	#[must_use]
+	pub fn num_frames(&self) -> usize {
+		self.num_frames
+	}
+
+	/// Returns the positions in the file that can be seeked to.
+	#[must_use]
+	pub fn points(&self) -> &[SeekPoint] {
+		&self.points
+	}
+
+	/// Returns how much memory the seek index is using (in bytes).
+	#[must_use]
+	pub fn memory_usage(&self) -> usize {
+		size_of::<Self>() + self.points.capacity() * size_of::<SeekPoint>()
+	}
+
+	/// Returns the length of the file the index was built for (in bytes).
+	#[must_use]
+	#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+	pub(crate) fn byte_len(&self) -> u64 {
+		self.byte_len
+	}
+
+	/// Returns the last point at or before the given frame.
+	#[must_use]
+	pub(crate) fn point_before(&self, frame_index: usize) -> SeekPoint {
+		let num_points_before = self
+			.points
+			.partition_point(|point| point.frame_index <= frame_index);
+		self.points[num_points_before.saturating_sub(1)]
+	}
+
+	/// Builds a seek index by reading through an MP3 file from any reader
+	/// that supports seeking.
+	///
+	/// To build a seek index for a file on disk, use
+	/// [`StreamingSoundData::build_seek_index`](super::StreamingSoundData::build_seek_index).
+	pub fn from_read(reader: impl Read + Seek) -> Result<Self, FromFileError> {
+		let mut reader = BufReader::new(reader);
+		let byte_len = reader.seek(SeekFrom::End(0))?;
+		reader.seek(SeekFrom::Start(0))?;
+		let mut position = skip_id3v2_tags(&mut reader)?;
+		let mut points = vec![];
+		let mut num_frames = 0;
+		let mut num_mpeg_frames = 0;
+		let mut is_first_frame = true;
+		while position + MPEG_HEADER_LEN <= byte_len {
+			let mut header = [0; MPEG_HEADER_LEN as usize];
+			reader.read_exact(&mut header)?;
+			let Some(frame) = FrameHeader::parse(header) else {
+				if &header[..3] == b"TAG" && byte_len - position == ID3V1_TAG_LEN {
+					break;
+				}
+				// look for the next frame one byte at a time
+				reader.seek_relative(1 - MPEG_HEADER_LEN as i64)?;
+				position += 1;
+				continue;
+			};
+			// a frame cut off by the end of the file can't be decoded
+			if position + frame.len > byte_len {
+				break;
+			}
+			let mut body = vec![0; (frame.len - MPEG_HEADER_LEN) as usize];
+			reader.read_exact(&mut body)?;
+			// the decoder skips the first frame if it only holds information
+			// about the file
+			let is_info_frame = is_first_frame && frame.is_info(&body);
+			is_first_frame = false;
+			if !is_info_frame {
+				if num_mpeg_frames % FRAMES_PER_SEEK_POINT == 0 {
+					points.push(SeekPoint {
+						frame_index: num_frames,
+						byte_offset: position,
+					});
+				}
+				num_mpeg_frames += 1;
+				num_frames += frame.num_samples;
+			}
+			position += frame.len;
+		}
+		if points.is_empty() {
+			return Err(FromFileError::SeekIndexUnsupported);
+		}
+		Ok(Self {
+			byte_len,
+			num_frames,
+			points,
+		})
+	}
+}
+
+/// A position in an audio file that a streaming sound can seek to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeekPoint {
+	/// The index of the first frame of audio decoded from this position.
+	pub frame_index: usize,
+	/// The position in the file (in bytes).
+	pub byte_offset: u64,
+}
+
+/// Skips the ID3v2 tags at the start of an MP3 file and returns the
+/// position after them.
+fn skip_id3v2_tags(reader: &mut (impl Read + Seek)) -> Result<u64, FromFileError> {
+	let mut position = 0;
+	loop {
+		let mut header = [0; 10];
+		if reader.read_exact(&mut header).is_err() || &header[..3] != b"ID3" {
+			reader.seek(SeekFrom::Start(position))?;
+			return Ok(position);
+		}
+		// the size is stored as 4 7-bit bytes
+		let size = header[6..]
+			.iter()
+			.fold(0, |size, byte| (size << 7) | u64::from(byte & 0x7f));
+		let has_footer = header[5] & 0x10 != 0;
+		position += 10 + size + if has_footer { 10 } else { 0 };
+		reader.seek(SeekFrom::Start(position))?;
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MpegVersion {
+	Mpeg1,
+	Mpeg2,
+	Mpeg2p5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MpegLayer {
+	Layer1,
+	Layer2,
+	Layer3,
+}
+
+/// The parts of an MPEG frame header needed to find the next frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FrameHeader {
+	version: MpegVersion,
+	layer: MpegLayer,
+	mono: bool,
+	/// The length of the frame, including the header (in bytes).
+	len: u64,
+	/// The number of samples of audio in the frame.
+	num_samples: usize,
+}
+
+impl FrameHeader {
+	/// Parses an MPEG frame header, or returns `None` if the bytes aren't
+	/// a valid header.
+	#[must_use]
+	fn parse(header: [u8; 4]) -> Option<Self> {
+		if header[0] != 0xff || header[1] & 0xe0 != 0xe0 {
+			return None;
+		}
+		let version = match (header[1] >> 3) & 0b11 {
+			0b00 => MpegVersion::Mpeg2p5,
+			0b10 => MpegVersion::Mpeg2,
+			0b11 => MpegVersion::Mpeg1,
+			_ => return None,
+		};
+		let layer = match (header[1] >> 1) & 0b11 {
+			0b01 => MpegLayer::Layer3,
+			0b10 => MpegLayer::Layer2,
+			0b11 => MpegLayer::Layer1,
+			_ => return None,
+		};
+		let bitrate_index = (header[2] >> 4) as usize;
+		// free format bitrates (0) aren't supported
+		if bitrate_index == 0 || bitrate_index == 15 {
+			return None;
+		}
+		let bitrate_kbps: u64 = match (version, layer) {
+			(MpegVersion::Mpeg1, MpegLayer::Layer1) => [
+				0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+			],
+			(MpegVersion::Mpeg1, MpegLayer::Layer2) => [
+				0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+			],
+			(MpegVersion::Mpeg1, MpegLayer::Layer3) => [
+				0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+			],
+			(_, MpegLayer::Layer1) => [
+				0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+			],
+			_ => [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+		}[bitrate_index];
+		let sample_rate: u64 = match (header[2] >> 2) & 0b11 {
+			0b11 => return None,
+			sample_rate_index => {
+				let base_sample_rate = [44_100, 48_000, 32_000][sample_rate_index as usize];
+				match version {
+					MpegVersion::Mpeg1 => base_sample_rate,
+					MpegVersion::Mpeg2 => base_sample_rate / 2,
+					MpegVersion::Mpeg2p5 => base_sample_rate / 4,
+				}
+			}
+		};
+		let padding = u64::from((header[2] >> 1) & 1);
+		let bitrate = bitrate_kbps * 1000;
+		let (len, num_samples) = match (layer, version) {
+			(MpegLayer::Layer1, _) => ((12 * bitrate / sample_rate + padding) * 4, 384),
+			(MpegLayer::Layer2, _) | (MpegLayer::Layer3, MpegVersion::Mpeg1) => {
+				(144 * bitrate / sample_rate + padding, 1152)
+			}
+			(MpegLayer::Layer3, _) => (72 * bitrate / sample_rate + padding, 576),
+		};
+		Some(Self {
+			version,
+			layer,
+			mono: header[3] >> 6 == 0b11,
+			len,
+			num_samples,
+		})
+	}
+
+	/// Returns `true` if the frame holds a Xing, Info, or VBRI tag
+	/// instead of audio. `body` is the frame after the header.
+	#[must_use]
+	fn is_info(&self, body: &[u8]) -> bool {
+		if self.layer != MpegLayer::Layer3 {
+			return false;
+		}
+		let side_info_len = match (self.version, self.mono) {
+			(MpegVersion::Mpeg1, true) => 17,
+			(MpegVersion::Mpeg1, false) => 32,
+			(_, true) => 9,
+			(_, false) => 17,
+		};
+		let is_xing = body
+			.get(side_info_len..side_info_len + 4)
+			.is_some_and(|id| {
+				(id == b"Xing" || id == b"Info")
+					&& body[..side_info_len].iter().all(|&byte| byte == 0)
+			});
+		let is_vbri = body.get(32..36).is_some_and(|id| id == b"VBRI");
+		is_xing || is_vbri
+	}
+}
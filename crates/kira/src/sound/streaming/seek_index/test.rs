@@ -0,0 +1,106 @@
+use std::io::Cursor;
+
+#[cfg(feature = "mp3")]
+use crate::sound::streaming::{symphonia::SymphoniaDecoder, Decoder};
+use crate::sound::FromFileError;
+
+use super::{SeekIndex, SeekPoint};
+
+/// The header of an MPEG 1 Layer 3 frame with a bitrate of 128 kbps and a
+/// sample rate of 44.1 kHz.
+const FRAME_HEADER: [u8; 4] = [0xff, 0xfb, 0x90, 0x00];
+/// The length of each frame (in bytes).
+const FRAME_LEN: usize = 417;
+/// The number of samples in each frame.
+const SAMPLES_PER_FRAME: usize = 1152;
+
+/// Creates an MP3 file with an ID3v2 tag, an Info frame, and silent frames.
+#[must_use]
+fn mp3_file(num_frames: usize) -> Vec<u8> {
+	let mut file = vec![];
+	// an ID3v2 tag with 20 bytes of data
+	file.extend_from_slice(b"ID3\x04\x00\x00\x00\x00\x00\x14");
+	file.extend_from_slice(&[0; 20]);
+	let mut info_frame = FRAME_HEADER.to_vec();
+	info_frame.extend_from_slice(&[0; 32]);
+	info_frame.extend_from_slice(b"Info");
+	info_frame.resize(FRAME_LEN, 0);
+	file.extend_from_slice(&info_frame);
+	for _ in 0..num_frames {
+		file.extend_from_slice(&FRAME_HEADER);
+		file.extend_from_slice(&[0; FRAME_LEN - 4]);
+	}
+	file
+}
+
+/// Tests that a `SeekIndex` records where frames are in an MP3 file.
+#[test]
+fn builds_seek_index() {
+	let mut file = mp3_file(40);
+	// an ID3v1 tag at the end of the file
+	file.extend_from_slice(b"TAG");
+	file.extend_from_slice(&[0; 125]);
+	let seek_index = SeekIndex::from_read(Cursor::new(&file)).unwrap();
+	assert_eq!(seek_index.num_frames(), 40 * SAMPLES_PER_FRAME);
+	let first_frame_offset = 30 + FRAME_LEN as u64;
+	assert_eq!(
+		seek_index.points(),
+		[0, 16, 32].map(|i| SeekPoint {
+			frame_index: i * SAMPLES_PER_FRAME,
+			byte_offset: first_frame_offset + (i * FRAME_LEN) as u64,
+		})
+	);
+	assert_eq!(seek_index.point_before(0), seek_index.points()[0]);
+	assert_eq!(
+		seek_index.point_before(20 * SAMPLES_PER_FRAME),
+		seek_index.points()[1]
+	);
+	assert_eq!(seek_index.point_before(usize::MAX), seek_index.points()[2]);
+}
+
+/// Tests that a `SeekIndex` can't be built for files that aren't MP3s.
+#[test]
+fn only_builds_seek_index_for_mp3s() {
+	assert!(matches!(
+		SeekIndex::from_read(Cursor::new(vec![0u8; 1000])),
+		Err(FromFileError::SeekIndexUnsupported)
+	));
+}
+
+/// Tests that a decoder using a `SeekIndex` seeks to the points in the
+/// index and knows the exact length of the audio.
+#[test]
+#[cfg(feature = "mp3")]
+fn seeks_with_seek_index() {
+	let file = mp3_file(40);
+	let seek_index = SeekIndex::from_read(Cursor::new(file.clone())).unwrap();
+	let mut decoder =
+		SymphoniaDecoder::with_seek_index(Box::new(Cursor::new(file)), seek_index).unwrap();
+	assert_eq!(decoder.num_frames(), 40 * SAMPLES_PER_FRAME);
+	assert_eq!(
+		decoder.seek(20 * SAMPLES_PER_FRAME).unwrap(),
+		16 * SAMPLES_PER_FRAME
+	);
+	// the rest of the file is decoded from the seek point
+	let mut num_frames_decoded = 0;
+	loop {
+		match decoder.decode() {
+			Ok(frames) => num_frames_decoded += frames.len(),
+			Err(FromFileError::SymphoniaError(symphonia::core::errors::Error::IoError(_))) => break,
+			Err(error) => panic!("{}", error),
+		}
+	}
+	assert_eq!(num_frames_decoded, 24 * SAMPLES_PER_FRAME);
+	assert_eq!(decoder.seek(100).unwrap(), 0);
+}
+
+/// Tests that a `SeekIndex` can't be used for a different file.
+#[test]
+#[cfg(feature = "mp3")]
+fn rejects_mismatched_seek_index() {
+	let seek_index = SeekIndex::from_read(Cursor::new(mp3_file(40))).unwrap();
+	assert!(matches!(
+		SymphoniaDecoder::with_seek_index(Box::new(Cursor::new(mp3_file(30))), seek_index),
+		Err(FromFileError::SeekIndexMismatch)
+	));
+}
@@ -1,11 +1,62 @@
 use crate::{
-	sound::{IntoOptionalRegion, PlaybackPosition, Region},
-	Tween,
-	Decibels, Panning, PlaybackRate, StartTime, Value,
+	group::GroupId,
+	sound::{ChannelMixMode, IntoOptionalRegion, PlaybackPosition, Region, SoundSends},
+	track::SendTrackId,
+	Decibels, Panning, PlaybackRate, StartTime, Tween, Value,
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+use super::StreamingWorkerPool;
+
+/// The default number of frames of decoded audio a streaming sound
+/// keeps ahead of the playback position.
+pub const DEFAULT_BUFFER_LENGTH: usize = 16_384;
+
+/// What a streaming sound should do when the decoder fails to decode
+/// a chunk of audio.
+///
+/// In every case, the error is sent to the
+/// [`StreamingSoundHandle`](super::StreamingSoundHandle), where it can be
+/// retrieved with [`pop_error`](super::StreamingSoundHandle::pop_error) or
+/// [`take_decode_errors`](super::StreamingSoundHandle::take_decode_errors).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DecodeErrorBehavior {
+	/// The chunk is skipped and playback continues with the next chunk.
+	///
+	/// This is useful for audio files that are slightly damaged. If the
+	/// decoder keeps failing, playback ends as if it reached the end of the
+	/// audio.
+	Skip,
+	/// Playback ends as if it reached the end of the audio.
+	EndPlayback,
+	/// The sound stops with [`FinishReason::EncounteredError`](crate::sound::FinishReason::EncounteredError).
+	#[default]
+	Fail,
+}
+
+/// What a streaming sound should do when playback reaches the end of
+/// the audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EndBehavior {
+	/// The sound finishes.
+	#[default]
+	Finish,
+	/// The sound pauses at the end of the audio, and it can be seeked
+	/// and resumed without loading the audio again.
+	///
+	/// The sound only finishes once it's stopped, or once its handle is
+	/// dropped while it's paused at the end.
+	Pause,
+	/// Playback starts over from the beginning of the audio.
+	///
+	/// This applies after the loop region has played as many times as
+	/// [`loop_count`](StreamingSoundSettings::loop_count) allows and after
+	/// the last section has played.
+	LoopWholeFile,
+}
+
 /// Settings for a streaming sound.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StreamingSoundSettings {
 	/// When the sound should start playing.
 	pub start_time: StartTime,
@@ -13,6 +64,16 @@ pub struct StreamingSoundSettings {
 	pub start_position: PlaybackPosition,
 	/// The portion of the sound that should be looped.
 	pub loop_region: Option<Region>,
+	/// Whether to loop the loop points stored in the audio file, like the
+	/// ones in a WAV file's `smpl` chunk or `LOOPSTART` tags, when
+	/// [`loop_region`](Self::loop_region) is `None`. Defaults to `true`.
+	pub use_detected_loop_region: bool,
+	/// How many times the loop region should play before playback
+	/// continues to the end of the sound, or `None` to loop indefinitely.
+	///
+	/// A loop count of `0` or `1` plays the loop region once without
+	/// repeating it. This has no effect if there's no loop region.
+	pub loop_count: Option<u32>,
 	/// The volume of the sound.
 	pub volume: Value<Decibels>,
 	/// The playback rate of the sound.
@@ -23,8 +84,73 @@ pub struct StreamingSoundSettings {
 	/// The panning of the sound, where 0 is hard left
 	/// and 1 is hard right.
 	pub panning: Value<Panning>,
+	/// How the left and right channels are combined before volume and
+	/// panning are applied.
+	pub channel_mix_mode: ChannelMixMode,
+	/// The send tracks the sound sends its audio to, in addition to the
+	/// track it's playing on.
+	///
+	/// Sounds playing on the main track don't send audio to send tracks.
+	pub sends: SoundSends,
 	/// An optional fade-in from silence.
 	pub fade_in_tween: Option<Tween>,
+	/// An optional fade-out to silence as playback approaches the end
+	/// of the sound.
+	///
+	/// Only the duration and easing of the tween are used. The fade-out
+	/// isn't applied while there are loops left to play, and stopping the
+	/// sound using its handle still fades out with the tween given to
+	/// `stop`.
+	pub fade_out_tween: Option<Tween>,
+	/// The [group](crate::group) the sound belongs to.
+	pub group: Option<GroupId>,
+	/// How many frames of decoded audio can be kept ahead of the
+	/// playback position.
+	///
+	/// A longer buffer gives the decoder more time to catch up when it
+	/// falls behind, which helps to avoid dropouts on slow storage, at the
+	/// cost of more memory. Playback starts as soon as the first frames
+	/// are decoded, so a longer buffer doesn't delay the start of the
+	/// sound. Defaults to [`DEFAULT_BUFFER_LENGTH`].
+	///
+	/// Sped up sounds use up decoded audio more quickly, so the buffer
+	/// grows with the playback rate, up to 3 times this length.
+	pub buffer_length: usize,
+	/// What the sound should do when the decoder fails to decode a chunk
+	/// of audio.
+	pub on_decode_error: DecodeErrorBehavior,
+	/// What the sound should do when playback reaches the end of the
+	/// audio.
+	pub on_end: EndBehavior,
+	/// Portions of the sound that should play back-to-back.
+	///
+	/// If this isn't empty, playback starts at the beginning of the first
+	/// section instead of the start position, and the sound finishes at the
+	/// end of the last section. More sections can be queued with
+	/// [`StreamingSoundHandle::queue_section`](super::StreamingSoundHandle::queue_section).
+	/// The loop region is ignored while a section is playing.
+	pub section_sequence: Vec<Region>,
+	/// The integrated loudness (in LUFS) the sound should be normalized to,
+	/// or `None` to play the sound at its original loudness.
+	///
+	/// The loudness of the sound is read from ReplayGain or EBU R128 tags
+	/// when the sound is created. Sounds without loudness tags aren't
+	/// normalized. The gain is applied on top of
+	/// [`volume`](Self::volume).
+	pub loudness_normalization: Option<f64>,
+	/// The worker pool that should decode audio for the sound, or `None`
+	/// to decode audio on a thread of its own.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub worker_pool: Option<StreamingWorkerPool>,
+	/// Whether the sound should decode audio on the audio thread as it
+	/// plays instead of on a separate thread.
+	///
+	/// This makes the sound's output the same every time it's played, even
+	/// when audio is processed faster than real time, like when rendering
+	/// with a [`TrackRenderer`](crate::track::TrackRenderer), but decoding
+	/// adds to the time the audio thread takes to process audio. On `wasm32`,
+	/// sounds always decode audio on the audio thread.
+	pub decode_on_audio_thread: bool,
 }
 
 impl StreamingSoundSettings {
@@ -35,10 +161,24 @@ impl StreamingSoundSettings {
 			start_time: StartTime::Immediate,
 			start_position: PlaybackPosition::Seconds(0.0),
 			loop_region: None,
+			use_detected_loop_region: true,
+			loop_count: None,
 			volume: Value::Fixed(Decibels::IDENTITY),
 			playback_rate: Value::Fixed(PlaybackRate(1.0)),
 			panning: Value::Fixed(Panning::CENTER),
+			channel_mix_mode: ChannelMixMode::Stereo,
+			sends: SoundSends::new(),
 			fade_in_tween: None,
+			fade_out_tween: None,
+			group: None,
+			buffer_length: DEFAULT_BUFFER_LENGTH,
+			on_decode_error: DecodeErrorBehavior::Fail,
+			on_end: EndBehavior::Finish,
+			section_sequence: vec![],
+			loudness_normalization: None,
+			#[cfg(not(target_arch = "wasm32"))]
+			worker_pool: None,
+			decode_on_audio_thread: false,
 		}
 	}
 
@@ -69,6 +209,30 @@ impl StreamingSoundSettings {
 		}
 	}
 
+	/**
+	Sets whether to loop the loop points stored in the audio file when
+	no loop region is set.
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn use_detected_loop_region(self, use_detected_loop_region: bool) -> Self {
+		Self {
+			use_detected_loop_region,
+			..self
+		}
+	}
+
+	/**
+	Sets how many times the loop region should play before playback
+	continues to the end of the sound, or `None` to loop indefinitely.
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn loop_count(self, loop_count: impl Into<Option<u32>>) -> Self {
+		Self {
+			loop_count: loop_count.into(),
+			..self
+		}
+	}
+
 	/** Sets the volume of the sound. */
 	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
 	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
@@ -104,6 +268,36 @@ impl StreamingSoundSettings {
 		}
 	}
 
+	/// Sets how the left and right channels are combined before volume
+	/// and panning are applied.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn channel_mix_mode(self, channel_mix_mode: ChannelMixMode) -> Self {
+		Self {
+			channel_mix_mode,
+			..self
+		}
+	}
+
+	/**
+	Sends the sound's audio to a send track at the given volume, in
+	addition to the track it's playing on.
+
+	The sound's output is sent after its own volume and panning are
+	applied. If the send track is removed, the sound stops sending audio
+	to it. Sounds playing on the main track don't send audio to send
+	tracks.
+
+	# Panics
+
+	Panics if the sound already sends to [`MAX_SOUND_SENDS`](crate::sound::MAX_SOUND_SENDS)
+	other send tracks.
+	*/
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn send(mut self, to: impl Into<SendTrackId>, volume: impl Into<Value<Decibels>>) -> Self {
+		self.sends.set(to, volume);
+		self
+	}
+
 	/// Sets the tween used to fade in the instance from silence.
 	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
 	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
@@ -112,6 +306,91 @@ impl StreamingSoundSettings {
 			..self
 		}
 	}
+
+	/// Sets the tween used to fade out the instance to silence as playback
+	/// approaches the end of the sound.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn fade_out_tween(self, fade_out_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_out_tween: fade_out_tween.into(),
+			..self
+		}
+	}
+
+	/// Sets the [group](crate::group) the sound belongs to.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn group(self, group: impl Into<GroupId>) -> Self {
+		Self {
+			group: Some(group.into()),
+			..self
+		}
+	}
+
+	/// Sets how many frames of decoded audio can be kept ahead of the
+	/// playback position.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn buffer_length(self, buffer_length: usize) -> Self {
+		Self {
+			buffer_length,
+			..self
+		}
+	}
+
+	/// Sets what the sound should do when the decoder fails to decode a
+	/// chunk of audio.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn on_decode_error(self, on_decode_error: DecodeErrorBehavior) -> Self {
+		Self {
+			on_decode_error,
+			..self
+		}
+	}
+
+	/// Sets what the sound should do when playback reaches the end of
+	/// the audio.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn on_end(self, on_end: EndBehavior) -> Self {
+		Self { on_end, ..self }
+	}
+
+	/// Sets the portions of the sound that should play back-to-back.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn section_sequence(self, section_sequence: Vec<Region>) -> Self {
+		Self {
+			section_sequence,
+			..self
+		}
+	}
+
+	/// Sets the integrated loudness (in LUFS) the sound should be
+	/// normalized to.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn apply_loudness_normalization(self, target_lufs: f64) -> Self {
+		Self {
+			loudness_normalization: Some(target_lufs),
+			..self
+		}
+	}
+
+	/// Sets the worker pool that should decode audio for the sound.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn worker_pool(self, worker_pool: &StreamingWorkerPool) -> Self {
+		Self {
+			worker_pool: Some(worker_pool.clone()),
+			..self
+		}
+	}
+
+	/// Sets whether the sound should decode audio on the audio thread as
+	/// it plays instead of on a separate thread.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn decode_on_audio_thread(self, decode_on_audio_thread: bool) -> Self {
+		Self {
+			decode_on_audio_thread,
+			..self
+		}
+	}
 }
 
 impl Default for StreamingSoundSettings {
@@ -3,32 +3,85 @@ pub(crate) mod decode_scheduler;
 #[cfg(test)]
 mod test;
 
-use std::sync::{
-	atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
-	Arc,
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+use std::{
+	collections::VecDeque,
+	sync::{
+		atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+		Arc, Mutex,
+	},
 };
 
 use crate::{
 	command::read_commands_into_parameters,
 	frame::{interpolate_frame, Frame},
+	group::{GroupMember, GroupPlaybackCommand},
 	info::Info,
 	playback_state_manager::PlaybackStateManager,
-	sound::{PlaybackState, Sound},
-	Tween,
-	Decibels, Panning, Parameter, PlaybackRate, StartTime,
+	sound::{
+		rerouting::Rerouting, ChannelMixMode, FinishReason, Finished, PlaybackState, Region, Sound,
+		SoundSendLevels,
+	},
+	track::{SendTrackId, TrackId, TrackMember},
+	Decibels, Panning, Parameter, PlaybackRate, StartTime, Tween,
 };
 use rtrb::Consumer;
 
-use super::{CommandReaders, StreamingSoundSettings};
+use super::{CommandReaders, EndBehavior, StreamingMemoryUsage, StreamingSoundSettings};
 
-use self::decode_scheduler::DecodeScheduler;
+use self::decode_scheduler::{DecodeIncrementally, DecodeScheduler};
+
+/// The most frames that are decoded each time
+/// [`on_start_processing`](Sound::on_start_processing) is called
+/// when decoding on the audio thread.
+const MAX_FRAMES_DECODED_PER_BLOCK: usize = 4096;
+/// The section index of frames that aren't part of a section.
+const NO_SECTION: usize = usize::MAX;
+/// The longest time the interpolated position of a sound can advance
+/// past the last position the sound reported.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_POSITION_INTERPOLATION: Duration = Duration::from_millis(250);
 
 #[derive(Debug)]
 pub(crate) struct Shared {
 	state: AtomicU8,
 	position: AtomicU64,
+	/// When the shared state was created. Timestamps are stored as the
+	/// number of nanoseconds since then.
+	#[cfg(not(target_arch = "wasm32"))]
+	created_at: Instant,
+	/// When the position was last reported.
+	#[cfg(not(target_arch = "wasm32"))]
+	position_reported_at: AtomicU64,
+	/// Whether the position was advancing when it was last reported.
+	position_advancing: AtomicBool,
 	reached_end: AtomicBool,
 	encountered_error: AtomicBool,
+	remaining_loops: AtomicU64,
+	buffering: AtomicBool,
+	underrun_count: AtomicU64,
+	silent_frames_inserted: AtomicU64,
+	playback_rate: AtomicU64,
+	section_index: AtomicU64,
+	/// Sections that should play after the current section ends.
+	queued_sections: Mutex<VecDeque<Region>>,
+	/// Whether `queued_sections` changed since the decoder last checked it.
+	queued_sections_changed: AtomicBool,
+	/// Whether the decoder should stop even though the sound isn't
+	/// stopped, which happens when the sound is dropped.
+	decoding_stopped: AtomicBool,
+	/// Whether the decode scheduler has been dropped.
+	decoder_finished: AtomicBool,
+	/// Whether the sound's handle has been dropped.
+	handle_dropped: AtomicBool,
+	/// How much memory the decoded audio is using (in bytes).
+	buffer_memory_usage: AtomicU64,
+	/// How much memory the decoder's own buffers are using (in bytes).
+	decoder_memory_usage: AtomicU64,
+	/// How much memory the decoder's seek index is using (in bytes).
+	seek_index_memory_usage: AtomicU64,
+	finished: Arc<Finished>,
 }
 
 impl Shared {
@@ -36,9 +89,29 @@ impl Shared {
 	pub fn new() -> Self {
 		Self {
 			position: AtomicU64::new(0.0f64.to_bits()),
+			#[cfg(not(target_arch = "wasm32"))]
+			created_at: Instant::now(),
+			#[cfg(not(target_arch = "wasm32"))]
+			position_reported_at: AtomicU64::new(0),
+			position_advancing: AtomicBool::new(false),
 			state: AtomicU8::new(PlaybackState::Playing as u8),
 			reached_end: AtomicBool::new(false),
 			encountered_error: AtomicBool::new(false),
+			remaining_loops: AtomicU64::new(u64::MAX),
+			buffering: AtomicBool::new(false),
+			underrun_count: AtomicU64::new(0),
+			silent_frames_inserted: AtomicU64::new(0),
+			playback_rate: AtomicU64::new(1.0f64.to_bits()),
+			section_index: AtomicU64::new(u64::MAX),
+			queued_sections: Mutex::new(VecDeque::new()),
+			queued_sections_changed: AtomicBool::new(false),
+			decoding_stopped: AtomicBool::new(false),
+			decoder_finished: AtomicBool::new(false),
+			handle_dropped: AtomicBool::new(false),
+			buffer_memory_usage: AtomicU64::new(0),
+			decoder_memory_usage: AtomicU64::new(0),
+			seek_index_memory_usage: AtomicU64::new(0),
+			finished: Arc::new(Finished::new()),
 		}
 	}
 
@@ -60,11 +133,95 @@ impl Shared {
 		self.state.store(state as u8, Ordering::SeqCst);
 	}
 
+	/// Returns `true` if the decoder should stop, either because the sound
+	/// stopped or because it was dropped.
+	#[must_use]
+	pub fn decoding_stopped(&self) -> bool {
+		self.decoding_stopped.load(Ordering::SeqCst) || self.state() == PlaybackState::Stopped
+	}
+
+	pub fn stop_decoding(&self) {
+		self.decoding_stopped.store(true, Ordering::SeqCst);
+	}
+
+	#[cfg(test)]
+	#[must_use]
+	pub fn decoder_finished(&self) -> bool {
+		self.decoder_finished.load(Ordering::SeqCst)
+	}
+
+	pub fn mark_decoder_finished(&self) {
+		self.decoder_finished.store(true, Ordering::SeqCst);
+	}
+
 	#[must_use]
 	pub fn position(&self) -> f64 {
 		f64::from_bits(self.position.load(Ordering::SeqCst))
 	}
 
+	fn report_position(&self, position: f64, advancing: bool) {
+		self.position.store(position.to_bits(), Ordering::SeqCst);
+		self.position_advancing.store(advancing, Ordering::SeqCst);
+		#[cfg(not(target_arch = "wasm32"))]
+		self.position_reported_at.store(
+			self.created_at.elapsed().as_nanos() as u64,
+			Ordering::SeqCst,
+		);
+	}
+
+	/// Returns the last reported position, moved forward by the time
+	/// that's passed since it was reported.
+	///
+	/// On the web, [`Instant`] isn't available, so this returns the last
+	/// reported position.
+	#[must_use]
+	pub fn interpolated_position(&self) -> f64 {
+		let position = self.position();
+		if !self.position_advancing.load(Ordering::SeqCst) || !self.state().is_advancing() {
+			return position;
+		}
+		#[cfg(not(target_arch = "wasm32"))]
+		{
+			let reported_at =
+				Duration::from_nanos(self.position_reported_at.load(Ordering::SeqCst));
+			let elapsed = self
+				.created_at
+				.elapsed()
+				.saturating_sub(reported_at)
+				.min(MAX_POSITION_INTERPOLATION);
+			position + elapsed.as_secs_f64() * self.playback_rate().max(0.0)
+		}
+		#[cfg(target_arch = "wasm32")]
+		position
+	}
+
+	#[must_use]
+	pub fn handle_dropped(&self) -> bool {
+		self.handle_dropped.load(Ordering::SeqCst)
+	}
+
+	pub fn mark_handle_dropped(&self) {
+		self.handle_dropped.store(true, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn memory_usage(&self) -> StreamingMemoryUsage {
+		StreamingMemoryUsage {
+			buffer: self.buffer_memory_usage.load(Ordering::SeqCst) as usize,
+			decoder: self.decoder_memory_usage.load(Ordering::SeqCst) as usize,
+			seek_index: self.seek_index_memory_usage.load(Ordering::SeqCst) as usize,
+		}
+	}
+
+	pub fn set_memory_usage(&self, memory_usage: StreamingMemoryUsage) {
+		self.buffer_memory_usage
+			.store(memory_usage.buffer as u64, Ordering::SeqCst);
+		self.decoder_memory_usage
+			.store(memory_usage.decoder as u64, Ordering::SeqCst);
+		self.seek_index_memory_usage
+			.store(memory_usage.seek_index as u64, Ordering::SeqCst);
+	}
+
 	#[must_use]
 	pub fn reached_end(&self) -> bool {
 		self.reached_end.load(Ordering::SeqCst)
@@ -74,6 +231,130 @@ impl Shared {
 	pub fn encountered_error(&self) -> bool {
 		self.encountered_error.load(Ordering::SeqCst)
 	}
+
+	#[must_use]
+	pub fn finished(&self) -> Arc<Finished> {
+		self.finished.clone()
+	}
+
+	#[must_use]
+	pub fn remaining_loops(&self) -> Option<u32> {
+		match self.remaining_loops.load(Ordering::SeqCst) {
+			u64::MAX => None,
+			remaining_loops => Some(remaining_loops as u32),
+		}
+	}
+
+	/// Stores the number of remaining loops, using `u64::MAX` to represent
+	/// looping indefinitely.
+	pub fn set_remaining_loops(&self, remaining_loops: Option<u32>) {
+		self.remaining_loops.store(
+			remaining_loops.map_or(u64::MAX, u64::from),
+			Ordering::SeqCst,
+		);
+	}
+
+	/// Returns the most recent playback rate of the sound as a factor.
+	#[must_use]
+	pub fn playback_rate(&self) -> f64 {
+		f64::from_bits(self.playback_rate.load(Ordering::SeqCst))
+	}
+
+	#[must_use]
+	pub fn is_buffering(&self) -> bool {
+		self.buffering.load(Ordering::SeqCst)
+	}
+
+	pub fn set_buffering(&self, buffering: bool) {
+		self.buffering.store(buffering, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn underrun_count(&self) -> u64 {
+		self.underrun_count.load(Ordering::SeqCst)
+	}
+
+	#[must_use]
+	pub fn silent_frames_inserted(&self) -> u64 {
+		self.silent_frames_inserted.load(Ordering::SeqCst)
+	}
+
+	/// Returns the index of the section that's playing, or `None` if the
+	/// sound isn't playing a section.
+	#[must_use]
+	pub fn section_index(&self) -> Option<usize> {
+		match self.section_index.load(Ordering::SeqCst) {
+			u64::MAX => None,
+			section_index => Some(section_index as usize),
+		}
+	}
+
+	fn set_section_index(&self, section_index: usize) {
+		let section_index = if section_index == NO_SECTION {
+			u64::MAX
+		} else {
+			section_index as u64
+		};
+		self.section_index.store(section_index, Ordering::SeqCst);
+	}
+
+	/// Adds sections to the end of the queue.
+	pub fn queue_sections(&self, sections: impl IntoIterator<Item = Region>) {
+		self.queued_sections
+			.lock()
+			.expect("queued sections mutex poisoned")
+			.extend(sections);
+		self.queued_sections_changed.store(true, Ordering::SeqCst);
+	}
+
+	pub fn clear_queued_sections(&self) {
+		self.queued_sections
+			.lock()
+			.expect("queued sections mutex poisoned")
+			.clear();
+		self.queued_sections_changed.store(true, Ordering::SeqCst);
+	}
+
+	/// Removes and returns the first queued section that `f` returns
+	/// `Some` for, discarding the sections before it.
+	pub fn pop_queued_section<T>(&self, f: impl Fn(Region) -> Option<T>) -> Option<T> {
+		let mut queued_sections = self
+			.queued_sections
+			.lock()
+			.expect("queued sections mutex poisoned");
+		std::iter::from_fn(|| queued_sections.pop_front()).find_map(f)
+	}
+
+	/// Returns the first queued section that `f` returns `Some` for
+	/// without removing it.
+	#[must_use]
+	pub fn peek_queued_section<T>(&self, f: impl Fn(Region) -> Option<T>) -> Option<T> {
+		self.queued_sections
+			.lock()
+			.expect("queued sections mutex poisoned")
+			.iter()
+			.copied()
+			.find_map(f)
+	}
+
+	/// Returns whether the queued sections changed since the last time
+	/// this was called.
+	#[must_use]
+	pub fn take_queued_sections_changed(&self) -> bool {
+		self.queued_sections_changed.swap(false, Ordering::SeqCst)
+	}
+
+	/// Records that the sound ran out of decoded audio.
+	fn record_underrun(&self) {
+		self.underrun_count.fetch_add(1, Ordering::SeqCst);
+	}
+
+	/// Records that the sound output `num_frames` frames of silence
+	/// because the decoder couldn't keep up.
+	fn add_silent_frames(&self, num_frames: usize) {
+		self.silent_frames_inserted
+			.fetch_add(num_frames as u64, Ordering::SeqCst);
+	}
 }
 
 pub(crate) struct StreamingSound {
@@ -85,20 +366,41 @@ pub(crate) struct StreamingSound {
 	current_frame: usize,
 	fractional_position: f64,
 	volume: Parameter<Decibels>,
+	/// The amplitude that normalizes the loudness of the sound.
+	loudness_gain: f32,
 	playback_rate: Parameter<PlaybackRate>,
 	panning: Parameter<Panning>,
+	/// The volumes of the sound's sends to send tracks.
+	send_levels: SoundSendLevels,
+	channel_mix_mode: ChannelMixMode,
+	rerouting: Rerouting,
+	group: Option<GroupMember>,
+	track_member: TrackMember,
+	finish_reason: FinishReason,
+	end_behavior: EndBehavior,
 	shared: Arc<Shared>,
+	/// Decodes audio when the sound is processed, if the audio isn't
+	/// being decoded on a separate thread.
+	decode_scheduler: Option<Box<dyn DecodeIncrementally>>,
+	/// Whether the sound has output any decoded audio. Waiting for audio
+	/// before this isn't counted as an underrun.
+	started_output: bool,
+	/// Whether the sound is waiting for the decoder to catch up.
+	buffering: bool,
 }
 
 impl StreamingSound {
 	#[must_use]
+	#[allow(clippy::too_many_arguments)]
 	pub(super) fn new<Error: Send + 'static>(
 		sample_rate: u32,
 		settings: StreamingSoundSettings,
 		shared: Arc<Shared>,
 		frame_consumer: Consumer<TimestampedFrame>,
 		command_readers: CommandReaders,
+		send_levels: SoundSendLevels,
 		scheduler: &DecodeScheduler<Error>,
+		loudness_gain: Decibels,
 	) -> Self {
 		let current_frame = scheduler.current_frame();
 		let start_position = current_frame as f64 / sample_rate as f64;
@@ -114,15 +416,46 @@ impl StreamingSound {
 			current_frame,
 			fractional_position: 0.0,
 			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			loudness_gain: loudness_gain.as_amplitude(),
 			playback_rate: Parameter::new(settings.playback_rate, PlaybackRate(1.0)),
 			panning: Parameter::new(settings.panning, Panning::CENTER),
+			send_levels,
+			channel_mix_mode: settings.channel_mix_mode,
+			rerouting: Rerouting::new(),
+			group: settings.group.map(GroupMember::new),
+			track_member: TrackMember::default(),
+			finish_reason: FinishReason::ReachedEnd,
+			end_behavior: settings.on_end,
 			shared,
+			decode_scheduler: None,
+			started_output: false,
+			buffering: false,
 		}
 	}
 
+	/// Makes the sound decode audio itself when it's processed instead of
+	/// relying on a separate decoding thread.
+	pub(super) fn decode_on_audio_thread<Error: Send + 'static>(
+		&mut self,
+		scheduler: DecodeScheduler<Error>,
+	) {
+		let mut scheduler = Box::new(scheduler);
+		// decode some audio up front so playback can start right away
+		scheduler.decode(MAX_FRAMES_DECODED_PER_BLOCK);
+		self.decode_scheduler = Some(scheduler);
+	}
+
 	fn update_shared_playback_state(&mut self) {
-		self.shared
-			.set_state(self.playback_state_manager.playback_state());
+		let state = self.playback_state_manager.playback_state();
+		self.shared.set_state(state);
+		if state == PlaybackState::Stopped {
+			let finish_reason = if self.playback_state_manager.stopped_early() {
+				FinishReason::Stopped
+			} else {
+				self.finish_reason
+			};
+			self.shared.finished.notify(finish_reason);
+		}
 	}
 
 	fn update_current_frame(&mut self) {
@@ -132,8 +465,14 @@ impl StreamingSound {
 			.unwrap();
 		let (a, b) = chunk.as_slices();
 		let mut iter = a.iter().chain(b.iter());
-		if let Some(TimestampedFrame { index, .. }) = iter.nth(1) {
+		if let Some(TimestampedFrame {
+			index,
+			section_index,
+			..
+		}) = iter.nth(1)
+		{
 			self.current_frame = *index;
+			self.shared.set_section_index(*section_index);
 		}
 	}
 
@@ -156,13 +495,36 @@ impl StreamingSound {
 		frames
 	}
 
+	/// Removes frames the playback position has moved past from the
+	/// frame ringbuffer.
+	///
+	/// At high playback rates, the position can move past every frame
+	/// that's been decoded. In that case, the most recent frame is kept
+	/// until the decoder catches up.
+	fn pop_played_frames(&mut self) {
+		while self.fractional_position >= 1.0
+			&& (self.frame_consumer.slots() >= 2 || self.shared.reached_end())
+		{
+			self.fractional_position -= 1.0;
+			self.frame_consumer.pop().ok();
+		}
+	}
+
+	/// Returns `true` if the sound has played all of the decoded audio and
+	/// the decoder reached the end.
+	#[must_use]
+	fn played_to_end(&self) -> bool {
+		self.shared.reached_end() && self.frame_consumer.is_empty()
+	}
+
 	#[must_use]
 	fn position(&self) -> f64 {
 		(self.current_frame as f64 + self.fractional_position) / self.sample_rate as f64
 	}
 
-	fn pause(&mut self, fade_out_tween: Tween) {
-		self.playback_state_manager.pause(fade_out_tween);
+	fn pause(&mut self, start_time: StartTime, fade_out_tween: Tween) {
+		self.playback_state_manager
+			.pause_at(start_time, fade_out_tween);
 		self.update_shared_playback_state();
 	}
 
@@ -172,46 +534,102 @@ impl StreamingSound {
 		self.update_shared_playback_state();
 	}
 
-	fn stop(&mut self, fade_out_tween: Tween) {
-		self.playback_state_manager.stop(fade_out_tween);
+	fn stop(&mut self, start_time: StartTime, fade_out_tween: Tween) {
+		self.playback_state_manager
+			.stop_at(start_time, fade_out_tween);
 		self.update_shared_playback_state();
 	}
 
+	fn apply_group_command(&mut self, command: GroupPlaybackCommand) {
+		match command {
+			GroupPlaybackCommand::Pause(tween) => self.pause(StartTime::Immediate, tween),
+			GroupPlaybackCommand::Resume(tween) => self.resume(StartTime::Immediate, tween),
+			GroupPlaybackCommand::Stop(tween) => self.stop(StartTime::Immediate, tween),
+		}
+	}
+
 	fn read_commands(&mut self) {
 		read_commands_into_parameters!(self, volume, playback_rate, panning);
-		if let Some(tween) = self.command_readers.pause.read() {
-			self.pause(tween);
+		self.send_levels.read_commands();
+		if let Some((destination, tween)) = self.command_readers.set_output_destination.read() {
+			self.rerouting.start(destination, tween);
+		}
+		if let Some((start_time, tween)) = self.command_readers.pause.read() {
+			self.pause(start_time, tween);
 		}
 		if let Some((start_time, tween)) = self.command_readers.resume.read() {
 			self.resume(start_time, tween);
 		}
-		if let Some(tween) = self.command_readers.stop.read() {
-			self.stop(tween);
+		if let Some((start_time, tween)) = self.command_readers.stop.read() {
+			self.stop(start_time, tween);
 		}
 	}
 }
 
 impl Sound for StreamingSound {
 	fn on_start_processing(&mut self) {
+		if let Some(scheduler) = &mut self.decode_scheduler {
+			scheduler.decode(MAX_FRAMES_DECODED_PER_BLOCK);
+		}
 		self.update_current_frame();
-		self.shared
-			.position
-			.store(self.position().to_bits(), Ordering::SeqCst);
+		let advancing = self.start_time == StartTime::Immediate
+			&& self.playback_state_manager.playback_state().is_advancing()
+			&& !self.buffering;
+		self.shared.report_position(self.position(), advancing);
 		self.read_commands();
+		// once the handle is dropped, a sound that paused at the end can't
+		// be played again
+		if self.shared.handle_dropped()
+			&& self.played_to_end()
+			&& self.playback_state_manager.playback_state() == PlaybackState::Paused
+		{
+			self.playback_state_manager.mark_as_stopped();
+			self.update_shared_playback_state();
+		}
+	}
+
+	fn memory_usage(&self) -> usize {
+		self.shared.memory_usage().total()
 	}
 
 	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
 		if self.shared.encountered_error() {
+			self.finish_reason = FinishReason::EncounteredError;
 			self.playback_state_manager.mark_as_stopped();
 			self.update_shared_playback_state();
 			out.fill(Frame::ZERO);
 			return;
 		}
+		// a start time measured in frames can be reached partway through
+		// the chunk, so process the frames before and after it separately
+		if let Some(split_index) = self.start_time.frames_into_chunk(out.len()) {
+			let (before, after) = out.split_at_mut(split_index);
+			self.process(before, dt, info);
+			self.process(after, dt, info);
+			return;
+		}
 
 		// update parameters
 		self.volume.update(dt * out.len() as f64, info);
 		self.playback_rate.update(dt * out.len() as f64, info);
+		let track_playback_rate = info.track_playback_rate();
+		self.shared.playback_rate.store(
+			(self.playback_rate.value().0 * track_playback_rate).to_bits(),
+			Ordering::SeqCst,
+		);
 		self.panning.update(dt * out.len() as f64, info);
+		self.send_levels.update(dt * out.len() as f64, info);
+		self.rerouting.update(dt * out.len() as f64, info);
+		if let Some(command) = self
+			.group
+			.as_mut()
+			.and_then(|group| group.update(dt * out.len() as f64, info))
+		{
+			self.apply_group_command(command);
+		}
+		if let Some(command) = self.track_member.update(info) {
+			self.apply_group_command(command);
+		}
 		let changed_playback_state = self
 			.playback_state_manager
 			.update(dt * out.len() as f64, info);
@@ -221,6 +639,7 @@ impl Sound for StreamingSound {
 
 		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
 		if will_never_start {
+			self.finish_reason = FinishReason::Stopped;
 			self.playback_state_manager.mark_as_stopped();
 			self.update_shared_playback_state();
 		}
@@ -233,52 +652,109 @@ impl Sound for StreamingSound {
 			out.fill(Frame::ZERO);
 			return;
 		}
-		// pause playback while waiting for audio data. the first frame
-		// in the ringbuffer is the previous frame, so we need to make
-		// sure there's at least 2 before we continue playing.
-		if self.frame_consumer.slots() < 2 && !self.shared.reached_end() {
-			out.fill(Frame::ZERO);
-			return;
-		}
-
 		let num_frames = out.len();
+		let mut silent_frames_inserted = 0;
 		for (i, frame) in out.iter_mut().enumerate() {
+			// the sound may have paused at the end partway through the chunk
+			if !self.playback_state_manager.playback_state().is_advancing() {
+				*frame = Frame::ZERO;
+				continue;
+			}
+			// if the decoder fell behind while the sound was advancing,
+			// catch up now that more audio might be available
+			self.pop_played_frames();
+			// pause playback while waiting for audio data. the first frame
+			// in the ringbuffer is the previous frame, so we need to make
+			// sure there's at least 2 before we continue playing.
+			let starved = self.frame_consumer.slots() < 2 && !self.shared.reached_end();
+			if starved != self.buffering {
+				self.buffering = starved;
+				self.shared.set_buffering(starved);
+				if starved && self.started_output {
+					self.shared.record_underrun();
+				}
+			}
+			if starved {
+				if self.started_output {
+					silent_frames_inserted += 1;
+				}
+				*frame = Frame::ZERO;
+				continue;
+			}
+			self.started_output = true;
 			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
-			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
-			let fade_volume = self
-				.playback_state_manager
-				.interpolated_fade_volume(time_in_chunk)
-				.as_amplitude();
+			let volume =
+				self.volume.interpolated_value(time_in_chunk).as_amplitude() * self.loudness_gain;
+			let fade_volume =
+				self.playback_state_manager
+					.interpolated_fade_volume(time_in_chunk)
+					.as_amplitude() * self.group.as_ref().map_or(1.0, |group| {
+					group.interpolated_volume(time_in_chunk).as_amplitude()
+				});
 			let panning = self.panning.interpolated_value(time_in_chunk);
-			let playback_rate = self.playback_rate.interpolated_value(time_in_chunk);
+			let playback_rate =
+				self.playback_rate.interpolated_value(time_in_chunk).0 * track_playback_rate;
 			let next_frames = self.next_frames();
-			let interpolated_out = interpolate_frame(
+			let interpolated_out = self.channel_mix_mode.apply(interpolate_frame(
 				next_frames[0],
 				next_frames[1],
 				next_frames[2],
 				next_frames[3],
 				self.fractional_position as f32,
-			);
-			self.fractional_position += self.sample_rate as f64 * playback_rate.0.max(0.0) * dt;
-			while self.fractional_position >= 1.0 {
-				self.fractional_position -= 1.0;
-				self.frame_consumer.pop().ok();
-			}
-			if self.shared.reached_end() && self.frame_consumer.is_empty() {
-				self.playback_state_manager.mark_as_stopped();
+			));
+			self.fractional_position += self.sample_rate as f64 * playback_rate.max(0.0) * dt;
+			self.pop_played_frames();
+			if self.played_to_end() {
+				if self.end_behavior == EndBehavior::Pause && !self.shared.handle_dropped() {
+					self.playback_state_manager.mark_as_paused();
+				} else {
+					self.playback_state_manager.mark_as_stopped();
+				}
 				self.update_shared_playback_state();
 			}
 			*frame = (interpolated_out * fade_volume * volume).panned(panning);
 		}
+		if silent_frames_inserted > 0 {
+			self.shared.add_silent_frames(silent_frames_inserted);
+		}
 	}
 
 	fn finished(&self) -> bool {
 		self.playback_state_manager.playback_state() == PlaybackState::Stopped
 	}
+
+	fn playback_state(&self) -> PlaybackState {
+		self.playback_state_manager.playback_state()
+	}
+
+	fn take_requested_track(&mut self) -> Option<(TrackId, Tween)> {
+		self.rerouting.take_requested_track()
+	}
+
+	fn on_move_finished(&mut self, moved: bool) {
+		if moved {
+			self.track_member.rejoin();
+		}
+	}
+
+	fn sends(&self) -> &[(SendTrackId, Parameter<Decibels>)] {
+		self.send_levels.levels()
+	}
+}
+
+impl Drop for StreamingSound {
+	fn drop(&mut self) {
+		// sounds are dropped when they finish or when the audio manager
+		// shuts down. either way, the decoder doesn't need to keep going
+		self.shared.stop_decoding();
+		self.decode_scheduler = None;
+	}
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct TimestampedFrame {
 	frame: Frame,
 	index: usize,
+	/// The index of the section the frame is part of, or [`NO_SECTION`].
+	section_index: usize,
 }
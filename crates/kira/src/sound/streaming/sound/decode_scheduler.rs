@@ -1,22 +1,96 @@
 use std::{
+	mem::size_of,
 	sync::{atomic::Ordering, Arc},
-	time::Duration,
 };
 
 use crate::{
 	frame::Frame,
 	sound::{
-		streaming::{decoder::Decoder, DecodeSchedulerCommandReaders, StreamingSoundSettings},
-		transport::Transport,
-		PlaybackState,
+		streaming::{
+			decoder::Decoder, DecodeErrorBehavior, DecodeSchedulerCommandReaders, EndBehavior,
+			StreamingMemoryUsage, StreamingSoundSettings,
+		},
+		transport::{FadeOut, Transport},
+		Region,
 	},
 };
 use rtrb::{Consumer, Producer, RingBuffer};
 
-use super::{Shared, TimestampedFrame};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sound::streaming::prepared::Preparation;
 
-const BUFFER_SIZE: usize = 16_384;
-const DECODER_THREAD_SLEEP_DURATION: Duration = Duration::from_millis(1);
+use super::{Shared, TimestampedFrame, NO_SECTION};
+
+/// The smallest number of frames that can be kept in the frame
+/// ringbuffer. The sound reads up to 4 frames at a time.
+const MIN_BUFFER_LENGTH: usize = 4;
+/// The highest playback rate the amount of audio decoded ahead of time
+/// is scaled up for.
+const MAX_BUFFERED_PLAYBACK_RATE: f64 = 3.0;
+/// How many frames at the start of the loop region and the next section
+/// are kept decoded so playback can jump there without waiting for the
+/// decoder to seek.
+const HEAD_LENGTH: usize = 8192;
+/// How many times in a row the decoder can fail before a sound that skips
+/// decode errors gives up and ends playback.
+const MAX_CONSECUTIVE_DECODE_ERRORS: usize = 16;
+#[cfg(not(target_arch = "wasm32"))]
+const DECODER_THREAD_SLEEP_DURATION: std::time::Duration = std::time::Duration::from_millis(1);
+/// How long the audio manager waits for decoding threads to stop
+/// when it shuts down.
+#[cfg(not(target_arch = "wasm32"))]
+const DECODER_SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(100);
+/// How often the audio manager checks whether decoding threads have
+/// stopped when it shuts down.
+#[cfg(not(target_arch = "wasm32"))]
+const DECODER_SHUTDOWN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_micros(100);
+
+/// The number of decoding threads that haven't exited yet.
+#[cfg(not(target_arch = "wasm32"))]
+static RUNNING_DECODER_THREADS: std::sync::atomic::AtomicUsize =
+	std::sync::atomic::AtomicUsize::new(0);
+
+/// Keeps [`RUNNING_DECODER_THREADS`] up to date for the lifetime of
+/// a decoding thread.
+#[cfg(not(target_arch = "wasm32"))]
+struct RunningDecoderThread;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RunningDecoderThread {
+	fn new() -> Self {
+		RUNNING_DECODER_THREADS.fetch_add(1, Ordering::SeqCst);
+		Self
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for RunningDecoderThread {
+	fn drop(&mut self) {
+		RUNNING_DECODER_THREADS.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+/// Gives decoding threads a moment to exit when it's dropped.
+///
+/// Dropping a streaming sound only tells its decoder to stop, so the
+/// audio manager holds onto one of these to avoid leaving decoding
+/// threads running after it shuts down.
+#[derive(Debug, Default)]
+pub(crate) struct DecoderThreadShutdown;
+
+impl Drop for DecoderThreadShutdown {
+	fn drop(&mut self) {
+		#[cfg(not(target_arch = "wasm32"))]
+		{
+			let start = std::time::Instant::now();
+			while RUNNING_DECODER_THREADS.load(Ordering::SeqCst) > 0
+				&& start.elapsed() < DECODER_SHUTDOWN_TIMEOUT
+			{
+				std::thread::sleep(DECODER_SHUTDOWN_POLL_INTERVAL);
+			}
+		}
+	}
+}
 
 pub(crate) enum NextStep {
 	Continue,
@@ -30,30 +104,52 @@ pub(crate) struct DecodeScheduler<Error: Send + 'static> {
 	slice: Option<(usize, usize)>,
 	num_frames: usize,
 	transport: Transport,
+	fade_out: Option<FadeOut>,
 	decoder_current_frame_index: usize,
 	decoded_chunk: Option<DecodedChunk>,
+	/// The first frames of the loop region, if there is one.
+	loop_head: DecodedChunk,
+	/// The start and end frames of the section that's playing, if any.
+	current_section: Option<(usize, usize)>,
+	/// How many sections have started playing, including the current one.
+	num_sections_started: usize,
+	/// The first frames of the next queued section, if there is one.
+	section_head: DecodedChunk,
 	command_readers: DecodeSchedulerCommandReaders,
 	frame_producer: Producer<TimestampedFrame>,
+	/// How many frames to keep decoded ahead of the playback position
+	/// when the sound is playing at normal speed.
+	buffer_length: usize,
 	error_producer: Producer<Error>,
+	decode_error_behavior: DecodeErrorBehavior,
+	consecutive_decode_errors: usize,
+	end_behavior: EndBehavior,
 	shared: Arc<Shared>,
+	/// Whether there's nothing left to decode. Only used when decoding
+	/// incrementally.
+	ended: bool,
 }
 
 impl<Error: Send + 'static> DecodeScheduler<Error> {
 	pub(crate) fn new(
 		mut decoder: Box<dyn Decoder<Error = Error>>,
 		slice: Option<(usize, usize)>,
-		settings: StreamingSoundSettings,
+		settings: &StreamingSoundSettings,
 		shared: Arc<Shared>,
 		command_readers: DecodeSchedulerCommandReaders,
 		error_producer: Producer<Error>,
 	) -> Result<(Self, Consumer<TimestampedFrame>), Error> {
-		let (mut frame_producer, frame_consumer) = RingBuffer::new(BUFFER_SIZE);
+		let buffer_length = settings.buffer_length.max(MIN_BUFFER_LENGTH);
+		// leave room for more frames when the sound is sped up
+		let (mut frame_producer, frame_consumer) =
+			RingBuffer::new((buffer_length as f64 * MAX_BUFFERED_PLAYBACK_RATE).ceil() as usize);
 		// pre-seed the frame ringbuffer with a zero frame. this is the "previous" frame
 		// when the sound just started.
 		frame_producer
 			.push(TimestampedFrame {
 				frame: Frame::ZERO,
 				index: 0,
+				section_index: NO_SECTION,
 			})
 			.expect("The frame producer shouldn't be full because we just created it");
 		let sample_rate = decoder.sample_rate();
@@ -64,25 +160,55 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 		};
 		let start_position = settings.start_position.into_samples(sample_rate);
 		let decoder_current_frame_index = decoder.seek(start_position)?;
-		let scheduler = Self {
+		// the loop points stored in the file are used if no loop region
+		// is set
+		let loop_region = match settings.loop_region {
+			None if settings.use_detected_loop_region => {
+				decoder.metadata().and_then(|metadata| metadata.loop_region)
+			}
+			loop_region => loop_region,
+		};
+		let mut transport =
+			Transport::new(start_position, loop_region, false, sample_rate, num_frames);
+		transport.set_loop_count(settings.loop_count);
+		shared.set_remaining_loops(transport.remaining_loops);
+		let mut scheduler = Self {
 			decoder,
 			sample_rate,
 			slice,
 			num_frames,
-			transport: Transport::new(
-				start_position,
-				settings.loop_region,
-				false,
-				sample_rate,
-				num_frames,
-			),
+			transport,
+			fade_out: settings
+				.fade_out_tween
+				.map(|tween| FadeOut::new(tween, sample_rate)),
 			decoder_current_frame_index,
 			decoded_chunk: None,
+			loop_head: DecodedChunk {
+				start_index: 0,
+				frames: Vec::with_capacity(HEAD_LENGTH),
+			},
+			current_section: None,
+			num_sections_started: 0,
+			section_head: DecodedChunk {
+				start_index: 0,
+				frames: Vec::with_capacity(HEAD_LENGTH),
+			},
 			command_readers,
 			frame_producer,
+			buffer_length,
 			error_producer,
+			decode_error_behavior: settings.on_decode_error,
+			consecutive_decode_errors: 0,
+			end_behavior: settings.on_end,
 			shared,
+			ended: false,
 		};
+		scheduler
+			.shared
+			.queue_sections(settings.section_sequence.iter().copied());
+		// playback starts at the beginning of the first section
+		scheduler.start_next_section()?;
+		scheduler.fill_loop_head()?;
 		Ok((scheduler, frame_consumer))
 	}
 
@@ -91,70 +217,287 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 		self.transport.position
 	}
 
-	pub fn start(mut self) {
-		std::thread::spawn(move || loop {
+	/// Decodes audio on a new thread until the sound finishes.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn start(self) {
+		let running = RunningDecoderThread::new();
+		std::thread::spawn(move || {
+			let _running = running;
+			self.decode_on_thread(None)
+		});
+	}
+
+	/// Decodes audio on a new thread before the sound is played.
+	///
+	/// If the sound still hasn't been played once the preparation expires,
+	/// the thread exits and returns the scheduler so decoding can be resumed
+	/// later.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn start_prepared(
+		self,
+		preparation: Arc<Preparation>,
+	) -> std::thread::JoinHandle<Option<Self>> {
+		let running = RunningDecoderThread::new();
+		std::thread::spawn(move || {
+			let _running = running;
+			self.decode_on_thread(Some(&preparation))
+		})
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	fn decode_on_thread(mut self, preparation: Option<&Preparation>) -> Option<Self> {
+		loop {
 			match self.run() {
 				Ok(result) => match result {
 					NextStep::Continue => {}
-					NextStep::Wait => std::thread::sleep(DECODER_THREAD_SLEEP_DURATION),
-					NextStep::End => break,
+					NextStep::Wait => {
+						if preparation.is_some_and(Preparation::expire) {
+							return Some(self);
+						}
+						std::thread::sleep(DECODER_THREAD_SLEEP_DURATION);
+					}
+					NextStep::End => return None,
 				},
-				Err(error) => {
-					self.error_producer.push(error).ok();
-					self.shared.encountered_error.store(true, Ordering::SeqCst);
-				}
+				Err(error) => self.report_error(error),
 			}
-		});
+		}
+	}
+
+	fn report_error(&mut self, error: Error) {
+		self.error_producer.push(error).ok();
+		self.shared.encountered_error.store(true, Ordering::SeqCst);
 	}
 
 	pub fn run(&mut self) -> Result<NextStep, Error> {
-		// if the sound was manually stopped, end the thread
-		if self.shared.state() == PlaybackState::Stopped {
+		// if the sound was stopped or dropped, end the thread
+		if self.shared.decoding_stopped() {
 			return Ok(NextStep::End);
 		}
-		// if the frame ringbuffer is full, sleep for a bit
-		if self.frame_producer.is_full() {
+		// if enough audio is decoded ahead of time, sleep for a bit
+		if self.num_buffered_frames() >= self.target_buffer_length() {
 			return Ok(NextStep::Wait);
 		}
 		// check for commands
 		if let Some(loop_region) = self.command_readers.set_loop_region.read() {
 			self.transport
 				.set_loop_region(loop_region, self.sample_rate, self.num_frames);
+			self.fill_loop_head()?;
 		}
+		if let Some(loop_count) = self.command_readers.set_loop_count.read() {
+			self.transport.set_loop_count(loop_count);
+			self.shared
+				.set_remaining_loops(self.transport.remaining_loops);
+		}
+		// if the decoder can't seek, the sound keeps playing from where
+		// it was
 		if let Some(amount) = self.command_readers.seek_by.read() {
-			self.seek_by(amount)?;
+			if let Err(error) = self.seek_by(amount) {
+				self.error_producer.push(error).ok();
+			}
 		}
 		if let Some(position) = self.command_readers.seek_to.read() {
-			self.seek_to(position)?;
+			if let Err(error) = self.seek_to(position) {
+				self.error_producer.push(error).ok();
+			}
+		}
+		if self.shared.take_queued_sections_changed() {
+			self.fill_section_head()?;
+		}
+		// sounds that pause at the end wait to be seeked back into the audio
+		if !self.transport.playing && self.end_behavior == EndBehavior::Pause {
+			return Ok(NextStep::Wait);
 		}
-		let frame = self.frame_at_index(self.transport.position)?;
+		// if the decoder doesn't have any audio ready yet, try again later
+		let Some(frame) = self.frame_at_index(self.transport.position)? else {
+			return Ok(NextStep::Wait);
+		};
+		let frame = frame * self.fade_out_volume();
 		self.frame_producer
 			.push(TimestampedFrame {
 				frame,
 				index: self.transport.position,
+				section_index: if self.current_section.is_some() {
+					self.num_sections_started - 1
+				} else {
+					NO_SECTION
+				},
 			})
 			.expect("could not push frame to frame producer");
-		self.transport.increment_position(self.num_frames);
+		self.advance()?;
 		if !self.transport.playing {
-			self.shared.reached_end.store(true, Ordering::SeqCst);
-			return Ok(NextStep::End);
+			return Ok(self.reach_end());
 		}
 		Ok(NextStep::Continue)
 	}
 
-	fn frame_at_index(&mut self, index: usize) -> Result<Frame, Error> {
-		let start = self.slice.map(|(start, _)| start).unwrap_or(0);
+	/// Handles playback reaching the end of the audio.
+	fn reach_end(&mut self) -> NextStep {
+		match self.end_behavior {
+			EndBehavior::Finish => {
+				self.shared.reached_end.store(true, Ordering::SeqCst);
+				NextStep::End
+			}
+			EndBehavior::Pause => {
+				self.shared.reached_end.store(true, Ordering::SeqCst);
+				NextStep::Wait
+			}
+			EndBehavior::LoopWholeFile => {
+				self.current_section = None;
+				self.transport.position = 0;
+				self.transport.playing = true;
+				NextStep::Continue
+			}
+		}
+	}
+
+	/// Moves playback to the next frame. At the end of the current section,
+	/// playback moves on to the next queued section.
+	fn advance(&mut self) -> Result<(), Error> {
+		if let Some((_, section_end)) = self.current_section {
+			if self.transport.position + 1 < section_end {
+				self.transport.position += 1;
+			} else if !self.start_next_section()? {
+				self.transport.playing = false;
+			}
+			return Ok(());
+		}
+		let remaining_loops = self.transport.remaining_loops;
+		self.transport.increment_position(self.num_frames);
+		if self.transport.remaining_loops != remaining_loops {
+			self.shared
+				.set_remaining_loops(self.transport.remaining_loops);
+		}
+		// sections queued while the sound isn't playing a section start
+		// once the sound reaches the end
+		if !self.transport.playing && self.start_next_section()? {
+			self.transport.playing = true;
+		}
+		Ok(())
+	}
+
+	/// Starts playing the next queued section. Returns `false` if no
+	/// sections are queued.
+	fn start_next_section(&mut self) -> Result<bool, Error> {
+		let Some(section) = self
+			.shared
+			.pop_queued_section(|region| self.section_in_frames(region))
+		else {
+			return Ok(false);
+		};
+		let head = std::mem::take(&mut self.section_head);
+		self.current_section = Some(section);
+		self.num_sections_started += 1;
+		self.transport.position = section.0;
+		self.fill_section_head()?;
+		// continue decoding after the frames that were decoded ahead of time
+		let slice_start = self.slice_start();
+		if head.start_index == slice_start + section.0 && !head.frames.is_empty() {
+			self.seek_decoder(head.start_index + head.frames.len())?;
+			self.decoded_chunk = Some(head);
+		} else {
+			self.seek_decoder(slice_start + section.0)?;
+			self.decoded_chunk = None;
+		}
+		Ok(true)
+	}
+
+	/// Converts a section to frame indices. Empty sections are skipped, so
+	/// they return `None`.
+	#[must_use]
+	fn section_in_frames(&self, section: Region) -> Option<(usize, usize)> {
+		let start = section.start.into_samples(self.sample_rate);
+		let end = section
+			.end_in_samples(self.sample_rate)
+			.unwrap_or(self.num_frames)
+			.min(self.num_frames);
+		(end > start).then_some((start, end))
+	}
+
+	/// Updates the length of the audio from the decoder.
+	fn update_num_frames(&mut self) {
+		let end = self.decoder.num_frames();
+		let end = self.slice.map_or(end, |(_, slice_end)| slice_end.min(end));
+		self.num_frames = end.saturating_sub(self.slice_start());
+	}
+
+	#[must_use]
+	fn slice_start(&self) -> usize {
+		self.slice.map(|(start, _)| start).unwrap_or(0)
+	}
+
+	#[must_use]
+	fn num_buffered_frames(&self) -> usize {
+		self.frame_producer.buffer().capacity() - self.frame_producer.slots()
+	}
+
+	/// Returns how many frames should be decoded ahead of the playback
+	/// position. Sounds that are sped up use up frames more quickly, so
+	/// they need more frames decoded ahead of time.
+	#[must_use]
+	fn target_buffer_length(&self) -> usize {
+		let playback_rate = self
+			.shared
+			.playback_rate()
+			.clamp(1.0, MAX_BUFFERED_PLAYBACK_RATE);
+		(self.buffer_length as f64 * playback_rate).ceil() as usize
+	}
+
+	/// Lets the handle know how much memory the decoded audio and the
+	/// decoder are using.
+	fn report_memory_usage(&self) {
+		let num_decoded_frames = self.loop_head.frames.capacity()
+			+ self.section_head.frames.capacity()
+			+ self
+				.decoded_chunk
+				.as_ref()
+				.map_or(0, |chunk| chunk.frames.capacity());
+		self.shared.set_memory_usage(StreamingMemoryUsage {
+			buffer: self.frame_producer.buffer().capacity() * size_of::<TimestampedFrame>()
+				+ num_decoded_frames * size_of::<Frame>(),
+			decoder: self.decoder.memory_usage(),
+			seek_index: self.decoder.seek_index_memory_usage(),
+		});
+	}
+
+	/// Returns the volume of the frame at the transport's position given
+	/// the fade-out at the end of the sound.
+	#[must_use]
+	fn fade_out_volume(&self) -> f32 {
+		// sounds that start over at the end never fade out
+		let Some(fade_out) = self
+			.fade_out
+			.filter(|_| self.end_behavior != EndBehavior::LoopWholeFile)
+		else {
+			return 1.0;
+		};
+		self.transport
+			.frames_until_end(false, self.num_frames)
+			.map_or(1.0, |frames_until_end| {
+				fade_out.volume(frames_until_end).as_amplitude()
+			})
+	}
+
+	/// Returns the frame at the given index, or `None` if the decoder
+	/// doesn't have it ready yet.
+	fn frame_at_index(&mut self, index: usize) -> Result<Option<Frame>, Error> {
+		let start = self.slice_start();
 		let end = self.slice.map(|(_, end)| end).unwrap_or(self.num_frames);
 		if index >= end - start {
-			return Ok(Frame::ZERO);
+			return Ok(Some(Frame::ZERO));
 		}
 		let index = start + index;
 		// if the requested frame is already loaded, return it
 		if let Some(chunk) = &self.decoded_chunk {
 			if let Some(frame) = chunk.frame_at_index(index) {
-				return Ok(frame);
+				return Ok(Some(frame));
 			}
 		}
+		if let Some(frame) = self.loop_head.frame_at_index(index) {
+			return Ok(Some(frame));
+		}
+		if let Some(frame) = self.section_head.frame_at_index(index) {
+			return Ok(Some(frame));
+		}
 		/*
 			otherwise, seek to the requested index and decode chunks sequentially
 			until we get the frame we want. just because we seek to an index does
@@ -163,23 +506,157 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 			get the frame we care about.
 		*/
 		if index < self.decoder_current_frame_index {
-			self.decoder_current_frame_index = self.decoder.seek(index)?;
+			self.seek_decoder(index)?;
 		}
 		loop {
+			// stop between packets if the sound was stopped, since finding
+			// the frame may take many packets
+			if self.shared.decoding_stopped() {
+				return Ok(None);
+			}
+			let frames = match self.decoder.decode() {
+				Ok(frames) => {
+					self.consecutive_decode_errors = 0;
+					frames
+				}
+				Err(error) => {
+					if self.recover_from_decode_error(error)? {
+						continue;
+					}
+					// end playback as if this was the end of the audio
+					self.transport.playing = false;
+					return Ok(Some(Frame::ZERO));
+				}
+			};
 			let decoded_chunk = DecodedChunk {
 				start_index: self.decoder_current_frame_index,
-				frames: self.decoder.decode()?,
+				frames,
 			};
 			self.decoder_current_frame_index += decoded_chunk.frames.len();
+			let no_audio_ready = decoded_chunk.frames.is_empty();
 			self.decoded_chunk = Some(decoded_chunk);
+			self.report_memory_usage();
+			if no_audio_ready {
+				// decoders for audio with an unknown length find out the
+				// length once they reach the end
+				self.update_num_frames();
+				if index - start >= self.num_frames {
+					self.transport.playing = false;
+					return Ok(Some(Frame::ZERO));
+				}
+				return Ok(None);
+			}
 			if let Some(chunk) = &self.decoded_chunk {
 				if let Some(frame) = chunk.frame_at_index(index) {
-					return Ok(frame);
+					return Ok(Some(frame));
 				}
 			}
 		}
 	}
 
+	/// Decodes the first frames of the loop region ahead of time so that
+	/// wrapping around to the start of the loop doesn't depend on how
+	/// accurately the decoder can seek.
+	fn fill_loop_head(&mut self) -> Result<(), Error> {
+		let mut loop_head = std::mem::take(&mut self.loop_head);
+		let result = self.decode_head(self.transport.loop_region, &mut loop_head);
+		self.loop_head = loop_head;
+		self.report_memory_usage();
+		result
+	}
+
+	/// Decodes the first frames of the next queued section ahead of time
+	/// so the transition from the current section is seamless.
+	fn fill_section_head(&mut self) -> Result<(), Error> {
+		let next_section = self
+			.shared
+			.peek_queued_section(|region| self.section_in_frames(region));
+		// the head may already be decoded if sections were added behind it
+		if let Some((section_start, _)) = next_section {
+			if self.section_head.start_index == self.slice_start() + section_start
+				&& !self.section_head.frames.is_empty()
+			{
+				return Ok(());
+			}
+		}
+		let mut section_head = std::mem::take(&mut self.section_head);
+		let result = self.decode_head(next_section, &mut section_head);
+		self.section_head = section_head;
+		self.report_memory_usage();
+		result
+	}
+
+	/// Decodes up to [`HEAD_LENGTH`] frames from the start of a region into
+	/// `head`, and then returns the decoder to where playback is.
+	fn decode_head(
+		&mut self,
+		region: Option<(usize, usize)>,
+		head: &mut DecodedChunk,
+	) -> Result<(), Error> {
+		head.frames.clear();
+		let Some((region_start, region_end)) = region else {
+			return Ok(());
+		};
+		let slice_start = self.slice_start();
+		let head_start = slice_start + region_start;
+		let head_end = head_start + (region_end - region_start).min(HEAD_LENGTH);
+		self.seek_decoder(head_start)?;
+		while self.decoder_current_frame_index < head_end && !self.shared.decoding_stopped() {
+			let chunk_start = self.decoder_current_frame_index;
+			let frames = match self.decoder.decode() {
+				Ok(frames) => frames,
+				Err(error) => {
+					// leave the rest of the region to be decoded
+					// during playback
+					self.recover_from_decode_error(error)?;
+					break;
+				}
+			};
+			if frames.is_empty() {
+				break;
+			}
+			self.decoder_current_frame_index += frames.len();
+			for (i, frame) in frames.into_iter().enumerate() {
+				if (head_start..head_end).contains(&(chunk_start + i)) {
+					head.frames.push(frame);
+				}
+			}
+		}
+		head.start_index = head_start;
+		// return to where playback is
+		self.decoded_chunk = None;
+		self.seek_decoder(slice_start + self.transport.position)?;
+		Ok(())
+	}
+
+	/// Handles an error from the decoder according to the sound's
+	/// [`DecodeErrorBehavior`]. Returns `true` if decoding should continue
+	/// with the next chunk, `false` if playback should end, or the error if
+	/// the sound should stop with an error.
+	fn recover_from_decode_error(&mut self, error: Error) -> Result<bool, Error> {
+		match self.decode_error_behavior {
+			DecodeErrorBehavior::Fail => Err(error),
+			DecodeErrorBehavior::Skip => {
+				self.error_producer.push(error).ok();
+				self.consecutive_decode_errors += 1;
+				Ok(self.consecutive_decode_errors < MAX_CONSECUTIVE_DECODE_ERRORS)
+			}
+			DecodeErrorBehavior::EndPlayback => {
+				self.error_producer.push(error).ok();
+				Ok(false)
+			}
+		}
+	}
+
+	/// Seeks the decoder to a little before the given index so the
+	/// decoder's output has time to settle. The frames before the index
+	/// are decoded and discarded.
+	fn seek_decoder(&mut self, index: usize) -> Result<(), Error> {
+		let pre_roll = self.decoder.seek_pre_roll();
+		self.decoder_current_frame_index = self.decoder.seek(index.saturating_sub(pre_roll))?;
+		Ok(())
+	}
+
 	fn seek_to(&mut self, position: f64) -> Result<(), Error> {
 		let index = (position * self.sample_rate as f64).round() as usize;
 		self.seek_to_index(index)?;
@@ -193,12 +670,88 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 	}
 
 	fn seek_to_index(&mut self, index: usize) -> Result<(), Error> {
+		let previous_position = self.transport.position;
+		// sounds that paused at the end can play again after seeking
+		self.transport.playing = true;
 		self.transport.seek_to(index, self.num_frames);
-		self.decoder_current_frame_index = self.decoder.seek(index)?;
+		// seeking past the end finishes the sound, so there's nothing
+		// more to decode
+		if !self.transport.playing {
+			self.transport.position = self.num_frames;
+			return Ok(());
+		}
+		let slice_start = self.slice_start();
+		if let Err(error) = self.seek_decoder(slice_start + self.transport.position) {
+			self.transport.position = previous_position;
+			self.transport.playing = previous_position < self.num_frames;
+			return Err(error);
+		}
+		// a sound that paused at the end has played all of its frames, so
+		// it needs a new "previous" frame to interpolate from
+		if self.shared.reached_end() && self.num_buffered_frames() == 0 {
+			self.frame_producer
+				.push(TimestampedFrame {
+					frame: Frame::ZERO,
+					index: self.transport.position,
+					section_index: NO_SECTION,
+				})
+				.expect("could not push frame to frame producer");
+		}
+		self.shared.reached_end.store(false, Ordering::SeqCst);
 		Ok(())
 	}
 }
 
+impl<Error: Send + 'static> Drop for DecodeScheduler<Error> {
+	fn drop(&mut self) {
+		self.shared.mark_decoder_finished();
+	}
+}
+
+/// Decodes audio for a streaming sound a little at a time, for when the
+/// sound doesn't have a decoding thread of its own. This is used when
+/// decoding on the audio thread on platforms where threads can't be used,
+/// and when decoding with a [`StreamingWorkerPool`](crate::sound::streaming::StreamingWorkerPool).
+pub(crate) trait DecodeIncrementally: Send {
+	/// Decodes up to `max_frames` frames, stopping early if the frame
+	/// buffer is full or the decoder doesn't have audio ready. Returns
+	/// the number of frames decoded.
+	fn decode(&mut self, max_frames: usize) -> usize;
+
+	/// Returns `true` if there's nothing left to decode.
+	#[must_use]
+	#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+	fn finished(&self) -> bool;
+}
+
+impl<Error: Send + 'static> DecodeIncrementally for DecodeScheduler<Error> {
+	fn decode(&mut self, max_frames: usize) -> usize {
+		if self.ended {
+			return 0;
+		}
+		for num_frames_decoded in 0..max_frames {
+			match self.run() {
+				Ok(NextStep::Continue) => {}
+				Ok(NextStep::Wait) => return num_frames_decoded,
+				Ok(NextStep::End) => {
+					self.ended = true;
+					return num_frames_decoded + 1;
+				}
+				Err(error) => {
+					self.report_error(error);
+					return num_frames_decoded;
+				}
+			}
+		}
+		max_frames
+	}
+
+	fn finished(&self) -> bool {
+		self.ended
+	}
+}
+
+#[derive(Default)]
 struct DecodedChunk {
 	pub start_index: usize,
 	pub frames: Vec<Frame>,
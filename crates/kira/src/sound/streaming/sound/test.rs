@@ -5,14 +5,24 @@ use crate::{
 	frame::Frame,
 	info::MockInfoBuilder,
 	sound::{
-		streaming::{decoder::mock::MockDecoder, StreamingSoundData, StreamingSoundSettings},
-		PlaybackState, Sound,
+		streaming::{
+			decoder::mock::{MockDecoder, MockDecoderError},
+			DecodeErrorBehavior, Decoder, EndBehavior, StreamState, StreamingSoundData,
+			StreamingSoundHandle, StreamingSoundSettings,
+		},
+		FinishReason, PlaybackState, Sound,
 	},
 	test_helpers::expect_frame_soon,
 	Decibels, Panning, StartTime, Tween,
 };
 
-use super::decode_scheduler::NextStep;
+#[cfg(feature = "wav")]
+use crate::test_helpers::wav_fixture;
+
+use super::{
+	decode_scheduler::{DecodeScheduler, NextStep},
+	StreamingSound,
+};
 
 /// Tests that a `StreamingSound` will play all of its samples before finishing.
 #[test]
@@ -84,7 +94,7 @@ fn waits_for_samples() {
 	}
 
 	for i in 1..=3 {
-		assert_eq!(handle.position(), (i - 1) as f64);
+		assert_eq!(handle.raw_position(), (i - 1) as f64);
 		assert_eq!(
 			sound.process_one(1.0, &MockInfoBuilder::new().build()),
 			Frame::from_mono(i as f32).panned(Panning::CENTER)
@@ -137,7 +147,7 @@ fn reports_playback_position() {
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
 
 	for i in 0..20 {
-		assert_eq!(handle.position(), i.clamp(0, 9) as f64);
+		assert_eq!(handle.raw_position(), i.clamp(0, 9) as f64);
 		sound.process_one(1.0, &MockInfoBuilder::new().build());
 		sound.on_start_processing();
 	}
@@ -316,6 +326,41 @@ fn stops_with_fade_out() {
 	}
 }
 
+/// Tests that a `StreamingSound` notifies its `FinishedReceiver`s with
+/// the reason it finished.
+#[test]
+fn notifies_when_finished() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 3])),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+	let finished = handle.on_finished();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	for _ in 0..3 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	assert_eq!(finished.try_recv(), None);
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+	assert_eq!(finished.try_recv(), Some(FinishReason::ReachedEnd));
+
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	let finished = handle.on_finished();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	handle.stop(Tween::default());
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+	assert_eq!(finished.try_recv(), Some(FinishReason::Stopped));
+}
+
 /// Tests that a `StreamingSound` will wait for its start clock time
 /// when appropriate.
 #[test]
@@ -563,6 +608,83 @@ fn resume_at() {
 	assert_eq!(handle.state(), PlaybackState::Playing);
 }
 
+/// Tests that a `StreamingSound` can be set to stop at a certain start time.
+#[test]
+fn stop_at() {
+	let mut info_builder = MockInfoBuilder::new();
+	let clock_id = info_builder.add_clock(true, 0, 0.0);
+	let info = info_builder.build();
+
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+
+	handle.stop_at(
+		StartTime::ClockTime(ClockTime {
+			clock: clock_id,
+			ticks: 1,
+			fraction: 0.0,
+		}),
+		Tween {
+			duration: Duration::from_secs(2),
+			..Default::default()
+		},
+	);
+	sound.on_start_processing();
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Playing);
+
+	let info = {
+		let mut builder = MockInfoBuilder::new();
+		builder.add_clock(true, 1, 0.0);
+		builder.build()
+	};
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Stopping);
+	sound.process_one(1.0, &info);
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
+/// Tests that a `StreamingSound` discards a scheduled pause if the clock
+/// it's waiting on is removed.
+#[test]
+fn pause_at_missing_clock() {
+	let clock_id = {
+		let mut info_builder = MockInfoBuilder::new();
+		info_builder.add_clock(true, 0, 0.0)
+	};
+
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+
+	handle.pause_at(
+		StartTime::ClockTime(ClockTime {
+			clock: clock_id,
+			ticks: 1,
+			fraction: 0.0,
+		}),
+		Tween::default(),
+	);
+	sound.on_start_processing();
+	for _ in 0..3 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+		sound.on_start_processing();
+		assert_eq!(handle.state(), PlaybackState::Playing);
+	}
+}
+
 /// Tests that a `StreamingSound` can be started partway through the sound.
 #[test]
 #[allow(clippy::float_cmp)]
@@ -578,7 +700,7 @@ fn start_position() {
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
 
 	for i in 3..=6 {
-		assert_eq!(handle.position(), i as f64);
+		assert_eq!(handle.raw_position(), i as f64);
 		assert_eq!(
 			sound.process_one(1.0, &MockInfoBuilder::new().build()),
 			Frame::from_mono(i as f32).panned(Panning::CENTER)
@@ -627,6 +749,34 @@ fn loops_forward() {
 	);
 }
 
+/// Tests that a `StreamingSound` plays its loop region the specified
+/// number of times and then continues to the end of the sound.
+#[test]
+fn loop_count() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new()
+			.loop_region(3.0..6.0)
+			.loop_count(2),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+	assert_eq!(handle.remaining_loops(), Some(1));
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert_eq!(handle.remaining_loops(), Some(0));
+
+	for i in [0, 1, 2, 3, 4, 5, 3, 4, 5, 6, 7, 8, 9] {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+}
+
 /// Tests that the volume of a `StreamingSound` can be adjusted.
 #[test]
 #[allow(clippy::float_cmp)]
@@ -876,3 +1026,990 @@ fn seek_by() {
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
 	expect_frame_soon(Frame::from_mono(20.0).panned(Panning::CENTER), &mut sound);
 }
+
+/// Tests that a `StreamingSound` fades out as playback approaches the end
+/// of the sound, but only after the last loop.
+#[test]
+#[allow(clippy::float_cmp)]
+fn fades_out_at_end() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 5])),
+		settings: StreamingSoundSettings::new()
+			.loop_region(..)
+			.loop_count(2)
+			.fade_out_tween(Tween {
+				duration: Duration::from_secs(4),
+				..Default::default()
+			}),
+		slice: None,
+	};
+	let (mut sound, _, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+
+	let mut out = vec![];
+	for _ in 0..10 {
+		out.push(sound.process_one(1.0, &MockInfoBuilder::new().build()));
+	}
+	let expected = [
+		1.0,
+		1.0,
+		1.0,
+		1.0,
+		1.0,
+		1.0,
+		Decibels(-15.0).as_amplitude(),
+		Decibels(-30.0).as_amplitude(),
+		Decibels(-45.0).as_amplitude(),
+		0.0,
+	]
+	.map(|amplitude| Frame::from_mono(amplitude).panned(Panning::CENTER));
+	assert_eq!(out, expected);
+}
+
+/// Tests that a `StreamingSound` with a start time measured in frames
+/// starts on exactly that frame, even partway through a chunk.
+#[test]
+fn waits_for_frames() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new().start_time(StartTime::Frames(6)),
+		slice: None,
+	};
+	let (mut sound, _, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+
+	let mut out = [Frame::ZERO; 8];
+	for chunk in out.chunks_mut(4) {
+		sound.on_start_processing();
+		sound.process(chunk, 1.0, &MockInfoBuilder::new().build());
+	}
+	assert_eq!(
+		out,
+		[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0]
+			.map(|amplitude| Frame::from_mono(amplitude).panned(Panning::CENTER))
+	);
+}
+
+/// Tests that a `StreamingSound` can decode audio on the audio thread,
+/// outputting silence while the decoder doesn't have audio ready.
+#[test]
+fn decodes_on_audio_thread() {
+	/// A decoder that only has audio ready every other time it's asked.
+	struct SlowDecoder {
+		frames: Vec<Frame>,
+		current_frame_index: usize,
+		ready: bool,
+	}
+
+	impl Decoder for SlowDecoder {
+		type Error = MockDecoderError;
+
+		fn sample_rate(&self) -> u32 {
+			1
+		}
+
+		fn num_frames(&self) -> usize {
+			self.frames.len()
+		}
+
+		fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+			self.ready = !self.ready;
+			if !self.ready {
+				return Ok(vec![]);
+			}
+			let frame = self.frames[self.current_frame_index];
+			self.current_frame_index += 1;
+			Ok(vec![frame])
+		}
+
+		fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+			self.current_frame_index = index;
+			Ok(index)
+		}
+	}
+
+	let data = StreamingSoundData {
+		decoder: Box::new(SlowDecoder {
+			frames: (1..=3).map(|i| Frame::from_mono(i as f32)).collect(),
+			current_frame_index: 0,
+			ready: false,
+		}),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (mut sound, _, scheduler) = data.split().unwrap();
+	sound.decode_on_audio_thread(scheduler);
+
+	let mut played = vec![];
+	for _ in 0..20 {
+		sound.on_start_processing();
+		let frame = sound.process_one(1.0, &MockInfoBuilder::new().build());
+		if frame != Frame::ZERO {
+			played.push(frame);
+		}
+	}
+	assert_eq!(
+		played,
+		[1.0, 2.0, 3.0].map(|amplitude| Frame::from_mono(amplitude).panned(Panning::CENTER))
+	);
+	assert!(sound.finished());
+}
+
+/// Tests that a `StreamingSound` only keeps as many decoded frames
+/// ahead of the playback position as its buffer length allows.
+#[test]
+fn buffer_length() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..=20).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new().buffer_length(8),
+		slice: None,
+	};
+	let (mut sound, _, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert_eq!(sound.frame_consumer.slots(), 8);
+
+	// playback starts right away and the decoder refills the buffer
+	// as frames are consumed
+	sound.on_start_processing();
+	let mut out = [Frame::ZERO; 4];
+	sound.process(&mut out, 1.0, &MockInfoBuilder::new().build());
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert_eq!(sound.frame_consumer.slots(), 8);
+}
+
+/// Tests that a `StreamingSound` reports when it runs out of decoded
+/// audio.
+#[test]
+fn reports_underruns() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..=10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::default(),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+
+	// waiting for audio before the sound starts isn't an underrun
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(handle.is_buffering());
+	assert_eq!(handle.underrun_count(), 0);
+	assert_eq!(handle.silent_frames_inserted(), 0);
+
+	for _ in 0..3 {
+		scheduler.run().unwrap();
+	}
+	for i in 1..=3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+		assert!(!handle.is_buffering());
+	}
+
+	// the decoder falls behind
+	for _ in 0..3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::ZERO.panned(Panning::CENTER)
+		);
+		assert!(handle.is_buffering());
+	}
+	assert_eq!(handle.underrun_count(), 1);
+	assert_eq!(handle.silent_frames_inserted(), 3);
+
+	// the decoder catches up
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(4.0).panned(Panning::CENTER)
+	);
+	assert!(!handle.is_buffering());
+	assert_eq!(handle.underrun_count(), 1);
+}
+
+/// Tests that a `StreamingSound` counts underruns that start partway
+/// through a chunk and only counts the frames that were missing.
+#[test]
+fn reports_underruns_partway_through_chunk() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..=10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::default(),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+	for _ in 0..3 {
+		scheduler.run().unwrap();
+	}
+
+	// the decoder falls behind after three frames
+	sound.on_start_processing();
+	let mut out = [Frame::ZERO; 8];
+	sound.process(&mut out, 1.0, &MockInfoBuilder::new().build());
+	for (i, frame) in out.iter().enumerate() {
+		let expected = if i < 3 { (i + 1) as f32 } else { 0.0 };
+		assert_eq!(*frame, Frame::from_mono(expected).panned(Panning::CENTER));
+	}
+	assert!(handle.is_buffering());
+	assert_eq!(handle.underrun_count(), 1);
+	assert_eq!(handle.silent_frames_inserted(), 5);
+
+	// the decoder catches up partway through the next chunk
+	for _ in 0..2 {
+		scheduler.run().unwrap();
+	}
+	sound.on_start_processing();
+	sound.process(&mut out, 1.0, &MockInfoBuilder::new().build());
+	assert_eq!(out[0], Frame::from_mono(4.0).panned(Panning::CENTER));
+	assert_eq!(handle.underrun_count(), 2);
+	assert_eq!(handle.silent_frames_inserted(), 11);
+}
+
+/// A decoder that outputs garbage for the first packet after seeking,
+/// like codecs that need to warm up after a seek.
+struct WarmUpDecoder {
+	num_frames: usize,
+	current_frame_index: usize,
+	warming_up: bool,
+}
+
+impl WarmUpDecoder {
+	const PACKET_SIZE: usize = 3;
+
+	fn new(num_frames: usize) -> Self {
+		Self {
+			num_frames,
+			current_frame_index: 0,
+			warming_up: false,
+		}
+	}
+}
+
+impl Decoder for WarmUpDecoder {
+	type Error = MockDecoderError;
+
+	fn sample_rate(&self) -> u32 {
+		1
+	}
+
+	fn num_frames(&self) -> usize {
+		self.num_frames
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		let start = self.current_frame_index;
+		let end = (start + Self::PACKET_SIZE).min(self.num_frames);
+		self.current_frame_index = end;
+		let warming_up = std::mem::take(&mut self.warming_up);
+		Ok((start..end)
+			.map(|i| Frame::from_mono(if warming_up { -1.0 } else { i as f32 }))
+			.collect())
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		self.current_frame_index = index / Self::PACKET_SIZE * Self::PACKET_SIZE;
+		self.warming_up = true;
+		Ok(self.current_frame_index)
+	}
+
+	fn seek_pre_roll(&self) -> usize {
+		Self::PACKET_SIZE
+	}
+}
+
+/// Tests that a `StreamingSound` wraps around its loop region without
+/// glitches, even if the decoder needs to warm up after seeking.
+#[test]
+fn loops_without_seek_glitches() {
+	let data = StreamingSoundData {
+		decoder: Box::new(WarmUpDecoder::new(10_000)),
+		settings: StreamingSoundSettings::new()
+			.start_position(4990.0)
+			.loop_region(5000.0..5100.0),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	// skip the "previous" frame the frame ringbuffer starts with
+	sound.frame_consumer.pop().unwrap();
+
+	let mut next_frame = || {
+		assert!(matches!(scheduler.run().unwrap(), NextStep::Continue));
+		sound.frame_consumer.pop().unwrap().frame.left
+	};
+	let expected = (4990..5100).chain(5000..5100).chain(5000..5050);
+	for index in expected {
+		assert_eq!(next_frame(), index as f32);
+	}
+
+	// changing the loop region while the sound is playing
+	handle.set_loop_region(5020.0..5060.0);
+	let expected = (5050..5060).chain(5020..5060).chain(5020..5030);
+	for index in expected {
+		assert_eq!(next_frame(), index as f32);
+	}
+}
+
+/// Creates a `StreamingSoundData` for a WAV file with 1,000 samples
+/// at 100Hz, where each sample's value is its index times 10.
+#[cfg(feature = "wav")]
+fn ramp_wav() -> StreamingSoundData<crate::sound::FromFileError> {
+	let samples = (0..1000).map(|i| i * 10).collect::<Vec<i16>>();
+	StreamingSoundData::from_read(std::io::Cursor::new(wav_fixture(100, &samples)), None).unwrap()
+}
+
+/// Returns the value of the sample at the given index of [`ramp_wav`].
+#[cfg(feature = "wav")]
+fn ramp_wav_sample(index: usize) -> f32 {
+	(index * 10) as f32 / 32768.0
+}
+
+/// Tests that a `StreamingSound` resumes with the correct audio after
+/// seeking, including when seeking close to the start of the audio.
+#[test]
+#[cfg(feature = "wav")]
+fn seeks_with_pre_roll() {
+	for position in [0.02, 5.0] {
+		let (mut sound, mut handle, mut scheduler) = ramp_wav().split().unwrap();
+		handle.seek_to(position);
+		// skip the "previous" frame the frame ringbuffer starts with
+		sound.frame_consumer.pop().unwrap();
+		let start_index = (position * 100.0) as usize;
+		for index in start_index..start_index + 10 {
+			assert!(matches!(scheduler.run().unwrap(), NextStep::Continue));
+			let frame = sound.frame_consumer.pop().unwrap();
+			assert_eq!(frame.index, index);
+			assert_eq!(frame.frame.left, ramp_wav_sample(index));
+		}
+	}
+}
+
+/// Tests that a `StreamingSound` finishes when seeking past the end of
+/// the audio.
+#[test]
+#[cfg(feature = "wav")]
+fn seeking_past_end_finishes() {
+	let (mut sound, mut handle, mut scheduler) = ramp_wav().split().unwrap();
+	handle.seek_to(20.0);
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	for _ in 0..10 {
+		sound.on_start_processing();
+		sound.process_one(0.01, &MockInfoBuilder::new().build());
+	}
+	assert!(sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+	assert_eq!(handle.position(), 10.0);
+	assert!(handle.pop_error().is_none());
+}
+
+/// Tests that a `StreamingSound` doesn't run out of decoded audio when
+/// its playback rate is increased, as long as the decoder can keep up.
+#[test]
+fn playback_rate_changes_without_underruns() {
+	const BLOCK_SIZE: usize = 32;
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.5); 10_000])),
+		settings: StreamingSoundSettings::new()
+			.playback_rate(0.5)
+			.buffer_length(BLOCK_SIZE * 2),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	handle.set_playback_rate(
+		2.0,
+		Tween {
+			duration: Duration::from_secs(600),
+			..Default::default()
+		},
+	);
+	let mut out = [Frame::ZERO; BLOCK_SIZE];
+	for _ in 0..40 {
+		while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+		sound.on_start_processing();
+		sound.process(&mut out, 1.0, &MockInfoBuilder::new().build());
+	}
+	assert_eq!(handle.underrun_count(), 0);
+	assert_eq!(handle.silent_frames_inserted(), 0);
+}
+
+/// A decoder that fails to decode one of its packets. Like real decoders,
+/// it moves on to the next packet after a failure and fails when asked
+/// to decode past the end of the audio.
+struct DamagedDecoder {
+	num_frames: usize,
+	damaged_packet: usize,
+	current_frame_index: usize,
+}
+
+impl DamagedDecoder {
+	const PACKET_SIZE: usize = 3;
+
+	fn new(num_frames: usize, damaged_packet: usize) -> Self {
+		Self {
+			num_frames,
+			damaged_packet,
+			current_frame_index: 0,
+		}
+	}
+}
+
+impl Decoder for DamagedDecoder {
+	type Error = MockDecoderError;
+
+	fn sample_rate(&self) -> u32 {
+		1
+	}
+
+	fn num_frames(&self) -> usize {
+		self.num_frames
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		let start = self.current_frame_index;
+		if start >= self.num_frames {
+			return Err(MockDecoderError);
+		}
+		let end = (start + Self::PACKET_SIZE).min(self.num_frames);
+		self.current_frame_index = end;
+		if start / Self::PACKET_SIZE == self.damaged_packet {
+			return Err(MockDecoderError);
+		}
+		Ok((start..end).map(|i| Frame::from_mono(i as f32)).collect())
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		self.current_frame_index = index / Self::PACKET_SIZE * Self::PACKET_SIZE;
+		Ok(self.current_frame_index)
+	}
+}
+
+/// Tests that a `StreamingSound` stops with an error when the decoder
+/// fails by default.
+#[test]
+fn fails_on_decode_error() {
+	let data = StreamingSoundData {
+		decoder: Box::new(DamagedDecoder::new(9, 1)),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (_sound, _, mut scheduler) = data.split().unwrap();
+	for _ in 0..3 {
+		assert!(matches!(scheduler.run().unwrap(), NextStep::Continue));
+	}
+	assert!(scheduler.run().is_err());
+}
+
+/// Tests that a `StreamingSound` skips chunks the decoder fails to decode
+/// and reports the errors to the handle.
+#[test]
+fn skips_decode_errors() {
+	let data = StreamingSoundData {
+		decoder: Box::new(DamagedDecoder::new(9, 1)),
+		settings: StreamingSoundSettings::new().on_decode_error(DecodeErrorBehavior::Skip),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	// skip the "previous" frame the frame ringbuffer starts with
+	sound.frame_consumer.pop().unwrap();
+	for expected in [0.0, 1.0, 2.0, 6.0, 7.0, 8.0] {
+		assert!(matches!(scheduler.run().unwrap(), NextStep::Continue));
+		assert_eq!(sound.frame_consumer.pop().unwrap().frame.left, expected);
+	}
+	assert_eq!(handle.take_decode_errors(), vec![MockDecoderError]);
+	assert!(handle.take_decode_errors().is_empty());
+
+	// the skipped chunk means the decoder runs out of audio early,
+	// so it keeps failing until playback ends
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert!(!handle.take_decode_errors().is_empty());
+	for _ in 0..20 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	assert!(sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
+/// Tests that a `StreamingSound` can end playback when the decoder fails
+/// as if it reached the end of the audio.
+#[test]
+fn ends_playback_on_decode_error() {
+	let data = StreamingSoundData {
+		decoder: Box::new(DamagedDecoder::new(9, 1)),
+		settings: StreamingSoundSettings::new().on_decode_error(DecodeErrorBehavior::EndPlayback),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	let finished = handle.on_finished();
+	for _ in 0..3 {
+		assert!(matches!(scheduler.run().unwrap(), NextStep::Continue));
+	}
+	assert!(matches!(scheduler.run().unwrap(), NextStep::End));
+	assert_eq!(handle.take_decode_errors(), vec![MockDecoderError]);
+	for _ in 0..5 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	assert!(sound.finished());
+	assert_eq!(finished.try_recv(), Some(FinishReason::ReachedEnd));
+}
+
+/// Tests that a `StreamingSound` plays its sections back-to-back, even
+/// when a section is earlier in the audio than the one before it.
+#[test]
+fn plays_sections_back_to_back() {
+	let data = StreamingSoundData {
+		decoder: Box::new(WarmUpDecoder::new(10_000)),
+		settings: StreamingSoundSettings::new().section_sequence(vec![
+			(5000.0..5010.0).into(),
+			(100.0..110.0).into(),
+			(9000.0..9005.0).into(),
+		]),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	let expected = [(0, 5000..5010), (1, 100..110), (2, 9000..9005)];
+	for (section_index, indices) in expected {
+		for index in indices {
+			sound.on_start_processing();
+			assert_eq!(handle.current_section_index(), Some(section_index));
+			assert_eq!(handle.raw_position(), index as f64);
+			assert_eq!(
+				sound.process_one(1.0, &MockInfoBuilder::new().build()),
+				Frame::from_mono(index as f32).panned(Panning::CENTER)
+			);
+		}
+	}
+	for _ in 0..5 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	assert!(sound.finished());
+}
+
+/// Tests that sections can be queued and cleared while a `StreamingSound`
+/// is playing.
+#[test]
+fn queues_sections() {
+	let data = StreamingSoundData {
+		decoder: Box::new(WarmUpDecoder::new(10_000)),
+		settings: StreamingSoundSettings::new().section_sequence(vec![(5000.0..5005.0).into()]),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	// skip the "previous" frame the frame ringbuffer starts with
+	sound.frame_consumer.pop().unwrap();
+	let mut next_frame = |expected_step: fn(&NextStep) -> bool| {
+		assert!(expected_step(&scheduler.run().unwrap()));
+		let frame = sound.frame_consumer.pop().unwrap();
+		(frame.frame.left, frame.section_index)
+	};
+	let continues = |step: &NextStep| matches!(step, NextStep::Continue);
+	assert_eq!(next_frame(continues), (5000.0, 0));
+	handle.queue_section(200.0..203.0);
+	handle.queue_section(300.0..302.0);
+	let expected = (5001..5005)
+		.map(|index| (index as f32, 0))
+		.chain((200..203).map(|index| (index as f32, 1)))
+		.chain([(300.0, 2)]);
+	for frame in expected {
+		assert_eq!(next_frame(continues), frame);
+	}
+	// the sound ends after the current section once the queue is cleared
+	handle.clear_queued_sections();
+	assert_eq!(next_frame(|step| matches!(step, NextStep::End)), (301.0, 2));
+}
+
+/// Tests that sections queued on a `StreamingSound` without a section
+/// sequence play after it reaches the end of the audio.
+#[test]
+fn plays_queued_sections_after_end() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(0..5).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	handle.queue_section(1.0..3.0);
+	// skip the "previous" frame the frame ringbuffer starts with
+	sound.frame_consumer.pop().unwrap();
+	let mut frames = vec![];
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {
+		frames.push(sound.frame_consumer.pop().unwrap().frame.left);
+	}
+	frames.push(sound.frame_consumer.pop().unwrap().frame.left);
+	assert_eq!(frames, [0.0, 1.0, 2.0, 3.0, 4.0, 1.0, 2.0]);
+}
+
+/// A decoder that doesn't know how long its audio is until it reaches
+/// the end, like a network stream.
+struct UnknownLengthDecoder {
+	num_frames: usize,
+	current_frame_index: usize,
+	reached_end: bool,
+}
+
+impl Decoder for UnknownLengthDecoder {
+	type Error = MockDecoderError;
+
+	fn sample_rate(&self) -> u32 {
+		1
+	}
+
+	fn num_frames(&self) -> usize {
+		if self.reached_end {
+			self.num_frames
+		} else {
+			usize::MAX
+		}
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		let start = self.current_frame_index;
+		let end = (start + 3).min(self.num_frames);
+		if start == end {
+			self.reached_end = true;
+		}
+		self.current_frame_index = end;
+		Ok((start..end)
+			.map(|i| Frame::from_mono(i as f32 + 1.0))
+			.collect())
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		if index == self.current_frame_index {
+			Ok(index)
+		} else {
+			Err(MockDecoderError)
+		}
+	}
+}
+
+/// Tests that a `StreamingSound` finishes when a decoder for audio of
+/// unknown length reaches the end.
+#[test]
+fn finishes_when_unknown_length_audio_ends() {
+	let data = StreamingSoundData {
+		decoder: Box::new(UnknownLengthDecoder {
+			num_frames: 5,
+			current_frame_index: 0,
+			reached_end: false,
+		}),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+	assert_eq!(handle.stream_state(), StreamState::Playing);
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert_eq!(handle.stream_state(), StreamState::Ended);
+	for i in 1..=5 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	for _ in 0..5 {
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	assert!(sound.finished());
+}
+
+/// A decoder for audio with loudness tags.
+struct TaggedDecoder {
+	decoder: MockDecoder,
+	loudness: f64,
+}
+
+impl Decoder for TaggedDecoder {
+	type Error = MockDecoderError;
+
+	fn sample_rate(&self) -> u32 {
+		self.decoder.sample_rate()
+	}
+
+	fn num_frames(&self) -> usize {
+		self.decoder.num_frames()
+	}
+
+	fn loudness(&self) -> Option<f64> {
+		Some(self.loudness)
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		self.decoder.decode()
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		self.decoder.seek(index)
+	}
+}
+
+/// Tests that a `StreamingSound` applies a gain to reach the target
+/// loudness when loudness normalization is enabled.
+#[test]
+fn normalizes_loudness() {
+	let data = StreamingSoundData {
+		decoder: Box::new(TaggedDecoder {
+			decoder: MockDecoder::new(vec![Frame::from_mono(0.5); 10]),
+			loudness: -20.0,
+		}),
+		settings: StreamingSoundSettings::new()
+			.volume(-3.0)
+			.apply_loudness_normalization(-14.0),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+	assert_eq!(handle.loudness_gain(), Decibels(6.0));
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	// skip the "previous" frame the frame ringbuffer starts with
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert_eq!(
+		sound.process_one(1.0, &MockInfoBuilder::new().build()),
+		Frame::from_mono(0.5 * Decibels(3.0).as_amplitude()).panned(Panning::CENTER)
+	);
+}
+
+/// Tests that a `StreamingSound` isn't normalized when loudness
+/// normalization isn't enabled or the loudness of the audio isn't known.
+#[test]
+fn only_normalizes_loudness_when_known() {
+	let data = StreamingSoundData {
+		decoder: Box::new(TaggedDecoder {
+			decoder: MockDecoder::new(vec![Frame::from_mono(0.5); 10]),
+			loudness: -20.0,
+		}),
+		settings: StreamingSoundSettings::new(),
+		slice: None,
+	};
+	let (_sound, handle, _scheduler) = data.split().unwrap();
+	assert_eq!(handle.loudness_gain(), Decibels::IDENTITY);
+
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.5); 10])),
+		settings: StreamingSoundSettings::new().apply_loudness_normalization(-14.0),
+		slice: None,
+	};
+	let (_sound, handle, _scheduler) = data.split().unwrap();
+	assert_eq!(handle.loudness_gain(), Decibels::IDENTITY);
+}
+
+/// Tests that the position reported by a `StreamingSoundHandle` advances
+/// smoothly between the times the sound reports its position.
+#[test]
+fn interpolates_position() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
+		settings: StreamingSoundSettings::new().playback_rate(2.0),
+		slice: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	sound.on_start_processing();
+	let raw_position = handle.raw_position();
+	std::thread::sleep(Duration::from_millis(20));
+	assert_eq!(handle.raw_position(), raw_position);
+	// the sound is playing at double speed
+	let position = handle.position();
+	assert!(position >= raw_position + 0.04);
+	assert!(position <= raw_position + 0.5);
+
+	// a paused sound's position doesn't advance
+	handle.pause(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	sound.on_start_processing();
+	std::thread::sleep(Duration::from_millis(20));
+	assert_eq!(handle.position(), handle.raw_position());
+}
+
+/// A decoder that reports how much memory it's using.
+struct BufferedDecoder {
+	decoder: MockDecoder,
+	memory_usage: usize,
+}
+
+impl Decoder for BufferedDecoder {
+	type Error = MockDecoderError;
+
+	fn sample_rate(&self) -> u32 {
+		self.decoder.sample_rate()
+	}
+
+	fn num_frames(&self) -> usize {
+		self.decoder.num_frames()
+	}
+
+	fn memory_usage(&self) -> usize {
+		self.memory_usage
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		self.decoder.decode()
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		self.decoder.seek(index)
+	}
+}
+
+/// Tests that a `StreamingSoundHandle` reports how much memory the
+/// decoded audio and the decoder are using.
+#[test]
+fn reports_memory_usage() {
+	let memory_usage = |buffer_length| {
+		let data = StreamingSoundData {
+			decoder: Box::new(BufferedDecoder {
+				decoder: MockDecoder::new(vec![Frame::from_mono(0.5); 10]),
+				memory_usage: 1000,
+			}),
+			settings: StreamingSoundSettings::new().buffer_length(buffer_length),
+			slice: None,
+		};
+		let (sound, handle, mut scheduler) = data.split().unwrap();
+		while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+		let memory_usage = handle.memory_usage();
+		assert_eq!(sound.memory_usage(), memory_usage.total());
+		memory_usage
+	};
+	let short_buffer = memory_usage(100);
+	assert_eq!(short_buffer.decoder, 1000);
+	assert_eq!(short_buffer.seek_index, 0);
+	assert!(short_buffer.buffer > 0);
+	// longer buffers hold more decoded audio
+	let long_buffer = memory_usage(1000);
+	assert!(long_buffer.buffer > short_buffer.buffer);
+}
+
+/// Plays a streaming sound that pauses at the end until it reaches the end.
+fn play_to_end_and_pause() -> (
+	StreamingSound,
+	StreamingSoundHandle<MockDecoderError>,
+	DecodeScheduler<MockDecoderError>,
+) {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![
+			Frame::from_mono(1.0),
+			Frame::from_mono(2.0),
+			Frame::from_mono(3.0),
+		])),
+		settings: StreamingSoundSettings::new().on_end(EndBehavior::Pause),
+		slice: None,
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+	assert!(matches!(
+		std::iter::repeat_with(|| scheduler.run().unwrap())
+			.find(|next_step| !matches!(next_step, NextStep::Continue)),
+		Some(NextStep::Wait)
+	));
+	for _ in 0..10 {
+		sound.on_start_processing();
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	(sound, handle, scheduler)
+}
+
+/// Tests that a `StreamingSound` that pauses at the end can be seeked
+/// and resumed to play the audio again.
+#[test]
+fn pauses_at_end() {
+	let (mut sound, mut handle, mut scheduler) = play_to_end_and_pause();
+	assert!(!sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Paused);
+	// the position stays at the last frame
+	assert_eq!(handle.raw_position(), 2.0);
+
+	handle.seek_to(0.0);
+	handle.resume(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	sound.on_start_processing();
+	for i in 1..=3 {
+		assert_eq!(
+			sound.process_one(1.0, &MockInfoBuilder::new().build()),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+	for _ in 0..10 {
+		sound.on_start_processing();
+		sound.process_one(1.0, &MockInfoBuilder::new().build());
+	}
+	assert!(!sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Paused);
+}
+
+/// Tests that a `StreamingSound` that paused at the end finishes once
+/// its handle is dropped or it's stopped.
+#[test]
+fn finishes_after_pausing_at_end() {
+	let (mut sound, handle, _scheduler) = play_to_end_and_pause();
+	drop(handle);
+	sound.on_start_processing();
+	assert!(sound.finished());
+
+	let (mut sound, mut handle, _scheduler) = play_to_end_and_pause();
+	handle.stop(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert!(sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
+/// Tests that a `StreamingSound` that loops the whole file starts over
+/// once it reaches the end.
+#[test]
+fn loops_whole_file() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.5); 3])),
+		settings: StreamingSoundSettings::new()
+			.loop_region(1.0..)
+			.loop_count(2)
+			.on_end(EndBehavior::LoopWholeFile),
+		slice: None,
+	};
+	let (mut sound, _handle, mut scheduler) = data.split().unwrap();
+	// skip the "previous" frame the frame ringbuffer starts with
+	sound.frame_consumer.pop().unwrap();
+	// the loop region plays twice, and then the whole file starts over
+	for index in [0, 1, 2, 1, 2, 0, 1, 2, 0] {
+		assert!(matches!(scheduler.run().unwrap(), NextStep::Continue));
+		assert_eq!(sound.frame_consumer.pop().unwrap().index, index);
+	}
+}
+
+/// Tests that a `StreamingSound` loops the loop points stored in its file
+/// when no other loop region is set.
+#[test]
+#[cfg(feature = "wav")]
+fn loops_detected_loop_region() {
+	let samples = (0..8).map(|i| i * 1000).collect::<Vec<_>>();
+	let wav = crate::test_helpers::with_wav_loop(wav_fixture(1, &samples), 2, 4);
+	let frame = |i: usize| Frame::from_mono(samples[i] as f32 / 32768.0).panned(Panning::CENTER);
+	let play = |data: StreamingSoundData<_>, num_frames: usize| {
+		let (mut sound, _handle, mut scheduler) = data.split().unwrap();
+		while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+		(0..num_frames)
+			.map(|_| sound.process_one(1.0, &MockInfoBuilder::new().build()))
+			.collect::<Vec<_>>()
+	};
+	let data = || StreamingSoundData::from_read(std::io::Cursor::new(wav.clone()), None).unwrap();
+	assert!(data().detected_loop_region().is_some());
+	assert_eq!(play(data(), 9), [0, 1, 2, 3, 4, 2, 3, 4, 2].map(frame));
+	assert_eq!(
+		play(data().use_detected_loop_region(false), 8),
+		[0, 1, 2, 3, 4, 5, 6, 7].map(frame)
+	);
+}
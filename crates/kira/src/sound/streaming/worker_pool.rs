@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	collections::VecDeque,
+	fmt::{Debug, Formatter},
+	sync::{Arc, Condvar, Mutex},
+	time::Duration,
+};
+
+use super::sound::decode_scheduler::DecodeIncrementally;
+
+/// The most frames a worker decodes for one sound before moving on to
+/// the next sound.
+const MAX_FRAMES_PER_TURN: usize = 1024;
+/// How long a worker sleeps after every sound it's decoding for turns
+/// out to have enough audio decoded already.
+const IDLE_SLEEP_DURATION: Duration = Duration::from_millis(1);
+
+/**
+A set of threads that decode audio for many streaming sounds.
+
+By default, each streaming sound decodes audio on its own thread. When
+many streaming sounds play at once, it can be more efficient to share a
+few threads between them. To use a worker pool for a sound, pass it to
+[`StreamingSoundSettings::worker_pool`](super::StreamingSoundSettings::worker_pool)
+or [`StreamingSoundData::worker_pool`](super::StreamingSoundData::worker_pool).
+
+The sounds take turns decoding a small amount of audio at a time, so a
+sound that's slow to decode can't keep the other sounds from being decoded.
+If there are more sounds than the threads can keep up with, the sounds
+take longer to start, but each sound still keeps its buffer of decoded
+audio filled as long as the decoders are fast enough overall.
+
+The threads stop once every [`StreamingWorkerPool`] handle has been dropped
+and all of the sounds using the pool have finished.
+
+# Examples
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	sound::streaming::{StreamingSoundData, StreamingWorkerPool},
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let pool = StreamingWorkerPool::new(2);
+for path in ["wind.ogg", "rain.ogg", "birds.ogg", "river.ogg"] {
+	manager.play(StreamingSoundData::from_file(path)?.worker_pool(&pool))?;
+}
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+#[derive(Clone)]
+pub struct StreamingWorkerPool {
+	owner: Arc<PoolOwner>,
+}
+
+impl StreamingWorkerPool {
+	/// Creates a worker pool with the given number of threads.
+	///
+	/// At least one thread is always created.
+	#[must_use]
+	pub fn new(num_threads: usize) -> Self {
+		let shared = Arc::new(PoolShared {
+			jobs: Mutex::new(Jobs {
+				queue: VecDeque::new(),
+				closed: false,
+			}),
+			job_added: Condvar::new(),
+		});
+		for _ in 0..num_threads.max(1) {
+			let shared = shared.clone();
+			std::thread::spawn(move || shared.run_worker());
+		}
+		Self {
+			owner: Arc::new(PoolOwner { shared }),
+		}
+	}
+
+	/// Adds a sound to the pool. A worker decodes audio for it until
+	/// there's nothing left to decode.
+	pub(crate) fn submit(&self, job: Box<dyn DecodeIncrementally>) {
+		let shared = &self.owner.shared;
+		shared
+			.jobs
+			.lock()
+			.expect("worker pool mutex poisoned")
+			.queue
+			.push_back(job);
+		shared.job_added.notify_one();
+	}
+}
+
+impl Debug for StreamingWorkerPool {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("StreamingWorkerPool")
+			.finish_non_exhaustive()
+	}
+}
+
+impl PartialEq for StreamingWorkerPool {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.owner, &other.owner)
+	}
+}
+
+/// Lets the worker threads know when every [`StreamingWorkerPool`] handle
+/// has been dropped.
+struct PoolOwner {
+	shared: Arc<PoolShared>,
+}
+
+impl Drop for PoolOwner {
+	fn drop(&mut self) {
+		self.shared
+			.jobs
+			.lock()
+			.expect("worker pool mutex poisoned")
+			.closed = true;
+		self.shared.job_added.notify_all();
+	}
+}
+
+struct PoolShared {
+	jobs: Mutex<Jobs>,
+	job_added: Condvar,
+}
+
+struct Jobs {
+	/// Sounds waiting for a turn to be decoded.
+	queue: VecDeque<Box<dyn DecodeIncrementally>>,
+	/// Whether no more sounds can be added to the pool.
+	closed: bool,
+}
+
+impl PoolShared {
+	fn run_worker(&self) {
+		// how many turns in a row sounds didn't need any audio decoded
+		let mut idle_turns = 0;
+		loop {
+			let Some((mut job, num_jobs)) = self.next_job() else {
+				return;
+			};
+			let num_frames_decoded = job.decode(MAX_FRAMES_PER_TURN);
+			if !job.finished() {
+				self.jobs
+					.lock()
+					.expect("worker pool mutex poisoned")
+					.queue
+					.push_back(job);
+			}
+			if num_frames_decoded > 0 {
+				idle_turns = 0;
+			} else {
+				idle_turns += 1;
+			}
+			// if none of the sounds needed more audio, wait a bit
+			// before checking again
+			if idle_turns >= num_jobs {
+				idle_turns = 0;
+				std::thread::sleep(IDLE_SLEEP_DURATION);
+			}
+		}
+	}
+
+	/// Waits for a sound to decode audio for and returns it along with
+	/// the number of sounds in the pool. Returns `None` if the pool is
+	/// closed and there aren't any sounds left.
+	fn next_job(&self) -> Option<(Box<dyn DecodeIncrementally>, usize)> {
+		let mut jobs = self.jobs.lock().expect("worker pool mutex poisoned");
+		loop {
+			let num_jobs = jobs.queue.len();
+			if let Some(job) = jobs.queue.pop_front() {
+				return Some((job, num_jobs));
+			}
+			if jobs.closed {
+				return None;
+			}
+			jobs = self
+				.job_added
+				.wait(jobs)
+				.expect("worker pool mutex poisoned");
+		}
+	}
+}
@@ -0,0 +1,133 @@
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use crate::{
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::{
+		streaming::{
+			decoder::mock::{MockDecoder, MockDecoderError},
+			Decoder, StreamState, StreamingSoundData, StreamingSoundHandle, StreamingSoundSettings,
+		},
+		SoundData,
+	},
+	Panning, Tween,
+};
+
+use super::StreamingWorkerPool;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn wait_until(mut condition: impl FnMut() -> bool) {
+	let start = Instant::now();
+	while !condition() {
+		assert!(start.elapsed() < TIMEOUT, "timed out");
+		std::thread::sleep(Duration::from_millis(1));
+	}
+}
+
+fn all_ended<Error>(handles: &[StreamingSoundHandle<Error>]) -> bool {
+	handles
+		.iter()
+		.all(|handle| handle.stream_state() == StreamState::Ended)
+}
+
+/// A decoder that takes a while to decode each packet and never runs
+/// out of audio.
+struct SlowDecoder;
+
+impl Decoder for SlowDecoder {
+	type Error = MockDecoderError;
+
+	fn sample_rate(&self) -> u32 {
+		1
+	}
+
+	fn num_frames(&self) -> usize {
+		usize::MAX
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		std::thread::sleep(Duration::from_micros(100));
+		Ok(vec![Frame::ZERO; 3])
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		Ok(index)
+	}
+}
+
+/// Tests that a `StreamingWorkerPool` decodes audio for more sounds
+/// than it has threads.
+#[test]
+fn decodes_many_sounds() {
+	let pool = StreamingWorkerPool::new(1);
+	let (mut sounds, handles): (Vec<_>, Vec<_>) = (0..4)
+		.map(|i| {
+			StreamingSoundData::from_decoder(MockDecoder::new(vec![Frame::from_mono(i as f32); 3]))
+				.worker_pool(&pool)
+				.into_sound()
+				.unwrap()
+		})
+		.unzip();
+	wait_until(|| all_ended(&handles));
+	for (i, sound) in sounds.iter_mut().enumerate() {
+		for _ in 0..3 {
+			assert_eq!(
+				sound.process_one(1.0, &MockInfoBuilder::new().build()),
+				Frame::from_mono(i as f32).panned(Panning::CENTER)
+			);
+		}
+	}
+}
+
+/// Tests that a sound that's slow to decode doesn't keep a
+/// `StreamingWorkerPool` from decoding other sounds.
+#[test]
+fn sounds_take_turns() {
+	let pool = StreamingWorkerPool::new(1);
+	// without turns, this sound would take minutes to fill its buffer
+	let (_slow_sound, _slow_handle) = StreamingSoundData {
+		decoder: Box::new(SlowDecoder),
+		settings: StreamingSoundSettings::new().buffer_length(10_000_000),
+		slice: None,
+	}
+	.worker_pool(&pool)
+	.into_sound()
+	.unwrap();
+	let (_sound, handle) =
+		StreamingSoundData::from_decoder(MockDecoder::new(vec![Frame::from_mono(1.0); 100]))
+			.worker_pool(&pool)
+			.into_sound()
+			.unwrap();
+	wait_until(|| handle.stream_state() == StreamState::Ended);
+}
+
+/// Tests that the threads of a `StreamingWorkerPool` stop once the pool
+/// is dropped and its sounds are finished.
+#[test]
+fn stops_when_dropped() {
+	let pool = StreamingWorkerPool::new(2);
+	let shared = pool.owner.shared.clone();
+	let (mut sound, mut handle) = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
+		settings: StreamingSoundSettings::new().buffer_length(10),
+		slice: None,
+	}
+	.worker_pool(&pool)
+	.into_sound()
+	.unwrap();
+	drop(pool);
+	// the threads keep decoding audio for sounds that are still playing
+	std::thread::sleep(Duration::from_millis(10));
+	assert_eq!(Arc::strong_count(&shared), 3);
+	handle.stop(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	wait_until(|| Arc::strong_count(&shared) == 1);
+}
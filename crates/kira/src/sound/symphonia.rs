@@ -1,46 +1,274 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	io::{Read, Seek, SeekFrom},
+	sync::Mutex,
+};
+
 use symphonia::core::{
-	audio::{AudioBuffer, AudioBufferRef, Signal},
+	audio::{AudioBuffer, AudioBufferRef, Channels, Signal},
 	conv::{FromSample, IntoSample},
+	io::{MediaSource, MediaSourceStream},
+	meta::Tag,
+	probe::{Hint, ProbeResult},
 	sample::Sample,
 };
 
 use crate::frame::Frame;
 
-use super::FromFileError;
+use super::{ChannelMapping, FromFileError, UnrecognizedFormatError};
+
+/// Detects the format of the audio and creates a format reader for it.
+///
+/// If the format can't be detected, the first bytes of the audio are
+/// included in the error.
+pub fn probe(
+	mut media_source: Box<dyn MediaSource>,
+	hint: &Hint,
+) -> Result<ProbeResult, FromFileError> {
+	let signature = if media_source.is_seekable() {
+		read_signature(&mut *media_source)
+	} else {
+		vec![]
+	};
+	let mss = MediaSourceStream::new(media_source, Default::default());
+	symphonia::default::get_probe()
+		.format(hint, mss, &Default::default(), &Default::default())
+		.map_err(|error| {
+			FromFileError::UnrecognizedFormat(UnrecognizedFormatError { error, signature })
+		})
+}
+
+/// Reads the first bytes of the audio and seeks back to where the reader
+/// started.
+#[must_use]
+fn read_signature<R: Read + Seek + ?Sized>(reader: &mut R) -> Vec<u8> {
+	let Ok(start) = reader.stream_position() else {
+		return vec![];
+	};
+	let mut signature = Vec::with_capacity(UnrecognizedFormatError::SIGNATURE_LEN);
+	(&mut *reader)
+		.take(UnrecognizedFormatError::SIGNATURE_LEN as u64)
+		.read_to_end(&mut signature)
+		.ok();
+	reader.seek(SeekFrom::Start(start)).ok();
+	signature
+}
 
-pub fn load_frames_from_buffer_ref(buffer: &AudioBufferRef) -> Result<Vec<Frame>, FromFileError> {
+/// Returns the tags stored before the container, like the ID3 tags in MP3
+/// files, followed by the tags stored in the container.
+#[must_use]
+pub fn probed_tags(probed: &mut ProbeResult) -> Vec<Tag> {
+	let mut tags = probed
+		.metadata
+		.get()
+		.and_then(|metadata| metadata.current().map(|revision| revision.tags().to_vec()))
+		.unwrap_or_default();
+	if let Some(revision) = probed.format.metadata().current() {
+		tags.extend_from_slice(revision.tags());
+	}
+	tags
+}
+
+/// The volume multiplier of -3 dB, used when downmixing.
+const MINUS_3_DB: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+pub fn load_frames_from_buffer_ref(
+	buffer: &AudioBufferRef,
+	channel_mapping: ChannelMapping,
+) -> Result<Vec<Frame>, FromFileError> {
 	match buffer {
-		AudioBufferRef::U8(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::U16(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::U24(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::U32(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::S8(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::S16(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::S24(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::S32(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::F32(buffer) => load_frames_from_buffer(buffer),
-		AudioBufferRef::F64(buffer) => load_frames_from_buffer(buffer),
+		AudioBufferRef::U8(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::U16(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::U24(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::U32(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::S8(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::S16(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::S24(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::S32(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::F32(buffer) => load_frames_from_buffer(buffer, channel_mapping),
+		AudioBufferRef::F64(buffer) => load_frames_from_buffer(buffer, channel_mapping),
 	}
 }
 
 pub fn load_frames_from_buffer<S: Sample>(
 	buffer: &AudioBuffer<S>,
+	channel_mapping: ChannelMapping,
 ) -> Result<Vec<Frame>, FromFileError>
 where
 	f32: FromSample<S>,
 {
-	match buffer.spec().channels.count() {
-		1 => Ok(buffer
+	let channels = buffer.spec().channels;
+	match (channel_mapping, channels.count()) {
+		(ChannelMapping::Select { left, right }, num_channels) => {
+			if left >= num_channels || right >= num_channels {
+				return Err(FromFileError::UnsupportedChannelConfiguration);
+			}
+			Ok(buffer
+				.chan(left)
+				.iter()
+				.zip(buffer.chan(right).iter())
+				.map(|(left, right)| Frame::new((*left).into_sample(), (*right).into_sample()))
+				.collect())
+		}
+		(ChannelMapping::Downmix, 1) => Ok(buffer
 			.chan(0)
 			.iter()
 			.map(|sample| Frame::from_mono((*sample).into_sample()))
 			.collect()),
-		2 => Ok(buffer
+		(ChannelMapping::Downmix, 2) => Ok(buffer
 			.chan(0)
 			.iter()
 			.zip(buffer.chan(1).iter())
 			.map(|(left, right)| Frame::new((*left).into_sample(), (*right).into_sample()))
 			.collect()),
-		_ => Err(FromFileError::UnsupportedChannelConfiguration),
+		(ChannelMapping::Downmix, _) => {
+			let gains = channels.iter().map(downmix_gains).collect::<Vec<_>>();
+			let mut frames = vec![Frame::ZERO; buffer.frames()];
+			for (channel, (left_gain, right_gain)) in gains.into_iter().enumerate() {
+				for (frame, sample) in frames.iter_mut().zip(buffer.chan(channel)) {
+					let sample: f32 = (*sample).into_sample();
+					*frame += Frame::new(sample * left_gain, sample * right_gain);
+				}
+			}
+			Ok(frames)
+		}
+	}
+}
+
+/// Returns how much of the channel is mixed into the left and right
+/// channels when downmixing.
+#[must_use]
+fn downmix_gains(channel: Channels) -> (f32, f32) {
+	let left_channels = Channels::REAR_LEFT
+		| Channels::FRONT_LEFT_CENTRE
+		| Channels::SIDE_LEFT
+		| Channels::TOP_FRONT_LEFT
+		| Channels::TOP_REAR_LEFT
+		| Channels::REAR_LEFT_CENTRE
+		| Channels::FRONT_LEFT_WIDE
+		| Channels::FRONT_LEFT_HIGH;
+	let right_channels = Channels::REAR_RIGHT
+		| Channels::FRONT_RIGHT_CENTRE
+		| Channels::SIDE_RIGHT
+		| Channels::TOP_FRONT_RIGHT
+		| Channels::TOP_REAR_RIGHT
+		| Channels::REAR_RIGHT_CENTRE
+		| Channels::FRONT_RIGHT_WIDE
+		| Channels::FRONT_RIGHT_HIGH;
+	if channel == Channels::FRONT_LEFT {
+		(1.0, 0.0)
+	} else if channel == Channels::FRONT_RIGHT {
+		(0.0, 1.0)
+	} else if (Channels::LFE1 | Channels::LFE2).contains(channel) {
+		(0.0, 0.0)
+	} else if left_channels.contains(channel) {
+		(MINUS_3_DB, 0.0)
+	} else if right_channels.contains(channel) {
+		(0.0, MINUS_3_DB)
+	} else {
+		// center channels
+		(MINUS_3_DB, MINUS_3_DB)
+	}
+}
+
+/// Wraps a reader so it can be used as a [`MediaSource`].
+///
+/// [`MediaSource`] requires `Sync`, but the reader is only ever used
+/// through a mutable reference, so the mutex never needs to be locked.
+pub struct ReadSeekSource<R> {
+	reader: Mutex<R>,
+	len: Option<u64>,
+}
+
+impl<R: Read + Seek> ReadSeekSource<R> {
+	pub fn new(mut reader: R) -> Self {
+		let len = reader_len(&mut reader);
+		Self {
+			reader: Mutex::new(reader),
+			len,
+		}
+	}
+}
+
+impl<R> ReadSeekSource<R> {
+	fn reader(&mut self) -> &mut R {
+		self.reader
+			.get_mut()
+			.unwrap_or_else(|error| error.into_inner())
+	}
+}
+
+/// Returns the length of the reader in bytes, or `None` if it can't
+/// be determined.
+fn reader_len(reader: &mut impl Seek) -> Option<u64> {
+	let position = reader.stream_position().ok()?;
+	let len = reader.seek(SeekFrom::End(0)).ok()?;
+	reader.seek(SeekFrom::Start(position)).ok()?;
+	Some(len)
+}
+
+impl<R: Read> Read for ReadSeekSource<R> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		self.reader().read(buf)
+	}
+}
+
+impl<R: Read + Seek> Seek for ReadSeekSource<R> {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		self.reader().seek(pos)
+	}
+}
+
+impl<R: Read + Seek + Send> MediaSource for ReadSeekSource<R> {
+	fn is_seekable(&self) -> bool {
+		true
+	}
+
+	fn byte_len(&self) -> Option<u64> {
+		self.len
+	}
+}
+
+/// Wraps a reader that can't seek, such as a network stream, so it can be
+/// used as a [`MediaSource`].
+pub struct ReadOnlySource<R> {
+	reader: Mutex<R>,
+}
+
+impl<R: Read> ReadOnlySource<R> {
+	pub fn new(reader: R) -> Self {
+		Self {
+			reader: Mutex::new(reader),
+		}
+	}
+}
+
+impl<R: Read> Read for ReadOnlySource<R> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		self.reader
+			.get_mut()
+			.unwrap_or_else(|error| error.into_inner())
+			.read(buf)
+	}
+}
+
+impl<R> Seek for ReadOnlySource<R> {
+	fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
+		Err(std::io::Error::new(
+			std::io::ErrorKind::Unsupported,
+			"the audio source does not support seeking",
+		))
+	}
+}
+
+impl<R: Read + Send> MediaSource for ReadOnlySource<R> {
+	fn is_seekable(&self) -> bool {
+		false
+	}
+
+	fn byte_len(&self) -> Option<u64> {
+		None
 	}
 }
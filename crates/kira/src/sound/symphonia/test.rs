@@ -0,0 +1,75 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use symphonia::core::audio::{AudioBuffer, Channels, Signal, SignalSpec};
+
+use crate::{
+	frame::Frame,
+	sound::{ChannelMapping, FromFileError},
+};
+
+use super::load_frames_from_buffer;
+
+/// Creates a one frame buffer with the given sample on each channel.
+fn buffer(channels: Channels, samples: &[f32]) -> AudioBuffer<f32> {
+	let mut buffer = AudioBuffer::new(1, SignalSpec::new(44100, channels));
+	buffer.render_reserved(Some(1));
+	for (channel, sample) in samples.iter().enumerate() {
+		buffer.chan_mut(channel)[0] = *sample;
+	}
+	buffer
+}
+
+/// Tests that 5.1 audio is downmixed into stereo audio.
+#[test]
+fn downmixes_surround_audio() {
+	let buffer = buffer(
+		Channels::FRONT_LEFT
+			| Channels::FRONT_RIGHT
+			| Channels::FRONT_CENTRE
+			| Channels::LFE1
+			| Channels::REAR_LEFT
+			| Channels::REAR_RIGHT,
+		&[0.1, 0.2, 0.3, 0.4, 0.5, 0.6],
+	);
+	let frames = load_frames_from_buffer(&buffer, ChannelMapping::Downmix).unwrap();
+	assert_eq!(frames.len(), 1);
+	let expected = Frame::new(
+		0.1 + (0.3 + 0.5) * FRAC_1_SQRT_2,
+		0.2 + (0.3 + 0.6) * FRAC_1_SQRT_2,
+	);
+	assert!((frames[0].left - expected.left).abs() < 1.0e-6);
+	assert!((frames[0].right - expected.right).abs() < 1.0e-6);
+}
+
+/// Tests that mono and stereo audio is not changed by downmixing.
+#[test]
+fn downmix_keeps_mono_and_stereo_audio() {
+	let mono = buffer(Channels::FRONT_CENTRE, &[0.5]);
+	assert_eq!(
+		load_frames_from_buffer(&mono, ChannelMapping::Downmix).unwrap(),
+		vec![Frame::from_mono(0.5)]
+	);
+	let stereo = buffer(Channels::FRONT_LEFT | Channels::FRONT_RIGHT, &[0.1, 0.2]);
+	assert_eq!(
+		load_frames_from_buffer(&stereo, ChannelMapping::Downmix).unwrap(),
+		vec![Frame::new(0.1, 0.2)]
+	);
+}
+
+/// Tests that the selected channels are used as the left and right
+/// channels.
+#[test]
+fn selects_channels() {
+	let buffer = buffer(
+		Channels::FRONT_LEFT | Channels::FRONT_RIGHT | Channels::REAR_LEFT | Channels::REAR_RIGHT,
+		&[0.1, 0.2, 0.3, 0.4],
+	);
+	assert_eq!(
+		load_frames_from_buffer(&buffer, ChannelMapping::Select { left: 3, right: 2 }).unwrap(),
+		vec![Frame::new(0.4, 0.3)]
+	);
+	assert!(matches!(
+		load_frames_from_buffer(&buffer, ChannelMapping::Select { left: 0, right: 4 }),
+		Err(FromFileError::UnsupportedChannelConfiguration)
+	));
+}
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use super::{EndPosition, PlaybackPosition, Region};
+
+/// Tests that a region created from a start and duration ends the
+/// right number of samples after the start.
+#[test]
+fn from_start_and_duration() {
+	let region = Region::from((PlaybackPosition::Samples(3), Duration::from_secs(2)));
+	assert_eq!(
+		region,
+		Region {
+			start: PlaybackPosition::Samples(3),
+			end: EndPosition::AfterStart(Duration::from_secs(2)),
+		}
+	);
+	assert_eq!(region.len_in_samples(10), Some(20));
+	assert_eq!(region.end_in_samples(10), Some(23));
+	assert_eq!(Region::from(0.5..).len_in_samples(10), None);
+}
+
+/// Tests that empty and inverted regions are reported as invalid.
+#[test]
+fn validation() {
+	const SAMPLE_RATE: u32 = 10;
+	let valid = Region::from(0.5..1.0);
+	assert!(valid.is_valid(SAMPLE_RATE));
+	assert!(Region::from(1.0..).is_valid(SAMPLE_RATE));
+
+	let empty = Region::from_start_and_duration(1.0, Duration::ZERO);
+	assert!(empty.is_empty(SAMPLE_RATE));
+	assert!(!empty.is_inverted(SAMPLE_RATE));
+	assert!(!empty.is_valid(SAMPLE_RATE));
+	assert_eq!(empty.len_in_samples(SAMPLE_RATE), Some(0));
+
+	// regions in different units are compared in samples
+	let inverted = Region {
+		start: PlaybackPosition::Samples(20),
+		end: EndPosition::Custom(PlaybackPosition::Seconds(1.0)),
+	};
+	assert!(inverted.is_inverted(SAMPLE_RATE));
+	assert!(!inverted.is_empty(SAMPLE_RATE));
+	assert!(!inverted.is_valid(SAMPLE_RATE));
+	assert_eq!(inverted.len_in_samples(SAMPLE_RATE), Some(0));
+
+	let negative = Region::from(-1.0..1.0);
+	assert!(!negative.is_valid(SAMPLE_RATE));
+}
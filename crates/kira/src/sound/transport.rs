@@ -1,4 +1,6 @@
-use super::{EndPosition, Region};
+use crate::{Decibels, Easing, Tween, Tweenable};
+
+use super::{LoopMode, Region};
 
 #[cfg(test)]
 mod test;
@@ -8,6 +10,21 @@ pub struct Transport {
 	/// The start and end frames of the sound that should be looped. The upper bound
 	/// is *exclusive*.
 	pub loop_region: Option<(usize, usize)>,
+	/// How many more times playback should jump back to the start of the
+	/// loop region, or `None` to loop indefinitely.
+	pub remaining_loops: Option<u32>,
+	pub loop_mode: LoopMode,
+	/// Whether playback has bounced off the edge of the loop region and
+	/// is travelling in the opposite direction. Only used for
+	/// [`LoopMode::PingPong`].
+	pub reflected: bool,
+	/// The requested length of the crossfade between the end and the
+	/// start of the loop region (in frames). Only used for
+	/// [`LoopMode::Forward`].
+	pub loop_crossfade_frames: usize,
+	/// How many times playback has wrapped around or bounced off the
+	/// edge of the loop region.
+	pub loops_completed: u64,
 	pub playing: bool,
 }
 
@@ -20,22 +37,27 @@ impl Transport {
 		sample_rate: u32,
 		num_frames: usize,
 	) -> Self {
-		let loop_region = loop_region.map(|loop_region| {
-			let loop_start = loop_region.start.into_samples(sample_rate);
-			let loop_end = match loop_region.end {
-				EndPosition::EndOfAudio => num_frames,
-				EndPosition::Custom(end_position) => end_position.into_samples(sample_rate),
-			};
-			(loop_start, loop_end)
-		});
+		let loop_region = loop_region
+			.and_then(|loop_region| loop_region_in_frames(loop_region, sample_rate, num_frames));
+		// when playing in reverse, the start position is measured from the
+		// end of the sound
+		let (position, playing) = if reverse {
+			match num_frames.checked_sub(start_position + 1) {
+				Some(position) => (position, true),
+				None => (0, false),
+			}
+		} else {
+			(start_position, true)
+		};
 		Self {
-			position: if reverse {
-				num_frames - 1 - start_position
-			} else {
-				start_position
-			},
+			position,
 			loop_region,
-			playing: true,
+			remaining_loops: None,
+			loop_mode: LoopMode::Forward,
+			reflected: false,
+			loop_crossfade_frames: 0,
+			loops_completed: 0,
+			playing,
 		}
 	}
 
@@ -45,14 +67,87 @@ impl Transport {
 		sample_rate: u32,
 		num_frames: usize,
 	) {
-		self.loop_region = loop_region.map(|loop_region| {
-			let loop_start = loop_region.start.into_samples(sample_rate);
-			let loop_end = match loop_region.end {
-				EndPosition::EndOfAudio => num_frames,
-				EndPosition::Custom(end_position) => end_position.into_samples(sample_rate),
-			};
-			(loop_start, loop_end)
-		});
+		self.loop_region = loop_region
+			.and_then(|loop_region| loop_region_in_frames(loop_region, sample_rate, num_frames));
+		// without a loop region, there's nothing to bounce between, so
+		// playback should continue in its original direction
+		if self.loop_region.is_none() {
+			self.reflected = false;
+		}
+	}
+
+	/// Sets how many times the loop region should play, counting the pass
+	/// that's currently in progress (or upcoming, if playback hasn't
+	/// reached the loop region yet).
+	pub fn set_loop_count(&mut self, loop_count: Option<u32>) {
+		self.remaining_loops = loop_count.map(|loop_count| loop_count.saturating_sub(1));
+	}
+
+	/// Returns the length of the loop crossfade (in frames), clamped so
+	/// the crossfaded portions at the start and end of the loop region
+	/// don't overlap.
+	#[must_use]
+	fn crossfade_frames(&self, loop_start: usize, loop_end: usize) -> usize {
+		if self.loop_mode != LoopMode::Forward {
+			return 0;
+		}
+		self.loop_crossfade_frames.min((loop_end - loop_start) / 2)
+	}
+
+	/**
+	If the current position is inside a loop crossfade, returns the index of
+	the frame that should be blended with the current frame and how much of
+	that frame should be mixed in (from `0.0` to `1.0`).
+
+	When playing forward, the end of the loop region fades into the start, and
+	playback jumps to just after the crossfaded portion at the start when
+	wrapping. When playing backward, the same happens in the opposite direction.
+	*/
+	#[must_use]
+	pub fn loop_crossfade(&self, backwards: bool) -> Option<(usize, f32)> {
+		if self.remaining_loops == Some(0) {
+			return None;
+		}
+		let (loop_start, loop_end) = self.loop_region?;
+		let crossfade_frames = self.crossfade_frames(loop_start, loop_end);
+		if crossfade_frames == 0 {
+			return None;
+		}
+		if backwards {
+			if self.position < loop_start || self.position >= loop_start + crossfade_frames {
+				return None;
+			}
+			let progress = self.position - loop_start;
+			Some((
+				loop_end - crossfade_frames + progress,
+				(crossfade_frames - 1 - progress) as f32 / crossfade_frames as f32,
+			))
+		} else {
+			if self.position < loop_end - crossfade_frames || self.position >= loop_end {
+				return None;
+			}
+			let progress = self.position - (loop_end - crossfade_frames);
+			Some((
+				loop_start + progress,
+				progress as f32 / crossfade_frames as f32,
+			))
+		}
+	}
+
+	/// Whether playback should bounce off the edge of the loop region in
+	/// [`LoopMode::PingPong`]. Once all of the loops have been played,
+	/// playback keeps bouncing until it's travelling in its original
+	/// direction again, so it can leave the loop region through the end.
+	#[must_use]
+	fn should_bounce(&self) -> bool {
+		self.remaining_loops != Some(0) || self.reflected
+	}
+
+	fn consume_loop(&mut self) {
+		self.loops_completed += 1;
+		if let Some(remaining_loops) = &mut self.remaining_loops {
+			*remaining_loops = remaining_loops.saturating_sub(1);
+		}
 	}
 
 	pub fn increment_position(&mut self, num_frames: usize) {
@@ -61,8 +156,22 @@ impl Transport {
 		}
 		self.position += 1;
 		if let Some((loop_start, loop_end)) = self.loop_region {
-			while self.position >= loop_end {
-				self.position -= loop_end - loop_start;
+			if self.loop_mode == LoopMode::PingPong {
+				if self.position >= loop_end && self.should_bounce() {
+					// mirror around the last frame of the loop region so
+					// that frame isn't played twice
+					self.position = loop_end.saturating_sub(2).max(loop_start);
+					self.reflected = !self.reflected;
+					self.consume_loop();
+				}
+			} else {
+				// skip over the start of the loop region if it was already
+				// blended into the end of the loop region
+				let crossfade_frames = self.crossfade_frames(loop_start, loop_end);
+				while self.position >= loop_end && self.remaining_loops != Some(0) {
+					self.position -= loop_end - loop_start - crossfade_frames;
+					self.consume_loop();
+				}
 			}
 		}
 		if self.position >= num_frames {
@@ -75,8 +184,21 @@ impl Transport {
 			return;
 		}
 		if let Some((loop_start, loop_end)) = self.loop_region {
-			while self.position <= loop_start {
-				self.position += loop_end - loop_start;
+			if self.loop_mode == LoopMode::PingPong {
+				if self.position <= loop_start && self.should_bounce() {
+					// mirror around the first frame of the loop region so
+					// that frame isn't played twice
+					self.position = (loop_start + 1).min(loop_end - 1);
+					self.reflected = !self.reflected;
+					self.consume_loop();
+					return;
+				}
+			} else {
+				let crossfade_frames = self.crossfade_frames(loop_start, loop_end);
+				while self.position <= loop_start && self.remaining_loops != Some(0) {
+					self.position += loop_end - loop_start - crossfade_frames;
+					self.consume_loop();
+				}
 			}
 		}
 		if self.position == 0 {
@@ -86,8 +208,88 @@ impl Transport {
 		}
 	}
 
+	/**
+	Moves the transport to a different range of the underlying audio.
+
+	The position and loop region are measured from the start of the range,
+	so they're shifted to stay at the same points in the audio, and the
+	loop region is clamped to the new range. If playback has already
+	passed the end of the new range in the direction it's travelling,
+	playback ends. If it hasn't reached the new range yet, it jumps to
+	the start of the range.
+	*/
+	pub fn rebase(
+		&mut self,
+		old_start: usize,
+		new_start: usize,
+		num_frames: usize,
+		backwards: bool,
+	) {
+		let rebase_index = |index: usize| {
+			(index + old_start)
+				.saturating_sub(new_start)
+				.min(num_frames)
+		};
+		self.loop_region = self
+			.loop_region
+			.map(|(loop_start, loop_end)| (rebase_index(loop_start), rebase_index(loop_end)))
+			.filter(|(loop_start, loop_end)| loop_end > loop_start);
+		if self.loop_region.is_none() {
+			self.reflected = false;
+		}
+		if !self.playing {
+			return;
+		}
+		let position = self.position + old_start;
+		if backwards {
+			if position < new_start {
+				self.position = 0;
+				self.playing = false;
+			} else {
+				self.position = (position - new_start).min(num_frames.saturating_sub(1));
+				if num_frames == 0 {
+					self.playing = false;
+				}
+			}
+		} else {
+			self.position = position.saturating_sub(new_start);
+			if self.position >= num_frames {
+				self.playing = false;
+			}
+		}
+	}
+
+	/// Returns how many frames are left to play before playback reaches the
+	/// end of the sound, counting the current frame, or `None` if playback
+	/// will loop before reaching the end.
+	#[must_use]
+	pub fn frames_until_end(&self, backwards: bool, num_frames: usize) -> Option<usize> {
+		if let Some((loop_start, loop_end)) = self
+			.loop_region
+			.filter(|_| self.remaining_loops != Some(0) || self.reflected)
+		{
+			let will_loop = if backwards {
+				self.position >= loop_start
+			} else {
+				self.position < loop_end
+			};
+			if will_loop {
+				return None;
+			}
+		}
+		Some(if backwards {
+			self.position + 1
+		} else {
+			num_frames.saturating_sub(self.position)
+		})
+	}
+
 	pub fn seek_to(&mut self, mut position: usize, num_frames: usize) {
-		if let Some((loop_start, loop_end)) = self.loop_region {
+		// once all of the loops have been played, seeking shouldn't wrap
+		// back into the loop region
+		if let Some((loop_start, loop_end)) =
+			self.loop_region.filter(|_| self.remaining_loops != Some(0))
+		{
 			if position > self.position {
 				while position >= loop_end {
 					position -= loop_end - loop_start;
@@ -104,3 +306,52 @@ impl Transport {
 		}
 	}
 }
+
+/// Converts a loop region to frame indices. Empty loop regions can't be
+/// looped, so they're treated as no loop region at all.
+#[must_use]
+fn loop_region_in_frames(
+	loop_region: Region,
+	sample_rate: u32,
+	num_frames: usize,
+) -> Option<(usize, usize)> {
+	let loop_start = loop_region.start.into_samples(sample_rate);
+	let loop_end = loop_region
+		.end_in_samples(sample_rate)
+		.unwrap_or(num_frames);
+	(loop_end > loop_start).then_some((loop_start, loop_end))
+}
+
+/// A fade to silence over the last frames before the end of a sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FadeOut {
+	frames: usize,
+	easing: Easing,
+}
+
+impl FadeOut {
+	/// Creates a fade-out with the duration and easing of a tween.
+	/// The tween's start time is ignored.
+	#[must_use]
+	pub fn new(tween: Tween, sample_rate: u32) -> Self {
+		Self {
+			frames: (tween.duration.as_secs_f64() * sample_rate as f64).round() as usize,
+			easing: tween.easing,
+		}
+	}
+
+	/// Returns the volume of a frame given how many frames are left to play,
+	/// counting that frame. The last frame is silent.
+	#[must_use]
+	pub fn volume(self, frames_until_end: usize) -> Decibels {
+		if frames_until_end > self.frames {
+			return Decibels::IDENTITY;
+		}
+		let progress = 1.0 - frames_until_end.saturating_sub(1) as f64 / self.frames as f64;
+		Decibels::interpolate(
+			Decibels::IDENTITY,
+			Decibels::SILENCE,
+			self.easing.apply(progress),
+		)
+	}
+}
@@ -1,10 +1,19 @@
-use super::Transport;
+use std::time::Duration;
+
+use crate::{sound::LoopMode, Decibels, Tween};
+
+use super::{FadeOut, Transport};
 
 #[test]
 fn stops_at_end() {
 	let mut transport = Transport {
 		position: 2,
 		loop_region: None,
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
 		playing: true,
 	};
 	for i in 2..4 {
@@ -21,6 +30,11 @@ fn stops_at_start_when_playing_backwards() {
 	let mut transport = Transport {
 		position: 2,
 		loop_region: None,
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
 		playing: true,
 	};
 	for i in (0..=2).rev() {
@@ -37,6 +51,11 @@ fn loops() {
 	let mut transport = Transport {
 		position: 0,
 		loop_region: Some((2, 5)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
 		playing: true,
 	};
 	for i in 0..5 {
@@ -56,6 +75,11 @@ fn loops_when_playing_backward() {
 	let mut transport = Transport {
 		position: 0,
 		loop_region: Some((2, 5)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
 		playing: true,
 	};
 	transport.position = 10;
@@ -76,6 +100,11 @@ fn loop_wrapping() {
 	let mut transport = Transport {
 		position: 0,
 		loop_region: Some((2, 5)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
 		playing: true,
 	};
 	transport.position = 6;
@@ -91,6 +120,11 @@ fn seek_loop_wrapping() {
 	let mut transport = Transport {
 		position: 0,
 		loop_region: Some((2, 5)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
 		playing: true,
 	};
 	transport.seek_to(7, 10);
@@ -105,3 +139,263 @@ fn seek_out_of_bounds() {
 	transport.seek_to(10, 10);
 	assert!(!transport.playing);
 }
+
+#[test]
+fn reverse_start_position() {
+	let transport = Transport::new(2, None, true, 1, 10);
+	assert_eq!(transport.position, 7);
+	assert!(transport.playing);
+}
+
+#[test]
+fn reverse_out_of_bounds_start_position() {
+	let transport = Transport::new(10, None, true, 1, 10);
+	assert!(!transport.playing);
+}
+
+#[test]
+fn ignores_empty_and_reversed_loop_regions() {
+	for loop_region in [3.0..3.0, 5.0..2.0] {
+		let mut transport = Transport::new(0, Some(loop_region.into()), false, 1, 10);
+		assert_eq!(transport.loop_region, None);
+		for _ in 0..10 {
+			transport.increment_position(10);
+		}
+		assert!(!transport.playing);
+	}
+}
+
+#[test]
+fn loop_count() {
+	let mut transport = Transport {
+		position: 0,
+		loop_region: Some((2, 4)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
+		playing: true,
+	};
+	transport.set_loop_count(Some(2));
+	for i in [1, 2, 3, 2, 3, 4] {
+		transport.increment_position(5);
+		assert_eq!(transport.position, i);
+		assert!(transport.playing);
+	}
+	assert_eq!(transport.remaining_loops, Some(0));
+	transport.increment_position(5);
+	assert!(!transport.playing);
+}
+
+#[test]
+fn loop_count_backward() {
+	let mut transport = Transport {
+		position: 4,
+		loop_region: Some((2, 4)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
+		playing: true,
+	};
+	transport.set_loop_count(Some(2));
+	for i in [3, 2, 3, 2, 1, 0] {
+		transport.decrement_position();
+		assert_eq!(transport.position, i);
+		assert!(transport.playing);
+	}
+	transport.decrement_position();
+	assert!(!transport.playing);
+}
+
+#[test]
+fn ping_pong() {
+	let mut transport = Transport {
+		position: 0,
+		loop_region: Some((2, 5)),
+		remaining_loops: None,
+		loop_mode: LoopMode::PingPong,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
+		playing: true,
+	};
+	for i in [1, 2, 3, 4, 3] {
+		transport.increment_position(10);
+		assert_eq!(transport.position, i);
+	}
+	assert!(transport.reflected);
+	for i in [2, 3] {
+		transport.decrement_position();
+		assert_eq!(transport.position, i);
+	}
+	assert!(!transport.reflected);
+}
+
+#[test]
+fn ping_pong_with_even_loop_count() {
+	let mut transport = Transport {
+		position: 0,
+		loop_region: Some((3, 6)),
+		remaining_loops: None,
+		loop_mode: LoopMode::PingPong,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
+		playing: true,
+	};
+	transport.set_loop_count(Some(2));
+	let mut positions = vec![transport.position];
+	while transport.playing {
+		if transport.reflected {
+			transport.decrement_position();
+		} else {
+			transport.increment_position(8);
+		}
+		positions.push(transport.position);
+	}
+	// playback bounces back towards the end of the loop region after the
+	// last loop so it reaches the rest of the sound
+	assert_eq!(positions, vec![0, 1, 2, 3, 4, 5, 4, 3, 4, 5, 6, 7, 8]);
+	assert!(!transport.reflected);
+}
+
+#[test]
+fn loop_crossfade() {
+	let mut transport = Transport {
+		position: 0,
+		loop_region: Some((2, 8)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 2,
+		loops_completed: 0,
+		playing: true,
+	};
+	let mut crossfades = vec![];
+	for _ in 0..12 {
+		crossfades.push(transport.loop_crossfade(false));
+		transport.increment_position(10);
+	}
+	assert_eq!(
+		crossfades,
+		vec![
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some((2, 0.0)),
+			Some((3, 0.5)),
+			None,
+			None,
+			Some((2, 0.0)),
+			Some((3, 0.5)),
+		]
+	);
+	assert_eq!(transport.position, 4);
+}
+
+#[test]
+fn loop_crossfade_clamps_to_half_of_loop_region() {
+	let transport = Transport {
+		position: 3,
+		loop_region: Some((2, 6)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 10,
+		loops_completed: 0,
+		playing: true,
+	};
+	assert_eq!(transport.loop_crossfade(false), None);
+	assert_eq!(transport.loop_crossfade(true), Some((5, 0.0)));
+}
+
+#[test]
+fn frames_until_end() {
+	let mut transport = Transport {
+		position: 3,
+		loop_region: Some((2, 6)),
+		remaining_loops: Some(1),
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
+		playing: true,
+	};
+	assert_eq!(transport.frames_until_end(false, 10), None);
+	assert_eq!(transport.frames_until_end(true, 10), None);
+	transport.remaining_loops = Some(0);
+	assert_eq!(transport.frames_until_end(false, 10), Some(7));
+	assert_eq!(transport.frames_until_end(true, 10), Some(4));
+	transport.remaining_loops = None;
+	transport.position = 7;
+	assert_eq!(transport.frames_until_end(false, 10), Some(3));
+	transport.position = 1;
+	assert_eq!(transport.frames_until_end(true, 10), Some(2));
+}
+
+#[test]
+fn fade_out_volume() {
+	let fade_out = FadeOut::new(
+		Tween {
+			duration: Duration::from_secs(4),
+			..Default::default()
+		},
+		1,
+	);
+	assert_eq!(fade_out.volume(5), Decibels::IDENTITY);
+	assert_eq!(fade_out.volume(4), Decibels(-15.0));
+	assert_eq!(fade_out.volume(2), Decibels(-45.0));
+	assert_eq!(fade_out.volume(1), Decibels::SILENCE);
+}
+
+#[test]
+fn rebase() {
+	let mut transport = Transport {
+		position: 3,
+		loop_region: Some((2, 6)),
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
+		playing: true,
+	};
+	transport.rebase(0, 2, 3, false);
+	assert_eq!(transport.position, 1);
+	assert_eq!(transport.loop_region, Some((0, 3)));
+	assert!(transport.playing);
+	transport.rebase(2, 0, 10, false);
+	assert_eq!(transport.position, 3);
+	assert_eq!(transport.loop_region, Some((2, 5)));
+	transport.rebase(0, 5, 5, false);
+	assert_eq!(transport.position, 0);
+	assert_eq!(transport.loop_region, None);
+	assert!(transport.playing);
+	transport.rebase(5, 0, 2, false);
+	assert!(!transport.playing);
+}
+
+#[test]
+fn rebase_when_playing_backwards() {
+	let mut transport = Transport {
+		position: 3,
+		loop_region: None,
+		remaining_loops: None,
+		loop_mode: LoopMode::Forward,
+		reflected: false,
+		loop_crossfade_frames: 0,
+		loops_completed: 0,
+		playing: true,
+	};
+	transport.rebase(0, 0, 2, true);
+	assert_eq!(transport.position, 1);
+	assert!(transport.playing);
+	transport.rebase(0, 4, 2, true);
+	assert!(!transport.playing);
+}
@@ -0,0 +1,66 @@
+#[cfg(not(feature = "symphonia"))]
+mod decoder;
+
+use std::fmt::Display;
+
+#[cfg(not(feature = "symphonia"))]
+pub(crate) use decoder::*;
+
+/// Errors that can occur when reading a WAV file without the `symphonia`
+/// feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WavError {
+	/// The file doesn't have a `fmt ` chunk before its `data` chunk.
+	MissingFormatChunk,
+	/// The file doesn't have a `data` chunk.
+	MissingDataChunk,
+	/// The `fmt ` chunk is too short or describes audio that can't exist,
+	/// like audio with no channels.
+	InvalidFormatChunk,
+	/// The audio is stored in an encoding that isn't supported. 16, 24, and
+	/// 32-bit integer audio and 32-bit floating point audio are supported.
+	UnsupportedEncoding {
+		/// The format tag of the audio, which is `1` for integer audio and
+		/// `3` for floating point audio.
+		format_tag: u16,
+		/// The number of bits in each sample.
+		bits_per_sample: u16,
+	},
+	/// A chunk claims to be longer than the rest of the file.
+	ChunkTooLong {
+		/// The ID of the chunk, like `*b"fmt "`.
+		id: [u8; 4],
+		/// The length the chunk claims to be (in bytes).
+		len: u32,
+	},
+}
+
+impl Display for WavError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			WavError::MissingFormatChunk => {
+				f.write_str("The WAV file does not have a `fmt ` chunk before its audio")
+			}
+			WavError::MissingDataChunk => f.write_str("The WAV file does not have a `data` chunk"),
+			WavError::InvalidFormatChunk => {
+				f.write_str("The `fmt ` chunk of the WAV file is invalid")
+			}
+			WavError::UnsupportedEncoding {
+				format_tag,
+				bits_per_sample,
+			} => write!(
+				f,
+				"WAV files with format tag {} and {} bits per sample are not supported",
+				format_tag, bits_per_sample
+			),
+			WavError::ChunkTooLong { id, len } => write!(
+				f,
+				"The `{}` chunk of the WAV file claims to be {} bytes long, which is longer than the rest of the file",
+				String::from_utf8_lossy(id),
+				len
+			),
+		}
+	}
+}
+
+impl std::error::Error for WavError {}
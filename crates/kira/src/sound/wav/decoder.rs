@@ -0,0 +1,243 @@
+#[cfg(test)]
+mod test;
+
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::{
+	frame::Frame,
+	sound::{streaming::Decoder, FromFileError, UnrecognizedFormatError},
+};
+
+use super::WavError;
+
+/// The format tag for integer PCM audio.
+const FORMAT_PCM: u16 = 1;
+/// The format tag for floating point audio.
+const FORMAT_IEEE_FLOAT: u16 = 3;
+/// The format tag for a `fmt ` chunk whose actual format tag is stored
+/// in its sub-format.
+const FORMAT_EXTENSIBLE: u16 = 0xfffe;
+/// How many frames are decoded at a time.
+const DECODE_CHUNK_FRAMES: usize = 1024;
+
+/// A reader that the WAV decoder can read audio from.
+pub(crate) trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// How each sample of the audio is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+	Int16,
+	Int24,
+	Int32,
+	Float32,
+}
+
+impl Encoding {
+	fn new(format_tag: u16, bits_per_sample: u16) -> Result<Self, WavError> {
+		match (format_tag, bits_per_sample) {
+			(FORMAT_PCM, 16) => Ok(Self::Int16),
+			(FORMAT_PCM, 24) => Ok(Self::Int24),
+			(FORMAT_PCM, 32) => Ok(Self::Int32),
+			(FORMAT_IEEE_FLOAT, 32) => Ok(Self::Float32),
+			_ => Err(WavError::UnsupportedEncoding {
+				format_tag,
+				bits_per_sample,
+			}),
+		}
+	}
+
+	#[must_use]
+	fn bytes_per_sample(self) -> usize {
+		match self {
+			Self::Int16 => 2,
+			Self::Int24 => 3,
+			Self::Int32 | Self::Float32 => 4,
+		}
+	}
+
+	#[must_use]
+	fn read_sample(self, bytes: &[u8]) -> f32 {
+		match self {
+			Self::Int16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32768.0,
+			// the sample is shifted into the top of an i32 to keep its sign
+			Self::Int24 => {
+				(i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8) as f32 / 8388608.0
+			}
+			Self::Int32 => {
+				i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32 / 2147483648.0
+			}
+			Self::Float32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+		}
+	}
+}
+
+/// The information in a `fmt ` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Format {
+	num_channels: u16,
+	sample_rate: u32,
+	encoding: Encoding,
+}
+
+impl Format {
+	fn parse(chunk: &[u8]) -> Result<Self, WavError> {
+		if chunk.len() < 16 {
+			return Err(WavError::InvalidFormatChunk);
+		}
+		let read_u16 = |offset: usize| u16::from_le_bytes([chunk[offset], chunk[offset + 1]]);
+		let mut format_tag = read_u16(0);
+		let num_channels = read_u16(2);
+		let sample_rate = u32::from_le_bytes(chunk[4..8].try_into().unwrap());
+		let block_align = read_u16(12);
+		let bits_per_sample = read_u16(14);
+		if format_tag == FORMAT_EXTENSIBLE {
+			// the sub-format is a guid starting with the actual format tag
+			if chunk.len() < 40 {
+				return Err(WavError::InvalidFormatChunk);
+			}
+			format_tag = read_u16(24);
+		}
+		let encoding = Encoding::new(format_tag, bits_per_sample)?;
+		if num_channels == 0
+			|| sample_rate == 0
+			|| block_align as usize != num_channels as usize * encoding.bytes_per_sample()
+		{
+			return Err(WavError::InvalidFormatChunk);
+		}
+		Ok(Self {
+			num_channels,
+			sample_rate,
+			encoding,
+		})
+	}
+
+	#[must_use]
+	fn frame_len(self) -> usize {
+		self.num_channels as usize * self.encoding.bytes_per_sample()
+	}
+}
+
+/// Decodes WAV files without Symphonia.
+///
+/// Audio with more than two channels is played using its first two
+/// channels.
+pub(crate) struct WavDecoder {
+	reader: Box<dyn ReadSeek>,
+	format: Format,
+	/// Where the audio starts in the reader (in bytes).
+	data_start: u64,
+	num_frames: usize,
+	current_frame_index: usize,
+}
+
+impl WavDecoder {
+	pub(crate) fn new(mut reader: Box<dyn ReadSeek>) -> Result<Self, FromFileError> {
+		let start = reader.stream_position()?;
+		let end = reader.seek(SeekFrom::End(0))?;
+		reader.seek(SeekFrom::Start(start))?;
+		let mut signature = Vec::with_capacity(UnrecognizedFormatError::SIGNATURE_LEN);
+		(&mut reader)
+			.take(UnrecognizedFormatError::SIGNATURE_LEN as u64)
+			.read_to_end(&mut signature)?;
+		if !signature.starts_with(b"RIFF") || signature.get(8..12) != Some(b"WAVE") {
+			return Err(FromFileError::UnrecognizedFormat(UnrecognizedFormatError {
+				signature,
+			}));
+		}
+		let mut format = None;
+		let mut chunk_start = start + 12;
+		loop {
+			if end.saturating_sub(chunk_start) < 8 {
+				return Err(FromFileError::InvalidWav(WavError::MissingDataChunk));
+			}
+			reader.seek(SeekFrom::Start(chunk_start))?;
+			let mut chunk_header = [0; 8];
+			reader.read_exact(&mut chunk_header)?;
+			let id: [u8; 4] = chunk_header[..4].try_into().unwrap();
+			let len = u32::from_le_bytes(chunk_header[4..].try_into().unwrap());
+			let body_start = chunk_start + 8;
+			let remaining = end - body_start;
+			if &id == b"data" {
+				let format: Format =
+					format.ok_or(FromFileError::InvalidWav(WavError::MissingFormatChunk))?;
+				// files that were cut off, or written by programs that
+				// didn't know the length of the audio ahead of time, claim
+				// to have more audio than they do, so the audio is read
+				// until the end of the file
+				let len = u64::from(len).min(remaining);
+				return Ok(Self {
+					reader,
+					format,
+					data_start: body_start,
+					num_frames: (len / format.frame_len() as u64) as usize,
+					current_frame_index: 0,
+				});
+			}
+			if u64::from(len) > remaining {
+				return Err(FromFileError::InvalidWav(WavError::ChunkTooLong {
+					id,
+					len,
+				}));
+			}
+			if &id == b"fmt " {
+				// only the first 40 bytes are needed, even for extensible
+				// formats
+				let mut chunk = vec![0; (len as usize).min(40)];
+				reader.read_exact(&mut chunk)?;
+				format = Some(Format::parse(&chunk).map_err(FromFileError::InvalidWav)?);
+			}
+			// chunks are padded to an even length
+			chunk_start = body_start + u64::from(len) + u64::from(len % 2);
+		}
+	}
+}
+
+impl Decoder for WavDecoder {
+	type Error = FromFileError;
+
+	fn sample_rate(&self) -> u32 {
+		self.format.sample_rate
+	}
+
+	fn num_frames(&self) -> usize {
+		self.num_frames
+	}
+
+	fn num_channels(&self) -> u16 {
+		self.format.num_channels
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		let num_frames = DECODE_CHUNK_FRAMES.min(self.num_frames - self.current_frame_index);
+		let frame_len = self.format.frame_len();
+		let mut bytes = vec![0; num_frames * frame_len];
+		self.reader.read_exact(&mut bytes)?;
+		self.current_frame_index += num_frames;
+		let encoding = self.format.encoding;
+		let sample_len = encoding.bytes_per_sample();
+		Ok(bytes
+			.chunks_exact(frame_len)
+			.map(|frame| {
+				let left = encoding.read_sample(frame);
+				if self.format.num_channels == 1 {
+					Frame::from_mono(left)
+				} else {
+					Frame::new(left, encoding.read_sample(&frame[sample_len..]))
+				}
+			})
+			.collect())
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		// every frame is the same length, so any frame can be seeked to
+		// directly
+		let index = index.min(self.num_frames);
+		self.reader.seek(SeekFrom::Start(
+			self.data_start + (index * self.format.frame_len()) as u64,
+		))?;
+		self.current_frame_index = index;
+		Ok(index)
+	}
+}
@@ -0,0 +1,195 @@
+use std::io::Cursor;
+
+use crate::{
+	frame::Frame,
+	sound::{static_sound::StaticSoundData, streaming::Decoder, wav::WavError, FromFileError},
+	test_helpers::wav_fixture,
+};
+
+use super::WavDecoder;
+
+/// Builds a WAV file with the given `fmt ` chunk and audio data.
+fn wav_file(fmt: &[u8], data: &[u8]) -> Vec<u8> {
+	let mut wav = b"RIFF".to_vec();
+	wav.extend_from_slice(&(4 + 8 + fmt.len() as u32 + 8 + data.len() as u32).to_le_bytes());
+	wav.extend_from_slice(b"WAVEfmt ");
+	wav.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+	wav.extend_from_slice(fmt);
+	wav.extend_from_slice(b"data");
+	wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+	wav.extend_from_slice(data);
+	wav
+}
+
+/// Builds a 16 byte `fmt ` chunk.
+fn fmt_chunk(format_tag: u16, num_channels: u16, bits_per_sample: u16) -> Vec<u8> {
+	let block_align = num_channels * bits_per_sample / 8;
+	let mut fmt = vec![];
+	fmt.extend_from_slice(&format_tag.to_le_bytes());
+	fmt.extend_from_slice(&num_channels.to_le_bytes());
+	fmt.extend_from_slice(&44_100u32.to_le_bytes());
+	fmt.extend_from_slice(&(44_100 * block_align as u32).to_le_bytes());
+	fmt.extend_from_slice(&block_align.to_le_bytes());
+	fmt.extend_from_slice(&bits_per_sample.to_le_bytes());
+	fmt
+}
+
+fn new_decoder(wav: Vec<u8>) -> Result<WavDecoder, FromFileError> {
+	WavDecoder::new(Box::new(Cursor::new(wav)))
+}
+
+/// Tests that 16-bit mono audio is decoded.
+#[test]
+fn decodes_16_bit_mono_audio() {
+	let mut decoder = new_decoder(wav_fixture(44_100, &[0, 16384, -32768])).unwrap();
+	assert_eq!(decoder.sample_rate(), 44_100);
+	assert_eq!(decoder.num_channels(), 1);
+	assert_eq!(decoder.num_frames(), 3);
+	assert_eq!(
+		decoder.decode().unwrap(),
+		vec![
+			Frame::from_mono(0.0),
+			Frame::from_mono(0.5),
+			Frame::from_mono(-1.0)
+		]
+	);
+	assert!(decoder.decode().unwrap().is_empty());
+}
+
+/// Tests that 24-bit, 32-bit, and floating point stereo audio is decoded.
+#[test]
+fn decodes_other_encodings() {
+	let mut data = vec![];
+	data.extend_from_slice(&(-4194304i32).to_le_bytes()[..3]);
+	data.extend_from_slice(&4194304i32.to_le_bytes()[..3]);
+	let mut decoder = new_decoder(wav_file(&fmt_chunk(1, 2, 24), &data)).unwrap();
+	assert_eq!(decoder.decode().unwrap(), vec![Frame::new(-0.5, 0.5)]);
+
+	let mut data = vec![];
+	data.extend_from_slice(&(i32::MIN / 4).to_le_bytes());
+	data.extend_from_slice(&i32::MIN.to_le_bytes());
+	let mut decoder = new_decoder(wav_file(&fmt_chunk(1, 2, 32), &data)).unwrap();
+	assert_eq!(decoder.decode().unwrap(), vec![Frame::new(-0.25, -1.0)]);
+
+	let mut data = vec![];
+	data.extend_from_slice(&0.75f32.to_le_bytes());
+	data.extend_from_slice(&(-0.125f32).to_le_bytes());
+	let mut decoder = new_decoder(wav_file(&fmt_chunk(3, 2, 32), &data)).unwrap();
+	assert_eq!(decoder.decode().unwrap(), vec![Frame::new(0.75, -0.125)]);
+}
+
+/// Tests that the actual format of audio with an extensible `fmt ` chunk
+/// is read from its sub-format, and that only the first two channels of
+/// audio with more than two channels are used.
+#[test]
+fn decodes_extensible_audio() {
+	let mut fmt = fmt_chunk(0xfffe, 3, 16);
+	fmt.extend_from_slice(&22u16.to_le_bytes());
+	fmt.extend_from_slice(&16u16.to_le_bytes());
+	fmt.extend_from_slice(&0u32.to_le_bytes());
+	fmt.extend_from_slice(&1u16.to_le_bytes());
+	fmt.extend_from_slice(&[0; 14]);
+	let data = [16384i16, -16384, 32767]
+		.iter()
+		.flat_map(|sample| sample.to_le_bytes())
+		.collect::<Vec<_>>();
+	let mut decoder = new_decoder(wav_file(&fmt, &data)).unwrap();
+	assert_eq!(decoder.num_channels(), 3);
+	assert_eq!(decoder.decode().unwrap(), vec![Frame::new(0.5, -0.5)]);
+}
+
+/// Tests that the decoder can seek to any frame.
+#[test]
+fn seeks() {
+	let samples = (0..2000).map(|i| i as i16).collect::<Vec<_>>();
+	let mut decoder = new_decoder(wav_fixture(44_100, &samples)).unwrap();
+	assert_eq!(decoder.decode().unwrap().len(), 1024);
+	assert_eq!(decoder.seek(1500).unwrap(), 1500);
+	let frames = decoder.decode().unwrap();
+	assert_eq!(frames.len(), 500);
+	assert_eq!(frames[0], Frame::from_mono(1500.0 / 32768.0));
+	assert_eq!(decoder.seek(5000).unwrap(), 2000);
+	assert!(decoder.decode().unwrap().is_empty());
+}
+
+/// Tests that audio is read until the end of the file if the `data` chunk
+/// claims to be longer than the rest of the file.
+#[test]
+fn reads_truncated_data_chunks() {
+	let mut wav = wav_fixture(44_100, &[1, 2, 3]);
+	wav.truncate(wav.len() - 1);
+	let mut decoder = new_decoder(wav).unwrap();
+	assert_eq!(decoder.num_frames(), 2);
+	assert_eq!(decoder.decode().unwrap().len(), 2);
+}
+
+/// Tests that invalid WAV files are reported.
+#[test]
+fn reports_invalid_files() {
+	let mut wav = wav_fixture(44_100, &[]);
+	wav[16..20].copy_from_slice(&1000u32.to_le_bytes());
+	assert!(matches!(
+		new_decoder(wav),
+		Err(FromFileError::InvalidWav(WavError::ChunkTooLong {
+			id: [b'f', b'm', b't', b' '],
+			len: 1000
+		}))
+	));
+
+	let mut wav = wav_fixture(44_100, &[0]);
+	wav.drain(12..36);
+	assert!(matches!(
+		new_decoder(wav),
+		Err(FromFileError::InvalidWav(WavError::MissingFormatChunk))
+	));
+
+	let mut wav = wav_fixture(44_100, &[0]);
+	wav.truncate(36);
+	assert!(matches!(
+		new_decoder(wav),
+		Err(FromFileError::InvalidWav(WavError::MissingDataChunk))
+	));
+
+	assert!(matches!(
+		new_decoder(wav_file(&fmt_chunk(1, 1, 8), &[0])),
+		Err(FromFileError::InvalidWav(WavError::UnsupportedEncoding {
+			format_tag: 1,
+			bits_per_sample: 8
+		}))
+	));
+
+	assert!(matches!(
+		new_decoder(wav_file(&fmt_chunk(1, 0, 16), &[])),
+		Err(FromFileError::InvalidWav(WavError::InvalidFormatChunk))
+	));
+}
+
+/// Tests that files that aren't WAV files are reported as unrecognized.
+#[test]
+fn reports_unrecognized_files() {
+	let Err(FromFileError::UnrecognizedFormat(error)) = new_decoder(b"OggS\0\x02".to_vec()) else {
+		panic!("the file should not be recognized");
+	};
+	assert_eq!(error.signature, b"OggS\0\x02");
+}
+
+/// Tests that static sounds can be loaded from WAV files without
+/// Symphonia.
+#[test]
+fn loads_static_sounds() {
+	let samples = (0..3000).map(|i| (i * 10) as i16).collect::<Vec<_>>();
+	let sound = StaticSoundData::from_bytes(wav_fixture(48_000, &samples)).unwrap();
+	assert_eq!(sound.sample_rate, 48_000);
+	assert_eq!(sound.num_frames(), 3000);
+	assert_eq!(sound.frames[2999], Frame::from_mono(29990.0 / 32768.0));
+}
+
+/// Tests that streaming sounds can be created from WAV files without
+/// Symphonia.
+#[test]
+fn creates_streaming_sounds() {
+	let sound =
+		crate::sound::streaming::StreamingSoundData::from_bytes(wav_fixture(44_100, &[0; 100]))
+			.unwrap();
+	assert_eq!(sound.num_frames(), 100);
+}
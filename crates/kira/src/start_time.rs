@@ -7,6 +7,7 @@ use crate::{
 
 /// Describes when an action should occur.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StartTime {
 	/// The action should occur immediately.
 	#[default]
@@ -15,7 +16,20 @@ pub enum StartTime {
 	Delayed(Duration),
 	/// The action should occur when a clock reaches a
 	/// specific time.
+	///
+	/// Clock times can't be serialized, since clocks only exist
+	/// while the audio manager that created them is running.
+	#[cfg_attr(feature = "serde", serde(skip))]
 	ClockTime(ClockTime),
+	/// The action should occur after a certain number of frames of
+	/// audio have been processed by the renderer.
+	///
+	/// Unlike [`StartTime::Delayed`], this doesn't depend on rounding
+	/// durations to frames, so sounds started with the same number of
+	/// frames of delay line up exactly. Sounds can start partway through
+	/// a chunk of audio, while other actions happen at the start of the
+	/// first chunk after the delay.
+	Frames(u64),
 }
 
 impl StartTime {
@@ -35,9 +49,32 @@ impl StartTime {
 				WhenToStart::Later => {}
 				WhenToStart::Never => return true,
 			},
+			StartTime::Frames(frames_remaining) => {
+				// the action occurs once all of the delay frames have passed,
+				// so it happens on the first frame after the delay
+				let elapsed_frames = info.num_frames_in(dt);
+				if elapsed_frames > *frames_remaining {
+					*self = StartTime::Immediate;
+				} else {
+					*frames_remaining -= elapsed_frames;
+				}
+			}
 		}
 		false
 	}
+
+	/// If this start time will be reached partway through a chunk of
+	/// `num_frames` frames, returns how many frames into the chunk
+	/// that will happen.
+	#[must_use]
+	pub(crate) fn frames_into_chunk(self, num_frames: usize) -> Option<usize> {
+		match self {
+			StartTime::Frames(frames_remaining) => usize::try_from(frames_remaining)
+				.ok()
+				.filter(|frames_remaining| (1..num_frames).contains(frames_remaining)),
+			_ => None,
+		}
+	}
 }
 
 pub(crate) type WillNeverStart = bool;
@@ -15,3 +15,103 @@ pub fn expect_frame_soon(expected_frame: Frame, sound: &mut dyn Sound) {
 		expected_frame, NUM_SAMPLES_TO_WAIT, collected_samples
 	);
 }
+
+/// Creates a mono 16-bit WAV file with the given samples.
+#[cfg(any(feature = "wav", not(feature = "symphonia")))]
+#[must_use]
+pub fn wav_fixture(sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+	let data_len = samples.len() as u32 * 2;
+	let mut wav = vec![];
+	wav.extend_from_slice(b"RIFF");
+	wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+	wav.extend_from_slice(b"WAVEfmt ");
+	wav.extend_from_slice(&16u32.to_le_bytes());
+	wav.extend_from_slice(&1u16.to_le_bytes()); // pcm
+	wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+	wav.extend_from_slice(&sample_rate.to_le_bytes());
+	wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+	wav.extend_from_slice(&2u16.to_le_bytes());
+	wav.extend_from_slice(&16u16.to_le_bytes());
+	wav.extend_from_slice(b"data");
+	wav.extend_from_slice(&data_len.to_le_bytes());
+	for sample in samples {
+		wav.extend_from_slice(&sample.to_le_bytes());
+	}
+	wav
+}
+
+/// Runs a future to completion on the current thread.
+#[cfg(all(feature = "async", feature = "symphonia"))]
+pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+	use std::{
+		pin::pin,
+		sync::Arc,
+		task::{Context, Poll, Wake},
+		thread::{self, Thread},
+	};
+
+	struct ThreadWaker(Thread);
+
+	impl Wake for ThreadWaker {
+		fn wake(self: Arc<Self>) {
+			self.0.unpark();
+		}
+	}
+
+	let waker = Arc::new(ThreadWaker(thread::current())).into();
+	let mut context = Context::from_waker(&waker);
+	let mut future = pin!(future);
+	loop {
+		match future.as_mut().poll(&mut context) {
+			Poll::Ready(output) => return output,
+			Poll::Pending => thread::park(),
+		}
+	}
+}
+
+/// Adds a `LIST` chunk with the given `INFO` tags to a WAV file created
+/// by [`wav_fixture`].
+#[cfg(feature = "wav")]
+#[must_use]
+pub fn with_wav_tags(mut wav: Vec<u8>, tags: &[(&[u8; 4], &str)]) -> Vec<u8> {
+	let mut list = b"INFO".to_vec();
+	for (id, value) in tags {
+		// values are null-terminated and padded to an even length
+		let mut value = value.as_bytes().to_vec();
+		value.push(0);
+		list.extend_from_slice(*id);
+		list.extend_from_slice(&(value.len() as u32).to_le_bytes());
+		if value.len() % 2 == 1 {
+			value.push(0);
+		}
+		list.extend_from_slice(&value);
+	}
+	// the tags have to come before the audio data, which starts after the
+	// 36 byte header
+	let mut chunk = b"LIST".to_vec();
+	chunk.extend_from_slice(&(list.len() as u32).to_le_bytes());
+	chunk.extend_from_slice(&list);
+	wav.splice(36..36, chunk);
+	let riff_len = wav.len() as u32 - 8;
+	wav[4..8].copy_from_slice(&riff_len.to_le_bytes());
+	wav
+}
+
+/// Adds a `smpl` chunk with one loop to a WAV file created by
+/// [`wav_fixture`]. `end` is the last sample of the loop.
+#[cfg(feature = "wav")]
+#[must_use]
+pub fn with_wav_loop(mut wav: Vec<u8>, start: u32, end: u32) -> Vec<u8> {
+	wav.extend_from_slice(b"smpl");
+	wav.extend_from_slice(&60u32.to_le_bytes());
+	// the sampler information before the number of loops isn't used
+	wav.extend_from_slice(&[0; 28]);
+	wav.extend_from_slice(&1u32.to_le_bytes());
+	wav.extend_from_slice(&0u32.to_le_bytes());
+	for value in [0, 0, start, end, 0, 0] {
+		wav.extend_from_slice(&value.to_le_bytes());
+	}
+	let riff_len = wav.len() as u32 - 8;
+	wav[4..8].copy_from_slice(&riff_len.to_le_bytes());
+	wav
+}
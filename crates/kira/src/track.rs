@@ -267,32 +267,115 @@ manager.add_spatial_sub_track(
 ```
 */
 
+mod capture;
+mod clipping;
+mod latency;
+mod levels;
 mod main;
+mod muffle;
+mod render;
 mod send;
+mod sidechain;
+mod sounds;
+mod stereo;
 mod sub;
 
+pub use capture::*;
+pub use clipping::*;
+pub use levels::*;
 pub use main::*;
+pub use muffle::*;
+pub use render::*;
 pub use send::*;
 pub use sub::*;
 
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+pub(crate) use latency::*;
+pub(crate) use sidechain::*;
+pub(crate) use sounds::*;
+pub(crate) use stereo::*;
 
-use crate::sound::PlaybackState;
+use std::sync::{
+	atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering},
+	Arc,
+};
+
+use crate::{manager::SoundCounts, sound::PlaybackState};
+
+/// A unique identifier for a mixer track that sounds can be played on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrackId(pub(crate) u64);
+
+impl TrackId {
+	pub(crate) const MAIN: Self = Self(0);
+
+	#[must_use]
+	fn next() -> Self {
+		static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+		Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+	}
+}
+
+impl From<&TrackHandle> for TrackId {
+	fn from(handle: &TrackHandle) -> Self {
+		handle.id()
+	}
+}
+
+impl From<&SpatialTrackHandle> for TrackId {
+	fn from(handle: &SpatialTrackHandle) -> Self {
+		handle.id()
+	}
+}
+
+impl From<&MainTrackHandle> for TrackId {
+	fn from(handle: &MainTrackHandle) -> Self {
+		handle.id()
+	}
+}
 
 #[derive(Debug)]
 pub(crate) struct TrackShared {
+	id: TrackId,
 	state: AtomicU8,
 	removed: AtomicBool,
+	/// Whether the track's handle asked for the track to fade out before
+	/// it's removed.
+	removal_requested: AtomicBool,
+	levels: LevelsShared,
+	/// The track this track is a sub-track of, or `None` if it's a
+	/// top-level track or a send track.
+	parent: Option<Arc<TrackShared>>,
+	/// Where this track is processed among its sibling tracks. Tracks with
+	/// lower ranks are processed first.
+	processing_rank: AtomicU32,
+	/// The order this track's sub-tracks have to be processed in.
+	sub_track_order: SubTrackOrder,
+	sound_counts: SoundCounts,
+	/// The latency the track's effects and sub-tracks introduce (in frames).
+	latency: AtomicUsize,
 }
 
 impl TrackShared {
-	pub fn new() -> Self {
+	pub fn new(parent: Option<Arc<TrackShared>>) -> Self {
 		Self {
+			id: TrackId::next(),
 			state: AtomicU8::new(TrackPlaybackState::Playing as u8),
 			removed: AtomicBool::new(false),
+			removal_requested: AtomicBool::new(false),
+			levels: LevelsShared::default(),
+			parent,
+			processing_rank: AtomicU32::new(0),
+			sub_track_order: SubTrackOrder::default(),
+			sound_counts: SoundCounts::default(),
+			latency: AtomicUsize::new(0),
 		}
 	}
 
+	#[must_use]
+	pub fn id(&self) -> TrackId {
+		self.id
+	}
+
 	pub fn state(&self) -> TrackPlaybackState {
 		match self.state.load(Ordering::SeqCst) {
 			0 => TrackPlaybackState::Playing,
@@ -316,6 +399,54 @@ impl TrackShared {
 	pub fn mark_for_removal(&self) {
 		self.removed.store(true, Ordering::SeqCst);
 	}
+
+	#[must_use]
+	pub fn is_removal_requested(&self) -> bool {
+		self.removal_requested.load(Ordering::SeqCst)
+	}
+
+	pub fn request_removal(&self) {
+		self.removal_requested.store(true, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn levels(&self) -> &LevelsShared {
+		&self.levels
+	}
+
+	#[must_use]
+	pub fn parent(&self) -> Option<&Arc<TrackShared>> {
+		self.parent.as_ref()
+	}
+
+	#[must_use]
+	pub fn processing_rank(&self) -> u32 {
+		self.processing_rank.load(Ordering::SeqCst)
+	}
+
+	pub fn set_processing_rank(&self, processing_rank: u32) {
+		self.processing_rank
+			.store(processing_rank, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn sub_track_order(&self) -> &SubTrackOrder {
+		&self.sub_track_order
+	}
+
+	#[must_use]
+	pub fn sound_counts(&self) -> &SoundCounts {
+		&self.sound_counts
+	}
+
+	#[must_use]
+	pub fn latency(&self) -> usize {
+		self.latency.load(Ordering::SeqCst)
+	}
+
+	pub fn set_latency(&self, latency: usize) {
+		self.latency.store(latency, Ordering::SeqCst);
+	}
 }
 
 /// The playback state of a mixer sub-track.
@@ -0,0 +1,145 @@
+use std::sync::{
+	atomic::{AtomicBool, AtomicU64, Ordering},
+	Arc, Mutex,
+};
+
+use rtrb::{Consumer, Producer, RingBuffer};
+
+use crate::{
+	backend::resources::{ResourceController, ResourceStorage},
+	Frame, ResourceLimitReached,
+};
+
+/// The maximum number of captures that can record a track at once.
+const CAPTURE_CAPACITY: usize = 4;
+
+/**
+Receives the audio a mixer track outputs.
+
+Captures are started with
+[`TrackHandle::start_capture`](super::TrackHandle::start_capture). The
+track's audio is recorded after its effects and volume are applied, and
+silence is recorded while the track is paused.
+
+The audio is stored in a ring buffer until it's read with
+[`drain`](Self::drain). If the ring buffer fills up, new audio is
+dropped, so the capture should be drained regularly. The audio's sample
+rate is the same as the [`AudioManager`](crate::AudioManager)'s, which is
+returned by [`AudioManager::sample_rate`](crate::AudioManager::sample_rate).
+
+When a [`CaptureHandle`] is dropped, the capture stops.
+*/
+#[derive(Debug)]
+pub struct CaptureHandle {
+	consumer: Mutex<Consumer<Frame>>,
+	shared: Arc<CaptureShared>,
+}
+
+impl CaptureHandle {
+	/// Moves all of the audio that's been recorded so far to the end of
+	/// `frames` and returns the number of frames that were moved.
+	pub fn drain(&mut self, frames: &mut Vec<Frame>) -> usize {
+		let mut consumer = self
+			.consumer
+			.lock()
+			.expect("capture consumer mutex poisoned");
+		let slots = consumer.slots();
+		let chunk = consumer
+			.read_chunk(slots)
+			.expect("the chunk should not be longer than the number of slots");
+		let len = chunk.len();
+		let (first, second) = chunk.as_slices();
+		frames.extend_from_slice(first);
+		frames.extend_from_slice(second);
+		chunk.commit_all();
+		len
+	}
+
+	/// Returns the number of frames that were dropped because the ring
+	/// buffer was full.
+	#[must_use]
+	pub fn num_dropped_frames(&self) -> u64 {
+		self.shared.num_dropped_frames.load(Ordering::SeqCst)
+	}
+
+	/// Stops recording the track's audio.
+	///
+	/// Audio that was recorded before the capture stopped can still be
+	/// read with [`drain`](Self::drain).
+	pub fn stop(&mut self) {
+		self.shared.stopped.store(true, Ordering::SeqCst);
+	}
+}
+
+impl Drop for CaptureHandle {
+	fn drop(&mut self) {
+		self.stop();
+	}
+}
+
+#[derive(Debug, Default)]
+struct CaptureShared {
+	stopped: AtomicBool,
+	num_dropped_frames: AtomicU64,
+}
+
+struct Capture {
+	producer: Producer<Frame>,
+	shared: Arc<CaptureShared>,
+}
+
+/// The captures recording a track, which live on the audio thread.
+pub(crate) struct Captures(ResourceStorage<Capture>);
+
+impl Captures {
+	#[must_use]
+	pub fn new() -> (Self, CaptureController) {
+		let (captures, controller) = ResourceStorage::new(CAPTURE_CAPACITY);
+		(Self(captures), CaptureController(controller))
+	}
+
+	pub fn on_start_processing(&mut self) {
+		self.0
+			.remove_and_add(|capture| capture.shared.stopped.load(Ordering::SeqCst));
+	}
+
+	/// Sends a chunk of the track's output to each capture.
+	pub fn record(&mut self, out: &[Frame]) {
+		for (_, capture) in &mut self.0 {
+			let mut num_dropped_frames = 0;
+			for frame in out {
+				if capture.producer.push(*frame).is_err() {
+					num_dropped_frames += 1;
+				}
+			}
+			if num_dropped_frames > 0 {
+				capture
+					.shared
+					.num_dropped_frames
+					.fetch_add(num_dropped_frames, Ordering::SeqCst);
+			}
+		}
+	}
+}
+
+/// Starts captures on a track.
+#[derive(Debug)]
+pub(crate) struct CaptureController(ResourceController<Capture>);
+
+impl CaptureController {
+	pub fn start(
+		&mut self,
+		ring_buffer_capacity: usize,
+	) -> Result<CaptureHandle, ResourceLimitReached> {
+		let (producer, consumer) = RingBuffer::new(ring_buffer_capacity);
+		let shared = Arc::new(CaptureShared::default());
+		self.0.insert(Capture {
+			producer,
+			shared: shared.clone(),
+		})?;
+		Ok(CaptureHandle {
+			consumer: Mutex::new(consumer),
+			shared,
+		})
+	}
+}
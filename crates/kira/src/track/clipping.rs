@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod test;
+
+use crate::Frame;
+
+/// The level above which [`ClippingBehavior::SoftClip`] starts to
+/// compress the signal.
+const SOFT_CLIP_THRESHOLD: f32 = 0.7;
+
+/// What a mixer track does with samples that go above `1.0` or below
+/// `-1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClippingBehavior {
+	/// Leaves the samples as they are.
+	#[default]
+	None,
+	/// Clamps samples to the range `-1.0..=1.0`.
+	Hard,
+	/// Smoothly rounds off samples louder than `0.7` so they approach,
+	/// but never reach, `1.0`. Quieter samples are left untouched.
+	///
+	/// This distorts loud peaks much less harshly than [`Hard`](Self::Hard).
+	SoftClip,
+}
+
+impl ClippingBehavior {
+	pub(crate) fn process(self, out: &mut [Frame]) {
+		match self {
+			ClippingBehavior::None => {}
+			ClippingBehavior::Hard => {
+				for frame in out {
+					frame.left = frame.left.clamp(-1.0, 1.0);
+					frame.right = frame.right.clamp(-1.0, 1.0);
+				}
+			}
+			ClippingBehavior::SoftClip => {
+				for frame in out {
+					frame.left = soft_clip(frame.left);
+					frame.right = soft_clip(frame.right);
+				}
+			}
+		}
+	}
+}
+
+/// Compresses the part of the sample above the threshold with a tanh
+/// curve, which meets the unchanged part of the signal smoothly.
+#[must_use]
+fn soft_clip(sample: f32) -> f32 {
+	let amplitude = sample.abs();
+	if amplitude <= SOFT_CLIP_THRESHOLD {
+		return sample;
+	}
+	let headroom = 1.0 - SOFT_CLIP_THRESHOLD;
+	let clipped =
+		SOFT_CLIP_THRESHOLD + headroom * ((amplitude - SOFT_CLIP_THRESHOLD) / headroom).tanh();
+	clipped.copysign(sample)
+}
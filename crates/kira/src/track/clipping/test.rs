@@ -0,0 +1,40 @@
+use crate::Frame;
+
+use super::ClippingBehavior;
+
+/// Tests that soft clipping leaves quiet samples untouched.
+#[test]
+fn soft_clip_passes_quiet_samples_through() {
+	let mut out = [Frame::new(0.5, -0.7), Frame::new(0.0, 0.25)];
+	let expected = out;
+	ClippingBehavior::SoftClip.process(&mut out);
+	assert_eq!(out, expected);
+}
+
+/// Tests that soft clipping keeps loud samples below 1 without changing
+/// their sign or their order.
+#[test]
+fn soft_clip_rounds_off_loud_samples() {
+	let mut previous = 0.7;
+	for input in [0.8, 1.0, 1.2, 1.5] {
+		let mut out = [Frame::new(input, -input)];
+		ClippingBehavior::SoftClip.process(&mut out);
+		assert!(out[0].left > previous && out[0].left < 1.0);
+		assert_eq!(out[0].right, -out[0].left);
+		previous = out[0].left;
+	}
+	let mut out = [Frame::new(100.0, -100.0)];
+	ClippingBehavior::SoftClip.process(&mut out);
+	assert!(out[0].left <= 1.0 && out[0].right >= -1.0);
+}
+
+/// Tests that hard clipping clamps samples and that no clipping leaves
+/// them alone.
+#[test]
+fn hard_clip_and_none() {
+	let mut out = [Frame::new(1.5, -3.0), Frame::new(0.5, -0.5)];
+	ClippingBehavior::None.process(&mut out);
+	assert_eq!(out, [Frame::new(1.5, -3.0), Frame::new(0.5, -0.5)]);
+	ClippingBehavior::Hard.process(&mut out);
+	assert_eq!(out, [Frame::new(1.0, -1.0), Frame::new(0.5, -0.5)]);
+}
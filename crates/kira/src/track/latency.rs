@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use crate::{backend::resources::ResourceStorage, Frame};
+
+use super::Track;
+
+/// The longest a track can be delayed to line up with its sibling tracks.
+pub(crate) const MAX_LATENCY_COMPENSATION: Duration = Duration::from_millis(100);
+
+/// Delays a track's output so it lines up with the output of its sibling
+/// tracks.
+pub(crate) struct LatencyCompensation {
+	enabled: bool,
+	/// Holds the most recent frames of the track's output, which is empty
+	/// if the track doesn't compensate for latency.
+	buffer: Vec<Frame>,
+	/// The index in the buffer the next frame is written to.
+	write_index: usize,
+	/// How many frames the output is delayed by.
+	delay: usize,
+}
+
+impl LatencyCompensation {
+	#[must_use]
+	pub fn new(enabled: bool) -> Self {
+		Self {
+			enabled,
+			buffer: vec![],
+			write_index: 0,
+			delay: 0,
+		}
+	}
+
+	#[must_use]
+	pub fn is_enabled(&self) -> bool {
+		self.enabled
+	}
+
+	/// Allocates room for the longest delay at the given sample rate.
+	pub fn init(&mut self, sample_rate: u32) {
+		if !self.enabled {
+			return;
+		}
+		let max_delay = (MAX_LATENCY_COMPENSATION.as_secs_f64() * sample_rate as f64) as usize;
+		self.buffer = vec![Frame::ZERO; max_delay + 1];
+		self.write_index = 0;
+		self.delay = self.delay.min(max_delay);
+	}
+
+	/// Sets how many frames the output is delayed by. Delays longer than
+	/// [`MAX_LATENCY_COMPENSATION`] are shortened to fit.
+	pub fn set_delay(&mut self, delay: usize) {
+		self.delay = delay.min(self.buffer.len().saturating_sub(1));
+	}
+
+	pub fn process(&mut self, out: &mut [Frame]) {
+		if self.buffer.is_empty() {
+			return;
+		}
+		let len = self.buffer.len();
+		for frame in out {
+			// the buffer always records the output so the delay can be
+			// lengthened without playing old audio
+			self.buffer[self.write_index] = *frame;
+			*frame = self.buffer[(self.write_index + len - self.delay) % len];
+			self.write_index = (self.write_index + 1) % len;
+		}
+	}
+}
+
+/// Delays the tracks that compensate for latency so they line up with
+/// the one that has the most latency, and returns that track's latency.
+pub(crate) fn align_latency(tracks: &mut ResourceStorage<Track>) -> usize {
+	let max_latency = tracks
+		.iter()
+		.filter(|(_, track)| track.compensates_latency())
+		.map(|(_, track)| track.latency())
+		.max()
+		.unwrap_or(0);
+	for (_, track) in tracks {
+		let latency = track.latency();
+		track.set_compensation_delay(max_latency.saturating_sub(latency));
+	}
+	max_latency
+}
@@ -0,0 +1,56 @@
+use crate::Frame;
+
+use super::LatencyCompensation;
+
+fn frames(values: &[f32]) -> Vec<Frame> {
+	values.iter().copied().map(Frame::from_mono).collect()
+}
+
+/// Tests that the output is delayed by the requested number of frames.
+#[test]
+fn delays_output() {
+	let mut compensation = LatencyCompensation::new(true);
+	compensation.init(100);
+	compensation.set_delay(2);
+	let mut out = frames(&[1.0, 2.0, 3.0]);
+	compensation.process(&mut out);
+	assert_eq!(out, frames(&[0.0, 0.0, 1.0]));
+	let mut out = frames(&[4.0, 5.0]);
+	compensation.process(&mut out);
+	assert_eq!(out, frames(&[2.0, 3.0]));
+}
+
+/// Tests that lengthening the delay replays recent audio instead of
+/// audio from before the buffer last wrapped around.
+#[test]
+fn lengthening_delay_replays_recent_audio() {
+	let mut compensation = LatencyCompensation::new(true);
+	compensation.init(10);
+	let mut out = frames(&[1.0, 2.0, 3.0]);
+	compensation.process(&mut out);
+	assert_eq!(out, frames(&[1.0, 2.0, 3.0]));
+	compensation.set_delay(1);
+	let mut out = frames(&[4.0]);
+	compensation.process(&mut out);
+	assert_eq!(out, frames(&[3.0]));
+}
+
+/// Tests that delays are limited to the longest delay the buffer can
+/// hold, and that disabled compensation doesn't delay anything.
+#[test]
+fn limits_delay() {
+	let mut compensation = LatencyCompensation::new(true);
+	// 100 milliseconds at 20 Hz is 2 frames
+	compensation.init(20);
+	compensation.set_delay(5);
+	let mut out = frames(&[1.0, 2.0, 3.0]);
+	compensation.process(&mut out);
+	assert_eq!(out, frames(&[0.0, 0.0, 1.0]));
+
+	let mut compensation = LatencyCompensation::new(false);
+	compensation.init(20);
+	compensation.set_delay(5);
+	let mut out = frames(&[1.0, 2.0]);
+	compensation.process(&mut out);
+	assert_eq!(out, frames(&[1.0, 2.0]));
+}
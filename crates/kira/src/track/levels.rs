@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	sync::atomic::{AtomicBool, AtomicU32, Ordering},
+	time::Duration,
+};
+
+use crate::Frame;
+
+/// The default length of the window RMS levels are measured over.
+pub(crate) const DEFAULT_RMS_WINDOW: Duration = Duration::from_millis(300);
+
+/**
+The levels of the audio a mixer track output most recently.
+
+Levels are measured after the track's effects and volume are applied,
+and they're amplitudes, so `1.0` is the loudest a sample can be without
+clipping. They can be converted to [`Decibels`](crate::Decibels) using
+[`Decibels::from_amplitude`](crate::Decibels::from_amplitude).
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackLevels {
+	/// The highest absolute value of the left channel during the most
+	/// recent chunk of audio.
+	pub left_peak: f32,
+	/// The highest absolute value of the right channel during the most
+	/// recent chunk of audio.
+	pub right_peak: f32,
+	/// The root mean square of the left channel over the track's RMS
+	/// window.
+	pub left_rms: f32,
+	/// The root mean square of the right channel over the track's RMS
+	/// window.
+	pub right_rms: f32,
+	/// Whether either channel went above `1.0` since the last time the
+	/// levels were read.
+	pub clipped: bool,
+}
+
+/// The levels of a track, written by the audio thread and read by the
+/// track's handle.
+#[derive(Debug, Default)]
+pub(crate) struct LevelsShared {
+	left_peak: AtomicU32,
+	right_peak: AtomicU32,
+	left_rms: AtomicU32,
+	right_rms: AtomicU32,
+	clipped: AtomicBool,
+	/// Tracks clipping separately from [`TrackLevels::clipped`] so
+	/// reading the levels doesn't reset it.
+	clipped_since_last_read: AtomicBool,
+}
+
+impl LevelsShared {
+	/// Returns the most recent levels and resets the clip indicator.
+	#[must_use]
+	pub fn read(&self) -> TrackLevels {
+		let load = |value: &AtomicU32| f32::from_bits(value.load(Ordering::SeqCst));
+		TrackLevels {
+			left_peak: load(&self.left_peak),
+			right_peak: load(&self.right_peak),
+			left_rms: load(&self.left_rms),
+			right_rms: load(&self.right_rms),
+			clipped: self.clipped.swap(false, Ordering::SeqCst),
+		}
+	}
+
+	/// Returns whether the track clipped since the last time this was
+	/// called and resets the flag.
+	#[must_use]
+	pub fn read_clipped(&self) -> bool {
+		self.clipped_since_last_read.swap(false, Ordering::SeqCst)
+	}
+}
+
+/// Measures the levels of a track's output.
+pub(crate) struct LevelMeter {
+	/// The length of the window RMS levels are measured over (in
+	/// seconds).
+	rms_window: f64,
+	left_mean_square: f32,
+	right_mean_square: f32,
+}
+
+impl LevelMeter {
+	#[must_use]
+	pub fn new(rms_window: Duration) -> Self {
+		Self {
+			rms_window: rms_window.as_secs_f64(),
+			left_mean_square: 0.0,
+			right_mean_square: 0.0,
+		}
+	}
+
+	/// Measures a chunk of audio and stores the levels in `shared`.
+	pub fn measure(&mut self, out: &[Frame], dt: f64, shared: &LevelsShared) {
+		// the mean square is an exponential moving average, so old
+		// audio fades out of it smoothly instead of needing to be stored
+		let smoothing = (1.0 - (-dt / self.rms_window).exp()) as f32;
+		let mut left_peak = 0.0f32;
+		let mut right_peak = 0.0f32;
+		for frame in out {
+			left_peak = left_peak.max(frame.left.abs());
+			right_peak = right_peak.max(frame.right.abs());
+			self.left_mean_square += (frame.left * frame.left - self.left_mean_square) * smoothing;
+			self.right_mean_square +=
+				(frame.right * frame.right - self.right_mean_square) * smoothing;
+		}
+		let store = |value: &AtomicU32, level: f32| value.store(level.to_bits(), Ordering::SeqCst);
+		store(&shared.left_peak, left_peak);
+		store(&shared.right_peak, right_peak);
+		store(&shared.left_rms, self.left_mean_square.sqrt());
+		store(&shared.right_rms, self.right_mean_square.sqrt());
+		if left_peak > 1.0 || right_peak > 1.0 {
+			shared.clipped.store(true, Ordering::SeqCst);
+			shared.clipped_since_last_read.store(true, Ordering::SeqCst);
+		}
+	}
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use crate::Frame;
+
+use super::{LevelMeter, LevelsShared};
+
+/// Tests that the peak of each channel is measured separately for each
+/// chunk of audio.
+#[test]
+fn measures_peaks() {
+	let shared = LevelsShared::default();
+	let mut meter = LevelMeter::new(Duration::from_millis(300));
+	meter.measure(
+		&[Frame::new(0.25, -0.5), Frame::new(-0.75, 0.125)],
+		1.0 / 48_000.0,
+		&shared,
+	);
+	let levels = shared.read();
+	assert_eq!(levels.left_peak, 0.75);
+	assert_eq!(levels.right_peak, 0.5);
+	meter.measure(&[Frame::new(0.125, 0.0)], 1.0 / 48_000.0, &shared);
+	let levels = shared.read();
+	assert_eq!(levels.left_peak, 0.125);
+	assert_eq!(levels.right_peak, 0.0);
+}
+
+/// Tests that the RMS level of a constant signal settles on the signal's
+/// amplitude over the RMS window.
+#[test]
+fn measures_rms_over_window() {
+	let shared = LevelsShared::default();
+	let mut meter = LevelMeter::new(Duration::from_secs(1));
+	let chunk = [Frame::new(0.5, -0.25); 100];
+	// after a tenth of the window, the level is still rising
+	meter.measure(&chunk, 0.001, &shared);
+	let levels = shared.read();
+	assert!(levels.left_rms > 0.1 && levels.left_rms < 0.2);
+	for _ in 0..100 {
+		meter.measure(&chunk, 0.001, &shared);
+	}
+	let levels = shared.read();
+	assert!((levels.left_rms - 0.5).abs() < 1.0e-3);
+	assert!((levels.right_rms - 0.25).abs() < 1.0e-3);
+}
+
+/// Tests that the clip indicator stays on until the levels are read.
+#[test]
+fn latches_clipping() {
+	let shared = LevelsShared::default();
+	let mut meter = LevelMeter::new(Duration::from_millis(300));
+	meter.measure(&[Frame::new(0.0, 1.5)], 1.0 / 48_000.0, &shared);
+	meter.measure(&[Frame::new(0.0, 0.5)], 1.0 / 48_000.0, &shared);
+	assert!(shared.read().clipped);
+	assert!(!shared.read().clipped);
+}
+
+/// Tests that reading the levels doesn't reset the separate clip flag.
+#[test]
+fn latches_clipping_separately_from_levels() {
+	let shared = LevelsShared::default();
+	let mut meter = LevelMeter::new(Duration::from_millis(300));
+	meter.measure(&[Frame::new(-1.5, 0.0)], 1.0 / 48_000.0, &shared);
+	assert!(shared.read().clipped);
+	assert!(shared.read_clipped());
+	assert!(!shared.read_clipped());
+}
@@ -4,59 +4,80 @@ mod handle;
 pub use builder::*;
 pub use handle::*;
 
+use std::sync::Arc;
+
 use crate::{
 	backend::resources::ResourceStorage,
 	command::{CommandReader, ValueChangeCommand},
 	effect::Effect,
 	info::Info,
+	manager::SoundCounts,
 	sound::Sound,
 	Decibels, Frame, Parameter,
 };
 
+use super::{Captures, ClippingBehavior, LevelMeter, LevelsShared, TrackSounds};
+
 pub(crate) struct MainTrack {
 	volume: Parameter<Decibels>,
 	set_volume_command_reader: CommandReader<ValueChangeCommand<Decibels>>,
-	sounds: ResourceStorage<Box<dyn Sound>>,
+	sounds: TrackSounds,
 	effects: Vec<Box<dyn Effect>>,
+	/// The tone control applied after the track's effects, or `None` if the
+	/// track was built without one.
+	tone_control: Option<Box<dyn Effect>>,
 	temp_buffer: Vec<Frame>,
 	internal_buffer_size: usize,
+	level_meter: LevelMeter,
+	clipping_behavior: ClippingBehavior,
+	set_clipping_behavior_command_reader: CommandReader<ClippingBehavior>,
+	levels: Arc<LevelsShared>,
+	sound_counts: Arc<SoundCounts>,
+	captures: Captures,
 }
 
 impl MainTrack {
 	pub fn init_effects(&mut self, sample_rate: u32) {
-		for effect in &mut self.effects {
+		for effect in self.effects.iter_mut().chain(&mut self.tone_control) {
 			effect.init(sample_rate, self.internal_buffer_size);
 		}
 	}
 
 	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
-		for effect in &mut self.effects {
+		for effect in self.effects.iter_mut().chain(&mut self.tone_control) {
 			effect.on_change_sample_rate(sample_rate);
 		}
 	}
 
+	#[must_use]
+	pub fn sounds(&self) -> &ResourceStorage<Box<dyn Sound>> {
+		&self.sounds.sounds
+	}
+
+	#[must_use]
+	pub fn sounds_mut(&mut self) -> &mut TrackSounds {
+		&mut self.sounds
+	}
+
 	pub fn on_start_processing(&mut self) {
 		self.volume
 			.read_command(&mut self.set_volume_command_reader);
-		self.sounds.remove_and_add(|sound| sound.finished());
-		for (_, sound) in &mut self.sounds {
-			sound.on_start_processing();
+		if let Some(clipping_behavior) = self.set_clipping_behavior_command_reader.read() {
+			self.clipping_behavior = clipping_behavior;
 		}
-		for effect in &mut self.effects {
+		self.sounds.on_start_processing();
+		self.sound_counts.record(&self.sounds.sounds);
+		for effect in self.effects.iter_mut().chain(&mut self.tone_control) {
 			effect.on_start_processing();
 		}
+		self.captures.on_start_processing();
 	}
 
 	pub fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
 		self.volume.update(dt * out.len() as f64, info);
-		for (_, sound) in &mut self.sounds {
-			sound.process(&mut self.temp_buffer[..out.len()], dt, info);
-			for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
-				*summed_out += sound_out;
-			}
-			self.temp_buffer.fill(Frame::ZERO);
-		}
-		for effect in &mut self.effects {
+		self.sounds
+			.process(out, &mut self.temp_buffer, dt, info, None);
+		for effect in self.effects.iter_mut().chain(&mut self.tone_control) {
 			effect.process(out, dt, info);
 		}
 		let num_frames = out.len();
@@ -65,5 +86,8 @@ impl MainTrack {
 			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
 			*frame *= volume;
 		}
+		self.level_meter.measure(out, dt, &self.levels);
+		self.clipping_behavior.process(out);
+		self.captures.record(out);
 	}
 }
@@ -1,5 +1,13 @@
+use std::{sync::Arc, time::Duration};
+
 use crate::{
-	backend::resources::ResourceStorage, command::command_writer_and_reader, effect::EffectBuilder,
+	backend::resources::ResourceStorage,
+	command::command_writer_and_reader,
+	effect::{tone_control::ToneControlBuilder, EffectBuilder},
+	manager::SoundCounts,
+	track::{
+		Captures, ClippingBehavior, LevelMeter, LevelsShared, TrackSounds, DEFAULT_RMS_WINDOW,
+	},
 	Decibels, Frame, Parameter, Value,
 };
 
@@ -14,6 +22,13 @@ pub struct MainTrackBuilder {
 	pub(crate) effects: Vec<Box<dyn Effect>>,
 	/// The maximum number of sounds that can be played simultaneously on this track.
 	pub(crate) sound_capacity: usize,
+	/// The length of the window RMS levels are measured over.
+	pub(crate) rms_window: Duration,
+	/// What the track does with samples that go above `1.0` or below
+	/// `-1.0`.
+	pub(crate) clipping_behavior: ClippingBehavior,
+	/// Whether the track has a tone control after its effects.
+	pub(crate) tone_control: bool,
 }
 
 impl MainTrackBuilder {
@@ -24,6 +39,9 @@ impl MainTrackBuilder {
 			volume: Value::Fixed(Decibels::IDENTITY),
 			effects: vec![],
 			sound_capacity: 128,
+			rms_window: DEFAULT_RMS_WINDOW,
+			clipping_behavior: ClippingBehavior::Hard,
+			tone_control: true,
 		}
 	}
 
@@ -45,6 +63,46 @@ impl MainTrackBuilder {
 		}
 	}
 
+	/// Sets what the track does with samples that go above `1.0` or below
+	/// `-1.0`. Defaults to [`ClippingBehavior::Hard`].
+	///
+	/// The audio sent to the output device is always clamped to `-1.0..=1.0`,
+	/// so [`ClippingBehavior::None`] only makes a difference to the audio
+	/// read by the main track's captures.
+	#[must_use = "This method consumes self and returns a modified MainTrackBuilder, so the return value should be used"]
+	pub fn clipping_behavior(self, clipping_behavior: ClippingBehavior) -> Self {
+		Self {
+			clipping_behavior,
+			..self
+		}
+	}
+
+	/// Sets the length of the window the track's RMS levels are measured
+	/// over. Defaults to 300 milliseconds.
+	///
+	/// Shorter windows make level meters react faster, and longer windows
+	/// make them smoother.
+	#[must_use = "This method consumes self and returns a modified MainTrackBuilder, so the return value should be used"]
+	pub fn rms_window(self, rms_window: Duration) -> Self {
+		Self { rms_window, ..self }
+	}
+
+	/// Sets whether the track has a tone control, which adjusts the bass
+	/// and treble of the track's output after its other effects. Defaults
+	/// to `true`.
+	///
+	/// The bass and treble are set with [`MainTrackHandle::set_bass`] and
+	/// [`MainTrackHandle::set_treble`]. While both are at 0 dB, the tone
+	/// control leaves the audio exactly as it was and does almost no work,
+	/// so it's fine to leave it enabled.
+	#[must_use = "This method consumes self and returns a modified MainTrackBuilder, so the return value should be used"]
+	pub fn tone_control(self, tone_control: bool) -> Self {
+		Self {
+			tone_control,
+			..self
+		}
+	}
+
 	/**
 	Adds an effect to the track.
 
@@ -144,18 +202,41 @@ impl MainTrackBuilder {
 	#[must_use]
 	pub(crate) fn build(self, internal_buffer_size: usize) -> (MainTrack, MainTrackHandle) {
 		let (set_volume_command_writer, set_volume_command_reader) = command_writer_and_reader();
+		let (set_clipping_behavior_command_writer, set_clipping_behavior_command_reader) =
+			command_writer_and_reader();
 		let (sounds, sound_controller) = ResourceStorage::new(self.sound_capacity);
+		let levels = Arc::new(LevelsShared::default());
+		let sound_counts = Arc::new(SoundCounts::default());
+		let (captures, capture_controller) = Captures::new();
+		let (tone_control, tone_control_handle) = if self.tone_control {
+			let (effect, handle) = ToneControlBuilder::new().build();
+			(Some(effect), Some(handle))
+		} else {
+			(None, None)
+		};
 		let track = MainTrack {
 			volume: Parameter::new(self.volume, Decibels::IDENTITY),
 			set_volume_command_reader,
-			sounds,
+			sounds: TrackSounds::new(sounds, internal_buffer_size),
 			effects: self.effects,
+			tone_control,
 			temp_buffer: vec![Frame::ZERO; internal_buffer_size],
 			internal_buffer_size,
+			level_meter: LevelMeter::new(self.rms_window),
+			clipping_behavior: self.clipping_behavior,
+			set_clipping_behavior_command_reader,
+			levels: levels.clone(),
+			sound_counts: sound_counts.clone(),
+			captures,
 		};
 		let handle = MainTrackHandle {
 			set_volume_command_writer,
+			set_clipping_behavior_command_writer,
 			sound_controller,
+			levels,
+			sound_counts,
+			capture_controller,
+			tone_control: tone_control_handle,
 		};
 		(track, handle)
 	}
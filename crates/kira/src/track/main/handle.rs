@@ -1,18 +1,67 @@
+use std::sync::Arc;
+
 use crate::{
 	backend::resources::ResourceController,
 	command::{CommandWriter, ValueChangeCommand},
+	effect::tone_control::ToneControlHandle,
+	manager::SoundCounts,
 	sound::{Sound, SoundData},
-	Decibels, PlaySoundError, Tween, Value,
+	track::{
+		CaptureController, CaptureHandle, ClippingBehavior, LevelsShared, TrackId, TrackLevels,
+	},
+	Decibels, PlaySoundError, ResourceLimitReached, Tween, Value,
 };
 
 /// Controls the main mixer track.
 #[derive(Debug)]
 pub struct MainTrackHandle {
 	pub(crate) set_volume_command_writer: CommandWriter<ValueChangeCommand<Decibels>>,
+	pub(crate) set_clipping_behavior_command_writer: CommandWriter<ClippingBehavior>,
 	pub(crate) sound_controller: ResourceController<Box<dyn Sound>>,
+	pub(crate) levels: Arc<LevelsShared>,
+	pub(crate) sound_counts: Arc<SoundCounts>,
+	pub(crate) capture_controller: CaptureController,
+	pub(crate) tone_control: Option<ToneControlHandle>,
 }
 
 impl MainTrackHandle {
+	/// Returns a unique identifier for the main track.
+	#[must_use]
+	pub fn id(&self) -> TrackId {
+		TrackId::MAIN
+	}
+
+	/// Returns the levels of the audio the track output most recently.
+	///
+	/// Reading the levels resets [`TrackLevels::clipped`].
+	#[must_use]
+	pub fn level(&self) -> TrackLevels {
+		self.levels.read()
+	}
+
+	/// Returns whether the track output a sample above `1.0` or below
+	/// `-1.0` since the last time this method was called.
+	///
+	/// This is measured before the track's
+	/// [clipping behavior](ClippingBehavior) is applied, and it's tracked
+	/// separately from [`TrackLevels::clipped`], so calling [`level`](Self::level)
+	/// doesn't reset it.
+	#[must_use]
+	pub fn clipped_since_last_read(&self) -> bool {
+		self.levels.read_clipped()
+	}
+
+	/// Starts recording the audio this track outputs into a ring buffer
+	/// that can hold `ring_buffer_capacity` frames.
+	///
+	/// Up to 4 captures can record a track at once.
+	pub fn start_capture(
+		&mut self,
+		ring_buffer_capacity: usize,
+	) -> Result<CaptureHandle, ResourceLimitReached> {
+		self.capture_controller.start(ring_buffer_capacity)
+	}
+
 	/// Plays a sound.
 	pub fn play<D: SoundData>(
 		&mut self,
@@ -35,6 +84,35 @@ impl MainTrackHandle {
 		})
 	}
 
+	/// Sets the volume adjustment for frequencies around and below
+	/// [`BASS_FREQUENCY`](crate::effect::tone_control::BASS_FREQUENCY).
+	///
+	/// This does nothing if the track was built without a
+	/// [tone control](super::MainTrackBuilder::tone_control).
+	pub fn set_bass(&mut self, bass: impl Into<Value<Decibels>>, tween: Tween) {
+		if let Some(tone_control) = &mut self.tone_control {
+			tone_control.set_bass(bass, tween);
+		}
+	}
+
+	/// Sets the volume adjustment for frequencies around and above
+	/// [`TREBLE_FREQUENCY`](crate::effect::tone_control::TREBLE_FREQUENCY).
+	///
+	/// This does nothing if the track was built without a
+	/// [tone control](super::MainTrackBuilder::tone_control).
+	pub fn set_treble(&mut self, treble: impl Into<Value<Decibels>>, tween: Tween) {
+		if let Some(tone_control) = &mut self.tone_control {
+			tone_control.set_treble(treble, tween);
+		}
+	}
+
+	/// Sets what the track does with samples that go above `1.0` or below
+	/// `-1.0`.
+	pub fn set_clipping_behavior(&mut self, clipping_behavior: ClippingBehavior) {
+		self.set_clipping_behavior_command_writer
+			.write(clipping_behavior)
+	}
+
 	/// Returns the maximum number of sounds that can play simultaneously on this track.
 	#[must_use]
 	pub fn sound_capacity(&self) -> usize {
@@ -46,4 +124,16 @@ impl MainTrackHandle {
 	pub fn num_sounds(&self) -> usize {
 		self.sound_controller.len()
 	}
+
+	/// Returns the number of sounds on this track in each
+	/// [`PlaybackState`](crate::sound::PlaybackState), in the order of
+	/// [`PlaybackState::ALL`](crate::sound::PlaybackState::ALL).
+	///
+	/// This is updated by the audio thread every time it processes a
+	/// batch of audio, so sounds that were just played may not be
+	/// counted yet.
+	#[must_use]
+	pub fn num_sounds_by_state(&self) -> [usize; 7] {
+		self.sound_counts.read()
+	}
 }
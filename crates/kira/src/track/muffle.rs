@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod test;
+
+use std::f64::consts::PI;
+
+use crate::{
+	command::{CommandReader, ValueChangeCommand},
+	info::Info,
+	Decibels, Easing, Frame, Parameter, Value,
+};
+
+/// The cutoff frequency of a track's muffle filter when the track isn't
+/// muffled at all (in Hz).
+pub const UNMUFFLED_CUTOFF: f64 = 20_000.0;
+
+/**
+How a track's muffle amount maps to the cutoff frequency and volume of its
+muffle filter.
+
+The cutoff frequency moves from [`UNMUFFLED_CUTOFF`] at a muffle amount of
+`0.0` to [`cutoff`](Self::cutoff) at `1.0`. It moves the same number of
+octaves for each step of the eased muffle amount, so halfway between the
+two is the geometric mean of the frequencies rather than their average. The
+volume moves from 0 dB to [`attenuation`](Self::attenuation) in even decibel
+steps.
+
+With the default curve, a muffle amount of `0.5` puts the cutoff at about
+2,800 Hz and turns the track down by 3 dB.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MuffleCurve {
+	/// The cutoff frequency of the low-pass filter when the track is fully
+	/// muffled (in Hz). Defaults to 400 Hz.
+	pub cutoff: f64,
+	/// How much the track is turned down when it's fully muffled.
+	/// Defaults to -6 dB.
+	pub attenuation: Decibels,
+	/// The curve applied to the muffle amount before it's mapped to the
+	/// cutoff frequency and volume. Defaults to [`Easing::Linear`].
+	pub easing: Easing,
+}
+
+impl Default for MuffleCurve {
+	fn default() -> Self {
+		Self {
+			cutoff: 400.0,
+			attenuation: Decibels(-6.0),
+			easing: Easing::Linear,
+		}
+	}
+}
+
+impl MuffleCurve {
+	/// Returns the cutoff frequency (in Hz) and volume of the muffle filter
+	/// for the given muffle amount.
+	#[must_use]
+	fn map(&self, amount: f32) -> (f64, f32) {
+		let amount = self.easing.apply(amount.clamp(0.0, 1.0) as f64);
+		let cutoff = UNMUFFLED_CUTOFF * (self.cutoff / UNMUFFLED_CUTOFF).powf(amount);
+		let volume = Decibels(self.attenuation.0 * amount as f32).as_amplitude();
+		(cutoff, volume)
+	}
+}
+
+/// A low-pass filter every track has that muffles the track's audio.
+pub(crate) struct Muffle {
+	amount: Parameter<f32>,
+	curve: MuffleCurve,
+	ic1eq: Frame,
+	ic2eq: Frame,
+}
+
+impl Muffle {
+	#[must_use]
+	pub fn new(amount: Value<f32>, curve: MuffleCurve) -> Self {
+		Self {
+			amount: Parameter::new(amount, 0.0),
+			curve,
+			ic1eq: Frame::ZERO,
+			ic2eq: Frame::ZERO,
+		}
+	}
+
+	pub fn read_command(&mut self, command_reader: &mut CommandReader<ValueChangeCommand<f32>>) {
+		self.amount.read_command(command_reader);
+	}
+
+	pub fn update(&mut self, dt: f64, info: &Info) {
+		self.amount.update(dt, info);
+	}
+
+	pub fn process(&mut self, out: &mut [Frame], dt: f64) {
+		let previous_amount = self.amount.previous_value();
+		let amount = self.amount.value();
+		// an unmuffled track skips the filter entirely, so it costs nothing
+		// and leaves the audio exactly as it was
+		if previous_amount <= 0.0 && amount <= 0.0 {
+			self.ic1eq = Frame::ZERO;
+			self.ic2eq = Frame::ZERO;
+			return;
+		}
+		let fixed_coefficients =
+			(previous_amount == amount).then(|| Coefficients::new(self.curve.map(amount), dt));
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			let coefficients = fixed_coefficients.unwrap_or_else(|| {
+				let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+				Coefficients::new(
+					self.curve
+						.map(self.amount.interpolated_value(time_in_chunk)),
+					dt,
+				)
+			});
+			let v3 = *frame - self.ic2eq;
+			let v1 = self.ic1eq * coefficients.a1 + v3 * coefficients.a2;
+			let v2 = self.ic2eq + self.ic1eq * coefficients.a2 + v3 * coefficients.a3;
+			self.ic1eq = v1 * 2.0 - self.ic1eq;
+			self.ic2eq = v2 * 2.0 - self.ic2eq;
+			*frame = v2 * coefficients.volume;
+		}
+	}
+}
+
+/// The values the muffle filter uses to process one frame of audio.
+#[derive(Clone, Copy)]
+struct Coefficients {
+	a1: f32,
+	a2: f32,
+	a3: f32,
+	volume: f32,
+}
+
+impl Coefficients {
+	#[must_use]
+	fn new((cutoff, volume): (f64, f32), dt: f64) -> Self {
+		// a Q of 1/sqrt(2) keeps the filter from ringing at the cutoff
+		const K: f64 = std::f64::consts::SQRT_2;
+		let g = (PI * (cutoff * dt).clamp(0.0001, 0.49)).tan();
+		let a1 = 1.0 / (1.0 + g * (g + K));
+		let a2 = g * a1;
+		let a3 = g * a2;
+		Self {
+			a1: a1 as f32,
+			a2: a2 as f32,
+			a3: a3 as f32,
+			volume,
+		}
+	}
+}
@@ -0,0 +1,71 @@
+use crate::{info::MockInfoBuilder, Decibels, Easing, Frame, Value};
+
+use super::{Muffle, MuffleCurve, UNMUFFLED_CUTOFF};
+
+const DT: f64 = 1.0 / 48_000.0;
+
+/// Processes enough of a signal for the filter to settle and returns the
+/// peak amplitude of the last chunk.
+fn settled_peak(muffle: &mut Muffle, signal: impl Fn(usize) -> f32) -> f32 {
+	let info = MockInfoBuilder::new().build();
+	let mut peak = 0.0;
+	for chunk in 0..100 {
+		let mut out: Vec<Frame> = (0..128)
+			.map(|i| Frame::from_mono(signal(chunk * 128 + i)))
+			.collect();
+		muffle.update(DT * out.len() as f64, &info);
+		muffle.process(&mut out, DT);
+		peak = out.iter().map(|frame| frame.left.abs()).fold(0.0, f32::max);
+	}
+	peak
+}
+
+/// Tests that the cutoff frequency moves evenly in octaves and the volume
+/// moves evenly in decibels.
+#[test]
+fn curve_maps_amount_to_cutoff_and_volume() {
+	let curve = MuffleCurve::default();
+	let (cutoff, volume) = curve.map(0.0);
+	assert_eq!(cutoff, UNMUFFLED_CUTOFF);
+	assert_eq!(volume, 1.0);
+	let (cutoff, volume) = curve.map(0.5);
+	assert!((cutoff - (UNMUFFLED_CUTOFF * 400.0f64).sqrt()).abs() < 0.001);
+	assert!((volume - Decibels(-3.0).as_amplitude()).abs() < 0.0001);
+	let (cutoff, volume) = curve.map(1.0);
+	assert!((cutoff - 400.0).abs() < 0.001);
+	assert!((volume - Decibels(-6.0).as_amplitude()).abs() < 0.0001);
+	// amounts outside of 0-1 are clamped
+	assert_eq!(curve.map(2.0), curve.map(1.0));
+
+	let eased = MuffleCurve {
+		easing: Easing::InPowi(2),
+		..curve
+	};
+	assert_eq!(eased.map(0.5), curve.map(0.25));
+}
+
+/// Tests that an unmuffled track's audio is left exactly as it was.
+#[test]
+fn leaves_audio_unchanged_when_unmuffled() {
+	let info = MockInfoBuilder::new().build();
+	let mut muffle = Muffle::new(Value::Fixed(0.0), MuffleCurve::default());
+	let input: Vec<Frame> = (0..128)
+		.map(|i| Frame::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+		.collect();
+	let mut out = input.clone();
+	muffle.update(DT * out.len() as f64, &info);
+	muffle.process(&mut out, DT);
+	assert_eq!(out, input);
+}
+
+/// Tests that a fully muffled track keeps its low frequencies, turned
+/// down by the curve's attenuation, and loses its high frequencies.
+#[test]
+fn removes_high_frequencies_when_muffled() {
+	let mut muffle = Muffle::new(Value::Fixed(1.0), MuffleCurve::default());
+	let low = settled_peak(&mut muffle, |_| 0.5);
+	assert!((low - 0.5 * Decibels(-6.0).as_amplitude()).abs() < 0.001);
+	let mut muffle = Muffle::new(Value::Fixed(1.0), MuffleCurve::default());
+	let high = settled_peak(&mut muffle, |i| if i % 2 == 0 { 0.5 } else { -0.5 });
+	assert!(high < 0.001);
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use crate::{
+	backend::mock::{MockBackend, MockBackendSettings},
+	sound::{
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		SoundData,
+	},
+	AudioManager, AudioManagerSettings, PlaySoundError, ResourceLimitReached,
+};
+
+use super::{TrackBuilder, TrackHandle};
+
+/**
+Renders the output of mixer tracks offline, without an audio backend.
+
+The audio is processed by an [`AudioManager`] using the
+[`MockBackend`], so tracks, effects, and sounds behave exactly as they
+would during live playback. Before creating the track to render, clocks
+and modulators can be added to the renderer's audio manager, which is
+returned by [`manager_mut`](Self::manager_mut), so values linked to them
+change the same way every time the track is rendered.
+
+Streaming sounds decode audio on a separate thread by default, which can
+fall behind offline rendering, so streaming sounds that are rendered should
+use [`StreamingSoundSettings::decode_on_audio_thread`](crate::sound::streaming::StreamingSoundSettings::decode_on_audio_thread).
+
+# Examples
+
+Render a track whose volume is linked to an LFO:
+
+```
+use std::time::Duration;
+use kira::{
+	modulator::lfo::LfoBuilder,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	track::{TrackBuilder, TrackRenderer},
+	Decibels, Frame, Mapping, Value, Easing,
+};
+
+let mut renderer = TrackRenderer::new(48_000);
+let manager = renderer.manager_mut();
+let lfo = manager.add_modulator(LfoBuilder::new())?;
+let mut track = manager.add_sub_track(TrackBuilder::new().volume(Value::FromModulator {
+	id: lfo.id(),
+	mapping: Mapping {
+		input_range: (-1.0, 1.0),
+		output_range: (Decibels(-12.0), Decibels(0.0)),
+		easing: Easing::Linear,
+	},
+}))?;
+track.play(StaticSoundData {
+	sample_rate: 48_000,
+	frames: (0..48_000).map(|_| Frame::from_mono(0.5)).collect(),
+	settings: StaticSoundSettings::default(),
+	slice: None,
+	metadata: None,
+})?;
+let rendered = renderer.render(&mut track, Duration::from_secs(1))?;
+assert_eq!(rendered.frames.len(), 48_000);
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+pub struct TrackRenderer {
+	manager: AudioManager<MockBackend>,
+	internal_buffer_size: usize,
+}
+
+impl TrackRenderer {
+	/// Creates a new [`TrackRenderer`] that renders audio at the given
+	/// sample rate.
+	#[must_use]
+	pub fn new(sample_rate: u32) -> Self {
+		let settings = AudioManagerSettings {
+			backend_settings: MockBackendSettings { sample_rate },
+			..Default::default()
+		};
+		let internal_buffer_size = settings.internal_buffer_size;
+		Self {
+			manager: AudioManager::new(settings).expect("the mock backend should always start"),
+			internal_buffer_size,
+		}
+	}
+
+	/// Returns the audio manager that renders the audio, which can be used
+	/// to add the tracks, sounds, clocks, and modulators to render.
+	#[must_use]
+	pub fn manager_mut(&mut self) -> &mut AudioManager<MockBackend> {
+		&mut self.manager
+	}
+
+	/**
+	Processes `duration` worth of audio and returns what the track output
+	during that time.
+
+	The track must belong to the renderer's audio manager. Everything
+	played by the audio manager advances while the audio is processed, so
+	rendering again continues where the last render left off.
+
+	Returns an error if the track already has the maximum number of
+	[captures](super::CaptureHandle) recording it.
+	*/
+	pub fn render(
+		&mut self,
+		track: &mut TrackHandle,
+		duration: Duration,
+	) -> Result<StaticSoundData, ResourceLimitReached> {
+		let sample_rate = self.manager.sample_rate();
+		let num_frames = (duration.as_secs_f64() * sample_rate as f64).round() as usize;
+		let mut capture = track.start_capture(num_frames.max(1))?;
+		let mut frames = Vec::with_capacity(num_frames);
+		while frames.len() < num_frames {
+			// the last chunk is shortened so the next render starts right
+			// where this one ends
+			let chunk_size = (num_frames - frames.len()).min(self.internal_buffer_size);
+			let backend = self.manager.backend_mut();
+			backend.on_start_processing();
+			backend.process_frames(chunk_size);
+			capture.drain(&mut frames);
+		}
+		Ok(StaticSoundData {
+			sample_rate,
+			frames: frames.into(),
+			settings: StaticSoundSettings::default(),
+			slice: None,
+			metadata: None,
+		})
+	}
+}
+
+/**
+Plays sounds on a track created from `builder` offline and returns
+`duration` worth of the track's output at the given sample rate.
+
+This is a shortcut for creating a [`TrackRenderer`], adding the track,
+playing the sounds on it, and rendering it. Values linked to modulators
+use their default values, since there are no modulators to link to. To
+render with modulators or clocks, use a [`TrackRenderer`] instead.
+
+# Examples
+
+```
+use std::time::Duration;
+use kira::{
+	effect::filter::FilterBuilder,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	track::{render_track, TrackBuilder},
+	Frame,
+};
+
+let sound = StaticSoundData {
+	sample_rate: 48_000,
+	frames: (0..48_000).map(|i| Frame::from_mono((i as f32 * 0.1).sin())).collect(),
+	settings: StaticSoundSettings::default(),
+	slice: None,
+	metadata: None,
+};
+let rendered = render_track(
+	TrackBuilder::new().with_effect(FilterBuilder::new().cutoff(500.0)),
+	[sound],
+	Duration::from_millis(500),
+	48_000,
+)?;
+assert_eq!(rendered.frames.len(), 24_000);
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+pub fn render_track<D: SoundData>(
+	builder: TrackBuilder,
+	sounds: impl IntoIterator<Item = D>,
+	duration: Duration,
+	sample_rate: u32,
+) -> Result<StaticSoundData, PlaySoundError<D::Error>> {
+	let mut renderer = TrackRenderer::new(sample_rate);
+	let mut track = renderer
+		.manager_mut()
+		.add_sub_track(builder)
+		.expect("a new audio manager should have room for a sub-track");
+	for sound in sounds {
+		track.play(sound)?;
+	}
+	Ok(renderer
+		.render(&mut track, duration)
+		.expect("a new track should have room for a capture"))
+}
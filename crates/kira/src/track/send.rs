@@ -31,6 +31,9 @@ pub(crate) struct SendTrack {
 	volume: Parameter<Decibels>,
 	set_volume_command_reader: CommandReader<ValueChangeCommand<Decibels>>,
 	effects: Vec<Box<dyn Effect>>,
+	/// Whether the track's effects are processed even when the track
+	/// receives no input and every effect is idle.
+	always_process: bool,
 	input: Vec<Frame>,
 	internal_buffer_size: usize,
 }
@@ -53,9 +56,14 @@ impl SendTrack {
 		self.shared.clone()
 	}
 
-	pub fn add_input(&mut self, input: &[Frame], volume: Decibels) {
-		for (input, added) in self.input.iter_mut().zip(input.iter().copied()) {
-			*input += added * volume.as_amplitude();
+	/// Adds audio from a track routed to this send track, fading between
+	/// the previous and current volume of the route over the chunk so
+	/// tweened and modulated send volumes change smoothly.
+	pub fn add_input(&mut self, input: &[Frame], volume: &Parameter<Decibels>) {
+		let num_frames = input.len();
+		for (i, (input, added)) in self.input.iter_mut().zip(input.iter().copied()).enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			*input += added * volume.interpolated_value(time_in_chunk).as_amplitude();
 		}
 	}
 
@@ -73,8 +81,15 @@ impl SendTrack {
 			*out_frame += input_frame;
 		}
 		self.input.fill(Frame::ZERO);
-		for effect in &mut self.effects {
-			effect.process(out, dt, info);
+		// skip the effects if the track received no input and none of the
+		// effects have anything left to output
+		let idle = !self.always_process
+			&& out.iter().all(|frame| *frame == Frame::ZERO)
+			&& self.effects.iter().all(|effect| effect.is_idle());
+		if !idle {
+			for effect in &mut self.effects {
+				effect.process(out, dt, info);
+			}
 		}
 		let num_frames = out.len();
 		for (i, frame) in out.iter_mut().enumerate() {
@@ -85,8 +100,23 @@ impl SendTrack {
 	}
 }
 
+/// Where the audio a track sends to a send track is taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SendTap {
+	/// The audio is taken before the track's volume and post-fader
+	/// effects are applied, so the amount sent doesn't change when the
+	/// track's volume changes.
+	PreFader,
+	/// The audio is taken after the track's volume and post-fader effects
+	/// are applied.
+	#[default]
+	PostFader,
+}
+
 pub(crate) struct SendTrackRoute {
 	pub(crate) volume: Parameter<Decibels>,
+	pub(crate) tap: SendTap,
 	pub(crate) set_volume_command_reader: CommandReader<ValueChangeCommand<Decibels>>,
 }
 
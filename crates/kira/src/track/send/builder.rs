@@ -14,6 +14,9 @@ pub struct SendTrackBuilder {
 	/// The effects that should be applied to the input audio
 	/// for this track.
 	pub(crate) effects: Vec<Box<dyn Effect>>,
+	/// Whether the track's effects are processed even when the track
+	/// receives no input.
+	pub(crate) always_process: bool,
 }
 
 impl SendTrackBuilder {
@@ -23,6 +26,7 @@ impl SendTrackBuilder {
 		Self {
 			volume: Value::Fixed(Decibels::IDENTITY),
 			effects: vec![],
+			always_process: false,
 		}
 	}
 
@@ -168,6 +172,22 @@ impl SendTrackBuilder {
 		self
 	}
 
+	/// Sets whether the track's effects are processed even when the track
+	/// receives no input. Defaults to `false`.
+	///
+	/// By default, a track that receives no input skips processing its
+	/// effects once every effect reports that it's idle (see
+	/// [`Effect::is_idle`](crate::effect::Effect::is_idle)). Effects that
+	/// generate sound from nothing should return `false` from `is_idle`,
+	/// but this can be used for effects that don't.
+	#[must_use = "This method consumes self and returns a modified SendTrackBuilder, so the return value should be used"]
+	pub fn always_process(self, always_process: bool) -> Self {
+		Self {
+			always_process,
+			..self
+		}
+	}
+
 	#[must_use]
 	pub(crate) fn build(
 		self,
@@ -175,12 +195,13 @@ impl SendTrackBuilder {
 		internal_buffer_size: usize,
 	) -> (SendTrack, SendTrackHandle) {
 		let (set_volume_command_writer, set_volume_command_reader) = command_writer_and_reader();
-		let shared = Arc::new(TrackShared::new());
+		let shared = Arc::new(TrackShared::new(None));
 		let track = SendTrack {
 			shared: shared.clone(),
 			volume: Parameter::new(self.volume, Decibels::IDENTITY),
 			set_volume_command_reader,
 			effects: self.effects,
+			always_process: self.always_process,
 			input: vec![Frame::ZERO; internal_buffer_size],
 			internal_buffer_size,
 		};
@@ -0,0 +1,311 @@
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex, Weak,
+	},
+};
+
+use atomic_arena::Key;
+
+use crate::{
+	backend::resources::{ResourceController, ResourceStorage},
+	effect::Effect,
+	info::Info,
+	Frame, ResourceLimitReached,
+};
+
+use super::{Track, TrackId, TrackShared};
+
+/// The maximum number of sidechain inputs a track's output can be sent
+/// to.
+const SIDECHAIN_OUTPUT_CAPACITY: usize = 16;
+
+/// Holds the most recent chunk of audio a track output for an effect
+/// that uses it as a sidechain input.
+///
+/// Both the track and the effect live on the audio thread, so each frame
+/// is stored as an atomic instead of locking the buffer.
+#[derive(Debug)]
+pub(crate) struct SidechainBuffer(Vec<AtomicU64>);
+
+impl SidechainBuffer {
+	#[must_use]
+	fn new(internal_buffer_size: usize) -> Self {
+		Self(
+			(0..internal_buffer_size)
+				.map(|_| AtomicU64::new(0))
+				.collect(),
+		)
+	}
+
+	fn write(&self, frames: &[Frame]) {
+		for (slot, frame) in self.0.iter().zip(frames) {
+			let bits = (u64::from(frame.left.to_bits()) << 32) | u64::from(frame.right.to_bits());
+			slot.store(bits, Ordering::Relaxed);
+		}
+	}
+
+	/// Moves the audio into `frames`, leaving silence behind so the audio
+	/// isn't read again if the track stops outputting audio.
+	pub fn take(&self, frames: &mut [Frame]) {
+		for (frame, slot) in frames.iter_mut().zip(&self.0) {
+			let bits = slot.swap(0, Ordering::Relaxed);
+			*frame = Frame {
+				left: f32::from_bits((bits >> 32) as u32),
+				right: f32::from_bits(bits as u32),
+			};
+		}
+	}
+}
+
+/// Sends a track's output to the effects that use it as a sidechain
+/// input.
+pub(crate) struct SidechainOutputs(ResourceStorage<Arc<SidechainBuffer>>);
+
+impl SidechainOutputs {
+	#[must_use]
+	pub fn new(internal_buffer_size: usize) -> (Self, SidechainOutputController) {
+		let (outputs, controller) = ResourceStorage::new(SIDECHAIN_OUTPUT_CAPACITY);
+		(
+			Self(outputs),
+			SidechainOutputController {
+				controller: Mutex::new(controller),
+				internal_buffer_size,
+			},
+		)
+	}
+
+	pub fn on_start_processing(&mut self) {
+		// the effect using the output has been dropped
+		self.0
+			.remove_and_add(|buffer| Arc::strong_count(buffer) == 1);
+	}
+
+	pub fn write(&mut self, out: &[Frame]) {
+		for (_, buffer) in &mut self.0 {
+			buffer.write(out);
+		}
+	}
+}
+
+/// Connects a track's output to new sidechain inputs.
+#[derive(Debug)]
+pub(crate) struct SidechainOutputController {
+	/// This is behind a mutex so effects can use a track as a sidechain
+	/// input without borrowing its handle mutably.
+	controller: Mutex<ResourceController<Arc<SidechainBuffer>>>,
+	internal_buffer_size: usize,
+}
+
+impl SidechainOutputController {
+	pub fn connect(&self) -> Result<Arc<SidechainBuffer>, ResourceLimitReached> {
+		let buffer = Arc::new(SidechainBuffer::new(self.internal_buffer_size));
+		self.controller
+			.lock()
+			.expect("sidechain output controller mutex poisoned")
+			.insert(buffer.clone())?;
+		Ok(buffer)
+	}
+}
+
+/// An effect that receives the output of another track as a sidechain
+/// input.
+pub(crate) struct SidechainEffect {
+	effect: Box<dyn Effect>,
+	buffer: Arc<SidechainBuffer>,
+	sidechain: Vec<Frame>,
+}
+
+impl SidechainEffect {
+	#[must_use]
+	pub fn new(effect: Box<dyn Effect>, buffer: Arc<SidechainBuffer>) -> Self {
+		Self {
+			effect,
+			buffer,
+			sidechain: vec![],
+		}
+	}
+}
+
+impl Effect for SidechainEffect {
+	fn init(&mut self, sample_rate: u32, internal_buffer_size: usize) {
+		self.sidechain = vec![Frame::ZERO; internal_buffer_size];
+		self.effect.init(sample_rate, internal_buffer_size);
+	}
+
+	fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.effect.on_change_sample_rate(sample_rate);
+	}
+
+	fn on_start_processing(&mut self) {
+		self.effect.on_start_processing();
+	}
+
+	fn is_idle(&self) -> bool {
+		self.effect.is_idle()
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.effect.latency_frames()
+	}
+
+	fn process(&mut self, input: &mut [Frame], dt: f64, info: &Info) {
+		let sidechain = &mut self.sidechain[..input.len()];
+		self.buffer.take(sidechain);
+		self.effect
+			.process_with_sidechain(input, sidechain, dt, info);
+	}
+}
+
+/**
+The order the sub-tracks of a track, or the top-level tracks, have to be
+processed in so sidechain inputs are processed before the effects that
+use them.
+
+Each pair of tracks is stored as the sub-tracks the two tracks are in (or
+are), since a sub-track is processed along with all of its own sub-tracks.
+The order is turned into a rank for each track, and tracks are processed
+from the lowest rank to the highest.
+*/
+#[derive(Debug, Default)]
+pub(crate) struct SubTrackOrder(Mutex<Vec<(Weak<TrackShared>, Weak<TrackShared>)>>);
+
+impl SubTrackOrder {
+	/// Makes `before` be processed before `after`. Returns `false` and
+	/// leaves the order unchanged if `after` already has to be processed
+	/// before `before`.
+	#[must_use]
+	fn insert(&self, before: &Arc<TrackShared>, after: &Arc<TrackShared>) -> bool {
+		let mut pairs = self.0.lock().expect("sub-track order mutex poisoned");
+		pairs.retain(|(before, after)| before.strong_count() > 0 && after.strong_count() > 0);
+		let ids = pairs
+			.iter()
+			.filter_map(|(before, after)| Some((before.upgrade()?.id(), after.upgrade()?.id())))
+			.collect::<Vec<_>>();
+		if depends_on(&ids, before.id(), after.id()) {
+			return false;
+		}
+		pairs.push((Arc::downgrade(before), Arc::downgrade(after)));
+		// each track's rank is the length of the longest chain of tracks
+		// that have to be processed before it
+		let pairs = pairs
+			.iter()
+			.filter_map(|(before, after)| Some((before.upgrade()?, after.upgrade()?)))
+			.collect::<Vec<_>>();
+		let mut ranks = HashMap::new();
+		for _ in 0..pairs.len() {
+			for (before, after) in &pairs {
+				let rank = ranks.get(&before.id()).copied().unwrap_or(0) + 1;
+				let after_rank = ranks.entry(after.id()).or_insert(0);
+				*after_rank = rank.max(*after_rank);
+			}
+		}
+		for (before, after) in &pairs {
+			for track in [before, after] {
+				track.set_processing_rank(ranks.get(&track.id()).copied().unwrap_or(0));
+			}
+		}
+		true
+	}
+}
+
+/// Returns `true` if `track` has to be processed after `other`.
+#[must_use]
+fn depends_on(pairs: &[(TrackId, TrackId)], track: TrackId, other: TrackId) -> bool {
+	let mut to_visit = vec![track];
+	let mut visited = vec![];
+	while let Some(id) = to_visit.pop() {
+		if id == other {
+			return true;
+		}
+		if visited.contains(&id) {
+			continue;
+		}
+		visited.push(id);
+		to_visit.extend(
+			pairs
+				.iter()
+				.filter(|(_, after)| *after == id)
+				.map(|(before, _)| *before),
+		);
+	}
+	false
+}
+
+/// Makes `source` be processed before the effects on `consumer`. Returns
+/// `false` if the output of `source` depends on the output of `consumer`.
+#[must_use]
+pub(crate) fn order_sidechain(
+	source: &Arc<TrackShared>,
+	consumer: &Arc<TrackShared>,
+	top_level_order: &SubTrackOrder,
+) -> bool {
+	let source_ancestors = ancestors(source);
+	let consumer_ancestors = ancestors(consumer);
+	// a track's sub-tracks are always processed before its effects
+	if source_ancestors
+		.iter()
+		.any(|track| track.id() == consumer.id())
+	{
+		return source.id() != consumer.id();
+	}
+	if consumer_ancestors
+		.iter()
+		.any(|track| track.id() == source.id())
+	{
+		return false;
+	}
+	for (i, ancestor) in consumer_ancestors.iter().enumerate().skip(1) {
+		if let Some(j) = source_ancestors
+			.iter()
+			.position(|track| track.id() == ancestor.id())
+		{
+			return ancestor
+				.sub_track_order()
+				.insert(&source_ancestors[j - 1], &consumer_ancestors[i - 1]);
+		}
+	}
+	top_level_order.insert(
+		source_ancestors
+			.last()
+			.expect("ancestors include the track itself"),
+		consumer_ancestors
+			.last()
+			.expect("ancestors include the track itself"),
+	)
+}
+
+/// Returns the track and each of its ancestors, starting with the track.
+#[must_use]
+fn ancestors(track: &Arc<TrackShared>) -> Vec<Arc<TrackShared>> {
+	let mut ancestors = vec![track.clone()];
+	while let Some(parent) = ancestors.last().and_then(|track| track.parent()) {
+		ancestors.push(parent.clone());
+	}
+	ancestors
+}
+
+/// Sorts the keys of `tracks` by the order the tracks have to be
+/// processed in. Tracks with the same rank keep the order they're stored
+/// in.
+pub(crate) fn update_processing_order(
+	order: &mut Vec<(u32, Key)>,
+	tracks: &ResourceStorage<Track>,
+) {
+	order.clear();
+	order.extend(
+		tracks
+			.iter()
+			.map(|(key, track)| (track.shared().processing_rank(), key)),
+	);
+	// this is an insertion sort so it doesn't allocate on the audio
+	// thread. there are usually only a few sub-tracks to sort.
+	for i in 1..order.len() {
+		let mut j = i;
+		while j > 0 && order[j - 1].0 > order[j].0 {
+			order.swap(j - 1, j);
+			j -= 1;
+		}
+	}
+}
@@ -0,0 +1,289 @@
+use std::sync::{
+	atomic::{AtomicU64, Ordering},
+	Arc,
+};
+
+use atomic_arena::Key;
+
+use crate::{backend::resources::ResourceStorage, info::Info, sound::Sound, Easing, Frame, Tween};
+
+use super::{SendTrack, TrackId};
+
+/// The maximum number of sounds that can start moving to a different
+/// track in a single chunk of audio. Any other requests are handled in
+/// the next chunk.
+pub(crate) const SOUND_MOVES_PER_CHUNK: usize = 16;
+
+/// A sound that has asked to be moved from one track to another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SoundMove {
+	pub source: TrackId,
+	pub key: Key,
+	pub destination: TrackId,
+	pub tween: Tween,
+}
+
+/// A sound that's done crossfading to another track and can be moved
+/// there, using the key that was reserved for it when the move started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FinishedSoundMove {
+	pub source: TrackId,
+	pub key: Key,
+	pub destination: TrackId,
+	pub destination_key: Key,
+}
+
+/// The sounds playing on a track, along with the audio that sounds on
+/// other tracks hand off to this track while they're moving to or from it.
+pub(crate) struct TrackSounds {
+	pub(crate) sounds: ResourceStorage<Box<dyn Sound>>,
+	crossfades: Vec<SoundCrossfade>,
+	crossfade_input: Arc<CrossfadeInput>,
+}
+
+impl TrackSounds {
+	#[must_use]
+	pub fn new(sounds: ResourceStorage<Box<dyn Sound>>, internal_buffer_size: usize) -> Self {
+		let capacity = sounds.resources.capacity();
+		Self {
+			sounds,
+			crossfades: Vec::with_capacity(capacity),
+			crossfade_input: Arc::new(CrossfadeInput::new(internal_buffer_size)),
+		}
+	}
+
+	pub fn on_start_processing(&mut self) {
+		self.sounds.remove_and_add(|sound| sound.finished());
+		for (_, sound) in &mut self.sounds {
+			sound.on_start_processing();
+		}
+		let sounds = &self.sounds;
+		self.crossfades.retain(|crossfade| {
+			!crossfade.finished() && sounds.resources.get(crossfade.key).is_some()
+		});
+		// audio handed off by a track that was processed last chunk
+		// belongs to a chunk that's already over
+		self.crossfade_input.clear();
+	}
+
+	/// Mixes the output of every sound into `out`.
+	///
+	/// Sounds that are moving to or from another track are split between
+	/// this track and the other track, and the audio other tracks have
+	/// handed off to this track is mixed in afterwards.
+	pub fn process(
+		&mut self,
+		out: &mut [Frame],
+		temp_buffer: &mut [Frame],
+		dt: f64,
+		info: &Info,
+		mut send_tracks: Option<&mut ResourceStorage<SendTrack>>,
+	) {
+		let temp_buffer = &mut temp_buffer[..out.len()];
+		for (key, sound) in &mut self.sounds {
+			sound.process(temp_buffer, dt, info);
+			if let Some(send_tracks) = &mut send_tracks {
+				for (send_track_id, volume) in sound.sends() {
+					let Some(send_track) = send_tracks.get_mut(send_track_id.0) else {
+						continue;
+					};
+					send_track.add_input(temp_buffer, volume);
+				}
+			}
+			match self
+				.crossfades
+				.iter_mut()
+				.find(|crossfade| crossfade.key == key)
+			{
+				Some(crossfade) => crossfade.process(out, temp_buffer, dt),
+				None => {
+					for (summed_out, sound_out) in out.iter_mut().zip(temp_buffer.iter().copied()) {
+						*summed_out += sound_out;
+					}
+				}
+			}
+			temp_buffer.fill(Frame::ZERO);
+		}
+		self.crossfade_input.take_into(out);
+	}
+
+	#[must_use]
+	pub fn crossfade_input(&self) -> Arc<CrossfadeInput> {
+		self.crossfade_input.clone()
+	}
+
+	/// Starts splitting the output of the sound with the given key between
+	/// this track and the track that owns `partner_input`.
+	///
+	/// If `destination` is `Some`, the sound is moving away from this track
+	/// and should be moved to the destination track with the reserved key
+	/// once the crossfade finishes. Otherwise, the sound has already been
+	/// moved to this track.
+	pub fn start_crossfade(
+		&mut self,
+		key: Key,
+		partner_input: Arc<CrossfadeInput>,
+		destination: Option<(TrackId, Key)>,
+		tween: Tween,
+	) {
+		if self.crossfades.len() == self.crossfades.capacity() {
+			return;
+		}
+		self.crossfades.push(SoundCrossfade {
+			key,
+			partner_input,
+			destination,
+			duration: tween.duration.as_secs_f64(),
+			easing: tween.easing,
+			elapsed: 0.0,
+		});
+	}
+
+	/// Takes the requests of sounds that want to be moved to a different
+	/// track, until `moves` is full. Sounds that are already moving aren't
+	/// checked until they've finished moving.
+	pub fn collect_moves(&mut self, id: TrackId, moves: &mut Vec<SoundMove>) {
+		for (key, sound) in &mut self.sounds {
+			if moves.len() == moves.capacity() {
+				return;
+			}
+			if self.crossfades.iter().any(|crossfade| crossfade.key == key) {
+				continue;
+			}
+			if let Some((destination, tween)) = sound.take_requested_track() {
+				moves.push(SoundMove {
+					source: id,
+					key,
+					destination,
+					tween,
+				});
+			}
+		}
+	}
+
+	/// Returns a sound on this track that's done crossfading to another
+	/// track and stops crossfading it.
+	///
+	/// Sounds that have finished or are on a track that's about to be
+	/// removed (`removing`) are moved right away, so the key reserved for
+	/// them on the destination track is always used.
+	#[must_use]
+	pub fn take_finished_move(&mut self, id: TrackId, removing: bool) -> Option<FinishedSoundMove> {
+		let sounds = &self.sounds;
+		let index = self.crossfades.iter().position(|crossfade| {
+			crossfade.destination.is_some()
+				&& (removing
+					|| crossfade.finished()
+					|| sounds
+						.resources
+						.get(crossfade.key)
+						.is_none_or(|sound| sound.finished()))
+		})?;
+		let crossfade = self.crossfades.swap_remove(index);
+		let (destination, destination_key) = crossfade.destination?;
+		Some(FinishedSoundMove {
+			source: id,
+			key: crossfade.key,
+			destination,
+			destination_key,
+		})
+	}
+}
+
+/// Splits the output of a sound that's moving from one track to another
+/// between the two tracks.
+struct SoundCrossfade {
+	/// The key of the sound on the track that's processing it.
+	key: Key,
+	/// Receives the part of the sound's output that belongs to the
+	/// other track.
+	partner_input: Arc<CrossfadeInput>,
+	/// The track the sound is moving to and the key reserved for it
+	/// there, or `None` if the sound is already on that track.
+	destination: Option<(TrackId, Key)>,
+	duration: f64,
+	easing: Easing,
+	elapsed: f64,
+}
+
+impl SoundCrossfade {
+	fn process(&mut self, out: &mut [Frame], sound_out: &[Frame], dt: f64) {
+		for (i, (summed_out, sound_out)) in
+			out.iter_mut().zip(sound_out.iter().copied()).enumerate()
+		{
+			self.elapsed += dt;
+			let progress = if self.duration > 0.0 {
+				(self.elapsed / self.duration).min(1.0)
+			} else {
+				1.0
+			};
+			let destination_volume = self.easing.apply(progress) as f32;
+			let volume = if self.destination.is_some() {
+				1.0 - destination_volume
+			} else {
+				destination_volume
+			};
+			*summed_out += sound_out * volume;
+			self.partner_input.add(i, sound_out * (1.0 - volume));
+		}
+	}
+
+	#[must_use]
+	fn finished(&self) -> bool {
+		self.elapsed >= self.duration
+	}
+}
+
+/// Holds the audio that sounds on other tracks hand off to a track
+/// while they're moving to or from it.
+///
+/// The sounds are processed by other tracks on the audio thread, so
+/// each frame is stored as an atomic, like a
+/// [`SidechainBuffer`](super::SidechainBuffer).
+#[derive(Debug)]
+pub(crate) struct CrossfadeInput(Vec<AtomicU64>);
+
+impl CrossfadeInput {
+	#[must_use]
+	fn new(internal_buffer_size: usize) -> Self {
+		Self(
+			(0..internal_buffer_size)
+				.map(|_| AtomicU64::new(0))
+				.collect(),
+		)
+	}
+
+	fn add(&self, index: usize, frame: Frame) {
+		let Some(slot) = self.0.get(index) else {
+			return;
+		};
+		let sum = decode_frame(slot.load(Ordering::Relaxed)) + frame;
+		slot.store(encode_frame(sum), Ordering::Relaxed);
+	}
+
+	/// Mixes the handed off audio into `out` and clears it.
+	fn take_into(&self, out: &mut [Frame]) {
+		for (frame, slot) in out.iter_mut().zip(&self.0) {
+			*frame += decode_frame(slot.swap(0, Ordering::Relaxed));
+		}
+	}
+
+	fn clear(&self) {
+		for slot in &self.0 {
+			slot.store(0, Ordering::Relaxed);
+		}
+	}
+}
+
+#[must_use]
+fn encode_frame(frame: Frame) -> u64 {
+	(u64::from(frame.left.to_bits()) << 32) | u64::from(frame.right.to_bits())
+}
+
+#[must_use]
+fn decode_frame(bits: u64) -> Frame {
+	Frame {
+		left: f32::from_bits((bits >> 32) as u32),
+		right: f32::from_bits(bits as u32),
+	}
+}
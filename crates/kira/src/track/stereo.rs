@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod test;
+
+use crate::{
+	command::{CommandReader, ValueChangeCommand},
+	info::Info,
+	Frame, Panning, Parameter, Value,
+};
+
+/// The settings that place a track's audio in the stereo field.
+pub(crate) struct StereoPlacement {
+	panning: Parameter<Panning>,
+	/// How far apart the left and right channels are spread, from `0.0`
+	/// (mono) upwards.
+	width: Parameter<f32>,
+	/// How much one channel is turned down, from `-1.0` (the right channel
+	/// is silent) to `1.0` (the left channel is silent).
+	balance: Parameter<f32>,
+}
+
+impl StereoPlacement {
+	#[must_use]
+	pub fn new(panning: Value<Panning>, width: Value<f32>, balance: Value<f32>) -> Self {
+		Self {
+			panning: Parameter::new(panning, Panning::CENTER),
+			width: Parameter::new(width, 1.0),
+			balance: Parameter::new(balance, 0.0),
+		}
+	}
+
+	pub fn read_commands(
+		&mut self,
+		panning: &mut CommandReader<ValueChangeCommand<Panning>>,
+		width: &mut CommandReader<ValueChangeCommand<f32>>,
+		balance: &mut CommandReader<ValueChangeCommand<f32>>,
+	) {
+		self.panning.read_command(panning);
+		self.width.read_command(width);
+		self.balance.read_command(balance);
+	}
+
+	pub fn update(&mut self, dt: f64, info: &Info) {
+		self.panning.update(dt, info);
+		self.width.update(dt, info);
+		self.balance.update(dt, info);
+	}
+
+	pub fn process(&self, out: &mut [Frame]) {
+		if self.is_neutral() {
+			return;
+		}
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			*frame = balanced(
+				widened(*frame, self.width.interpolated_value(time_in_chunk))
+					.panned(self.panning.interpolated_value(time_in_chunk)),
+				self.balance.interpolated_value(time_in_chunk),
+			);
+		}
+	}
+
+	/// Returns `true` if none of the settings change the audio during the
+	/// current chunk.
+	#[must_use]
+	#[allow(clippy::float_cmp)]
+	fn is_neutral(&self) -> bool {
+		[self.panning.previous_value(), self.panning.value()]
+			.iter()
+			.all(|panning| *panning == Panning::CENTER)
+			&& [self.width.previous_value(), self.width.value()]
+				.iter()
+				.all(|width| *width == 1.0)
+			&& [self.balance.previous_value(), self.balance.value()]
+				.iter()
+				.all(|balance| *balance == 0.0)
+	}
+}
+
+/// Scales the difference between the channels (the side signal) without
+/// changing their sum (the mid signal), so the frame sounds the same when
+/// it's mixed down to mono.
+#[must_use]
+fn widened(frame: Frame, width: f32) -> Frame {
+	let mid = (frame.left + frame.right) * 0.5;
+	let side = (frame.left - frame.right) * 0.5 * width.max(0.0);
+	Frame::new(mid + side, mid - side)
+}
+
+/// Turns down the channel on the opposite side of the balance and leaves
+/// the other channel as it is.
+#[must_use]
+fn balanced(frame: Frame, balance: f32) -> Frame {
+	let balance = balance.clamp(-1.0, 1.0);
+	Frame::new(
+		frame.left * (1.0 - balance.max(0.0)),
+		frame.right * (1.0 + balance.min(0.0)),
+	)
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use crate::{
+	command::{command_writer_and_reader, ValueChangeCommand},
+	info::MockInfoBuilder,
+	Frame, Panning, Tween, Value,
+};
+
+use super::{balanced, widened, StereoPlacement};
+
+/// Tests that a width of 0 mixes the audio down to mono, a width of 1
+/// leaves it unchanged, and wider settings never change the mono mix.
+#[test]
+fn width_keeps_mono_mix() {
+	let frame = Frame::new(1.0, -0.5);
+	assert_eq!(widened(frame, 0.0), Frame::from_mono(0.25));
+	assert_eq!(widened(frame, 1.0), frame);
+	for width in [0.5, 2.0, 10.0] {
+		let widened = widened(frame, width);
+		assert_eq!(widened.left + widened.right, frame.left + frame.right);
+	}
+	assert_eq!(widened(frame, 2.0), Frame::new(1.75, -1.25));
+	// negative widths would swap the channels, so they're treated as 0
+	assert_eq!(widened(frame, -1.0), Frame::from_mono(0.25));
+}
+
+/// Tests that the balance only turns down one channel.
+#[test]
+fn balance_turns_down_one_channel() {
+	let frame = Frame::new(1.0, 1.0);
+	assert_eq!(balanced(frame, 0.0), frame);
+	assert_eq!(balanced(frame, 0.25), Frame::new(0.75, 1.0));
+	assert_eq!(balanced(frame, -1.0), Frame::new(1.0, 0.0));
+	assert_eq!(balanced(frame, 2.0), Frame::new(0.0, 1.0));
+}
+
+/// Tests that the audio is left untouched with the default settings.
+#[test]
+fn default_settings_leave_audio_unchanged() {
+	let placement = StereoPlacement::new(
+		Value::Fixed(Panning::CENTER),
+		Value::Fixed(1.0),
+		Value::Fixed(0.0),
+	);
+	let mut out = [Frame::new(1.0, -0.5), Frame::new(0.25, 0.75)];
+	let expected = out;
+	placement.process(&mut out);
+	assert_eq!(out, expected);
+}
+
+/// Tests that changes to the settings take effect after the next update.
+#[test]
+fn reads_commands() {
+	let mut placement = StereoPlacement::new(
+		Value::Fixed(Panning::CENTER),
+		Value::Fixed(1.0),
+		Value::Fixed(0.0),
+	);
+	let (_, mut panning) = command_writer_and_reader();
+	let (mut width_writer, mut width) = command_writer_and_reader();
+	let (_, mut balance) = command_writer_and_reader();
+	width_writer.write(ValueChangeCommand {
+		target: Value::Fixed(0.0),
+		tween: Tween {
+			duration: Duration::ZERO,
+			..Default::default()
+		},
+	});
+	placement.read_commands(&mut panning, &mut width, &mut balance);
+	let info = MockInfoBuilder::new().build();
+	placement.update(1.0, &info);
+	placement.update(1.0, &info);
+	let mut out = [Frame::new(1.0, 0.0)];
+	placement.process(&mut out);
+	assert_eq!(out, [Frame::from_mono(0.5)]);
+}
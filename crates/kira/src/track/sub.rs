@@ -1,32 +1,53 @@
+mod added_effect;
 mod builder;
+mod ducker;
 mod handle;
+mod names;
 mod spatial_builder;
 mod spatial_handle;
+mod template;
 
 pub use builder::*;
 pub use handle::*;
 pub use spatial_builder::*;
 pub use spatial_handle::*;
 
-use std::{error::Error, f32::consts::FRAC_PI_8, fmt::Display, sync::Arc};
+pub(crate) use added_effect::*;
+pub(crate) use ducker::*;
+pub(crate) use names::*;
+pub(crate) use template::*;
 
+use std::{error::Error, f32::consts::FRAC_PI_8, fmt::Display, sync::Arc, time::Duration};
+
+use atomic_arena::Key;
 use glam::{Quat, Vec3};
 
 use crate::{
 	backend::resources::{
-		clocks::Clocks, listeners::Listeners, modulators::Modulators, ResourceStorage,
+		clocks::Clocks, groups::Groups, listeners::Listeners, modulators::Modulators,
+		voice_groups::VoiceGroups, ResourceStorage,
 	},
 	command::ValueChangeCommand,
 	command_writers_and_readers,
-	effect::Effect,
+	effect::{Effect, EffectId},
+	group::GroupPlaybackCommand,
 	info::{Info, SpatialTrackInfo},
 	listener::ListenerId,
 	playback_state_manager::PlaybackStateManager,
-	sound::Sound,
-	Decibels, Easing, Frame, Parameter, StartTime, Tween, Tweenable,
+	sound::PlaybackState,
+	Decibels, Easing, Frame, Panning, Parameter, PlaybackRate, ResourceLimitReached, ResourceUsage,
+	StartTime, Tween, Tweenable, Value,
+};
+
+use super::{
+	align_latency, update_processing_order, Captures, ClippingBehavior, FinishedSoundMove,
+	LatencyCompensation, LevelMeter, Muffle, SendTap, SendTrack, SendTrackId, SendTrackRoute,
+	SidechainOutputs, SoundMove, StereoPlacement, TrackId, TrackShared, TrackSounds,
 };
 
-use super::{SendTrack, SendTrackId, SendTrackRoute, TrackShared};
+/// How long it takes a track to fade out when it's muted and fade back
+/// in when it's unmuted.
+const MUTE_FADE_DURATION: Duration = Duration::from_millis(5);
 
 /// An error that's returned when trying to change the volume of a track route
 /// that did not exist originally.
@@ -41,23 +62,146 @@ impl Display for NonexistentRoute {
 
 impl Error for NonexistentRoute {}
 
+/// An error that's returned when trying to reorder the effects on a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetEffectOrderError {
+	/// The effect isn't on the track, either because it was added to a
+	/// different track or because it was removed.
+	NonexistentEffect(EffectId),
+	/// The effect was listed more than once.
+	DuplicateEffect(EffectId),
+	/// The effect is on the track but wasn't listed.
+	MissingEffect(EffectId),
+	/// The track's effects were reordered too many times before the audio
+	/// thread could apply the changes.
+	CommandQueueFull,
+}
+
+impl Display for SetEffectOrderError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SetEffectOrderError::NonexistentEffect(id) => {
+				write!(f, "The effect {:?} is not on the track", id)
+			}
+			SetEffectOrderError::DuplicateEffect(id) => {
+				write!(f, "The effect {:?} was listed more than once", id)
+			}
+			SetEffectOrderError::MissingEffect(id) => {
+				write!(f, "The effect {:?} is on the track but was not listed", id)
+			}
+			SetEffectOrderError::CommandQueueFull => f.write_str(
+				"The effects were reordered too many times before the audio thread could apply the changes",
+			),
+		}
+	}
+}
+
+impl Error for SetEffectOrderError {}
+
+/// An error that's returned when a sub-track can't be added.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AddSubTrackError {
+	/// The maximum number of sub-tracks has been reached.
+	ResourceLimitReached,
+	/// Another track already has the name given to the track. This holds
+	/// the full name of the track, including the names of its parent tracks.
+	DuplicateName(String),
+}
+
+impl Display for AddSubTrackError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			AddSubTrackError::ResourceLimitReached => f.write_str(
+				"Could not add a sub-track because the maximum number of sub-tracks has been reached",
+			),
+			AddSubTrackError::DuplicateName(name) => {
+				write!(f, "Another track is already named {:?}", name)
+			}
+		}
+	}
+}
+
+impl Error for AddSubTrackError {}
+
+impl From<ResourceLimitReached> for AddSubTrackError {
+	fn from(_: ResourceLimitReached) -> Self {
+		AddSubTrackError::ResourceLimitReached
+	}
+}
+
 pub(crate) struct Track {
 	shared: Arc<TrackShared>,
 	command_readers: CommandReaders,
 	volume: Parameter<Decibels>,
-	sounds: ResourceStorage<Box<dyn Sound>>,
+	/// The playback rate of the sounds on the track, which is multiplied
+	/// by the playback rate of the track's parent.
+	playback_rate: Parameter<PlaybackRate>,
+	/// The track's panning, stereo width, and balance.
+	stereo_placement: StereoPlacement,
+	/// Low-pass filters and turns down the track's audio after its effects.
+	muffle: Muffle,
+	sounds: TrackSounds,
 	sub_tracks: ResourceStorage<Track>,
+	/// The keys of the sub-tracks in the order they're processed in, which
+	/// makes sure tracks used as sidechain inputs are processed before the
+	/// effects that use them.
+	sub_track_order: Vec<(u32, Key)>,
 	effects: Vec<Box<dyn Effect>>,
+	/// Effects added after the track was created, which are applied after
+	/// the effects from the track's builder.
+	added_effects: AddedEffects,
+	/// The effects that are applied after the track's volume.
+	post_fader_effects: Vec<Box<dyn Effect>>,
+	/// Turns the track down while another track is outputting audio.
+	ducker: Option<Ducker>,
 	sends: Vec<(SendTrackId, SendTrackRoute)>,
 	persist_until_sounds_finish: bool,
+	/// Whether the track mixes its input down to mono and processes its
+	/// effects with [`Effect::process_mono`].
+	mono: bool,
+	/// Whether the track is fading out before it's removed, either because
+	/// its handle's `remove` method was called or because it's a
+	/// sub-track of a track that's being removed.
+	removing: bool,
+	/// Whether the track's effects are processed even when the track
+	/// receives no input and every effect is idle.
+	always_process: bool,
 	spatial_data: Option<SpatialData>,
 	playback_state_manager: PlaybackStateManager,
+	muted: bool,
+	/// Whether the track is silenced because a track that isn't one of
+	/// its ancestors or descendants is soloed.
+	soloed_out: bool,
+	/// Fades the track between `1.0` and `0.0` when it's muted or
+	/// unmuted. This is separate from the track's volume so unmuting
+	/// the track restores its volume exactly.
+	mute_volume: Parameter<f32>,
+	level_meter: LevelMeter,
+	/// What the track does with samples that go above `1.0` or below
+	/// `-1.0`, which is applied after the track's levels are measured.
+	clipping_behavior: ClippingBehavior,
+	captures: Captures,
+	sidechain_outputs: SidechainOutputs,
+	/// Delays the track's output so it lines up with its sibling tracks.
+	latency_compensation: LatencyCompensation,
+	/// The latency the track's effects and sub-tracks introduce (in frames).
+	latency: usize,
+	/// How many commands have been sent to every sound on the track.
+	/// This is used to number each command so sounds can tell which ones
+	/// they've already applied.
+	num_sound_playback_commands: u64,
+	sound_playback_command: Option<(u64, GroupPlaybackCommand)>,
 	temp_buffer: Vec<Frame>,
 	internal_buffer_size: usize,
 }
 
 impl Track {
 	fn update_shared_playback_state(&mut self) {
+		// the shared state can't represent a stopped track, and the track
+		// can't be resumed once it's being removed anyway
+		if self.removing {
+			return;
+		}
 		self.shared
 			.set_state(self.playback_state_manager.playback_state());
 	}
@@ -73,19 +217,63 @@ impl Track {
 		self.update_shared_playback_state();
 	}
 
+	/// Fades out the track and stops the sounds on it and its sub-tracks
+	/// so the track can be removed.
+	fn start_removal(&mut self, fade_out_tween: Tween) {
+		self.removing = true;
+		self.playback_state_manager.stop(fade_out_tween);
+		self.apply_sound_playback_command(GroupPlaybackCommand::Stop(fade_out_tween), false);
+		for (_, sub_track) in &mut self.sub_tracks {
+			sub_track.start_removal(fade_out_tween);
+		}
+	}
+
+	fn set_silenced(&mut self, muted: bool, soloed_out: bool) {
+		let was_silenced = self.muted || self.soloed_out;
+		self.muted = muted;
+		self.soloed_out = soloed_out;
+		let silenced = muted || soloed_out;
+		if silenced != was_silenced {
+			self.mute_volume.set(
+				Value::Fixed(if silenced { 0.0 } else { 1.0 }),
+				Tween {
+					duration: MUTE_FADE_DURATION,
+					..Default::default()
+				},
+			);
+		}
+	}
+
+	fn apply_sound_playback_command(
+		&mut self,
+		command: GroupPlaybackCommand,
+		include_sub_tracks: bool,
+	) {
+		self.num_sound_playback_commands += 1;
+		self.sound_playback_command = Some((self.num_sound_playback_commands, command));
+		if include_sub_tracks {
+			for (_, sub_track) in &mut self.sub_tracks {
+				sub_track.apply_sound_playback_command(command, true);
+			}
+		}
+	}
+
 	pub fn init_effects(&mut self, sample_rate: u32) {
-		for effect in &mut self.effects {
+		for effect in self.effects.iter_mut().chain(&mut self.post_fader_effects) {
 			effect.init(sample_rate, self.internal_buffer_size);
 		}
+		self.latency_compensation.init(sample_rate);
 		for (_, sub_track) in &mut self.sub_tracks {
 			sub_track.init_effects(sample_rate);
 		}
 	}
 
 	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
-		for effect in &mut self.effects {
+		for effect in self.effects.iter_mut().chain(&mut self.post_fader_effects) {
 			effect.on_change_sample_rate(sample_rate);
 		}
+		self.latency_compensation.init(sample_rate);
+		self.added_effects.on_change_sample_rate(sample_rate);
 		for (_, sub_track) in &mut self.sub_tracks {
 			sub_track.on_change_sample_rate(sample_rate);
 		}
@@ -96,7 +284,106 @@ impl Track {
 		self.shared.clone()
 	}
 
+	/// Takes the requests of sounds on this track and its sub-tracks that
+	/// want to be moved to a different track, until `moves` is full.
+	pub fn collect_sound_moves(&mut self, moves: &mut Vec<SoundMove>) {
+		self.sounds.collect_moves(self.shared.id(), moves);
+		for (_, sub_track) in &mut self.sub_tracks {
+			sub_track.collect_sound_moves(moves);
+		}
+	}
+
+	/// Finds a sound on this track or one of its sub-tracks that's done
+	/// crossfading to a different track.
+	#[must_use]
+	pub fn take_finished_sound_move(&mut self) -> Option<FinishedSoundMove> {
+		let removing = self.should_be_removed();
+		self.sounds
+			.take_finished_move(self.shared.id(), removing)
+			.or_else(|| {
+				self.sub_tracks
+					.iter_mut()
+					.find_map(|(_, sub_track)| sub_track.take_finished_sound_move())
+			})
+	}
+
+	/// Returns the position of the track with the given ID in the order
+	/// tracks are processed in, counting from `index`. If this track and
+	/// its sub-tracks don't have the ID, `index` is advanced past them.
+	#[must_use]
+	pub fn processing_index(&self, id: TrackId, index: &mut usize) -> Option<usize> {
+		for (_, key) in &self.sub_track_order {
+			let Some(sub_track) = self.sub_tracks.resources.get(*key) else {
+				continue;
+			};
+			if let Some(found) = sub_track.processing_index(id, index) {
+				return Some(found);
+			}
+		}
+		if self.shared.id() == id {
+			return Some(*index);
+		}
+		*index += 1;
+		None
+	}
+
+	/// Returns `true` if this track or one of its sub-tracks has the given ID
+	/// and hasn't been removed by the user.
+	#[must_use]
+	pub fn contains_live_track(&self, id: TrackId) -> bool {
+		if self.shared.id() == id {
+			return !self.shared.is_marked_for_removal();
+		}
+		self.sub_tracks
+			.iter()
+			.any(|(_, sub_track)| sub_track.contains_live_track(id))
+	}
+
+	/// Returns the sounds of this track or the sub-track with the given ID.
+	#[must_use]
+	pub fn sounds_mut(&mut self, id: TrackId) -> Option<&mut TrackSounds> {
+		if self.shared.id() == id {
+			return Some(&mut self.sounds);
+		}
+		self.sub_tracks
+			.iter_mut()
+			.find_map(|(_, sub_track)| sub_track.sounds_mut(id))
+	}
+
+	/// Adds the resources used by the sounds on this track and its
+	/// sub-tracks.
+	pub fn add_resource_usage(&self, resource_usage: &mut ResourceUsage) {
+		resource_usage.add_sounds(&self.sounds.sounds);
+		for (_, sub_track) in self.sub_tracks.iter() {
+			sub_track.add_resource_usage(resource_usage);
+		}
+	}
+
+	/// Silences this track if a track is soloed and this track isn't that
+	/// track or one of its ancestors or descendants. Returns `true` if
+	/// this track or one of its sub-tracks is the soloed track.
+	pub fn update_solo(&mut self, soloed_track: Option<TrackId>, in_soloed_track: bool) -> bool {
+		let is_soloed = soloed_track == Some(self.shared.id());
+		let in_soloed_track = in_soloed_track || is_soloed;
+		let mut contains_soloed_track = is_soloed;
+		for (_, sub_track) in &mut self.sub_tracks {
+			contains_soloed_track |= sub_track.update_solo(soloed_track, in_soloed_track);
+		}
+		self.set_silenced(
+			self.muted,
+			soloed_track.is_some() && !in_soloed_track && !contains_soloed_track,
+		);
+		contains_soloed_track
+	}
+
 	pub fn should_be_removed(&self) -> bool {
+		if self.removing {
+			return self.playback_state_manager.playback_state() == PlaybackState::Stopped;
+		}
+		// wait for the track to read the command that starts fading it out
+		if self.shared.is_removal_requested() {
+			return false;
+		}
 		if self
 			.sub_tracks
 			.iter()
@@ -105,7 +392,7 @@ impl Track {
 			return false;
 		}
 		if self.persist_until_sounds_finish {
-			self.shared().is_marked_for_removal() && self.sounds.is_empty()
+			self.shared().is_marked_for_removal() && self.sounds.sounds.is_empty()
 		} else {
 			self.shared().is_marked_for_removal()
 		}
@@ -113,18 +400,32 @@ impl Track {
 
 	pub fn on_start_processing(&mut self) {
 		self.read_commands();
-		self.sounds.remove_and_add(|sound| sound.finished());
-		for (_, sound) in &mut self.sounds {
-			sound.on_start_processing();
-		}
+		self.sounds.on_start_processing();
+		self.shared.sound_counts().record(&self.sounds.sounds);
 		self.sub_tracks
 			.remove_and_add(|sub_track| sub_track.should_be_removed());
+		update_processing_order(&mut self.sub_track_order, &self.sub_tracks);
 		for (_, sub_track) in &mut self.sub_tracks {
 			sub_track.on_start_processing();
 		}
 		for effect in &mut self.effects {
 			effect.on_start_processing();
 		}
+		self.added_effects.on_start_processing();
+		for effect in &mut self.post_fader_effects {
+			effect.on_start_processing();
+		}
+		self.latency = align_latency(&mut self.sub_tracks)
+			+ self
+				.effects
+				.iter()
+				.chain(&self.post_fader_effects)
+				.map(|effect| effect.latency_frames())
+				.sum::<usize>()
+			+ self.added_effects.latency_frames();
+		self.shared.set_latency(self.latency);
+		self.captures.on_start_processing();
+		self.sidechain_outputs.on_start_processing();
 	}
 
 	#[allow(clippy::too_many_arguments)]
@@ -135,7 +436,10 @@ impl Track {
 		clocks: &Clocks,
 		modulators: &Modulators,
 		listeners: &Listeners,
+		voice_groups: &VoiceGroups,
+		groups: &Groups,
 		parent_spatial_track_info: Option<SpatialTrackInfo>,
+		parent_playback_rate: f64,
 		send_tracks: &mut ResourceStorage<SendTrack>,
 	) {
 		// get info
@@ -151,11 +455,21 @@ impl Track {
 			&clocks.0.resources,
 			&modulators.0.resources,
 			&listeners.0.resources,
+			&voice_groups.0.resources,
+			&groups.0.resources,
 			spatial_track_info,
-		);
+			dt,
+		)
+		.with_sound_playback_command(self.sound_playback_command);
+		self.playback_rate.update(dt * out.len() as f64, &info);
+		let playback_rate = (parent_playback_rate * self.playback_rate.value().0).max(0.0);
+		let info = info.with_track_playback_rate(playback_rate);
 
 		// update volume parameters
 		self.volume.update(dt * out.len() as f64, &info);
+		self.mute_volume.update(dt * out.len() as f64, &info);
+		self.stereo_placement.update(dt * out.len() as f64, &info);
+		self.muffle.update(dt * out.len() as f64, &info);
 		for (_, route) in &mut self.sends {
 			route.volume.update(dt * out.len() as f64, &info);
 		}
@@ -167,22 +481,34 @@ impl Track {
 		if changed_playback_state {
 			self.update_shared_playback_state();
 		}
-		if !self.playback_state_manager.playback_state().is_advancing() {
+		// tracks that are being removed keep processing their sounds so
+		// the sounds can finish stopping
+		if !self.removing && !self.playback_state_manager.playback_state().is_advancing() {
 			out.fill(Frame::ZERO);
+			self.level_meter.measure(out, dt, self.shared.levels());
+			self.captures.record(out);
+			self.sidechain_outputs.write(out);
+			self.latency_compensation.process(out);
 			return;
 		}
 
 		let num_frames = out.len();
 
 		// process sub tracks
-		for (_, sub_track) in &mut self.sub_tracks {
+		for (_, key) in &self.sub_track_order {
+			let Some(sub_track) = self.sub_tracks.get_mut(*key) else {
+				continue;
+			};
 			sub_track.process(
 				&mut self.temp_buffer[..out.len()],
 				dt,
 				clocks,
 				modulators,
 				listeners,
+				voice_groups,
+				groups,
 				spatial_track_info,
+				playback_rate,
 				send_tracks,
 			);
 			for (summed_out, track_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
@@ -192,18 +518,42 @@ impl Track {
 		}
 
 		// process sounds
-		for (_, sound) in &mut self.sounds {
-			sound.process(&mut self.temp_buffer[..out.len()], dt, &info);
-			for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
-				*summed_out += sound_out;
+		self.sounds.process(
+			out,
+			&mut self.temp_buffer,
+			dt,
+			&info,
+			Some(&mut *send_tracks),
+		);
+
+		if self.mono {
+			for frame in out.iter_mut() {
+				*frame = frame.as_mono();
 			}
-			self.temp_buffer.fill(Frame::ZERO);
 		}
 
-		// apply effects
-		for effect in &mut self.effects {
-			effect.process(out, dt, &info);
+		// apply effects, unless the track received no input and none of
+		// the effects have anything left to output
+		let idle = !self.always_process
+			&& out.iter().all(|frame| *frame == Frame::ZERO)
+			&& self.effects.iter().all(|effect| effect.is_idle())
+			&& self.added_effects.is_idle();
+		if !idle {
+			for effect in &mut self.effects {
+				if self.mono {
+					effect.process_mono(out, dt, &info);
+				} else {
+					effect.process(out, dt, &info);
+				}
+			}
+			self.added_effects
+				.process(out, &mut self.temp_buffer, dt, &info, self.mono);
+			self.temp_buffer.fill(Frame::ZERO);
+		}
+		if let Some(ducker) = &mut self.ducker {
+			ducker.process(out, dt, &info);
 		}
+		self.muffle.process(out, dt);
 
 		// apply spatialization
 		if let Some(spatial_data) = &mut self.spatial_data {
@@ -230,29 +580,123 @@ impl Track {
 			}
 		}
 
-		// apply volume fade
+		// output to pre-fader send tracks, which are still silenced when
+		// the track is paused or muted
+		if self
+			.sends
+			.iter()
+			.any(|(_, route)| route.tap == SendTap::PreFader)
+		{
+			// taking the buffer doesn't allocate, and it lets the buffer be
+			// used while the track is borrowed
+			let mut pre_fader_out = std::mem::take(&mut self.temp_buffer);
+			pre_fader_out[..num_frames].copy_from_slice(out);
+			for (i, frame) in pre_fader_out[..num_frames].iter_mut().enumerate() {
+				*frame *= self.fade_volume((i + 1) as f64 / num_frames as f64);
+			}
+			self.send(&pre_fader_out[..num_frames], SendTap::PreFader, send_tracks);
+			pre_fader_out.fill(Frame::ZERO);
+			self.temp_buffer = pre_fader_out;
+		}
+
+		// apply volume
 		for (i, frame) in out.iter_mut().enumerate() {
 			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
-			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
-			let fade_volume = self
-				.playback_state_manager
-				.interpolated_fade_volume(time_in_chunk)
-				.as_amplitude();
-			*frame *= volume * fade_volume;
+			*frame *= self.volume.interpolated_value(time_in_chunk).as_amplitude();
+		}
+		self.stereo_placement.process(out);
+
+		// apply post-fader effects
+		let post_fader_idle = !self.always_process
+			&& out.iter().all(|frame| *frame == Frame::ZERO)
+			&& self
+				.post_fader_effects
+				.iter()
+				.all(|effect| effect.is_idle());
+		if !post_fader_idle {
+			for effect in &mut self.post_fader_effects {
+				if self.mono {
+					effect.process_mono(out, dt, &info);
+				} else {
+					effect.process(out, dt, &info);
+				}
+			}
+		}
+
+		// apply pause and mute fades
+		for (i, frame) in out.iter_mut().enumerate() {
+			*frame *= self.fade_volume((i + 1) as f64 / num_frames as f64);
 		}
+		self.level_meter.measure(out, dt, self.shared.levels());
+		self.clipping_behavior.process(out);
+		self.captures.record(out);
+		self.sidechain_outputs.write(out);
+
+		// output to post-fader send tracks
+		self.send(out, SendTap::PostFader, send_tracks);
+
+		// line up the output with the track's sibling tracks
+		self.latency_compensation.process(out);
+	}
+
+	/// Returns the latency the track's effects and sub-tracks introduce
+	/// (in frames).
+	#[must_use]
+	pub fn latency(&self) -> usize {
+		self.latency
+	}
+
+	#[must_use]
+	pub fn compensates_latency(&self) -> bool {
+		self.latency_compensation.is_enabled()
+	}
+
+	/// Sets how many frames the track's output is delayed by to line up
+	/// with its sibling tracks.
+	pub fn set_compensation_delay(&mut self, delay: usize) {
+		self.latency_compensation.set_delay(delay);
+	}
+
+	/// Returns how much the track is turned down by pausing and muting at
+	/// the given point in the current chunk.
+	#[must_use]
+	fn fade_volume(&self, time_in_chunk: f64) -> f32 {
+		let fade_volume = self
+			.playback_state_manager
+			.interpolated_fade_volume(time_in_chunk)
+			.as_amplitude();
+		fade_volume * self.mute_volume.interpolated_value(time_in_chunk)
+	}
 
-		// output to send tracks
-		for (send_track_id, SendTrackRoute { volume, .. }) in &self.sends {
+	/// Adds audio to the send tracks routed to from the given point in
+	/// the track.
+	fn send(&self, out: &[Frame], tap: SendTap, send_tracks: &mut ResourceStorage<SendTrack>) {
+		for (send_track_id, route) in &self.sends {
+			if route.tap != tap {
+				continue;
+			}
 			let Some(send_track) = send_tracks.get_mut(send_track_id.0) else {
 				continue;
 			};
-			send_track.add_input(out, volume.value());
+			send_track.add_input(out, &route.volume);
 		}
 	}
 
 	fn read_commands(&mut self) {
 		self.volume
 			.read_command(&mut self.command_readers.set_volume);
+		self.playback_rate
+			.read_command(&mut self.command_readers.set_playback_rate);
+		self.stereo_placement.read_commands(
+			&mut self.command_readers.set_panning,
+			&mut self.command_readers.set_stereo_width,
+			&mut self.command_readers.set_balance,
+		);
+		self.muffle
+			.read_command(&mut self.command_readers.set_muffle);
+		if let Some(ducker) = &mut self.ducker {
+			ducker.read_command(&mut self.command_readers.set_duck_amount);
+		}
 		for (_, route) in &mut self.sends {
 			route.read_commands();
 		}
@@ -272,6 +716,59 @@ impl Track {
 		if let Some((start_time, tween)) = self.command_readers.resume.read() {
 			self.resume(start_time, tween);
 		}
+		if let Some(tween) = self.command_readers.remove.read() {
+			self.start_removal(tween);
+		}
+		if let Some(muted) = self.command_readers.set_muted.read() {
+			self.set_silenced(muted, self.soloed_out);
+		}
+		if let Some(clipping_behavior) = self.command_readers.set_clipping_behavior.read() {
+			self.clipping_behavior = clipping_behavior;
+		}
+		if let Some((command, include_sub_tracks)) = self.command_readers.set_sound_playback.read()
+		{
+			self.apply_sound_playback_command(command, include_sub_tracks);
+		}
+	}
+}
+
+/// Applies the commands sent to every sound on a track to one of the
+/// sounds on it.
+#[derive(Debug, Default)]
+pub(crate) struct TrackMember {
+	/// The number of the most recent command that's been applied,
+	/// or `None` if the sound hasn't checked its track yet.
+	last_command: Option<u64>,
+}
+
+impl TrackMember {
+	/// Returns a playback command sent to the sound's track that the sound
+	/// should apply.
+	#[must_use]
+	pub fn update(&mut self, info: &Info) -> Option<GroupPlaybackCommand> {
+		let Some((number, command)) = info.sound_playback_command() else {
+			self.last_command = Some(0);
+			return None;
+		};
+		match self.last_command.replace(number) {
+			Some(last_command) if number > last_command => Some(command),
+			Some(_) => None,
+			// sounds that start on a track whose sounds are paused start
+			// paused
+			None => match command {
+				GroupPlaybackCommand::Pause(_) => Some(GroupPlaybackCommand::Pause(Tween {
+					duration: Duration::ZERO,
+					..Default::default()
+				})),
+				GroupPlaybackCommand::Resume(_) | GroupPlaybackCommand::Stop(_) => None,
+			},
+		}
+	}
+
+	/// Makes the sound check its track's commands as if it had just
+	/// started, which is needed after it's moved to a different track.
+	pub fn rejoin(&mut self) {
+		self.last_command = None;
 	}
 }
 
@@ -368,8 +865,18 @@ fn listener_ear_directions(listener_orientation: Quat) -> (Vec3, Vec3) {
 
 command_writers_and_readers! {
 	set_volume: ValueChangeCommand<Decibels>,
+	set_playback_rate: ValueChangeCommand<PlaybackRate>,
+	set_panning: ValueChangeCommand<Panning>,
+	set_stereo_width: ValueChangeCommand<f32>,
+	set_balance: ValueChangeCommand<f32>,
+	set_muffle: ValueChangeCommand<f32>,
 	set_position: ValueChangeCommand<Vec3>,
 	set_spatialization_strength: ValueChangeCommand<f32>,
 	pause: Tween,
 	resume: (StartTime, Tween),
+	set_sound_playback: (GroupPlaybackCommand, bool),
+	set_muted: bool,
+	remove: Tween,
+	set_duck_amount: ValueChangeCommand<Decibels>,
+	set_clipping_behavior: ClippingBehavior,
 }
@@ -0,0 +1,352 @@
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	time::Duration,
+};
+
+use atomic_arena::Key;
+use rtrb::{Consumer, Producer, RingBuffer};
+
+use crate::{
+	backend::resources::{ResourceController, ResourceStorage},
+	effect::{Effect, EffectBuilder, EffectHandle, EffectId, EffectTemplate},
+	info::Info,
+	Frame, Parameter, ResourceLimitReached, Tween, Value,
+};
+
+use super::SetEffectOrderError;
+
+/// How long it takes an effect to fade in when it's added to a track and
+/// fade out when it's removed.
+const EFFECT_FADE_DURATION: Duration = Duration::from_millis(10);
+/// How many effect orders can be sent to a track before the audio thread
+/// receives them.
+const EFFECT_ORDER_CAPACITY: usize = 8;
+
+/// The effects added to a track after the track was created.
+pub(crate) struct AddedEffects {
+	effects: ResourceStorage<AddedEffect>,
+	/// The IDs and keys of the effects in the order they're applied.
+	order: Vec<(EffectId, Key)>,
+	order_consumer: Consumer<Vec<EffectId>>,
+	unused_order_producer: Producer<Vec<EffectId>>,
+}
+
+impl AddedEffects {
+	#[must_use]
+	pub fn new(capacity: usize) -> (Self, AddedEffectController) {
+		let (effects, effect_controller) = ResourceStorage::new(capacity);
+		let (order_producer, order_consumer) = RingBuffer::new(EFFECT_ORDER_CAPACITY);
+		let (unused_order_producer, unused_order_consumer) = RingBuffer::new(EFFECT_ORDER_CAPACITY);
+		(
+			Self {
+				effects,
+				order: Vec::with_capacity(capacity),
+				order_consumer,
+				unused_order_producer,
+			},
+			AddedEffectController {
+				effect_controller,
+				shared: HashMap::new(),
+				templates: vec![],
+				order_producer: Mutex::new(order_producer),
+				unused_order_consumer: Mutex::new(unused_order_consumer),
+			},
+		)
+	}
+
+	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		for (_, effect) in &mut self.effects {
+			effect.on_change_sample_rate(sample_rate);
+		}
+	}
+
+	pub fn on_start_processing(&mut self) {
+		self.effects.remove_and_add(|effect| effect.finished());
+		let effects = &mut self.effects;
+		self.order
+			.retain(|(_, key)| effects.get_mut(*key).is_some());
+		let num_existing_effects = self.order.len();
+		for (key, effect) in self.effects.iter() {
+			if !self.order[..num_existing_effects]
+				.iter()
+				.any(|(_, existing_key)| *existing_key == key)
+			{
+				self.order.push((effect.id(), key));
+			}
+		}
+		// the arena gives the newest effects first, but effects go at the end
+		// of the chain in the order they were added
+		self.order[num_existing_effects..].sort_unstable_by_key(|(id, _)| *id);
+		while let Ok(order) = self.order_consumer.pop() {
+			reorder(&mut self.order, &order);
+			self.unused_order_producer
+				.push(order)
+				.unwrap_or_else(|_| panic!("unused effect order producer is full"));
+		}
+		for (_, effect) in &mut self.effects {
+			effect.on_start_processing();
+		}
+	}
+
+	/// Returns `true` if every effect is idle.
+	#[must_use]
+	pub fn is_idle(&self) -> bool {
+		self.effects.iter().all(|(_, effect)| effect.is_idle())
+	}
+
+	/// Returns the total latency of the effects (in frames).
+	#[must_use]
+	pub fn latency_frames(&self) -> usize {
+		self.effects
+			.iter()
+			.map(|(_, effect)| effect.effect.latency_frames())
+			.sum()
+	}
+
+	/// Applies the effects to `out`, using `dry` as scratch space. If
+	/// `mono` is `true`, the effects are processed with
+	/// [`Effect::process_mono`].
+	pub fn process(
+		&mut self,
+		out: &mut [Frame],
+		dry: &mut [Frame],
+		dt: f64,
+		info: &Info,
+		mono: bool,
+	) {
+		for (_, key) in &self.order {
+			if let Some(effect) = self.effects.get_mut(*key) {
+				effect.process(out, dry, dt, info, mono);
+			}
+		}
+	}
+}
+
+/// Moves the effects with the given IDs to the start of the chain in the
+/// given order. Other effects keep their order after them.
+fn reorder(order: &mut [(EffectId, Key)], ids: &[EffectId]) {
+	let mut num_sorted = 0;
+	for id in ids {
+		if let Some(index) = order[num_sorted..]
+			.iter()
+			.position(|(effect_id, _)| effect_id == id)
+		{
+			order[num_sorted..=num_sorted + index].rotate_right(1);
+			num_sorted += 1;
+		}
+	}
+}
+
+/// Adds, removes, and reorders the effects added to a track after the
+/// track was created.
+#[derive(Debug)]
+pub(crate) struct AddedEffectController {
+	effect_controller: ResourceController<AddedEffect>,
+	/// The shared state of every effect that hasn't been removed.
+	shared: HashMap<EffectId, Arc<AddedEffectShared>>,
+	/// The templates of every effect that hasn't been removed, in the
+	/// order the effects are applied, or `None` for effects that can't
+	/// be copied.
+	templates: Vec<(EffectId, Option<Arc<dyn EffectTemplate>>)>,
+	order_producer: Mutex<Producer<Vec<EffectId>>>,
+	unused_order_consumer: Mutex<Consumer<Vec<EffectId>>>,
+}
+
+impl AddedEffectController {
+	pub fn add<B: EffectBuilder>(
+		&mut self,
+		builder: B,
+		sample_rate: u32,
+		internal_buffer_size: usize,
+	) -> Result<EffectHandle<B::Handle>, ResourceLimitReached> {
+		let (effect, handle) = builder.build();
+		let id = EffectId::next();
+		let template = effect.template();
+		let (mut effect, shared) = AddedEffect::new(id, effect);
+		effect.init(sample_rate, internal_buffer_size);
+		self.effect_controller.insert(effect)?;
+		self.shared.insert(id, shared);
+		self.templates.push((id, template));
+		Ok(EffectHandle { id, handle })
+	}
+
+	pub fn remove(&mut self, id: EffectId) {
+		if let Some(shared) = self.shared.remove(&id) {
+			shared.mark_for_removal();
+		}
+		self.templates.retain(|(template_id, _)| *template_id != id);
+	}
+
+	pub fn set_order(&mut self, order: &[EffectId]) -> Result<(), SetEffectOrderError> {
+		for (i, id) in order.iter().enumerate() {
+			if !self.shared.contains_key(id) {
+				return Err(SetEffectOrderError::NonexistentEffect(*id));
+			}
+			if order[..i].contains(id) {
+				return Err(SetEffectOrderError::DuplicateEffect(*id));
+			}
+		}
+		if let Some(id) = self.shared.keys().find(|id| !order.contains(id)) {
+			return Err(SetEffectOrderError::MissingEffect(*id));
+		}
+		// orders the audio thread is done with are dropped here so they
+		// aren't deallocated on the audio thread
+		let unused_order_consumer = self
+			.unused_order_consumer
+			.get_mut()
+			.expect("unused effect order consumer mutex poisoned");
+		while unused_order_consumer.pop().is_ok() {}
+		self.order_producer
+			.get_mut()
+			.expect("effect order producer mutex poisoned")
+			.push(order.to_vec())
+			.map_err(|_| SetEffectOrderError::CommandQueueFull)?;
+		self.templates.sort_by_key(|(id, _)| {
+			order
+				.iter()
+				.position(|order_id| order_id == id)
+				.expect("every effect is in the order")
+		});
+		Ok(())
+	}
+
+	/// Returns the templates of the effects that can be copied, in the
+	/// order the effects are applied.
+	pub fn templates(&self) -> impl Iterator<Item = &Arc<dyn EffectTemplate>> {
+		self.templates
+			.iter()
+			.filter_map(|(_, template)| template.as_ref())
+	}
+
+	#[must_use]
+	pub fn capacity(&self) -> usize {
+		self.effect_controller.capacity()
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.effect_controller.len()
+	}
+}
+
+/// An effect added to a track after the track was created, which fades
+/// between the track's unprocessed and processed audio when it's added
+/// and removed.
+pub(crate) struct AddedEffect {
+	id: EffectId,
+	effect: Box<dyn Effect>,
+	shared: Arc<AddedEffectShared>,
+	/// How much of the effect's output is mixed into the track's audio.
+	mix: Parameter<f32>,
+	removing: bool,
+}
+
+impl AddedEffect {
+	#[must_use]
+	pub fn new(id: EffectId, effect: Box<dyn Effect>) -> (Self, Arc<AddedEffectShared>) {
+		let shared = Arc::new(AddedEffectShared {
+			removed: AtomicBool::new(false),
+		});
+		let mut mix = Parameter::new(Value::Fixed(0.0), 0.0);
+		mix.set(Value::Fixed(1.0), fade_tween());
+		(
+			Self {
+				id,
+				effect,
+				shared: shared.clone(),
+				mix,
+				removing: false,
+			},
+			shared,
+		)
+	}
+
+	#[must_use]
+	pub fn id(&self) -> EffectId {
+		self.id
+	}
+
+	pub fn init(&mut self, sample_rate: u32, internal_buffer_size: usize) {
+		self.effect.init(sample_rate, internal_buffer_size);
+	}
+
+	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.effect.on_change_sample_rate(sample_rate);
+	}
+
+	pub fn on_start_processing(&mut self) {
+		if self.shared.is_marked_for_removal() && !self.removing {
+			self.removing = true;
+			self.mix.set(Value::Fixed(0.0), fade_tween());
+		}
+		self.effect.on_start_processing();
+	}
+
+	/// Applies the effect to `out`, using `dry` to hold a copy of the
+	/// unprocessed audio.
+	pub fn process(
+		&mut self,
+		out: &mut [Frame],
+		dry: &mut [Frame],
+		dt: f64,
+		info: &Info,
+		mono: bool,
+	) {
+		let num_frames = out.len();
+		self.mix.update(dt * num_frames as f64, info);
+		let dry = &mut dry[..num_frames];
+		dry.copy_from_slice(out);
+		if mono {
+			self.effect.process_mono(out, dt, info);
+		} else {
+			self.effect.process(out, dt, info);
+		}
+		for (i, (frame, dry)) in out.iter_mut().zip(dry.iter().copied()).enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let mix = self.mix.interpolated_value(time_in_chunk);
+			*frame = dry + (*frame - dry) * mix;
+		}
+	}
+
+	/// Returns `true` if the effect is idle. Effects that are being
+	/// removed are never idle so they can finish fading out.
+	#[must_use]
+	pub fn is_idle(&self) -> bool {
+		!self.removing && self.effect.is_idle()
+	}
+
+	/// Returns `true` if the effect has been removed and has finished
+	/// fading out.
+	#[must_use]
+	pub fn finished(&self) -> bool {
+		self.removing && self.mix.value() == 0.0
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct AddedEffectShared {
+	removed: AtomicBool,
+}
+
+impl AddedEffectShared {
+	#[must_use]
+	pub fn is_marked_for_removal(&self) -> bool {
+		self.removed.load(Ordering::SeqCst)
+	}
+
+	pub fn mark_for_removal(&self) {
+		self.removed.store(true, Ordering::SeqCst);
+	}
+}
+
+#[must_use]
+fn fade_tween() -> Tween {
+	Tween {
+		duration: EFFECT_FADE_DURATION,
+		..Default::default()
+	}
+}
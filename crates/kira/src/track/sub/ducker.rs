@@ -0,0 +1,101 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+	command::{CommandReader, ValueChangeCommand},
+	info::Info,
+	track::SidechainBuffer,
+	Decibels, Frame, Parameter, Tweenable, Value,
+};
+
+/// How loud the source track has to be to duck the track by default.
+pub(crate) const DEFAULT_DUCK_THRESHOLD: Decibels = Decibels(-30.0);
+/// How long the track stays ducked after the source track gets quieter
+/// than the threshold by default.
+pub(crate) const DEFAULT_DUCK_HOLD: Duration = Duration::from_millis(100);
+
+/// The settings for ducking a track passed to
+/// [`TrackBuilder::duck_by`](super::TrackBuilder::duck_by).
+pub(crate) struct DuckSettings {
+	pub amount: Decibels,
+	pub attack: Duration,
+	pub release: Duration,
+	pub sidechain: Arc<SidechainBuffer>,
+}
+
+/// Turns down a track's volume while another track is outputting audio.
+pub(crate) struct Ducker {
+	sidechain: Arc<SidechainBuffer>,
+	sidechain_buffer: Vec<Frame>,
+	/// How much the track is turned down when it's fully ducked.
+	amount: Parameter<Decibels>,
+	threshold: Decibels,
+	attack: Duration,
+	release: Duration,
+	hold: Duration,
+	/// How long the track will stay ducked if the source track stays
+	/// quieter than the threshold.
+	hold_remaining: Duration,
+	/// How much the track is ducked, from `0.0` (not at all) to `1.0`
+	/// (fully).
+	envelope: f32,
+}
+
+impl Ducker {
+	#[must_use]
+	pub fn new(
+		settings: DuckSettings,
+		threshold: Decibels,
+		hold: Duration,
+		internal_buffer_size: usize,
+	) -> Self {
+		Self {
+			sidechain: settings.sidechain,
+			sidechain_buffer: vec![Frame::ZERO; internal_buffer_size],
+			amount: Parameter::new(Value::Fixed(settings.amount), Decibels::IDENTITY),
+			threshold,
+			attack: settings.attack,
+			release: settings.release,
+			hold,
+			hold_remaining: Duration::ZERO,
+			envelope: 0.0,
+		}
+	}
+
+	pub fn read_command(
+		&mut self,
+		command_reader: &mut CommandReader<ValueChangeCommand<Decibels>>,
+	) {
+		self.amount.read_command(command_reader);
+	}
+
+	pub fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		let num_frames = out.len();
+		self.amount.update(dt * num_frames as f64, info);
+		let sidechain = &mut self.sidechain_buffer[..num_frames];
+		self.sidechain.take(sidechain);
+		let threshold = self.threshold.as_amplitude();
+		let step = Duration::from_secs_f64(dt);
+		for (i, (frame, sidechain_frame)) in out.iter_mut().zip(sidechain.iter()).enumerate() {
+			let level = sidechain_frame.left.abs().max(sidechain_frame.right.abs());
+			if level >= threshold {
+				self.hold_remaining = self.hold;
+			}
+			let (target, duration) = if level >= threshold || !self.hold_remaining.is_zero() {
+				(1.0, self.attack)
+			} else {
+				(0.0, self.release)
+			};
+			self.hold_remaining = self.hold_remaining.saturating_sub(step);
+			let speed = if duration.is_zero() {
+				0.0
+			} else {
+				(-dt / duration.as_secs_f64()).exp() as f32
+			};
+			self.envelope = target + speed * (self.envelope - target);
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let amount = self.amount.interpolated_value(time_in_chunk);
+			let volume = Tweenable::interpolate(Decibels::IDENTITY, amount, self.envelope.into());
+			*frame *= volume.as_amplitude();
+		}
+	}
+}
@@ -1,39 +1,63 @@
 use std::{
 	collections::HashMap,
-	sync::{atomic::Ordering, Arc},
+	sync::{atomic::Ordering, Arc, Mutex, MutexGuard},
 };
 
 use crate::{
 	backend::{resources::ResourceController, RendererShared},
 	command::{CommandWriter, ValueChangeCommand},
+	effect::{EffectBuilder, EffectHandle, EffectId},
+	group::GroupPlaybackCommand,
 	listener::ListenerId,
 	sound::{Sound, SoundData},
-	track::TrackPlaybackState,
-	Decibels, PlaySoundError, ResourceLimitReached, StartTime, Tween, Value,
+	track::{
+		CaptureController, CaptureHandle, ClippingBehavior, SidechainOutputController, TrackId,
+		TrackLevels, TrackPlaybackState,
+	},
+	Decibels, Panning, PlaySoundError, PlaybackRate, ResourceLimitReached, StartTime, Tween, Value,
 };
 
 use super::{
-	CommandWriters, NonexistentRoute, SendTrackId, SpatialTrackBuilder, SpatialTrackHandle, Track,
-	TrackBuilder, TrackShared,
+	AddSubTrackError, AddedEffectController, CommandWriters, NonexistentRoute, SendTrackId,
+	SetEffectOrderError, SpatialTrackBuilder, SpatialTrackHandle, Track, TrackBuilder, TrackShared,
+	TrackTemplate,
 };
 
 /// Controls a mixer track.
 ///
-/// When a [`TrackHandle`] is dropped, the corresponding mixer
-/// track will be removed.
-#[derive(Debug)]
+/// Cloning a [`TrackHandle`] creates another handle that controls the
+/// same track. When every handle to a track is dropped, the corresponding
+/// mixer track is removed the next time the audio thread processes audio,
+/// which frees its slot for a new track. Tracks with sub-tracks wait until
+/// their sub-tracks are removed, and tracks set to
+/// [persist until their sounds finish](TrackBuilder::persist_until_sounds_finish)
+/// wait for their sounds as well. To fade out the track and stop its
+/// sounds first, use [`remove`](Self::remove).
+#[derive(Debug, Clone)]
 pub struct TrackHandle {
 	pub(crate) renderer_shared: Arc<RendererShared>,
 	pub(crate) shared: Arc<TrackShared>,
-	pub(crate) command_writers: CommandWriters,
-	pub(crate) sound_controller: ResourceController<Box<dyn Sound>>,
-	pub(crate) sub_track_controller: ResourceController<Track>,
-	pub(crate) send_volume_command_writers:
-		HashMap<SendTrackId, CommandWriter<ValueChangeCommand<Decibels>>>,
+	pub(crate) controls: Arc<Mutex<TrackControls>>,
+	/// The full name of the track, if it has one.
+	pub(crate) name: Option<String>,
 	pub(crate) internal_buffer_size: usize,
 }
 
 impl TrackHandle {
+	/// Returns a unique identifier for this track.
+	#[must_use]
+	pub fn id(&self) -> TrackId {
+		self.shared.id()
+	}
+
+	/// Returns the full name of the track, which is the name given to
+	/// [`TrackBuilder::name`] after the names of its parent tracks, or
+	/// `None` if the track doesn't have a name.
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.name.as_deref()
+	}
+
 	/// Returns the current playback state of the track.
 	#[must_use]
 	pub fn state(&self) -> TrackPlaybackState {
@@ -48,21 +72,30 @@ impl TrackHandle {
 		let (sound, handle) = sound_data
 			.into_sound()
 			.map_err(PlaySoundError::IntoSoundError)?;
-		self.sound_controller
+		self.controls()
+			.sound_controller
 			.insert(sound)
 			.map_err(|_| PlaySoundError::SoundLimitReached)?;
 		Ok(handle)
 	}
 
 	/// Adds a child track to this track.
+	///
+	/// If the child track has a [name](TrackBuilder::name), its full name
+	/// is this track's name, a `/`, and then the child track's name.
 	pub fn add_sub_track(
 		&mut self,
 		builder: TrackBuilder,
-	) -> Result<TrackHandle, ResourceLimitReached> {
-		let (mut track, handle) =
-			builder.build(self.renderer_shared.clone(), self.internal_buffer_size);
+	) -> Result<TrackHandle, AddSubTrackError> {
+		let (mut track, handle) = builder.build(
+			self.renderer_shared.clone(),
+			self.internal_buffer_size,
+			Some(self.shared.clone()),
+			self.name.as_deref(),
+		);
+		handle.register_name()?;
 		track.init_effects(self.renderer_shared.sample_rate.load(Ordering::SeqCst));
-		self.sub_track_controller.insert(track)?;
+		self.controls().sub_track_controller.insert(track)?;
 		Ok(handle)
 	}
 
@@ -78,24 +111,290 @@ impl TrackHandle {
 			self.internal_buffer_size,
 			listener.into(),
 			position.into().to_(),
+			Some(self.shared.clone()),
 		);
 		track.init_effects(self.renderer_shared.sample_rate.load(Ordering::SeqCst));
-		self.sub_track_controller.insert(track)?;
+		self.controls().sub_track_controller.insert(track)?;
 		Ok(handle)
 	}
 
+	/**
+	Adds an effect to the track.
+
+	The effect is applied after the track's other effects, including
+	effects that were added earlier with this method. It fades in over a
+	few milliseconds to avoid clicks.
+
+	Returns [`ResourceLimitReached`] if the track already has
+	[`effect_capacity`](Self::effect_capacity) effects that were added
+	with this method.
+
+	# Examples
+
+	Muffle the audio on a track while the player is underwater:
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend,
+		effect::filter::FilterBuilder,
+		track::TrackBuilder,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut track = manager.add_sub_track(TrackBuilder::new())?;
+	let filter = track.add_effect(FilterBuilder::new().cutoff(500.0))?;
+	// later...
+	track.remove_effect(&filter);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn add_effect<B: EffectBuilder>(
+		&mut self,
+		builder: B,
+	) -> Result<EffectHandle<B::Handle>, ResourceLimitReached> {
+		self.controls().added_effect_controller.add(
+			builder,
+			self.renderer_shared.sample_rate.load(Ordering::SeqCst),
+			self.internal_buffer_size,
+		)
+	}
+
+	/// Removes an effect that was added with [`add_effect`](Self::add_effect).
+	///
+	/// The effect fades out over a few milliseconds to avoid clicks. Nothing
+	/// happens if the effect was already removed.
+	pub fn remove_effect(&mut self, effect: impl Into<EffectId>) {
+		self.controls()
+			.added_effect_controller
+			.remove(effect.into());
+	}
+
+	/// Sets the order the effects added with [`add_effect`](Self::add_effect)
+	/// are applied in.
+	///
+	/// `order` must list every effect on the track that hasn't been removed
+	/// exactly once. Otherwise, the effects aren't reordered and an error is
+	/// returned. The effects keep their internal state, like the echoes in
+	/// a delay, when they're reordered.
+	pub fn set_effect_order(&mut self, order: &[EffectId]) -> Result<(), SetEffectOrderError> {
+		self.controls().added_effect_controller.set_order(order)
+	}
+
+	/// Fades out the track with the given tween and then removes it,
+	/// freeing its slot for a new track.
+	///
+	/// Every sound on the track and its sub-tracks is stopped with the
+	/// same tween, and the sub-tracks are removed along with the track,
+	/// even if their handles still exist. The track is removed even if
+	/// other clones of this handle still exist.
+	///
+	/// The track's name is freed up right away, so a new track can be
+	/// given the same name.
+	pub fn remove(self, tween: Tween) {
+		self.controls().command_writers.remove.write(tween);
+		self.shared.request_removal();
+		if let Some(name) = &self.name {
+			self.renderer_shared
+				.track_names
+				.remove(name, self.shared.id());
+		}
+	}
+
 	/// Sets the (post-effects) volume of the mixer track.
 	pub fn set_volume(&mut self, volume: impl Into<Value<Decibels>>, tween: Tween) {
-		self.command_writers.set_volume.write(ValueChangeCommand {
-			target: volume.into(),
-			tween,
-		})
+		let volume = volume.into();
+		let mut controls = self.controls();
+		controls.template.volume = volume;
+		controls
+			.command_writers
+			.set_volume
+			.write(ValueChangeCommand {
+				target: volume,
+				tween,
+			})
+	}
+
+	/// Sets the playback rate of every sound on the track and its
+	/// sub-tracks.
+	///
+	/// The track's playback rate is multiplied by the playback rate of
+	/// each sound, and by the playback rates of the track's parent tracks,
+	/// so a sound playing at `2.0` on a track set to `0.5` plays at its
+	/// original speed. This changes the pitch of the sounds as well
+	/// as their speed.
+	///
+	/// Clocks aren't affected, so sounds and tracks that are waiting for
+	/// a clock tick or a delay to start still start at the same time.
+	pub fn set_playback_rate(
+		&mut self,
+		playback_rate: impl Into<Value<PlaybackRate>>,
+		tween: Tween,
+	) {
+		let playback_rate = playback_rate.into();
+		let mut controls = self.controls();
+		controls.template.playback_rate = playback_rate;
+		controls
+			.command_writers
+			.set_playback_rate
+			.write(ValueChangeCommand {
+				target: playback_rate,
+				tween,
+			})
+	}
+
+	/// Sets the panning of the track, which moves the audio towards the
+	/// left or right speaker the same way as the panning of a sound.
+	///
+	/// The track's panning, [stereo width](Self::set_stereo_width), and
+	/// [balance](Self::set_balance) are applied after its volume, so they
+	/// don't affect the audio sent to pre-fader send tracks.
+	pub fn set_panning(&mut self, panning: impl Into<Value<Panning>>, tween: Tween) {
+		let panning = panning.into();
+		let mut controls = self.controls();
+		controls.template.panning = panning;
+		controls
+			.command_writers
+			.set_panning
+			.write(ValueChangeCommand {
+				target: panning,
+				tween,
+			})
+	}
+
+	/// Sets how far apart the track's left and right channels are spread.
+	///
+	/// A width of `0.0` mixes the track down to mono, `1.0` leaves it as it
+	/// is, and higher values exaggerate the differences between the
+	/// channels. The width only changes the difference between the
+	/// channels, so the track sounds the same as before when it's mixed
+	/// down to mono. Negative widths are treated as `0.0`.
+	pub fn set_stereo_width(&mut self, stereo_width: impl Into<Value<f32>>, tween: Tween) {
+		let stereo_width = stereo_width.into();
+		let mut controls = self.controls();
+		controls.template.stereo_width = stereo_width;
+		controls
+			.command_writers
+			.set_stereo_width
+			.write(ValueChangeCommand {
+				target: stereo_width,
+				tween,
+			})
+	}
+
+	/// Sets the balance between the track's left and right channels.
+	///
+	/// Unlike [panning](Self::set_panning), which moves the audio from one
+	/// side to the other, the balance only turns down one of the channels.
+	/// A balance of `-1.0` silences the right channel, `1.0` silences the
+	/// left channel, and `0.0` leaves both channels as they are.
+	pub fn set_balance(&mut self, balance: impl Into<Value<f32>>, tween: Tween) {
+		let balance = balance.into();
+		let mut controls = self.controls();
+		controls.template.balance = balance;
+		controls
+			.command_writers
+			.set_balance
+			.write(ValueChangeCommand {
+				target: balance,
+				tween,
+			})
+	}
+
+	/// Sets how muffled the track is, from `0.0` (not muffled) to `1.0`
+	/// (fully muffled).
+	///
+	/// Muffling low-pass filters the track and turns it down, which is
+	/// useful for making a group of sounds seem like they're underwater or
+	/// behind a wall. The filter is applied after the track's effects and
+	/// before its volume, and the track's
+	/// [muffle curve](TrackBuilder::muffle_curve) controls how the muffle amount
+	/// maps to the filter's cutoff frequency and volume. While the muffle
+	/// amount is `0.0`, the filter is skipped entirely.
+	pub fn set_muffle(&mut self, muffle: impl Into<Value<f32>>, tween: Tween) {
+		let muffle = muffle.into();
+		let mut controls = self.controls();
+		controls.template.muffle = muffle;
+		controls
+			.command_writers
+			.set_muffle
+			.write(ValueChangeCommand {
+				target: muffle,
+				tween,
+			})
+	}
+
+	/// Sets how much the track is turned down while the track passed to
+	/// [`TrackBuilder::duck_by`] is outputting audio.
+	///
+	/// This does nothing if the track isn't ducked by another track.
+	pub fn set_duck_amount(&mut self, amount: impl Into<Value<Decibels>>, tween: Tween) {
+		self.controls()
+			.command_writers
+			.set_duck_amount
+			.write(ValueChangeCommand {
+				target: amount.into(),
+				tween,
+			})
+	}
+
+	/// Sets what the track does with samples that go above `1.0` or below
+	/// `-1.0`.
+	pub fn set_clipping_behavior(&mut self, clipping_behavior: ClippingBehavior) {
+		let mut controls = self.controls();
+		controls.template.clipping_behavior = clipping_behavior;
+		controls
+			.command_writers
+			.set_clipping_behavior
+			.write(clipping_behavior)
+	}
+
+	/// Returns the levels of the audio the track output most recently.
+	///
+	/// Reading the levels resets [`TrackLevels::clipped`].
+	#[must_use]
+	pub fn level(&self) -> TrackLevels {
+		self.shared.levels().read()
+	}
+
+	/// Returns whether the track output a sample above `1.0` or below
+	/// `-1.0` since the last time this method was called.
+	///
+	/// This is measured before the track's
+	/// [clipping behavior](ClippingBehavior) is applied, and it's tracked
+	/// separately from [`TrackLevels::clipped`], so calling [`level`](Self::level)
+	/// doesn't reset it.
+	#[must_use]
+	pub fn clipped_since_last_read(&self) -> bool {
+		self.shared.levels().read_clipped()
+	}
+
+	/// Starts recording the audio this track outputs into a ring buffer
+	/// that can hold `ring_buffer_capacity` frames.
+	///
+	/// Up to 4 captures can record a track at once.
+	pub fn start_capture(
+		&mut self,
+		ring_buffer_capacity: usize,
+	) -> Result<CaptureHandle, ResourceLimitReached> {
+		self.controls()
+			.capture_controller
+			.start(ring_buffer_capacity)
+	}
+
+	/// Mutes or unmutes the track.
+	///
+	/// The track fades out or in over a few milliseconds to avoid clicks.
+	/// Muting doesn't change the track's volume, so unmuting it restores
+	/// the volume it had before, even if it's linked to a modulator.
+	pub fn set_muted(&mut self, muted: bool) {
+		self.controls().command_writers.set_muted.write(muted)
 	}
 
 	/// Sets the volume of this track's route to a send track.
 	///
 	/// This can only be used to change the volume of existing routes,
-	/// not to add new routes.
+	/// not to add new routes. Like the track's own volume, the volume
+	/// of a route can be tweened or linked to a modulator.
 	pub fn set_send(
 		&mut self,
 		to: impl Into<SendTrackId>,
@@ -103,13 +402,19 @@ impl TrackHandle {
 		tween: Tween,
 	) -> Result<(), NonexistentRoute> {
 		let to = to.into();
-		self.send_volume_command_writers
+		let volume = volume.into();
+		let mut controls = self.controls();
+		controls
+			.send_volume_command_writers
 			.get_mut(&to)
 			.ok_or(NonexistentRoute)?
 			.write(ValueChangeCommand {
-				target: volume.into(),
+				target: volume,
 				tween,
 			});
+		if let Some((send_volume, _)) = controls.template.sends.get_mut(&to) {
+			*send_volume = volume;
+		}
 		Ok(())
 	}
 
@@ -117,7 +422,7 @@ impl TrackHandle {
 	/// pauses playback, pausing all sounds and emitters playing on this
 	/// track.
 	pub fn pause(&mut self, tween: Tween) {
-		self.command_writers.pause.write(tween)
+		self.controls().command_writers.pause.write(tween)
 	}
 
 	/// Resumes playback and fades in the sound from silence
@@ -130,36 +435,272 @@ impl TrackHandle {
 	/// Resumes playback at the given start time and fades in
 	/// the sound from silence with the given tween.
 	pub fn resume_at(&mut self, start_time: StartTime, tween: Tween) {
-		self.command_writers.resume.write((start_time, tween))
+		self.controls()
+			.command_writers
+			.resume
+			.write((start_time, tween))
+	}
+
+	/**
+	Fades out every sound playing on this track with the given tween and
+	then pauses them. If `include_sub_tracks` is `true`, the sounds
+	playing on this track's sub-tracks are paused too.
+
+	Unlike [`pause`](Self::pause), which pauses the track itself, this
+	pauses each sound, so their handles report them as paused and the
+	track's effects keep running. Sounds that start playing on the track
+	before [`resume_all`](Self::resume_all) is called start paused.
+
+	# Examples
+
+	Pause the game world while the pause menu is open, but keep the
+	menu's sounds playing:
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+		track::TrackBuilder,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut world = manager.add_sub_track(TrackBuilder::new())?;
+	let ui = manager.add_sub_track(TrackBuilder::new())?;
+	// when the game is paused
+	world.pause_all(Tween::default(), true);
+	// when the game is unpaused
+	world.resume_all(Tween::default(), true);
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn pause_all(&mut self, tween: Tween, include_sub_tracks: bool) {
+		self.controls()
+			.command_writers
+			.set_sound_playback
+			.write((GroupPlaybackCommand::Pause(tween), include_sub_tracks))
+	}
+
+	/// Resumes every sound playing on this track and fades them in from
+	/// silence with the given tween. If `include_sub_tracks` is `true`,
+	/// the sounds playing on this track's sub-tracks are resumed too.
+	pub fn resume_all(&mut self, tween: Tween, include_sub_tracks: bool) {
+		self.controls()
+			.command_writers
+			.set_sound_playback
+			.write((GroupPlaybackCommand::Resume(tween), include_sub_tracks))
+	}
+
+	/// Fades out every sound playing on this track with the given tween and
+	/// then stops them. If `include_sub_tracks` is `true`, the sounds
+	/// playing on this track's sub-tracks are stopped too.
+	pub fn stop_all(&mut self, tween: Tween, include_sub_tracks: bool) {
+		self.controls()
+			.command_writers
+			.set_sound_playback
+			.write((GroupPlaybackCommand::Stop(tween), include_sub_tracks))
+	}
+
+	/**
+	Returns a [`TrackBuilder`] that creates a copy of this track with
+	its current settings and effects.
+
+	The copy has the track's volume, playback rate, send volumes, and the
+	other settings it was created with, as well as copies of its effects,
+	including effects added with [`add_effect`](Self::add_effect), in the
+	order they're applied. The copied effects start with the settings most
+	recently passed to the effects' handles, but without any audio the
+	original effects are holding onto, like the echoes in a delay.
+
+	Some things aren't copied:
+
+	- Effects that don't return an [`EffectTemplate`](crate::effect::EffectTemplate)
+	  from [`Effect::template`](crate::effect::Effect::template), which
+	  includes custom effects that don't implement it and effects added
+	  with [`TrackBuilder::add_effect_with_sidechain`]
+	- The track's ducking settings from [`TrackBuilder::duck_by`]
+	- Whether the track is muted or paused
+	- The track's sounds and sub-tracks
+
+	Tweens that are in progress are copied as their target values. The
+	handles of the original effects don't control the copies, so the
+	handles of the copies can't be retrieved.
+
+	# Examples
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+		effect::filter::FilterBuilder,
+		track::TrackBuilder,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut builder = TrackBuilder::new();
+	let mut filter = builder.add_effect(FilterBuilder::new());
+	let mut track = manager.add_sub_track(builder)?;
+	filter.set_cutoff(500.0, Tween::default());
+	track.set_volume(-6.0, Tween::default());
+	// this track has its own filter with a cutoff of 500 Hz,
+	// and its volume is -6 dB
+	let copy = manager.add_sub_track(track.builder_snapshot())?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn builder_snapshot(&self) -> TrackBuilder {
+		let controls = self.controls();
+		controls
+			.template
+			.to_builder(controls.added_effect_controller.templates())
 	}
 
 	/// Returns the maximum number of sounds that can play simultaneously on this track.
 	#[must_use]
 	pub fn sound_capacity(&self) -> usize {
-		self.sound_controller.capacity()
+		self.controls().sound_controller.capacity()
 	}
 
 	/// Returns the number of sounds currently playing on this track.
 	#[must_use]
 	pub fn num_sounds(&self) -> usize {
-		self.sound_controller.len()
+		self.controls().sound_controller.len()
+	}
+
+	/// Returns the number of sounds on this track in each
+	/// [`PlaybackState`](crate::sound::PlaybackState), in the order of
+	/// [`PlaybackState::ALL`](crate::sound::PlaybackState::ALL).
+	///
+	/// Sounds on the track's sub-tracks aren't counted. This is updated
+	/// by the audio thread every time it processes a batch of audio, so
+	/// sounds that were just played may not be counted yet.
+	#[must_use]
+	pub fn num_sounds_by_state(&self) -> [usize; 7] {
+		self.shared.sound_counts().read()
+	}
+
+	/// Returns how many frames the track's effects and sub-tracks delay its
+	/// audio by.
+	///
+	/// This is the latency the track's effects report, plus the latency
+	/// of the sub-track with the most latency. It doesn't include the delay
+	/// added to line the track up with its sibling tracks. See
+	/// [`TrackBuilder::compensate_latency`] for more information. This is
+	/// updated by the audio thread every time it processes a batch of
+	/// audio.
+	#[must_use]
+	pub fn reported_latency(&self) -> usize {
+		self.shared.latency()
 	}
 
 	/// Returns the maximum number of child tracks this track can have.
 	#[must_use]
 	pub fn sub_track_capacity(&self) -> usize {
-		self.sub_track_controller.capacity()
+		self.controls().sub_track_controller.capacity()
 	}
 
 	/// Returns the number of child tracks this track has.
 	#[must_use]
 	pub fn num_sub_tracks(&self) -> usize {
-		self.sub_track_controller.len()
+		self.controls().sub_track_controller.len()
+	}
+
+	/// Returns the maximum number of effects that can be added to this
+	/// track with [`add_effect`](Self::add_effect).
+	#[must_use]
+	pub fn effect_capacity(&self) -> usize {
+		self.controls().added_effect_controller.capacity()
+	}
+
+	/// Returns the number of effects added with [`add_effect`](Self::add_effect)
+	/// that count towards the track's [`effect_capacity`](Self::effect_capacity).
+	///
+	/// Effects are counted as soon as they're added, and effects that were
+	/// removed are counted until they finish fading out.
+	#[must_use]
+	pub fn num_effects(&self) -> usize {
+		self.controls().added_effect_controller.len()
+	}
+
+	/// Returns the number of send tracks this track is routed to.
+	///
+	/// Routes are set up when the track is created, so this doesn't change.
+	#[must_use]
+	pub fn num_sends(&self) -> usize {
+		self.controls().send_volume_command_writers.len()
 	}
 }
 
-impl Drop for TrackHandle {
+impl TrackHandle {
+	/// Creates another handle for the track the controls belong to.
+	#[must_use]
+	pub(crate) fn from_controls(
+		controls: Arc<Mutex<TrackControls>>,
+		internal_buffer_size: usize,
+	) -> Self {
+		let (renderer_shared, shared, name) = {
+			let controls = controls.lock().expect("track controls mutex poisoned");
+			(
+				controls.renderer_shared.clone(),
+				controls.shared.clone(),
+				controls.name.clone(),
+			)
+		};
+		Self {
+			renderer_shared,
+			shared,
+			controls,
+			name,
+			internal_buffer_size,
+		}
+	}
+
+	pub(crate) fn controls(&self) -> MutexGuard<'_, TrackControls> {
+		self.controls.lock().expect("track controls mutex poisoned")
+	}
+
+	/// Gives the track its name, or returns an error if another track
+	/// already has it.
+	pub(crate) fn register_name(&self) -> Result<(), AddSubTrackError> {
+		let Some(name) = &self.name else {
+			return Ok(());
+		};
+		if self
+			.renderer_shared
+			.track_names
+			.insert(name, self.shared.id(), &self.controls)
+		{
+			Ok(())
+		} else {
+			Err(AddSubTrackError::DuplicateName(name.clone()))
+		}
+	}
+}
+
+/// The parts of a [`TrackHandle`] that are shared by all of its clones.
+///
+/// When this is dropped, the track is removed and its name is freed up.
+#[derive(Debug)]
+pub(crate) struct TrackControls {
+	pub renderer_shared: Arc<RendererShared>,
+	pub shared: Arc<TrackShared>,
+	pub name: Option<String>,
+	pub command_writers: CommandWriters,
+	pub sound_controller: ResourceController<Box<dyn Sound>>,
+	pub sub_track_controller: ResourceController<Track>,
+	pub added_effect_controller: AddedEffectController,
+	pub capture_controller: CaptureController,
+	pub sidechain_output_controller: SidechainOutputController,
+	pub send_volume_command_writers:
+		HashMap<SendTrackId, CommandWriter<ValueChangeCommand<Decibels>>>,
+	pub template: TrackTemplate,
+}
+
+impl Drop for TrackControls {
 	fn drop(&mut self) {
 		self.shared.mark_for_removal();
+		if let Some(name) = &self.name {
+			self.renderer_shared
+				.track_names
+				.remove(name, self.shared.id());
+		}
 	}
 }
@@ -0,0 +1,61 @@
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, Weak},
+};
+
+use crate::track::TrackId;
+
+use super::TrackControls;
+
+/// The names of the tracks that have them, which are used by
+/// [`AudioManager::track_by_name`](crate::AudioManager::track_by_name).
+///
+/// This is only used by the handles, so the audio thread never has to
+/// wait for the lock.
+#[derive(Debug, Default)]
+pub(crate) struct TrackNames(Mutex<HashMap<String, NamedTrack>>);
+
+impl TrackNames {
+	/// Gives the name to a track. Returns `false` if another track already
+	/// has the name.
+	#[must_use]
+	pub fn insert(&self, name: &str, id: TrackId, controls: &Arc<Mutex<TrackControls>>) -> bool {
+		let mut names = self.0.lock().expect("track names mutex poisoned");
+		if names.contains_key(name) {
+			return false;
+		}
+		names.insert(
+			name.to_string(),
+			NamedTrack {
+				id,
+				controls: Arc::downgrade(controls),
+			},
+		);
+		true
+	}
+
+	/// Frees up the name if it belongs to the track with the given ID.
+	pub fn remove(&self, name: &str, id: TrackId) {
+		let mut names = self.0.lock().expect("track names mutex poisoned");
+		if names.get(name).is_some_and(|track| track.id == id) {
+			names.remove(name);
+		}
+	}
+
+	/// Returns the controls of the track with the given name.
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<Arc<Mutex<TrackControls>>> {
+		let names = self.0.lock().expect("track names mutex poisoned");
+		names.get(name).and_then(|track| track.controls.upgrade())
+	}
+}
+
+#[derive(Debug)]
+struct NamedTrack {
+	/// The ID of the track, which is used to make sure a track only frees
+	/// up its own name.
+	id: TrackId,
+	/// The controls shared by the track's handles, which are dropped
+	/// once every handle is dropped.
+	controls: Weak<Mutex<TrackControls>>,
+}
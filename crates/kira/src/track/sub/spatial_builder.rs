@@ -1,4 +1,4 @@
-use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc, time::Duration};
 
 use glam::Vec3;
 
@@ -8,28 +8,57 @@ use crate::{
 	effect::EffectBuilder,
 	listener::ListenerId,
 	playback_state_manager::PlaybackStateManager,
+	track::{
+		order_sidechain, Captures, ClippingBehavior, LatencyCompensation, LevelMeter, Muffle,
+		MuffleCurve, SidechainOutputs, StereoPlacement, TrackSounds, DEFAULT_RMS_WINDOW,
+	},
 	tween::Easing,
-	Decibels, Frame, Parameter, Value,
+	Decibels, Frame, Panning, Parameter, PlaybackRate, ResourceLimitReached, Value,
 };
 
 use super::{
-	command_writers_and_readers, Effect, SendTrackId, SendTrackRoute, SpatialData,
-	SpatialTrackHandle, Track, TrackShared,
+	command_writers_and_readers, AddedEffects, DuckSettings, Ducker, Effect, SendTap, SendTrackId,
+	SendTrackRoute, SpatialData, SpatialTrackHandle, Track, TrackHandle, TrackShared,
+	DEFAULT_DUCK_HOLD, DEFAULT_DUCK_THRESHOLD,
 };
 
 /// Configures a spatial mixer track.
 pub struct SpatialTrackBuilder {
 	/// The volume of the track.
 	pub(crate) volume: Value<Decibels>,
+	/// The playback rate of the sounds on the track.
+	pub(crate) playback_rate: Value<PlaybackRate>,
+	pub(crate) panning: Value<Panning>,
+	pub(crate) stereo_width: Value<f32>,
+	pub(crate) balance: Value<f32>,
+	/// How muffled the track is, from `0.0` to `1.0`.
+	pub(crate) muffle: Value<f32>,
+	/// How the muffle amount maps to the muffle filter's settings.
+	pub(crate) muffle_curve: MuffleCurve,
 	/// The effects that should be applied to the input audio
 	/// for this track.
 	pub(crate) effects: Vec<Box<dyn Effect>>,
+	/// The effects that should be applied after the track's volume.
+	pub(crate) post_fader_effects: Vec<Box<dyn Effect>>,
 	/// The number of child tracks that can be added to this track.
 	pub(crate) sub_track_capacity: usize,
 	/// The maximum number of sounds that can be played simultaneously on this track.
 	pub(crate) sound_capacity: usize,
-	pub(crate) sends: HashMap<SendTrackId, Value<Decibels>>,
+	/// The maximum number of effects that can be added to the track after
+	/// it's created.
+	pub(crate) effect_capacity: usize,
+	pub(crate) sends: HashMap<SendTrackId, (Value<Decibels>, SendTap)>,
 	pub(crate) persist_until_sounds_finish: bool,
+	/// Whether the track's effects are processed even when the track
+	/// receives no input.
+	pub(crate) always_process: bool,
+	/// Whether the track processes a single channel of audio.
+	pub(crate) mono: bool,
+	/// Whether the track is delayed to line up with its sibling tracks.
+	pub(crate) compensate_latency: bool,
+	/// What the track does with samples that go above `1.0` or below
+	/// `-1.0`.
+	pub(crate) clipping_behavior: ClippingBehavior,
 	/// The distances from a listener at which the track is loudest and quietest.
 	pub(crate) distances: SpatialTrackDistances,
 	/// How the track's volume will change with distance.
@@ -42,6 +71,17 @@ pub struct SpatialTrackBuilder {
 	/// This value should be between `0.0` and `1.0`. `0.0` disables spatialization
 	/// entirely.
 	pub(crate) spatialization_strength: Value<f32>,
+	/// The length of the window RMS levels are measured over.
+	pub(crate) rms_window: Duration,
+	/// How the track is ducked by another track, if it is.
+	pub(crate) duck_settings: Option<DuckSettings>,
+	/// How loud the track ducking this track has to be to duck it.
+	pub(crate) duck_threshold: Decibels,
+	/// How long this track stays ducked after the track ducking it gets
+	/// quieter than the threshold.
+	pub(crate) duck_hold: Duration,
+	/// The tracks used as sidechain inputs by the track.
+	pub(crate) sidechain_sources: Vec<Arc<TrackShared>>,
 }
 
 impl SpatialTrackBuilder {
@@ -50,11 +90,28 @@ impl SpatialTrackBuilder {
 	pub fn new() -> Self {
 		Self {
 			volume: Value::Fixed(Decibels::IDENTITY),
+			playback_rate: Value::Fixed(PlaybackRate(1.0)),
+			panning: Value::Fixed(Panning::CENTER),
+			stereo_width: Value::Fixed(1.0),
+			balance: Value::Fixed(0.0),
+			muffle: Value::Fixed(0.0),
+			muffle_curve: MuffleCurve::default(),
 			effects: vec![],
+			post_fader_effects: vec![],
 			sub_track_capacity: 128,
 			sound_capacity: 128,
+			effect_capacity: 16,
 			sends: HashMap::new(),
 			persist_until_sounds_finish: false,
+			always_process: false,
+			mono: false,
+			compensate_latency: true,
+			clipping_behavior: ClippingBehavior::None,
+			rms_window: DEFAULT_RMS_WINDOW,
+			duck_settings: None,
+			duck_threshold: DEFAULT_DUCK_THRESHOLD,
+			duck_hold: DEFAULT_DUCK_HOLD,
+			sidechain_sources: vec![],
 			distances: SpatialTrackDistances::default(),
 			attenuation_function: Some(Easing::Linear),
 			spatialization_strength: Value::Fixed(0.75),
@@ -107,6 +164,73 @@ impl SpatialTrackBuilder {
 		}
 	}
 
+	/// Sets the playback rate of every sound on the track and its
+	/// sub-tracks.
+	///
+	/// See [`SpatialTrackHandle::set_playback_rate`] for more information.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn playback_rate(self, playback_rate: impl Into<Value<PlaybackRate>>) -> Self {
+		Self {
+			playback_rate: playback_rate.into(),
+			..self
+		}
+	}
+
+	/// Sets the panning of the track.
+	///
+	/// See [`SpatialTrackHandle::set_panning`] for more information.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets how far apart the track's left and right channels are spread.
+	///
+	/// See [`SpatialTrackHandle::set_stereo_width`] for more information.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn stereo_width(self, stereo_width: impl Into<Value<f32>>) -> Self {
+		Self {
+			stereo_width: stereo_width.into(),
+			..self
+		}
+	}
+
+	/// Sets the balance between the track's left and right channels.
+	///
+	/// See [`SpatialTrackHandle::set_balance`] for more information.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn balance(self, balance: impl Into<Value<f32>>) -> Self {
+		Self {
+			balance: balance.into(),
+			..self
+		}
+	}
+
+	/// Sets how muffled the track is, from `0.0` (not muffled) to `1.0`
+	/// (fully muffled).
+	///
+	/// See [`SpatialTrackHandle::set_muffle`] for more information.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn muffle(self, muffle: impl Into<Value<f32>>) -> Self {
+		Self {
+			muffle: muffle.into(),
+			..self
+		}
+	}
+
+	/// Sets how the track's muffle amount maps to the cutoff frequency and
+	/// volume of its muffle filter. Defaults to [`MuffleCurve::default`].
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn muffle_curve(self, muffle_curve: MuffleCurve) -> Self {
+		Self {
+			muffle_curve,
+			..self
+		}
+	}
+
 	/// Sets the maximum number of sub-tracks this track can have.
 	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
 	pub fn sub_track_capacity(self, capacity: usize) -> Self {
@@ -125,13 +249,64 @@ impl SpatialTrackBuilder {
 		}
 	}
 
+	/// Sets the maximum number of effects that can be added to the track
+	/// after it's created. Defaults to `16`.
+	///
+	/// Effects passed to [`add_effect`](Self::add_effect) and
+	/// [`with_effect`](Self::with_effect) don't count towards this limit,
+	/// and there's no limit to how many of them a track can have.
+	/// Effects that were removed from the track count towards it until
+	/// they finish fading out. Once the limit is reached,
+	/// [`SpatialTrackHandle::add_effect`](super::SpatialTrackHandle::add_effect) returns
+	/// [`ResourceLimitReached`].
+	///
+	/// Room for this many effects is allocated when the track is created,
+	/// since the audio thread can't allocate memory. Each slot is the size
+	/// of a few pointers, so large capacities are cheap. The effects
+	/// themselves don't get their own audio buffers: every effect on the
+	/// track processes the track's audio in place, and the one scratch
+	/// buffer of [`internal_buffer_size`](crate::AudioManagerSettings::internal_buffer_size)
+	/// frames the track allocates is reused by its sounds, sub-tracks, and
+	/// effects, so the memory cost of a long effect chain is only the memory
+	/// the effects use for their own state, like the buffer of a delay.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn effect_capacity(self, capacity: usize) -> Self {
+		Self {
+			effect_capacity: capacity,
+			..self
+		}
+	}
+
 	/// Routes this track to the given send track with the given volume.
+	///
+	/// A track can be routed to any number of send tracks. The track's
+	/// output keeps going to its parent track as well. If the send track
+	/// is removed, the route stops sending audio to it.
 	pub fn with_send(
 		mut self,
 		track: impl Into<SendTrackId>,
 		volume: impl Into<Value<Decibels>>,
 	) -> Self {
-		self.sends.insert(track.into(), volume.into());
+		self.sends
+			.insert(track.into(), (volume.into(), SendTap::PostFader));
+		self
+	}
+
+	/// Routes this track to the given send track with the given volume,
+	/// taking the audio from the given point in the track.
+	///
+	/// Routes added with [`with_send`](Self::with_send) use
+	/// [`SendTap::PostFader`]. Pre-fader routes keep sending the same
+	/// amount of audio when the track's volume changes, but they're still
+	/// silenced when the track is paused or muted.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn with_send_tap(
+		mut self,
+		track: impl Into<SendTrackId>,
+		volume: impl Into<Value<Decibels>>,
+		tap: SendTap,
+	) -> Self {
+		self.sends.insert(track.into(), (volume.into(), tap));
 		self
 	}
 
@@ -178,6 +353,42 @@ impl SpatialTrackBuilder {
 		self
 	}
 
+	/// Adds an effect to the track that's applied before the track's
+	/// volume.
+	///
+	/// This is the same as [`add_effect`](Self::add_effect).
+	pub fn add_effect_pre_fader<B: EffectBuilder>(&mut self, builder: B) -> B::Handle {
+		self.add_effect(builder)
+	}
+
+	/// Adds an effect to the track that's applied after the track's
+	/// volume.
+	///
+	/// Post-fader effects are applied after every pre-fader effect,
+	/// including effects added with
+	/// [`TrackHandle::add_effect`](super::TrackHandle::add_effect), and
+	/// after the track's ducking and spatialization. Changing the track's
+	/// volume changes how loud the audio they receive is, so effects
+	/// like a compressor or distortion respond to the volume, but it
+	/// doesn't interrupt them.
+	pub fn add_effect_post_fader<B: EffectBuilder>(&mut self, builder: B) -> B::Handle {
+		let (effect, handle) = builder.build();
+		self.post_fader_effects.push(effect);
+		handle
+	}
+
+	/// Adds an effect to the track that's applied after the track's volume
+	/// and returns the [`SpatialTrackBuilder`].
+	///
+	/// If you need to modify the effect later, use
+	/// [`add_effect_post_fader`](Self::add_effect_post_fader), which
+	/// returns the effect handle.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn with_effect_post_fader<B: EffectBuilder>(mut self, builder: B) -> Self {
+		self.add_effect_post_fader(builder);
+		self
+	}
+
 	/** Adds an already built effect into this track.
 
 	`Box<dyn Effect>` values are created when calling `build` on an effect builder, which gives you
@@ -243,6 +454,91 @@ impl SpatialTrackBuilder {
 		}
 	}
 
+	/// Sets whether the track's effects are processed even when the track
+	/// receives no input. Defaults to `false`.
+	///
+	/// By default, a track that receives no input skips processing its
+	/// effects once every effect reports that it's idle (see
+	/// [`Effect::is_idle`](crate::effect::Effect::is_idle)). Effects that
+	/// generate sound from nothing should return `false` from `is_idle`,
+	/// but this can be used for effects that don't.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn always_process(self, always_process: bool) -> Self {
+		Self {
+			always_process,
+			..self
+		}
+	}
+
+	/// Makes the track process a single channel of audio.
+	///
+	/// The audio from the track's sounds and sub-tracks is mixed down to
+	/// mono before the track's effects are applied, so sounds played on
+	/// the track can't be panned. Effects are processed with
+	/// [`Effect::process_mono`](crate::effect::Effect::process_mono), which
+	/// lets effects like [filters](crate::effect::filter) only process one
+	/// channel, and the track outputs the same audio to both channels.
+	///
+	/// The track is still spatialized after its effects are applied, so
+	/// its output to its parent track is only mono if the track is
+	/// directly in front of or behind the listener.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn mono(self) -> Self {
+		Self { mono: true, ..self }
+	}
+
+	/**
+	Sets whether the track is delayed to line up with its sibling tracks
+	when it has less latency than them. Defaults to `true`.
+
+	Effects that delay their input, like limiters that look ahead, report
+	how much they delay it with
+	[`Effect::latency_frames`](crate::effect::Effect::latency_frames). A
+	track's latency is the latency of its effects plus the latency of
+	the sub-track with the most latency. Sub-tracks of the same parent
+	track, or top-level tracks, are delayed so they all have the same
+	latency, which keeps sounds that are split across them from being
+	misaligned when they're mixed together. Tracks are delayed by up to
+	100 milliseconds.
+
+	If this is `false`, the track isn't delayed, and its sibling tracks
+	aren't delayed to line up with it. This is useful for tracks that
+	should respond as quickly as possible, like UI sounds. Sounds played
+	directly on a parent track and send tracks are never delayed.
+	*/
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn compensate_latency(self, compensate_latency: bool) -> Self {
+		Self {
+			compensate_latency,
+			..self
+		}
+	}
+
+	/// Sets what the track does with samples that go above `1.0` or below
+	/// `-1.0`. Defaults to [`ClippingBehavior::None`], since the main
+	/// track clips the final output anyway.
+	///
+	/// The track's levels are measured before clipping, so
+	/// [`TrackLevels::clipped`](crate::track::TrackLevels::clipped) still
+	/// reports samples that were clipped.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn clipping_behavior(self, clipping_behavior: ClippingBehavior) -> Self {
+		Self {
+			clipping_behavior,
+			..self
+		}
+	}
+
+	/// Sets the length of the window the track's RMS levels are measured
+	/// over. Defaults to 300 milliseconds.
+	///
+	/// Shorter windows make level meters react faster, and longer windows
+	/// make them smoother.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn rms_window(self, rms_window: Duration) -> Self {
+		Self { rms_window, ..self }
+	}
+
 	/// Sets the distances from a listener at which the emitter is loudest and quietest.
 	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
 	pub fn distances(self, distances: impl Into<SpatialTrackDistances>) -> Self {
@@ -276,6 +572,93 @@ impl SpatialTrackBuilder {
 		}
 	}
 
+	/**
+	Turns the track down by `amount` while `source` is outputting audio.
+
+	The track is turned down over `attack` when `source` gets louder than
+	the threshold set by [`duck_threshold`](Self::duck_threshold), and
+	it's turned back up over `release` after `source` stays quieter than
+	the threshold for the time set by [`duck_hold`](Self::duck_hold).
+	`amount` should be negative, like `-12.0`, and it can be changed
+	later with [`SpatialTrackHandle::set_duck_amount`]. If `source` is removed, the
+	track is turned back up.
+
+	`source` is used as a sidechain input, so like
+	[`TrackBuilder::add_effect_with_sidechain`](crate::track::TrackBuilder::add_effect_with_sidechain),
+	this returns an error if too many effects are already using `source`
+	as a sidechain input, and adding the track panics if `source` can't
+	be processed before this track. Calling this again replaces the
+	previous ducking.
+
+	# Examples
+
+	Duck the music under dialogue:
+
+	```no_run
+	use std::time::Duration;
+
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend,
+		track::{SpatialTrackBuilder, TrackBuilder},
+		Decibels,
+	};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let dialogue = manager.add_sub_track(TrackBuilder::new())?;
+	let mut builder = SpatialTrackBuilder::new();
+	builder.duck_by(
+		&dialogue,
+		Decibels(-12.0),
+		Duration::from_millis(50),
+		Duration::from_millis(500),
+	)?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn duck_by(
+		&mut self,
+		source: &TrackHandle,
+		amount: Decibels,
+		attack: Duration,
+		release: Duration,
+	) -> Result<(), ResourceLimitReached> {
+		let sidechain = source.controls().sidechain_output_controller.connect()?;
+		self.duck_settings = Some(DuckSettings {
+			amount,
+			attack,
+			release,
+			sidechain,
+		});
+		self.sidechain_sources.push(source.shared.clone());
+		Ok(())
+	}
+
+	/// Sets how loud the track passed to [`duck_by`](Self::duck_by) has
+	/// to be to duck this track. Defaults to -30 decibels.
+	///
+	/// This keeps quiet sounds, like breaths in dialogue, from ducking the
+	/// track.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn duck_threshold(self, threshold: impl Into<Decibels>) -> Self {
+		Self {
+			duck_threshold: threshold.into(),
+			..self
+		}
+	}
+
+	/// Sets how long the track stays ducked after the track passed to
+	/// [`duck_by`](Self::duck_by) gets quieter than the threshold.
+	/// Defaults to 100 milliseconds.
+	///
+	/// This keeps the track from being turned up during short pauses.
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn duck_hold(self, hold: Duration) -> Self {
+		Self {
+			duck_hold: hold,
+			..self
+		}
+	}
+
 	#[must_use]
 	pub(crate) fn build(
 		self,
@@ -283,20 +666,32 @@ impl SpatialTrackBuilder {
 		internal_buffer_size: usize,
 		listener_id: ListenerId,
 		position: Value<Vec3>,
+		parent: Option<Arc<TrackShared>>,
 	) -> (Track, SpatialTrackHandle) {
 		let (command_writers, command_readers) = command_writers_and_readers();
-		let shared = Arc::new(TrackShared::new());
+		let shared = Arc::new(TrackShared::new(parent));
+		for source in &self.sidechain_sources {
+			assert!(
+				order_sidechain(source, &shared, &renderer_shared.sub_track_order),
+				"a sidechain input depends on the output of the track it's used on"
+			);
+		}
 		let (sounds, sound_controller) = ResourceStorage::new(self.sound_capacity);
 		let (sub_tracks, sub_track_controller) = ResourceStorage::new(self.sub_track_capacity);
+		let (added_effects, added_effect_controller) = AddedEffects::new(self.effect_capacity);
+		let (captures, capture_controller) = Captures::new();
+		// spatial tracks can't be used as sidechain inputs
+		let (sidechain_outputs, _) = SidechainOutputs::new(internal_buffer_size);
 		let mut sends = vec![];
 		let mut send_volume_command_writers = HashMap::new();
-		for (send_track_id, volume) in self.sends {
+		for (send_track_id, (volume, tap)) in self.sends {
 			let (set_volume_command_writer, set_volume_command_reader) =
 				command_writer_and_reader();
 			sends.push((
 				send_track_id,
 				SendTrackRoute {
 					volume: Parameter::new(volume, Decibels::IDENTITY),
+					tap,
 					set_volume_command_reader,
 				},
 			));
@@ -306,11 +701,28 @@ impl SpatialTrackBuilder {
 			shared: shared.clone(),
 			command_readers,
 			volume: Parameter::new(self.volume, Decibels::IDENTITY),
-			sounds,
+			playback_rate: Parameter::new(self.playback_rate, PlaybackRate(1.0)),
+			stereo_placement: StereoPlacement::new(self.panning, self.stereo_width, self.balance),
+			muffle: Muffle::new(self.muffle, self.muffle_curve),
+			sounds: TrackSounds::new(sounds, internal_buffer_size),
 			sub_tracks,
+			sub_track_order: Vec::with_capacity(self.sub_track_capacity),
 			effects: self.effects,
+			post_fader_effects: self.post_fader_effects,
+			added_effects,
+			ducker: self.duck_settings.map(|settings| {
+				Ducker::new(
+					settings,
+					self.duck_threshold,
+					self.duck_hold,
+					internal_buffer_size,
+				)
+			}),
 			sends,
 			persist_until_sounds_finish: self.persist_until_sounds_finish,
+			removing: false,
+			always_process: self.always_process,
+			mono: self.mono,
 			spatial_data: Some(SpatialData {
 				listener_id,
 				position: Parameter::new(position, Vec3::ZERO),
@@ -319,6 +731,17 @@ impl SpatialTrackBuilder {
 				spatialization_strength: Parameter::new(self.spatialization_strength, 0.75),
 			}),
 			playback_state_manager: PlaybackStateManager::new(None),
+			muted: false,
+			soloed_out: false,
+			mute_volume: Parameter::new(Value::Fixed(1.0), 1.0),
+			level_meter: LevelMeter::new(self.rms_window),
+			clipping_behavior: self.clipping_behavior,
+			captures,
+			sidechain_outputs,
+			latency_compensation: LatencyCompensation::new(self.compensate_latency),
+			latency: 0,
+			num_sound_playback_commands: 0,
+			sound_playback_command: None,
 			temp_buffer: vec![Frame::ZERO; internal_buffer_size],
 			internal_buffer_size,
 		};
@@ -328,6 +751,8 @@ impl SpatialTrackBuilder {
 			command_writers,
 			sound_controller,
 			sub_track_controller,
+			added_effect_controller,
+			capture_controller,
 			send_volume_command_writers,
 			internal_buffer_size,
 		};
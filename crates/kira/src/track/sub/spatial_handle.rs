@@ -6,21 +6,31 @@ use std::{
 use crate::{
 	backend::{resources::ResourceController, RendererShared},
 	command::{CommandWriter, ValueChangeCommand},
+	effect::{EffectBuilder, EffectHandle, EffectId},
+	group::GroupPlaybackCommand,
 	listener::ListenerId,
 	sound::{Sound, SoundData},
-	track::TrackPlaybackState,
-	Decibels, PlaySoundError, ResourceLimitReached, StartTime, Tween, Value,
+	track::{
+		CaptureController, CaptureHandle, ClippingBehavior, TrackId, TrackLevels,
+		TrackPlaybackState,
+	},
+	Decibels, Panning, PlaySoundError, PlaybackRate, ResourceLimitReached, StartTime, Tween, Value,
 };
 
 use super::{
-	CommandWriters, NonexistentRoute, SendTrackId, SpatialTrackBuilder, Track, TrackBuilder,
-	TrackHandle, TrackShared,
+	AddSubTrackError, AddedEffectController, CommandWriters, NonexistentRoute, SendTrackId,
+	SetEffectOrderError, SpatialTrackBuilder, Track, TrackBuilder, TrackHandle, TrackShared,
 };
 
 /// Controls a mixer track.
 ///
-/// When a [`SpatialTrackHandle`] is dropped, the corresponding mixer
-/// track will be removed.
+/// When a [`SpatialTrackHandle`] is dropped, the corresponding mixer track is
+/// removed the next time the audio thread processes audio, which frees
+/// its slot for a new track. Tracks with sub-tracks wait until their
+/// sub-tracks are removed, and tracks set to
+/// [persist until their sounds finish](SpatialTrackBuilder::persist_until_sounds_finish)
+/// wait for their sounds as well. To fade out the track and stop its
+/// sounds first, use [`remove`](Self::remove).
 #[derive(Debug)]
 pub struct SpatialTrackHandle {
 	pub(crate) renderer_shared: Arc<RendererShared>,
@@ -28,12 +38,20 @@ pub struct SpatialTrackHandle {
 	pub(crate) command_writers: CommandWriters,
 	pub(crate) sound_controller: ResourceController<Box<dyn Sound>>,
 	pub(crate) sub_track_controller: ResourceController<Track>,
+	pub(crate) added_effect_controller: AddedEffectController,
+	pub(crate) capture_controller: CaptureController,
 	pub(crate) send_volume_command_writers:
 		HashMap<SendTrackId, CommandWriter<ValueChangeCommand<Decibels>>>,
 	pub(crate) internal_buffer_size: usize,
 }
 
 impl SpatialTrackHandle {
+	/// Returns a unique identifier for this track.
+	#[must_use]
+	pub fn id(&self) -> TrackId {
+		self.shared.id()
+	}
+
 	/// Returns the current playback state of the track.
 	#[must_use]
 	pub fn state(&self) -> TrackPlaybackState {
@@ -55,12 +73,20 @@ impl SpatialTrackHandle {
 	}
 
 	/// Adds a child track to this track.
+	///
+	/// Spatial tracks don't have names, so if the child track has a
+	/// [name](TrackBuilder::name), its full name is just that name.
 	pub fn add_sub_track(
 		&mut self,
 		builder: TrackBuilder,
-	) -> Result<TrackHandle, ResourceLimitReached> {
-		let (mut track, handle) =
-			builder.build(self.renderer_shared.clone(), self.internal_buffer_size);
+	) -> Result<TrackHandle, AddSubTrackError> {
+		let (mut track, handle) = builder.build(
+			self.renderer_shared.clone(),
+			self.internal_buffer_size,
+			Some(self.shared.clone()),
+			None,
+		);
+		handle.register_name()?;
 		track.init_effects(self.renderer_shared.sample_rate.load(Ordering::SeqCst));
 		self.sub_track_controller.insert(track)?;
 		Ok(handle)
@@ -78,12 +104,63 @@ impl SpatialTrackHandle {
 			self.internal_buffer_size,
 			listener.into(),
 			position.into().to_(),
+			Some(self.shared.clone()),
 		);
 		track.init_effects(self.renderer_shared.sample_rate.load(Ordering::SeqCst));
 		self.sub_track_controller.insert(track)?;
 		Ok(handle)
 	}
 
+	/// Adds an effect to the track.
+	///
+	/// The effect is applied after the track's other effects, including
+	/// effects that were added earlier with this method. It fades in over a
+	/// few milliseconds to avoid clicks.
+	///
+	/// Returns [`ResourceLimitReached`] if the track already has
+	/// [`effect_capacity`](Self::effect_capacity) effects that were added
+	/// with this method.
+	pub fn add_effect<B: EffectBuilder>(
+		&mut self,
+		builder: B,
+	) -> Result<EffectHandle<B::Handle>, ResourceLimitReached> {
+		self.added_effect_controller.add(
+			builder,
+			self.renderer_shared.sample_rate.load(Ordering::SeqCst),
+			self.internal_buffer_size,
+		)
+	}
+
+	/// Removes an effect that was added with [`add_effect`](Self::add_effect).
+	///
+	/// The effect fades out over a few milliseconds to avoid clicks. Nothing
+	/// happens if the effect was already removed.
+	pub fn remove_effect(&mut self, effect: impl Into<EffectId>) {
+		self.added_effect_controller.remove(effect.into());
+	}
+
+	/// Sets the order the effects added with [`add_effect`](Self::add_effect)
+	/// are applied in.
+	///
+	/// `order` must list every effect on the track that hasn't been removed
+	/// exactly once. Otherwise, the effects aren't reordered and an error is
+	/// returned. The effects keep their internal state, like the echoes in
+	/// a delay, when they're reordered.
+	pub fn set_effect_order(&mut self, order: &[EffectId]) -> Result<(), SetEffectOrderError> {
+		self.added_effect_controller.set_order(order)
+	}
+
+	/// Fades out the track with the given tween and then removes it,
+	/// freeing its slot for a new track.
+	///
+	/// Every sound on the track and its sub-tracks is stopped with the
+	/// same tween, and the sub-tracks are removed along with the track,
+	/// even if their handles still exist.
+	pub fn remove(mut self, tween: Tween) {
+		self.command_writers.remove.write(tween);
+		self.shared.request_removal();
+	}
+
 	/// Sets the (post-effects) volume of the mixer track.
 	pub fn set_volume(&mut self, volume: impl Into<Value<Decibels>>, tween: Tween) {
 		self.command_writers.set_volume.write(ValueChangeCommand {
@@ -119,10 +196,157 @@ impl SpatialTrackHandle {
 			})
 	}
 
+	/// Sets the playback rate of every sound on the track and its
+	/// sub-tracks.
+	///
+	/// The track's playback rate is multiplied by the playback rate of
+	/// each sound, and by the playback rates of the track's parent tracks,
+	/// so a sound playing at `2.0` on a track set to `0.5` plays at its
+	/// original speed. This changes the pitch of the sounds as well
+	/// as their speed.
+	///
+	/// Clocks aren't affected, so sounds and tracks that are waiting for
+	/// a clock tick or a delay to start still start at the same time.
+	pub fn set_playback_rate(
+		&mut self,
+		playback_rate: impl Into<Value<PlaybackRate>>,
+		tween: Tween,
+	) {
+		self.command_writers
+			.set_playback_rate
+			.write(ValueChangeCommand {
+				target: playback_rate.into(),
+				tween,
+			})
+	}
+
+	/// Sets the panning of the track, which moves the audio towards the
+	/// left or right speaker the same way as the panning of a sound.
+	///
+	/// The track's panning, [stereo width](Self::set_stereo_width), and
+	/// [balance](Self::set_balance) are applied after its volume, so they
+	/// don't affect the audio sent to pre-fader send tracks. They're
+	/// applied after the track is spatialized, so they move the audio
+	/// relative to where the listener hears it.
+	pub fn set_panning(&mut self, panning: impl Into<Value<Panning>>, tween: Tween) {
+		self.command_writers.set_panning.write(ValueChangeCommand {
+			target: panning.into(),
+			tween,
+		})
+	}
+
+	/// Sets how far apart the track's left and right channels are spread.
+	///
+	/// A width of `0.0` mixes the track down to mono, `1.0` leaves it as it
+	/// is, and higher values exaggerate the differences between the
+	/// channels. The width only changes the difference between the
+	/// channels, so the track sounds the same as before when it's mixed
+	/// down to mono. Negative widths are treated as `0.0`.
+	pub fn set_stereo_width(&mut self, stereo_width: impl Into<Value<f32>>, tween: Tween) {
+		self.command_writers
+			.set_stereo_width
+			.write(ValueChangeCommand {
+				target: stereo_width.into(),
+				tween,
+			})
+	}
+
+	/// Sets the balance between the track's left and right channels.
+	///
+	/// Unlike [panning](Self::set_panning), which moves the audio from one
+	/// side to the other, the balance only turns down one of the channels.
+	/// A balance of `-1.0` silences the right channel, `1.0` silences the
+	/// left channel, and `0.0` leaves both channels as they are.
+	pub fn set_balance(&mut self, balance: impl Into<Value<f32>>, tween: Tween) {
+		self.command_writers.set_balance.write(ValueChangeCommand {
+			target: balance.into(),
+			tween,
+		})
+	}
+
+	/// Sets how muffled the track is, from `0.0` (not muffled) to `1.0`
+	/// (fully muffled).
+	///
+	/// Muffling low-pass filters the track and turns it down, which is
+	/// useful for making a group of sounds seem like they're underwater or
+	/// behind a wall. The filter is applied after the track's effects and
+	/// before it's spatialized, and the track's
+	/// [muffle curve](SpatialTrackBuilder::muffle_curve) controls how the
+	/// muffle amount maps to the filter's cutoff frequency and volume.
+	/// While the muffle amount is `0.0`, the filter is skipped entirely.
+	pub fn set_muffle(&mut self, muffle: impl Into<Value<f32>>, tween: Tween) {
+		self.command_writers.set_muffle.write(ValueChangeCommand {
+			target: muffle.into(),
+			tween,
+		})
+	}
+
+	/// Sets how much the track is turned down while the track passed to
+	/// [`SpatialTrackBuilder::duck_by`] is outputting audio.
+	///
+	/// This does nothing if the track isn't ducked by another track.
+	pub fn set_duck_amount(&mut self, amount: impl Into<Value<Decibels>>, tween: Tween) {
+		self.command_writers
+			.set_duck_amount
+			.write(ValueChangeCommand {
+				target: amount.into(),
+				tween,
+			})
+	}
+
+	/// Sets what the track does with samples that go above `1.0` or below
+	/// `-1.0`.
+	pub fn set_clipping_behavior(&mut self, clipping_behavior: ClippingBehavior) {
+		self.command_writers
+			.set_clipping_behavior
+			.write(clipping_behavior)
+	}
+
+	/// Returns the levels of the audio the track output most recently.
+	///
+	/// Reading the levels resets [`TrackLevels::clipped`].
+	#[must_use]
+	pub fn level(&self) -> TrackLevels {
+		self.shared.levels().read()
+	}
+
+	/// Returns whether the track output a sample above `1.0` or below
+	/// `-1.0` since the last time this method was called.
+	///
+	/// This is measured before the track's
+	/// [clipping behavior](ClippingBehavior) is applied, and it's tracked
+	/// separately from [`TrackLevels::clipped`], so calling [`level`](Self::level)
+	/// doesn't reset it.
+	#[must_use]
+	pub fn clipped_since_last_read(&self) -> bool {
+		self.shared.levels().read_clipped()
+	}
+
+	/// Starts recording the audio this track outputs into a ring buffer
+	/// that can hold `ring_buffer_capacity` frames.
+	///
+	/// Up to 4 captures can record a track at once.
+	pub fn start_capture(
+		&mut self,
+		ring_buffer_capacity: usize,
+	) -> Result<CaptureHandle, ResourceLimitReached> {
+		self.capture_controller.start(ring_buffer_capacity)
+	}
+
+	/// Mutes or unmutes the track.
+	///
+	/// The track fades out or in over a few milliseconds to avoid clicks.
+	/// Muting doesn't change the track's volume, so unmuting it restores
+	/// the volume it had before, even if it's linked to a modulator.
+	pub fn set_muted(&mut self, muted: bool) {
+		self.command_writers.set_muted.write(muted)
+	}
+
 	/// Sets the volume of this track's route to a send track.
 	///
 	/// This can only be used to change the volume of existing routes,
-	/// not to add new routes.
+	/// not to add new routes. Like the track's own volume, the volume
+	/// of a route can be tweened or linked to a modulator.
 	pub fn set_send(
 		&mut self,
 		to: impl Into<SendTrackId>,
@@ -160,6 +384,35 @@ impl SpatialTrackHandle {
 		self.command_writers.resume.write((start_time, tween))
 	}
 
+	/// Fades out every sound playing on this track with the given tween and
+	/// then pauses them. If `include_sub_tracks` is `true`, the sounds
+	/// playing on this track's sub-tracks are paused too.
+	///
+	/// See [`TrackHandle::pause_all`] for more information.
+	pub fn pause_all(&mut self, tween: Tween, include_sub_tracks: bool) {
+		self.command_writers
+			.set_sound_playback
+			.write((GroupPlaybackCommand::Pause(tween), include_sub_tracks))
+	}
+
+	/// Resumes every sound playing on this track and fades them in from
+	/// silence with the given tween. If `include_sub_tracks` is `true`,
+	/// the sounds playing on this track's sub-tracks are resumed too.
+	pub fn resume_all(&mut self, tween: Tween, include_sub_tracks: bool) {
+		self.command_writers
+			.set_sound_playback
+			.write((GroupPlaybackCommand::Resume(tween), include_sub_tracks))
+	}
+
+	/// Fades out every sound playing on this track with the given tween and
+	/// then stops them. If `include_sub_tracks` is `true`, the sounds
+	/// playing on this track's sub-tracks are stopped too.
+	pub fn stop_all(&mut self, tween: Tween, include_sub_tracks: bool) {
+		self.command_writers
+			.set_sound_playback
+			.write((GroupPlaybackCommand::Stop(tween), include_sub_tracks))
+	}
+
 	/// Returns the maximum number of sounds that can play simultaneously on this track.
 	#[must_use]
 	pub fn sound_capacity(&self) -> usize {
@@ -172,6 +425,32 @@ impl SpatialTrackHandle {
 		self.sound_controller.len()
 	}
 
+	/// Returns the number of sounds on this track in each
+	/// [`PlaybackState`](crate::sound::PlaybackState), in the order of
+	/// [`PlaybackState::ALL`](crate::sound::PlaybackState::ALL).
+	///
+	/// Sounds on the track's sub-tracks aren't counted. This is updated
+	/// by the audio thread every time it processes a batch of audio, so
+	/// sounds that were just played may not be counted yet.
+	#[must_use]
+	pub fn num_sounds_by_state(&self) -> [usize; 7] {
+		self.shared.sound_counts().read()
+	}
+
+	/// Returns how many frames the track's effects and sub-tracks delay its
+	/// audio by.
+	///
+	/// This is the latency the track's effects report, plus the latency
+	/// of the sub-track with the most latency. It doesn't include the delay
+	/// added to line the track up with its sibling tracks. See
+	/// [`SpatialTrackBuilder::compensate_latency`] for more information. This is
+	/// updated by the audio thread every time it processes a batch of
+	/// audio.
+	#[must_use]
+	pub fn reported_latency(&self) -> usize {
+		self.shared.latency()
+	}
+
 	/// Returns the maximum number of child tracks this track can have.
 	#[must_use]
 	pub fn sub_track_capacity(&self) -> usize {
@@ -183,6 +462,31 @@ impl SpatialTrackHandle {
 	pub fn num_sub_tracks(&self) -> usize {
 		self.sub_track_controller.len()
 	}
+
+	/// Returns the maximum number of effects that can be added to this
+	/// track with [`add_effect`](Self::add_effect).
+	#[must_use]
+	pub fn effect_capacity(&self) -> usize {
+		self.added_effect_controller.capacity()
+	}
+
+	/// Returns the number of effects added with [`add_effect`](Self::add_effect)
+	/// that count towards the track's [`effect_capacity`](Self::effect_capacity).
+	///
+	/// Effects are counted as soon as they're added, and effects that were
+	/// removed are counted until they finish fading out.
+	#[must_use]
+	pub fn num_effects(&self) -> usize {
+		self.added_effect_controller.len()
+	}
+
+	/// Returns the number of send tracks this track is routed to.
+	///
+	/// Routes are set up when the track is created, so this doesn't change.
+	#[must_use]
+	pub fn num_sends(&self) -> usize {
+		self.send_volume_command_writers.len()
+	}
 }
 
 impl Drop for SpatialTrackHandle {
@@ -0,0 +1,114 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use crate::{effect::EffectTemplate, track::MuffleCurve, Decibels, Panning, PlaybackRate, Value};
+
+use super::{ClippingBehavior, SendTap, SendTrackId, TrackBuilder};
+
+/// The settings of a track, which are kept up to date by the track's
+/// handle and used by [`TrackHandle::builder_snapshot`](super::TrackHandle::builder_snapshot).
+#[derive(Debug, Clone)]
+pub(crate) struct TrackTemplate {
+	pub volume: Value<Decibels>,
+	pub playback_rate: Value<PlaybackRate>,
+	pub panning: Value<Panning>,
+	pub stereo_width: Value<f32>,
+	pub balance: Value<f32>,
+	pub muffle: Value<f32>,
+	muffle_curve: MuffleCurve,
+	pub sends: HashMap<SendTrackId, (Value<Decibels>, SendTap)>,
+	sub_track_capacity: usize,
+	sound_capacity: usize,
+	effect_capacity: usize,
+	persist_until_sounds_finish: bool,
+	always_process: bool,
+	mono: bool,
+	compensate_latency: bool,
+	pub clipping_behavior: ClippingBehavior,
+	rms_window: Duration,
+	duck_threshold: Decibels,
+	duck_hold: Duration,
+	/// The templates of the effects the track was created with, or `None`
+	/// for effects that can't be copied.
+	effects: Vec<Option<Arc<dyn EffectTemplate>>>,
+	post_fader_effects: Vec<Option<Arc<dyn EffectTemplate>>>,
+}
+
+impl TrackTemplate {
+	#[must_use]
+	pub fn new(builder: &TrackBuilder) -> Self {
+		Self {
+			volume: builder.volume,
+			playback_rate: builder.playback_rate,
+			panning: builder.panning,
+			stereo_width: builder.stereo_width,
+			balance: builder.balance,
+			muffle: builder.muffle,
+			muffle_curve: builder.muffle_curve,
+			sends: builder.sends.clone(),
+			sub_track_capacity: builder.sub_track_capacity,
+			sound_capacity: builder.sound_capacity,
+			effect_capacity: builder.effect_capacity,
+			persist_until_sounds_finish: builder.persist_until_sounds_finish,
+			always_process: builder.always_process,
+			mono: builder.mono,
+			compensate_latency: builder.compensate_latency,
+			clipping_behavior: builder.clipping_behavior,
+			rms_window: builder.rms_window,
+			duck_threshold: builder.duck_threshold,
+			duck_hold: builder.duck_hold,
+			effects: builder
+				.effects
+				.iter()
+				.map(|effect| effect.template())
+				.collect(),
+			post_fader_effects: builder
+				.post_fader_effects
+				.iter()
+				.map(|effect| effect.template())
+				.collect(),
+		}
+	}
+
+	/// Creates a [`TrackBuilder`] with the track's settings and copies of
+	/// its effects, with copies of the effects in `added_effects` after its
+	/// other pre-fader effects.
+	#[must_use]
+	pub fn to_builder<'a>(
+		&'a self,
+		added_effects: impl IntoIterator<Item = &'a Arc<dyn EffectTemplate>>,
+	) -> TrackBuilder {
+		let mut builder = TrackBuilder {
+			name: None,
+			volume: self.volume,
+			playback_rate: self.playback_rate,
+			panning: self.panning,
+			stereo_width: self.stereo_width,
+			balance: self.balance,
+			muffle: self.muffle,
+			muffle_curve: self.muffle_curve,
+			effects: vec![],
+			post_fader_effects: vec![],
+			sub_track_capacity: self.sub_track_capacity,
+			sound_capacity: self.sound_capacity,
+			effect_capacity: self.effect_capacity,
+			sends: self.sends.clone(),
+			persist_until_sounds_finish: self.persist_until_sounds_finish,
+			always_process: self.always_process,
+			mono: self.mono,
+			compensate_latency: self.compensate_latency,
+			clipping_behavior: self.clipping_behavior,
+			rms_window: self.rms_window,
+			duck_settings: None,
+			duck_threshold: self.duck_threshold,
+			duck_hold: self.duck_hold,
+			sidechain_sources: vec![],
+		};
+		for template in self.effects.iter().flatten().chain(added_effects) {
+			builder.add_built_effect(template.create_effect());
+		}
+		for template in self.post_fader_effects.iter().flatten() {
+			builder.post_fader_effects.push(template.create_effect());
+		}
+		builder
+	}
+}
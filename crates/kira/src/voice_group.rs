@@ -0,0 +1,234 @@
+/*!
+Limits on how many instances of a sound can play at once.
+
+When a sound is triggered rapidly, like a footstep or a button click,
+many overlapping copies of it can build up, wasting sound capacity
+and producing a muddy mix. A voice group caps the number of sounds
+that can play at the same time. When a new sound in a full voice group
+starts, another sound in the group is stopped with a very short fade-out
+to make room for it.
+
+To create a voice group, use [`AudioManager::add_voice_group`](crate::AudioManager::add_voice_group).
+Sounds can then be added to the group using
+[`StaticSoundSettings::voice_group`](crate::sound::static_sound::StaticSoundSettings::voice_group).
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	sound::static_sound::StaticSoundData,
+	voice_group::VoiceStealPolicy,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let voice_group = manager.add_voice_group(4, VoiceStealPolicy::Oldest)?;
+let sound_data = StaticSoundData::from_file("click.ogg")?.voice_group(&voice_group);
+// only the 4 most recent clicks will be heard
+for _ in 0..10 {
+	manager.play(sound_data.clone())?;
+}
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+Voice groups only affect sounds that are playing, so sounds in a group
+can be routed to any mixer track.
+*/
+
+mod handle;
+
+#[cfg(test)]
+mod test;
+
+pub use handle::*;
+
+use std::sync::{
+	atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+	Arc,
+};
+
+use atomic_arena::Key;
+
+/// A unique identifier for a voice group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VoiceGroupId(pub(crate) Key);
+
+impl From<&VoiceGroupHandle> for VoiceGroupId {
+	fn from(handle: &VoiceGroupHandle) -> Self {
+		handle.id()
+	}
+}
+
+/// Which sound is stopped when a new sound starts in a full voice group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoiceStealPolicy {
+	/// The sound that started playing first is stopped.
+	#[default]
+	Oldest,
+	/// The sound with the lowest output volume is stopped. If multiple
+	/// sounds are equally quiet, the oldest one is stopped.
+	Quietest,
+}
+
+pub(crate) struct VoiceGroup {
+	shared: Arc<VoiceGroupShared>,
+}
+
+impl VoiceGroup {
+	#[must_use]
+	pub fn new(
+		id: VoiceGroupId,
+		max_instances: usize,
+		steal_policy: VoiceStealPolicy,
+	) -> (Self, VoiceGroupHandle) {
+		let shared = Arc::new(VoiceGroupShared::new(max_instances, steal_policy));
+		(
+			Self {
+				shared: shared.clone(),
+			},
+			VoiceGroupHandle { id, shared },
+		)
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> &Arc<VoiceGroupShared> {
+		&self.shared
+	}
+}
+
+/// The state of a voice group shared between the voice group handle
+/// and the sounds playing in the group.
+///
+/// Voices are only claimed and released on the audio thread; the atomics
+/// just allow the state to be shared with the gameplay thread.
+#[derive(Debug)]
+pub(crate) struct VoiceGroupShared {
+	removed: AtomicBool,
+	steal_policy: VoiceStealPolicy,
+	voices: Box<[Voice]>,
+	/// The identifier to give the next voice that's claimed. Later voices
+	/// have higher identifiers, and 0 marks an unused voice.
+	next_voice_id: AtomicU64,
+}
+
+impl VoiceGroupShared {
+	#[must_use]
+	fn new(max_instances: usize, steal_policy: VoiceStealPolicy) -> Self {
+		Self {
+			removed: AtomicBool::new(false),
+			steal_policy,
+			voices: (0..max_instances.max(1))
+				.map(|_| Voice::default())
+				.collect(),
+			next_voice_id: AtomicU64::new(1),
+		}
+	}
+
+	#[must_use]
+	pub fn is_marked_for_removal(&self) -> bool {
+		self.removed.load(Ordering::SeqCst)
+	}
+
+	pub fn mark_for_removal(&self) {
+		self.removed.store(true, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn num_playing(&self) -> usize {
+		self.voices
+			.iter()
+			.filter(|voice| voice.id.load(Ordering::SeqCst) != 0)
+			.count()
+	}
+
+	#[must_use]
+	pub fn max_instances(&self) -> usize {
+		self.voices.len()
+	}
+
+	/// Claims a voice for a new sound, taking it from another sound
+	/// if every voice is in use.
+	#[must_use]
+	pub fn claim(&self) -> VoiceClaim {
+		let id = self.next_voice_id.fetch_add(1, Ordering::SeqCst);
+		let index = self
+			.voices
+			.iter()
+			.position(|voice| voice.id.load(Ordering::SeqCst) == 0)
+			.unwrap_or_else(|| self.voice_to_steal());
+		let voice = &self.voices[index];
+		voice.id.store(id, Ordering::SeqCst);
+		voice.volume.store(1.0f32.to_bits(), Ordering::SeqCst);
+		VoiceClaim { index, id }
+	}
+
+	/// Returns `true` if the sound that claimed a voice still owns it.
+	#[must_use]
+	pub fn owns(&self, claim: VoiceClaim) -> bool {
+		self.voices[claim.index].id.load(Ordering::SeqCst) == claim.id
+	}
+
+	/// Reports the current output volume (as amplitude) of the sound
+	/// that owns a voice.
+	pub fn report_volume(&self, claim: VoiceClaim, volume: f32) {
+		if self.owns(claim) {
+			self.voices[claim.index]
+				.volume
+				.store(volume.to_bits(), Ordering::SeqCst);
+		}
+	}
+
+	/// Frees up a voice if the sound that claimed it still owns it.
+	pub fn release(&self, claim: VoiceClaim) {
+		self.voices[claim.index]
+			.id
+			.compare_exchange(claim.id, 0, Ordering::SeqCst, Ordering::SeqCst)
+			.ok();
+	}
+
+	#[must_use]
+	fn voice_to_steal(&self) -> usize {
+		let oldest = |voices: &mut dyn Iterator<Item = (usize, &Voice)>| {
+			voices
+				.min_by_key(|(_, voice)| voice.id.load(Ordering::SeqCst))
+				.map(|(index, _)| index)
+				.expect("voice groups always have at least one voice")
+		};
+		match self.steal_policy {
+			VoiceStealPolicy::Oldest => oldest(&mut self.voices.iter().enumerate()),
+			VoiceStealPolicy::Quietest => {
+				let quietest_volume = self
+					.voices
+					.iter()
+					.map(Voice::volume)
+					.fold(f32::INFINITY, f32::min);
+				oldest(
+					&mut self
+						.voices
+						.iter()
+						.enumerate()
+						.filter(|(_, voice)| voice.volume() <= quietest_volume),
+				)
+			}
+		}
+	}
+}
+
+/// A voice that was claimed by a sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct VoiceClaim {
+	index: usize,
+	id: u64,
+}
+
+#[derive(Debug, Default)]
+struct Voice {
+	id: AtomicU64,
+	volume: AtomicU32,
+}
+
+impl Voice {
+	#[must_use]
+	fn volume(&self) -> f32 {
+		f32::from_bits(self.volume.load(Ordering::SeqCst))
+	}
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use super::{VoiceGroupId, VoiceGroupShared};
+
+/// Controls a voice group.
+///
+/// When a [`VoiceGroupHandle`] is dropped, the corresponding
+/// voice group will be removed, and sounds in the group will
+/// no longer be limited.
+#[derive(Debug)]
+pub struct VoiceGroupHandle {
+	pub(crate) id: VoiceGroupId,
+	pub(crate) shared: Arc<VoiceGroupShared>,
+}
+
+impl VoiceGroupHandle {
+	/// Returns the unique identifier for the voice group.
+	#[must_use]
+	pub fn id(&self) -> VoiceGroupId {
+		self.id
+	}
+
+	/// Returns the maximum number of sounds that can play in the
+	/// group at once.
+	#[must_use]
+	pub fn max_instances(&self) -> usize {
+		self.shared.max_instances()
+	}
+
+	/// Returns the number of sounds currently playing in the group.
+	#[must_use]
+	pub fn num_playing(&self) -> usize {
+		self.shared.num_playing()
+	}
+}
+
+impl Drop for VoiceGroupHandle {
+	fn drop(&mut self) {
+		self.shared.mark_for_removal();
+	}
+}
@@ -0,0 +1,68 @@
+use super::{VoiceGroupShared, VoiceStealPolicy};
+
+/// Tests that a voice group hands out free voices before stealing any.
+#[test]
+fn claims_free_voices() {
+	let voice_group = VoiceGroupShared::new(2, VoiceStealPolicy::Oldest);
+	let first = voice_group.claim();
+	let second = voice_group.claim();
+	assert_eq!(voice_group.num_playing(), 2);
+	assert!(voice_group.owns(first));
+	assert!(voice_group.owns(second));
+}
+
+/// Tests that a full voice group steals the oldest voice when
+/// using `VoiceStealPolicy::Oldest`.
+#[test]
+fn steals_oldest() {
+	let voice_group = VoiceGroupShared::new(2, VoiceStealPolicy::Oldest);
+	let first = voice_group.claim();
+	let second = voice_group.claim();
+	voice_group.report_volume(first, 1.0);
+	voice_group.report_volume(second, 0.1);
+	let third = voice_group.claim();
+	assert!(!voice_group.owns(first));
+	assert!(voice_group.owns(second));
+	assert!(voice_group.owns(third));
+	assert_eq!(voice_group.num_playing(), 2);
+}
+
+/// Tests that a full voice group steals the quietest voice when
+/// using `VoiceStealPolicy::Quietest`.
+#[test]
+fn steals_quietest() {
+	let voice_group = VoiceGroupShared::new(3, VoiceStealPolicy::Quietest);
+	let first = voice_group.claim();
+	let second = voice_group.claim();
+	let third = voice_group.claim();
+	voice_group.report_volume(first, 1.0);
+	voice_group.report_volume(second, 0.1);
+	voice_group.report_volume(third, 0.1);
+	let fourth = voice_group.claim();
+	assert!(voice_group.owns(first));
+	// ties go to the oldest voice
+	assert!(!voice_group.owns(second));
+	assert!(voice_group.owns(third));
+	assert!(voice_group.owns(fourth));
+}
+
+/// Tests that released voices can be claimed again, and that releasing
+/// a voice that was stolen doesn't free the new owner's voice.
+#[test]
+fn release() {
+	let voice_group = VoiceGroupShared::new(1, VoiceStealPolicy::Oldest);
+	let first = voice_group.claim();
+	let second = voice_group.claim();
+	voice_group.release(first);
+	assert!(voice_group.owns(second));
+	assert_eq!(voice_group.num_playing(), 1);
+	voice_group.release(second);
+	assert_eq!(voice_group.num_playing(), 0);
+}
+
+/// Tests that a voice group always has room for at least one sound.
+#[test]
+fn zero_max_instances() {
+	let voice_group = VoiceGroupShared::new(0, VoiceStealPolicy::Oldest);
+	assert_eq!(voice_group.max_instances(), 1);
+}
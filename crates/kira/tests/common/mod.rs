@@ -0,0 +1,198 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use kira::{
+	backend::mock::MockBackend,
+	effect::{Effect, EffectBuilder},
+	info::Info,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	track::MainTrackBuilder,
+	AudioManager, AudioManagerSettings, Decibels, Easing, Frame, StartTime, Tween,
+};
+use rtrb::{Consumer, Producer, RingBuffer};
+
+/// A tween that applies changes immediately.
+pub const INSTANT: Tween = Tween {
+	start_time: StartTime::Immediate,
+	duration: Duration::ZERO,
+	easing: Easing::Linear,
+};
+
+/// A sound with the default settings that plays `frames`.
+pub fn static_sound_data(
+	sample_rate: u32,
+	frames: impl IntoIterator<Item = Frame>,
+) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate,
+		frames: frames.into_iter().collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+/// A long sound that plays a constant signal.
+pub fn sound_data() -> StaticSoundData {
+	static_sound_data(1, (0..100_000).map(|_| Frame::from_mono(1.0)))
+}
+
+/// Adds a constant signal to the input.
+struct SourceEffect(Frame);
+
+impl Effect for SourceEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		for frame in input {
+			*frame += self.0;
+		}
+	}
+}
+
+pub struct SourceEffectBuilder(pub Frame);
+
+impl SourceEffectBuilder {
+	/// Adds the same value to both channels of the input.
+	pub fn mono(value: f32) -> Self {
+		Self(Frame::from_mono(value))
+	}
+}
+
+impl EffectBuilder for SourceEffectBuilder {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(Box::new(SourceEffect(self.0)), ())
+	}
+}
+
+/// Reports the left channel of the last frame of each chunk of audio it
+/// receives.
+struct ProbeEffect {
+	producer: Producer<f32>,
+}
+
+impl Effect for ProbeEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		self.producer.push(input.last().unwrap().left).unwrap();
+	}
+}
+
+pub struct ProbeEffectBuilder;
+
+impl EffectBuilder for ProbeEffectBuilder {
+	type Handle = Consumer<f32>;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		let (producer, consumer) = RingBuffer::new(100);
+		(Box::new(ProbeEffect { producer }), consumer)
+	}
+}
+
+/// Reports every frame of audio it receives.
+struct FrameProbeEffect {
+	producer: Producer<Frame>,
+}
+
+impl Effect for FrameProbeEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		for frame in input {
+			self.producer.push(*frame).unwrap();
+		}
+	}
+}
+
+pub struct FrameProbeEffectBuilder;
+
+impl EffectBuilder for FrameProbeEffectBuilder {
+	type Handle = Consumer<Frame>;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		let (producer, consumer) = RingBuffer::new(1000);
+		(Box::new(FrameProbeEffect { producer }), consumer)
+	}
+}
+
+/// Processes one chunk of audio.
+pub fn process(manager: &mut AudioManager<MockBackend>) {
+	let backend = manager.backend_mut();
+	backend.on_start_processing();
+	backend.process();
+}
+
+/// Creates an audio manager that processes 4 frames at a time.
+pub fn manager() -> AudioManager<MockBackend> {
+	AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 4,
+		..Default::default()
+	})
+	.unwrap()
+}
+
+/// Creates an audio manager with a probe on the main track.
+pub fn manager_with_probe() -> (AudioManager<MockBackend>, Consumer<f32>) {
+	let mut main_track_builder = MainTrackBuilder::new();
+	let probe = main_track_builder.add_effect(ProbeEffectBuilder);
+	let manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		main_track_builder,
+		..Default::default()
+	})
+	.unwrap();
+	(manager, probe)
+}
+
+/// Creates an audio manager with a [`FrameProbeEffectBuilder`] on the
+/// main track.
+pub fn manager_with_frame_probe() -> (AudioManager<MockBackend>, Consumer<Frame>) {
+	let mut main_track_builder = MainTrackBuilder::new();
+	let probe = main_track_builder.add_effect(FrameProbeEffectBuilder);
+	let manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		main_track_builder,
+		..Default::default()
+	})
+	.unwrap();
+	(manager, probe)
+}
+
+/// Processes one chunk of audio and returns what the probe received.
+pub fn probe_output(manager: &mut AudioManager<MockBackend>, probe: &mut Consumer<f32>) -> f32 {
+	process(manager);
+	probe.pop().unwrap()
+}
+
+/// Processes one chunk of audio and returns every frame the probe
+/// received.
+pub fn frame_probe_output(
+	manager: &mut AudioManager<MockBackend>,
+	probe: &mut Consumer<Frame>,
+) -> Vec<Frame> {
+	process(manager);
+	let mut frames = vec![];
+	while let Ok(frame) = probe.pop() {
+		frames.push(frame);
+	}
+	frames
+}
+
+/// Processes `num_chunks` chunks of audio with a [`manager`] and returns
+/// the last frame the main track output.
+pub fn main_output(manager: &mut AudioManager<MockBackend>, num_chunks: usize) -> Frame {
+	let mut capture = manager.main_track().start_capture(4 * num_chunks).unwrap();
+	for _ in 0..num_chunks {
+		process(manager);
+	}
+	let mut frames = vec![];
+	capture.drain(&mut frames);
+	*frames.last().unwrap()
+}
+
+pub fn assert_amplitude(amplitude: f32, volume: Decibels) {
+	assert!(
+		(amplitude - volume.as_amplitude()).abs() < 1.0e-6,
+		"expected {}, got {}",
+		volume.as_amplitude(),
+		amplitude
+	);
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use kira::{
+	backend::mock::{MockBackend, MockBackendSettings},
+	track::{MainTrackBuilder, TrackBuilder},
+	AudioManager, AudioManagerSettings, Decibels, Frame, Tween,
+};
+
+use common::{main_output, SourceEffectBuilder};
+
+fn manager(main_track_builder: MainTrackBuilder) -> AudioManager<MockBackend> {
+	AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 4,
+		main_track_builder,
+		backend_settings: MockBackendSettings {
+			sample_rate: 48_000,
+		},
+		..Default::default()
+	})
+	.unwrap()
+}
+
+#[test]
+fn main_track_tone_control_is_transparent_by_default() {
+	let mut manager = manager(MainTrackBuilder::new());
+	let _track = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.3)))
+		.unwrap();
+	assert_eq!(main_output(&mut manager, 1), Frame::from_mono(0.3));
+}
+
+#[test]
+fn main_track_set_bass_adjusts_low_frequencies() {
+	let mut manager = manager(MainTrackBuilder::new());
+	let _track = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.3)))
+		.unwrap();
+	manager
+		.main_track()
+		.set_bass(Decibels(-6.0), Tween::default());
+	manager
+		.main_track()
+		.set_treble(Decibels(6.0), Tween::default());
+	// a constant signal is only affected by the bass adjustment
+	let output = main_output(&mut manager, 1000);
+	assert!((output.left - 0.3 * Decibels(-6.0).as_amplitude()).abs() < 0.01);
+}
+
+#[test]
+fn main_track_without_tone_control_ignores_set_bass() {
+	let mut manager = manager(MainTrackBuilder::new().tone_control(false));
+	let _track = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.3)))
+		.unwrap();
+	manager
+		.main_track()
+		.set_bass(Decibels(-6.0), Tween::default());
+	assert_eq!(main_output(&mut manager, 100), Frame::from_mono(0.3));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use kira::{
+	backend::mock::MockBackend,
+	track::{SendTap, SendTrackBuilder, TrackBuilder},
+	AudioManager, AudioManagerSettings, Decibels,
+};
+
+use common::{assert_amplitude, process, ProbeEffectBuilder, SourceEffectBuilder, INSTANT};
+
+#[test]
+fn sends_audio_to_multiple_send_tracks() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut reverb_probe;
+	let reverb = manager
+		.add_send_track({
+			let mut builder = SendTrackBuilder::new();
+			reverb_probe = builder.add_effect(ProbeEffectBuilder);
+			builder
+		})
+		.unwrap();
+	let mut delay_probe;
+	let delay = manager
+		.add_send_track({
+			let mut builder = SendTrackBuilder::new();
+			delay_probe = builder.add_effect(ProbeEffectBuilder);
+			builder
+		})
+		.unwrap();
+	let mut weapons = manager
+		.add_sub_track({
+			let mut builder = TrackBuilder::new()
+				.with_send(&reverb, Decibels(-12.0))
+				.with_send(&delay, Decibels(-6.0));
+			builder.add_effect(SourceEffectBuilder::mono(1.0));
+			builder
+		})
+		.unwrap();
+	assert_eq!(weapons.num_sends(), 2);
+	process(&mut manager);
+	assert_amplitude(reverb_probe.pop().unwrap(), Decibels(-12.0));
+	assert_amplitude(delay_probe.pop().unwrap(), Decibels(-6.0));
+
+	weapons.set_send(&reverb, Decibels(-3.0), INSTANT).unwrap();
+	process(&mut manager);
+	assert_amplitude(reverb_probe.pop().unwrap(), Decibels(-3.0));
+	assert_amplitude(delay_probe.pop().unwrap(), Decibels(-6.0));
+
+	// removing a send track stops audio from being routed to it without
+	// affecting the other sends
+	drop(delay);
+	process(&mut manager);
+	process(&mut manager);
+	assert_amplitude(reverb_probe.pop().unwrap(), Decibels(-3.0));
+	assert_amplitude(reverb_probe.pop().unwrap(), Decibels(-3.0));
+}
+
+#[test]
+fn pre_fader_sends_ignore_track_volume() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut pre_fader_probe;
+	let pre_fader = manager
+		.add_send_track({
+			let mut builder = SendTrackBuilder::new();
+			pre_fader_probe = builder.add_effect(ProbeEffectBuilder);
+			builder
+		})
+		.unwrap();
+	let mut post_fader_probe;
+	let post_fader = manager
+		.add_send_track({
+			let mut builder = SendTrackBuilder::new();
+			post_fader_probe = builder.add_effect(ProbeEffectBuilder);
+			builder
+		})
+		.unwrap();
+	let mut track = manager
+		.add_sub_track({
+			let mut builder = TrackBuilder::new()
+				.volume(Decibels(-12.0))
+				.with_send_tap(&pre_fader, Decibels(-6.0), SendTap::PreFader)
+				.with_send_tap(&post_fader, Decibels(-6.0), SendTap::PostFader);
+			builder.add_effect(SourceEffectBuilder::mono(1.0));
+			builder
+		})
+		.unwrap();
+	process(&mut manager);
+	assert_amplitude(pre_fader_probe.pop().unwrap(), Decibels(-6.0));
+	assert_amplitude(post_fader_probe.pop().unwrap(), Decibels(-18.0));
+
+	track.set_volume(Decibels(-3.0), INSTANT);
+	process(&mut manager);
+	assert_amplitude(pre_fader_probe.pop().unwrap(), Decibels(-6.0));
+	assert_amplitude(post_fader_probe.pop().unwrap(), Decibels(-9.0));
+
+	// pre-fader sends are still silenced when the track is muted
+	track.set_muted(true);
+	process(&mut manager);
+	assert_amplitude(pre_fader_probe.pop().unwrap(), Decibels::SILENCE);
+	assert_amplitude(post_fader_probe.pop().unwrap(), Decibels::SILENCE);
+}
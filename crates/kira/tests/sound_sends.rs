@@ -0,0 +1,105 @@
+mod common;
+
+use kira::{
+	backend::mock::MockBackend,
+	track::{SendTrackBuilder, SendTrackHandle, TrackBuilder},
+	AudioManager, Decibels,
+};
+use rtrb::Consumer;
+
+use common::{assert_amplitude, manager, process, sound_data, ProbeEffectBuilder, INSTANT};
+
+fn send_track_with_probe(
+	manager: &mut AudioManager<MockBackend>,
+) -> (SendTrackHandle, Consumer<f32>) {
+	let mut builder = SendTrackBuilder::new();
+	let probe = builder.add_effect(ProbeEffectBuilder);
+	(manager.add_send_track(builder).unwrap(), probe)
+}
+
+/// Processes two chunks of audio and returns the last amplitude the probe
+/// received.
+fn send_output(manager: &mut AudioManager<MockBackend>, probe: &mut Consumer<f32>) -> f32 {
+	process(manager);
+	process(manager);
+	let mut amplitude = None;
+	while let Ok(value) = probe.pop() {
+		amplitude = Some(value);
+	}
+	amplitude.unwrap()
+}
+
+#[test]
+fn only_sends_sounds_with_a_send() {
+	let mut manager = manager();
+	let (reverb, mut probe) = send_track_with_probe(&mut manager);
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let _sent = track
+		.play(sound_data().send(&reverb, Decibels(-6.0)))
+		.unwrap();
+	let _dry = track.play(sound_data()).unwrap();
+	assert_amplitude(send_output(&mut manager, &mut probe), Decibels(-6.0));
+}
+
+#[test]
+fn sends_the_sound_after_its_volume() {
+	let mut manager = manager();
+	let (reverb, mut probe) = send_track_with_probe(&mut manager);
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let _sound = track
+		.play(
+			sound_data()
+				.volume(Decibels(-3.0))
+				.send(&reverb, Decibels(-6.0)),
+		)
+		.unwrap();
+	assert_amplitude(send_output(&mut manager, &mut probe), Decibels(-9.0));
+}
+
+#[test]
+fn handle_changes_send_level() {
+	let mut manager = manager();
+	let (reverb, mut probe) = send_track_with_probe(&mut manager);
+	let (delay, _delay_probe) = send_track_with_probe(&mut manager);
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut sound = track
+		.play(sound_data().send(&reverb, Decibels(-6.0)))
+		.unwrap();
+	send_output(&mut manager, &mut probe);
+	sound
+		.set_send_level(&reverb, Decibels(-12.0), INSTANT)
+		.unwrap();
+	assert_amplitude(send_output(&mut manager, &mut probe), Decibels(-12.0));
+	assert!(sound
+		.set_send_level(&delay, Decibels(-12.0), INSTANT)
+		.is_err());
+}
+
+#[test]
+fn removed_send_tracks_are_skipped() {
+	let mut manager = manager();
+	let (reverb, mut probe) = send_track_with_probe(&mut manager);
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	// keep the output below the main track's hard clipping
+	let _sound = track
+		.play(
+			sound_data()
+				.volume(Decibels(-12.0))
+				.send(&reverb, Decibels(-6.0)),
+		)
+		.unwrap();
+	let mut capture = manager.main_track().start_capture(8).unwrap();
+	send_output(&mut manager, &mut probe);
+	let mut frames = vec![];
+	capture.drain(&mut frames);
+	let dry = Decibels(-12.0).as_amplitude();
+	let wet = Decibels(-18.0).as_amplitude();
+	assert!((frames.last().unwrap().left - (dry + wet)).abs() < 1.0e-6);
+
+	drop(reverb);
+	process(&mut manager);
+	process(&mut manager);
+	let mut frames = vec![];
+	capture.drain(&mut frames);
+	assert!((frames.last().unwrap().left - dry).abs() < 1.0e-6);
+}
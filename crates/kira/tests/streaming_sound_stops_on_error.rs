@@ -4,7 +4,7 @@ use kira::{
 	backend::mock::MockBackend,
 	sound::{
 		streaming::{Decoder, StreamingSoundData},
-		PlaybackState,
+		FinishReason, PlaybackState,
 	},
 	AudioManager, AudioManagerSettings, Frame,
 };
@@ -39,11 +39,13 @@ fn streaming_sound_stops_on_error() {
 	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
 	let data = StreamingSoundData::from_decoder(MockDecoder);
 	let mut sound = manager.play(data).unwrap();
+	let finished = sound.on_finished();
 	manager.backend_mut().on_start_processing();
 	std::thread::sleep(Duration::from_secs(1));
 	manager.backend_mut().process();
 	manager.backend_mut().on_start_processing();
 	assert_eq!(sound.state(), PlaybackState::Stopped);
+	assert_eq!(finished.try_recv(), Some(FinishReason::EncounteredError));
 	assert_eq!(sound.pop_error(), Some(MockDecoderError));
 	assert_eq!(manager.main_track().num_sounds(), 0);
 }
@@ -2,14 +2,21 @@ use kira::{
 	backend::cpal::CpalBackend,
 	clock::ClockHandle,
 	effect::{
-		compressor::CompressorHandle, delay::DelayHandle, distortion::DistortionHandle,
-		eq_filter::EqFilterHandle, filter::FilterHandle, panning_control::PanningControlHandle,
-		reverb::ReverbHandle, volume_control::VolumeControlHandle,
+		chorus::ChorusHandle, compressor::CompressorHandle,
+		convolution_reverb::ConvolutionReverbHandle, delay::DelayHandle,
+		distortion::DistortionHandle, eq_filter::EqFilterHandle, filter::FilterHandle,
+		gate::GateHandle, limiter::LimiterHandle, panning_control::PanningControlHandle,
+		parametric_eq::ParametricEqHandle, phaser::PhaserHandle, pitch_shift::PitchShiftHandle,
+		reverb::ReverbHandle, saturator::SaturatorHandle, stereo_tool::StereoToolHandle,
+		tone_control::ToneControlHandle, volume_control::VolumeControlHandle,
 	},
 	listener::ListenerHandle,
 	modulator::{lfo::LfoHandle, tweener::TweenerHandle},
-	sound::{static_sound::StaticSoundHandle, streaming::StreamingSoundHandle, FromFileError},
-	track::{MainTrackHandle, SendTrackHandle, SpatialTrackHandle, TrackHandle},
+	sound::{
+		live::LiveSoundHandle, static_sound::StaticSoundHandle, streaming::StreamingSoundHandle,
+		FromFileError,
+	},
+	track::{CaptureHandle, MainTrackHandle, SendTrackHandle, SpatialTrackHandle, TrackHandle},
 	AudioManager,
 };
 
@@ -23,6 +30,7 @@ fn main() {
 	sync_send::<ReverbHandle>();
 	sync_send::<TweenerHandle>();
 	sync_send::<EqFilterHandle>();
+	sync_send::<LimiterHandle>();
 	sync_send::<ListenerHandle>();
 	sync_send::<MainTrackHandle>();
 	sync_send::<SendTrackHandle>();
@@ -30,9 +38,20 @@ fn main() {
 	sync_send::<DistortionHandle>();
 	sync_send::<StaticSoundHandle>();
 	sync_send::<SpatialTrackHandle>();
+	sync_send::<ToneControlHandle>();
+	sync_send::<ParametricEqHandle>();
+	sync_send::<ConvolutionReverbHandle>();
+	sync_send::<ChorusHandle>();
+	sync_send::<PhaserHandle>();
+	sync_send::<PitchShiftHandle>();
+	sync_send::<StereoToolHandle>();
+	sync_send::<SaturatorHandle>();
+	sync_send::<GateHandle>();
 	sync_send::<VolumeControlHandle>();
 	sync_send::<PanningControlHandle>();
 	sync_send::<StreamingSoundHandle<FromFileError>>();
+	sync_send::<LiveSoundHandle>();
+	sync_send::<CaptureHandle>();
 }
 
 fn sync_send<T: Sync + Send>() {}
@@ -0,0 +1,45 @@
+mod common;
+
+use kira::{track::TrackBuilder, Decibels};
+
+use common::{manager, process, SourceEffectBuilder};
+
+#[test]
+fn captures_track_output() {
+	let mut manager = manager();
+	let mut track_builder = TrackBuilder::new();
+	track_builder.add_effect(SourceEffectBuilder::mono(1.0));
+	let mut track = manager
+		.add_sub_track(track_builder.volume(Decibels::from_amplitude(0.25)))
+		.unwrap();
+	let mut capture = track.start_capture(8).unwrap();
+	process(&mut manager);
+	process(&mut manager);
+	let mut frames = vec![];
+	assert_eq!(capture.drain(&mut frames), 8);
+	for frame in frames {
+		assert!((frame.left - 0.25).abs() < 0.0001);
+	}
+	assert_eq!(capture.num_dropped_frames(), 0);
+}
+
+#[test]
+fn counts_dropped_frames() {
+	let mut manager = manager();
+	let mut capture = manager.main_track().start_capture(6).unwrap();
+	process(&mut manager);
+	process(&mut manager);
+	assert_eq!(capture.drain(&mut vec![]), 6);
+	assert_eq!(capture.num_dropped_frames(), 2);
+}
+
+#[test]
+fn stops_capturing() {
+	let mut manager = manager();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut capture = track.start_capture(16).unwrap();
+	process(&mut manager);
+	capture.stop();
+	process(&mut manager);
+	assert_eq!(capture.drain(&mut vec![]), 4);
+}
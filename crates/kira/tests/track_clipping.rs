@@ -0,0 +1,79 @@
+mod common;
+
+use kira::{
+	backend::mock::MockBackend,
+	track::{ClippingBehavior, MainTrackBuilder, TrackBuilder},
+	AudioManager, AudioManagerSettings, Frame,
+};
+
+use common::{main_output, process, SourceEffectBuilder};
+
+fn manager(main_track_builder: MainTrackBuilder) -> AudioManager<MockBackend> {
+	AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 4,
+		main_track_builder,
+		..Default::default()
+	})
+	.unwrap()
+}
+
+#[test]
+fn main_track_hard_clips_by_default() {
+	let mut manager = manager(MainTrackBuilder::new());
+	let _track = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(1.5)))
+		.unwrap();
+	assert_eq!(main_output(&mut manager, 1), Frame::from_mono(1.0));
+	manager
+		.main_track()
+		.set_clipping_behavior(ClippingBehavior::None);
+	assert_eq!(main_output(&mut manager, 1), Frame::from_mono(1.5));
+}
+
+#[test]
+fn sub_tracks_can_soft_clip() {
+	let mut manager = manager(MainTrackBuilder::new().clipping_behavior(ClippingBehavior::None));
+	let mut track = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.with_effect(SourceEffectBuilder::mono(1.5))
+				.clipping_behavior(ClippingBehavior::SoftClip),
+		)
+		.unwrap();
+	let output = main_output(&mut manager, 1);
+	assert!(output.left > 0.9 && output.left < 1.0);
+	track.set_clipping_behavior(ClippingBehavior::Hard);
+	assert_eq!(main_output(&mut manager, 1), Frame::from_mono(1.0));
+}
+
+#[test]
+fn soft_clip_leaves_quiet_audio_untouched() {
+	let mut manager =
+		manager(MainTrackBuilder::new().clipping_behavior(ClippingBehavior::SoftClip));
+	let _track = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.5)))
+		.unwrap();
+	assert_eq!(main_output(&mut manager, 1), Frame::from_mono(0.5));
+}
+
+#[test]
+fn latches_clipping_per_track() {
+	let mut manager = manager(MainTrackBuilder::new());
+	let loud = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.with_effect(SourceEffectBuilder::mono(0.75))
+				.volume(12.0),
+		)
+		.unwrap();
+	let quiet = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.1)))
+		.unwrap();
+	process(&mut manager);
+	// reading the levels doesn't reset the flag
+	assert!(loud.level().clipped);
+	assert!(loud.clipped_since_last_read());
+	assert!(!loud.clipped_since_last_read());
+	assert!(!quiet.clipped_since_last_read());
+	assert!(manager.main_track().clipped_since_last_read());
+}
@@ -0,0 +1,196 @@
+mod common;
+
+use std::sync::{
+	atomic::{AtomicUsize, Ordering},
+	Arc,
+};
+
+use kira::{
+	backend::mock::MockBackend,
+	effect::{Effect, EffectBuilder},
+	info::Info,
+	track::{SetEffectOrderError, TrackBuilder},
+	AudioManager, AudioManagerSettings, Decibels, Frame, Tween,
+};
+
+use common::{manager_with_probe, probe_output, SourceEffectBuilder};
+
+/// Multiplies the input by a constant.
+struct GainEffect(f32);
+
+impl Effect for GainEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		for frame in input {
+			*frame *= self.0;
+		}
+	}
+}
+
+struct GainEffectBuilder(f32);
+
+impl EffectBuilder for GainEffectBuilder {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(Box::new(GainEffect(self.0)), ())
+	}
+}
+
+/// Counts how many times it's been processed and is always idle.
+struct IdleEffect(Arc<AtomicUsize>);
+
+impl Effect for IdleEffect {
+	fn is_idle(&self) -> bool {
+		true
+	}
+
+	fn process(&mut self, _input: &mut [Frame], _dt: f64, _info: &Info) {
+		self.0.fetch_add(1, Ordering::SeqCst);
+	}
+}
+
+struct IdleEffectBuilder;
+
+impl EffectBuilder for IdleEffectBuilder {
+	type Handle = Arc<AtomicUsize>;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		let num_process_calls = Arc::new(AtomicUsize::new(0));
+		(
+			Box::new(IdleEffect(num_process_calls.clone())),
+			num_process_calls,
+		)
+	}
+}
+
+#[test]
+fn adds_and_removes_effects() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	probe_output(&mut manager, &mut probe);
+	assert_eq!(probe_output(&mut manager, &mut probe), 0.0);
+	let source = track.add_effect(SourceEffectBuilder::mono(1.0)).unwrap();
+	assert_eq!(probe_output(&mut manager, &mut probe), 1.0);
+	// effects are applied in the order they were added
+	let gain = track.add_effect(GainEffectBuilder(2.0)).unwrap();
+	assert_eq!(probe_output(&mut manager, &mut probe), 2.0);
+	track.remove_effect(&source);
+	assert_eq!(probe_output(&mut manager, &mut probe), 0.0);
+	track.remove_effect(gain.id());
+	track.add_effect(SourceEffectBuilder::mono(3.0)).unwrap();
+	assert_eq!(probe_output(&mut manager, &mut probe), 3.0);
+}
+
+#[test]
+fn limits_the_number_of_added_effects() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut track = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.effect_capacity(1)
+				// effects added to the builder don't count towards the limit
+				.with_effect(GainEffectBuilder(1.0))
+				.with_effect(GainEffectBuilder(1.0)),
+		)
+		.unwrap();
+	assert_eq!(track.effect_capacity(), 1);
+	assert_eq!(track.num_effects(), 0);
+	let source = track.add_effect(SourceEffectBuilder::mono(1.0)).unwrap();
+	assert_eq!(track.num_effects(), 1);
+	assert!(track.add_effect(SourceEffectBuilder::mono(1.0)).is_err());
+	track.remove_effect(&source);
+	// removed effects count until they finish fading out
+	assert_eq!(track.num_effects(), 1);
+	let backend = manager.backend_mut();
+	backend.on_start_processing();
+	backend.process();
+	backend.on_start_processing();
+	assert_eq!(track.num_effects(), 0);
+	assert!(track.add_effect(SourceEffectBuilder::mono(1.0)).is_ok());
+}
+
+#[test]
+fn reorders_effects() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let source = track.add_effect(SourceEffectBuilder::mono(1.0)).unwrap();
+	let gain = track.add_effect(GainEffectBuilder(2.0)).unwrap();
+	probe_output(&mut manager, &mut probe);
+	assert_eq!(probe_output(&mut manager, &mut probe), 2.0);
+	track.set_effect_order(&[gain.id(), source.id()]).unwrap();
+	assert_eq!(probe_output(&mut manager, &mut probe), 1.0);
+	// new effects are added to the end of the chain
+	track.add_effect(GainEffectBuilder(3.0)).unwrap();
+	assert_eq!(probe_output(&mut manager, &mut probe), 3.0);
+}
+
+#[test]
+fn rejects_invalid_effect_orders() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut other_track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let source = track.add_effect(SourceEffectBuilder::mono(1.0)).unwrap();
+	let gain = track.add_effect(GainEffectBuilder(2.0)).unwrap();
+	let other_effect = other_track.add_effect(GainEffectBuilder(2.0)).unwrap();
+	assert_eq!(
+		track.set_effect_order(&[source.id(), other_effect.id()]),
+		Err(SetEffectOrderError::NonexistentEffect(other_effect.id()))
+	);
+	assert_eq!(
+		track.set_effect_order(&[source.id(), source.id()]),
+		Err(SetEffectOrderError::DuplicateEffect(source.id()))
+	);
+	assert_eq!(
+		track.set_effect_order(&[source.id()]),
+		Err(SetEffectOrderError::MissingEffect(gain.id()))
+	);
+	track.remove_effect(&gain);
+	assert_eq!(track.set_effect_order(&[source.id()]), Ok(()));
+}
+
+#[test]
+fn skips_idle_effects_on_silent_tracks() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut silent_builder = TrackBuilder::new();
+	let silent_calls = silent_builder.add_effect(IdleEffectBuilder);
+	let _silent_track = manager.add_sub_track(silent_builder).unwrap();
+	// the idle effect is processed because the source effect isn't idle
+	let mut source_builder = TrackBuilder::new().with_effect(SourceEffectBuilder::mono(1.0));
+	let source_calls = source_builder.add_effect(IdleEffectBuilder);
+	let _source_track = manager.add_sub_track(source_builder).unwrap();
+	probe_output(&mut manager, &mut probe);
+	assert_eq!(silent_calls.load(Ordering::SeqCst), 0);
+	assert_eq!(source_calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn always_processes_effects_if_requested() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut builder = TrackBuilder::new().always_process(true);
+	let num_process_calls = builder.add_effect(IdleEffectBuilder);
+	let _track = manager.add_sub_track(builder).unwrap();
+	probe_output(&mut manager, &mut probe);
+	assert_eq!(num_process_calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn applies_post_fader_effects_after_volume() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut track = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.volume(Decibels(-6.0))
+				.with_effect(SourceEffectBuilder::mono(1.0))
+				.with_effect_post_fader(SourceEffectBuilder::mono(0.5)),
+		)
+		.unwrap();
+	let output = probe_output(&mut manager, &mut probe);
+	assert!((output - (Decibels(-6.0).as_amplitude() + 0.5)).abs() < 1.0e-6);
+	track.set_volume(Decibels::IDENTITY, Tween::default());
+	probe_output(&mut manager, &mut probe);
+	assert_eq!(probe_output(&mut manager, &mut probe), 1.5);
+	// muting the track silences the post-fader effects as well
+	track.set_muted(true);
+	probe_output(&mut manager, &mut probe);
+	assert_eq!(probe_output(&mut manager, &mut probe), 0.0);
+}
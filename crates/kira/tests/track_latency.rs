@@ -0,0 +1,180 @@
+mod common;
+
+use std::time::Duration;
+
+use kira::{
+	backend::mock::{MockBackend, MockBackendSettings},
+	effect::{limiter::LimiterBuilder, Effect, EffectBuilder},
+	info::Info,
+	track::{MainTrackBuilder, TrackBuilder},
+	AudioManager, AudioManagerSettings, Frame,
+};
+use rtrb::Consumer;
+
+use common::{frame_probe_output, FrameProbeEffectBuilder};
+
+/// Adds a single frame of audio to the input the first time it's
+/// processed.
+struct ImpulseEffect {
+	finished: bool,
+}
+
+impl Effect for ImpulseEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		if !self.finished {
+			input[0] += Frame::from_mono(1.0);
+			self.finished = true;
+		}
+	}
+}
+
+struct ImpulseEffectBuilder;
+
+impl EffectBuilder for ImpulseEffectBuilder {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(Box::new(ImpulseEffect { finished: false }), ())
+	}
+}
+
+/// Delays the input by a number of frames and reports that latency.
+struct LatencyEffect {
+	buffer: Vec<Frame>,
+}
+
+impl Effect for LatencyEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		for frame in input {
+			self.buffer.push(*frame);
+			*frame = self.buffer.remove(0);
+		}
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.buffer.len()
+	}
+}
+
+struct LatencyEffectBuilder(usize);
+
+impl EffectBuilder for LatencyEffectBuilder {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(
+			Box::new(LatencyEffect {
+				buffer: vec![Frame::ZERO; self.0],
+			}),
+			(),
+		)
+	}
+}
+
+/// Creates an audio manager with a sample rate of 100 Hz, so tracks can
+/// be delayed by up to 10 frames.
+fn manager_with_probe() -> (AudioManager<MockBackend>, Consumer<Frame>) {
+	let mut main_track_builder = MainTrackBuilder::new();
+	let probe = main_track_builder.add_effect(FrameProbeEffectBuilder);
+	let manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		main_track_builder,
+		backend_settings: MockBackendSettings { sample_rate: 100 },
+		..Default::default()
+	})
+	.unwrap();
+	(manager, probe)
+}
+
+/// Returns the indices of the frames that aren't silent.
+fn process(manager: &mut AudioManager<MockBackend>, probe: &mut Consumer<Frame>) -> Vec<usize> {
+	frame_probe_output(manager, probe)
+		.iter()
+		.enumerate()
+		.filter(|(_, frame)| **frame != Frame::ZERO)
+		.map(|(index, _)| index)
+		.collect()
+}
+
+#[test]
+fn delays_tracks_to_line_up_with_siblings() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let _slow = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.with_effect(ImpulseEffectBuilder)
+				.with_effect(LatencyEffectBuilder(3)),
+		)
+		.unwrap();
+	let _fast = manager
+		.add_sub_track(TrackBuilder::new().with_effect(ImpulseEffectBuilder))
+		.unwrap();
+	let mut parent = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let _nested = parent
+		.add_sub_track(TrackBuilder::new().with_effect(ImpulseEffectBuilder))
+		.unwrap();
+	assert_eq!(process(&mut manager, &mut probe), [3]);
+}
+
+#[test]
+fn tracks_can_opt_out_of_latency_compensation() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let _slow = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.with_effect(ImpulseEffectBuilder)
+				.with_effect(LatencyEffectBuilder(3)),
+		)
+		.unwrap();
+	let _fast = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.compensate_latency(false)
+				.with_effect(ImpulseEffectBuilder),
+		)
+		.unwrap();
+	assert_eq!(process(&mut manager, &mut probe), [0, 3]);
+}
+
+#[test]
+fn reports_latency_of_effects_and_sub_tracks() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut parent = manager
+		.add_sub_track(TrackBuilder::new().with_effect(LatencyEffectBuilder(2)))
+		.unwrap();
+	let _slow = parent
+		.add_sub_track(TrackBuilder::new().with_effect(LatencyEffectBuilder(3)))
+		.unwrap();
+	let _ignored = parent
+		.add_sub_track(
+			TrackBuilder::new()
+				.compensate_latency(false)
+				.with_effect(LatencyEffectBuilder(5)),
+		)
+		.unwrap();
+	let added = parent.add_effect(LatencyEffectBuilder(1)).unwrap();
+	process(&mut manager, &mut probe);
+	assert_eq!(parent.reported_latency(), 6);
+	parent.remove_effect(&added);
+	// the removed effect is counted until it finishes fading out
+	process(&mut manager, &mut probe);
+	process(&mut manager, &mut probe);
+	assert_eq!(parent.reported_latency(), 5);
+}
+
+#[test]
+fn compensates_for_limiter_lookahead() {
+	let (mut manager, mut probe) = manager_with_probe();
+	// at 100 Hz, a 10 millisecond lookahead delays the track by 1 frame
+	let limited = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.with_effect(ImpulseEffectBuilder)
+				.with_effect(LimiterBuilder::new().lookahead(Duration::from_millis(10))),
+		)
+		.unwrap();
+	let _unlimited = manager
+		.add_sub_track(TrackBuilder::new().with_effect(ImpulseEffectBuilder))
+		.unwrap();
+	assert_eq!(process(&mut manager, &mut probe), [1]);
+	assert_eq!(limited.reported_latency(), 1);
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use kira::{
+	effect::{
+		filter::{FilterBuilder, FilterMode},
+		Effect, EffectBuilder,
+	},
+	info::Info,
+	track::TrackBuilder,
+	Frame,
+};
+
+use common::{frame_probe_output, manager_with_frame_probe};
+
+/// Adds a signal that alternates between a frame and its inverse to
+/// the input.
+struct AlternatingEffect {
+	frame: Frame,
+}
+
+impl Effect for AlternatingEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		for frame in input {
+			*frame += self.frame;
+			self.frame = -self.frame;
+		}
+	}
+}
+
+struct AlternatingEffectBuilder(Frame);
+
+impl EffectBuilder for AlternatingEffectBuilder {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(Box::new(AlternatingEffect { frame: self.0 }), ())
+	}
+}
+
+#[test]
+fn mixes_input_down_to_mono() {
+	let (mut manager, mut probe) = manager_with_frame_probe();
+	let mut track = manager.add_sub_track(TrackBuilder::new().mono()).unwrap();
+	let mut sub_track_builder = TrackBuilder::new();
+	sub_track_builder.add_effect(AlternatingEffectBuilder(Frame::new(1.0, 0.0)));
+	let _sub_track = track.add_sub_track(sub_track_builder).unwrap();
+	frame_probe_output(&mut manager, &mut probe);
+	let frames = frame_probe_output(&mut manager, &mut probe);
+	assert!(!frames.is_empty());
+	for frame in frames {
+		assert_eq!(frame.left.abs(), 0.5);
+		assert_eq!(frame.left, frame.right);
+	}
+}
+
+#[test]
+fn mixes_output_of_stereo_effects_down_to_mono() {
+	let (mut manager, mut probe) = manager_with_frame_probe();
+	let mut builder = TrackBuilder::new().mono();
+	builder.add_effect(AlternatingEffectBuilder(Frame::new(0.0, 1.0)));
+	let _track = manager.add_sub_track(builder).unwrap();
+	frame_probe_output(&mut manager, &mut probe);
+	let frames = frame_probe_output(&mut manager, &mut probe);
+	assert!(!frames.is_empty());
+	for frame in frames {
+		assert_eq!(frame.left.abs(), 0.5);
+		assert_eq!(frame.left, frame.right);
+	}
+}
+
+#[test]
+fn filters_on_mono_tracks_match_stereo_tracks() {
+	let output = |mono: bool| {
+		let (mut manager, mut probe) = manager_with_frame_probe();
+		let mut builder = TrackBuilder::new();
+		if mono {
+			builder = builder.mono();
+		}
+		builder.add_effect(AlternatingEffectBuilder(Frame::from_mono(0.5)));
+		builder.add_effect(
+			FilterBuilder::new()
+				.mode(FilterMode::LowPass)
+				.cutoff(0.1)
+				.resonance(0.5),
+		);
+		let _track = manager.add_sub_track(builder).unwrap();
+		let mut frames = frame_probe_output(&mut manager, &mut probe);
+		frames.extend(frame_probe_output(&mut manager, &mut probe));
+		frames
+	};
+	let stereo = output(false);
+	assert!(stereo.iter().any(|frame| frame.left != 0.0));
+	assert_eq!(output(true), stereo);
+}
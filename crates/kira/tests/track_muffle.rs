@@ -0,0 +1,59 @@
+mod common;
+
+use kira::{
+	backend::mock::MockBackend,
+	track::{MuffleCurve, TrackBuilder, TrackHandle},
+	AudioManager, Decibels, Frame, Tween,
+};
+
+use common::{main_output, manager, SourceEffectBuilder};
+
+fn add_track(manager: &mut AudioManager<MockBackend>, builder: TrackBuilder) -> TrackHandle {
+	manager
+		.add_sub_track(builder.with_effect(SourceEffectBuilder::mono(0.5)))
+		.unwrap()
+}
+
+#[test]
+fn unmuffled_track_is_unchanged() {
+	let mut manager = manager();
+	let _track = add_track(&mut manager, TrackBuilder::new());
+	assert_eq!(main_output(&mut manager, 16), Frame::from_mono(0.5));
+}
+
+#[test]
+fn set_muffle_turns_down_track() {
+	let mut manager = manager();
+	let mut track = add_track(&mut manager, TrackBuilder::new());
+	track.set_muffle(1.0, Tween::default());
+	let output = main_output(&mut manager, 16);
+	assert!((output.left - 0.5 * Decibels(-6.0).as_amplitude()).abs() < 0.001);
+	track.set_muffle(0.0, Tween::default());
+	assert_eq!(main_output(&mut manager, 16), Frame::from_mono(0.5));
+}
+
+#[test]
+fn muffle_curve_sets_attenuation() {
+	let mut manager = manager();
+	let _track = add_track(
+		&mut manager,
+		TrackBuilder::new().muffle(1.0).muffle_curve(MuffleCurve {
+			attenuation: Decibels(-12.0),
+			..Default::default()
+		}),
+	);
+	let output = main_output(&mut manager, 16);
+	assert!((output.left - 0.5 * Decibels(-12.0).as_amplitude()).abs() < 0.001);
+}
+
+#[test]
+fn builder_snapshot_keeps_muffle() {
+	let mut manager = manager();
+	let mut track = add_track(&mut manager, TrackBuilder::new().muffle(1.0));
+	let builder = track.builder_snapshot();
+	track.pause(Tween::default());
+	// the source effect can't be copied, so the copy gets its own
+	let _copy = add_track(&mut manager, builder);
+	let output = main_output(&mut manager, 16);
+	assert!((output.left - 0.5 * Decibels(-6.0).as_amplitude()).abs() < 0.001);
+}
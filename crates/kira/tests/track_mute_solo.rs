@@ -0,0 +1,73 @@
+mod common;
+
+use kira::{track::TrackBuilder, Decibels};
+
+use common::{manager_with_probe, probe_output, SourceEffectBuilder};
+
+fn track_builder(value: f32) -> TrackBuilder {
+	let mut builder = TrackBuilder::new();
+	builder.add_effect(SourceEffectBuilder::mono(value));
+	builder
+}
+
+#[test]
+fn mutes_tracks_without_changing_their_volume() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut track = manager
+		.add_sub_track(track_builder(1.0).volume(Decibels(-6.0)))
+		.unwrap();
+	let _other_track = manager.add_sub_track(track_builder(10.0)).unwrap();
+	let unmuted = probe_output(&mut manager, &mut probe);
+	assert!((unmuted - (Decibels(-6.0).as_amplitude() + 10.0)).abs() < 1.0e-4);
+
+	track.set_muted(true);
+	assert_eq!(probe_output(&mut manager, &mut probe), 10.0);
+
+	track.set_muted(false);
+	assert!((probe_output(&mut manager, &mut probe) - unmuted).abs() < 1.0e-6);
+}
+
+#[test]
+fn solos_tracks() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let mut music = manager.add_sub_track(track_builder(1.0)).unwrap();
+	let mut drums = music.add_sub_track(track_builder(10.0)).unwrap();
+	let kick = drums.add_sub_track(track_builder(100.0)).unwrap();
+	let _bass = music.add_sub_track(track_builder(1000.0)).unwrap();
+	let mut sfx = manager.add_sub_track(track_builder(10_000.0)).unwrap();
+	assert_eq!(probe_output(&mut manager, &mut probe), 11_111.0);
+
+	// the soloed track's ancestors and descendants keep playing
+	manager.solo_track(&drums);
+	assert_eq!(manager.soloed_track(), Some(drums.id()));
+	assert_eq!(probe_output(&mut manager, &mut probe), 111.0);
+
+	manager.solo_track(&kick);
+	assert_eq!(probe_output(&mut manager, &mut probe), 111.0);
+
+	// soloing another track unsolos the first one
+	manager.solo_track(&sfx);
+	assert_eq!(probe_output(&mut manager, &mut probe), 10_000.0);
+
+	// soloed tracks can still be muted
+	sfx.set_muted(true);
+	assert_eq!(probe_output(&mut manager, &mut probe), 0.0);
+
+	sfx.set_muted(false);
+	drop(kick);
+	manager.clear_solo();
+	assert_eq!(manager.soloed_track(), None);
+	assert_eq!(probe_output(&mut manager, &mut probe), 11_011.0);
+}
+
+#[test]
+fn soloing_a_removed_track_does_not_silence_anything() {
+	let (mut manager, mut probe) = manager_with_probe();
+	let removed = manager.add_sub_track(track_builder(1.0)).unwrap();
+	let _other_track = manager.add_sub_track(track_builder(10.0)).unwrap();
+	manager.solo_track(&removed);
+	drop(removed);
+	// the track is removed at the start of the next chunk
+	probe_output(&mut manager, &mut probe);
+	assert_eq!(probe_output(&mut manager, &mut probe), 10.0);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use kira::{
+	track::{AddSubTrackError, TrackBuilder},
+	Decibels, Tween,
+};
+
+use common::{main_output, manager, SourceEffectBuilder};
+
+#[test]
+fn finds_tracks_by_full_name() {
+	let mut manager = manager();
+	let mut sfx = manager
+		.add_sub_track(TrackBuilder::new().name("sfx"))
+		.unwrap();
+	let weapons = sfx
+		.add_sub_track(TrackBuilder::new().name("weapons"))
+		.unwrap();
+	assert_eq!(weapons.name(), Some("sfx/weapons"));
+	assert_eq!(manager.track_by_name("sfx").unwrap().id(), sfx.id());
+	assert_eq!(
+		manager.track_by_name("sfx/weapons").unwrap().id(),
+		weapons.id()
+	);
+	assert!(manager.track_by_name("weapons").is_none());
+}
+
+#[test]
+fn found_handles_control_the_track() {
+	let mut manager = manager();
+	let _track = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.name("music")
+				.with_effect(SourceEffectBuilder::mono(1.0)),
+		)
+		.unwrap();
+	manager
+		.track_by_name("music")
+		.unwrap()
+		.set_volume(Decibels::from_amplitude(0.5), Tween::default());
+	assert!((main_output(&mut manager, 2).left - 0.5).abs() < 0.0001);
+}
+
+#[test]
+fn rejects_duplicate_names() {
+	let mut manager = manager();
+	let mut sfx = manager
+		.add_sub_track(TrackBuilder::new().name("sfx"))
+		.unwrap();
+	assert_eq!(
+		manager
+			.add_sub_track(TrackBuilder::new().name("sfx"))
+			.unwrap_err(),
+		AddSubTrackError::DuplicateName("sfx".to_string())
+	);
+	let _weapons = sfx
+		.add_sub_track(TrackBuilder::new().name("weapons"))
+		.unwrap();
+	assert_eq!(
+		sfx.add_sub_track(TrackBuilder::new().name("weapons"))
+			.unwrap_err(),
+		AddSubTrackError::DuplicateName("sfx/weapons".to_string())
+	);
+	// the same name can be used under a different parent
+	let _weapons = manager
+		.add_sub_track(TrackBuilder::new().name("weapons"))
+		.unwrap();
+	assert_eq!(sfx.num_sub_tracks(), 1);
+}
+
+#[test]
+fn frees_names_of_removed_tracks() {
+	let mut manager = manager();
+	let track = manager
+		.add_sub_track(TrackBuilder::new().name("ui"))
+		.unwrap();
+	// clones keep the track alive
+	let clone = track.clone();
+	drop(track);
+	assert_eq!(manager.track_by_name("ui").unwrap().id(), clone.id());
+	drop(clone);
+	assert!(manager.track_by_name("ui").is_none());
+	let track = manager
+		.add_sub_track(TrackBuilder::new().name("ui"))
+		.unwrap();
+	track.remove(Tween::default());
+	assert!(manager.track_by_name("ui").is_none());
+	manager
+		.add_sub_track(TrackBuilder::new().name("ui"))
+		.unwrap();
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use std::time::Duration;
+
+use kira::{
+	effect::volume_control::VolumeControlBuilder,
+	modulator::tweener::TweenerBuilder,
+	sound::{
+		static_sound::StaticSoundData,
+		streaming::{Decoder, StreamingSoundData},
+	},
+	track::{render_track, TrackBuilder, TrackRenderer},
+	Decibels, Easing, Frame, Mapping, Tween, Value,
+};
+
+use common::static_sound_data;
+
+const SAMPLE_RATE: u32 = 100;
+
+fn ramp(index: usize) -> Frame {
+	Frame::from_mono(index as f32 / 1000.0)
+}
+
+fn static_sound() -> StaticSoundData {
+	static_sound_data(SAMPLE_RATE, (0..1000).map(ramp))
+}
+
+/// Decodes the same ramp as [`static_sound`] a few frames at a time.
+struct RampDecoder {
+	position: usize,
+}
+
+impl Decoder for RampDecoder {
+	type Error = ();
+
+	fn sample_rate(&self) -> u32 {
+		SAMPLE_RATE
+	}
+
+	fn num_frames(&self) -> usize {
+		1000
+	}
+
+	fn decode(&mut self) -> Result<Vec<Frame>, Self::Error> {
+		let end = (self.position + 16).min(1000);
+		let frames = (self.position..end).map(ramp).collect();
+		self.position = end;
+		Ok(frames)
+	}
+
+	fn seek(&mut self, index: usize) -> Result<usize, Self::Error> {
+		self.position = index;
+		Ok(index)
+	}
+}
+
+#[test]
+fn renders_the_track_output() {
+	let rendered = render_track(
+		TrackBuilder::new().with_effect(VolumeControlBuilder::new(Decibels(-6.0))),
+		[static_sound(), static_sound()],
+		Duration::from_secs(2),
+		SAMPLE_RATE,
+	)
+	.unwrap();
+	assert_eq!(rendered.sample_rate, SAMPLE_RATE);
+	assert_eq!(rendered.frames.len(), 200);
+	let expected = static_sound().render(SAMPLE_RATE, 10_000);
+	for (rendered, expected) in rendered.frames.iter().zip(&expected) {
+		let expected = expected.left * 2.0 * Decibels(-6.0).as_amplitude();
+		assert!((rendered.left - expected).abs() < 1.0e-6);
+	}
+}
+
+#[test]
+fn renders_streaming_sounds_decoded_on_the_audio_thread() {
+	let rendered = render_track(
+		TrackBuilder::new(),
+		[
+			StreamingSoundData::from_decoder(RampDecoder { position: 0 })
+				.decode_on_audio_thread(true),
+		],
+		Duration::from_secs(5),
+		SAMPLE_RATE,
+	)
+	.unwrap();
+	let expected = static_sound().render(SAMPLE_RATE, 10_000);
+	assert_eq!(rendered.frames[..], expected[..500]);
+}
+
+#[test]
+fn renders_values_linked_to_modulators() {
+	let render = || {
+		let mut renderer = TrackRenderer::new(SAMPLE_RATE);
+		let manager = renderer.manager_mut();
+		let mut tweener = manager
+			.add_modulator(TweenerBuilder { initial_value: 0.0 })
+			.unwrap();
+		let mut track = manager
+			.add_sub_track(TrackBuilder::new().volume(Value::FromModulator {
+				id: tweener.id(),
+				mapping: Mapping {
+					input_range: (0.0, 1.0),
+					output_range: (Decibels::SILENCE, Decibels::IDENTITY),
+					easing: Easing::Linear,
+				},
+			}))
+			.unwrap();
+		track.play(static_sound()).unwrap();
+		tweener.set(
+			1.0,
+			Tween {
+				duration: Duration::from_secs(10),
+				..Default::default()
+			},
+		);
+		renderer
+			.render(&mut track, Duration::from_secs(10))
+			.unwrap()
+	};
+	let rendered = render();
+	// the volume fades in from silence over the whole render
+	assert!(rendered.frames[500].left < ramp(500).left * 0.1);
+	assert!(rendered.frames[999].left > rendered.frames[500].left);
+	assert_eq!(render().frames, rendered.frames);
+}
+
+#[test]
+fn continues_where_the_last_render_left_off() {
+	let mut renderer = TrackRenderer::new(SAMPLE_RATE);
+	let mut track = renderer
+		.manager_mut()
+		.add_sub_track(TrackBuilder::new())
+		.unwrap();
+	track.play(static_sound()).unwrap();
+	let first = renderer
+		.render(&mut track, Duration::from_millis(500))
+		.unwrap();
+	let second = renderer
+		.render(&mut track, Duration::from_millis(500))
+		.unwrap();
+	let expected = static_sound().render(SAMPLE_RATE, 10_000);
+	assert_eq!(first.frames[..], expected[..50]);
+	assert_eq!(second.frames[..], expected[50..100]);
+}
@@ -0,0 +1,229 @@
+mod common;
+
+use std::time::Duration;
+
+use kira::{
+	backend::mock::MockBackend,
+	effect::{compressor::CompressorBuilder, Effect, EffectBuilder},
+	info::Info,
+	track::{TrackBuilder, TrackHandle},
+	AudioManager, AudioManagerSettings, Decibels, Frame, Tween,
+};
+use rtrb::{Consumer, Producer, RingBuffer};
+
+use common::{process, ProbeEffectBuilder, SourceEffectBuilder};
+
+/// Reports the last frame of each chunk of sidechain audio it receives.
+struct SidechainProbeEffect {
+	producer: Producer<f32>,
+}
+
+impl Effect for SidechainProbeEffect {
+	fn process(&mut self, _input: &mut [Frame], _dt: f64, _info: &Info) {
+		panic!("sidechain probe was processed without a sidechain input");
+	}
+
+	fn process_with_sidechain(
+		&mut self,
+		_input: &mut [Frame],
+		sidechain: &[Frame],
+		_dt: f64,
+		_info: &Info,
+	) {
+		self.producer.push(sidechain.last().unwrap().left).unwrap();
+	}
+}
+
+struct SidechainProbeEffectBuilder;
+
+impl EffectBuilder for SidechainProbeEffectBuilder {
+	type Handle = Consumer<f32>;
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		let (producer, consumer) = RingBuffer::new(100);
+		(Box::new(SidechainProbeEffect { producer }), consumer)
+	}
+}
+
+fn probed_track(source: &TrackHandle) -> (TrackBuilder, Consumer<f32>) {
+	let mut builder = TrackBuilder::new();
+	let probe = builder
+		.add_effect_with_sidechain(SidechainProbeEffectBuilder, source)
+		.unwrap();
+	(builder, probe)
+}
+
+#[test]
+fn receives_the_output_of_the_source_track() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let source = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.5)))
+		.unwrap();
+	let (builder, mut probe) = probed_track(&source);
+	let _consumer = manager.add_sub_track(builder).unwrap();
+	// the source is processed first even though the consumer was added
+	// later
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(0.5));
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(0.5));
+}
+
+#[test]
+fn processes_sources_on_other_branches_first() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut source_parent = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let source = source_parent
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.5)))
+		.unwrap();
+	let mut consumer_parent = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let (builder, mut probe) = probed_track(&source);
+	let _consumer = consumer_parent.add_sub_track(builder).unwrap();
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(0.5));
+}
+
+#[test]
+fn can_use_sub_tracks_as_sources() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut parent = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let source = parent
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.5)))
+		.unwrap();
+	let (builder, mut probe) = probed_track(&source);
+	let _sibling = parent.add_sub_track(builder).unwrap();
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(0.5));
+}
+
+#[test]
+fn receives_silence_after_the_source_is_removed() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let source = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.5)))
+		.unwrap();
+	let (builder, mut probe) = probed_track(&source);
+	let _consumer = manager.add_sub_track(builder).unwrap();
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(0.5));
+	drop(source);
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(0.0));
+}
+
+#[test]
+#[should_panic]
+fn rejects_ancestors_as_sources() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut parent = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let (builder, _probe) = probed_track(&parent);
+	let _ = parent.add_sub_track(builder);
+}
+
+#[test]
+#[should_panic]
+fn rejects_cycles() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut a = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut b = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let a_source = a.add_sub_track(TrackBuilder::new()).unwrap();
+	let b_source = b.add_sub_track(TrackBuilder::new()).unwrap();
+	let (builder, _probe) = probed_track(&a_source);
+	let _b_consumer = b.add_sub_track(builder).unwrap();
+	// `a` would have to be processed both before and after `b`
+	let (builder, _probe) = probed_track(&b_source);
+	let _ = a.add_sub_track(builder);
+}
+
+#[test]
+fn ducks_with_a_compressor() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let dialogue = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(1.0)))
+		.unwrap();
+	let mut builder = TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.5));
+	let compressor = builder
+		.add_effect_with_sidechain(
+			CompressorBuilder::new().threshold(-20.0).ratio(4.0),
+			&dialogue,
+		)
+		.unwrap();
+	let mut probe = builder.add_effect(ProbeEffectBuilder);
+	let _music = manager.add_sub_track(builder).unwrap();
+	assert_eq!(compressor.gain_reduction(), Decibels(0.0));
+	process(&mut manager);
+	// the music is quieter than the threshold, so it's only compressed
+	// because of the dialogue
+	let level = probe.pop().unwrap();
+	assert!(level < 0.5, "{}", level);
+	let gain_reduction = compressor.gain_reduction();
+	assert!(gain_reduction.0 < 0.0, "{:?}", gain_reduction);
+	assert!((gain_reduction.as_amplitude() * 0.5 - level).abs() < 1.0e-4);
+}
+
+/// Adds a track ducked by `source` under a track that reports the
+/// ducked track's output.
+fn ducked_track(
+	manager: &mut AudioManager<MockBackend>,
+	source: &TrackHandle,
+) -> (TrackHandle, TrackHandle, Consumer<f32>) {
+	let mut parent_builder = TrackBuilder::new();
+	let probe = parent_builder.add_effect(ProbeEffectBuilder);
+	let mut parent = manager.add_sub_track(parent_builder).unwrap();
+	let mut builder = TrackBuilder::new().with_effect(SourceEffectBuilder::mono(1.0));
+	builder
+		.duck_by(source, Decibels(-12.0), Duration::ZERO, Duration::ZERO)
+		.unwrap();
+	let track = parent.add_sub_track(builder).unwrap();
+	(parent, track, probe)
+}
+
+#[test]
+fn ducks_while_the_source_is_loud() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let source = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(1.0)))
+		.unwrap();
+	let (_parent, _track, mut probe) = ducked_track(&mut manager, &source);
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(Decibels(-12.0).as_amplitude()));
+}
+
+#[test]
+fn ignores_sources_quieter_than_the_threshold() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let source = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(0.001)))
+		.unwrap();
+	let (_parent, _track, mut probe) = ducked_track(&mut manager, &source);
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(1.0));
+}
+
+#[test]
+fn sets_duck_amount() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let source = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(1.0)))
+		.unwrap();
+	let (_parent, mut track, mut probe) = ducked_track(&mut manager, &source);
+	process(&mut manager);
+	probe.pop().unwrap();
+	track.set_duck_amount(Decibels(-6.0), Tween::default());
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(Decibels(-6.0).as_amplitude()));
+}
+
+#[test]
+fn releases_after_the_source_is_removed() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let source = manager
+		.add_sub_track(TrackBuilder::new().with_effect(SourceEffectBuilder::mono(1.0)))
+		.unwrap();
+	let (_parent, _track, mut probe) = ducked_track(&mut manager, &source);
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(Decibels(-12.0).as_amplitude()));
+	drop(source);
+	process(&mut manager);
+	assert_eq!(probe.pop(), Ok(1.0));
+}
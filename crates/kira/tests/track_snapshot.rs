@@ -0,0 +1,140 @@
+mod common;
+
+use std::time::Duration;
+
+use kira::{
+	backend::mock::MockBackend,
+	effect::{
+		delay::DelayBuilder,
+		distortion::{DistortionBuilder, DistortionKind},
+		filter::{FilterBuilder, FilterMode},
+		volume_control::VolumeControlBuilder,
+		Effect, EffectBuilder,
+	},
+	info::Info,
+	track::{TrackBuilder, TrackHandle},
+	AudioManager, Frame, Tween,
+};
+
+use common::{frame_probe_output, manager_with_frame_probe};
+
+/// Adds a sine wave to the input.
+struct SineEffect {
+	phase: f32,
+}
+
+impl Effect for SineEffect {
+	fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+		for frame in input {
+			*frame += Frame::from_mono(self.phase.sin());
+			self.phase += 0.3;
+		}
+	}
+}
+
+struct SineEffectBuilder;
+
+impl EffectBuilder for SineEffectBuilder {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(Box::new(SineEffect { phase: 0.0 }), ())
+	}
+}
+
+/// Adds a track to a new audio manager, gives it a sub-track that
+/// outputs a sine wave, and returns the audio the manager outputs.
+fn output(builder: TrackBuilder) -> Vec<Frame> {
+	let (mut manager, mut probe) = manager_with_frame_probe();
+	let mut track = manager.add_sub_track(builder).unwrap();
+	let _source = track
+		.add_sub_track(TrackBuilder::new().with_effect(SineEffectBuilder))
+		.unwrap();
+	let mut frames = frame_probe_output(&mut manager, &mut probe);
+	frames.extend(frame_probe_output(&mut manager, &mut probe));
+	frames
+}
+
+fn snapshot(setup: impl FnOnce(&mut AudioManager<MockBackend>) -> TrackHandle) -> TrackBuilder {
+	let (mut manager, _probe) = manager_with_frame_probe();
+	setup(&mut manager).builder_snapshot()
+}
+
+#[test]
+fn copies_current_settings_and_effects() {
+	let snapshot = snapshot(|manager| {
+		let mut builder = TrackBuilder::new();
+		let mut filter = builder.add_effect(FilterBuilder::new());
+		let mut delay = builder.add_effect(
+			DelayBuilder::new()
+				.delay_time(Duration::from_secs(10))
+				.with_feedback_effect(FilterBuilder::new().cutoff(0.05)),
+		);
+		let mut track = manager.add_sub_track(builder).unwrap();
+		filter.set_mode(FilterMode::HighPass);
+		filter.set_cutoff(0.1, Tween::default());
+		delay.set_feedback(-3.0, Tween::default());
+		track.set_volume(-6.0, Tween::default());
+		let mut volume_control = track.add_effect(VolumeControlBuilder::new(-12.0)).unwrap();
+		volume_control.set_bypassed(true, Tween::default());
+		track
+			.add_effect(DistortionBuilder::new().kind(DistortionKind::SoftClip))
+			.unwrap();
+		track
+	});
+	let expected = output(
+		TrackBuilder::new()
+			.volume(-6.0)
+			.with_effect(FilterBuilder::new().mode(FilterMode::HighPass).cutoff(0.1))
+			.with_effect(
+				DelayBuilder::new()
+					.delay_time(Duration::from_secs(10))
+					.feedback(-3.0)
+					.with_feedback_effect(FilterBuilder::new().cutoff(0.05)),
+			)
+			.with_effect(DistortionBuilder::new().kind(DistortionKind::SoftClip)),
+	);
+	assert!(expected.iter().any(|frame| frame.left != 0.0));
+	assert_eq!(output(snapshot), expected);
+}
+
+#[test]
+fn copies_added_effects_in_order() {
+	let snapshot = snapshot(|manager| {
+		let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+		let distortion = track.add_effect(DistortionBuilder::new()).unwrap();
+		let volume_control = track.add_effect(VolumeControlBuilder::new(12.0)).unwrap();
+		let removed = track.add_effect(VolumeControlBuilder::new(-60.0)).unwrap();
+		track.remove_effect(&removed);
+		track
+			.set_effect_order(&[volume_control.id(), distortion.id()])
+			.unwrap();
+		track
+	});
+	let expected = output(
+		TrackBuilder::new()
+			.with_effect(VolumeControlBuilder::new(12.0))
+			.with_effect(DistortionBuilder::new()),
+	);
+	assert!(expected.iter().any(|frame| frame.left == 1.0));
+	assert_eq!(output(snapshot), expected);
+}
+
+#[test]
+fn skips_effects_that_cannot_be_copied() {
+	let snapshot = snapshot(|manager| {
+		manager
+			.add_sub_track(
+				TrackBuilder::new()
+					.always_process(true)
+					.with_effect(SineEffectBuilder)
+					.with_effect(FilterBuilder::new().cutoff(0.1)),
+			)
+			.unwrap()
+	});
+	let (mut manager, mut probe) = manager_with_frame_probe();
+	let _track = manager.add_sub_track(snapshot).unwrap();
+	let frames = frame_probe_output(&mut manager, &mut probe);
+	assert!(!frames.is_empty());
+	assert!(frames.iter().all(|frame| *frame == Frame::ZERO));
+}
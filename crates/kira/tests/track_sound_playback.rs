@@ -0,0 +1,189 @@
+mod common;
+
+use std::time::Duration;
+
+use kira::{
+	backend::mock::MockBackend, sound::PlaybackState, track::TrackBuilder, AudioManager,
+	AudioManagerSettings, Capacities, Tween,
+};
+
+use common::{process, sound_data, INSTANT};
+
+#[test]
+fn pauses_and_resumes_sounds_on_track() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut world = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut ui = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let world_sound = world.play(sound_data()).unwrap();
+	let ui_sound = ui.play(sound_data()).unwrap();
+	process(&mut manager);
+
+	world.pause_all(INSTANT, false);
+	process(&mut manager);
+	assert_eq!(world_sound.state(), PlaybackState::Paused);
+	assert_eq!(ui_sound.state(), PlaybackState::Playing);
+
+	// sounds started while the track's sounds are paused start paused
+	let new_sound = world.play(sound_data()).unwrap();
+	process(&mut manager);
+	assert_eq!(new_sound.state(), PlaybackState::Paused);
+
+	world.resume_all(INSTANT, false);
+	process(&mut manager);
+	assert_eq!(world_sound.state(), PlaybackState::Playing);
+	assert_eq!(new_sound.state(), PlaybackState::Playing);
+	let newer_sound = world.play(sound_data()).unwrap();
+	process(&mut manager);
+	assert_eq!(newer_sound.state(), PlaybackState::Playing);
+}
+
+#[test]
+fn applies_commands_to_sub_tracks() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut world = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut ambience = world.add_sub_track(TrackBuilder::new()).unwrap();
+	let world_sound = world.play(sound_data()).unwrap();
+	let ambience_sound = ambience.play(sound_data()).unwrap();
+	process(&mut manager);
+
+	world.pause_all(INSTANT, false);
+	process(&mut manager);
+	assert_eq!(world_sound.state(), PlaybackState::Paused);
+	assert_eq!(ambience_sound.state(), PlaybackState::Playing);
+
+	world.stop_all(INSTANT, true);
+	process(&mut manager);
+	assert_eq!(world_sound.state(), PlaybackState::Stopped);
+	assert_eq!(ambience_sound.state(), PlaybackState::Stopped);
+}
+
+#[test]
+fn track_playback_rate_affects_sounds_on_track_and_sub_tracks() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut normal = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut world = manager
+		.add_sub_track(TrackBuilder::new().playback_rate(2.0))
+		.unwrap();
+	let mut ambience = world
+		.add_sub_track(TrackBuilder::new().playback_rate(1.5))
+		.unwrap();
+	let normal_sound = normal.play(sound_data()).unwrap();
+	let world_sound = world.play(sound_data()).unwrap();
+	let ambience_sound = ambience.play(sound_data()).unwrap();
+	process(&mut manager);
+	let normal_start = normal_sound.position();
+	let world_start = world_sound.position();
+	let ambience_start = ambience_sound.position();
+	for _ in 0..4 {
+		process(&mut manager);
+	}
+	let normal_elapsed = normal_sound.position() - normal_start;
+	assert!(normal_elapsed > 0.0);
+	assert_eq!(world_sound.position() - world_start, normal_elapsed * 2.0);
+	// playback rates are multiplied by the playback rates of parent tracks
+	assert_eq!(
+		ambience_sound.position() - ambience_start,
+		normal_elapsed * 3.0
+	);
+
+	world.set_playback_rate(0.5, INSTANT);
+	process(&mut manager);
+	let world_start = world_sound.position();
+	let ambience_start = ambience_sound.position();
+	for _ in 0..4 {
+		process(&mut manager);
+	}
+	assert_eq!(world_sound.position() - world_start, normal_elapsed * 0.5);
+	assert_eq!(
+		ambience_sound.position() - ambience_start,
+		normal_elapsed * 0.75
+	);
+}
+
+#[test]
+fn counts_sounds_by_playback_state() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut world = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut ambience = world.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut paused_sound = world.play(sound_data()).unwrap();
+	let _playing_sound = world.play(sound_data()).unwrap();
+	let _ambience_sound = ambience.play(sound_data()).unwrap();
+	let _main_sound = manager.play(sound_data()).unwrap();
+	assert_eq!(manager.num_sounds(), 0);
+	process(&mut manager);
+	assert_eq!(manager.num_sounds(), 4);
+	assert_eq!(manager.num_sounds_by_state(), [4, 0, 0, 0, 0, 0, 0]);
+
+	paused_sound.pause(INSTANT);
+	process(&mut manager);
+	process(&mut manager);
+	let paused = PlaybackState::Paused as usize;
+	let mut expected = [0; 7];
+	expected[PlaybackState::Playing as usize] = 3;
+	expected[paused] = 1;
+	assert_eq!(manager.num_sounds(), 4);
+	assert_eq!(manager.num_sounds_by_state(), expected);
+	// sounds on sub-tracks aren't counted by their parent tracks
+	assert_eq!(world.num_sounds_by_state()[paused], 1);
+	assert_eq!(world.num_sounds_by_state().iter().sum::<usize>(), 2);
+	assert_eq!(ambience.num_sounds_by_state(), [1, 0, 0, 0, 0, 0, 0]);
+	assert_eq!(
+		manager.main_track().num_sounds_by_state(),
+		[1, 0, 0, 0, 0, 0, 0]
+	);
+}
+
+fn manager_with_sub_track_capacity(sub_track_capacity: usize) -> AudioManager<MockBackend> {
+	AudioManager::<MockBackend>::new(AudioManagerSettings {
+		capacities: Capacities {
+			sub_track_capacity,
+			..Default::default()
+		},
+		..Default::default()
+	})
+	.unwrap()
+}
+
+#[test]
+fn removing_track_stops_sounds_and_frees_slot() {
+	let mut manager = manager_with_sub_track_capacity(2);
+	let _other = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut sub_track = track.add_sub_track(TrackBuilder::new()).unwrap();
+	let sound = track.play(sound_data()).unwrap();
+	let sub_track_sound = sub_track.play(sound_data()).unwrap();
+	process(&mut manager);
+	assert_eq!(manager.sub_track_capacity_remaining(), 0);
+	assert!(manager.add_sub_track(TrackBuilder::new()).is_err());
+
+	// each chunk of audio is 128 seconds long with the mock backend
+	track.remove(Tween {
+		duration: Duration::from_secs(200),
+		..Default::default()
+	});
+	process(&mut manager);
+	assert_eq!(sound.state(), PlaybackState::Stopping);
+	assert_eq!(sub_track_sound.state(), PlaybackState::Stopping);
+	assert_eq!(manager.sub_track_capacity_remaining(), 0);
+
+	// sub-tracks are removed with their parent track, even if their
+	// handles still exist
+	for _ in 0..3 {
+		process(&mut manager);
+	}
+	assert_eq!(sound.state(), PlaybackState::Stopped);
+	assert_eq!(sub_track_sound.state(), PlaybackState::Stopped);
+	assert_eq!(manager.sub_track_capacity_remaining(), 1);
+	assert!(manager.add_sub_track(TrackBuilder::new()).is_ok());
+}
+
+#[test]
+fn dropping_track_handle_frees_slot() {
+	let mut manager = manager_with_sub_track_capacity(1);
+	let track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	process(&mut manager);
+	assert_eq!(manager.sub_track_capacity_remaining(), 0);
+	drop(track);
+	process(&mut manager);
+	assert_eq!(manager.sub_track_capacity_remaining(), 1);
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use kira::{
+	backend::mock::MockBackend,
+	track::{TrackBuilder, TrackHandle},
+	AudioManager, Frame, Panning, Tween,
+};
+
+use common::{main_output, manager, SourceEffectBuilder};
+
+/// Adds a track that outputs `source` to a new audio manager.
+fn setup(source: Frame, builder: TrackBuilder) -> (AudioManager<MockBackend>, TrackHandle) {
+	let mut manager = manager();
+	let track = manager
+		.add_sub_track(builder.with_effect(SourceEffectBuilder(source)))
+		.unwrap();
+	(manager, track)
+}
+
+#[test]
+fn leaves_audio_unchanged_by_default() {
+	let (mut manager, _track) = setup(Frame::new(0.5, -0.25), TrackBuilder::new());
+	assert_eq!(main_output(&mut manager, 2), Frame::new(0.5, -0.25));
+}
+
+#[test]
+fn stereo_width_keeps_mono_mix() {
+	let (mut manager, mut track) = setup(
+		Frame::new(0.5, -0.25),
+		TrackBuilder::new().stereo_width(0.0),
+	);
+	assert_eq!(main_output(&mut manager, 2), Frame::from_mono(0.125));
+	track.set_stereo_width(2.0, Tween::default());
+	let output = main_output(&mut manager, 2);
+	assert_eq!(output, Frame::new(0.875, -0.625));
+	assert_eq!(output.left + output.right, 0.25);
+}
+
+#[test]
+fn balance_turns_down_one_channel() {
+	let (mut manager, mut track) = setup(Frame::new(0.5, 0.5), TrackBuilder::new().balance(0.5));
+	assert_eq!(main_output(&mut manager, 2), Frame::new(0.25, 0.5));
+	track.set_balance(-1.0, Tween::default());
+	assert_eq!(main_output(&mut manager, 2), Frame::new(0.5, 0.0));
+}
+
+#[test]
+fn panning_matches_sound_panning() {
+	let source = Frame::new(0.5, 0.5);
+	let (mut manager, mut track) = setup(source, TrackBuilder::new().panning(Panning::RIGHT));
+	assert_eq!(main_output(&mut manager, 2), source.panned(Panning::RIGHT));
+	track.set_panning(-0.5, Tween::default());
+	assert_eq!(main_output(&mut manager, 2), source.panned(Panning(-0.5)));
+}